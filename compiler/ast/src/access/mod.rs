@@ -14,6 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+mod array_access;
+pub use array_access::*;
+
 mod associated_constant_access;
 pub use associated_constant_access::*;
 
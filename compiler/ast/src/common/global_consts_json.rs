@@ -50,6 +50,7 @@ pub fn deserialize<'de, D: Deserializer<'de>>(
                     .map(|ident_name| Identifier {
                         name: Symbol::intern(ident_name),
                         span: Default::default(),
+                        id: Default::default(),
                     })
                     .collect::<Vec<Identifier>>(),
                 program,
@@ -26,6 +26,13 @@ pub mod positive_number;
 pub use positive_number::*;
 
 pub mod node;
+
+pub mod node_builder;
+pub use node_builder::*;
+
+pub mod node_table;
+pub use node_table::*;
+
 pub mod static_string;
 
 pub use static_string::*;
@@ -16,6 +16,9 @@
 
 use leo_span::Span;
 
+/// A unique identifier for a node in the AST.
+pub type NodeId = usize;
+
 /// A node in the AST.
 pub trait Node:
     std::fmt::Debug + std::fmt::Display + Clone + PartialEq + Eq + serde::Serialize + serde::de::DeserializeOwned
@@ -25,6 +28,12 @@ pub trait Node:
 
     /// Sets the span of the node.
     fn set_span(&mut self, span: Span);
+
+    /// Returns the ID of the node.
+    fn id(&self) -> NodeId;
+
+    /// Sets the ID of the node.
+    fn set_id(&mut self, id: NodeId);
 }
 
 #[macro_export]
@@ -38,6 +47,14 @@ macro_rules! simple_node_impl {
             fn set_span(&mut self, span: Span) {
                 self.span = span;
             }
+
+            fn id(&self) -> $crate::NodeId {
+                self.id
+            }
+
+            fn set_id(&mut self, id: $crate::NodeId) {
+                self.id = id;
+            }
         }
     };
 }
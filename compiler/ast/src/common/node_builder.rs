@@ -0,0 +1,43 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::NodeId;
+
+use std::cell::Cell;
+
+/// Creates the IDs of AST nodes, so that each node has an ID that no other node has.
+/// IDs are created through a shared reference, so one builder can be used by the parser and by every pass that creates nodes.
+/// Passes that copy nodes before the types of the program are final, i.e. monomorphization and loop unrolling, give the copies fresh IDs.
+/// Note that function inlining, which runs once the program is flattened, keeps the IDs of the nodes that it copies.
+#[derive(Clone, Debug, Default)]
+pub struct NodeBuilder {
+    /// The ID of the next node.
+    next: Cell<NodeId>,
+}
+
+impl NodeBuilder {
+    /// Returns a builder whose first ID is `next`.
+    pub fn new(next: NodeId) -> Self {
+        Self { next: Cell::new(next) }
+    }
+
+    /// Returns a new unique ID.
+    pub fn next_id(&self) -> NodeId {
+        let id = self.next.get();
+        self.next.set(id + 1);
+        id
+    }
+}
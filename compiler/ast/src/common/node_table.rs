@@ -0,0 +1,82 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Node, NodeId};
+
+use indexmap::IndexMap;
+
+/// A side table of metadata that a pass records for AST nodes, keyed by the IDs of the nodes.
+/// Later passes look the metadata up by node, rather than by the name of a variable or struct.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NodeTable<T> {
+    /// The metadata of each node that has any.
+    entries: IndexMap<NodeId, T>,
+}
+
+impl<T> Default for NodeTable<T> {
+    fn default() -> Self {
+        Self {
+            entries: IndexMap::new(),
+        }
+    }
+}
+
+impl<T> NodeTable<T> {
+    /// Records `value` for `node`, returning the value previously recorded for it, if any.
+    pub fn insert(&mut self, node: &impl Node, value: T) -> Option<T> {
+        self.entries.insert(node.id(), value)
+    }
+
+    /// Returns the value recorded for `node`, if any.
+    pub fn get(&self, node: &impl Node) -> Option<&T> {
+        self.entries.get(&node.id())
+    }
+
+    /// Returns the value recorded for the node with the ID `id`, if any.
+    pub fn get_by_id(&self, id: NodeId) -> Option<&T> {
+        self.entries.get(&id)
+    }
+
+    /// Returns `true` if a value is recorded for `node`.
+    pub fn contains(&self, node: &impl Node) -> bool {
+        self.entries.contains_key(&node.id())
+    }
+
+    /// Removes the value recorded for `node`, returning it if there was one.
+    pub fn remove(&mut self, node: &impl Node) -> Option<T> {
+        self.entries.remove(&node.id())
+    }
+
+    /// Returns the number of nodes with a recorded value.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no values are recorded.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the IDs of the nodes and their values, in the order that they were recorded.
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &T)> {
+        self.entries.iter().map(|(id, value)| (*id, value))
+    }
+
+    /// Returns the recorded values, which may be modified, in the order that they were recorded.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.entries.values_mut()
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Identifier, Node, NodeId};
+use leo_span::{Span, Symbol};
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// An enum type definition, e.g. `enum State { Idle, Running, Done }`.
+/// The variants carry no data, so each value of an enum is one of its variants, e.g. `State::Idle`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Enum {
+    /// The name of the enum.
+    pub identifier: Identifier,
+    /// The variants of the enum, in the order they are declared.
+    pub variants: Vec<Identifier>,
+    /// The entire span of the enum definition.
+    pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
+}
+
+impl Enum {
+    /// Returns the enum name as a Symbol.
+    pub fn name(&self) -> Symbol {
+        self.identifier.name
+    }
+
+    /// Returns the position of the variant `name` among the variants of the enum, if it is one.
+    pub fn variant_index(&self, name: Symbol) -> Option<usize> {
+        self.variants.iter().position(|variant| variant.name == name)
+    }
+}
+
+impl fmt::Display for Enum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let variants = self
+            .variants
+            .iter()
+            .map(|variant| variant.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "enum {} {{ {} }}", self.identifier, variants)
+    }
+}
+
+crate::simple_node_impl!(Enum);
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{access::*, Node};
+use crate::{access::*, Node, NodeId};
 use leo_span::Span;
 
 use serde::{Deserialize, Serialize};
@@ -23,8 +23,8 @@ use std::fmt;
 /// An access expressions, extracting a smaller part out of a whole.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AccessExpression {
-    // /// An `array[index]` expression.
-    // Array(ArrayAccess),
+    /// An `array[index]` expression.
+    Array(ArrayAccess),
     // /// An expression accessing a range of an array.
     // ArrayRange(ArrayRangeAccess),
     /// Access to an associated variable of a struct e.g `u8::MAX`.
@@ -40,6 +40,7 @@ pub enum AccessExpression {
 impl Node for AccessExpression {
     fn span(&self) -> Span {
         match self {
+            AccessExpression::Array(n) => n.span(),
             AccessExpression::AssociatedConstant(n) => n.span(),
             AccessExpression::AssociatedFunction(n) => n.span(),
             AccessExpression::Member(n) => n.span(),
@@ -49,12 +50,33 @@ impl Node for AccessExpression {
 
     fn set_span(&mut self, span: Span) {
         match self {
+            AccessExpression::Array(n) => n.set_span(span),
             AccessExpression::AssociatedConstant(n) => n.set_span(span),
             AccessExpression::AssociatedFunction(n) => n.set_span(span),
             AccessExpression::Member(n) => n.set_span(span),
             AccessExpression::Tuple(n) => n.set_span(span),
         }
     }
+
+    fn id(&self) -> NodeId {
+        match self {
+            AccessExpression::Array(n) => n.id(),
+            AccessExpression::AssociatedConstant(n) => n.id(),
+            AccessExpression::AssociatedFunction(n) => n.id(),
+            AccessExpression::Member(n) => n.id(),
+            AccessExpression::Tuple(n) => n.id(),
+        }
+    }
+
+    fn set_id(&mut self, id: NodeId) {
+        match self {
+            AccessExpression::Array(n) => n.set_id(id),
+            AccessExpression::AssociatedConstant(n) => n.set_id(id),
+            AccessExpression::AssociatedFunction(n) => n.set_id(id),
+            AccessExpression::Member(n) => n.set_id(id),
+            AccessExpression::Tuple(n) => n.set_id(id),
+        }
+    }
 }
 
 impl fmt::Display for AccessExpression {
@@ -62,6 +84,7 @@ impl fmt::Display for AccessExpression {
         use AccessExpression::*;
 
         match self {
+            Array(access) => access.fmt(f),
             AssociatedConstant(access) => access.fmt(f),
             AssociatedFunction(access) => access.fmt(f),
             Member(access) => access.fmt(f),
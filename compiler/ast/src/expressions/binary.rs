@@ -175,6 +175,8 @@ pub struct BinaryExpression {
     pub op: BinaryOperation,
     /// The span from `left` to `right`.
     pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
 }
 
 impl fmt::Display for BinaryExpression {
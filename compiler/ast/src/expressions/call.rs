@@ -15,6 +15,9 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use super::*;
+use crate::Type;
+
+use std::cell::RefCell;
 
 /// A function call expression, e.g.`foo(args)` or `Foo::bar(args)`.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -24,10 +27,21 @@ pub struct CallExpression {
     pub function: Box<Expression>, // todo: make this identifier?
     /// Expressions for the arguments passed to the functions parameters.
     pub arguments: Vec<Expression>,
+    /// The types that the type parameters of a generic callee are instantiated with, in order.
+    /// These are inferred from the arguments during type checking.
+    #[serde(default, skip_serializing_if = "is_not_generic")]
+    pub type_arguments: RefCell<Vec<Type>>,
     /// The name of the external program call, e.g.`bar` in `bar.leo`.
     pub external: Option<Box<Expression>>,
     /// Span of the entire call `function(arguments)`.
     pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
+}
+
+/// Returns `true` if no type arguments have been inferred for the call.
+fn is_not_generic(type_arguments: &RefCell<Vec<Type>>) -> bool {
+    type_arguments.borrow().is_empty()
 }
 
 impl fmt::Display for CallExpression {
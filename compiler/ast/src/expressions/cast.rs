@@ -0,0 +1,40 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Type;
+
+use super::*;
+
+/// A cast expression, that is, `expression as type_`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CastExpression {
+    /// The expression whose value is cast.
+    pub expression: Box<Expression>,
+    /// The type the value is cast to.
+    pub type_: Type,
+    /// The span from `expression` to `type_`.
+    pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
+}
+
+impl fmt::Display for CastExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({} as {})", self.expression, self.type_)
+    }
+}
+
+crate::simple_node_impl!(CastExpression);
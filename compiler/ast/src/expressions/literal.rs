@@ -14,44 +14,102 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{GroupLiteral, IntegerType};
+use crate::{GroupLiteral, IntegerType, NodeBuilder, NodeId};
 
 use super::*;
 
+use std::cell::Cell;
+
 // TODO: Refactor integer literals to use `IntegerType`.
 /// A literal.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Literal {
     // todo: deserialize values here
     /// An address literal, e.g., `aleo1qnr4dkkvkgfqph0vzc3y6z2eu975wnpz2925ntjccd5cfqxtyu8s7pyjh9`.
-    Address(String, #[serde(with = "leo_span::span_json")] Span),
+    Address(String, #[serde(with = "leo_span::span_json")] Span, NodeId),
     /// A boolean literal, either `true` or `false`.
-    Boolean(bool, #[serde(with = "leo_span::span_json")] Span),
+    Boolean(bool, #[serde(with = "leo_span::span_json")] Span, NodeId),
     /// A field literal, e.g., `42field`.
     /// A signed number followed by the keyword `field`.
-    Field(String, #[serde(with = "leo_span::span_json")] Span),
+    Field(String, #[serde(with = "leo_span::span_json")] Span, NodeId),
     /// A group literal, either product or affine.
     /// For example, `42group` or `(12, 52)group`.
     Group(Box<GroupLiteral>),
     /// An integer literal, e.g., `42`.
-    Integer(IntegerType, String, #[serde(with = "leo_span::span_json")] Span),
+    Integer(IntegerType, String, #[serde(with = "leo_span::span_json")] Span, NodeId),
     /// A scalar literal, e.g. `1scalar`.
     /// An unsigned number followed by the keyword `scalar`.
-    Scalar(String, #[serde(with = "leo_span::span_json")] Span),
+    Scalar(String, #[serde(with = "leo_span::span_json")] Span, NodeId),
     /// A string literal, e.g., `"foobar"`.
-    String(String, #[serde(with = "leo_span::span_json")] Span),
+    String(String, #[serde(with = "leo_span::span_json")] Span, NodeId),
+    /// An integer literal without a type suffix, e.g., `42`.
+    /// Its type is inferred from its context during type checking, falling back to a default integer type.
+    Unsuffixed(
+        String,
+        #[serde(default)] Cell<Option<IntegerType>>,
+        #[serde(with = "leo_span::span_json")] Span,
+        NodeId,
+    ),
+}
+
+impl Literal {
+    /// Returns the integer type of an integer literal, if it is known.
+    /// The type of an unsuffixed literal is only known once it has been resolved by type checking.
+    pub fn integer_type(&self) -> Option<IntegerType> {
+        match self {
+            Self::Integer(type_, ..) => Some(*type_),
+            Self::Unsuffixed(_, type_, ..) => type_.get(),
+            _ => None,
+        }
+    }
+
+    /// The number of bytes of a string that are packed into each field of its lowered form.
+    /// Each field then holds at most `u128::MAX`.
+    pub const STRING_BYTES_PER_FIELD: usize = 16;
+
+    /// Returns the number of fields that a string of `length` bytes is lowered to.
+    pub fn string_field_count(length: usize) -> usize {
+        ((length + Self::STRING_BYTES_PER_FIELD - 1) / Self::STRING_BYTES_PER_FIELD).max(1)
+    }
+
+    /// Returns the field literals that a string literal is lowered to, or `None` if the literal is not a string.
+    /// The bytes of the string are packed into fields in order, each field holding the little-endian value of its bytes.
+    /// The empty string is lowered to a single zero field.
+    pub fn string_fields(&self, node_builder: &NodeBuilder) -> Option<Vec<Literal>> {
+        let (string, span) = match self {
+            Self::String(string, span, _) => (string, *span),
+            _ => return None,
+        };
+        let mut fields = string
+            .as_bytes()
+            .chunks(Self::STRING_BYTES_PER_FIELD)
+            .map(|chunk| {
+                let mut bytes = [0u8; Self::STRING_BYTES_PER_FIELD];
+                bytes[..chunk.len()].copy_from_slice(chunk);
+                Literal::Field(u128::from_le_bytes(bytes).to_string(), span, node_builder.next_id())
+            })
+            .collect::<Vec<_>>();
+        if fields.is_empty() {
+            fields.push(Literal::Field("0".to_string(), span, node_builder.next_id()));
+        }
+        Some(fields)
+    }
 }
 
 impl fmt::Display for Literal {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match &self {
-            Self::Address(address, _) => write!(f, "{}", address),
-            Self::Boolean(boolean, _) => write!(f, "{}", boolean),
-            Self::Field(field, _) => write!(f, "{}field", field),
+            Self::Address(address, ..) => write!(f, "{}", address),
+            Self::Boolean(boolean, ..) => write!(f, "{}", boolean),
+            Self::Field(field, ..) => write!(f, "{}field", field),
             Self::Group(group) => write!(f, "{}group", group),
-            Self::Integer(type_, value, _) => write!(f, "{}{}", value, type_),
-            Self::Scalar(scalar, _) => write!(f, "{}scalar", scalar),
-            Self::String(string, _) => write!(f, "\"{}\"", string),
+            Self::Integer(type_, value, ..) => write!(f, "{}{}", value, type_),
+            Self::Scalar(scalar, ..) => write!(f, "{}scalar", scalar),
+            Self::String(string, ..) => write!(f, "\"{}\"", string),
+            Self::Unsuffixed(value, type_, ..) => match type_.get() {
+                Some(type_) => write!(f, "{}{}", value, type_),
+                None => write!(f, "{}", value),
+            },
         }
     }
 }
@@ -59,31 +117,53 @@ impl fmt::Display for Literal {
 impl Node for Literal {
     fn span(&self) -> Span {
         match &self {
-            Self::Address(_, span)
-            | Self::Boolean(_, span)
-            | Self::Field(_, span)
-            | Self::Integer(_, _, span)
-            | Self::Scalar(_, span)
-            | Self::String(_, span) => *span,
-            Self::Group(group) => match &**group {
-                GroupLiteral::Single(_, span) => *span,
-                GroupLiteral::Tuple(tuple) => tuple.span,
-            },
+            Self::Address(_, span, _)
+            | Self::Boolean(_, span, _)
+            | Self::Field(_, span, _)
+            | Self::Integer(_, _, span, _)
+            | Self::Scalar(_, span, _)
+            | Self::String(_, span, _)
+            | Self::Unsuffixed(_, _, span, _) => *span,
+            Self::Group(group) => *group.span(),
         }
     }
 
     fn set_span(&mut self, new_span: Span) {
         match self {
-            Self::Address(_, span)
-            | Self::Boolean(_, span)
-            | Self::Field(_, span)
-            | Self::Integer(_, _, span)
-            | Self::Scalar(_, span)
-            | Self::String(_, span) => *span = new_span,
-            Self::Group(group) => match &mut **group {
-                GroupLiteral::Single(_, span) => *span = new_span,
-                GroupLiteral::Tuple(tuple) => tuple.span = new_span,
-            },
+            Self::Address(_, span, _)
+            | Self::Boolean(_, span, _)
+            | Self::Field(_, span, _)
+            | Self::Integer(_, _, span, _)
+            | Self::Scalar(_, span, _)
+            | Self::String(_, span, _)
+            | Self::Unsuffixed(_, _, span, _) => *span = new_span,
+            Self::Group(group) => group.set_span(new_span),
+        }
+    }
+
+    fn id(&self) -> NodeId {
+        match &self {
+            Self::Address(_, _, id)
+            | Self::Boolean(_, _, id)
+            | Self::Field(_, _, id)
+            | Self::Integer(_, _, _, id)
+            | Self::Scalar(_, _, id)
+            | Self::String(_, _, id)
+            | Self::Unsuffixed(_, _, _, id) => *id,
+            Self::Group(group) => group.id(),
+        }
+    }
+
+    fn set_id(&mut self, new_id: NodeId) {
+        match self {
+            Self::Address(_, _, id)
+            | Self::Boolean(_, _, id)
+            | Self::Field(_, _, id)
+            | Self::Integer(_, _, _, id)
+            | Self::Scalar(_, _, id)
+            | Self::String(_, _, id)
+            | Self::Unsuffixed(_, _, _, id) => *id = new_id,
+            Self::Group(group) => group.set_id(new_id),
         }
     }
 }
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Identifier, Node};
+use crate::{Identifier, Node, NodeId, Size};
 use leo_span::Span;
 
 use serde::{Deserialize, Serialize};
@@ -23,12 +23,18 @@ use std::fmt;
 mod access;
 pub use access::*;
 
+mod array_init;
+pub use array_init::*;
+
 mod binary;
 pub use binary::*;
 
 mod call;
 pub use call::*;
 
+mod cast;
+pub use cast::*;
+
 mod struct_init;
 pub use struct_init::*;
 
@@ -52,10 +58,14 @@ pub use literal::*;
 pub enum Expression {
     /// A struct access expression, e.g., `Foo.bar`.
     Access(AccessExpression),
+    /// An array expression e.g., `[foo, 42, bar]`.
+    Array(ArrayExpression),
     /// A binary expression, e.g., `42 + 24`.
     Binary(BinaryExpression),
     /// A call expression, e.g., `my_fun(args)`.
     Call(CallExpression),
+    /// A cast expression, e.g., `42u8 as u32`.
+    Cast(CastExpression),
     /// An expression constructing a struct like `Foo { bar: 42, baz }`.
     Struct(StructExpression),
     /// An expression of type "error".
@@ -78,8 +88,10 @@ impl Node for Expression {
         use Expression::*;
         match self {
             Access(n) => n.span(),
+            Array(n) => n.span(),
             Binary(n) => n.span(),
             Call(n) => n.span(),
+            Cast(n) => n.span(),
             Struct(n) => n.span(),
             Err(n) => n.span(),
             Identifier(n) => n.span(),
@@ -94,8 +106,10 @@ impl Node for Expression {
         use Expression::*;
         match self {
             Access(n) => n.set_span(span),
+            Array(n) => n.set_span(span),
             Binary(n) => n.set_span(span),
             Call(n) => n.set_span(span),
+            Cast(n) => n.set_span(span),
             Struct(n) => n.set_span(span),
             Identifier(n) => n.set_span(span),
             Literal(n) => n.set_span(span),
@@ -105,6 +119,42 @@ impl Node for Expression {
             Unary(n) => n.set_span(span),
         }
     }
+
+    fn id(&self) -> NodeId {
+        use Expression::*;
+        match self {
+            Access(n) => n.id(),
+            Array(n) => n.id(),
+            Binary(n) => n.id(),
+            Call(n) => n.id(),
+            Cast(n) => n.id(),
+            Struct(n) => n.id(),
+            Err(n) => n.id(),
+            Identifier(n) => n.id(),
+            Literal(n) => n.id(),
+            Ternary(n) => n.id(),
+            Tuple(n) => n.id(),
+            Unary(n) => n.id(),
+        }
+    }
+
+    fn set_id(&mut self, id: NodeId) {
+        use Expression::*;
+        match self {
+            Access(n) => n.set_id(id),
+            Array(n) => n.set_id(id),
+            Binary(n) => n.set_id(id),
+            Call(n) => n.set_id(id),
+            Cast(n) => n.set_id(id),
+            Struct(n) => n.set_id(id),
+            Identifier(n) => n.set_id(id),
+            Literal(n) => n.set_id(id),
+            Err(n) => n.set_id(id),
+            Ternary(n) => n.set_id(id),
+            Tuple(n) => n.set_id(id),
+            Unary(n) => n.set_id(id),
+        }
+    }
 }
 
 impl fmt::Display for Expression {
@@ -112,8 +162,10 @@ impl fmt::Display for Expression {
         use Expression::*;
         match &self {
             Access(n) => n.fmt(f),
+            Array(n) => n.fmt(f),
             Binary(n) => n.fmt(f),
             Call(n) => n.fmt(f),
+            Cast(n) => n.fmt(f),
             Struct(n) => n.fmt(f),
             Err(n) => n.fmt(f),
             Identifier(n) => n.fmt(f),
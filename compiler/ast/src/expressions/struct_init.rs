@@ -43,6 +43,9 @@ impl fmt::Display for StructVariableInitializer {
 pub struct StructExpression {
     /// The name of the structure type to initialize.
     pub name: Identifier,
+    /// The const arguments of an instance of a generic struct, e.g. the `4` in `Buffer::<4> { ... }`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub const_arguments: Vec<Size>,
     /// Initializer expressions for each of the fields in the struct.
     ///
     /// N.B. Any functions or member constants in the struct definition
@@ -50,6 +53,8 @@ pub struct StructExpression {
     pub members: Vec<StructVariableInitializer>,
     /// A span from `name` to `}`.
     pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
 }
 
 impl StructExpression {
@@ -55,7 +55,7 @@ impl UnaryOperation {
     }
 
     /// Represents the opera.tor as a string.
-    fn as_str(self) -> &'static str {
+    pub fn as_str(self) -> &'static str {
         match self {
             Self::Abs => "abs",
             Self::AbsWrapped => "abs_wrapped",
@@ -78,6 +78,8 @@ pub struct UnaryExpression {
     pub op: UnaryOperation,
     /// The span covering `op inner`.
     pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
 }
 
 impl fmt::Display for UnaryExpression {
@@ -0,0 +1,136 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+/// A document that lays out a piece of source code, breaking its groups over several lines when they do not fit.
+/// Each group is printed on a single line if the rest of the line, up to its next possible break, fits within the width.
+/// Otherwise, the lines of the group are broken and the groups it contains are laid out in turn.
+#[derive(Clone, Debug)]
+pub(crate) enum Doc {
+    /// Text printed as is.
+    Text(String),
+    /// A space when the enclosing group fits on a line, and a line break otherwise.
+    Line,
+    /// Nothing when the enclosing group fits on a line, and a line break otherwise.
+    SoftLine,
+    /// Text that is only printed when the enclosing group is broken, e.g. a trailing comma.
+    IfBroken(&'static str),
+    /// A sequence of documents.
+    Concat(Vec<Doc>),
+    /// A document whose line breaks are indented one level further.
+    Nest(Box<Doc>),
+    /// A document that is printed on a single line if it fits.
+    Group(Box<Doc>),
+}
+
+impl Doc {
+    pub(crate) fn text(text: impl Into<String>) -> Self {
+        Self::Text(text.into())
+    }
+
+    pub(crate) fn concat(docs: impl IntoIterator<Item = Doc>) -> Self {
+        Self::Concat(docs.into_iter().collect())
+    }
+
+    pub(crate) fn nest(doc: Doc) -> Self {
+        Self::Nest(Box::new(doc))
+    }
+
+    pub(crate) fn group(doc: Doc) -> Self {
+        Self::Group(Box::new(doc))
+    }
+
+    /// Appends the document to `out`, whose last line has `column` characters and is indented by `indent` spaces.
+    /// Nested documents are indented by a further `step` spaces, and groups are broken to fit within `width` characters.
+    /// Returns the number of characters on the last line of `out`.
+    pub(crate) fn render(
+        &self,
+        out: &mut String,
+        mut column: usize,
+        indent: usize,
+        step: usize,
+        width: usize,
+    ) -> usize {
+        // The documents left to print, with their indentation and whether they are printed on a single line.
+        // The outermost document is laid out as if it were broken, so that its groups decide for themselves.
+        let mut stack = vec![(indent, false, self)];
+
+        while let Some((indent, flat, doc)) = stack.pop() {
+            match doc {
+                Doc::Text(text) => {
+                    out.push_str(text);
+                    column += text.chars().count();
+                }
+                Doc::Line if flat => {
+                    out.push(' ');
+                    column += 1;
+                }
+                Doc::SoftLine if flat => {}
+                Doc::Line | Doc::SoftLine => {
+                    out.push('\n');
+                    out.push_str(&" ".repeat(indent));
+                    column = indent;
+                }
+                Doc::IfBroken(text) => {
+                    if !flat {
+                        out.push_str(text);
+                        column += text.chars().count();
+                    }
+                }
+                Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (indent, flat, doc))),
+                Doc::Nest(doc) => stack.push((indent + step, flat, doc)),
+                Doc::Group(doc) => {
+                    let flat = flat || fits(width as isize - column as isize, (indent, doc), &stack);
+                    stack.push((indent, flat, doc));
+                }
+            }
+        }
+
+        column
+    }
+}
+
+/// Returns true if `doc`, printed on a single line, and the documents that follow it up to their next line break
+/// fit within the `remaining` characters of the line.
+fn fits(mut remaining: isize, (indent, doc): (usize, &Doc), rest: &[(usize, bool, &Doc)]) -> bool {
+    let mut stack = vec![(indent, true, doc)];
+    let mut rest = rest.iter().rev();
+
+    loop {
+        if remaining < 0 {
+            return false;
+        }
+        let (indent, flat, doc) = match stack.pop() {
+            Some(next) => next,
+            None => match rest.next() {
+                Some(next) => *next,
+                None => return true,
+            },
+        };
+        match doc {
+            Doc::Text(text) => remaining -= text.chars().count() as isize,
+            Doc::Line if flat => remaining -= 1,
+            Doc::SoftLine if flat => {}
+            Doc::Line | Doc::SoftLine => return true,
+            Doc::IfBroken(text) => {
+                if !flat {
+                    remaining -= text.chars().count() as isize;
+                }
+            }
+            Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (indent, flat, doc))),
+            Doc::Nest(doc) | Doc::Group(doc) => stack.push((indent, flat, doc)),
+        }
+    }
+}
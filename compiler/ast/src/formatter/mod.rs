@@ -0,0 +1,924 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+mod doc;
+use doc::Doc;
+
+use crate::{
+    AccessExpression, AssertVariant, BinaryOperation, Block, CallType, ConstDeclaration, Enum, Expression, Function,
+    Input, Literal, Mapping, Mode, Node, Output, Program, ProgramScope, Statement, Struct, Type, UnaryOperation,
+};
+use leo_span::{span::BytePos, Span};
+
+use std::{iter::Peekable, vec};
+
+/// The precedence of expressions that bind tighter than any operator, e.g. identifiers, calls, and accesses.
+const POSTFIX: u8 = 14;
+/// The precedence of the prefix unary operators `!` and `-`.
+const UNARY: u8 = 13;
+/// The precedence of casts, which bind looser than prefix operators but tighter than any binary operator.
+const CAST: u8 = 12;
+/// The precedence of ternary expressions, which bind looser than any operator.
+const TERNARY: u8 = 0;
+
+/// Renders programs back to canonical Leo source code.
+///
+/// The source code parses back to the same program, up to spans, so formatting it again leaves it unchanged.
+/// Comments are not part of the AST, so they are passed alongside it and placed by their spans.
+/// A comment within a statement is placed after the statement.
+/// An `@edition` annotation is not preserved, since the source code is written for the current edition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Formatter {
+    /// The number of characters that lines are kept within, by breaking lists over several lines.
+    pub width: usize,
+    /// The number of spaces that each level of nesting is indented by.
+    pub indent: usize,
+}
+
+impl Default for Formatter {
+    fn default() -> Self {
+        Self { width: 100, indent: 4 }
+    }
+}
+
+impl Formatter {
+    /// Returns a new formatter that keeps lines within `width` characters and indents by `indent` spaces.
+    pub fn new(width: usize, indent: usize) -> Self {
+        Self { width, indent }
+    }
+
+    /// Renders a program as canonical Leo source code.
+    pub fn format(&self, program: &Program) -> String {
+        self.format_with_comments(program, Vec::new())
+    }
+
+    /// Renders a program as canonical Leo source code, keeping the comments of its source code.
+    /// Each comment is placed before the item or statement that follows it, unless it follows code on the same line,
+    /// in which case it stays at the end of the line that the code is rendered on.
+    pub fn format_with_comments(&self, program: &Program, mut comments: Vec<Comment>) -> String {
+        comments.sort_by_key(|comment| comment.span.lo);
+        let mut printer = Printer::new(self);
+        printer.comments = comments.into_iter().peekable();
+        printer.program(program);
+        printer.out
+    }
+
+    /// Renders a statement as canonical Leo source code, without a trailing newline.
+    pub fn format_statement(&self, statement: &Statement) -> String {
+        let mut printer = Printer::new(self);
+        printer.open_statement(statement);
+        printer.out
+    }
+
+    /// Renders an expression as canonical Leo source code.
+    pub fn format_expression(&self, expression: &Expression) -> String {
+        let mut printer = Printer::new(self);
+        printer.write(expression_doc(expression, TERNARY, false));
+        printer.out
+    }
+}
+
+/// A comment in the source code of a program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Comment {
+    /// The text of the comment, including its delimiters.
+    pub text: String,
+    /// Whether the comment follows code on the same line, rather than starting a line of its own.
+    pub trailing: bool,
+    /// The span of the comment.
+    pub span: Span,
+}
+
+/// An item of a program scope.
+enum Item<'a> {
+    Const(&'a ConstDeclaration),
+    Struct(&'a Struct),
+    Enum(&'a Enum),
+    Mapping(&'a Mapping),
+    Function(&'a Function),
+}
+
+impl Item<'_> {
+    /// Returns the position that the item starts at, including any annotations.
+    fn lo(&self) -> BytePos {
+        match self {
+            Item::Const(const_) => const_.span.lo,
+            Item::Struct(struct_) => struct_.span.lo,
+            Item::Enum(enum_) => enum_.span.lo,
+            Item::Mapping(mapping) => mapping.span.lo,
+            Item::Function(function) => function
+                .annotations
+                .iter()
+                .map(|annotation| annotation.span.lo)
+                .fold(function.span.lo, BytePos::min),
+        }
+    }
+}
+
+/// Writes the source code of a program line by line, laying out each line with the formatter.
+struct Printer<'a> {
+    formatter: &'a Formatter,
+    out: String,
+    /// The level of nesting of the current line.
+    level: usize,
+    /// The number of characters on the current line.
+    column: usize,
+    /// The comments that are yet to be written, in the order they appear in.
+    comments: Peekable<vec::IntoIter<Comment>>,
+}
+
+impl<'a> Printer<'a> {
+    fn new(formatter: &'a Formatter) -> Self {
+        Self {
+            formatter,
+            out: String::new(),
+            level: 0,
+            column: 0,
+            comments: Vec::new().into_iter().peekable(),
+        }
+    }
+
+    /// Writes the comments that appear before `position` on lines of their own.
+    fn comments_before(&mut self, position: BytePos) {
+        while let Some(comment) = self.comments.next_if(|comment| comment.span.lo < position) {
+            self.line(Doc::text(comment.text.trim_end()));
+        }
+    }
+
+    /// Appends the comments that follow code on the same line and appear before `position` to the current line.
+    /// Since a line comment ends its line, the comments after it are written on lines of their own.
+    fn trailing_comments_before(&mut self, position: BytePos) {
+        let mut line_ended = false;
+        while let Some(comment) = self
+            .comments
+            .next_if(|comment| comment.trailing && comment.span.lo < position)
+        {
+            let text = comment.text.trim_end();
+            if line_ended {
+                self.end_line();
+                self.start_line();
+                self.write(Doc::text(text));
+            } else {
+                self.write(Doc::text(format!(" {}", text)));
+            }
+            line_ended = comment.text.starts_with("//");
+        }
+    }
+
+    /// Starts a new line at the current level of nesting.
+    fn start_line(&mut self) {
+        self.column = self.level * self.formatter.indent;
+        self.out.push_str(&" ".repeat(self.column));
+    }
+
+    fn end_line(&mut self) {
+        self.out.push('\n');
+        self.column = 0;
+    }
+
+    /// Appends `doc` to the current line, breaking its groups as needed.
+    fn write(&mut self, doc: Doc) {
+        self.column = doc.render(
+            &mut self.out,
+            self.column,
+            self.level * self.formatter.indent,
+            self.formatter.indent,
+            self.formatter.width,
+        );
+    }
+
+    /// Writes `doc` on a line of its own.
+    fn line(&mut self, doc: Doc) {
+        self.start_line();
+        self.write(doc);
+        self.end_line();
+    }
+
+    fn program(&mut self, program: &Program) {
+        for name in program.imports.keys() {
+            self.comments_before(name.span.lo);
+            self.line(Doc::text(format!("import {}.leo;", name)));
+        }
+        for name in program.modules.iter() {
+            self.comments_before(name.span.lo);
+            self.line(Doc::text(format!("import {};", name)));
+        }
+        for (i, program_scope) in program.program_scopes.values().enumerate() {
+            if i > 0 || !program.imports.is_empty() || !program.modules.is_empty() {
+                self.end_line();
+            }
+            self.program_scope(program_scope);
+        }
+        self.comments_before(BytePos(u32::MAX));
+    }
+
+    fn program_scope(&mut self, program_scope: &ProgramScope) {
+        self.comments_before(program_scope.span.lo);
+        if let Some(type_) = program_scope.default_integer_type {
+            self.line(Doc::text(format!("@default_integer_type({})", type_)));
+        }
+        self.line(Doc::text(format!("program {} {{", program_scope.program_id)));
+        self.level += 1;
+
+        // Items are written in the order they appear in, so that the comments between them stay in place.
+        let mut items = program_scope
+            .consts
+            .values()
+            .map(Item::Const)
+            .chain(program_scope.structs.values().map(Item::Struct))
+            .chain(program_scope.enums.values().map(Item::Enum))
+            .chain(program_scope.mappings.values().map(Item::Mapping))
+            .chain(program_scope.functions.values().map(Item::Function))
+            .collect::<Vec<_>>();
+        items.sort_by_key(Item::lo);
+
+        for (i, item) in items.iter().enumerate() {
+            // Consecutive constants and mappings are listed together, while other items are separated by blank lines.
+            let listed = i > 0
+                && matches!(
+                    (&items[i - 1], item),
+                    (Item::Const(_), Item::Const(_)) | (Item::Mapping(_), Item::Mapping(_))
+                );
+            if i > 0 && !listed {
+                self.end_line();
+            }
+            self.comments_before(item.lo());
+
+            let next = items.get(i + 1).map_or(program_scope.span.hi, Item::lo);
+            match item {
+                Item::Const(const_) => {
+                    self.start_line();
+                    self.write(Doc::concat([
+                        Doc::text(format!(
+                            "const {}: {} = ",
+                            const_.identifier,
+                            type_to_string(&const_.type_)
+                        )),
+                        expression_doc(&const_.value, TERNARY, false),
+                        Doc::text(";"),
+                    ]));
+                }
+                Item::Struct(struct_) => self.struct_(struct_),
+                Item::Enum(enum_) => self.enum_(enum_),
+                Item::Mapping(mapping) => {
+                    self.start_line();
+                    self.write(Doc::text(format!(
+                        "mapping {}: {} => {};",
+                        mapping.identifier,
+                        type_to_string(&mapping.key_type),
+                        type_to_string(&mapping.value_type)
+                    )));
+                }
+                Item::Function(function) => self.function(function),
+            }
+            self.trailing_comments_before(next);
+            self.end_line();
+        }
+
+        self.comments_before(program_scope.span.hi);
+        self.level -= 1;
+        self.start_line();
+        self.write(Doc::text("}"));
+        self.trailing_comments_before(BytePos(u32::MAX));
+        self.end_line();
+    }
+
+    /// Writes a struct, leaving the line of its closing brace open.
+    fn struct_(&mut self, struct_: &Struct) {
+        let keyword = if struct_.is_record { "record" } else { "struct" };
+        // The members that the parser injects into records are left for it to inject again.
+        let members = struct_
+            .members
+            .iter()
+            .filter(|member| !struct_.injected_members.contains(&member.name()))
+            .collect::<Vec<_>>();
+        let mut header = format!("{} {}", keyword, struct_.identifier);
+        if struct_.is_generic() {
+            let parameters = struct_.const_parameters.iter().map(|parameter| parameter.to_string());
+            header.push_str(&format!("<{}>", parameters.collect::<Vec<_>>().join(", ")));
+        }
+        self.start_line();
+        if members.is_empty() && !self.has_comments_before(struct_.span.hi) {
+            return self.write(Doc::text(format!("{} {{}}", header)));
+        }
+
+        self.write(Doc::text(format!("{} {{", header)));
+        self.end_line();
+        self.level += 1;
+        for (i, member) in members.iter().enumerate() {
+            self.comments_before(member.identifier.span.lo);
+            self.start_line();
+            self.write(Doc::text(format!(
+                "{}: {},",
+                member.identifier,
+                type_to_string(&member.type_)
+            )));
+            let next = members
+                .get(i + 1)
+                .map_or(struct_.span.hi, |member| member.identifier.span.lo);
+            self.trailing_comments_before(next);
+            self.end_line();
+        }
+        self.comments_before(struct_.span.hi);
+        self.level -= 1;
+        self.start_line();
+        self.write(Doc::text("}"));
+    }
+
+    /// Writes an enum, with a variant per line, leaving the line of its closing brace open.
+    fn enum_(&mut self, enum_: &Enum) {
+        self.start_line();
+        self.write(Doc::text(format!("enum {} {{", enum_.identifier)));
+        self.end_line();
+        self.level += 1;
+        for (i, variant) in enum_.variants.iter().enumerate() {
+            self.comments_before(variant.span.lo);
+            self.start_line();
+            self.write(Doc::text(format!("{},", variant)));
+            let next = enum_
+                .variants
+                .get(i + 1)
+                .map_or(enum_.span.hi, |variant| variant.span.lo);
+            self.trailing_comments_before(next);
+            self.end_line();
+        }
+        self.comments_before(enum_.span.hi);
+        self.level -= 1;
+        self.start_line();
+        self.write(Doc::text("}"));
+    }
+
+    /// Writes a function and its finalize block, if any, leaving the line of the last closing brace open.
+    fn function(&mut self, function: &Function) {
+        for annotation in function.annotations.iter() {
+            self.line(Doc::text(annotation.to_string()));
+        }
+        let keyword = match function.call_type {
+            CallType::Inline => "inline",
+            CallType::Standard => "function",
+            CallType::Transition => "transition",
+        };
+        self.start_line();
+        self.write(signature_doc(
+            format!("{} {}", keyword, function.identifier),
+            &function.input,
+            &function.output,
+        ));
+        self.block(&function.block);
+
+        if let Some(finalize) = &function.finalize {
+            self.trailing_comments_before(finalize.span.lo);
+            self.end_line();
+            self.end_line();
+            self.comments_before(finalize.span.lo);
+            self.start_line();
+            self.write(signature_doc(
+                format!("finalize {}", finalize.identifier),
+                &finalize.input,
+                &finalize.output,
+            ));
+            self.block(&finalize.block);
+        }
+    }
+
+    /// Returns true if there are comments to write before `position`.
+    fn has_comments_before(&mut self, position: BytePos) -> bool {
+        self.comments.peek().map_or(false, |comment| comment.span.lo < position)
+    }
+
+    /// Appends a block to the current line, leaving the line of its closing brace open.
+    fn block(&mut self, block: &Block) {
+        if block.statements.is_empty() && !self.has_comments_before(block.span.hi) {
+            return self.write(Doc::text("{}"));
+        }
+
+        self.write(Doc::text("{"));
+        self.end_line();
+        self.level += 1;
+        for (i, statement) in block.statements.iter().enumerate() {
+            let next = block
+                .statements
+                .get(i + 1)
+                .map_or(block.span.hi, |statement| statement.span().lo);
+            self.comments_before(statement.span().lo);
+            self.start_line();
+            self.open_statement(statement);
+            self.trailing_comments_before(next);
+            self.end_line();
+        }
+        self.comments_before(block.span.hi);
+        self.level -= 1;
+        self.start_line();
+        self.write(Doc::text("}"));
+    }
+
+    /// Appends a statement to the current line, leaving the line of its closing brace or semicolon open.
+    fn open_statement(&mut self, statement: &Statement) {
+        let doc = match statement {
+            Statement::Assert(assert) => {
+                let (name, arguments) = match &assert.variant {
+                    AssertVariant::Assert(expression) => ("assert", vec![expression]),
+                    AssertVariant::AssertEq(left, right) => ("assert_eq", vec![left, right]),
+                    AssertVariant::AssertNeq(left, right) => ("assert_neq", vec![left, right]),
+                };
+                // The arguments of assert statements cannot have a trailing comma, so they are kept on one line.
+                let mut docs = vec![Doc::text(format!("{}(", name))];
+                for (i, argument) in arguments.into_iter().enumerate() {
+                    if i > 0 {
+                        docs.push(Doc::text(", "));
+                    }
+                    docs.push(expression_doc(argument, TERNARY, false));
+                }
+                docs.push(Doc::text(");"));
+                Doc::concat(docs)
+            }
+            Statement::Assign(assign) => Doc::concat([
+                expression_doc(&assign.place, TERNARY, false),
+                Doc::text(" = "),
+                expression_doc(&assign.value, TERNARY, false),
+                Doc::text(";"),
+            ]),
+            Statement::Block(block) => return self.block(block),
+            Statement::Cfg(cfg) => {
+                // Like the annotations of a function, the annotation is written on a line of its own.
+                self.write(Doc::text(cfg.cfg.to_string()));
+                self.end_line();
+                self.start_line();
+                return self.open_statement(&cfg.statement);
+            }
+            Statement::Conditional(conditional) => {
+                // The condition cannot contain a struct expression outside of parentheses, as its `{` opens the block.
+                self.write(Doc::concat([
+                    Doc::text("if "),
+                    expression_doc(&conditional.condition, TERNARY, true),
+                    Doc::text(" "),
+                ]));
+                self.block(&conditional.then);
+                if let Some(otherwise) = &conditional.otherwise {
+                    self.write(Doc::text(" else "));
+                    self.open_statement(otherwise);
+                }
+                return;
+            }
+            Statement::Decrement(decrement) => Doc::concat([
+                Doc::text("decrement"),
+                list_doc(
+                    "(",
+                    vec![
+                        Doc::text(decrement.mapping.to_string()),
+                        expression_doc(&decrement.index, TERNARY, false),
+                        expression_doc(&decrement.amount, TERNARY, false),
+                    ],
+                    ")",
+                    false,
+                ),
+                Doc::text(";"),
+            ]),
+            Statement::Definition(definition) => Doc::concat([
+                Doc::text(format!("{} ", definition.declaration_type)),
+                expression_doc(&definition.place, TERNARY, false),
+                Doc::text(match definition.type_() {
+                    Some(type_) => format!(": {} = ", type_to_string(&type_)),
+                    None => " = ".to_string(),
+                }),
+                expression_doc(&definition.value, TERNARY, false),
+                Doc::text(";"),
+            ]),
+            Statement::Expression(expression) => {
+                Doc::concat([expression_doc(&expression.expression, TERNARY, false), Doc::text(";")])
+            }
+            Statement::Finalize(finalize) => Doc::concat([
+                Doc::text("async finalize"),
+                arguments_doc(finalize.arguments.iter()),
+                Doc::text(";"),
+            ]),
+            Statement::Increment(increment) => Doc::concat([
+                Doc::text("increment"),
+                list_doc(
+                    "(",
+                    vec![
+                        Doc::text(increment.mapping.to_string()),
+                        expression_doc(&increment.index, TERNARY, false),
+                        expression_doc(&increment.amount, TERNARY, false),
+                    ],
+                    ")",
+                    false,
+                ),
+                Doc::text(";"),
+            ]),
+            Statement::Iteration(iteration) => {
+                // Like a condition, the bounds or the array cannot contain a struct expression outside of parentheses.
+                let range = match &iteration.stop {
+                    Some(stop) => Doc::concat([
+                        Doc::text(if iteration.inclusive { "..=" } else { ".." }),
+                        expression_doc(stop, TERNARY, true),
+                    ]),
+                    None => Doc::text(""),
+                };
+                self.write(Doc::concat([
+                    Doc::text(format!(
+                        "for {}: {} in ",
+                        iteration.variable,
+                        type_to_string(&iteration.type_)
+                    )),
+                    expression_doc(&iteration.start, TERNARY, true),
+                    range,
+                    Doc::text(" "),
+                ]));
+                return self.block(&iteration.block);
+            }
+            Statement::Match(match_) => {
+                // Like a condition, the expression cannot contain a struct expression outside of parentheses.
+                self.write(Doc::concat([
+                    Doc::text("match "),
+                    expression_doc(&match_.expression, TERNARY, true),
+                    Doc::text(" {"),
+                ]));
+                self.end_line();
+                self.level += 1;
+                for (i, arm) in match_.arms.iter().enumerate() {
+                    let next = match_.arms.get(i + 1).map_or(match_.span.hi, |arm| arm.span.lo);
+                    self.comments_before(arm.span.lo);
+                    self.start_line();
+                    self.write(Doc::text(format!("{} => ", arm.pattern)));
+                    self.block(&arm.block);
+                    self.trailing_comments_before(next);
+                    self.end_line();
+                }
+                self.comments_before(match_.span.hi);
+                self.level -= 1;
+                self.start_line();
+                return self.write(Doc::text("}"));
+            }
+            Statement::Return(return_) => Doc::concat([
+                Doc::text("return "),
+                expression_doc(&return_.expression, TERNARY, false),
+                Doc::text(";"),
+            ]),
+        };
+        self.write(doc);
+    }
+}
+
+/// Lays out the signature of a function or finalize block, up to the `{` of its body.
+fn signature_doc(name: String, input: &[Input], output: &[Output]) -> Doc {
+    let input = input
+        .iter()
+        .map(|input| {
+            Doc::text(match input {
+                Input::Internal(input) => format!(
+                    "{}{}: {}",
+                    mode_to_string(input.mode),
+                    input.identifier,
+                    type_to_string(&input.type_)
+                ),
+                Input::External(external) => format!(
+                    "{}: {}.leo/{}.record",
+                    external.identifier, external.program_name, external.record
+                ),
+            })
+        })
+        .collect();
+    let mut output = output
+        .iter()
+        .map(|output| {
+            Doc::text(match output {
+                Output::Internal(output) => format!("{}{}", mode_to_string(output.mode), type_to_string(&output.type_)),
+                Output::External(external) => format!("{}.leo/{}.record", external.program_name, external.record),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut docs = vec![Doc::text(name), list_doc("(", input, ")", false)];
+    match output.len() {
+        0 => {}
+        1 => docs.extend([Doc::text(" -> "), output.remove(0)]),
+        _ => docs.extend([Doc::text(" -> "), list_doc("(", output, ")", false)]),
+    }
+    docs.push(Doc::text(" "));
+    Doc::concat(docs)
+}
+
+/// Lays out a list of items separated by commas, which is broken over several lines, one item per line,
+/// with a trailing comma, if it does not fit on one line.
+/// A padded list is separated from its delimiters by spaces when it fits on one line.
+fn list_doc(open: &str, items: Vec<Doc>, close: &str, padded: bool) -> Doc {
+    if items.is_empty() {
+        return Doc::text(format!("{}{}", open, close));
+    }
+
+    let padding = || if padded { Doc::Line } else { Doc::SoftLine };
+    let mut inner = vec![padding()];
+    for (i, item) in items.into_iter().enumerate() {
+        if i > 0 {
+            inner.extend([Doc::text(","), Doc::Line]);
+        }
+        inner.push(item);
+    }
+    inner.push(Doc::IfBroken(","));
+
+    Doc::group(Doc::concat([
+        Doc::text(open),
+        Doc::nest(Doc::concat(inner)),
+        padding(),
+        Doc::text(close),
+    ]))
+}
+
+/// Lays out the parenthesized arguments of a call.
+fn arguments_doc<'a>(arguments: impl IntoIterator<Item = &'a Expression>) -> Doc {
+    let arguments = arguments
+        .into_iter()
+        .map(|argument| expression_doc(argument, TERNARY, false))
+        .collect();
+    list_doc("(", arguments, ")", false)
+}
+
+/// Returns the precedence and the minimum precedences of the operands of an infix binary operator,
+/// following the grammar of the parser, or `None` if the operation is written as a method call.
+fn infix_precedence(op: BinaryOperation) -> Option<(u8, u8, u8)> {
+    use BinaryOperation::*;
+
+    // Operators at the same level are left-associative, except for the comparisons, which do not associate,
+    // and exponentiation, which is right-associative and whose base is a cast expression.
+    let left_associative = |precedence: u8| Some((precedence, precedence, precedence + 1));
+    match op {
+        Or => left_associative(1),
+        And => left_associative(2),
+        Eq | Neq => Some((3, 4, 4)),
+        Lt | Lte | Gt | Gte => Some((4, 5, 5)),
+        Xor => left_associative(5),
+        BitwiseOr => left_associative(6),
+        BitwiseAnd => left_associative(7),
+        Shl | Shr => left_associative(8),
+        Add | Sub => left_associative(9),
+        Mul | Div | Rem => left_associative(10),
+        Pow => Some((11, CAST, 11)),
+        AddWrapped | DivWrapped | Mod | MulWrapped | Nand | Nor | PowWrapped | RemWrapped | ShlWrapped | ShrWrapped
+        | SubWrapped => None,
+    }
+}
+
+/// Returns the name of the method that a binary operation without an infix operator is written as.
+fn binary_method(op: BinaryOperation) -> &'static str {
+    use BinaryOperation::*;
+
+    match op {
+        AddWrapped => "add_wrapped",
+        DivWrapped => "div_wrapped",
+        Mod => "mod",
+        MulWrapped => "mul_wrapped",
+        Nand => "nand",
+        Nor => "nor",
+        PowWrapped => "pow_wrapped",
+        RemWrapped => "rem_wrapped",
+        ShlWrapped => "shl_wrapped",
+        ShrWrapped => "shr_wrapped",
+        SubWrapped => "sub_wrapped",
+        _ => unreachable!("infix operators are not written as method calls"),
+    }
+}
+
+/// Returns the name of the method that a unary operation without a prefix operator is written as.
+fn unary_method(op: UnaryOperation) -> &'static str {
+    use UnaryOperation::*;
+
+    match op {
+        Abs => "abs",
+        AbsWrapped => "abs_wrapped",
+        Double => "double",
+        Inverse => "inv",
+        Square => "square",
+        SquareRoot => "square_root",
+        Negate | Not => unreachable!("prefix operators are not written as method calls"),
+    }
+}
+
+/// Returns the precedence of an expression, i.e. how tightly it binds to its neighbors.
+fn precedence(expression: &Expression) -> u8 {
+    match expression {
+        Expression::Ternary(_) => TERNARY,
+        Expression::Cast(_) => CAST,
+        Expression::Binary(binary) => infix_precedence(binary.op).map_or(POSTFIX, |(precedence, ..)| precedence),
+        Expression::Unary(unary) if matches!(unary.op, UnaryOperation::Negate | UnaryOperation::Not) => UNARY,
+        // A negative literal is parsed as a negated literal, so it binds like one.
+        Expression::Literal(literal) if literal_to_string(literal).starts_with('-') => UNARY,
+        _ => POSTFIX,
+    }
+}
+
+/// Lays out an expression that binds at least as tightly as `min_precedence`, parenthesizing it otherwise.
+/// If `no_struct` is set, the expression is followed by a block, so struct expressions are parenthesized as well,
+/// since their `{` would open the block, and so are ternary expressions, whose last operand may be a struct expression.
+fn expression_doc(expression: &Expression, min_precedence: u8, no_struct: bool) -> Doc {
+    if precedence(expression) < min_precedence
+        || (no_struct && matches!(expression, Expression::Struct(_) | Expression::Ternary(_)))
+    {
+        parenthesized_doc(expression)
+    } else {
+        unparenthesized_doc(expression, no_struct)
+    }
+}
+
+fn parenthesized_doc(expression: &Expression) -> Doc {
+    Doc::concat([Doc::text("("), unparenthesized_doc(expression, false), Doc::text(")")])
+}
+
+fn unparenthesized_doc(expression: &Expression, no_struct: bool) -> Doc {
+    match expression {
+        Expression::Access(AccessExpression::Array(access)) => Doc::concat([
+            expression_doc(&access.array, POSTFIX, no_struct),
+            Doc::text("["),
+            expression_doc(&access.index, TERNARY, false),
+            Doc::text("]"),
+        ]),
+        Expression::Access(AccessExpression::AssociatedConstant(constant)) => {
+            Doc::text(format!("{}::{}", type_to_string(&constant.ty), constant.name))
+        }
+        Expression::Access(AccessExpression::AssociatedFunction(function)) => Doc::concat([
+            Doc::text(format!("{}::{}", type_to_string(&function.ty), function.name)),
+            arguments_doc(function.args.iter()),
+        ]),
+        Expression::Access(AccessExpression::Member(access)) => Doc::concat([
+            expression_doc(&access.inner, POSTFIX, no_struct),
+            Doc::text(format!(".{}", access.name)),
+        ]),
+        Expression::Access(AccessExpression::Tuple(access)) => Doc::concat([
+            expression_doc(&access.tuple, POSTFIX, no_struct),
+            Doc::text(format!(".{}", access.index)),
+        ]),
+        Expression::Array(array) => list_doc(
+            "[",
+            array
+                .elements
+                .iter()
+                .map(|element| expression_doc(element, TERNARY, false))
+                .collect(),
+            "]",
+            false,
+        ),
+        Expression::Binary(binary) => match infix_precedence(binary.op) {
+            Some((precedence, left, right)) => {
+                // A chain of operators at the same level, e.g. `a && b && c`, is broken before each operator.
+                let mut operands = vec![(binary.op, binary.right.as_ref())];
+                let mut first = binary.left.as_ref();
+                while let Expression::Binary(inner) = first {
+                    match infix_precedence(inner.op) {
+                        Some((inner_precedence, ..)) if inner_precedence == precedence && left == precedence => {
+                            operands.push((inner.op, inner.right.as_ref()));
+                            first = inner.left.as_ref();
+                        }
+                        _ => break,
+                    }
+                }
+
+                let rest = operands.into_iter().rev().flat_map(|(op, operand)| {
+                    [
+                        Doc::Line,
+                        Doc::text(format!("{} ", op)),
+                        expression_doc(operand, right, no_struct),
+                    ]
+                });
+                Doc::group(Doc::concat([
+                    expression_doc(first, left, no_struct),
+                    Doc::nest(Doc::concat(rest)),
+                ]))
+            }
+            None => Doc::concat([
+                expression_doc(&binary.left, POSTFIX, no_struct),
+                Doc::text(format!(".{}", binary_method(binary.op))),
+                arguments_doc([binary.right.as_ref()]),
+            ]),
+        },
+        Expression::Call(call) => {
+            let mut docs = Vec::with_capacity(4);
+            if let Some(external) = &call.external {
+                docs.extend([expression_doc(external, POSTFIX, no_struct), Doc::text(".leo/")]);
+            }
+            docs.extend([
+                expression_doc(&call.function, POSTFIX, no_struct),
+                arguments_doc(call.arguments.iter()),
+            ]);
+            Doc::concat(docs)
+        }
+        Expression::Cast(cast) => Doc::concat([
+            expression_doc(&cast.expression, CAST, no_struct),
+            Doc::text(format!(" as {}", type_to_string(&cast.type_))),
+        ]),
+        Expression::Struct(struct_) => {
+            let members = struct_
+                .members
+                .iter()
+                .map(|member| match &member.expression {
+                    Some(expression) => Doc::concat([
+                        Doc::text(format!("{}: ", member.identifier)),
+                        expression_doc(expression, TERNARY, false),
+                    ]),
+                    None => Doc::text(member.identifier.to_string()),
+                })
+                .collect::<Vec<_>>();
+            let name = match struct_.const_arguments.is_empty() {
+                true => struct_.name.to_string(),
+                false => {
+                    let arguments = struct_.const_arguments.iter().map(|argument| argument.to_string());
+                    format!("{}::<{}>", struct_.name, arguments.collect::<Vec<_>>().join(", "))
+                }
+            };
+            match members.is_empty() {
+                true => Doc::text(format!("{} {{}}", name)),
+                false => list_doc(&format!("{} {{", name), members, "}", true),
+            }
+        }
+        Expression::Err(_) => Doc::text("error"),
+        Expression::Identifier(identifier) => Doc::text(identifier.to_string()),
+        Expression::Literal(literal) => Doc::text(literal_to_string(literal)),
+        Expression::Ternary(ternary) => Doc::concat([
+            expression_doc(&ternary.condition, TERNARY + 1, no_struct),
+            Doc::text(" ? "),
+            expression_doc(&ternary.if_true, TERNARY, false),
+            Doc::text(" : "),
+            expression_doc(&ternary.if_false, TERNARY, false),
+        ]),
+        Expression::Tuple(tuple) => match tuple.elements.as_slice() {
+            // A parenthesized expression is only a tuple if it has a trailing comma.
+            [element] => Doc::concat([Doc::text("("), expression_doc(element, TERNARY, false), Doc::text(",)")]),
+            elements => list_doc(
+                "(",
+                elements
+                    .iter()
+                    .map(|element| expression_doc(element, TERNARY, false))
+                    .collect(),
+                ")",
+                false,
+            ),
+        },
+        Expression::Unary(unary) => match unary.op {
+            UnaryOperation::Negate | UnaryOperation::Not => {
+                let operator = if unary.op == UnaryOperation::Negate { "-" } else { "!" };
+                // A negated integer literal would be parsed as a negative literal, so it is parenthesized.
+                let receiver = match (unary.op, unary.receiver.as_ref()) {
+                    (UnaryOperation::Negate, Expression::Literal(_)) => parenthesized_doc(&unary.receiver),
+                    _ => expression_doc(&unary.receiver, UNARY, no_struct),
+                };
+                Doc::concat([Doc::text(operator), receiver])
+            }
+            op => Doc::concat([
+                expression_doc(&unary.receiver, POSTFIX, no_struct),
+                Doc::text(format!(".{}()", unary_method(op))),
+            ]),
+        },
+    }
+}
+
+fn literal_to_string(literal: &Literal) -> String {
+    match literal {
+        Literal::Address(address, ..) => address.clone(),
+        Literal::Boolean(boolean, ..) => boolean.to_string(),
+        Literal::Field(field, ..) => format!("{}field", field),
+        Literal::Group(group) => match group.as_ref() {
+            crate::GroupLiteral::Single(group, ..) => format!("{}group", group),
+            crate::GroupLiteral::Tuple(tuple) => format!("({}, {})group", tuple.x, tuple.y),
+        },
+        Literal::Integer(type_, value, ..) => format!("{}{}", value, type_),
+        Literal::Scalar(scalar, ..) => format!("{}scalar", scalar),
+        Literal::String(string, ..) => format!("\"{}\"", string),
+        // The type of an unsuffixed literal is inferred, so it is not written.
+        Literal::Unsuffixed(value, ..) => value.clone(),
+    }
+}
+
+fn mode_to_string(mode: Mode) -> &'static str {
+    match mode {
+        Mode::Const => "constant ",
+        Mode::Public => "public ",
+        // Inputs and outputs are private by default, and there is no keyword to mark them as such.
+        Mode::None | Mode::Private => "",
+    }
+}
+
+fn type_to_string(type_: &Type) -> String {
+    match type_ {
+        Type::Array(element_type, length) => format!("[{}; {}]", type_to_string(element_type), length),
+        Type::Boolean => "bool".to_string(),
+        Type::Mapping(mapping) => format!(
+            "({} => {})",
+            type_to_string(&mapping.key),
+            type_to_string(&mapping.value)
+        ),
+        Type::Tuple(tuple) => format!("({})", tuple.iter().map(type_to_string).collect::<Vec<_>>().join(", ")),
+        type_ => type_.to_string(),
+    }
+}
@@ -14,27 +14,62 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{simple_node_impl, Identifier, Node};
+use crate::{simple_node_impl, Identifier, Node, NodeId};
 
 use leo_span::Span;
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-/// An annotation, e.g. @program.
+/// An annotation, e.g. @program or @allow(empty_function).
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Annotation {
     // TODO: Consider using a symbol instead of an identifier.
     /// The name of the annotation.
     pub identifier: Identifier,
+    /// The arguments of the annotation, e.g. the lints in `@allow(empty_function)`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<AnnotationArgument>,
     /// A span locating where the annotation occurred in the source.
     pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
 }
 
 simple_node_impl!(Annotation);
 
 impl fmt::Display for Annotation {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "@{}", self.identifier)
+        write!(f, "@{}", self.identifier)?;
+        if !self.arguments.is_empty() {
+            let arguments: Vec<String> = self.arguments.iter().map(|argument| argument.to_string()).collect();
+            write!(f, "({})", arguments.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// An argument of an annotation, e.g. `empty_function` in `@allow(empty_function)` or `network = "testnet3"` in `@cfg(network = "testnet3")`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AnnotationArgument {
+    /// The name of the argument.
+    pub identifier: Identifier,
+    /// The string that the argument is set to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// A span locating where the argument occurred in the source.
+    pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
+}
+
+simple_node_impl!(AnnotationArgument);
+
+impl fmt::Display for AnnotationArgument {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "{} = \"{}\"", self.identifier, value),
+            None => write!(f, "{}", self.identifier),
+        }
     }
 }
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Block, Identifier, Input, Node, Output, Tuple, Type};
+use crate::{Block, Identifier, Input, Node, NodeId, Output, Tuple, Type};
 
 use leo_span::Span;
 
@@ -36,11 +36,20 @@ pub struct Finalize {
     pub block: Block,
     /// The entire span of the finalize block.
     pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
 }
 
 impl Finalize {
     /// Create a new finalize block.
-    pub fn new(identifier: Identifier, input: Vec<Input>, output: Vec<Output>, block: Block, span: Span) -> Self {
+    pub fn new(
+        identifier: Identifier,
+        input: Vec<Input>,
+        output: Vec<Output>,
+        block: Block,
+        span: Span,
+        id: NodeId,
+    ) -> Self {
         let output_type = match output.len() {
             0 => Type::Unit,
             1 => output[0].type_(),
@@ -54,6 +63,7 @@ impl Finalize {
             output_type,
             block,
             span,
+            id,
         }
     }
 }
@@ -14,9 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{External, Identifier, Mode, Node, Type};
-use leo_span::Span;
+use crate::{External, Identifier, Mode, Node, NodeId, Type};
+use leo_span::{Span, Symbol};
 
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -60,6 +61,22 @@ impl Input {
             External(_) => Mode::None,
         }
     }
+
+    /// Returns the input with the type parameters in its type replaced, as in `Type::substitute`.
+    pub fn substitute(&self, substitution: &IndexMap<Symbol, Type>) -> Self {
+        self.map_type(|type_| type_.substitute(substitution))
+    }
+
+    /// Returns the input with its type replaced by the result of `map`, if it is internal.
+    pub fn map_type(&self, map: impl Fn(&Type) -> Type) -> Self {
+        match self {
+            Input::Internal(input) => Input::Internal(FunctionInput {
+                type_: map(&input.type_),
+                ..input.clone()
+            }),
+            Input::External(_) => self.clone(),
+        }
+    }
 }
 
 impl Node for Input {
@@ -78,6 +95,22 @@ impl Node for Input {
             External(input) => input.set_span(span),
         }
     }
+
+    fn id(&self) -> NodeId {
+        use Input::*;
+        match self {
+            Internal(input) => input.id(),
+            External(input) => input.id(),
+        }
+    }
+
+    fn set_id(&mut self, id: NodeId) {
+        use Input::*;
+        match self {
+            Internal(input) => input.set_id(id),
+            External(input) => input.set_id(id),
+        }
+    }
 }
 
 /// A function parameter.
@@ -91,6 +124,8 @@ pub struct FunctionInput {
     pub type_: Type,
     /// The parameters span from any annotations to its type.
     pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
 }
 
 impl FunctionInput {
@@ -14,9 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{External, Mode, Node, Type};
-use leo_span::Span;
+use crate::{External, Mode, Node, NodeId, Type};
+use leo_span::{Span, Symbol};
 
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -40,6 +41,22 @@ impl Output {
             Output::External(_) => Mode::None,
         }
     }
+
+    /// Returns the output with the type parameters in its type replaced, as in `Type::substitute`.
+    pub fn substitute(&self, substitution: &IndexMap<Symbol, Type>) -> Self {
+        self.map_type(|type_| type_.substitute(substitution))
+    }
+
+    /// Returns the output with its type replaced by the result of `map`, if it is internal.
+    pub fn map_type(&self, map: impl Fn(&Type) -> Type) -> Self {
+        match self {
+            Output::Internal(output) => Output::Internal(FunctionOutput {
+                type_: map(&output.type_),
+                ..output.clone()
+            }),
+            Output::External(_) => self.clone(),
+        }
+    }
 }
 
 impl fmt::Display for Output {
@@ -68,6 +85,22 @@ impl Node for Output {
             External(output) => output.set_span(span),
         }
     }
+
+    fn id(&self) -> NodeId {
+        use Output::*;
+        match self {
+            Internal(output) => output.id(),
+            External(output) => output.id(),
+        }
+    }
+
+    fn set_id(&mut self, id: NodeId) {
+        use Output::*;
+        match self {
+            Internal(output) => output.set_id(id),
+            External(output) => output.set_id(id),
+        }
+    }
 }
 
 /// A function output.
@@ -79,6 +112,8 @@ pub struct FunctionOutput {
     pub type_: Type,
     /// The parameters span from any annotations to its type.
     pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
 }
 
 impl fmt::Display for FunctionOutput {
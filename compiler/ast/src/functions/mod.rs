@@ -35,7 +35,10 @@ pub use function_output::*;
 pub mod mode;
 pub use mode::*;
 
-use crate::{Block, Identifier, Node, Tuple, Type};
+pub mod type_parameter;
+pub use type_parameter::*;
+
+use crate::{Block, Identifier, Node, NodeId, Tuple, Type};
 use leo_span::{sym, Span, Symbol};
 
 use serde::{Deserialize, Serialize};
@@ -50,6 +53,9 @@ pub struct Function {
     pub call_type: CallType,
     /// The function identifier, e.g., `foo` in `function foo(...) { ... }`.
     pub identifier: Identifier,
+    /// The type parameters of a generic function, e.g. `T: integer` in `function max<T: integer>(a: T, b: T) -> T`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub type_parameters: Vec<TypeParameter>,
     /// The function's input parameters.
     pub input: Vec<Input>,
     /// The function's output declarations.
@@ -62,6 +68,8 @@ pub struct Function {
     pub finalize: Option<Finalize>,
     /// The entire span of the function definition.
     pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
 }
 
 impl PartialEq for Function {
@@ -79,11 +87,13 @@ impl Function {
         annotations: Vec<Annotation>,
         call_type: CallType,
         identifier: Identifier,
+        type_parameters: Vec<TypeParameter>,
         input: Vec<Input>,
         output: Vec<Output>,
         block: Block,
         finalize: Option<Finalize>,
         span: Span,
+        id: NodeId,
     ) -> Self {
         // Determine the output type of the function
         let get_output_type = |output: &Output| match &output {
@@ -101,12 +111,14 @@ impl Function {
             annotations,
             call_type,
             identifier,
+            type_parameters,
             input,
             output,
             output_type,
             block,
             finalize,
             span,
+            id,
         }
     }
     /// Returns function name.
@@ -119,6 +131,11 @@ impl Function {
         self.name() == sym::main
     }
 
+    /// Returns `true` if the function has type parameters.
+    pub fn is_generic(&self) -> bool {
+        !self.type_parameters.is_empty()
+    }
+
     ///
     /// Private formatting method used for optimizing [fmt::Debug] and [fmt::Display] implementations.
     ///
@@ -129,6 +146,15 @@ impl Function {
             CallType::Transition => write!(f, "transition ")?,
         }
         write!(f, "{}", self.identifier)?;
+        if self.is_generic() {
+            let type_parameters = self
+                .type_parameters
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            write!(f, "<{}>", type_parameters)?;
+        }
 
         let parameters = self.input.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(",");
         let returns = match self.output.len() {
@@ -34,17 +34,19 @@ impl TryFrom<(Type, Expression)> for InputValue {
     fn try_from(value: (Type, Expression)) -> Result<Self> {
         Ok(match value {
             (type_, Expression::Literal(lit)) => match (type_, lit) {
-                (Type::Address, Literal::Address(value, _)) => Self::Address(value),
-                (Type::Boolean, Literal::Boolean(value, _)) => Self::Boolean(value),
-                (Type::Field, Literal::Field(value, _)) => Self::Field(value),
+                (Type::Address, Literal::Address(value, ..)) => Self::Address(value),
+                (Type::Boolean, Literal::Boolean(value, ..)) => Self::Boolean(value),
+                (Type::Field, Literal::Field(value, ..)) => Self::Field(value),
                 (Type::Group, Literal::Group(value)) => Self::Group(*value),
-                (Type::Integer(expected), Literal::Integer(actual, value, span)) => {
+                (Type::Integer(expected), Literal::Integer(actual, value, span, _)) => {
                     if expected == actual {
                         Self::Integer(expected, value)
                     } else {
                         return Err(InputError::unexpected_type(expected.to_string(), actual, span).into());
                     }
                 }
+                // Unsuffixed integers take the declared type of the input.
+                (Type::Integer(expected), Literal::Unsuffixed(value, ..)) => Self::Integer(expected, value),
                 (x, y) => {
                     return Err(InputError::unexpected_type(x, &y, y.span()).into());
                 }
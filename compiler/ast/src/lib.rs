@@ -33,9 +33,18 @@ pub use self::r#struct::*;
 pub mod common;
 pub use self::common::*;
 
+pub mod constant;
+pub use self::constant::*;
+
+pub mod r#enum;
+pub use self::r#enum::*;
+
 pub mod expressions;
 pub use self::expressions::*;
 
+pub mod formatter;
+pub use self::formatter::*;
+
 pub mod functions;
 pub use self::functions::*;
 
@@ -67,6 +76,8 @@ pub use common::node::*;
 
 use leo_errors::{AstError, Result};
 
+use serde::Deserialize;
+
 /// The abstract syntax tree (AST) for a Leo program.
 ///
 /// The [`Ast`] type represents a Leo program as a series of recursive data types.
@@ -135,8 +146,15 @@ impl Ast {
     }
 
     /// Deserializes the JSON string into a ast.
+    /// The symbols of the AST are interned and its spans are kept, so the string must be deserialized within the session
+    /// whose source map the spans refer to for diagnostics to point to the program.
     pub fn from_json_string(json: &str) -> Result<Self> {
-        let ast: Program = serde_json::from_str(json).map_err(|e| AstError::failed_to_read_json_string_to_ast(&e))?;
+        // The AST is as deeply nested as the program, which may exceed the default recursion limit of `serde_json`.
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        deserializer.disable_recursion_limit();
+        let ast = Program::deserialize(&mut deserializer)
+            .and_then(|ast| deserializer.end().map(|()| ast))
+            .map_err(|e| AstError::failed_to_read_json_string_to_ast(&e))?;
         Ok(Self { ast })
     }
 
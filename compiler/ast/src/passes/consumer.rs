@@ -19,31 +19,55 @@
 
 use crate::*;
 
+/// Generates `ExpressionConsumer::consume_expression`.
+macro_rules! expression_consumer {
+    (
+        $(
+            $variant:ident($node:ident) => $visit:ident, $reconstruct:ident, $consume:ident
+            $({ $($field:ident: $kind:ident),* })?;
+        )*
+    ) => {
+        fn consume_expression(&mut self, input: Expression) -> Self::Output {
+            match input {
+                $(Expression::$variant(node) => self.$consume(node),)*
+            }
+        }
+    };
+}
+
+/// Generates `StatementConsumer::consume_statement`.
+macro_rules! statement_consumer {
+    (
+        $(
+            $(#[$boxed:ident])?
+            $variant:ident($node:ident) => $visit:ident, $reconstruct:ident, $consume:ident
+            $({ $($field:ident: $kind:ident),* })?;
+        )*
+    ) => {
+        fn consume_statement(&mut self, input: Statement) -> Self::Output {
+            match input {
+                $(Statement::$variant(node) => self.$consume(unbox_node!($($boxed)? node)),)*
+            }
+        }
+    };
+}
+
 /// A Consumer trait for expressions in the AST.
 pub trait ExpressionConsumer {
     type Output;
 
-    fn consume_expression(&mut self, input: Expression) -> Self::Output {
-        match input {
-            Expression::Access(access) => self.consume_access(access),
-            Expression::Binary(binary) => self.consume_binary(binary),
-            Expression::Call(call) => self.consume_call(call),
-            Expression::Struct(struct_) => self.consume_struct_init(struct_),
-            Expression::Err(err) => self.consume_err(err),
-            Expression::Identifier(identifier) => self.consume_identifier(identifier),
-            Expression::Literal(value) => self.consume_literal(value),
-            Expression::Ternary(ternary) => self.consume_ternary(ternary),
-            Expression::Tuple(tuple) => self.consume_tuple(tuple),
-            Expression::Unary(unary) => self.consume_unary(unary),
-        }
-    }
+    expression_nodes!(expression_consumer);
 
     fn consume_access(&mut self, _input: AccessExpression) -> Self::Output;
 
+    fn consume_array(&mut self, _input: ArrayExpression) -> Self::Output;
+
     fn consume_binary(&mut self, _input: BinaryExpression) -> Self::Output;
 
     fn consume_call(&mut self, _input: CallExpression) -> Self::Output;
 
+    fn consume_cast(&mut self, _input: CastExpression) -> Self::Output;
+
     fn consume_struct_init(&mut self, _input: StructExpression) -> Self::Output;
 
     fn consume_err(&mut self, _input: ErrExpression) -> Self::Output {
@@ -65,39 +89,32 @@ pub trait ExpressionConsumer {
 pub trait StatementConsumer {
     type Output;
 
-    fn consume_statement(&mut self, input: Statement) -> Self::Output {
-        match input {
-            Statement::Assign(stmt) => self.consume_assign(*stmt),
-            Statement::Block(stmt) => self.consume_block(stmt),
-            Statement::Conditional(stmt) => self.consume_conditional(stmt),
-            Statement::Console(stmt) => self.consume_console(stmt),
-            Statement::Decrement(stmt) => self.consume_decrement(stmt),
-            Statement::Definition(stmt) => self.consume_definition(stmt),
-            Statement::Finalize(stmt) => self.consume_finalize(stmt),
-            Statement::Increment(stmt) => self.consume_increment(stmt),
-            Statement::Iteration(stmt) => self.consume_iteration(*stmt),
-            Statement::Return(stmt) => self.consume_return(stmt),
-        }
-    }
+    statement_nodes!(statement_consumer);
+
+    fn consume_assert(&mut self, input: AssertStatement) -> Self::Output;
 
     fn consume_assign(&mut self, input: AssignStatement) -> Self::Output;
 
     fn consume_block(&mut self, input: Block) -> Self::Output;
 
-    fn consume_conditional(&mut self, input: ConditionalStatement) -> Self::Output;
+    fn consume_cfg(&mut self, input: CfgStatement) -> Self::Output;
 
-    fn consume_console(&mut self, input: ConsoleStatement) -> Self::Output;
+    fn consume_conditional(&mut self, input: ConditionalStatement) -> Self::Output;
 
     fn consume_decrement(&mut self, input: DecrementStatement) -> Self::Output;
 
     fn consume_definition(&mut self, input: DefinitionStatement) -> Self::Output;
 
+    fn consume_expression_statement(&mut self, input: ExpressionStatement) -> Self::Output;
+
     fn consume_finalize(&mut self, input: FinalizeStatement) -> Self::Output;
 
     fn consume_increment(&mut self, input: IncrementStatement) -> Self::Output;
 
     fn consume_iteration(&mut self, input: IterationStatement) -> Self::Output;
 
+    fn consume_match(&mut self, input: MatchStatement) -> Self::Output;
+
     fn consume_return(&mut self, input: ReturnStatement) -> Self::Output;
 }
 
@@ -19,6 +19,9 @@
 
 // TODO: Move the files in this module into `leo-passes` in a future PR.
 
+#[macro_use]
+mod nodes;
+
 pub mod consumer;
 pub use consumer::*;
 
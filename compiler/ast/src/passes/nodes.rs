@@ -0,0 +1,117 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! This module contains the single definition of the AST nodes walked by the visitor,
+//! reconstructor, and consumer traits. Each trait generates its dispatch methods, and the
+//! default walks of nodes that only contain expressions, from these tables.
+//! A new node therefore only needs to be registered here and, if its walk is irregular,
+//! given a hand-written default in each trait.
+
+/// Invokes `$generator!` with one entry per variant of `Expression`.
+///
+/// Each entry names the variant, the type of its node, and the methods of the visitor, reconstructor,
+/// and consumer that handle it. Nodes whose default walk only descends into their child expressions
+/// list those children, either as a `Box<Expression>` (`Box`) or a `Vec<Expression>` (`Vec`).
+macro_rules! expression_nodes {
+    ($generator:ident) => {
+        $generator! {
+            Access(AccessExpression) => visit_access, reconstruct_access, consume_access;
+            Array(ArrayExpression) => visit_array, reconstruct_array, consume_array { elements: Vec };
+            Binary(BinaryExpression) => visit_binary, reconstruct_binary, consume_binary { left: Box, right: Box };
+            Call(CallExpression) => visit_call, reconstruct_call, consume_call;
+            Cast(CastExpression) => visit_cast, reconstruct_cast, consume_cast { expression: Box };
+            Struct(StructExpression) => visit_struct_init, reconstruct_struct_init, consume_struct_init;
+            Err(ErrExpression) => visit_err, reconstruct_err, consume_err;
+            Identifier(Identifier) => visit_identifier, reconstruct_identifier, consume_identifier;
+            Literal(Literal) => visit_literal, reconstruct_literal, consume_literal;
+            Ternary(TernaryExpression) => visit_ternary, reconstruct_ternary, consume_ternary {
+                condition: Box,
+                if_true: Box,
+                if_false: Box
+            };
+            Tuple(TupleExpression) => visit_tuple, reconstruct_tuple, consume_tuple { elements: Vec };
+            Unary(UnaryExpression) => visit_unary, reconstruct_unary, consume_unary { receiver: Box };
+        }
+    };
+}
+
+/// Invokes `$generator!` with one entry per variant of `Statement`.
+///
+/// Entries follow the layout of `expression_nodes!`, with two additions. Children may also be a plain
+/// `Expression`, and variants that hold their node in a `Box` are marked `#[boxed]`. The walks of
+/// `#[boxed]` variants are always written by hand.
+macro_rules! statement_nodes {
+    ($generator:ident) => {
+        $generator! {
+            Assert(AssertStatement) => visit_assert, reconstruct_assert, consume_assert;
+            #[boxed] Assign(AssignStatement) => visit_assign, reconstruct_assign, consume_assign;
+            Block(Block) => visit_block, reconstruct_block_statement, consume_block;
+            Cfg(CfgStatement) => visit_cfg, reconstruct_cfg, consume_cfg;
+            Conditional(ConditionalStatement) => visit_conditional, reconstruct_conditional, consume_conditional;
+            Decrement(DecrementStatement) => visit_decrement, reconstruct_decrement, consume_decrement;
+            Definition(DefinitionStatement) => visit_definition, reconstruct_definition, consume_definition {
+                value: Expression
+            };
+            Expression(ExpressionStatement) =>
+                visit_expression_statement,
+                reconstruct_expression_statement,
+                consume_expression_statement { expression: Expression };
+            Finalize(FinalizeStatement) => visit_finalize, reconstruct_finalize, consume_finalize { arguments: Vec };
+            Increment(IncrementStatement) => visit_increment, reconstruct_increment, consume_increment;
+            #[boxed] Iteration(IterationStatement) => visit_iteration, reconstruct_iteration, consume_iteration;
+            Match(MatchStatement) => visit_match, reconstruct_match, consume_match;
+            Return(ReturnStatement) => visit_return, reconstruct_return, consume_return { expression: Expression };
+        }
+    };
+}
+
+/// Visits a child described in a node table with `$visitor`.
+macro_rules! visit_child {
+    ($visitor:ident, Vec, $child:expr, $additional:expr) => {
+        $child.iter().for_each(|expr| {
+            $visitor.visit_expression(expr, $additional);
+        })
+    };
+    ($visitor:ident, $kind:ident, $child:expr, $additional:expr) => {
+        $visitor.visit_expression($child, $additional)
+    };
+}
+
+/// Reconstructs a child described in a node table with `$reconstructor`.
+macro_rules! reconstruct_child {
+    ($reconstructor:ident, Box, $child:expr) => {
+        Box::new($reconstructor.reconstruct_expression(*$child).0)
+    };
+    ($reconstructor:ident, Vec, $child:expr) => {
+        $child
+            .into_iter()
+            .map(|expr| $reconstructor.reconstruct_expression(expr).0)
+            .collect()
+    };
+    ($reconstructor:ident, Expression, $child:expr) => {
+        $reconstructor.reconstruct_expression($child).0
+    };
+}
+
+/// Moves a node out of its variant, which is a `Box` for `#[boxed]` variants.
+macro_rules! unbox_node {
+    (boxed $node:expr) => {
+        *$node
+    };
+    ($node:expr) => {
+        $node
+    };
+}
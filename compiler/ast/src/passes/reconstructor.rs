@@ -20,28 +20,78 @@
 
 use crate::*;
 
+/// Generates `ExpressionReconstructor::reconstruct_expression` and the default walks of regular expressions.
+macro_rules! expression_reconstructor {
+    (
+        $(
+            $variant:ident($node:ident) => $visit:ident, $reconstruct:ident, $consume:ident
+            $({ $($field:ident: $kind:ident),* })?;
+        )*
+    ) => {
+        fn reconstruct_expression(&mut self, input: Expression) -> (Expression, Self::AdditionalOutput) {
+            match input {
+                $(Expression::$variant(node) => self.$reconstruct(node),)*
+            }
+        }
+
+        $($(
+            fn $reconstruct(&mut self, input: $node) -> (Expression, Self::AdditionalOutput) {
+                (
+                    Expression::$variant($node {
+                        $($field: reconstruct_child!(self, $kind, input.$field),)*
+                        ..input
+                    }),
+                    Default::default(),
+                )
+            }
+        )?)*
+    };
+}
+
+/// Generates `StatementReconstructor::reconstruct_statement` and the default walks of regular statements.
+macro_rules! statement_reconstructor {
+    (
+        $(
+            $(#[$boxed:ident])?
+            $variant:ident($node:ident) => $visit:ident, $reconstruct:ident, $consume:ident
+            $({ $($field:ident: $kind:ident),* })?;
+        )*
+    ) => {
+        fn reconstruct_statement(&mut self, input: Statement) -> (Statement, Self::AdditionalOutput) {
+            match input {
+                $(Statement::$variant(node) => self.$reconstruct(unbox_node!($($boxed)? node)),)*
+            }
+        }
+
+        $($(
+            fn $reconstruct(&mut self, input: $node) -> (Statement, Self::AdditionalOutput) {
+                (
+                    Statement::$variant($node {
+                        $($field: reconstruct_child!(self, $kind, input.$field),)*
+                        ..input
+                    }),
+                    Default::default(),
+                )
+            }
+        )?)*
+    };
+}
+
 /// A Reconstructor trait for expressions in the AST.
 pub trait ExpressionReconstructor {
     type AdditionalOutput: Default;
 
-    fn reconstruct_expression(&mut self, input: Expression) -> (Expression, Self::AdditionalOutput) {
-        match input {
-            Expression::Access(access) => self.reconstruct_access(access),
-            Expression::Binary(binary) => self.reconstruct_binary(binary),
-            Expression::Call(call) => self.reconstruct_call(call),
-            Expression::Struct(struct_) => self.reconstruct_struct_init(struct_),
-            Expression::Err(err) => self.reconstruct_err(err),
-            Expression::Identifier(identifier) => self.reconstruct_identifier(identifier),
-            Expression::Literal(value) => self.reconstruct_literal(value),
-            Expression::Ternary(ternary) => self.reconstruct_ternary(ternary),
-            Expression::Tuple(tuple) => self.reconstruct_tuple(tuple),
-            Expression::Unary(unary) => self.reconstruct_unary(unary),
-        }
-    }
+    expression_nodes!(expression_reconstructor);
 
     fn reconstruct_access(&mut self, input: AccessExpression) -> (Expression, Self::AdditionalOutput) {
         (
             Expression::Access(match input {
+                AccessExpression::Array(array) => AccessExpression::Array(ArrayAccess {
+                    array: Box::new(self.reconstruct_expression(*array.array).0),
+                    index: Box::new(self.reconstruct_expression(*array.index).0),
+                    span: array.span,
+                    id: array.id,
+                }),
                 AccessExpression::AssociatedFunction(function) => {
                     AccessExpression::AssociatedFunction(AssociatedFunction {
                         ty: function.ty,
@@ -52,17 +102,20 @@ pub trait ExpressionReconstructor {
                             .map(|arg| self.reconstruct_expression(arg).0)
                             .collect(),
                         span: function.span,
+                        id: function.id,
                     })
                 }
                 AccessExpression::Member(member) => AccessExpression::Member(MemberAccess {
                     inner: Box::new(self.reconstruct_expression(*member.inner).0),
                     name: member.name,
                     span: member.span,
+                    id: member.id,
                 }),
                 AccessExpression::Tuple(tuple) => AccessExpression::Tuple(TupleAccess {
                     tuple: Box::new(self.reconstruct_expression(*tuple.tuple).0),
                     index: tuple.index,
                     span: tuple.span,
+                    id: tuple.id,
                 }),
                 expr => expr,
             }),
@@ -70,18 +123,6 @@ pub trait ExpressionReconstructor {
         )
     }
 
-    fn reconstruct_binary(&mut self, input: BinaryExpression) -> (Expression, Self::AdditionalOutput) {
-        (
-            Expression::Binary(BinaryExpression {
-                left: Box::new(self.reconstruct_expression(*input.left).0),
-                right: Box::new(self.reconstruct_expression(*input.right).0),
-                op: input.op,
-                span: input.span,
-            }),
-            Default::default(),
-        )
-    }
-
     fn reconstruct_call(&mut self, input: CallExpression) -> (Expression, Self::AdditionalOutput) {
         (
             Expression::Call(CallExpression {
@@ -91,8 +132,10 @@ pub trait ExpressionReconstructor {
                     .into_iter()
                     .map(|arg| self.reconstruct_expression(arg).0)
                     .collect(),
+                type_arguments: input.type_arguments,
                 external: input.external,
                 span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
@@ -113,64 +156,32 @@ pub trait ExpressionReconstructor {
     fn reconstruct_literal(&mut self, input: Literal) -> (Expression, Self::AdditionalOutput) {
         (Expression::Literal(input), Default::default())
     }
+}
 
-    fn reconstruct_ternary(&mut self, input: TernaryExpression) -> (Expression, Self::AdditionalOutput) {
-        (
-            Expression::Ternary(TernaryExpression {
-                condition: Box::new(self.reconstruct_expression(*input.condition).0),
-                if_true: Box::new(self.reconstruct_expression(*input.if_true).0),
-                if_false: Box::new(self.reconstruct_expression(*input.if_false).0),
-                span: input.span,
-            }),
-            Default::default(),
-        )
-    }
-
-    fn reconstruct_tuple(&mut self, input: TupleExpression) -> (Expression, Self::AdditionalOutput) {
-        (
-            Expression::Tuple(TupleExpression {
-                elements: input
-                    .elements
-                    .into_iter()
-                    .map(|element| self.reconstruct_expression(element).0)
-                    .collect(),
-                span: input.span,
-            }),
-            Default::default(),
-        )
-    }
+/// A Reconstructor trait for statements in the AST.
+pub trait StatementReconstructor: ExpressionReconstructor {
+    statement_nodes!(statement_reconstructor);
 
-    fn reconstruct_unary(&mut self, input: UnaryExpression) -> (Expression, Self::AdditionalOutput) {
+    fn reconstruct_assert(&mut self, input: AssertStatement) -> (Statement, Self::AdditionalOutput) {
         (
-            Expression::Unary(UnaryExpression {
-                receiver: Box::new(self.reconstruct_expression(*input.receiver).0),
-                op: input.op,
+            Statement::Assert(AssertStatement {
+                variant: match input.variant {
+                    AssertVariant::Assert(expr) => AssertVariant::Assert(self.reconstruct_expression(expr).0),
+                    AssertVariant::AssertEq(left, right) => AssertVariant::AssertEq(
+                        self.reconstruct_expression(left).0,
+                        self.reconstruct_expression(right).0,
+                    ),
+                    AssertVariant::AssertNeq(left, right) => AssertVariant::AssertNeq(
+                        self.reconstruct_expression(left).0,
+                        self.reconstruct_expression(right).0,
+                    ),
+                },
                 span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
     }
-}
-
-/// A Reconstructor trait for statements in the AST.
-pub trait StatementReconstructor: ExpressionReconstructor {
-    fn reconstruct_statement(&mut self, input: Statement) -> (Statement, Self::AdditionalOutput) {
-        match input {
-            Statement::Assign(stmt) => self.reconstruct_assign(*stmt),
-            Statement::Block(stmt) => {
-                let (stmt, output) = self.reconstruct_block(stmt);
-                (Statement::Block(stmt), output)
-            }
-            Statement::Conditional(stmt) => self.reconstruct_conditional(stmt),
-            Statement::Console(stmt) => self.reconstruct_console(stmt),
-            Statement::Decrement(stmt) => self.reconstruct_decrement(stmt),
-            Statement::Definition(stmt) => self.reconstruct_definition(stmt),
-            Statement::Finalize(stmt) => self.reconstruct_finalize(stmt),
-            Statement::Increment(stmt) => self.reconstruct_increment(stmt),
-            Statement::Iteration(stmt) => self.reconstruct_iteration(*stmt),
-            Statement::Return(stmt) => self.reconstruct_return(stmt),
-        }
-    }
 
     fn reconstruct_assign(&mut self, input: AssignStatement) -> (Statement, Self::AdditionalOutput) {
         (
@@ -178,6 +189,7 @@ pub trait StatementReconstructor: ExpressionReconstructor {
                 place: input.place,
                 value: self.reconstruct_expression(input.value).0,
                 span: input.span,
+                id: input.id,
             })),
             Default::default(),
         )
@@ -192,38 +204,37 @@ pub trait StatementReconstructor: ExpressionReconstructor {
                     .map(|s| self.reconstruct_statement(s).0)
                     .collect(),
                 span: input.span,
+                id: input.id,
             },
             Default::default(),
         )
     }
 
-    fn reconstruct_conditional(&mut self, input: ConditionalStatement) -> (Statement, Self::AdditionalOutput) {
+    fn reconstruct_block_statement(&mut self, input: Block) -> (Statement, Self::AdditionalOutput) {
+        let (block, output) = self.reconstruct_block(input);
+        (Statement::Block(block), output)
+    }
+
+    fn reconstruct_cfg(&mut self, input: CfgStatement) -> (Statement, Self::AdditionalOutput) {
         (
-            Statement::Conditional(ConditionalStatement {
-                condition: self.reconstruct_expression(input.condition).0,
-                then: self.reconstruct_block(input.then).0,
-                otherwise: input.otherwise.map(|n| Box::new(self.reconstruct_statement(*n).0)),
+            Statement::Cfg(CfgStatement {
+                cfg: input.cfg,
+                statement: Box::new(self.reconstruct_statement(*input.statement).0),
                 span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
     }
 
-    fn reconstruct_console(&mut self, input: ConsoleStatement) -> (Statement, Self::AdditionalOutput) {
+    fn reconstruct_conditional(&mut self, input: ConditionalStatement) -> (Statement, Self::AdditionalOutput) {
         (
-            Statement::Console(ConsoleStatement {
-                function: match input.function {
-                    ConsoleFunction::Assert(expr) => ConsoleFunction::Assert(self.reconstruct_expression(expr).0),
-                    ConsoleFunction::AssertEq(left, right) => ConsoleFunction::AssertEq(
-                        self.reconstruct_expression(left).0,
-                        self.reconstruct_expression(right).0,
-                    ),
-                    ConsoleFunction::AssertNeq(left, right) => ConsoleFunction::AssertNeq(
-                        self.reconstruct_expression(left).0,
-                        self.reconstruct_expression(right).0,
-                    ),
-                },
+            Statement::Conditional(ConditionalStatement {
+                condition: self.reconstruct_expression(input.condition).0,
+                then: self.reconstruct_block(input.then).0,
+                otherwise: input.otherwise.map(|n| Box::new(self.reconstruct_statement(*n).0)),
                 span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
@@ -236,33 +247,7 @@ pub trait StatementReconstructor: ExpressionReconstructor {
                 index: input.index,
                 amount: input.amount,
                 span: input.span,
-            }),
-            Default::default(),
-        )
-    }
-
-    fn reconstruct_definition(&mut self, input: DefinitionStatement) -> (Statement, Self::AdditionalOutput) {
-        (
-            Statement::Definition(DefinitionStatement {
-                declaration_type: input.declaration_type,
-                variable_name: input.variable_name,
-                type_: input.type_,
-                value: self.reconstruct_expression(input.value).0,
-                span: input.span,
-            }),
-            Default::default(),
-        )
-    }
-
-    fn reconstruct_finalize(&mut self, input: FinalizeStatement) -> (Statement, Self::AdditionalOutput) {
-        (
-            Statement::Finalize(FinalizeStatement {
-                arguments: input
-                    .arguments
-                    .into_iter()
-                    .map(|arg| self.reconstruct_expression(arg).0)
-                    .collect(),
-                span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
@@ -275,6 +260,7 @@ pub trait StatementReconstructor: ExpressionReconstructor {
                 index: input.index,
                 amount: input.amount,
                 span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
@@ -287,21 +273,33 @@ pub trait StatementReconstructor: ExpressionReconstructor {
                 type_: input.type_,
                 start: self.reconstruct_expression(input.start).0,
                 start_value: input.start_value,
-                stop: self.reconstruct_expression(input.stop).0,
+                stop: input.stop.map(|stop| self.reconstruct_expression(stop).0),
                 stop_value: input.stop_value,
                 block: self.reconstruct_block(input.block).0,
                 inclusive: input.inclusive,
                 span: input.span,
+                id: input.id,
             })),
             Default::default(),
         )
     }
 
-    fn reconstruct_return(&mut self, input: ReturnStatement) -> (Statement, Self::AdditionalOutput) {
+    fn reconstruct_match(&mut self, input: MatchStatement) -> (Statement, Self::AdditionalOutput) {
         (
-            Statement::Return(ReturnStatement {
+            Statement::Match(MatchStatement {
                 expression: self.reconstruct_expression(input.expression).0,
+                arms: input
+                    .arms
+                    .into_iter()
+                    .map(|arm| MatchArm {
+                        pattern: arm.pattern,
+                        block: self.reconstruct_block(arm.block).0,
+                        span: arm.span,
+                        id: arm.id,
+                    })
+                    .collect(),
                 span: input.span,
+                id: input.id,
             }),
             Default::default(),
         )
@@ -317,6 +315,7 @@ pub trait ProgramReconstructor: StatementReconstructor {
                 .into_iter()
                 .map(|(id, import)| (id, self.reconstruct_import(import)))
                 .collect(),
+            modules: input.modules,
             program_scopes: input
                 .program_scopes
                 .into_iter()
@@ -328,11 +327,17 @@ pub trait ProgramReconstructor: StatementReconstructor {
     fn reconstruct_program_scope(&mut self, input: ProgramScope) -> ProgramScope {
         ProgramScope {
             program_id: input.program_id,
+            consts: input
+                .consts
+                .into_iter()
+                .map(|(i, c)| (i, self.reconstruct_const(c)))
+                .collect(),
             structs: input
                 .structs
                 .into_iter()
                 .map(|(i, c)| (i, self.reconstruct_struct(c)))
                 .collect(),
+            enums: input.enums,
             mappings: input
                 .mappings
                 .into_iter()
@@ -343,6 +348,7 @@ pub trait ProgramReconstructor: StatementReconstructor {
                 .into_iter()
                 .map(|(i, f)| (i, self.reconstruct_function(f)))
                 .collect(),
+            default_integer_type: input.default_integer_type,
             span: input.span,
         }
     }
@@ -352,6 +358,7 @@ pub trait ProgramReconstructor: StatementReconstructor {
             annotations: input.annotations,
             call_type: input.call_type,
             identifier: input.identifier,
+            type_parameters: input.type_parameters,
             input: input.input,
             output: input.output,
             output_type: input.output_type,
@@ -363,8 +370,20 @@ pub trait ProgramReconstructor: StatementReconstructor {
                 output_type: finalize.output_type,
                 block: self.reconstruct_block(finalize.block).0,
                 span: finalize.span,
+                id: finalize.id,
             }),
             span: input.span,
+            id: input.id,
+        }
+    }
+
+    fn reconstruct_const(&mut self, input: ConstDeclaration) -> ConstDeclaration {
+        ConstDeclaration {
+            identifier: input.identifier,
+            type_: input.type_,
+            value: self.reconstruct_expression(input.value).0,
+            span: input.span,
+            id: input.id,
         }
     }
 
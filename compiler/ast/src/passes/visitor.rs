@@ -20,28 +20,65 @@
 
 use crate::*;
 
+/// Generates `ExpressionVisitor::visit_expression` and the default walks of regular expressions.
+macro_rules! expression_visitor {
+    (
+        $(
+            $variant:ident($node:ident) => $visit:ident, $reconstruct:ident, $consume:ident
+            $({ $($field:ident: $kind:ident),* })?;
+        )*
+    ) => {
+        fn visit_expression(&mut self, input: &'a Expression, additional: &Self::AdditionalInput) -> Self::Output {
+            match input {
+                $(Expression::$variant(node) => self.$visit(node, additional),)*
+            }
+        }
+
+        $($(
+            fn $visit(&mut self, input: &'a $node, additional: &Self::AdditionalInput) -> Self::Output {
+                $(visit_child!(self, $kind, &input.$field, additional);)*
+                Default::default()
+            }
+        )?)*
+    };
+}
+
+/// Generates `StatementVisitor::visit_statement` and the default walks of regular statements.
+macro_rules! statement_visitor {
+    (
+        $(
+            $(#[$boxed:ident])?
+            $variant:ident($node:ident) => $visit:ident, $reconstruct:ident, $consume:ident
+            $({ $($field:ident: $kind:ident),* })?;
+        )*
+    ) => {
+        fn visit_statement(&mut self, input: &'a Statement) {
+            match input {
+                $(Statement::$variant(node) => self.$visit(node),)*
+            }
+        }
+
+        $($(
+            fn $visit(&mut self, input: &'a $node) {
+                $(visit_child!(self, $kind, &input.$field, &Default::default());)*
+            }
+        )?)*
+    };
+}
+
 /// A Visitor trait for expressions in the AST.
 pub trait ExpressionVisitor<'a> {
     type AdditionalInput: Default;
     type Output: Default;
 
-    fn visit_expression(&mut self, input: &'a Expression, additional: &Self::AdditionalInput) -> Self::Output {
-        match input {
-            Expression::Access(access) => self.visit_access(access, additional),
-            Expression::Binary(binary) => self.visit_binary(binary, additional),
-            Expression::Call(call) => self.visit_call(call, additional),
-            Expression::Struct(struct_) => self.visit_struct_init(struct_, additional),
-            Expression::Err(err) => self.visit_err(err, additional),
-            Expression::Identifier(identifier) => self.visit_identifier(identifier, additional),
-            Expression::Literal(literal) => self.visit_literal(literal, additional),
-            Expression::Ternary(ternary) => self.visit_ternary(ternary, additional),
-            Expression::Tuple(tuple) => self.visit_tuple(tuple, additional),
-            Expression::Unary(unary) => self.visit_unary(unary, additional),
-        }
-    }
+    expression_nodes!(expression_visitor);
 
     fn visit_access(&mut self, input: &'a AccessExpression, additional: &Self::AdditionalInput) -> Self::Output {
         match input {
+            AccessExpression::Array(array) => {
+                self.visit_expression(&array.array, additional);
+                self.visit_expression(&array.index, additional);
+            }
             AccessExpression::AssociatedFunction(function) => {
                 function.args.iter().for_each(|arg| {
                     self.visit_expression(arg, &Default::default());
@@ -59,12 +96,6 @@ pub trait ExpressionVisitor<'a> {
         Default::default()
     }
 
-    fn visit_binary(&mut self, input: &'a BinaryExpression, additional: &Self::AdditionalInput) -> Self::Output {
-        self.visit_expression(&input.left, additional);
-        self.visit_expression(&input.right, additional);
-        Default::default()
-    }
-
     fn visit_call(&mut self, input: &'a CallExpression, additional: &Self::AdditionalInput) -> Self::Output {
         input.arguments.iter().for_each(|expr| {
             self.visit_expression(expr, additional);
@@ -87,42 +118,26 @@ pub trait ExpressionVisitor<'a> {
     fn visit_literal(&mut self, _input: &'a Literal, _additional: &Self::AdditionalInput) -> Self::Output {
         Default::default()
     }
-
-    fn visit_ternary(&mut self, input: &'a TernaryExpression, additional: &Self::AdditionalInput) -> Self::Output {
-        self.visit_expression(&input.condition, additional);
-        self.visit_expression(&input.if_true, additional);
-        self.visit_expression(&input.if_false, additional);
-        Default::default()
-    }
-
-    fn visit_tuple(&mut self, input: &'a TupleExpression, additional: &Self::AdditionalInput) -> Self::Output {
-        input.elements.iter().for_each(|expr| {
-            self.visit_expression(expr, additional);
-        });
-        Default::default()
-    }
-
-    fn visit_unary(&mut self, input: &'a UnaryExpression, additional: &Self::AdditionalInput) -> Self::Output {
-        self.visit_expression(&input.receiver, additional);
-        Default::default()
-    }
 }
 
 /// A Visitor trait for statements in the AST.
 pub trait StatementVisitor<'a>: ExpressionVisitor<'a> {
-    fn visit_statement(&mut self, input: &'a Statement) {
-        match input {
-            Statement::Assign(stmt) => self.visit_assign(stmt),
-            Statement::Block(stmt) => self.visit_block(stmt),
-            Statement::Conditional(stmt) => self.visit_conditional(stmt),
-            Statement::Console(stmt) => self.visit_console(stmt),
-            Statement::Decrement(stmt) => self.visit_decrement(stmt),
-            Statement::Definition(stmt) => self.visit_definition(stmt),
-            Statement::Finalize(stmt) => self.visit_finalize(stmt),
-            Statement::Increment(stmt) => self.visit_increment(stmt),
-            Statement::Iteration(stmt) => self.visit_iteration(stmt),
-            Statement::Return(stmt) => self.visit_return(stmt),
-        }
+    statement_nodes!(statement_visitor);
+
+    fn visit_assert(&mut self, input: &'a AssertStatement) {
+        match &input.variant {
+            AssertVariant::Assert(expr) => {
+                self.visit_expression(expr, &Default::default());
+            }
+            AssertVariant::AssertEq(left, right) => {
+                self.visit_expression(left, &Default::default());
+                self.visit_expression(right, &Default::default());
+            }
+            AssertVariant::AssertNeq(left, right) => {
+                self.visit_expression(left, &Default::default());
+                self.visit_expression(right, &Default::default());
+            }
+        };
     }
 
     fn visit_assign(&mut self, input: &'a AssignStatement) {
@@ -133,6 +148,10 @@ pub trait StatementVisitor<'a>: ExpressionVisitor<'a> {
         input.statements.iter().for_each(|stmt| self.visit_statement(stmt));
     }
 
+    fn visit_cfg(&mut self, input: &'a CfgStatement) {
+        self.visit_statement(&input.statement);
+    }
+
     fn visit_conditional(&mut self, input: &'a ConditionalStatement) {
         self.visit_expression(&input.condition, &Default::default());
         self.visit_block(&input.then);
@@ -141,38 +160,12 @@ pub trait StatementVisitor<'a>: ExpressionVisitor<'a> {
         }
     }
 
-    fn visit_console(&mut self, input: &'a ConsoleStatement) {
-        match &input.function {
-            ConsoleFunction::Assert(expr) => {
-                self.visit_expression(expr, &Default::default());
-            }
-            ConsoleFunction::AssertEq(left, right) => {
-                self.visit_expression(left, &Default::default());
-                self.visit_expression(right, &Default::default());
-            }
-            ConsoleFunction::AssertNeq(left, right) => {
-                self.visit_expression(left, &Default::default());
-                self.visit_expression(right, &Default::default());
-            }
-        };
-    }
-
     fn visit_decrement(&mut self, input: &'a DecrementStatement) {
         self.visit_expression(&input.amount, &Default::default());
         self.visit_expression(&input.index, &Default::default());
         self.visit_identifier(&input.mapping, &Default::default());
     }
 
-    fn visit_definition(&mut self, input: &'a DefinitionStatement) {
-        self.visit_expression(&input.value, &Default::default());
-    }
-
-    fn visit_finalize(&mut self, input: &'a FinalizeStatement) {
-        input.arguments.iter().for_each(|expr| {
-            self.visit_expression(expr, &Default::default());
-        });
-    }
-
     fn visit_increment(&mut self, input: &'a IncrementStatement) {
         self.visit_expression(&input.amount, &Default::default());
         self.visit_expression(&input.index, &Default::default());
@@ -181,12 +174,15 @@ pub trait StatementVisitor<'a>: ExpressionVisitor<'a> {
 
     fn visit_iteration(&mut self, input: &'a IterationStatement) {
         self.visit_expression(&input.start, &Default::default());
-        self.visit_expression(&input.stop, &Default::default());
+        if let Some(stop) = input.stop.as_ref() {
+            self.visit_expression(stop, &Default::default());
+        }
         self.visit_block(&input.block);
     }
 
-    fn visit_return(&mut self, input: &'a ReturnStatement) {
+    fn visit_match(&mut self, input: &'a MatchStatement) {
         self.visit_expression(&input.expression, &Default::default());
+        input.arms.iter().for_each(|arm| self.visit_block(&arm.block));
     }
 }
 
@@ -202,8 +198,12 @@ pub trait ProgramVisitor<'a>: StatementVisitor<'a> {
     }
 
     fn visit_program_scope(&mut self, input: &'a ProgramScope) {
+        input.consts.values().for_each(|const_| self.visit_const(const_));
+
         input.structs.values().for_each(|function| self.visit_struct(function));
 
+        input.enums.values().for_each(|enum_| self.visit_enum(enum_));
+
         input.mappings.values().for_each(|mapping| self.visit_mapping(mapping));
 
         input
@@ -216,8 +216,12 @@ pub trait ProgramVisitor<'a>: StatementVisitor<'a> {
         self.visit_program(input)
     }
 
+    fn visit_const(&mut self, _input: &'a ConstDeclaration) {}
+
     fn visit_struct(&mut self, _input: &'a Struct) {}
 
+    fn visit_enum(&mut self, _input: &'a Enum) {}
+
     fn visit_mapping(&mut self, _input: &'a Mapping) {}
 
     fn visit_function(&mut self, input: &'a Function) {
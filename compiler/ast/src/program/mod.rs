@@ -16,6 +16,9 @@
 
 //! A Leo program consists of import statements and program scopes.
 
+pub mod module;
+pub use module::*;
+
 pub mod program_id;
 pub use program_id::*;
 
@@ -33,6 +36,9 @@ use std::fmt;
 pub struct Program {
     /// A map from import names to import definitions.
     pub imports: IndexMap<Identifier, Program>,
+    /// The modules imported with `import foo;`, whose definitions are merged into the program scope.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modules: Vec<Identifier>,
     /// A map from program names to program scopes.
     pub program_scopes: IndexMap<ProgramId, ProgramScope>,
 }
@@ -42,6 +48,9 @@ impl fmt::Display for Program {
         for (id, _import) in self.imports.iter() {
             writeln!(f, "import {}.leo;", id)?;
         }
+        for id in self.modules.iter() {
+            writeln!(f, "import {};", id)?;
+        }
         for (_, program_scope) in self.program_scopes.iter() {
             program_scope.fmt(f)?;
             writeln!(f,)?;
@@ -55,6 +64,7 @@ impl Default for Program {
     fn default() -> Self {
         Self {
             imports: IndexMap::new(),
+            modules: Vec::new(),
             program_scopes: IndexMap::new(),
         }
     }
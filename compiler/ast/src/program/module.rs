@@ -0,0 +1,41 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! A Leo module is a file of definitions that a program imports with `import foo;`.
+
+use crate::{ConstDeclaration, Enum, Function, Identifier, Mapping, Struct};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+/// Stores the Leo module abstract syntax tree.
+/// A module has no program scope of its own, as its definitions are merged into that of the importing program.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Module {
+    /// The modules imported by the module.
+    pub modules: Vec<Identifier>,
+    /// A map from constant names to constant declarations.
+    pub consts: IndexMap<Identifier, ConstDeclaration>,
+    /// A map from struct names to struct definitions.
+    pub structs: IndexMap<Identifier, Struct>,
+    /// A map from enum names to enum definitions.
+    #[serde(default)]
+    pub enums: IndexMap<Identifier, Enum>,
+    /// A map from mapping names to mapping definitions.
+    pub mappings: IndexMap<Identifier, Mapping>,
+    /// A map from function names to function definitions.
+    pub functions: IndexMap<Identifier, Function>,
+}
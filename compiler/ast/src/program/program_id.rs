@@ -15,6 +15,7 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::Identifier;
+use leo_span::Symbol;
 
 use core::fmt;
 use serde::de::Visitor;
@@ -45,7 +46,7 @@ impl Serialize for ProgramId {
 
         // Load the struct elements into a BTreeMap (to preserve serialized ordering of keys).
         let mut key: BTreeMap<String, String> = BTreeMap::new();
-        key.insert("name".to_string(), self.name.to_string());
+        key.insert("name".to_string(), to_json_string(&self.name)?);
         key.insert("network".to_string(), to_json_string(&self.network)?);
 
         // Convert the serialized object into a string for use as a key.
@@ -76,8 +77,10 @@ impl<'de> Deserialize<'de> for ProgramId {
                 // Convert the serialized string into a BTreeMap to recover ProgramId.
                 let key: BTreeMap<String, String> = to_json_string(value)?;
 
+                // Note that earlier versions serialized the name as a bare string, without its span.
                 let name: Identifier = match key.get("name") {
-                    Some(name) => to_json_string(name)?,
+                    Some(name) => to_json_string(name)
+                        .unwrap_or_else(|_: E| Identifier::new(Symbol::intern(name), Default::default())),
                     None => return Err(E::custom("missing 'name' in serialized ProgramId struct")),
                 };
 
@@ -14,9 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-//! A Leo program scope consists of struct, function, and mapping definitions.
+//! A Leo program scope consists of constant, struct, enum, function, and mapping definitions.
 
-use crate::{Function, Identifier, Mapping, ProgramId, Struct};
+use crate::{ConstDeclaration, Enum, Function, Identifier, IntegerType, Mapping, ProgramId, Struct};
 
 use indexmap::IndexMap;
 use leo_span::Span;
@@ -28,22 +28,40 @@ use std::fmt;
 pub struct ProgramScope {
     /// The program id of the program scope.
     pub program_id: ProgramId,
+    /// A map from constant names to constant declarations.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub consts: IndexMap<Identifier, ConstDeclaration>,
     /// A map from struct names to struct definitions.
     pub structs: IndexMap<Identifier, Struct>,
+    /// A map from enum names to enum definitions.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub enums: IndexMap<Identifier, Enum>,
     /// A map from mapping names to mapping definitions.
     pub mappings: IndexMap<Identifier, Mapping>,
     /// A map from function names to function definitions.
     pub functions: IndexMap<Identifier, Function>,
+    /// The type given to unsuffixed integer literals whose type cannot be inferred, set by `@default_integer_type(..)`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_integer_type: Option<IntegerType>,
     /// The span associated with the program scope.
     pub span: Span,
 }
 
 impl fmt::Display for ProgramScope {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(type_) = self.default_integer_type {
+            writeln!(f, "@default_integer_type({})", type_)?;
+        }
         writeln!(f, "program {} {{", self.program_id)?;
+        for (_, const_) in self.consts.iter() {
+            writeln!(f, "    {}", const_)?;
+        }
         for (_, struct_) in self.structs.iter() {
             writeln!(f, "    {}", struct_)?;
         }
+        for (_, enum_) in self.enums.iter() {
+            writeln!(f, "    {}", enum_)?;
+        }
         for (_, mapping) in self.mappings.iter() {
             writeln!(f, "    {}", mapping)?;
         }
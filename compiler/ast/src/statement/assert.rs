@@ -0,0 +1,55 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Expression, Node, NodeId};
+use leo_span::Span;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A variant of an assert statement.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum AssertVariant {
+    /// An `assert(expr)` statement, asserting that the expression evaluates to true.
+    Assert(Expression),
+    /// An `assert_eq(expr1, expr2)` statement, asserting that the operands are equal.
+    AssertEq(Expression, Expression),
+    /// An `assert_neq(expr1, expr2)` statement, asserting that the operands are not equal.
+    AssertNeq(Expression, Expression),
+}
+
+/// An assert statement, e.g. `assert_eq(a, b);`, which constrains the program to fail if it does not hold.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct AssertStatement {
+    /// The variant of the assert statement.
+    pub variant: AssertVariant,
+    /// The span excluding the semicolon.
+    pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
+}
+
+impl fmt::Display for AssertStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.variant {
+            AssertVariant::Assert(expr) => write!(f, "assert({});", expr),
+            AssertVariant::AssertEq(expr1, expr2) => write!(f, "assert_eq({}, {});", expr1, expr2),
+            AssertVariant::AssertNeq(expr1, expr2) => write!(f, "assert_neq({}, {});", expr1, expr2),
+        }
+    }
+}
+
+crate::simple_node_impl!(AssertStatement);
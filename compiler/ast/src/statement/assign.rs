@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Expression, Node};
+use crate::{Expression, Node, NodeId};
 use leo_span::Span;
 
 use serde::{Deserialize, Serialize};
@@ -30,6 +30,8 @@ pub struct AssignStatement {
     pub value: Expression,
     /// The span, excluding the semicolon.
     pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
 }
 
 impl fmt::Display for AssignStatement {
@@ -14,28 +14,29 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::Expression;
+use crate::{Annotation, Node, NodeId, Statement};
+use leo_span::Span;
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-/// A console logging function to invoke.
+/// A statement that is only compiled when its `@cfg(..)` annotation holds, e.g. `@cfg(test) assert(check(x));`.
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
-pub enum ConsoleFunction {
-    /// A `console.assert(expr)` call to invoke, asserting that the expression evaluates to true.
-    Assert(Expression),
-    /// A `console.assert_eq(expr1, expr2)` call to invoke, asserting that the operands are equal.
-    AssertEq(Expression, Expression),
-    /// A `console.assert_neq(expr1, expr2)` call to invoke, asserting that the operands are not equal.
-    AssertNeq(Expression, Expression),
+pub struct CfgStatement {
+    /// The `@cfg(..)` annotation on the statement.
+    pub cfg: Annotation,
+    /// The annotated statement.
+    pub statement: Box<Statement>,
+    /// The span from the annotation to the end of the statement.
+    pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
 }
 
-impl fmt::Display for ConsoleFunction {
+impl fmt::Display for CfgStatement {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            ConsoleFunction::Assert(expr) => write!(f, "assert({})", expr),
-            ConsoleFunction::AssertEq(expr1, expr2) => write!(f, "assert_eq({}, {})", expr1, expr2),
-            ConsoleFunction::AssertNeq(expr1, expr2) => write!(f, "assert_neq({}, {})", expr1, expr2),
-        }
+        write!(f, "{} {}", self.cfg, self.statement)
     }
 }
+
+crate::simple_node_impl!(CfgStatement);
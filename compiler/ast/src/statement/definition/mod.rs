@@ -14,11 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Expression, Identifier, Node, Type};
+use crate::{Expression, Node, NodeId, Type};
 use leo_span::Span;
 
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use std::{cell::RefCell, fmt};
 
 mod declaration_type;
 pub use declaration_type::*;
@@ -29,20 +29,33 @@ pub struct DefinitionStatement {
     /// What sort of declaration is this? `let` or `const`?.
     pub declaration_type: DeclarationType,
     /// The bindings / variable names to declare.
-    pub variable_name: Identifier,
-    /// The types of the bindings, if specified, or inferred otherwise.
-    pub type_: Type,
+    /// This is either an `Identifier` or a `TupleExpression` of `Identifier`s for a destructuring definition.
+    pub place: Expression,
+    /// The types of the bindings, if annotated.
+    /// The types of unannotated bindings are inferred from the initializer during type checking.
+    pub type_: RefCell<Option<Type>>,
     /// An initializer value for the bindings.
     pub value: Expression,
     /// The span excluding the semicolon.
     pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
+}
+
+impl DefinitionStatement {
+    /// Returns the types of the bindings, if they were annotated or have been inferred.
+    pub fn type_(&self) -> Option<Type> {
+        self.type_.borrow().clone()
+    }
 }
 
 impl fmt::Display for DefinitionStatement {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{} ", self.declaration_type)?;
-        write!(f, "{}", self.variable_name)?;
-        write!(f, ": {}", self.type_)?;
+        write!(f, "{}", self.place)?;
+        if let Some(type_) = self.type_() {
+            write!(f, ": {type_}")?;
+        }
         write!(f, " = {};", self.value)
     }
 }
@@ -14,31 +14,28 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{ConsoleFunction, Node};
+use crate::{Expression, Node, NodeId};
+
 use leo_span::Span;
 
+use core::fmt;
 use serde::{Deserialize, Serialize};
-use std::fmt;
-
-/// A console logging statement like `console.log(...);`.
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct ConsoleStatement {
-    /// The logging function to run.
-    pub function: ConsoleFunction,
-    /// The span excluding the semicolon.
-    pub span: Span,
-}
 
-impl fmt::Display for ConsoleStatement {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "console.{};", self.function)
-    }
+/// An expression statement `Mapping::set(foo, bar, 1);`, evaluated only for its effects.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct ExpressionStatement {
+    /// The expression to be evaluated.
+    pub expression: Expression,
+    /// The span of the expression excluding the semicolon.
+    pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
 }
 
-impl fmt::Debug for ConsoleStatement {
+impl fmt::Display for ExpressionStatement {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "console.{};", self.function)
+        write!(f, "{};", self.expression)
     }
 }
 
-crate::simple_node_impl!(ConsoleStatement);
+crate::simple_node_impl!(ExpressionStatement);
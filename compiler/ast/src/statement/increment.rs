@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Expression, Identifier, Node};
+use crate::{Expression, Identifier, Node, NodeId};
 
 use leo_span::Span;
 
@@ -32,6 +32,8 @@ pub struct IncrementStatement {
     pub amount: Expression,
     /// The span of `increment(foo, bar, 1)` excluding the semicolon.
     pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
 }
 
 impl fmt::Display for IncrementStatement {
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Block, Expression, Identifier, Node, Type, Value};
+use crate::{Block, Expression, Identifier, Node, NodeId, Type, Value};
 
 use leo_span::Span;
 
@@ -22,22 +22,24 @@ use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::fmt;
 
-/// A bounded `for` loop statement `for variable in start .. =? stop block`.
+/// A bounded `for` loop statement `for variable in start .. =? stop block`,
+/// or a `for` loop over the elements of an array `for variable in array block`.
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub struct IterationStatement {
     /// The binding / variable to introduce in the body `block`.
     pub variable: Identifier,
     /// The type of the iteration.
     pub type_: Type,
-    /// The start of the iteration.
+    /// The start of the iteration, or the array whose elements are iterated over if there is no `stop`.
     pub start: Expression,
     /// The concrete value of `start`.
-    #[serde(skip)]
+    #[serde(default, skip_serializing_if = "is_unknown")]
     pub start_value: RefCell<Option<Value>>,
-    /// The end of the iteration, possibly `inclusive`.
-    pub stop: Expression,
+    /// The end of the iteration, possibly `inclusive`, or `None` if the loop iterates over the elements of an array.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop: Option<Expression>,
     /// The concrete value of `stop`.
-    #[serde(skip)]
+    #[serde(default, skip_serializing_if = "is_unknown")]
     pub stop_value: RefCell<Option<Value>>,
     /// Whether `stop` is inclusive or not.
     /// Signified with `=` when parsing.
@@ -46,16 +48,26 @@ pub struct IterationStatement {
     pub block: Block,
     /// The span from `for` to `block`.
     pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
+}
+
+/// Returns `true` if the concrete value of a bound has not been evaluated by type checking.
+fn is_unknown(value: &RefCell<Option<Value>>) -> bool {
+    value.borrow().is_none()
 }
 
 impl fmt::Display for IterationStatement {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let eq = if self.inclusive { "=" } else { "" };
-        write!(
-            f,
-            "for {} in {}..{}{} {}",
-            self.variable, self.start, eq, self.stop, self.block
-        )
+        match &self.stop {
+            Some(stop) => write!(
+                f,
+                "for {} in {}..{}{} {}",
+                self.variable, self.start, eq, stop, self.block
+            ),
+            None => write!(f, "for {} in {} {}", self.variable, self.start, self.block),
+        }
     }
 }
 
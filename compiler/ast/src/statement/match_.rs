@@ -0,0 +1,96 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Block, Expression, Identifier, Node, NodeId};
+use leo_span::Span;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A `match expression { arms }` statement over the value of an enum.
+/// Type checking requires the arms to cover every variant of the enum.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct MatchStatement {
+    /// The enum-typed expression whose value is matched.
+    pub expression: Expression,
+    /// The arms of the match, which are tried in order.
+    pub arms: Vec<MatchArm>,
+    /// The span from `match` to the closing brace.
+    pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
+}
+
+impl fmt::Display for MatchStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "match {} {{", self.expression)?;
+        for arm in self.arms.iter() {
+            writeln!(f, "{}", arm)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+crate::simple_node_impl!(MatchStatement);
+
+/// An arm of a `match` statement, e.g. `State::Idle => { ... }`.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct MatchArm {
+    /// The pattern that the matched value is compared against.
+    pub pattern: MatchPattern,
+    /// The block to evaluate when the pattern matches.
+    pub block: Block,
+    /// The span from the pattern to the end of the block.
+    pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
+}
+
+impl fmt::Display for MatchArm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} => {}", self.pattern, self.block)
+    }
+}
+
+crate::simple_node_impl!(MatchArm);
+
+/// The pattern of a `match` arm.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum MatchPattern {
+    /// A variant of an enum, e.g. `State::Idle`, which matches exactly that value.
+    Variant(Identifier, Identifier),
+    /// The wildcard `_`, which matches any value.
+    Wildcard(Span),
+}
+
+impl MatchPattern {
+    /// Returns the span of the pattern.
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Variant(enum_, variant) => enum_.span + variant.span,
+            Self::Wildcard(span) => *span,
+        }
+    }
+}
+
+impl fmt::Display for MatchPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Variant(enum_, variant) => write!(f, "{}::{}", enum_, variant),
+            Self::Wildcard(_) => write!(f, "_"),
+        }
+    }
+}
@@ -14,24 +14,30 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+pub mod assert;
+pub use assert::*;
+
 pub mod assign;
 pub use assign::*;
 
 pub mod block;
 pub use block::*;
 
+pub mod cfg;
+pub use cfg::*;
+
 pub mod conditional;
 pub use conditional::*;
 
-pub mod console;
-pub use console::*;
-
 pub mod decrement;
 pub use decrement::*;
 
 pub mod definition;
 pub use definition::*;
 
+pub mod expression;
+pub use expression::*;
+
 pub mod finalize;
 pub use finalize::*;
 
@@ -41,10 +47,13 @@ pub use increment::*;
 pub mod iteration;
 pub use iteration::*;
 
+pub mod match_;
+pub use match_::*;
+
 pub mod return_;
 pub use return_::*;
 
-use crate::Node;
+use crate::{Node, NodeId};
 
 use leo_span::Span;
 
@@ -54,34 +63,41 @@ use std::fmt;
 /// Program statement that defines some action (or expression) to be carried out.
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub enum Statement {
+    /// An assert statement.
+    Assert(AssertStatement),
     /// An assignment statement.
     Assign(Box<AssignStatement>),
     /// A block statement.
     Block(Block),
+    /// A statement annotated with `@cfg(..)`.
+    Cfg(CfgStatement),
     /// An `if` statement.
     Conditional(ConditionalStatement),
-    /// A console logging statement.
-    Console(ConsoleStatement),
     /// A decrement statement.
     Decrement(DecrementStatement),
     /// A binding or set of bindings / variables to declare.
     Definition(DefinitionStatement),
+    /// An expression evaluated for its effects, e.g. `Mapping::set(foo, bar, 1);`.
+    Expression(ExpressionStatement),
     /// A finalize statement.
     Finalize(FinalizeStatement),
     /// An increment statement.
     Increment(IncrementStatement),
     /// A `for` statement.
     Iteration(Box<IterationStatement>),
+    /// A `match` statement.
+    Match(MatchStatement),
     /// A return statement `return expr;`.
     Return(ReturnStatement),
 }
 
 impl Statement {
     /// Returns a dummy statement made from an empty block `{}`.
-    pub fn dummy(span: Span) -> Self {
+    pub fn dummy(span: Span, id: NodeId) -> Self {
         Self::Block(Block {
             statements: Vec::new(),
             span,
+            id,
         })
     }
 }
@@ -89,15 +105,18 @@ impl Statement {
 impl fmt::Display for Statement {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            Statement::Assert(x) => x.fmt(f),
             Statement::Assign(x) => x.fmt(f),
             Statement::Block(x) => x.fmt(f),
+            Statement::Cfg(x) => x.fmt(f),
             Statement::Conditional(x) => x.fmt(f),
-            Statement::Console(x) => x.fmt(f),
             Statement::Decrement(x) => x.fmt(f),
             Statement::Definition(x) => x.fmt(f),
+            Statement::Expression(x) => x.fmt(f),
             Statement::Finalize(x) => x.fmt(f),
             Statement::Increment(x) => x.fmt(f),
             Statement::Iteration(x) => x.fmt(f),
+            Statement::Match(x) => x.fmt(f),
             Statement::Return(x) => x.fmt(f),
         }
     }
@@ -107,15 +126,18 @@ impl Node for Statement {
     fn span(&self) -> Span {
         use Statement::*;
         match self {
+            Assert(n) => n.span(),
             Assign(n) => n.span(),
             Block(n) => n.span(),
+            Cfg(n) => n.span(),
             Conditional(n) => n.span(),
-            Console(n) => n.span(),
             Decrement(n) => n.span(),
             Definition(n) => n.span(),
+            Expression(n) => n.span(),
             Finalize(n) => n.span(),
             Increment(n) => n.span(),
             Iteration(n) => n.span(),
+            Match(n) => n.span(),
             Return(n) => n.span(),
         }
     }
@@ -123,16 +145,57 @@ impl Node for Statement {
     fn set_span(&mut self, span: Span) {
         use Statement::*;
         match self {
+            Assert(n) => n.set_span(span),
             Assign(n) => n.set_span(span),
             Block(n) => n.set_span(span),
+            Cfg(n) => n.set_span(span),
             Conditional(n) => n.set_span(span),
-            Console(n) => n.set_span(span),
             Decrement(n) => n.set_span(span),
             Definition(n) => n.set_span(span),
+            Expression(n) => n.set_span(span),
             Finalize(n) => n.set_span(span),
             Increment(n) => n.set_span(span),
             Iteration(n) => n.set_span(span),
+            Match(n) => n.set_span(span),
             Return(n) => n.set_span(span),
         }
     }
+
+    fn id(&self) -> NodeId {
+        use Statement::*;
+        match self {
+            Assert(n) => n.id(),
+            Assign(n) => n.id(),
+            Block(n) => n.id(),
+            Cfg(n) => n.id(),
+            Conditional(n) => n.id(),
+            Decrement(n) => n.id(),
+            Definition(n) => n.id(),
+            Expression(n) => n.id(),
+            Finalize(n) => n.id(),
+            Increment(n) => n.id(),
+            Iteration(n) => n.id(),
+            Match(n) => n.id(),
+            Return(n) => n.id(),
+        }
+    }
+
+    fn set_id(&mut self, id: NodeId) {
+        use Statement::*;
+        match self {
+            Assert(n) => n.set_id(id),
+            Assign(n) => n.set_id(id),
+            Block(n) => n.set_id(id),
+            Cfg(n) => n.set_id(id),
+            Conditional(n) => n.set_id(id),
+            Decrement(n) => n.set_id(id),
+            Definition(n) => n.set_id(id),
+            Expression(n) => n.set_id(id),
+            Finalize(n) => n.set_id(id),
+            Increment(n) => n.set_id(id),
+            Iteration(n) => n.set_id(id),
+            Match(n) => n.set_id(id),
+            Return(n) => n.set_id(id),
+        }
+    }
 }
@@ -0,0 +1,43 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{simple_node_impl, Identifier, IntegerType, Node, NodeId};
+
+use leo_span::Span;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A const parameter of a generic struct, e.g. `const N: u32` in `struct Buffer<const N: u32> { data: [u8; N] }`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConstParameter {
+    /// The name of the const parameter.
+    pub identifier: Identifier,
+    /// The unsigned integer type of the const arguments that the parameter may be instantiated with.
+    pub type_: IntegerType,
+    /// A span locating where the const parameter occurred in the source.
+    pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
+}
+
+simple_node_impl!(ConstParameter);
+
+impl fmt::Display for ConstParameter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "const {}: {}", self.identifier, self.type_)
+    }
+}
@@ -14,11 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+pub mod const_parameter;
+pub use const_parameter::*;
+
 pub mod member;
 pub use member::*;
 
-use crate::{Identifier, Node};
-use leo_span::{Span, Symbol};
+use crate::{Identifier, IntegerType, Node, NodeBuilder, NodeId, Size, Type};
+use leo_span::{sym, Span, Symbol};
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -33,13 +36,22 @@ use std::fmt;
 pub struct Struct {
     /// The name of the type in the type system in this module.
     pub identifier: Identifier,
+    /// The const parameters of a generic struct, e.g. `const N: u32` in `struct Buffer<const N: u32> { ... }`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub const_parameters: Vec<ConstParameter>,
     /// The fields, constant variables, and functions of this structure.
     pub members: Vec<Member>,
     /// Was this a `record Foo { ... }`?
     /// If so, it wasn't a struct.
     pub is_record: bool,
+    /// The names of the members that were injected by the compiler, rather than declared by the user.
+    /// For records, these are the required `owner` and `gates` members, when they are not declared.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub injected_members: Vec<Symbol>,
     /// The entire span of the struct definition.
     pub span: Span,
+    /// The ID of the node.
+    pub id: NodeId,
 }
 
 impl PartialEq for Struct {
@@ -55,6 +67,59 @@ impl Struct {
     pub fn name(&self) -> Symbol {
         self.identifier.name
     }
+
+    /// Returns `true` if the struct has const parameters, so that it is instantiated for the const arguments it is used with.
+    pub fn is_generic(&self) -> bool {
+        !self.const_parameters.is_empty()
+    }
+
+    /// Returns the members of the struct with each const parameter replaced by the corresponding const argument,
+    /// if the argument is an integer literal.
+    pub fn instantiate_members(&self, const_arguments: &[Size]) -> Vec<Member> {
+        let sizes = self
+            .const_parameters
+            .iter()
+            .zip(const_arguments)
+            .filter_map(|(parameter, argument)| Some((parameter.identifier.name, argument.value()?)))
+            .collect();
+        self.members
+            .iter()
+            .map(|member| Member {
+                identifier: member.identifier,
+                type_: member.type_.substitute_sizes(&sizes),
+            })
+            .collect()
+    }
+
+    /// Canonicalizes a record, so that its members begin with the required `owner: address` and `gates: u64` members.
+    /// Required members that are not declared are injected and recorded in `injected_members`.
+    pub fn inject_record_members(&mut self, node_builder: &NodeBuilder) {
+        if !self.is_record {
+            return;
+        }
+        let span = self.identifier.span;
+        let mut required = Vec::with_capacity(2);
+        for (name, type_) in [
+            (sym::owner, Type::Address),
+            (sym::gates, Type::Integer(IntegerType::U64)),
+        ] {
+            match self.members.iter().position(|member| member.name() == name) {
+                Some(index) => required.push(self.members.remove(index)),
+                None => {
+                    required.push(Member {
+                        identifier: Identifier {
+                            name,
+                            span,
+                            id: node_builder.next_id(),
+                        },
+                        type_,
+                    });
+                    self.injected_members.push(name);
+                }
+            }
+        }
+        self.members.splice(0..0, required);
+    }
 }
 
 impl fmt::Debug for Struct {
@@ -66,8 +131,17 @@ impl fmt::Debug for Struct {
 impl fmt::Display for Struct {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.write_str(if self.is_record { "record" } else { "struct" })?;
-        writeln!(f, " {} {{ ", self.identifier)?;
-        for field in self.members.iter() {
+        write!(f, " {}", self.identifier)?;
+        if self.is_generic() {
+            let parameters = self.const_parameters.iter().map(|parameter| parameter.to_string());
+            write!(f, "<{}>", parameters.collect::<Vec<_>>().join(", "))?;
+        }
+        writeln!(f, " {{ ")?;
+        for field in self
+            .members
+            .iter()
+            .filter(|member| !self.injected_members.contains(&member.name()))
+        {
             writeln!(f, "    {}", field)?;
         }
         write!(f, "}}")
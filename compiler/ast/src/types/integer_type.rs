@@ -17,7 +17,7 @@
 use leo_span::{sym, Symbol};
 
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use std::{fmt, str::FromStr};
 
 /// Explicit integer type.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -59,6 +59,27 @@ impl IntegerType {
     }
 }
 
+impl FromStr for IntegerType {
+    type Err = String;
+
+    /// Parses an integer type from its name, e.g. `u32`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "u8" => Self::U8,
+            "u16" => Self::U16,
+            "u32" => Self::U32,
+            "u64" => Self::U64,
+            "u128" => Self::U128,
+            "i8" => Self::I8,
+            "i16" => Self::I16,
+            "i32" => Self::I32,
+            "i64" => Self::I64,
+            "i128" => Self::I128,
+            _ => return Err(format!("`{}` is not an integer type", s)),
+        })
+    }
+}
+
 impl fmt::Display for IntegerType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -20,6 +20,12 @@ pub use integer_type::*;
 pub mod mapping;
 pub use mapping::*;
 
+pub mod size;
+pub use size::*;
+
+pub mod struct_instance;
+pub use struct_instance::*;
+
 pub mod tuple;
 pub use tuple::*;
 
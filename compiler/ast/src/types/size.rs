@@ -0,0 +1,70 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Identifier;
+
+use leo_span::Symbol;
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::{fmt, num::NonZeroUsize};
+
+/// The size of an array type, e.g. the `4` in `[u8; 4]` or the `N` in `[u8; N]`.
+/// A named size is a program constant, whose value is substituted for it by monomorphization.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Size {
+    /// A size given by an integer literal.
+    Literal(NonZeroUsize),
+    /// A size given by the name of a constant.
+    Constant(Identifier),
+}
+
+impl Size {
+    /// Returns the value of the size, if it is a literal.
+    pub fn value(&self) -> Option<NonZeroUsize> {
+        match self {
+            Size::Literal(value) => Some(*value),
+            Size::Constant(_) => None,
+        }
+    }
+
+    /// Returns the size with its name replaced by its value in `sizes`, if any.
+    pub fn substitute(&self, sizes: &IndexMap<Symbol, NonZeroUsize>) -> Size {
+        match self {
+            Size::Constant(name) => sizes.get(&name.name).map_or(*self, |value| Size::Literal(*value)),
+            Size::Literal(_) => *self,
+        }
+    }
+
+    /// Returns the value of the size.
+    /// Panics if the size is named, so this may only be used once monomorphization has substituted the values of named sizes.
+    pub fn get(&self) -> usize {
+        match self {
+            Size::Literal(value) => value.get(),
+            Size::Constant(name) => unreachable!("Monomorphization substitutes the value of the size `{name}`."),
+        }
+    }
+}
+
+impl fmt::Display for Size {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Size::Literal(value) => write!(f, "{}", value),
+            Size::Constant(name) => write!(f, "{}", name),
+        }
+    }
+}
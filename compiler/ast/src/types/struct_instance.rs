@@ -0,0 +1,37 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Identifier, Size};
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// An instance of a generic struct type, e.g. `Buffer<4>` or `Buffer<N>`.
+/// Monomorphization replaces each instance by a struct whose const parameters are replaced by the arguments.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StructInstance {
+    /// The name of the generic struct.
+    pub identifier: Identifier,
+    /// The const arguments of the instance, one for each const parameter of the struct.
+    pub const_arguments: Vec<Size>,
+}
+
+impl fmt::Display for StructInstance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let arguments = self.const_arguments.iter().map(|argument| argument.to_string());
+        write!(f, "{}<{}>", self.identifier, arguments.collect::<Vec<_>>().join(", "))
+    }
+}
@@ -14,10 +14,13 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Identifier, IntegerType, MappingType, Tuple};
+use crate::{Identifier, IntegerType, MappingType, Member, Size, Struct, StructInstance, Tuple};
 
+use leo_span::Symbol;
+
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use std::{fmt, num::NonZeroUsize};
 
 /// Explicit type used for defining a variable or expression type
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -25,6 +28,8 @@ pub enum Type {
     // Data types
     /// The `address` type.
     Address,
+    /// A static array of a fixed, non-zero length.
+    Array(Box<Type>, Size),
     /// The `bool` type.
     Boolean,
     /// The `field` type.
@@ -39,8 +44,13 @@ pub enum Type {
     Mapping(MappingType),
     /// The `scalar` type.
     Scalar,
-    /// The `string` type.
-    String,
+    /// The `signature` type.
+    Signature,
+    /// The `string` type, along with the length of the string in bytes.
+    /// The length of an annotated `string` is unknown, and is inferred from the value it annotates.
+    String(Option<usize>),
+    /// An instance of a generic struct type, e.g. `Buffer<4>`.
+    StructInstance(StructInstance),
     /// A static tuple of at least one type.
     Tuple(Tuple),
     /// The `unit` type.
@@ -63,8 +73,13 @@ impl Type {
             | (Type::Field, Type::Field)
             | (Type::Group, Type::Group)
             | (Type::Scalar, Type::Scalar)
-            | (Type::String, Type::String)
+            | (Type::Signature, Type::Signature)
             | (Type::Unit, Type::Unit) => true,
+            // A string of unknown length is equal to a string of any length.
+            (Type::String(left), Type::String(right)) => left.is_none() || right.is_none() || left == right,
+            (Type::Array(left, left_length), Type::Array(right, right_length)) => {
+                left_length == right_length && left.eq_flat(right)
+            }
             (Type::Integer(left), Type::Integer(right)) => left.eq(right),
             (Type::Mapping(left), Type::Mapping(right)) => {
                 left.key.eq_flat(&right.key) && left.value.eq_flat(&right.value)
@@ -74,15 +89,106 @@ impl Type {
                 .zip(right.iter())
                 .all(|(left_type, right_type)| left_type.eq_flat(right_type)),
             (Type::Identifier(left), Type::Identifier(right)) => left.matches(right),
+            (Type::StructInstance(left), Type::StructInstance(right)) => {
+                left.identifier.matches(&right.identifier) && left.const_arguments == right.const_arguments
+            }
             _ => false,
         }
     }
+
+    ///
+    /// Returns the type with each type parameter replaced by the type that it is instantiated with, if any.
+    ///
+    pub fn substitute(&self, substitution: &IndexMap<Symbol, Type>) -> Type {
+        match self {
+            Type::Identifier(identifier) => substitution
+                .get(&identifier.name)
+                .cloned()
+                .unwrap_or_else(|| self.clone()),
+            Type::Array(element_type, length) => Type::Array(Box::new(element_type.substitute(substitution)), *length),
+            Type::Tuple(tuple) => Type::Tuple(Tuple(
+                tuple.0.iter().map(|type_| type_.substitute(substitution)).collect(),
+            )),
+            _ => self.clone(),
+        }
+    }
+
+    ///
+    /// Returns the type with each named size replaced by its value in `sizes`, if any.
+    ///
+    pub fn substitute_sizes(&self, sizes: &IndexMap<Symbol, NonZeroUsize>) -> Type {
+        match self {
+            Type::Array(element_type, length) => {
+                Type::Array(Box::new(element_type.substitute_sizes(sizes)), length.substitute(sizes))
+            }
+            Type::StructInstance(instance) => Type::StructInstance(StructInstance {
+                identifier: instance.identifier,
+                const_arguments: instance
+                    .const_arguments
+                    .iter()
+                    .map(|argument| argument.substitute(sizes))
+                    .collect(),
+            }),
+            Type::Tuple(tuple) => Type::Tuple(Tuple(
+                tuple.0.iter().map(|type_| type_.substitute_sizes(sizes)).collect(),
+            )),
+            _ => self.clone(),
+        }
+    }
+
+    ///
+    /// Returns a human-friendly rendering of the type, e.g. for hover text and type mismatch diagnostics.
+    ///
+    /// Struct and record types are rendered with a summary of their members, e.g. `struct Foo { a: u8, b: Bar }`.
+    /// `lookup_struct` resolves the name of a struct to its definition, such as `SymbolTable::lookup_struct`.
+    /// The types of members, and structs that cannot be resolved, are rendered by name only.
+    ///
+    pub fn display_friendly<'a>(&self, lookup_struct: &dyn Fn(Symbol) -> Option<&'a Struct>) -> String {
+        match self {
+            Type::Identifier(identifier) => match lookup_struct(identifier.name) {
+                Some(struct_) => {
+                    let members = struct_
+                        .members
+                        .iter()
+                        .map(|Member { identifier, type_ }| format!("{}: {}", identifier, type_))
+                        .collect::<Vec<_>>();
+                    let kind = if struct_.is_record { "record" } else { "struct" };
+                    format!("{} {} {{ {} }}", kind, identifier, members.join(", "))
+                }
+                None => self.to_string(),
+            },
+            Type::StructInstance(instance) => match lookup_struct(instance.identifier.name) {
+                Some(struct_) => {
+                    let members = struct_
+                        .instantiate_members(&instance.const_arguments)
+                        .iter()
+                        .map(|Member { identifier, type_ }| format!("{}: {}", identifier, type_))
+                        .collect::<Vec<_>>();
+                    let kind = if struct_.is_record { "record" } else { "struct" };
+                    format!("{} {} {{ {} }}", kind, instance, members.join(", "))
+                }
+                None => self.to_string(),
+            },
+            Type::Array(element_type, length) => {
+                format!("[{}; {}]", element_type.display_friendly(lookup_struct), length)
+            }
+            Type::Tuple(tuple) => {
+                let elements = tuple
+                    .iter()
+                    .map(|type_| type_.display_friendly(lookup_struct))
+                    .collect::<Vec<_>>();
+                format!("({})", elements.join(", "))
+            }
+            _ => self.to_string(),
+        }
+    }
 }
 
 impl fmt::Display for Type {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Type::Address => write!(f, "address"),
+            Type::Array(ref element_type, length) => write!(f, "[{}; {}]", element_type, length),
             Type::Boolean => write!(f, "boolean"),
             Type::Field => write!(f, "field"),
             Type::Group => write!(f, "group"),
@@ -90,7 +196,10 @@ impl fmt::Display for Type {
             Type::Integer(ref integer_type) => write!(f, "{}", integer_type),
             Type::Mapping(ref mapping_type) => write!(f, "{}", mapping_type),
             Type::Scalar => write!(f, "scalar"),
-            Type::String => write!(f, "string"),
+            Type::Signature => write!(f, "signature"),
+            Type::String(None) => write!(f, "string"),
+            Type::String(Some(length)) => write!(f, "string of length {}", length),
+            Type::StructInstance(ref instance) => write!(f, "{}", instance),
             Type::Tuple(ref tuple) => write!(f, "{}", tuple),
             Type::Unit => write!(f, "()"),
             Type::Err => write!(f, "error"),
@@ -0,0 +1,121 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The evaluation of operations on booleans and integers.
+//!
+//! These are shared by the constant evaluator of the compiler and by `leo-interpreter`, so that both agree on
+//! the result of every operation. They do not depend on snarkVM; fields, groups, and scalars are only evaluated
+//! by the interpreter.
+
+use crate::{BinaryOperation, IntegerType, Type, UnaryOperation, Value, WideInteger};
+
+use leo_errors::Result;
+use leo_span::Span;
+
+impl Value {
+    /// Applies a binary operation to two booleans or integers, giving the result the span `span`.
+    /// Returns `None` if the operation is not defined on the operands, or if it divides by zero.
+    /// Errors if the operation fails on overflow and its result does not fit in its type.
+    pub fn evaluate_binary(self, op: BinaryOperation, other: Self, span: Span) -> Result<Option<Self>> {
+        use BinaryOperation::*;
+
+        match (Type::from(&self), Type::from(&other)) {
+            // Logical operations are only defined on booleans.
+            (Type::Boolean, Type::Boolean) => match op {
+                And | BitwiseAnd => self.bitand(other, span).map(Some),
+                Or | BitwiseOr => self.bitor(other, span).map(Some),
+                Nand => self.bitand(other, span)?.not(span).map(Some),
+                Nor => self.bitor(other, span)?.not(span).map(Some),
+                Xor => self.xor(other, span).map(Some),
+                Eq => self.eq(other, span).map(Some),
+                Neq => self.eq(other, span)?.not(span).map(Some),
+                _ => Ok(None),
+            },
+            // Exponentiation and shifts take a magnitude as their right operand.
+            (Type::Integer(_), Type::Integer(IntegerType::U8 | IntegerType::U16 | IntegerType::U32))
+                if matches!(op, Pow | PowWrapped | Shl | ShlWrapped | Shr | ShrWrapped) =>
+            {
+                match op {
+                    Pow => self.pow(other, span).map(Some),
+                    PowWrapped => self.pow_wrapped(other, span).map(Some),
+                    Shl => self.shl(other, span).map(Some),
+                    ShlWrapped => self.shl_wrapped(other, span).map(Some),
+                    Shr => self.shr(other, span).map(Some),
+                    _ => self.shr_wrapped(other, span).map(Some),
+                }
+            }
+            // All other operations require both operands to be of the same integer type.
+            (Type::Integer(left_type), Type::Integer(right_type)) if left_type == right_type => {
+                // Division by zero is an error at runtime, not an overflow, so it is left to the caller.
+                if matches!(op, Div | DivWrapped | Mod | Rem | RemWrapped)
+                    && WideInteger::from_value(&other) == WideInteger::parse("0")
+                {
+                    return Ok(None);
+                }
+                match op {
+                    Add => self.add(other, span).map(Some),
+                    AddWrapped => self.add_wrapped(other, span).map(Some),
+                    Sub => self.sub(other, span).map(Some),
+                    SubWrapped => self.sub_wrapped(other, span).map(Some),
+                    Mul => self.mul(other, span).map(Some),
+                    MulWrapped => self.mul_wrapped(other, span).map(Some),
+                    Div => self.div(other, span).map(Some),
+                    DivWrapped => self.div_wrapped(other, span).map(Some),
+                    // The remainder is computed from the quotient, since there are no constant operations for it.
+                    // Note that `mod` is only defined on unsigned integers, for which it coincides with `%`.
+                    Mod | Rem => {
+                        let quotient = self.clone().div(other.clone(), span)?;
+                        self.sub(quotient.mul(other, span)?, span).map(Some)
+                    }
+                    RemWrapped => {
+                        let quotient = self.clone().div_wrapped(other.clone(), span)?;
+                        self.sub_wrapped(quotient.mul_wrapped(other, span)?, span).map(Some)
+                    }
+                    BitwiseAnd => self.bitand(other, span).map(Some),
+                    BitwiseOr => self.bitor(other, span).map(Some),
+                    Xor => self.xor(other, span).map(Some),
+                    Eq => self.eq(other, span).map(Some),
+                    Neq => self.eq(other, span)?.not(span).map(Some),
+                    Lt => self.lt(other, span).map(Some),
+                    Lte => self.le(other, span).map(Some),
+                    Gt => self.gt(other, span).map(Some),
+                    Gte => self.ge(other, span).map(Some),
+                    _ => Ok(None),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Applies a unary operation to a boolean or an integer, giving the result the span `span`.
+    /// Returns `None` if the operation is not defined on the operand.
+    /// Errors if the operation fails on overflow and its result does not fit in its type.
+    pub fn evaluate_unary(self, op: UnaryOperation, span: Span) -> Result<Option<Self>> {
+        match (op, Type::from(&self)) {
+            (UnaryOperation::Not, Type::Boolean | Type::Integer(_)) => self.not(span).map(Some),
+            (UnaryOperation::Abs, Type::Integer(type_)) if type_.is_signed() => self.abs(span).map(Some),
+            (UnaryOperation::AbsWrapped, Type::Integer(type_)) if type_.is_signed() => self.abs_wrapped(span).map(Some),
+            (UnaryOperation::Negate, Type::Integer(type_)) if type_.is_signed() => self.neg(span).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Casts an integer to `integer_type`, giving the result the span `span`.
+    /// Returns `None` if the value is not an integer or does not fit in `integer_type`.
+    pub fn cast_integer(&self, integer_type: IntegerType, span: Span) -> Option<Self> {
+        WideInteger::from_value(self)?.to_value(integer_type, span)
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{IntegerType, Value};
+
+use leo_span::Span;
+
+use std::fmt;
+
+/// An integer wide enough to hold the value of every Leo integer type, along with the result of
+/// any checked operation on such values, whether or not that result fits in the operands' type.
+///
+/// The value is stored as a sign and a 128-bit magnitude, so it ranges over `-u128::MAX..=u128::MAX`.
+/// Operations whose results fall outside of that range return `None`; such results cannot fit in any integer type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct WideInteger {
+    /// Whether the value is negative. Zero is never negative.
+    negative: bool,
+    /// The absolute value.
+    magnitude: u128,
+}
+
+impl WideInteger {
+    fn new(negative: bool, magnitude: u128) -> Self {
+        Self {
+            negative: negative && magnitude != 0,
+            magnitude,
+        }
+    }
+
+    /// Parses a decimal integer with an optional leading `-`, as written in an integer literal.
+    pub fn parse(string: &str) -> Option<Self> {
+        let (negative, digits) = match string.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, string),
+        };
+        // `u128::from_str` accepts a leading `+`, which is not valid in a literal.
+        if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+        Some(Self::new(negative, digits.parse().ok()?))
+    }
+
+    /// Returns the value of an integer `Value`, or `None` if it is not an integer.
+    pub fn from_value(value: &Value) -> Option<Self> {
+        Some(match value {
+            Value::U8(v, _) => Self::new(false, *v as u128),
+            Value::U16(v, _) => Self::new(false, *v as u128),
+            Value::U32(v, _) => Self::new(false, *v as u128),
+            Value::U64(v, _) => Self::new(false, *v as u128),
+            Value::U128(v, _) => Self::new(false, *v),
+            Value::I8(v, _) => Self::new(*v < 0, v.unsigned_abs() as u128),
+            Value::I16(v, _) => Self::new(*v < 0, v.unsigned_abs() as u128),
+            Value::I32(v, _) => Self::new(*v < 0, v.unsigned_abs() as u128),
+            Value::I64(v, _) => Self::new(*v < 0, v.unsigned_abs() as u128),
+            Value::I128(v, _) => Self::new(*v < 0, v.unsigned_abs()),
+            _ => return None,
+        })
+    }
+
+    /// Returns a non-negative value.
+    pub fn from_magnitude(magnitude: u128) -> Self {
+        Self::new(false, magnitude)
+    }
+
+    /// Returns `true` if the value is within the range of `integer_type`.
+    pub fn fits(&self, integer_type: IntegerType) -> bool {
+        let (min_magnitude, max) = match integer_type {
+            IntegerType::U8 => (0, u8::MAX as u128),
+            IntegerType::U16 => (0, u16::MAX as u128),
+            IntegerType::U32 => (0, u32::MAX as u128),
+            IntegerType::U64 => (0, u64::MAX as u128),
+            IntegerType::U128 => (0, u128::MAX),
+            IntegerType::I8 => (i8::MIN.unsigned_abs() as u128, i8::MAX as u128),
+            IntegerType::I16 => (i16::MIN.unsigned_abs() as u128, i16::MAX as u128),
+            IntegerType::I32 => (i32::MIN.unsigned_abs() as u128, i32::MAX as u128),
+            IntegerType::I64 => (i64::MIN.unsigned_abs() as u128, i64::MAX as u128),
+            IntegerType::I128 => (i128::MIN.unsigned_abs(), i128::MAX as u128),
+        };
+        match self.negative {
+            true => self.magnitude <= min_magnitude,
+            false => self.magnitude <= max,
+        }
+    }
+
+    /// Converts the value into a `Value` of type `integer_type`, or returns `None` if it does not fit.
+    pub fn to_value(self, integer_type: IntegerType, span: Span) -> Option<Value> {
+        if !self.fits(integer_type) {
+            return None;
+        }
+        // Signed values are reassembled from their two's complement, which is exact for every value that fits.
+        let signed = match self.negative {
+            true => self.magnitude.wrapping_neg() as i128,
+            false => self.magnitude as i128,
+        };
+        Some(match integer_type {
+            IntegerType::U8 => Value::U8(self.magnitude as u8, span),
+            IntegerType::U16 => Value::U16(self.magnitude as u16, span),
+            IntegerType::U32 => Value::U32(self.magnitude as u32, span),
+            IntegerType::U64 => Value::U64(self.magnitude as u64, span),
+            IntegerType::U128 => Value::U128(self.magnitude, span),
+            IntegerType::I8 => Value::I8(signed as i8, span),
+            IntegerType::I16 => Value::I16(signed as i16, span),
+            IntegerType::I32 => Value::I32(signed as i32, span),
+            IntegerType::I64 => Value::I64(signed as i64, span),
+            IntegerType::I128 => Value::I128(signed, span),
+        })
+    }
+
+    /// Returns the value as an exponent or shift amount, if it is small enough to be one.
+    pub fn to_u32(self) -> Option<u32> {
+        match self.negative {
+            true => None,
+            false => u32::try_from(self.magnitude).ok(),
+        }
+    }
+
+    pub fn negate(self) -> Self {
+        Self::new(!self.negative, self.magnitude)
+    }
+
+    pub fn abs(self) -> Self {
+        Self::new(false, self.magnitude)
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        if self.negative == other.negative {
+            return Some(Self::new(self.negative, self.magnitude.checked_add(other.magnitude)?));
+        }
+        // The operands have opposite signs, so the result takes the sign of the operand with the larger magnitude.
+        Some(match self.magnitude >= other.magnitude {
+            true => Self::new(self.negative, self.magnitude - other.magnitude),
+            false => Self::new(other.negative, other.magnitude - self.magnitude),
+        })
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(other.negate())
+    }
+
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        Some(Self::new(
+            self.negative != other.negative,
+            self.magnitude.checked_mul(other.magnitude)?,
+        ))
+    }
+
+    /// Divides, rounding towards zero. Returns `None` when dividing by zero.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        Some(Self::new(
+            self.negative != other.negative,
+            self.magnitude.checked_div(other.magnitude)?,
+        ))
+    }
+
+    pub fn checked_pow(self, exponent: u32) -> Option<Self> {
+        Some(Self::new(
+            self.negative && exponent % 2 == 1,
+            self.magnitude.checked_pow(exponent)?,
+        ))
+    }
+}
+
+impl fmt::Display for WideInteger {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.negative {
+            true => write!(f, "-{}", self.magnitude),
+            false => write!(f, "{}", self.magnitude),
+        }
+    }
+}
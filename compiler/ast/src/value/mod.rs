@@ -14,17 +14,24 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{GroupLiteral, Identifier, IntegerType, Literal, Type};
+use crate::{GroupLiteral, Identifier, IntegerType, Literal, NodeId, Type};
 
 use leo_errors::{type_name, FlattenError, LeoError, Result};
 use leo_span::{Span, Symbol};
 
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use std::{
     fmt::Display,
+    num::NonZeroUsize,
     ops::{BitAnd, BitOr, BitXor, Not},
 };
 
+mod evaluate;
+
+mod integer;
+pub use integer::*;
+
 // TODO: Consider refactoring this module to use the console implementations from snarkVM.
 
 // This is temporary since the currently unused code is used in constant folding.
@@ -70,9 +77,7 @@ macro_rules! implement_const_unary {
             l: $logic:expr
         ]),+]
     ) => {
-        // TODO: This is temporary since the currently unused code is used in constant folding.
-        #[allow(dead_code)]
-        pub(crate) fn $name(self, span: Span) -> Result<Self> {
+        pub fn $name(self, span: Span) -> Result<Self> {
             use Value::*;
 
             match self {
@@ -156,9 +161,7 @@ macro_rules! implement_const_binary {
             logic: $logic:expr
         ]),+]
     ) => {
-        // This is temporary since the currently unused code is used in constant folding.
-        #[allow(dead_code)]
-        pub(crate) fn $name(self, other: Self, span: Span) -> Result<Self> {
+        pub fn $name(self, other: Self, span: Span) -> Result<Self> {
             use Value::*;
 
             match (self, other) {
@@ -178,7 +181,7 @@ macro_rules! implement_const_binary {
     };
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Value {
     Input(Type, Identifier),
     Address(String, Span),
@@ -201,9 +204,8 @@ pub enum Value {
 }
 
 impl Value {
-    // TODO: This is temporary since the currently unused code is used in constant folding.
-    #[allow(dead_code)]
-    pub(crate) fn is_supported_const_fold_type(&self) -> bool {
+    /// Returns `true` if the value supports constant operations.
+    pub fn is_supported_const_fold_type(&self) -> bool {
         use Value::*;
         matches!(
             self,
@@ -221,6 +223,15 @@ impl Value {
         )
     }
 
+    /// Returns the value as a size, if it is a positive integer.
+    pub fn to_size(&self) -> Option<NonZeroUsize> {
+        if !matches!(Type::from(self), Type::Integer(_)) {
+            return None;
+        }
+        let value = u128::try_from(self).ok()?;
+        NonZeroUsize::new(usize::try_from(value).ok()?)
+    }
+
     implement_const_unary!(
         @overflowing
         name: abs,
@@ -859,7 +870,7 @@ impl From<&Value> for Type {
             U64(_, _) => Type::Integer(IntegerType::U64),
             U128(_, _) => Type::Integer(IntegerType::U128),
             Scalar(_, _) => Type::Scalar,
-            String(_, _) => Type::String,
+            String(string, _) => Type::String(Some(string.len())),
         }
     }
 }
@@ -870,13 +881,21 @@ impl From<&Literal> for Value {
     /// This should only be invoked on literals that are known to be valid.
     fn from(literal: &Literal) -> Self {
         match literal {
-            Literal::Address(string, span) => Self::Address(string.clone(), *span),
-            Literal::Boolean(bool, span) => Self::Boolean(*bool, *span),
-            Literal::Field(string, span) => Self::Field(string.clone(), *span),
+            Literal::Address(string, span, _) => Self::Address(string.clone(), *span),
+            Literal::Boolean(bool, span, _) => Self::Boolean(*bool, *span),
+            Literal::Field(string, span, _) => Self::Field(string.clone(), *span),
             Literal::Group(group_literal) => Self::Group(group_literal.clone()),
-            Literal::Scalar(string, span) => Self::Scalar(string.clone(), *span),
-            Literal::String(string, span) => Self::String(string.clone(), *span),
-            Literal::Integer(integer_type, string, span) => match integer_type {
+            Literal::Scalar(string, span, _) => Self::Scalar(string.clone(), *span),
+            Literal::String(string, span, _) => Self::String(string.clone(), *span),
+            Literal::Unsuffixed(string, integer_type, span, id) => Self::from(&Literal::Integer(
+                integer_type
+                    .get()
+                    .expect("Type checking resolves the type of unsuffixed integer literals."),
+                string.clone(),
+                *span,
+                *id,
+            )),
+            Literal::Integer(integer_type, string, span, _) => match integer_type {
                 IntegerType::U8 => Self::U8(string.parse().unwrap(), *span),
                 IntegerType::U16 => Self::U16(string.parse().unwrap(), *span),
                 IntegerType::U32 => Self::U32(string.parse().unwrap(), *span),
@@ -892,28 +911,32 @@ impl From<&Literal> for Value {
     }
 }
 
-impl From<Value> for Literal {
-    fn from(v: Value) -> Self {
+impl From<(Value, NodeId)> for Literal {
+    /// Converts a value to a literal with the given node ID.
+    fn from((v, id): (Value, NodeId)) -> Self {
         use Value::*;
         match v {
             Input(_, _) => todo!("We need to test if this is hittable"),
-            Address(v, span) => Literal::Address(v, span),
-            Boolean(v, span) => Literal::Boolean(v, span),
+            Address(v, span) => Literal::Address(v, span, id),
+            Boolean(v, span) => Literal::Boolean(v, span, id),
             Struct(_ident, _values) => todo!("We need to test if this is hittable"),
-            Field(v, span) => Literal::Field(v, span),
-            Group(v) => Literal::Group(v),
-            I8(v, span) => Literal::Integer(IntegerType::I8, v.to_string(), span),
-            I16(v, span) => Literal::Integer(IntegerType::I16, v.to_string(), span),
-            I32(v, span) => Literal::Integer(IntegerType::I32, v.to_string(), span),
-            I64(v, span) => Literal::Integer(IntegerType::I64, v.to_string(), span),
-            I128(v, span) => Literal::Integer(IntegerType::I128, v.to_string(), span),
-            U8(v, span) => Literal::Integer(IntegerType::U8, v.to_string(), span),
-            U16(v, span) => Literal::Integer(IntegerType::U16, v.to_string(), span),
-            U32(v, span) => Literal::Integer(IntegerType::U32, v.to_string(), span),
-            U64(v, span) => Literal::Integer(IntegerType::U64, v.to_string(), span),
-            U128(v, span) => Literal::Integer(IntegerType::U128, v.to_string(), span),
-            Scalar(v, span) => Literal::Scalar(v, span),
-            String(v, span) => Literal::String(v, span),
+            Field(v, span) => Literal::Field(v, span, id),
+            Group(mut v) => {
+                v.set_id(id);
+                Literal::Group(v)
+            }
+            I8(v, span) => Literal::Integer(IntegerType::I8, v.to_string(), span, id),
+            I16(v, span) => Literal::Integer(IntegerType::I16, v.to_string(), span, id),
+            I32(v, span) => Literal::Integer(IntegerType::I32, v.to_string(), span, id),
+            I64(v, span) => Literal::Integer(IntegerType::I64, v.to_string(), span, id),
+            I128(v, span) => Literal::Integer(IntegerType::I128, v.to_string(), span, id),
+            U8(v, span) => Literal::Integer(IntegerType::U8, v.to_string(), span, id),
+            U16(v, span) => Literal::Integer(IntegerType::U16, v.to_string(), span, id),
+            U32(v, span) => Literal::Integer(IntegerType::U32, v.to_string(), span, id),
+            U64(v, span) => Literal::Integer(IntegerType::U64, v.to_string(), span, id),
+            U128(v, span) => Literal::Integer(IntegerType::U128, v.to_string(), span, id),
+            Scalar(v, span) => Literal::Scalar(v, span, id),
+            String(v, span) => Literal::String(v, span, id),
         }
     }
 }
@@ -0,0 +1,351 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! An example of embedding the Leo compiler in a service, such as the backend of an online playground.
+//!
+//! The server accepts a Leo program as the body of a `POST` request, and responds with JSON:
+//! - `POST /compile` compiles the program, and returns its Aleo instructions and diagnostics.
+//! - `POST /diagnose` only checks the program, and returns its diagnostics.
+//!
+//! Each request is compiled on its own thread, subject to the `Limits` of the server.
+//! A `Sandbox` may additionally reject requests before they are compiled.
+//! A compilation that exceeds the time limit is abandoned rather than cancelled, since the compiler cannot be interrupted.
+//! An abandoned compilation keeps running on its thread until it finishes, and counts towards the limit on concurrent
+//! compilations until then, so that slow programs cannot exhaust the server.
+//!
+//! Run the server with `cargo run -p leo-compiler --features server --example compile_server [address]`.
+
+use leo_ast::NodeBuilder;
+use leo_compiler::Compiler;
+use leo_errors::{
+    emitter::{BufferEmitter, Handler, JsonDiagnostic},
+    PackageError,
+};
+use leo_parser::Edition;
+use leo_span::{span::BytePos, symbol::create_session_if_not_set_then};
+
+use serde::Serialize;
+use std::{
+    fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+/// The resources that a single request may use.
+#[derive(Clone, Copy, Debug)]
+struct Limits {
+    /// The maximum size of a program, in bytes.
+    max_source_bytes: usize,
+    /// The maximum time spent compiling a program.
+    timeout: Duration,
+    /// The maximum number of programs compiled at once, including those whose compilation was abandoned.
+    max_compilations: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_source_bytes: 64 * 1024,
+            timeout: Duration::from_secs(10),
+            max_compilations: 4,
+        }
+    }
+}
+
+/// A hook that decides whether a request is compiled, e.g. to rate limit clients or to reject programs that import others.
+trait Sandbox: Send + Sync {
+    /// Returns an error message if the `source` sent to `endpoint` should not be compiled.
+    fn admit(&self, endpoint: &str, source: &str) -> Result<(), String>;
+}
+
+/// A sandbox that rejects programs with imports, since the server does not provide an `imports` directory.
+struct NoImports;
+
+impl Sandbox for NoImports {
+    fn admit(&self, _endpoint: &str, source: &str) -> Result<(), String> {
+        // A program that fails to parse is admitted, so that the compiler reports its errors.
+        let has_imports = create_session_if_not_set_then(|_| {
+            let (handler, _) = Handler::new_with_buf();
+            leo_parser::parse_ast(
+                &handler,
+                &NodeBuilder::default(),
+                source,
+                BytePos(0),
+                Edition::default(),
+            )
+            .map(|ast| !ast.as_repr().imports.is_empty() || !ast.as_repr().modules.is_empty())
+            .unwrap_or(false)
+        });
+        match has_imports {
+            true => Err("Imports are not supported by this server.".to_string()),
+            false => Ok(()),
+        }
+    }
+}
+
+/// Counts the compilations that are running, including those that were abandoned after exceeding the time limit.
+#[derive(Clone, Default)]
+struct Compilations(Arc<AtomicUsize>);
+
+impl Compilations {
+    /// Reserves a slot for a compilation, unless `max` compilations are already running.
+    fn reserve(&self, max: usize) -> Option<Slot> {
+        self.0
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |running| {
+                (running < max).then(|| running + 1)
+            })
+            .ok()
+            .map(|_| Slot(self.0.clone()))
+    }
+}
+
+/// A compilation that counts towards the running compilations until it is dropped.
+struct Slot(Arc<AtomicUsize>);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// The response to a request.
+#[derive(Debug, Default, Serialize)]
+struct CompileResponse {
+    /// Whether the program compiled without errors.
+    success: bool,
+    /// The Aleo instructions of the program, if it was compiled.
+    bytecode: Option<String>,
+    /// The errors, in the format written by `leo build --json`.
+    errors: Vec<JsonDiagnostic>,
+    /// The warnings, in the format written by `leo build --json`.
+    warnings: Vec<JsonDiagnostic>,
+}
+
+/// Returns the name declared by the program scope of `source`, e.g. `hello` for `program hello.aleo { .. }`.
+/// The compiler reports an error if the program does not declare the name it is given, so the declared name is used.
+fn program_name(source: &str) -> String {
+    source
+        .split_once("program ")
+        .and_then(|(_, rest)| rest.split_once('.'))
+        .map(|(name, _)| name.trim().to_string())
+        .unwrap_or_else(|| "playground".to_string())
+}
+
+/// Compiles `source`, generating its instructions only if `generate` is set.
+/// The program is written to `directory`, which is removed afterwards.
+fn compile(source: &str, directory: PathBuf, generate: bool) -> CompileResponse {
+    let main_file_path = directory.join("main.leo");
+    if let Err(error) = fs::create_dir_all(&directory).and_then(|()| fs::write(&main_file_path, source)) {
+        return CompileResponse {
+            errors: JsonDiagnostic::error(&PackageError::io_error_main_file(error).into())
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+    }
+
+    // Diagnostics are formatted with the source of the program, so they are collected before the session ends.
+    let response = create_session_if_not_set_then(|_| {
+        let (handler, buffer): (Handler, BufferEmitter) = Handler::new_with_buf();
+        let mut compiler = Compiler::new(
+            program_name(source),
+            "aleo".to_string(),
+            &handler,
+            main_file_path,
+            directory.clone(),
+            None,
+            None,
+        );
+        let result = match generate {
+            true => compiler
+                .compile_and_generate_instructions()
+                .map(|(_, bytecode)| Some(bytecode)),
+            false => compiler.compile().map(|_| None),
+        };
+
+        let mut errors: Vec<JsonDiagnostic> = buffer
+            .extract_errs()
+            .into_inner()
+            .iter()
+            .filter_map(JsonDiagnostic::error)
+            .collect();
+        let bytecode = match result {
+            Ok(bytecode) => bytecode,
+            Err(error) => {
+                errors.extend(JsonDiagnostic::error(&error));
+                None
+            }
+        };
+        CompileResponse {
+            success: errors.is_empty(),
+            bytecode,
+            errors,
+            warnings: buffer
+                .extract_warnings()
+                .into_inner()
+                .iter()
+                .map(JsonDiagnostic::warning)
+                .collect(),
+        }
+    });
+    let _ = fs::remove_dir_all(&directory);
+    response
+}
+
+/// Reads a request from `stream`, returning its method, path, and body.
+/// Bodies larger than `max_body_bytes` are rejected.
+fn read_request(stream: &TcpStream, max_body_bytes: usize) -> Result<(String, String, String), (u16, String)> {
+    let bad_request = |message: &str| (400, message.to_string());
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader
+        .read_line(&mut request_line)
+        .map_err(|_| bad_request("Malformed request."))?;
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = match (parts.next(), parts.next()) {
+        (Some(method), Some(path)) => (method.to_string(), path.to_string()),
+        _ => return Err(bad_request("Malformed request line.")),
+    };
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader
+            .read_line(&mut header)
+            .map_err(|_| bad_request("Malformed header."))?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| bad_request("Invalid content length."))?;
+            }
+        }
+    }
+    if content_length > max_body_bytes {
+        return Err((413, format!("Programs may be at most {} bytes.", max_body_bytes)));
+    }
+
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| bad_request("Truncated body."))?;
+    let body = String::from_utf8(body).map_err(|_| bad_request("The program is not valid UTF-8."))?;
+    Ok((method, path, body))
+}
+
+/// Writes a response with the JSON `body` to `stream`.
+fn write_response(mut stream: &TcpStream, status: u16, body: &str) {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        413 => "Payload Too Large",
+        500 => "Internal Server Error",
+        _ => "Service Unavailable",
+    };
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+}
+
+/// Returns the JSON body of a response that only carries an error `message`.
+fn error_body(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+/// Handles a single connection.
+fn handle(stream: TcpStream, limits: Limits, sandbox: &dyn Sandbox, compilations: &Compilations, request_id: usize) {
+    let (method, path, source) = match read_request(&stream, limits.max_source_bytes) {
+        Ok(request) => request,
+        Err((status, message)) => return write_response(&stream, status, &error_body(&message)),
+    };
+    let generate = match (method.as_str(), path.as_str()) {
+        ("POST", "/compile") => true,
+        ("POST", "/diagnose") => false,
+        _ => {
+            return write_response(
+                &stream,
+                404,
+                &error_body("Expected `POST /compile` or `POST /diagnose`."),
+            )
+        }
+    };
+    if let Err(message) = sandbox.admit(&path, &source) {
+        return write_response(&stream, 403, &error_body(&message));
+    }
+
+    let slot = match compilations.reserve(limits.max_compilations) {
+        Some(slot) => slot,
+        None => return write_response(&stream, 503, &error_body("The server is busy, try again later.")),
+    };
+
+    // Compile on a separate thread, so that the request can be answered once the time limit is exceeded.
+    // The slot is released once the compilation finishes, even if the request was answered before then.
+    let directory = std::env::temp_dir().join(format!("leo-compile-server-{}-{}", std::process::id(), request_id));
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(compile(&source, directory, generate));
+        drop(slot);
+    });
+    match receiver.recv_timeout(limits.timeout) {
+        Ok(response) => {
+            let body = serde_json::to_string_pretty(&response).expect("Failed to serialize the response.");
+            write_response(&stream, 200, &body)
+        }
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            write_response(&stream, 503, &error_body("The program took too long to compile."))
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            write_response(&stream, 500, &error_body("The compiler failed unexpectedly."))
+        }
+    }
+}
+
+fn main() -> std::io::Result<()> {
+    let address = std::env::args().nth(1).unwrap_or_else(|| "127.0.0.1:3030".to_string());
+    let listener = TcpListener::bind(&address)?;
+    println!("Listening on http://{}", address);
+
+    let limits = Limits::default();
+    let sandbox: Arc<dyn Sandbox> = Arc::new(NoImports);
+    let compilations = Compilations::default();
+    for (request_id, stream) in listener.incoming().enumerate() {
+        let stream = stream?;
+        let sandbox = sandbox.clone();
+        let compilations = compilations.clone();
+        thread::spawn(move || handle(stream, limits, sandbox.as_ref(), &compilations, request_id));
+    }
+    Ok(())
+}
@@ -0,0 +1,114 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_errors::{CompilerError, Result};
+
+use indexmap::IndexSet;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Component, Path, PathBuf},
+};
+
+/// The files that a build of a program wrote to the output directory,
+/// so that the files written by earlier builds can be removed once they are stale.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactManifest {
+    /// The paths of the files, relative to the output directory.
+    pub files: IndexSet<PathBuf>,
+}
+
+impl ArtifactManifest {
+    /// Returns the path of the manifest of `program_name` in the output `directory`.
+    pub fn path(directory: &Path, program_name: &str) -> PathBuf {
+        directory.join(format!("{}.artifacts.json", program_name))
+    }
+
+    /// Returns the manifest of `program_name` in the output `directory`.
+    /// A manifest that is missing or cannot be read is treated as empty.
+    pub fn load(directory: &Path, program_name: &str) -> Self {
+        fs::read_to_string(Self::path(directory, program_name))
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Writes the manifest to the output `directory`, replacing any previous manifest of `program_name`.
+    pub fn write(&self, directory: &Path, program_name: &str) -> Result<()> {
+        let path = Self::path(directory, program_name);
+        let json = serde_json::to_string_pretty(self).expect("Failed to serialize the artifact manifest.");
+        fs::write(&path, json).map_err(|e| CompilerError::failed_to_write_artifact_manifest(&path, e))?;
+        Ok(())
+    }
+
+    /// Records that the file at `path`, relative to the output directory, was written.
+    pub fn record(&mut self, path: impl Into<PathBuf>) {
+        self.files.insert(path.into());
+    }
+
+    /// Removes the files of the manifest from the output `directory`, except those that are also in `keep`.
+    /// Files that no longer exist are skipped, as are paths that could lead outside of the output directory.
+    pub fn remove_files(&self, directory: &Path, keep: &Self) -> Result<()> {
+        let is_relative = |path: &Path| path.components().all(|c| matches!(c, Component::Normal(_)));
+        for file in self.files.difference(&keep.files).filter(|file| is_relative(file)) {
+            let path = directory.join(file);
+            match fs::remove_file(&path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                    return Err(CompilerError::failed_to_remove_artifact(&path, e).into());
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_artifact_manifest() {
+        let directory = tempfile::tempdir().expect("Failed to open temporary directory");
+        let outside = tempfile::tempdir().expect("Failed to open temporary directory");
+        for file in ["initial_ast.json", "ssa_ast.json"] {
+            fs::write(directory.path().join(file), "{}").unwrap();
+        }
+        fs::write(outside.path().join("keep.json"), "{}").unwrap();
+        assert_eq!(
+            ArtifactManifest::load(directory.path(), "test"),
+            ArtifactManifest::default()
+        );
+
+        let mut previous = ArtifactManifest::default();
+        previous.record("initial_ast.json");
+        previous.record("ssa_ast.json");
+        previous.record("profile.json");
+        previous.record(outside.path().join("keep.json"));
+        previous.write(directory.path(), "test").unwrap();
+        assert_eq!(ArtifactManifest::load(directory.path(), "test"), previous);
+
+        // Only the files that the current build did not write are removed.
+        let mut current = ArtifactManifest::default();
+        current.record("initial_ast.json");
+        ArtifactManifest::load(directory.path(), "test")
+            .remove_files(directory.path(), &current)
+            .unwrap();
+        assert!(directory.path().join("initial_ast.json").exists());
+        assert!(!directory.path().join("ssa_ast.json").exists());
+        assert!(outside.path().join("keep.json").exists());
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{BuildFingerprint, ProgramStatistics, SymbolTable};
+
+use leo_ast::{Program, Struct};
+use leo_errors::{CompilerError, Result, StoredWarning};
+use leo_span::Symbol;
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The outputs of a previous build of a program, which are reused as long as its sources and options are unchanged.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedBuild {
+    /// A SHA256 hash of the sources of the program, its imports and its modules, the options, and the compiler version.
+    pub key: String,
+    /// The AST after the last compiler pass.
+    pub ast: Program,
+    /// The structs and records declared by the program.
+    pub structs: IndexMap<Symbol, Struct>,
+    /// The generated Aleo instructions.
+    pub bytecode: String,
+    /// The fingerprint of the build.
+    pub fingerprint: BuildFingerprint,
+    /// Statistics of the generated Aleo instructions.
+    pub statistics: ProgramStatistics,
+    /// The warnings emitted by the build, which are emitted again when it is reused.
+    pub warnings: Vec<StoredWarning>,
+    /// The contents of the files written by the build, by their paths relative to the output directory,
+    /// which are written again when it is reused.
+    pub outputs: IndexMap<PathBuf, String>,
+}
+
+impl CachedBuild {
+    /// Returns the path of the cached build of `program_name` in the output `directory`.
+    pub fn path(directory: &Path, program_name: &str) -> PathBuf {
+        directory.join("cache").join(format!("{}.json", program_name))
+    }
+
+    /// Returns the cached build of `program_name` in the output `directory`, if there is one with the given `key`.
+    /// A cache that cannot be read, such as one written by another version of the compiler, is ignored.
+    pub fn load(directory: &Path, program_name: &str, key: &str) -> Option<Self> {
+        let json = fs::read_to_string(Self::path(directory, program_name)).ok()?;
+        serde_json::from_str::<Self>(&json)
+            .ok()
+            .filter(|build| build.key == key)
+    }
+
+    /// Writes the build to the cache in the output `directory`, replacing any previous build of `program_name`.
+    pub fn write(&self, directory: &Path, program_name: &str) -> Result<()> {
+        let path = Self::path(directory, program_name);
+        let json = serde_json::to_string(self).expect("Failed to serialize the cached build.");
+        fs::create_dir_all(directory.join("cache"))
+            .and_then(|_| fs::write(&path, json))
+            .map_err(|e| CompilerError::failed_to_write_build_cache(&path, e))?;
+        Ok(())
+    }
+}
+
+/// The symbol table of a build, which is only complete if the program was compiled rather than reused from the cache.
+#[derive(Clone, Debug)]
+pub enum BuildSymbols {
+    /// The symbol table of a program that the build compiled.
+    Fresh(SymbolTable),
+    /// The structs and records of a program whose cached build was reused, since the cache records no other symbols.
+    Cached(IndexMap<Symbol, Struct>),
+}
+
+impl BuildSymbols {
+    /// Returns the symbol table, if the build compiled the program.
+    pub fn symbol_table(&self) -> Option<&SymbolTable> {
+        match self {
+            Self::Fresh(symbol_table) => Some(symbol_table),
+            Self::Cached(_) => None,
+        }
+    }
+
+    /// Returns the structs and records declared by the program.
+    pub fn structs(&self) -> &IndexMap<Symbol, Struct> {
+        match self {
+            Self::Fresh(symbol_table) => &symbol_table.structs,
+            Self::Cached(structs) => structs,
+        }
+    }
+
+    /// Returns the structs and records declared by the program, consuming the symbols.
+    pub fn into_structs(self) -> IndexMap<Symbol, Struct> {
+        match self {
+            Self::Fresh(symbol_table) => symbol_table.structs,
+            Self::Cached(structs) => structs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use leo_span::symbol::create_session_if_not_set_then;
+
+    #[test]
+    fn test_cached_build() {
+        create_session_if_not_set_then(|_| {
+            let directory = tempfile::tempdir().expect("Failed to open temporary directory");
+            let build = CachedBuild {
+                key: "key".to_string(),
+                ast: Program::default(),
+                structs: IndexMap::new(),
+                bytecode: "program test.aleo;\n".to_string(),
+                fingerprint: BuildFingerprint::default(),
+                statistics: ProgramStatistics::default(),
+                warnings: Vec::new(),
+                outputs: IndexMap::from([(PathBuf::from("initial_ast.json"), "{}".to_string())]),
+            };
+            assert_eq!(CachedBuild::load(directory.path(), "test", "key"), None);
+
+            build.write(directory.path(), "test").unwrap();
+            assert_eq!(CachedBuild::load(directory.path(), "test", "key"), Some(build));
+            assert_eq!(CachedBuild::load(directory.path(), "test", "other"), None);
+            assert_eq!(CachedBuild::load(directory.path(), "other", "key"), None);
+        });
+    }
+}
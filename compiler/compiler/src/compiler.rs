@@ -17,20 +17,27 @@
 //! The compiler for Leo programs.
 //!
 //! The [`Compiler`] type compiles Leo programs into R1CS circuits.
-use leo_ast::Program;
 pub use leo_ast::{Ast, InputAst};
-use leo_errors::emitter::Handler;
-use leo_errors::{CompilerError, Result};
+use leo_ast::{NodeBuilder, Program};
+use leo_errors::emitter::{Handler, LintLevel};
+use leo_errors::{emitter::JsonSpan, CompilerError, LeoWarning, Result, StoredWarning};
 pub use leo_passes::SymbolTable;
 use leo_passes::*;
-use leo_span::source_map::FileName;
+use leo_span::source_map::{FileName, SourceFile};
 use leo_span::symbol::with_session_globals;
+use leo_span::{span::BytePos, Span};
 
-use sha2::{Digest, Sha256};
+use indexmap::{IndexMap, IndexSet};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
-use crate::OutputOptions;
+use crate::{
+    sha256_hex, ArtifactManifest, AstSnapshot, BuildFingerprint, BuildOptions, BuildSymbols, CachedBuild,
+    CompileReport, CompilerPass, Disassembly, ImportResolver, InstructionSourceMap, LeoFunction, LocationSpan,
+    OutputOptions, PassManager, PassState, ProfileNode, ProgramStatistics,
+};
 
 /// The primary entry point of the Leo compiler.
 #[derive(Clone)]
@@ -47,10 +54,24 @@ pub struct Compiler<'a> {
     pub network: String,
     /// The AST for the program.
     pub ast: Ast,
+    /// The builder of the IDs of the nodes in the AST, shared by the parser and the passes that create nodes.
+    node_builder: NodeBuilder,
     /// The input ast for the program if it exists.
     pub input_ast: Option<InputAst>,
     /// Compiler options on some optional output files.
     output_options: OutputOptions,
+    /// Compiler options that affect how the program is built.
+    build_options: BuildOptions,
+    /// A report of the work performed by each pass that has been run.
+    report: CompileReport,
+    /// Content hashes of the program and of the generated instructions.
+    fingerprint: BuildFingerprint,
+    /// The files written to the output directory by this build.
+    artifacts: ArtifactManifest,
+    /// The estimated number of constraints of the program, if requested.
+    cost_report: Option<CostReport>,
+    /// Statistics of the generated Aleo instructions.
+    statistics: Option<ProgramStatistics>,
 }
 
 impl<'a> Compiler<'a> {
@@ -62,6 +83,7 @@ impl<'a> Compiler<'a> {
         main_file_path: PathBuf,
         output_directory: PathBuf,
         output_options: Option<OutputOptions>,
+        build_options: Option<BuildOptions>,
     ) -> Self {
         Self {
             handler,
@@ -70,11 +92,41 @@ impl<'a> Compiler<'a> {
             program_name,
             network,
             ast: Ast::new(Program::default()),
+            node_builder: NodeBuilder::default(),
             input_ast: None,
             output_options: output_options.unwrap_or_default(),
+            build_options: build_options.unwrap_or_default(),
+            report: CompileReport::default(),
+            fingerprint: BuildFingerprint::default(),
+            artifacts: ArtifactManifest::default(),
+            cost_report: None,
+            statistics: None,
         }
     }
 
+    /// Returns a report of the work performed by each pass that has been run.
+    pub fn report(&self) -> &CompileReport {
+        &self.report
+    }
+
+    /// Returns the estimated number of constraints of each function and line of the program.
+    /// The estimate is only set once the instructions have been generated, if the cost report is enabled.
+    pub fn cost_report(&self) -> Option<&CostReport> {
+        self.cost_report.as_ref()
+    }
+
+    /// Returns statistics of the generated Aleo instructions, such as the number of instructions with each opcode.
+    /// The statistics are only set once the instructions have been generated or reused from a previous build.
+    pub fn statistics(&self) -> Option<&ProgramStatistics> {
+        self.statistics.as_ref()
+    }
+
+    /// Returns the content hashes of the program and of the generated instructions.
+    /// The hashes are only set once the corresponding compiler stages have been run.
+    pub fn fingerprint(&self) -> &BuildFingerprint {
+        &self.fingerprint
+    }
+
     /// Returns a SHA256 checksum of the program file.
     pub fn checksum(&self) -> Result<String> {
         // Read in the main file as string
@@ -82,20 +134,21 @@ impl<'a> Compiler<'a> {
             .map_err(|e| CompilerError::file_read_error(self.main_file_path.clone(), e))?;
 
         // Hash the file contents
-        let mut hasher = Sha256::new();
-        hasher.update(unparsed_file.as_bytes());
-        let hash = hasher.finalize();
-
-        Ok(format!("{:x}", hash))
+        Ok(sha256_hex(unparsed_file.as_bytes()))
     }
 
     /// Parses and stores a program file content from a string, constructs a syntax tree, and generates a program.
     pub fn parse_program_from_string(&mut self, program_string: &str, name: FileName) -> Result<()> {
+        // Set the levels of lints before any warnings are emitted.
+        self.configure_lints()?;
+
         // Register the source (`program_string`) in the source map.
         let prg_sf = with_session_globals(|s| s.source_map.new_source(program_string, name));
 
         // Use the parser to construct the abstract syntax tree (ast).
-        self.ast = leo_parser::parse_ast(self.handler, &prg_sf.src, prg_sf.start_pos)?;
+        // Note that a program may declare the edition it is written for, which takes precedence over the build option.
+        let edition = self.build_options.edition.unwrap_or_default();
+        self.ast = leo_parser::parse_ast(self.handler, &self.node_builder, &prg_sf.src, prg_sf.start_pos, edition)?;
 
         // If the program is imported, then check that the name of its program scope matches the file name.
         // Note that parsing enforces that there is exactly one program scope in a file.
@@ -111,9 +164,11 @@ impl<'a> Compiler<'a> {
             .into());
         }
 
-        if self.output_options.initial_ast {
-            self.write_ast_to_json("initial_ast.json")?;
-        }
+        // Merge the modules imported with `import foo;`, which are read from the directory of the main file.
+        let directory = self.main_file_path.parent().map(Path::to_path_buf).unwrap_or_default();
+        ImportResolver::new(self.handler, &self.node_builder, directory, edition).resolve(&mut self.ast.ast)?;
+
+        self.write_ast_snapshot(AstSnapshot::Initial)?;
 
         Ok(())
     }
@@ -127,6 +182,21 @@ impl<'a> Compiler<'a> {
         self.parse_program_from_string(&program_string, FileName::Real(self.main_file_path.clone()))
     }
 
+    /// Sets the levels of lints in the handler, as given by the build options.
+    fn configure_lints(&self) -> Result<()> {
+        if self.build_options.deny_warnings {
+            self.handler.set_default_lint_level(LintLevel::Deny);
+        }
+        for (lint, level) in self.build_options.lint_levels.iter() {
+            if !LeoWarning::names().any(|name| name == lint) {
+                let lints: Vec<String> = LeoWarning::names().map(|name| format!("`{}`", name)).collect();
+                return Err(CompilerError::unknown_lint(lint, lints.join(", ")).into());
+            }
+            self.handler.set_lint_level(lint, *level);
+        }
+        Ok(())
+    }
+
     /// Parses and stores the input file, constructs a syntax tree, and generates a program input.
     pub fn parse_input(&mut self, input_file_path: PathBuf) -> Result<()> {
         if input_file_path.exists() {
@@ -135,8 +205,9 @@ impl<'a> Compiler<'a> {
                 .map_err(|e| CompilerError::file_read_error(&input_file_path, e))?;
 
             // Parse and serialize it.
-            let input_ast = leo_parser::parse_input(self.handler, &input_sf.src, input_sf.start_pos)?;
-            if self.output_options.initial_ast {
+            let input_ast =
+                leo_parser::parse_input(self.handler, &self.node_builder, &input_sf.src, input_sf.start_pos)?;
+            if self.output_options.initial_input_ast {
                 // Write the input AST snapshot post parsing.
                 if self.output_options.spans_enabled {
                     input_ast.to_json_file(self.output_directory.clone(), "initial_input_ast.json")?;
@@ -147,6 +218,7 @@ impl<'a> Compiler<'a> {
                         &["span"],
                     )?;
                 }
+                self.record_artifact(&self.output_directory.join("initial_input_ast.json"));
             }
 
             self.input_ast = Some(input_ast);
@@ -154,93 +226,1295 @@ impl<'a> Compiler<'a> {
         Ok(())
     }
 
+    /// Runs the cfg stripping pass.
+    pub fn cfg_stripping_pass(&mut self) -> Result<()> {
+        let options = CfgOptions {
+            // Programs are only compiled for the network that the compiler's dependencies target.
+            network: "testnet3".to_string(),
+            features: self.build_options.features.clone(),
+            test: self.build_options.test,
+        };
+        let (ast, summary) = CfgStripper::do_pass((std::mem::take(&mut self.ast), &options))?;
+        self.ast = ast;
+        self.report.push(summary);
+
+        Ok(())
+    }
+
     /// Runs the symbol table pass.
-    pub fn symbol_table_pass(&self) -> Result<SymbolTable> {
-        CreateSymbolTable::do_pass((&self.ast, self.handler))
+    pub fn symbol_table_pass(&mut self) -> Result<SymbolTable> {
+        let (symbol_table, summary) = CreateSymbolTable::do_pass((&self.ast, self.handler))?;
+        self.report.push(summary);
+
+        Ok(symbol_table)
     }
 
-    /// Runs the type checker pass.
-    pub fn type_checker_pass(&'a self, symbol_table: SymbolTable) -> Result<SymbolTable> {
-        TypeChecker::do_pass((&self.ast, self.handler, symbol_table))
+    /// Runs the type checker pass, which also returns the types of the program's expressions and variables.
+    pub fn type_checker_pass(
+        &mut self,
+        symbol_table: SymbolTable,
+    ) -> Result<(SymbolTable, TypeTable, StructGraph, CallGraph)> {
+        let (symbol_table, type_table, struct_graph, call_graph, summary) = TypeChecker::do_pass((
+            &self.ast,
+            self.handler,
+            symbol_table,
+            self.build_options.default_integer_type,
+        ))?;
+        self.report.push(summary);
+
+        if self.output_options.symbol_table {
+            let path = self.output_directory.join("symbol_table.json");
+            fs::write(&path, symbol_table.to_json()?)
+                .map_err(|e| CompilerError::failed_to_write_symbol_table(&path, e))?;
+            self.record_artifact(&path);
+        }
+
+        Ok((symbol_table, type_table, struct_graph, call_graph))
     }
 
-    /// Runs the loop unrolling pass.
-    pub fn loop_unrolling_pass(&mut self, symbol_table: SymbolTable) -> Result<SymbolTable> {
-        let (ast, symbol_table) = Unroller::do_pass((std::mem::take(&mut self.ast), self.handler, symbol_table))?;
+    /// Runs the enum lowering pass.
+    pub fn enum_lowering_pass(&mut self, symbol_table: SymbolTable) -> Result<SymbolTable> {
+        let (ast, symbol_table, summary) =
+            EnumLowerer::do_pass((std::mem::take(&mut self.ast), symbol_table, &self.node_builder))?;
         self.ast = ast;
+        self.report.push(summary);
 
-        if self.output_options.unrolled_ast {
-            self.write_ast_to_json("unrolled_ast.json")?;
-        }
+        self.write_ast_snapshot(AstSnapshot::Lower)?;
 
         Ok(symbol_table)
     }
 
+    /// Runs the monomorphization pass.
+    pub fn monomorphization_pass(
+        &mut self,
+        symbol_table: SymbolTable,
+        call_graph: CallGraph,
+        struct_graph: StructGraph,
+        type_table: TypeTable,
+    ) -> Result<(SymbolTable, CallGraph, StructGraph, TypeTable)> {
+        let (ast, symbol_table, call_graph, struct_graph, type_table, summary) = Monomorphizer::do_pass((
+            std::mem::take(&mut self.ast),
+            symbol_table,
+            call_graph,
+            struct_graph,
+            type_table,
+            &self.node_builder,
+        ))?;
+        self.ast = ast;
+        self.report.push(summary);
+
+        self.write_ast_snapshot(AstSnapshot::Monomorphize)?;
+
+        Ok((symbol_table, call_graph, struct_graph, type_table))
+    }
+
+    /// Runs the loop unrolling pass.
+    pub fn loop_unrolling_pass(
+        &mut self,
+        symbol_table: SymbolTable,
+        type_table: TypeTable,
+    ) -> Result<(SymbolTable, TypeTable)> {
+        let (ast, symbol_table, type_table, summary) = Unroller::do_pass((
+            std::mem::take(&mut self.ast),
+            self.handler,
+            symbol_table,
+            type_table,
+            &self.node_builder,
+        ))?;
+        self.ast = ast;
+        self.report.push(summary);
+
+        self.write_ast_snapshot(AstSnapshot::Unroll)?;
+
+        Ok((symbol_table, type_table))
+    }
+
+    /// Runs the tuple lowering pass.
+    /// The types of the variables and expressions that the pass introduces are added to `type_table`.
+    pub fn tuple_lowering_pass(&mut self, type_table: &mut TypeTable) -> Result<()> {
+        let (ast, summary) = TupleLowerer::do_pass((std::mem::take(&mut self.ast), &self.node_builder, type_table))?;
+        self.ast = ast;
+        self.report.push(summary);
+
+        Ok(())
+    }
+
+    /// Runs the layout analysis pass.
+    pub fn layout_analysis_pass(&mut self) -> Result<()> {
+        let summary = LayoutAnalyzer::do_pass((&self.ast, self.handler))?;
+        self.report.push(summary);
+
+        Ok(())
+    }
+
+    /// Runs the gates analysis pass.
+    pub fn gates_analysis_pass(&mut self) -> Result<()> {
+        let summary = GatesAnalyzer::do_pass((&self.ast, self.handler))?;
+        self.report.push(summary);
+
+        Ok(())
+    }
+
+    /// Runs the taint analysis pass.
+    pub fn taint_analysis_pass(&mut self) -> Result<()> {
+        let summary = TaintAnalyzer::do_pass((&self.ast, self.handler, self.build_options.privacy_warnings))?;
+        self.report.push(summary);
+
+        Ok(())
+    }
+
+    /// Runs the assertion checking pass.
+    pub fn assertion_checking_pass(&mut self) -> Result<()> {
+        let summary = AssertionChecker::do_pass((&self.ast, self.handler))?;
+        self.report.push(summary);
+
+        Ok(())
+    }
+
     /// Runs the static single assignment pass.
-    pub fn static_single_assignment_pass(&mut self) -> Result<Assigner> {
-        let (ast, assigner) = StaticSingleAssigner::do_pass(std::mem::take(&mut self.ast))?;
+    /// The types of the variables that the pass introduces are added to `type_table`.
+    pub fn static_single_assignment_pass(&mut self, type_table: &mut TypeTable) -> Result<Assigner> {
+        let (ast, assigner, summary) = StaticSingleAssigner::do_pass((
+            std::mem::take(&mut self.ast),
+            &self.node_builder,
+            type_table,
+            self.build_options.global_ssa_numbering,
+        ))?;
         self.ast = ast;
+        self.report.push(summary);
 
-        if self.output_options.ssa_ast {
-            self.write_ast_to_json("ssa_ast.json")?;
-        }
+        self.write_ast_snapshot(AstSnapshot::Ssa)?;
 
         Ok(assigner)
     }
 
     /// Runs the flattening pass.
-    pub fn flattening_pass(&mut self, symbol_table: &SymbolTable, assigner: Assigner) -> Result<()> {
-        self.ast = Flattener::do_pass((std::mem::take(&mut self.ast), symbol_table, assigner))?;
+    pub fn flattening_pass(
+        &mut self,
+        symbol_table: &SymbolTable,
+        type_table: &mut TypeTable,
+        assigner: Assigner,
+    ) -> Result<Assigner> {
+        let (ast, assigner, summary) = Flattener::do_pass((
+            std::mem::take(&mut self.ast),
+            symbol_table,
+            assigner,
+            &self.node_builder,
+            type_table,
+            self.build_options.sanitize,
+        ))?;
+        self.ast = ast;
+        self.report.push(summary);
 
-        if self.output_options.flattened_ast {
-            self.write_ast_to_json("flattened_ast.json")?;
-        }
+        self.write_ast_snapshot(AstSnapshot::Flatten)?;
+
+        Ok(assigner)
+    }
+
+    /// Runs the function inlining pass.
+    pub fn function_inlining_pass(
+        &mut self,
+        call_graph: &CallGraph,
+        assigner: Assigner,
+        budget: Option<usize>,
+    ) -> Result<Assigner> {
+        let (ast, assigner, summary) = FunctionInliner::do_pass((
+            std::mem::take(&mut self.ast),
+            call_graph,
+            assigner,
+            &self.node_builder,
+            budget,
+        ))?;
+        self.ast = ast;
+        self.report.push(summary);
+
+        self.write_ast_snapshot(AstSnapshot::Inline)?;
+
+        Ok(assigner)
+    }
+
+    /// Runs the boolean simplification pass.
+    pub fn boolean_simplification_pass(&mut self) -> Result<()> {
+        let (ast, summary) = BooleanSimplifier::do_pass((std::mem::take(&mut self.ast), &self.node_builder))?;
+        self.ast = ast;
+        self.report.push(summary);
+
+        self.write_ast_snapshot(AstSnapshot::Simplify)?;
+
+        Ok(())
+    }
+
+    /// Runs the range analysis pass.
+    pub fn range_analysis_pass(&mut self) -> Result<()> {
+        let (ast, summary) = RangeAnalyzer::do_pass((
+            std::mem::take(&mut self.ast),
+            &self.node_builder,
+            self.build_options.sanitize,
+        ))?;
+        self.ast = ast;
+        self.report.push(summary);
+
+        self.write_ast_snapshot(AstSnapshot::Range)?;
+
+        Ok(())
+    }
+
+    /// Runs the strength reduction pass.
+    pub fn strength_reduction_pass(&mut self) -> Result<()> {
+        let (ast, summary) = StrengthReducer::do_pass((std::mem::take(&mut self.ast), &self.node_builder))?;
+        self.ast = ast;
+        self.report.push(summary);
+
+        self.write_ast_snapshot(AstSnapshot::Reduce)?;
 
         Ok(())
     }
 
+    /// Runs the ternary balancing pass, rebalancing chains of at least `threshold` selections.
+    pub fn ternary_balancing_pass(&mut self, assigner: Assigner, threshold: usize) -> Result<Assigner> {
+        let (ast, assigner, summary) =
+            TernaryBalancer::do_pass((std::mem::take(&mut self.ast), assigner, &self.node_builder, threshold))?;
+        self.ast = ast;
+        self.report.push(summary);
+
+        self.write_ast_snapshot(AstSnapshot::Balance)?;
+
+        Ok(assigner)
+    }
+
+    /// Runs the function outlining pass.
+    pub fn function_outlining_pass(&mut self, assigner: Assigner) -> Result<()> {
+        let (ast, summary) = FunctionOutliner::do_pass((std::mem::take(&mut self.ast), assigner, &self.node_builder))?;
+        self.ast = ast;
+        self.report.push(summary);
+
+        self.write_ast_snapshot(AstSnapshot::Outline)?;
+
+        Ok(())
+    }
+
+    /// Runs the validation pass, checking that the AST upholds `invariants`.
+    /// The validation pass checks the compiler rather than the program, so it is not recorded in the report.
+    pub fn validation_pass(
+        &mut self,
+        symbol_table: Option<&SymbolTable>,
+        invariants: &IndexSet<Invariant>,
+    ) -> Result<()> {
+        Validator::do_pass((&self.ast, self.handler, symbol_table, invariants))?;
+
+        Ok(())
+    }
+
+    /// Counts the nodes of the AST that the next pass is given, if the pass report is enabled.
+    pub(crate) fn count_nodes(&self) -> Option<NodeCount> {
+        self.output_options.pass_report.then(|| NodeCount::new(&self.ast))
+    }
+
+    /// Records the nodes visited by the pass that was run last, given the nodes of the AST it was given,
+    /// along with the nodes it rewrote if it transformed the AST.
+    pub(crate) fn record_nodes(&mut self, input: Option<NodeCount>, transformed: bool) {
+        if let (Some(input), Some(summary)) = (input, self.report.last_mut()) {
+            summary.count_nodes(&input, transformed.then_some(&self.ast));
+        }
+    }
+
+    /// Returns whether the AST is validated after every pass, as requested by `--debug-compiler`.
+    pub(crate) fn debug_compiler(&self) -> bool {
+        self.build_options.debug_compiler
+    }
+
     /// Runs the compiler stages.
-    pub fn compiler_stages(&mut self) -> Result<SymbolTable> {
-        let st = self.symbol_table_pass()?;
-        let st = self.type_checker_pass(st)?;
+    pub fn compiler_stages(&mut self) -> Result<(SymbolTable, StructGraph)> {
+        let state = PassManager::frontend().run(self, PassState::default())?;
+
+        // Hash the program before any optimizations are applied.
+        self.fingerprint.program_hash = sha256_hex(resolved_source(self.ast.as_repr()).as_bytes());
 
-        // TODO: Make this pass optional.
-        let st = self.loop_unrolling_pass(st)?;
+        let state = PassManager::lowering(&self.build_options).run(self, state)?;
+
+        match (state.symbol_table, state.struct_graph) {
+            (Some(symbol_table), Some(struct_graph)) => Ok((symbol_table, struct_graph)),
+            _ => unreachable!("The frontend creates the symbol table and the struct graph."),
+        }
+    }
 
-        // TODO: Make this pass optional.
-        let assigner = self.static_single_assignment_pass()?;
+    /// Parses the program and runs the passes that the interpreter depends on.
+    /// Afterwards, enums have been lowered and generic functions instantiated, but loops and conditionals are intact.
+    pub fn interpreter_stages(&mut self) -> Result<()> {
+        self.parse_program()?;
 
-        self.flattening_pass(&st, assigner)?;
+        let state = PassManager::frontend().run(self, PassState::default())?;
+        PassManager::default()
+            .register(CompilerPass::EnumLowering)
+            .register(CompilerPass::Monomorphization)
+            .run(self, state)?;
 
-        Ok(st)
+        Ok(())
     }
 
     /// Returns a compiled Leo program and prints the resulting bytecode.
+    /// If incremental builds are enabled and the previous build is reused, only the program's structs are returned, as [`BuildSymbols::Cached`].
     // TODO: Remove when code generation is ready to be integrated into the compiler.
-    pub fn compile_and_generate_instructions(&mut self) -> Result<(SymbolTable, String)> {
+    pub fn compile_and_generate_instructions(&mut self) -> Result<(BuildSymbols, String)> {
+        let cache_key = match self.build_options.incremental {
+            true => Some(self.cache_key()?),
+            false => None,
+        };
+        // The cost report is estimated from the generated instructions, which are not cached.
+        if let Some(build) = cache_key
+            .as_ref()
+            .filter(|_| !self.output_options.cost_report)
+            .and_then(|key| CachedBuild::load(&self.output_directory, &self.program_name, key))
+        {
+            return self.reuse_build(build);
+        }
+        // The warnings and outputs of the build are cached, so that they can be replayed when the build is reused.
+        let warnings = self.handler.warnings().len();
+        let artifacts = self.artifacts.clone();
+
         self.parse_program()?;
-        let symbol_table = self.compiler_stages()?;
+        let (symbol_table, struct_graph) = self.compiler_stages()?;
+
+        let input = self.count_nodes();
+        let (program, summary) = CodeGenerator::do_pass((&self.ast, self.handler, &struct_graph, &self.network))?;
+        self.report.push(summary);
+        self.record_nodes(input, false);
+        let (program, summary) = RegisterAllocator::do_pass(program);
+        self.report.push(summary);
+        let program = match self.output_options.cost_report {
+            true => {
+                let (program, report, summary) = CostEstimator::do_pass(program);
+                self.report.push(summary);
+                self.cost_report = Some(report);
+                program
+            }
+            false => program,
+        };
+        let bytecode = program.to_string();
+        self.fingerprint.bytecode_hash = sha256_hex(bytecode.as_bytes());
+        let statistics = ProgramStatistics::new(&program);
+        self.statistics = Some(statistics.clone());
+
+        if self.output_options.profile {
+            let source = fs::read_to_string(&self.main_file_path)
+                .map_err(|e| CompilerError::file_read_error(&self.main_file_path, e))?;
+            let path = self.output_directory.join("profile.json");
+            ProfileNode::new(&program, &source).write(&path)?;
+            self.record_artifact(&path);
+        }
+        if self.output_options.source_map {
+            let path = self.output_directory.join("source_map.json");
+            InstructionSourceMap::new(&program, &self.main_file_path).write(&path)?;
+            self.record_artifact(&path);
+        }
+        if self.output_options.disassembly {
+            let source = fs::read_to_string(&self.main_file_path)
+                .map_err(|e| CompilerError::file_read_error(&self.main_file_path, e))?;
+            let path = self.output_directory.join("disassembly.json");
+            Disassembly::new(&program, &self.main_file_path, &source, &self.leo_functions()).write(&path)?;
+            self.record_artifact(&path);
+        }
+
+        if let Some(key) = cache_key {
+            let mut outputs = IndexMap::new();
+            for file in self.artifacts.files.difference(&artifacts.files) {
+                let path = self.output_directory.join(file);
+                let contents = fs::read_to_string(&path).map_err(|e| CompilerError::file_read_error(&path, e))?;
+                outputs.insert(file.clone(), contents);
+            }
+            let build = CachedBuild {
+                key,
+                ast: self.ast.as_repr().clone(),
+                structs: symbol_table.structs.clone(),
+                bytecode: bytecode.clone(),
+                fingerprint: self.fingerprint.clone(),
+                statistics,
+                warnings: self.handler.warnings()[warnings..]
+                    .iter()
+                    .map(StoredWarning::new)
+                    .collect(),
+                outputs,
+            };
+            build.write(&self.output_directory, &self.program_name)?;
+            self.record_artifact(&CachedBuild::path(&self.output_directory, &self.program_name));
+        }
+
+        self.prune_output()?;
+
+        Ok((BuildSymbols::Fresh(symbol_table), bytecode))
+    }
 
-        let bytecode = CodeGenerator::do_pass((&self.ast, self.handler))?;
+    /// Reuses a cached build of the program, emitting its warnings and writing its outputs again.
+    /// Only the program's structs are returned, since the cache records no other symbols.
+    fn reuse_build(&mut self, build: CachedBuild) -> Result<(BuildSymbols, String)> {
+        // The source files that the warnings refer to are loaded into the source map, so that their spans can be restored.
+        let mut source_files = HashMap::new();
+        for warning in build.warnings {
+            let span = warning
+                .span
+                .as_ref()
+                .and_then(|span| Self::restore_span(&mut source_files, span));
+            self.handler.emit_warning(warning.into_warning(span));
+        }
+        for (file, contents) in build.outputs.iter() {
+            let path = self.output_directory.join(file);
+            fs::write(&path, contents).map_err(|e| CompilerError::failed_to_write_cached_output(&path, e))?;
+            self.record_artifact(&path);
+        }
+        self.record_artifact(&CachedBuild::path(&self.output_directory, &self.program_name));
+        self.prune_output()?;
 
-        Ok((symbol_table, bytecode))
+        self.ast = Ast::new(build.ast);
+        self.fingerprint = build.fingerprint;
+        self.statistics = Some(build.statistics);
+        Ok((BuildSymbols::Cached(build.structs), build.bytecode))
+    }
+
+    /// Returns the span of the current session that covers the range of `span`, loading its source file if it is not yet loaded.
+    /// Returns `None` if the source file can no longer be read or no longer contains the range.
+    fn restore_span(source_files: &mut HashMap<String, Option<Rc<SourceFile>>>, span: &JsonSpan) -> Option<Span> {
+        let source_file = source_files
+            .entry(span.file.clone())
+            .or_insert_with(|| with_session_globals(|s| s.source_map.load_file(Path::new(&span.file))).ok())
+            .as_ref()?;
+        let (lo, hi) = (u32::try_from(span.lo).ok()?, u32::try_from(span.hi).ok()?);
+        (span.hi <= source_file.src.len())
+            .then(|| Span::new(source_file.start_pos + BytePos(lo), source_file.start_pos + BytePos(hi)))
+    }
+
+    /// Removes the files written to the output directory by previous builds of the program that this build did not write,
+    /// and replaces the artifact manifest with the files that this build wrote.
+    fn prune_output(&self) -> Result<()> {
+        ArtifactManifest::load(&self.output_directory, &self.program_name)
+            .remove_files(&self.output_directory, &self.artifacts)?;
+        self.artifacts.write(&self.output_directory, &self.program_name)
+    }
+
+    /// Removes the files written to the output directory by previous builds of the program, along with their manifest.
+    pub fn clean_output(&self) -> Result<()> {
+        ArtifactManifest::load(&self.output_directory, &self.program_name)
+            .remove_files(&self.output_directory, &ArtifactManifest::default())?;
+        let path = ArtifactManifest::path(&self.output_directory, &self.program_name);
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(CompilerError::failed_to_remove_artifact(&path, e).into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Records that the file at `path`, in the output directory, was written by this build.
+    fn record_artifact(&mut self, path: &Path) {
+        if let Ok(relative) = path.strip_prefix(&self.output_directory) {
+            self.artifacts.record(relative);
+        }
+    }
+
+    /// Returns the declarations of the functions and finalize blocks of the program, keyed as expected by [`Disassembly::new`].
+    fn leo_functions(&self) -> HashMap<String, LeoFunction> {
+        let leo_function = |span, inputs: &[leo_ast::Input]| {
+            with_session_globals(|s| s.source_map.span_to_location(span)).map(|location| LeoFunction {
+                span: LocationSpan {
+                    line_start: location.line_start,
+                    col_start: location.col_start,
+                    line_stop: location.line_stop,
+                    col_stop: location.col_stop,
+                },
+                inputs: inputs.iter().map(|input| input.identifier().to_string()).collect(),
+            })
+        };
+
+        let mut functions = HashMap::new();
+        for function in self
+            .ast
+            .as_repr()
+            .program_scopes
+            .values()
+            .flat_map(|scope| scope.functions.values())
+        {
+            let name = function.identifier.to_string();
+            if let Some(finalize) = &function.finalize {
+                functions.extend(
+                    leo_function(finalize.span, &finalize.input)
+                        .map(|finalize| (format!("{}/finalize", name), finalize)),
+                );
+            }
+            functions.extend(leo_function(function.span, &function.input).map(|function| (name, function)));
+        }
+        functions
+    }
+
+    /// Returns the key identifying the current build in the cache.
+    /// The key hashes the compiler version, the options, and the sources of the program and of every file that it may import.
+    fn cache_key(&self) -> Result<String> {
+        // The options are serialized as JSON, whose form, unlike their `Debug` form, is stable across compiler versions.
+        let options = serde_json::to_string(&(&self.build_options, &self.output_options))
+            .expect("Failed to serialize the build options.");
+        let mut key = format!(
+            "{}\n{}.{}\n{}\n",
+            env!("CARGO_PKG_VERSION"),
+            self.program_name,
+            self.network,
+            options
+        )
+        .into_bytes();
+        key.extend(
+            fs::read(&self.main_file_path).map_err(|e| CompilerError::file_read_error(&self.main_file_path, e))?,
+        );
+
+        // Imports are resolved from the `imports` directory of the working directory,
+        // and modules from the directory of the main file, so every file in them and in their subdirectories is hashed.
+        // The outputs of builds are not, since they would change the key on every build: the output directory is skipped,
+        // as are the files that builds recorded in its manifest, in case it is the directory of the main file.
+        let mut outputs: IndexSet<PathBuf> = ArtifactManifest::load(&self.output_directory, &self.program_name)
+            .files
+            .iter()
+            .map(|file| self.output_directory.join(file))
+            .collect();
+        outputs.insert(ArtifactManifest::path(&self.output_directory, &self.program_name));
+        outputs.insert(self.output_directory.clone());
+        let imports_directory = std::env::current_dir()
+            .map_err(|e| CompilerError::file_read_error(".", e))?
+            .join("imports");
+        let modules_directory = self.main_file_path.parent().map(Path::to_path_buf).unwrap_or_default();
+        for directory in [imports_directory, modules_directory] {
+            let mut files = Vec::new();
+            Self::collect_files(&directory, &outputs, &mut files)?;
+            files.sort();
+            for file in files {
+                let relative = file.strip_prefix(&directory).unwrap_or(&file);
+                key.extend(format!("\n{}\n", relative.display()).into_bytes());
+                key.extend(fs::read(&file).map_err(|e| CompilerError::file_read_error(&file, e))?);
+            }
+        }
+
+        Ok(sha256_hex(&key))
+    }
+
+    /// Appends the paths of the files in `directory` and in its subdirectories to `files`, except for those in `skip`.
+    /// A directory that does not exist contains no files.
+    fn collect_files(directory: &Path, skip: &IndexSet<PathBuf>, files: &mut Vec<PathBuf>) -> Result<()> {
+        let entries = match fs::read_dir(directory) {
+            Ok(entries) => entries,
+            Err(_) => return Ok(()),
+        };
+        for entry in entries {
+            let path = entry.map_err(|e| CompilerError::file_read_error(directory, e))?.path();
+            if skip.contains(&path) {
+                continue;
+            }
+            match path.is_dir() {
+                true => Self::collect_files(&path, skip, files)?,
+                false => files.push(path),
+            }
+        }
+        Ok(())
     }
 
     /// Returns a compiled Leo program.
     pub fn compile(&mut self) -> Result<SymbolTable> {
         self.parse_program()?;
-        self.compiler_stages()
+        let (symbol_table, _) = self.compiler_stages()?;
+        Ok(symbol_table)
+    }
+
+    /// Writes the AST to the file of `snapshot`, if the snapshot is enabled.
+    fn write_ast_snapshot(&mut self, snapshot: AstSnapshot) -> Result<()> {
+        match self.output_options.ast_snapshots.contains(&snapshot) {
+            true if self.output_options.source_names => {
+                let ast = Ast::new(restore_source_names(self.ast.as_repr().clone()));
+                self.write_ast_to_json(&ast, snapshot.file_name())?;
+            }
+            true => self.write_ast_to_json(&self.ast, snapshot.file_name())?,
+            false => return Ok(()),
+        }
+        self.record_artifact(&self.output_directory.join(snapshot.file_name()));
+        Ok(())
     }
 
     /// Writes the AST to a JSON file.
-    fn write_ast_to_json(&self, file_name: &str) -> Result<()> {
+    fn write_ast_to_json(&self, ast: &Ast, file_name: &str) -> Result<()> {
         // Remove `Span`s if they are not enabled.
         if self.output_options.spans_enabled {
-            self.ast.to_json_file(self.output_directory.clone(), file_name)?;
+            ast.to_json_file(self.output_directory.clone(), file_name)?;
         } else {
-            self.ast
-                .to_json_file_without_keys(self.output_directory.clone(), file_name, &["span"])?;
+            ast.to_json_file_without_keys(self.output_directory.clone(), file_name, &["span"])?;
         }
         Ok(())
     }
 }
+
+/// Returns the source of `program`, preceded by the source of every program that it imports.
+/// The source is printed from the AST, so it does not depend on formatting or comments.
+fn resolved_source(program: &Program) -> String {
+    let mut source = program.imports.values().map(resolved_source).collect::<String>();
+    source.push_str(&program.to_string());
+    source
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use leo_ast::{Block, Expression, ExpressionVisitor, Node, NodeId, ProgramVisitor, Statement, StatementVisitor};
+    use leo_span::symbol::create_session_if_not_set_then;
+
+    #[test]
+    fn test_source_names() {
+        create_session_if_not_set_then(|_| {
+            let directory = tempfile::tempdir().expect("Failed to open temporary directory");
+            let handler = Handler::default();
+            let output_options = OutputOptions {
+                ast_snapshots: vec![AstSnapshot::Ssa],
+                source_names: true,
+                ..Default::default()
+            };
+            let mut compiler = Compiler::new(
+                "test".to_string(),
+                "aleo".to_string(),
+                &handler,
+                directory.path().join("main.leo"),
+                directory.path().to_path_buf(),
+                Some(output_options),
+                None,
+            );
+
+            let program = "program test.aleo {
+                transition main(a: u8, b: bool) -> u8 {
+                    let x: u8 = a;
+                    if b {
+                        x = x + 1u8;
+                    }
+                    let y: u8 = x * 2u8;
+                    return y;
+                }
+            }";
+            compiler
+                .parse_program_from_string(program, FileName::Custom("test".into()))
+                .unwrap();
+            let st = compiler.symbol_table_pass().unwrap();
+            let (st, type_table, ..) = compiler.type_checker_pass(st).unwrap();
+            let (_, mut type_table) = compiler.loop_unrolling_pass(st, type_table).unwrap();
+            compiler.static_single_assignment_pass(&mut type_table).unwrap();
+
+            // The versions of `x` are numbered, `y` has a single version, and the variables generated for the conditional are unchanged.
+            let ast = fs::read_to_string(directory.path().join("ssa_ast.json")).unwrap();
+            for name in ["x#1", "x#2", "x#3", "\\\"y\\\"", "$var$"] {
+                assert!(ast.contains(name), "`{name}` is missing from the snapshot");
+            }
+            for name in ["x$", "y$"] {
+                assert!(!ast.contains(name), "`{name}` is in the snapshot");
+            }
+        });
+    }
+
+    #[test]
+    fn test_ssa_numbering() {
+        create_session_if_not_set_then(|_| {
+            let handler = Handler::default();
+            // Returns the SSA snapshot of a program that assigns to `x` three times, and to `y` once.
+            let ssa_snapshot = |global_ssa_numbering| {
+                let directory = tempfile::tempdir().expect("Failed to open temporary directory");
+                let output_options = OutputOptions {
+                    ast_snapshots: vec![AstSnapshot::Ssa],
+                    ..Default::default()
+                };
+                let build_options = BuildOptions {
+                    global_ssa_numbering,
+                    ..Default::default()
+                };
+                let mut compiler = Compiler::new(
+                    "test".to_string(),
+                    "aleo".to_string(),
+                    &handler,
+                    directory.path().join("main.leo"),
+                    directory.path().to_path_buf(),
+                    Some(output_options),
+                    Some(build_options),
+                );
+
+                let program = "program test.aleo {
+                    transition main(a: u8, b: bool) -> u8 {
+                        let x: u8 = a;
+                        if b {
+                            x = x + 1u8;
+                        }
+                        let y: u8 = x * 2u8;
+                        return y;
+                    }
+                }";
+                compiler
+                    .parse_program_from_string(program, FileName::Custom("test".into()))
+                    .unwrap();
+                let st = compiler.symbol_table_pass().unwrap();
+                let (st, type_table, ..) = compiler.type_checker_pass(st).unwrap();
+                let (_, mut type_table) = compiler.loop_unrolling_pass(st, type_table).unwrap();
+                compiler.static_single_assignment_pass(&mut type_table).unwrap();
+                fs::read_to_string(directory.path().join("ssa_ast.json")).unwrap()
+            };
+
+            // Each variable is numbered from zero.
+            let ast = ssa_snapshot(false);
+            for name in ["x$0", "x$1", "x$2", "y$0"] {
+                assert!(ast.contains(name), "`{name}` is missing from the snapshot");
+            }
+            assert!(!ast.contains("x$3"), "`x$3` is in the snapshot");
+
+            // All variables share a counter, so `y` is not numbered from zero.
+            let ast = ssa_snapshot(true);
+            assert!(ast.contains("x$0"), "`x$0` is missing from the snapshot");
+            assert!(!ast.contains("y$0"), "`y$0` is in the snapshot");
+        });
+    }
+
+    #[test]
+    fn test_pass_report() {
+        create_session_if_not_set_then(|_| {
+            let handler = Handler::default();
+            // Returns the summaries of the passes run over a program with a loop, with the pass report enabled or not.
+            let summaries = |pass_report| {
+                let directory = tempfile::tempdir().expect("Failed to open temporary directory");
+                let output_options = OutputOptions {
+                    pass_report,
+                    ..Default::default()
+                };
+                let mut compiler = Compiler::new(
+                    "test".to_string(),
+                    "aleo".to_string(),
+                    &handler,
+                    directory.path().join("main.leo"),
+                    directory.path().to_path_buf(),
+                    Some(output_options),
+                    None,
+                );
+
+                let program = "program test.aleo {
+                    transition main(a: u8) -> u8 {
+                        for i: u8 in 0u8..2u8 {
+                            a = a + i;
+                        }
+                        return a;
+                    }
+                }";
+                compiler
+                    .parse_program_from_string(program, FileName::Custom("test".into()))
+                    .unwrap();
+                compiler.compiler_stages().unwrap();
+                compiler.report().passes().to_vec()
+            };
+
+            // Nodes are only counted if the pass report is enabled.
+            let passes = summaries(false);
+            assert!(passes
+                .iter()
+                .all(|pass| pass.nodes_visited == 0 && pass.nodes_rewritten == 0));
+
+            let passes = summaries(true);
+            let pass = |name| passes.iter().find(|pass| pass.name == name).unwrap();
+            assert!(pass("type checker").nodes_visited > 0);
+            assert_eq!(pass("type checker").nodes_rewritten, 0);
+            assert!(pass("loop unrolling").nodes_rewritten > 0);
+
+            // Only the passes that emit diagnostics through the handler count them.
+            assert_eq!(pass("type checker").diagnostics_emitted, Some(0));
+            assert_eq!(pass("static single assignment").diagnostics_emitted, None);
+        });
+    }
+
+    #[test]
+    fn test_phi_placement() {
+        create_session_if_not_set_then(|_| {
+            let directory = tempfile::tempdir().expect("Failed to open temporary directory");
+            let handler = Handler::default();
+            let output_options = OutputOptions {
+                ast_snapshots: vec![AstSnapshot::Ssa],
+                ..Default::default()
+            };
+            let mut compiler = Compiler::new(
+                "test".to_string(),
+                "aleo".to_string(),
+                &handler,
+                directory.path().join("main.leo"),
+                directory.path().to_path_buf(),
+                Some(output_options),
+                None,
+            );
+
+            let program = "program test.aleo {
+                transition main(a: u8, b: bool, c: bool) -> u8 {
+                    let x: u8 = a;
+                    let y: u8 = a;
+                    if b {
+                        x = x + 1u8;
+                        if c {
+                            y = y * 2u8;
+                        }
+                        assert(x > 1u8);
+                    } else {
+                        x = x + 2u8;
+                        y = x;
+                    }
+                    return y;
+                }
+            }";
+            compiler
+                .parse_program_from_string(program, FileName::Custom("test".into()))
+                .unwrap();
+            let st = compiler.symbol_table_pass().unwrap();
+            let (st, type_table, ..) = compiler.type_checker_pass(st).unwrap();
+            let (_, mut type_table) = compiler.loop_unrolling_pass(st, type_table).unwrap();
+            compiler.static_single_assignment_pass(&mut type_table).unwrap();
+
+            // `y` is read after both conditional statements, so it is merged after each of them, as `y$2` and `y$4`.
+            // `x` is never read after the outer conditional statement, so its versions `x$1` and `x$2` are not merged.
+            let ast = fs::read_to_string(directory.path().join("ssa_ast.json")).unwrap();
+            for name in ["x$2", "y$2", "y$4"] {
+                assert!(ast.contains(name), "`{name}` is missing from the snapshot");
+            }
+            for name in ["x$3", "y$5"] {
+                assert!(!ast.contains(name), "`{name}` is in the snapshot");
+            }
+        });
+    }
+
+    #[test]
+    fn test_prune_output() {
+        create_session_if_not_set_then(|_| {
+            let directory = tempfile::tempdir().expect("Failed to open temporary directory");
+            let main_file_path = directory.path().join("main.leo");
+            fs::write(
+                &main_file_path,
+                "program test.aleo { transition main(a: u8) -> u8 { return a; } }",
+            )
+            .unwrap();
+
+            let handler = Handler::default();
+            let build = |ast_snapshots| {
+                let output_options = OutputOptions {
+                    ast_snapshots,
+                    ..Default::default()
+                };
+                let mut compiler = Compiler::new(
+                    "test".to_string(),
+                    "aleo".to_string(),
+                    &handler,
+                    main_file_path.clone(),
+                    directory.path().to_path_buf(),
+                    Some(output_options),
+                    None,
+                );
+                compiler.compile_and_generate_instructions().unwrap();
+                compiler
+            };
+            let exists = |file_name: &str| directory.path().join(file_name).exists();
+
+            build(vec![AstSnapshot::Initial, AstSnapshot::Ssa]);
+            assert!(exists("initial_ast.json") && exists("ssa_ast.json") && exists("test.artifacts.json"));
+
+            // The snapshot that is no longer written is removed.
+            let compiler = build(vec![AstSnapshot::Initial]);
+            assert!(exists("initial_ast.json") && !exists("ssa_ast.json"));
+
+            compiler.clean_output().unwrap();
+            assert!(!exists("initial_ast.json") && !exists("test.artifacts.json") && exists("main.leo"));
+        });
+    }
+
+    #[test]
+    fn test_incremental_build() {
+        create_session_if_not_set_then(|_| {
+            let directory = tempfile::tempdir().expect("Failed to open temporary directory");
+            let source_directory = directory.path().join("src");
+            fs::create_dir_all(source_directory.join("nested")).unwrap();
+            let main_file_path = source_directory.join("main.leo");
+            fs::write(
+                &main_file_path,
+                "program test.aleo { transition main(a: u8) -> u8 { return a; } transition noop() {} }",
+            )
+            .unwrap();
+            fs::write(source_directory.join("nested").join("notes.txt"), "first").unwrap();
+
+            let (handler, buffer) = Handler::new_with_buf();
+            let new_compiler = || {
+                let output_options = OutputOptions {
+                    ast_snapshots: vec![AstSnapshot::Initial],
+                    symbol_table: true,
+                    ..Default::default()
+                };
+                let build_options = BuildOptions {
+                    incremental: true,
+                    ..Default::default()
+                };
+                Compiler::new(
+                    "test".to_string(),
+                    "aleo".to_string(),
+                    &handler,
+                    main_file_path.clone(),
+                    directory.path().to_path_buf(),
+                    Some(output_options),
+                    Some(build_options),
+                )
+            };
+            let exists = |file_name: &str| directory.path().join(file_name).exists();
+
+            let (symbols, bytecode) = new_compiler().compile_and_generate_instructions().unwrap();
+            assert!(matches!(symbols, BuildSymbols::Fresh(_)));
+            assert_eq!(handler.warnings().len(), 1);
+            let key = new_compiler().cache_key().unwrap();
+
+            // The reused build emits its warning again, and writes the outputs that were removed since.
+            fs::remove_file(directory.path().join("initial_ast.json")).unwrap();
+            fs::remove_file(directory.path().join("symbol_table.json")).unwrap();
+            let mut compiler = new_compiler();
+            let (symbols, reused) = compiler.compile_and_generate_instructions().unwrap();
+            assert!(matches!(symbols, BuildSymbols::Cached(_)));
+            assert_eq!(reused, bytecode);
+            assert!(compiler.report.passes().is_empty());
+            let warnings = buffer.extract_warnings().into_inner();
+            assert_eq!(warnings.len(), 2);
+            assert_eq!(warnings[0].to_string(), warnings[1].to_string());
+            assert!(exists("initial_ast.json") && exists("symbol_table.json"));
+
+            // Files in subdirectories of the source directory are part of the key.
+            fs::write(source_directory.join("nested").join("notes.txt"), "second").unwrap();
+            assert_ne!(new_compiler().cache_key().unwrap(), key);
+        });
+    }
+
+    #[test]
+    fn test_incremental_build_outputs_are_not_hashed() {
+        create_session_if_not_set_then(|_| {
+            // The outputs are written to the directory of the main file, and to a subdirectory of it.
+            let directory = tempfile::tempdir().expect("Failed to open temporary directory");
+            let main_file_path = directory.path().join("main.leo");
+            fs::write(
+                &main_file_path,
+                "program test.aleo { transition main(a: u8) -> u8 { return a; } }",
+            )
+            .unwrap();
+
+            let handler = Handler::default();
+            let new_compiler = |output_directory: PathBuf| {
+                let output_options = OutputOptions {
+                    ast_snapshots: vec![AstSnapshot::Initial],
+                    ..Default::default()
+                };
+                let build_options = BuildOptions {
+                    incremental: true,
+                    ..Default::default()
+                };
+                Compiler::new(
+                    "test".to_string(),
+                    "aleo".to_string(),
+                    &handler,
+                    main_file_path.clone(),
+                    output_directory,
+                    Some(output_options),
+                    Some(build_options),
+                )
+            };
+
+            for output_directory in [directory.path().to_path_buf(), directory.path().join("build")] {
+                fs::create_dir_all(&output_directory).unwrap();
+                let key = new_compiler(output_directory.clone()).cache_key().unwrap();
+                let (symbols, _) = new_compiler(output_directory.clone())
+                    .compile_and_generate_instructions()
+                    .unwrap();
+                assert!(matches!(symbols, BuildSymbols::Fresh(_)));
+                assert!(output_directory.join("initial_ast.json").exists());
+                assert_eq!(new_compiler(output_directory.clone()).cache_key().unwrap(), key);
+                let (symbols, _) = new_compiler(output_directory)
+                    .compile_and_generate_instructions()
+                    .unwrap();
+                assert!(matches!(symbols, BuildSymbols::Cached(_)));
+            }
+        });
+    }
+
+    #[test]
+    fn test_generic_function_instances() {
+        create_session_if_not_set_then(|_| {
+            let directory = tempfile::tempdir().expect("Failed to open temporary directory");
+            let main_file_path = directory.path().join("main.leo");
+            fs::write(
+                &main_file_path,
+                "program test.aleo {
+                    transition main(a: u8, b: u8, c: u32, d: u32) -> (u8, u32) {
+                        return (distance(a, b), distance(c, d));
+                    }
+
+                    function distance<T: integer>(a: T, b: T) -> T {
+                        let difference: T = a > b ? a - b : b - a;
+                        return difference;
+                    }
+                }",
+            )
+            .unwrap();
+
+            let handler = Handler::default();
+            let new_compiler = || {
+                Compiler::new(
+                    "test".to_string(),
+                    "aleo".to_string(),
+                    &handler,
+                    main_file_path.clone(),
+                    directory.path().to_path_buf(),
+                    None,
+                    None,
+                )
+            };
+
+            // The types that the type table records for the nodes of each instance are those of the instance.
+            let mut compiler = new_compiler();
+            compiler.parse_program().unwrap();
+            let state = PassManager::frontend()
+                .run(&mut compiler, PassState::default())
+                .unwrap();
+            let state = PassManager::default()
+                .register(CompilerPass::EnumLowering)
+                .register(CompilerPass::Monomorphization)
+                .run(&mut compiler, state)
+                .unwrap();
+            let type_table = state.type_table.unwrap();
+            let mut ids = NodeIds::default();
+            ids.visit_program(compiler.ast.as_repr());
+            for id in ids.0 {
+                if let Some(type_) = type_table.get_by_id(id) {
+                    assert!(!type_.to_string().contains('T'), "node {id} has the type `{type_}`");
+                }
+            }
+
+            // Each instance is compiled with the types that it is instantiated with.
+            let (_, bytecode) = new_compiler().compile_and_generate_instructions().unwrap();
+            for type_ in ["u8", "u32"] {
+                let closure = format!(
+                    "closure distance_{type_}:
+    input r0 as {type_};
+    input r1 as {type_};
+    gt r0 r1 into r2;
+    sub r0 r1 into r3;
+    sub r1 r0 into r4;
+    ternary r2 r3 r4 into r5;
+    output r5 as {type_};
+"
+                );
+                assert!(
+                    bytecode.contains(&closure),
+                    "`distance_{type_}` is missing from\n{bytecode}"
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn test_conditional_record_output() {
+        create_session_if_not_set_then(|_| {
+            let directory = tempfile::tempdir().expect("Failed to open temporary directory");
+            let main_file_path = directory.path().join("main.leo");
+            fs::write(
+                &main_file_path,
+                include_str!("../../../tests/compiler/records/conditional_record_output.leo"),
+            )
+            .unwrap();
+
+            let handler = Handler::default();
+            let (_, bytecode) = Compiler::new(
+                "test".to_string(),
+                "aleo".to_string(),
+                &handler,
+                main_file_path,
+                directory.path().to_path_buf(),
+                None,
+                None,
+            )
+            .compile_and_generate_instructions()
+            .unwrap();
+
+            // The members of the records are selected and cast in the order of their declaration in the bytecode,
+            // in which `owner` and `gates` come first, and keep their visibility.
+            let expected = "program test.aleo;
+
+interface Amount:
+    value as u64;
+    memo as u8;
+
+record token:
+    owner as address.private;
+    gates as u64.private;
+    amount as Amount.private;
+
+record ticket:
+    owner as address.private;
+    gates as u64.private;
+    seat as u8.private;
+
+
+function choose:
+    input r0 as boolean.private;
+    input r1 as token.record;
+    input r2 as token.record;
+    ternary r0 r1.owner r2.owner into r3;
+    ternary r0 r1.gates r2.gates into r4;
+    ternary r0 r1.amount.value r2.amount.value into r5;
+    ternary r0 r1.amount.memo r2.amount.memo into r6;
+    cast r5 r6 into r7 as Amount;
+    cast r3 r4 r7 into r8 as token.record;
+    output r8 as token.record;
+
+function pick:
+    input r0 as boolean.private;
+    input r1 as ticket.record;
+    input r2 as ticket.record;
+    ternary r0 r1.owner r2.owner into r3;
+    ternary r0 r1.gates r2.gates into r4;
+    ternary r0 r1.seat r2.seat into r5;
+    cast r3 r4 r5 into r6 as ticket.record;
+    output r6 as ticket.record;
+
+function mint:
+    input r0 as boolean.private;
+    input r1 as address.private;
+    input r2 as u64.private;
+    cast r2 0u8 into r3 as Amount;
+    cast r1 0u64 r3 into r4 as token.record;
+    cast 0u64 1u8 into r5 as Amount;
+    cast self.caller 0u64 r5 into r6 as token.record;
+    ternary r0 r4.owner r6.owner into r7;
+    ternary r0 r4.gates r6.gates into r8;
+    ternary r0 r4.amount.value r6.amount.value into r9;
+    ternary r0 r4.amount.memo r6.amount.memo into r10;
+    cast r9 r10 into r11 as Amount;
+    cast r7 r8 r11 into r12 as token.record;
+    output r12 as token.record;
+
+";
+            assert_eq!(bytecode, expected);
+        });
+    }
+
+    /// Collects the IDs of the expressions, statements, and blocks of a program.
+    #[derive(Default)]
+    struct NodeIds(Vec<NodeId>);
+
+    impl<'a> ExpressionVisitor<'a> for NodeIds {
+        type AdditionalInput = ();
+        type Output = ();
+
+        fn visit_expression(&mut self, input: &'a Expression, additional: &Self::AdditionalInput) -> Self::Output {
+            self.0.push(input.id());
+            match input {
+                Expression::Access(access) => self.visit_access(access, additional),
+                Expression::Array(array) => self.visit_array(array, additional),
+                Expression::Binary(binary) => self.visit_binary(binary, additional),
+                Expression::Call(call) => self.visit_call(call, additional),
+                Expression::Cast(cast) => self.visit_cast(cast, additional),
+                Expression::Struct(struct_) => self.visit_struct_init(struct_, additional),
+                Expression::Err(err) => self.visit_err(err, additional),
+                Expression::Identifier(identifier) => self.visit_identifier(identifier, additional),
+                Expression::Literal(literal) => self.visit_literal(literal, additional),
+                Expression::Ternary(ternary) => self.visit_ternary(ternary, additional),
+                Expression::Tuple(tuple) => self.visit_tuple(tuple, additional),
+                Expression::Unary(unary) => self.visit_unary(unary, additional),
+            }
+        }
+    }
+
+    impl<'a> StatementVisitor<'a> for NodeIds {
+        fn visit_statement(&mut self, input: &'a Statement) {
+            match input {
+                Statement::Block(block) => self.visit_block(block),
+                Statement::Assign(assign) => {
+                    self.0.push(assign.id);
+                    self.visit_expression(&assign.place, &());
+                    self.visit_assign(assign);
+                }
+                Statement::Definition(definition) => {
+                    self.0.push(definition.id);
+                    self.visit_expression(&definition.place, &());
+                    self.visit_definition(definition);
+                }
+                Statement::Assert(assert) => {
+                    self.0.push(assert.id);
+                    self.visit_assert(assert);
+                }
+                Statement::Conditional(conditional) => {
+                    self.0.push(conditional.id);
+                    self.visit_conditional(conditional);
+                }
+                Statement::Iteration(iteration) => {
+                    self.0.push(iteration.id);
+                    self.visit_iteration(iteration);
+                }
+                Statement::Return(return_) => {
+                    self.0.push(return_.id);
+                    self.visit_return(return_);
+                }
+                statement => panic!("unexpected statement `{statement}`"),
+            }
+        }
+
+        fn visit_block(&mut self, input: &'a Block) {
+            self.0.push(input.id);
+            input
+                .statements
+                .iter()
+                .for_each(|statement| self.visit_statement(statement));
+        }
+    }
+
+    impl<'a> ProgramVisitor<'a> for NodeIds {}
+
+    #[test]
+    fn test_unique_node_ids() {
+        create_session_if_not_set_then(|_| {
+            let directory = tempfile::tempdir().expect("Failed to open temporary directory");
+            let handler = Handler::default();
+            let mut compiler = Compiler::new(
+                "test".to_string(),
+                "aleo".to_string(),
+                &handler,
+                directory.path().join("main.leo"),
+                directory.path().to_path_buf(),
+                None,
+                None,
+            );
+
+            let program = "program test.aleo {
+                transition main(a: u8, b: u32) -> (u8, u32) {
+                    for i: u8 in 0u8..2u8 {
+                        for j: u8 in 0u8..2u8 {
+                            a = sum(a, i + j);
+                        }
+                    }
+                    return (a, sum(b, 1u32));
+                }
+
+                inline sum<T: integer>(a: T, b: T) -> T {
+                    let c: T = a;
+                    for i: u8 in 0u8..3u8 {
+                        c = c + b;
+                    }
+                    return c;
+                }
+            }";
+            compiler
+                .parse_program_from_string(program, FileName::Custom("test".into()))
+                .unwrap();
+            let state = PassManager::frontend()
+                .run(&mut compiler, PassState::default())
+                .unwrap();
+            PassManager::default()
+                .register(CompilerPass::EnumLowering)
+                .register(CompilerPass::Monomorphization)
+                .register(CompilerPass::LoopUnrolling)
+                .run(&mut compiler, state)
+                .unwrap();
+
+            // The unrolled iterations and the instances of `sum` are copies, whose nodes are nonetheless distinct.
+            let mut ids = NodeIds::default();
+            ids.visit_program(compiler.ast.as_repr());
+            let unique = ids.0.iter().collect::<IndexSet<_>>();
+            assert_eq!(unique.len(), ids.0.len());
+        });
+    }
+}
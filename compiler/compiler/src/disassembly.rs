@@ -0,0 +1,222 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Body, FunctionSourceMap, InstructionSpan, LocationSpan};
+
+use leo_errors::{CompilerError, Result};
+use leo_ir::Program;
+
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::Path};
+
+/// The Leo code of a function or finalize block, as declared in the Leo program.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LeoFunction {
+    /// The location of the declaration in the Leo program.
+    pub span: LocationSpan,
+    /// The names of the parameters, in order.
+    pub inputs: Vec<String>,
+}
+
+/// The Leo code of each closure, function, and finalize block of an Aleo program, paired with its instructions.
+/// Web tools can use it to show the Leo program and its Aleo instructions side by side.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Disassembly {
+    /// The Aleo program, e.g. `hello.aleo`.
+    pub program: String,
+    /// The path of the Leo program.
+    pub file: String,
+    /// The closures, functions, and finalize blocks, in the order that they appear in the Aleo program.
+    pub functions: Vec<FunctionDisassembly>,
+}
+
+/// The disassembly of a closure, function, or finalize block.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionDisassembly {
+    pub name: String,
+    /// One of `closure`, `function`, or `finalize`.
+    pub kind: String,
+    /// The location of the declaration in the Leo program.
+    /// Closures generated by the compiler, e.g. by function outlining, have no location.
+    pub span: Option<LocationSpan>,
+    /// The lines of the Leo program that the declaration spans, starting at `span.line_start`.
+    pub source: Option<String>,
+    pub instructions: Vec<InstructionSpan>,
+    /// The registers, in order.
+    pub registers: Vec<RegisterSource>,
+}
+
+/// Where the value of a register comes from.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisterSource {
+    /// The register, e.g. `r0`.
+    pub register: String,
+    /// The parameter of the Leo function, if the register is an input.
+    pub input: Option<String>,
+    /// The index of the instruction that assigns the register, if it is not an input.
+    pub instruction: Option<usize>,
+}
+
+impl FunctionDisassembly {
+    fn new(body: &Body, leo_function: Option<&LeoFunction>, lines: &[&str]) -> Self {
+        let mut registers: Vec<RegisterSource> = body
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(index, input)| RegisterSource {
+                register: input.register.to_string(),
+                input: leo_function.and_then(|function| function.inputs.get(index).cloned()),
+                instruction: None,
+            })
+            .collect();
+        for (index, instruction) in body.instructions.iter().enumerate() {
+            let mut instruction = instruction.clone();
+            registers.extend(
+                instruction
+                    .destinations_mut()
+                    .into_iter()
+                    .map(|register| RegisterSource {
+                        register: register.to_string(),
+                        input: None,
+                        instruction: Some(index),
+                    }),
+            );
+        }
+
+        let span = leo_function.map(|function| function.span);
+        Self {
+            name: body.name.to_string(),
+            kind: body.kind.clone(),
+            span,
+            source: span.and_then(|span| {
+                lines
+                    .get(span.line_start.checked_sub(1)?..span.line_stop)
+                    .map(|lines| lines.join("\n"))
+            }),
+            instructions: FunctionSourceMap::new(body).instructions,
+            registers,
+        }
+    }
+}
+
+impl Disassembly {
+    /// Returns the disassembly of `program`, which was compiled from the Leo program `source` at `file`.
+    /// The declarations of the Leo program are given by `leo_functions`, keyed by name, where finalize blocks are named `{function}/finalize`.
+    pub fn new(program: &Program, file: &Path, source: &str, leo_functions: &HashMap<String, LeoFunction>) -> Self {
+        let lines: Vec<&str> = source.lines().collect();
+        let functions = Body::all(program)
+            .iter()
+            .map(|body| {
+                let key = match body.kind.as_str() {
+                    "finalize" => format!("{}/finalize", body.name),
+                    _ => body.name.to_string(),
+                };
+                FunctionDisassembly::new(body, leo_functions.get(&key), &lines)
+            })
+            .collect();
+
+        Self {
+            program: format!("{}.{}", program.name, program.network),
+            file: file.display().to_string(),
+            functions,
+        }
+    }
+
+    /// Writes the disassembly to `path`.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).expect("Failed to serialize the disassembly.");
+        fs::write(path, json).map_err(|e| CompilerError::failed_to_write_disassembly(path, e))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use leo_ir::{
+        testing::{function, program},
+        BinaryOperation, Finalize, FunctionKind, Input, Instruction, Operand, Register, ValueType,
+    };
+
+    #[test]
+    fn test_disassembly() {
+        let add = Instruction::Binary {
+            operation: BinaryOperation::Add,
+            left: Operand::Register(Register(0)),
+            right: Operand::Register(Register(1)),
+            destination: Register(2),
+        };
+        let output = Instruction::Output(
+            Operand::Register(Register(2)),
+            ValueType::Plaintext("u8".to_string(), None),
+        );
+        let mut main = function(FunctionKind::Function, "main", vec![add, output], vec![Some(3)]);
+        main.finalize = Some(Finalize {
+            name: "main".to_string(),
+            inputs: main.inputs.clone(),
+            instructions: Vec::new(),
+            locations: Vec::new(),
+        });
+        main.inputs.push(Input {
+            register: Register(1),
+            type_: ValueType::Plaintext("u8".to_string(), None),
+        });
+        let program = program(vec![main]);
+        let source = "program test.aleo {\n    transition main(a: u8, b: u8) -> u8 {\n        return a + b;\n    }\n}";
+        let leo_functions = HashMap::from([(
+            "main".to_string(),
+            LeoFunction {
+                span: LocationSpan {
+                    line_start: 2,
+                    col_start: 5,
+                    line_stop: 4,
+                    col_stop: 6,
+                },
+                inputs: vec!["a".to_string(), "b".to_string()],
+            },
+        )]);
+
+        let disassembly = Disassembly::new(&program, Path::new("src/main.leo"), source, &leo_functions);
+        assert_eq!(disassembly.program, "test.aleo");
+        let main = &disassembly.functions[0];
+        assert_eq!(
+            main.source.as_deref(),
+            Some("    transition main(a: u8, b: u8) -> u8 {\n        return a + b;\n    }")
+        );
+        assert_eq!(main.instructions[0].instruction, "add r0 r1 into r2;");
+        assert_eq!(main.instructions[0].span.map(|span| span.line_start), Some(3));
+        let registers: Vec<_> = main
+            .registers
+            .iter()
+            .map(|register| {
+                (
+                    register.register.as_str(),
+                    register.input.as_deref(),
+                    register.instruction,
+                )
+            })
+            .collect();
+        assert_eq!(
+            registers,
+            [("r0", Some("a"), None), ("r1", Some("b"), None), ("r2", None, Some(0))]
+        );
+
+        // The finalize block is not declared in `leo_functions`, so its source is unknown.
+        let finalize = &disassembly.functions[1];
+        assert_eq!((finalize.kind.as_str(), finalize.span), ("finalize", None));
+        assert_eq!(finalize.registers[0].input, None);
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_errors::{CompilerError, Result};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{fs, path::Path};
+
+/// Content hashes that identify a build, so that deployed bytecode can be traced back to the source that produced it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildFingerprint {
+    /// A SHA256 hash of the program, including its imports, after type checking and before any optimizations.
+    /// Spans are excluded, so changes to formatting or comments do not change the hash.
+    pub program_hash: String,
+    /// A SHA256 hash of the generated Aleo instructions.
+    pub bytecode_hash: String,
+}
+
+impl BuildFingerprint {
+    /// Writes the fingerprint as JSON to `file_name` in `directory`.
+    pub fn write_to_file(&self, directory: &Path, file_name: &str) -> Result<()> {
+        let path = directory.join(file_name);
+        let json = serde_json::to_string_pretty(self).expect("Failed to serialize the build fingerprint.");
+        fs::write(&path, json).map_err(|e| CompilerError::failed_to_write_build_fingerprint(&path, e))?;
+        Ok(())
+    }
+}
+
+/// Returns the SHA256 hash of `bytes` as a hexadecimal string.
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
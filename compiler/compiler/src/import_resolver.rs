@@ -0,0 +1,175 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The import resolver merges the modules imported with `import foo;` into the program scope.
+
+use leo_ast::{Identifier, Module, NodeBuilder, Program, ProgramScope};
+use leo_errors::emitter::Handler;
+use leo_errors::{CompilerError, Result};
+use leo_parser::Edition;
+use leo_span::source_map::FileName;
+use leo_span::symbol::with_session_globals;
+use leo_span::Symbol;
+
+use indexmap::{IndexMap, IndexSet};
+use std::fs;
+use std::path::PathBuf;
+
+/// Resolves the modules imported by a program, reading the module `foo` from `foo.leo` in the `src/` directory.
+/// Modules may import other modules, which are resolved relative to the same directory.
+pub struct ImportResolver<'a> {
+    /// The handler is used for error and warning emissions.
+    handler: &'a Handler,
+    /// The builder of the IDs of the nodes in the parsed modules.
+    node_builder: &'a NodeBuilder,
+    /// The directory that modules are read from.
+    directory: PathBuf,
+    /// The edition that modules are parsed for.
+    edition: Edition,
+    /// The modules being resolved, from the outermost import to the current one.
+    stack: Vec<Symbol>,
+    /// The modules that have been merged. A module imported by several others is only merged once.
+    resolved: IndexSet<Symbol>,
+    /// The names of the definitions in the program scope, qualified by the module they come from, e.g. `foo::bar`.
+    definitions: IndexMap<Symbol, String>,
+}
+
+impl<'a> ImportResolver<'a> {
+    /// Returns a new import resolver that reads modules from `directory`.
+    pub fn new(handler: &'a Handler, node_builder: &'a NodeBuilder, directory: PathBuf, edition: Edition) -> Self {
+        Self {
+            handler,
+            node_builder,
+            directory,
+            edition,
+            stack: Vec::new(),
+            resolved: IndexSet::new(),
+            definitions: IndexMap::new(),
+        }
+    }
+
+    /// Merges the definitions of the modules that `program` imports, directly or not, into its program scope.
+    pub fn resolve(mut self, program: &mut Program) -> Result<()> {
+        if program.modules.is_empty() {
+            return Ok(());
+        }
+
+        // Note that parsing enforces that there is exactly one program scope in a file.
+        let scope = program.program_scopes.values_mut().next().unwrap();
+        let names = scope
+            .consts
+            .keys()
+            .chain(scope.structs.keys())
+            .chain(scope.enums.keys())
+            .chain(scope.mappings.keys())
+            .chain(scope.functions.keys());
+        for id in names {
+            self.definitions.insert(id.name, id.name.to_string());
+        }
+
+        self.resolve_modules(&program.modules, scope)
+    }
+
+    /// Resolves the modules imported by one file, in the order they are imported.
+    fn resolve_modules(&mut self, modules: &[Identifier], scope: &mut ProgramScope) -> Result<()> {
+        for (i, module) in modules.iter().enumerate() {
+            if modules[..i].iter().any(|other| other.name == module.name) {
+                return Err(CompilerError::duplicate_module_import(module, module.span).into());
+            }
+        }
+
+        modules.iter().try_for_each(|module| self.resolve_module(module, scope))
+    }
+
+    /// Parses the module `module`, resolves its own imports, and then merges its definitions into `scope`.
+    fn resolve_module(&mut self, module: &Identifier, scope: &mut ProgramScope) -> Result<()> {
+        if let Some(position) = self.stack.iter().position(|name| *name == module.name) {
+            let cycle = self.stack[position..]
+                .iter()
+                .chain(std::iter::once(&module.name))
+                .map(|name| format!("`{}`", name))
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            return Err(CompilerError::cyclic_module_import(cycle, module.span).into());
+        }
+        if self.resolved.contains(&module.name) {
+            return Ok(());
+        }
+
+        // Read the module file into a string.
+        let path = self.directory.join(format!("{}.leo", module.name));
+        if !path.exists() {
+            return Err(CompilerError::import_not_found(path.display(), module.span).into());
+        }
+        let source = fs::read_to_string(&path).map_err(|e| CompilerError::file_read_error(&path, e))?;
+
+        // Register the source in the source map, so that errors in the module point into its file.
+        let module_sf = with_session_globals(|s| s.source_map.new_source(&source, FileName::Real(path)));
+        let parsed = leo_parser::parse_module(
+            self.handler,
+            self.node_builder,
+            &module_sf.src,
+            module_sf.start_pos,
+            self.edition,
+        )?;
+
+        self.stack.push(module.name);
+        self.resolve_modules(&parsed.modules, scope)?;
+        self.stack.pop();
+
+        self.merge(module.name, parsed, scope)?;
+        self.resolved.insert(module.name);
+
+        Ok(())
+    }
+
+    /// Merges the definitions of `module` into `scope`.
+    fn merge(&mut self, module: Symbol, parsed: Module, scope: &mut ProgramScope) -> Result<()> {
+        for (id, const_) in parsed.consts {
+            self.define(module, id)?;
+            scope.consts.insert(id, const_);
+        }
+        for (id, struct_) in parsed.structs {
+            self.define(module, id)?;
+            scope.structs.insert(id, struct_);
+        }
+        for (id, enum_) in parsed.enums {
+            self.define(module, id)?;
+            scope.enums.insert(id, enum_);
+        }
+        for (id, mapping) in parsed.mappings {
+            self.define(module, id)?;
+            scope.mappings.insert(id, mapping);
+        }
+        for (id, function) in parsed.functions {
+            self.define(module, id)?;
+            scope.functions.insert(id, function);
+        }
+        Ok(())
+    }
+
+    /// Records the definition `id` of `module`, checking that no other definition has the same name.
+    fn define(&mut self, module: Symbol, id: Identifier) -> Result<()> {
+        let qualified = format!("{}::{}", module, id.name);
+        match self.definitions.get(&id.name) {
+            Some(existing) => Err(CompilerError::duplicate_module_definition(qualified, existing, id.span).into()),
+            None => {
+                self.definitions.insert(id.name, qualified);
+                Ok(())
+            }
+        }
+    }
+}
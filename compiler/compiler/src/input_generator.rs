@@ -0,0 +1,214 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! A seeded generator of the inputs of a transition, derived from the types of its inputs.
+//! Tests that execute programs use it to cover the boundaries of each type reproducibly, since the same seed always generates the same inputs.
+
+use crate::mock_ledger::Value;
+
+use leo_ast::IntegerType;
+use leo_ir::{Input, Program, ValueType};
+
+use std::str::FromStr;
+
+/// The addresses that generated inputs are drawn from.
+/// The pool is small, so that generated inputs often refer to the same accounts.
+const ADDRESSES: [&str; 4] = [
+    "aleo1qnr4dkkvkgfqph0vzc3y6z2eu975wnpz2925ntjccd5cfqxtyu8s7pyjh9",
+    "aleo1mgfq6g40l6zkhsm063n3uhr43hkwatzkwh8ugghp97csk4vd2ugs39z3qa",
+    "aleo10qerras5799u6k7rjtc9y3hcwxuykr45qra7x7dp6jgnc0923czqm0lgta",
+    "aleo1jtjc68kf2x3euxufngq0mm7szwwvhpzugqzk7ej4p4wvhym4cursppgq38",
+];
+
+/// The field elements at the boundaries of the field: zero, one, and the largest element.
+const FIELD_BOUNDARIES: [&str; 3] = [
+    "0",
+    "1",
+    "8444461749428370424248824938781546531375899335154063827935233455917409239040",
+];
+
+/// A deterministic generator of inputs.
+pub(crate) struct InputGenerator {
+    /// The state of the SplitMix64 generator.
+    state: u64,
+}
+
+impl InputGenerator {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next pseudo-random number.
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn next_u128(&mut self) -> u128 {
+        ((self.next_u64() as u128) << 64) | self.next_u64() as u128
+    }
+
+    /// Returns one of `choices`.
+    fn choose<T: Copy>(&mut self, choices: &[T]) -> T {
+        choices[(self.next_u64() % choices.len() as u64) as usize]
+    }
+
+    /// Returns inputs for `inputs`, the inputs of a transition of `program`.
+    pub(crate) fn inputs(&mut self, program: &Program, inputs: &[Input]) -> Result<Vec<Value>, String> {
+        inputs.iter().map(|input| self.value(program, &input.type_)).collect()
+    }
+
+    fn value(&mut self, program: &Program, type_: &ValueType) -> Result<Value, String> {
+        match type_ {
+            ValueType::Plaintext(type_, _) => self.plaintext(program, type_),
+            ValueType::Record(record) => self.composite(program, record),
+            ValueType::ExternalRecord { .. } => Err(format!("Inputs of type `{}` cannot be generated", type_)),
+        }
+    }
+
+    fn plaintext(&mut self, program: &Program, type_: &str) -> Result<Value, String> {
+        match type_ {
+            "address" => Ok(Value::Address(self.choose(&ADDRESSES).to_string())),
+            "boolean" => Ok(Value::Boolean(self.next_u64() % 2 == 0)),
+            // Half of the field elements are boundaries.
+            "field" => Ok(Value::Field(match self.next_u64() % 2 {
+                0 => self.choose(&FIELD_BOUNDARIES).to_string(),
+                _ => self.next_u128().to_string(),
+            })),
+            type_ => match IntegerType::from_str(type_) {
+                Ok(type_) => Ok(self.integer(type_)),
+                Err(_) => self.composite(program, type_),
+            },
+        }
+    }
+
+    /// Returns an integer of `type_`, half of which are the boundaries of the type.
+    fn integer(&mut self, type_: IntegerType) -> Value {
+        let bits = match type_ {
+            IntegerType::U8 | IntegerType::I8 => 8,
+            IntegerType::U16 | IntegerType::I16 => 16,
+            IntegerType::U32 | IntegerType::I32 => 32,
+            IntegerType::U64 | IntegerType::I64 => 64,
+            IntegerType::U128 | IntegerType::I128 => 128,
+        };
+        // Integers are sign extended to a `u128`, as expected by the mock ledger.
+        let extend = |value: u128| match type_.is_signed() {
+            true => (((value << (128 - bits)) as i128) >> (128 - bits)) as u128,
+            false => value & (u128::MAX >> (128 - bits)),
+        };
+
+        let value = match self.next_u64() % 2 {
+            0 => {
+                let max = u128::MAX >> (128 - bits + type_.is_signed() as u32);
+                let boundaries = match type_.is_signed() {
+                    // The minimum, which is one more than the maximum, and its successor.
+                    true => [max.wrapping_add(1), max.wrapping_add(2), u128::MAX, 0, 1, max - 1, max],
+                    false => [0, 1, 2, max / 2, max / 2 + 1, max - 1, max],
+                };
+                self.choose(&boundaries)
+            }
+            _ => self.next_u128(),
+        };
+        Value::Integer(type_, extend(value))
+    }
+
+    /// Returns a struct or record of `program` named `name`, each of whose members is generated independently.
+    fn composite(&mut self, program: &Program, name: &str) -> Result<Value, String> {
+        let composite = program
+            .composites
+            .iter()
+            .find(|composite| composite.name == name)
+            .ok_or_else(|| format!("Inputs of type `{}` cannot be generated", name))?;
+        let members = composite
+            .members
+            .iter()
+            .map(|(member, type_)| Ok((member.clone(), self.value(program, type_)?)))
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Value::Composite(members))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use leo_ir::{Composite, Register, Visibility};
+
+    #[test]
+    fn test_input_generator() {
+        let plaintext = |type_: &str| ValueType::Plaintext(type_.to_string(), Some(Visibility::Private));
+        let program = Program {
+            composites: vec![Composite {
+                name: "token".to_string(),
+                is_record: true,
+                members: vec![
+                    ("owner".to_string(), plaintext("address")),
+                    ("amount".to_string(), plaintext("i8")),
+                ],
+            }],
+            ..Default::default()
+        };
+        let inputs: Vec<Input> = [
+            plaintext("u8"),
+            plaintext("i128"),
+            plaintext("field"),
+            ValueType::Record("token".to_string()),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(register, type_)| Input {
+            register: Register(register as u64),
+            type_,
+        })
+        .collect();
+
+        let generate = |seed| {
+            let mut generator = InputGenerator::new(seed);
+            (0..100)
+                .map(|_| generator.inputs(&program, &inputs).unwrap())
+                .collect::<Vec<_>>()
+        };
+        // The same seed generates the same inputs.
+        let generated = generate(1);
+        assert_eq!(generated, generate(1));
+        assert_ne!(generated, generate(2));
+
+        // Every generated input is a valid literal of its type.
+        for inputs in &generated {
+            for input in inputs {
+                if let Value::Integer(..) | Value::Field(_) = input {
+                    assert_eq!(Value::from_str(&input.to_string()).as_ref(), Ok(input));
+                }
+            }
+        }
+
+        // The boundaries of each type are covered.
+        let printed: Vec<String> = generated.iter().flatten().map(|input| input.to_string()).collect();
+        for boundary in ["0u8", "255u8", "-170141183460469231731687303715884105728i128", "0field"] {
+            assert!(
+                printed.contains(&boundary.to_string()),
+                "`{}` was not generated",
+                boundary
+            );
+        }
+        assert!(printed
+            .iter()
+            .any(|input| input.starts_with("{ owner: aleo1") && input.contains("i8 }")));
+    }
+}
@@ -19,11 +19,50 @@
 #![allow(clippy::upper_case_acronyms)]
 #![doc = include_str!("../README.md")]
 
+mod artifacts;
+pub use artifacts::*;
+
+mod cache;
+pub use cache::*;
+
 mod compiler;
 pub use compiler::*;
 
+mod disassembly;
+pub use disassembly::*;
+
+mod fingerprint;
+pub use fingerprint::*;
+
+mod import_resolver;
+pub use import_resolver::*;
+
+#[cfg(test)]
+mod input_generator;
+
+#[cfg(test)]
+mod mock_ledger;
+
 mod options;
 pub use options::*;
 
+mod pass_manager;
+pub use pass_manager::*;
+
+mod profile;
+pub use profile::*;
+
+mod source_map;
+pub use source_map::*;
+
+mod report;
+pub use report::*;
+
+mod statistics;
+pub use statistics::*;
+
+mod unit_test;
+pub use unit_test::*;
+
 #[cfg(test)]
 mod test;
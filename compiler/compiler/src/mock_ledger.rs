@@ -0,0 +1,593 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! A mock ledger that executes the Aleo instructions of a program, so that tests can check the effects of finalize blocks without a network.
+//!
+//! Only booleans, integers, addresses, fields, structs, and records are supported, and fields can only be compared, stored, and output.
+//! Instructions on other values, such as groups, field arithmetic, core functions, and calls to other programs halt execution.
+
+// The `u128` arms of the macros below cast `u128` integers to themselves.
+#![allow(clippy::unnecessary_cast)]
+
+use leo_ast::IntegerType;
+use leo_ir::{BinaryOperation, CastType, Function, FunctionKind, Input, Instruction, Operand, Program, UnaryOperation};
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    str::FromStr,
+};
+
+/// Applies `$body` to the integers `$a` and `$b` of type `$type_`, bound to `$x` and `$y` as the Rust integers of the same type.
+/// Integers are stored as the bits of the Rust integer, sign extended to a `u128`.
+macro_rules! integers {
+    ($type_:expr, $a:expr, $b:expr, |$x:ident, $y:ident| $body:expr) => {
+        match $type_ {
+            IntegerType::U8 => {
+                let ($x, $y) = ($a as u8, $b as u8);
+                $body
+            }
+            IntegerType::U16 => {
+                let ($x, $y) = ($a as u16, $b as u16);
+                $body
+            }
+            IntegerType::U32 => {
+                let ($x, $y) = ($a as u32, $b as u32);
+                $body
+            }
+            IntegerType::U64 => {
+                let ($x, $y) = ($a as u64, $b as u64);
+                $body
+            }
+            IntegerType::U128 => {
+                let ($x, $y) = ($a as u128, $b as u128);
+                $body
+            }
+            IntegerType::I8 => {
+                let ($x, $y) = ($a as i8, $b as i8);
+                $body
+            }
+            IntegerType::I16 => {
+                let ($x, $y) = ($a as i16, $b as i16);
+                $body
+            }
+            IntegerType::I32 => {
+                let ($x, $y) = ($a as i32, $b as i32);
+                $body
+            }
+            IntegerType::I64 => {
+                let ($x, $y) = ($a as i64, $b as i64);
+                $body
+            }
+            IntegerType::I128 => {
+                let ($x, $y) = ($a as i128, $b as i128);
+                $body
+            }
+        }
+    };
+}
+
+/// Like `integers!`, for the operations that are only defined on signed integers, returning `None` for unsigned integers.
+macro_rules! signed {
+    ($type_:expr, $a:expr, |$x:ident| $body:expr) => {
+        match $type_ {
+            IntegerType::I8 => {
+                let $x = $a as i8;
+                $body.map(|result| result as u128)
+            }
+            IntegerType::I16 => {
+                let $x = $a as i16;
+                $body.map(|result| result as u128)
+            }
+            IntegerType::I32 => {
+                let $x = $a as i32;
+                $body.map(|result| result as u128)
+            }
+            IntegerType::I64 => {
+                let $x = $a as i64;
+                $body.map(|result| result as u128)
+            }
+            IntegerType::I128 => {
+                let $x = $a as i128;
+                $body.map(|result| result as u128)
+            }
+            _ => None,
+        }
+    };
+}
+
+/// A value of the mock ledger.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Value {
+    Address(String),
+    Boolean(bool),
+    /// A field element, which can only be compared, stored, and output, since the mock ledger does not implement field arithmetic.
+    Field(String),
+    Integer(IntegerType, u128),
+    /// A struct or record, with its members in the order they are declared.
+    Composite(Vec<(String, Value)>),
+}
+
+impl FromStr for Value {
+    type Err = String;
+
+    /// Parses a literal, e.g. `1u8`, `-1i8`, `true`, or `aleo1...`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "true" => return Ok(Self::Boolean(true)),
+            "false" => return Ok(Self::Boolean(false)),
+            _ if s.starts_with("aleo1") => return Ok(Self::Address(s.to_string())),
+            _ => {}
+        }
+
+        let suffix = s
+            .find(|c: char| c.is_ascii_alphabetic())
+            .ok_or_else(|| format!("`{}` is not a literal", s))?;
+        let (digits, type_) = s.split_at(suffix);
+        if type_ == "field" {
+            return Ok(Self::Field(digits.to_string()));
+        }
+        let type_ = IntegerType::from_str(type_).map_err(|_| format!("`{}` is not supported by the mock ledger", s))?;
+        let bits = integers!(type_, 0, 0, |x, _y| parse_like(x, digits).map(|parsed| parsed as u128));
+        bits.map(|bits| Self::Integer(type_, bits))
+            .ok_or_else(|| format!("`{}` is out of range for `{}`", digits, type_))
+    }
+}
+
+/// Parses `digits` as an integer of the same type as `_like`.
+fn parse_like<T: FromStr>(_like: T, digits: &str) -> Option<T> {
+    digits.parse().ok()
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Address(address) => write!(f, "{}", address),
+            Self::Boolean(boolean) => write!(f, "{}", boolean),
+            Self::Field(field) => write!(f, "{}field", field),
+            Self::Integer(type_, bits) => integers!(type_, *bits, 0, |x, _y| write!(f, "{}{}", x, type_)),
+            Self::Composite(members) => {
+                let members: Vec<String> = members
+                    .iter()
+                    .map(|(name, value)| format!("{}: {}", name, value))
+                    .collect();
+                write!(f, "{{ {} }}", members.join(", "))
+            }
+        }
+    }
+}
+
+impl Value {
+    /// Returns the integer of the value, if it is one.
+    fn integer(&self) -> Result<(IntegerType, u128), String> {
+        match self {
+            Self::Integer(type_, bits) => Ok((*type_, *bits)),
+            value => Err(format!("`{}` is not an integer", value)),
+        }
+    }
+
+    /// Returns the boolean of the value, if it is one.
+    fn boolean(&self) -> Result<bool, String> {
+        match self {
+            Self::Boolean(boolean) => Ok(*boolean),
+            value => Err(format!("`{}` is not a boolean", value)),
+        }
+    }
+}
+
+/// The entries of each mapping of a program, keyed by the mapping and then by the printed key.
+pub(crate) type Mappings = BTreeMap<String, BTreeMap<String, Value>>;
+
+/// The on-chain state seen by finalize blocks: the mappings of each program, keyed by program, e.g. `test.aleo`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MockLedger {
+    pub(crate) programs: BTreeMap<String, Mappings>,
+}
+
+impl MockLedger {
+    /// Executes the transition `function` of `program` as `caller`, followed by its finalize block, if any.
+    /// Returns the outputs of the transition, or the reason that execution halted.
+    /// The mappings are only updated if both the transition and its finalize block succeed.
+    pub(crate) fn execute(
+        &mut self,
+        program: &Program,
+        function: &str,
+        arguments: Vec<Value>,
+        caller: &str,
+    ) -> Result<Vec<Value>, String> {
+        let transition = transition(program, function)?;
+
+        let mut frame = Frame::new(program, caller, None);
+        frame.run(&transition.inputs, &transition.instructions, arguments)?;
+
+        if let Some(finalize) = &transition.finalize {
+            let arguments = frame
+                .finalize
+                .take()
+                .ok_or_else(|| format!("`{}` did not call its finalize block", function))?;
+            let program_id = format!("{}.{}", program.name, program.network);
+            let mut mappings = self.programs.get(&program_id).cloned().unwrap_or_else(|| {
+                program
+                    .mappings
+                    .iter()
+                    .map(|mapping| (mapping.name.clone(), BTreeMap::new()))
+                    .collect()
+            });
+            Frame::new(program, caller, Some(&mut mappings)).run(
+                &finalize.inputs,
+                &finalize.instructions,
+                arguments,
+            )?;
+            self.programs.insert(program_id, mappings);
+        }
+
+        Ok(frame.outputs)
+    }
+}
+
+/// Returns the transition `function` of `program`.
+pub(crate) fn transition<'a>(program: &'a Program, function: &str) -> Result<&'a Function, String> {
+    program
+        .functions
+        .iter()
+        .find(|candidate| candidate.kind == FunctionKind::Function && candidate.name == function)
+        .ok_or_else(|| format!("`{}` is not a transition of the program", function))
+}
+
+/// The state of a closure, function, or finalize block during its execution.
+struct Frame<'a> {
+    program: &'a Program,
+    caller: &'a str,
+    registers: HashMap<u64, Value>,
+    outputs: Vec<Value>,
+    /// The arguments of the finalize block, once its `finalize` instruction is executed.
+    finalize: Option<Vec<Value>>,
+    /// The mappings of the program, which are only accessible to finalize blocks.
+    mappings: Option<&'a mut Mappings>,
+}
+
+impl<'a> Frame<'a> {
+    fn new(program: &'a Program, caller: &'a str, mappings: Option<&'a mut Mappings>) -> Self {
+        Self {
+            program,
+            caller,
+            registers: HashMap::new(),
+            outputs: Vec::new(),
+            finalize: None,
+            mappings,
+        }
+    }
+
+    /// Executes `instructions`, with `arguments` assigned to the registers of `inputs`.
+    fn run(&mut self, inputs: &[Input], instructions: &[Instruction], arguments: Vec<Value>) -> Result<(), String> {
+        if inputs.len() != arguments.len() {
+            return Err(format!("Expected {} inputs, but got {}", inputs.len(), arguments.len()));
+        }
+        for (input, argument) in inputs.iter().zip(arguments) {
+            self.registers.insert(input.register.0, argument);
+        }
+        instructions
+            .iter()
+            .try_for_each(|instruction| self.instruction(instruction))
+    }
+
+    /// Returns the value of `operand`.
+    fn operand(&self, operand: &Operand) -> Result<Value, String> {
+        match operand {
+            Operand::Register(register) => self
+                .registers
+                .get(&register.0)
+                .cloned()
+                .ok_or_else(|| format!("`{}` is read before it is assigned", register)),
+            Operand::Literal(literal) => Value::from_str(literal),
+            // Since the mock ledger does not call other programs, the signer is always the caller.
+            Operand::Member(inner, member)
+                if **inner == Operand::Name("self".to_string()) && (member == "caller" || member == "signer") =>
+            {
+                Ok(Value::Address(self.caller.to_string()))
+            }
+            Operand::Member(inner, member) => match self.operand(inner)? {
+                Value::Composite(members) => members
+                    .into_iter()
+                    .find(|(name, _)| name == member)
+                    .map(|(_, value)| value)
+                    .ok_or_else(|| format!("`{}` has no member `{}`", inner, member)),
+                value => Err(format!("`{}` has no member `{}`", value, member)),
+            },
+            Operand::Name(name) => Err(format!("`{}` is not supported by the mock ledger", name)),
+        }
+    }
+
+    /// Returns the mappings of the program, if a finalize block is executing.
+    fn mapping(&mut self, mapping: &str) -> Result<&mut BTreeMap<String, Value>, String> {
+        self.mappings
+            .as_mut()
+            .ok_or_else(|| format!("`{}` is only accessible in finalize blocks", mapping))?
+            .get_mut(mapping)
+            .ok_or_else(|| format!("`{}` is not a mapping of the program", mapping))
+    }
+
+    /// Executes `instruction`, returning the reason that execution halted, if it did.
+    fn instruction(&mut self, instruction: &Instruction) -> Result<(), String> {
+        let halt = |reason: &str| Err(format!("`{}` halted: {}", instruction, reason));
+        match instruction {
+            Instruction::AssertEq(left, right) => {
+                if self.operand(left)? != self.operand(right)? {
+                    return halt("the operands are not equal");
+                }
+            }
+            Instruction::AssertNeq(left, right) => {
+                if self.operand(left)? == self.operand(right)? {
+                    return halt("the operands are equal");
+                }
+            }
+            Instruction::Binary {
+                operation,
+                left,
+                right,
+                destination,
+            } => {
+                let value = match binary(*operation, self.operand(left)?, self.operand(right)?)? {
+                    Some(value) => value,
+                    None => return halt("the operation overflowed or divided by zero"),
+                };
+                self.registers.insert(destination.0, value);
+            }
+            Instruction::Call {
+                program: None,
+                function,
+                arguments,
+                destinations,
+            } => {
+                let closure = self
+                    .program
+                    .functions
+                    .iter()
+                    .find(|closure| closure.kind == FunctionKind::Closure && closure.name == *function)
+                    .ok_or_else(|| format!("`{}` is not a closure of the program", function))?;
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| self.operand(argument))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let mut frame = Frame::new(self.program, self.caller, None);
+                frame.run(&closure.inputs, &closure.instructions, arguments)?;
+                for (destination, output) in destinations.iter().zip(frame.outputs) {
+                    self.registers.insert(destination.0, output);
+                }
+            }
+            Instruction::Cast {
+                operands,
+                destination,
+                type_: CastType::Literal(type_),
+            } => {
+                let operand = match operands.as_slice() {
+                    [operand] => self.operand(operand)?,
+                    _ => return Err(format!("`{}` does not cast a single operand", instruction)),
+                };
+                let value = match cast(operand, type_)? {
+                    Some(value) => value,
+                    None => return halt("the cast value is out of range"),
+                };
+                self.registers.insert(destination.0, value);
+            }
+            Instruction::Cast {
+                operands,
+                destination,
+                type_: CastType::Struct(name) | CastType::Record(name),
+            } => {
+                let composite = self
+                    .program
+                    .composites
+                    .iter()
+                    .find(|composite| composite.name == *name)
+                    .ok_or_else(|| format!("`{}` is not a struct or record of the program", name))?;
+                let members = composite
+                    .members
+                    .iter()
+                    .zip(operands)
+                    .map(|((member, _), operand)| Ok((member.clone(), self.operand(operand)?)))
+                    .collect::<Result<Vec<_>, String>>()?;
+                self.registers.insert(destination.0, Value::Composite(members));
+            }
+            Instruction::Decrement { mapping, key, amount } | Instruction::Increment { mapping, key, amount } => {
+                let (key, amount) = (self.operand(key)?.to_string(), self.operand(amount)?);
+                let (type_, _) = amount.integer()?;
+                // An entry that does not exist yet starts at zero.
+                let current = self
+                    .mapping(mapping)?
+                    .get(&key)
+                    .cloned()
+                    .unwrap_or(Value::Integer(type_, 0));
+                let operation = match instruction {
+                    Instruction::Increment { .. } => BinaryOperation::Add,
+                    _ => BinaryOperation::Sub,
+                };
+                let value = match binary(operation, current, amount)? {
+                    Some(value) => value,
+                    None => return halt("the entry overflowed"),
+                };
+                self.mapping(mapping)?.insert(key, value);
+            }
+            Instruction::Finalize(arguments) => {
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| self.operand(argument))
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.finalize = Some(arguments);
+            }
+            Instruction::Get {
+                mapping,
+                key,
+                destination,
+            } => {
+                let key = self.operand(key)?.to_string();
+                let value = match self.mapping(mapping)?.get(&key) {
+                    Some(value) => value.clone(),
+                    None => return halt(&format!("`{}` has no entry for `{}`", mapping, key)),
+                };
+                self.registers.insert(destination.0, value);
+            }
+            Instruction::GetOrUse {
+                mapping,
+                key,
+                default,
+                destination,
+            } => {
+                let (key, default) = (self.operand(key)?.to_string(), self.operand(default)?);
+                let value = self.mapping(mapping)?.get(&key).cloned().unwrap_or(default);
+                self.registers.insert(destination.0, value);
+            }
+            Instruction::Output(operand, _) => {
+                let value = self.operand(operand)?;
+                self.outputs.push(value);
+            }
+            Instruction::Set { mapping, key, value } => {
+                let (key, value) = (self.operand(key)?.to_string(), self.operand(value)?);
+                self.mapping(mapping)?.insert(key, value);
+            }
+            Instruction::Ternary {
+                condition,
+                if_true,
+                if_false,
+                destination,
+            } => {
+                let value = match self.operand(condition)?.boolean()? {
+                    true => self.operand(if_true)?,
+                    false => self.operand(if_false)?,
+                };
+                self.registers.insert(destination.0, value);
+            }
+            Instruction::Unary {
+                operation,
+                operand,
+                destination,
+            } => {
+                let value = match unary(*operation, self.operand(operand)?)? {
+                    Some(value) => value,
+                    None => return halt("the operation overflowed"),
+                };
+                self.registers.insert(destination.0, value);
+            }
+            Instruction::Call { program: Some(_), .. } | Instruction::Core { .. } | Instruction::RandChaCha { .. } => {
+                return Err(format!("`{}` is not supported by the mock ledger", instruction));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the result of the binary `operation`, or `None` if it halts.
+fn binary(operation: BinaryOperation, left: Value, right: Value) -> Result<Option<Value>, String> {
+    use BinaryOperation::*;
+
+    match (operation, &left, &right) {
+        (IsEq, ..) => return Ok(Some(Value::Boolean(left == right))),
+        (IsNeq, ..) => return Ok(Some(Value::Boolean(left != right))),
+        (_, Value::Boolean(a), Value::Boolean(b)) => {
+            let result = match operation {
+                And => a & b,
+                Or => a | b,
+                Xor => a ^ b,
+                Nand => !(a & b),
+                Nor => !(a | b),
+                _ => return Err(format!("`{}` is not defined on booleans", operation)),
+            };
+            return Ok(Some(Value::Boolean(result)));
+        }
+        _ => {}
+    }
+
+    let (type_, a) = left.integer()?;
+    let (right_type, b) = right.integer()?;
+    // The exponent of `pow` and the magnitude of shifts are `u8`, `u16`, or `u32`.
+    // Checked shifts halt if the magnitude is at least the number of bits of the integer.
+    let magnitude = b as u32;
+    if !matches!(operation, Pow | PowWrapped | Shl | ShlWrapped | Shr | ShrWrapped) && type_ != right_type {
+        return Err(format!("`{}` and `{}` have different types", left, right));
+    }
+
+    let integer = |result: Option<u128>| result.map(|bits| Value::Integer(type_, bits));
+    let boolean = |result: bool| Some(Value::Boolean(result));
+    Ok(integers!(type_, a, b, |x, y| match operation {
+        Add => integer(x.checked_add(y).map(|result| result as u128)),
+        AddWrapped => integer(Some(x.wrapping_add(y) as u128)),
+        Sub => integer(x.checked_sub(y).map(|result| result as u128)),
+        SubWrapped => integer(Some(x.wrapping_sub(y) as u128)),
+        Mul => integer(x.checked_mul(y).map(|result| result as u128)),
+        MulWrapped => integer(Some(x.wrapping_mul(y) as u128)),
+        Div => integer(x.checked_div(y).map(|result| result as u128)),
+        DivWrapped => integer((y != 0).then(|| x.wrapping_div(y) as u128)),
+        Rem => integer(x.checked_rem(y).map(|result| result as u128)),
+        RemWrapped => integer((y != 0).then(|| x.wrapping_rem(y) as u128)),
+        Mod => integer(x.checked_rem_euclid(y).map(|result| result as u128)),
+        Pow => integer(x.checked_pow(magnitude).map(|result| result as u128)),
+        PowWrapped => integer(Some(x.wrapping_pow(magnitude) as u128)),
+        Shl => integer(x.checked_shl(magnitude).map(|result| result as u128)),
+        ShlWrapped => integer(Some(x.wrapping_shl(magnitude) as u128)),
+        Shr => integer(x.checked_shr(magnitude).map(|result| result as u128)),
+        ShrWrapped => integer(Some(x.wrapping_shr(magnitude) as u128)),
+        And => integer(Some((x & y) as u128)),
+        Or => integer(Some((x | y) as u128)),
+        Xor => integer(Some((x ^ y) as u128)),
+        Gt => boolean(x > y),
+        Gte => boolean(x >= y),
+        Lt => boolean(x < y),
+        Lte => boolean(x <= y),
+        IsEq | IsNeq | Nand | Nor => return Err(format!("`{}` is not defined on integers", operation)),
+    }))
+}
+
+/// Returns the result of the unary `operation`, or `None` if it halts.
+fn unary(operation: UnaryOperation, operand: Value) -> Result<Option<Value>, String> {
+    if let (UnaryOperation::Not, Value::Boolean(boolean)) = (operation, &operand) {
+        return Ok(Some(Value::Boolean(!boolean)));
+    }
+
+    let (type_, a) = operand.integer()?;
+    let result = match operation {
+        UnaryOperation::Not => integers!(type_, a, 0, |x, _y| Some(!x as u128)),
+        UnaryOperation::Negate => signed!(type_, a, |x| x.checked_neg()),
+        UnaryOperation::Abs => signed!(type_, a, |x| x.checked_abs()),
+        UnaryOperation::AbsWrapped => signed!(type_, a, |x| Some(x.wrapping_abs())),
+        _ => return Err(format!("`{}` is not supported by the mock ledger", operation)),
+    };
+    Ok(result.map(|bits| Value::Integer(type_, bits)))
+}
+
+/// Returns the result of casting `operand` to the literal type `type_`, or `None` if it halts.
+fn cast(operand: Value, type_: &str) -> Result<Option<Value>, String> {
+    // Booleans are cast as `0` or `1`, and integers and fields as their value.
+    let digits = match &operand {
+        Value::Boolean(boolean) => (*boolean as u8).to_string(),
+        Value::Field(field) => field.clone(),
+        Value::Integer(integer_type, bits) => integers!(integer_type, *bits, 0, |x, _y| x.to_string()),
+        _ => return Err(format!("casting `{}` is not supported by the mock ledger", operand)),
+    };
+
+    if type_ == "field" {
+        // A negative integer is cast to its value modulo the order of the field, which the mock ledger does not implement.
+        return match digits.starts_with('-') {
+            true => Err(format!("casting `{}` to a field is not supported", operand)),
+            false => Ok(Some(Value::Field(digits))),
+        };
+    }
+
+    let target = IntegerType::from_str(type_)
+        .map_err(|_| format!("casting to `{}` is not supported by the mock ledger", type_))?;
+    let bits = integers!(target, 0, 0, |x, _y| parse_like(x, &digits)
+        .map(|parsed| parsed as u128));
+    Ok(bits.map(|bits| Value::Integer(target, bits)))
+}
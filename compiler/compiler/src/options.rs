@@ -14,18 +14,177 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-#[derive(Clone, Default)]
+use leo_ast::IntegerType;
+use leo_errors::emitter::LintLevel;
+use leo_parser::Edition;
+
+use serde::Serialize;
+use std::{fmt, str::FromStr};
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct BuildOptions {
+    /// If enabled, searches for inputs on which the assertions of each transition fail, and warns about them.
+    pub assertion_checking: bool,
+    /// The type of unsuffixed integer literals whose type cannot be inferred from their context.
+    /// A `@default_integer_type(..)` annotation on a program scope takes precedence.
+    pub default_integer_type: Option<IntegerType>,
+    /// If enabled, the AST is validated after every pass, for debugging the compiler.
+    /// The AST is always validated in debug builds.
+    pub debug_compiler: bool,
+    /// If enabled, warnings are emitted as errors, unless the level of their lint is set.
+    pub deny_warnings: bool,
+    /// The edition of Leo that programs are written for, which defaults to the latest edition.
+    /// An `@edition(..)` annotation on a program scope takes precedence.
+    pub edition: Option<Edition>,
+    /// The enabled features, against which the `feature = ".."` conditions of `@cfg(..)` annotations are evaluated.
+    pub features: Vec<String>,
+    /// If enabled, repeated sequences of statements are outlined into shared functions, reducing the size of the program at the cost of additional calls.
+    pub function_outlining: bool,
+    /// If enabled, the variables renamed by static single assignment are numbered by a single counter shared by all variables, e.g. `x$37`,
+    /// as in earlier versions of the compiler, so that AST snapshots can be compared with theirs.
+    /// Otherwise, the versions of each variable are numbered separately, e.g. `x$0`, `x$1`, and so on.
+    pub global_ssa_numbering: bool,
+    /// If enabled, the outputs of a build are cached under the output directory,
+    /// and reused by later builds of the same sources with the same options instead of compiling the program again.
+    pub incremental: bool,
+    /// If set, standard functions are inlined when doing so adds at most this many statements to the program.
+    /// An `@inline(always)` or `@inline(never)` annotation on a function takes precedence.
+    pub inlining_budget: Option<usize>,
+    /// If enabled, warns about structs and records whose members could be reordered or narrowed to reduce their serialized size.
+    pub layout_suggestions: bool,
+    /// The levels of lints, by name, e.g. to allow `empty_function`.
+    /// An `@allow(..)` annotation on a function takes precedence within the function.
+    pub lint_levels: Vec<(String, LintLevel)>,
+    /// If enabled, warns about public outputs and finalize arguments that depend on private inputs.
+    pub privacy_warnings: bool,
+    /// If enabled, injects assertions that check the invariants relied on by the compiler's optimizations at runtime, for debugging.
+    pub sanitize: bool,
+    /// If set, chains of at least this many selections are rebalanced into trees, reducing their depth at the cost of additional instructions.
+    pub ternary_balancing: Option<usize>,
+    /// If enabled, the functions and statements annotated with `@cfg(test)` are compiled.
+    pub test: bool,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
 pub struct OutputOptions {
     /// Whether spans are enabled in the output ASTs.
     pub spans_enabled: bool,
-    /// If enabled writes the AST after parsing.
-    pub initial_ast: bool,
     /// If enabled writes the input AST after parsing.
     pub initial_input_ast: bool,
-    /// If enabled writes the AST after loop unrolling.
-    pub unrolled_ast: bool,
-    /// If enabled writes the AST after static single assignment.
-    pub ssa_ast: bool,
-    /// If enabled writes the AST after flattening.
-    pub flattened_ast: bool,
+    /// The points in the compiler pipeline after which the AST is written.
+    pub ast_snapshots: Vec<AstSnapshot>,
+    /// If enabled, variables renamed by static single assignment are written with their source names in the output ASTs.
+    pub source_names: bool,
+    /// If enabled writes a profile of the estimated cost of proving each line of the program.
+    pub profile: bool,
+    /// If enabled writes a map from each generated Aleo instruction to the Leo code it was generated from.
+    pub source_map: bool,
+    /// If enabled writes the Leo code of each function alongside its Aleo instructions, for side-by-side viewers.
+    pub disassembly: bool,
+    /// If enabled, estimates the number of constraints of each function and line of the program.
+    pub cost_report: bool,
+    /// If enabled, the nodes that each pass visits and rewrites are counted in the report of the work performed by the passes.
+    /// Counting them traverses the AST before and after every pass, so it is disabled by default.
+    pub pass_report: bool,
+    /// If enabled writes the symbol table after type checking, with the variables of every scope and the signature of every function.
+    pub symbol_table: bool,
+}
+
+/// A point in the compiler pipeline after which the AST can be written, named after the pass that precedes it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum AstSnapshot {
+    /// After parsing.
+    Initial,
+    /// After enum lowering.
+    Lower,
+    /// After monomorphization.
+    Monomorphize,
+    /// After loop unrolling.
+    Unroll,
+    /// After static single assignment.
+    Ssa,
+    /// After flattening.
+    Flatten,
+    /// After function inlining.
+    Inline,
+    /// After boolean simplification.
+    Simplify,
+    /// After range analysis.
+    Range,
+    /// After strength reduction.
+    Reduce,
+    /// After ternary balancing, if enabled.
+    Balance,
+    /// After function outlining, if enabled.
+    Outline,
+}
+
+impl AstSnapshot {
+    /// All snapshots, in the order that the passes run.
+    pub const ALL: [AstSnapshot; 12] = [
+        AstSnapshot::Initial,
+        AstSnapshot::Lower,
+        AstSnapshot::Monomorphize,
+        AstSnapshot::Unroll,
+        AstSnapshot::Ssa,
+        AstSnapshot::Flatten,
+        AstSnapshot::Inline,
+        AstSnapshot::Simplify,
+        AstSnapshot::Range,
+        AstSnapshot::Reduce,
+        AstSnapshot::Balance,
+        AstSnapshot::Outline,
+    ];
+
+    /// Returns the name of the file that the snapshot is written to.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::Initial => "initial_ast.json",
+            Self::Lower => "lowered_ast.json",
+            Self::Monomorphize => "monomorphized_ast.json",
+            Self::Unroll => "unrolled_ast.json",
+            Self::Ssa => "ssa_ast.json",
+            Self::Flatten => "flattened_ast.json",
+            Self::Inline => "inlined_ast.json",
+            Self::Simplify => "simplified_ast.json",
+            Self::Range => "range_analyzed_ast.json",
+            Self::Reduce => "strength_reduced_ast.json",
+            Self::Balance => "balanced_ast.json",
+            Self::Outline => "outlined_ast.json",
+        }
+    }
+}
+
+impl fmt::Display for AstSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Initial => write!(f, "initial"),
+            Self::Lower => write!(f, "lower"),
+            Self::Monomorphize => write!(f, "monomorphize"),
+            Self::Unroll => write!(f, "unroll"),
+            Self::Ssa => write!(f, "ssa"),
+            Self::Flatten => write!(f, "flatten"),
+            Self::Inline => write!(f, "inline"),
+            Self::Simplify => write!(f, "simplify"),
+            Self::Range => write!(f, "range"),
+            Self::Reduce => write!(f, "reduce"),
+            Self::Balance => write!(f, "balance"),
+            Self::Outline => write!(f, "outline"),
+        }
+    }
+}
+
+impl FromStr for AstSnapshot {
+    type Err = String;
+
+    /// Parses a snapshot from its name, e.g. `ssa`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|snapshot| snapshot.to_string() == s)
+            .ok_or_else(|| {
+                let names: Vec<String> = Self::ALL.iter().map(|snapshot| snapshot.to_string()).collect();
+                format!("`{}` is not an AST snapshot, expected one of {}", s, names.join(", "))
+            })
+    }
 }
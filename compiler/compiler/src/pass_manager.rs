@@ -0,0 +1,462 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{BuildOptions, Compiler};
+
+use leo_errors::{CompilerError, Result};
+use leo_passes::{Assigner, CallGraph, Invariant, StructGraph, SymbolTable, TypeTable};
+
+use indexmap::IndexSet;
+use std::fmt;
+
+/// A compiler pass that can be registered with a `PassManager`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompilerPass {
+    CfgStripping,
+    SymbolTableCreation,
+    TypeChecking,
+    EnumLowering,
+    Monomorphization,
+    LoopUnrolling,
+    LayoutAnalysis,
+    TupleLowering,
+    StaticSingleAssignment,
+    GatesAnalysis,
+    TaintAnalysis,
+    AssertionChecking,
+    Flattening,
+    /// Inlines functions, inlining standard functions only if doing so adds at most the given number of statements.
+    FunctionInlining(Option<usize>),
+    BooleanSimplification,
+    RangeAnalysis,
+    StrengthReduction,
+    /// Rebalances chains of at least the given number of selections.
+    TernaryBalancing(usize),
+    FunctionOutlining,
+}
+
+impl CompilerPass {
+    /// Returns the invariants that must hold before the pass is run.
+    pub fn requires(&self) -> &'static [Invariant] {
+        use Invariant::*;
+        match self {
+            Self::CfgStripping => &[],
+            Self::SymbolTableCreation => &[NoCfgStatements],
+            Self::TypeChecking => &[SymbolTable],
+            Self::EnumLowering | Self::Monomorphization | Self::LayoutAnalysis => &[TypeChecked],
+            // Loops in generic functions are unrolled in their instances, whose bounds may depend on the type parameters.
+            Self::LoopUnrolling => &[TypeChecked, NoGenerics],
+            Self::TupleLowering => &[TypeChecked, NoMatchStatements, NoLoops],
+            Self::StaticSingleAssignment => &[NoCfgStatements, NoMatchStatements, NoLoops, NoTuples],
+            Self::GatesAnalysis | Self::TaintAnalysis | Self::AssertionChecking | Self::Flattening => {
+                &[SingleAssignment]
+            }
+            Self::FunctionInlining(_) => &[TypeChecked, NoConditionals],
+            Self::BooleanSimplification | Self::RangeAnalysis | Self::StrengthReduction => &[NoConditionals],
+            Self::TernaryBalancing(_) | Self::FunctionOutlining => &[SingleAssignment, NoConditionals],
+        }
+    }
+
+    /// Returns whether the pass transforms the AST, rather than only analyzing it.
+    pub fn transforms(&self) -> bool {
+        !matches!(
+            self,
+            Self::SymbolTableCreation
+                | Self::TypeChecking
+                | Self::LayoutAnalysis
+                | Self::GatesAnalysis
+                | Self::TaintAnalysis
+                | Self::AssertionChecking
+        )
+    }
+
+    /// Returns the invariants that hold once the pass has run.
+    pub fn establishes(&self) -> &'static [Invariant] {
+        match self {
+            Self::CfgStripping => &[Invariant::NoCfgStatements],
+            Self::SymbolTableCreation => &[Invariant::SymbolTable],
+            Self::TypeChecking => &[Invariant::TypeChecked],
+            Self::EnumLowering => &[Invariant::NoMatchStatements],
+            Self::Monomorphization => &[Invariant::NoGenerics],
+            Self::LoopUnrolling => &[Invariant::NoLoops],
+            Self::TupleLowering => &[Invariant::NoTuples],
+            Self::StaticSingleAssignment => &[Invariant::SingleAssignment],
+            Self::Flattening => &[Invariant::NoConditionals],
+            _ => &[],
+        }
+    }
+
+    /// Returns whether `invariant` still holds once the pass has run, if it held before.
+    /// Passes that only analyze the AST preserve every invariant.
+    pub fn preserves(&self, invariant: Invariant) -> bool {
+        match self {
+            // The call graph produced by type checking no longer matches the program once inlined functions are removed,
+            // or once statements are outlined into new functions.
+            Self::FunctionInlining(_) | Self::FunctionOutlining => invariant != Invariant::TypeChecked,
+            _ => true,
+        }
+    }
+
+    /// Updates the invariants in `established`, which hold before the pass, to those that hold once it has run.
+    pub fn update(&self, established: &mut IndexSet<Invariant>) {
+        established.retain(|invariant| self.preserves(*invariant));
+        established.extend(self.establishes());
+    }
+}
+
+impl fmt::Display for CompilerPass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::CfgStripping => "cfg stripping",
+            Self::SymbolTableCreation => "symbol table creation",
+            Self::TypeChecking => "type checking",
+            Self::EnumLowering => "enum lowering",
+            Self::Monomorphization => "monomorphization",
+            Self::LoopUnrolling => "loop unrolling",
+            Self::LayoutAnalysis => "layout analysis",
+            Self::TupleLowering => "tuple lowering",
+            Self::StaticSingleAssignment => "static single assignment",
+            Self::GatesAnalysis => "gates analysis",
+            Self::TaintAnalysis => "taint analysis",
+            Self::AssertionChecking => "assertion checking",
+            Self::Flattening => "flattening",
+            Self::FunctionInlining(_) => "function inlining",
+            Self::BooleanSimplification => "boolean simplification",
+            Self::RangeAnalysis => "range analysis",
+            Self::StrengthReduction => "strength reduction",
+            Self::TernaryBalancing(_) => "ternary balancing",
+            Self::FunctionOutlining => "function outlining",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The state threaded between compiler passes, along with the invariants established so far.
+#[derive(Default)]
+pub struct PassState {
+    pub symbol_table: Option<SymbolTable>,
+    pub type_table: Option<TypeTable>,
+    pub struct_graph: Option<StructGraph>,
+    pub call_graph: Option<CallGraph>,
+    pub assigner: Option<Assigner>,
+    established: IndexSet<Invariant>,
+}
+
+impl PassState {
+    /// Returns the invariants established by the passes run so far.
+    pub fn established(&self) -> &IndexSet<Invariant> {
+        &self.established
+    }
+}
+
+/// Runs a sequence of compiler passes, checking that each pass is run only once the invariants it requires hold.
+/// In debug builds, and when the compiler is being debugged, the AST is also validated after every pass.
+#[derive(Clone, Debug, Default)]
+pub struct PassManager {
+    passes: Vec<CompilerPass>,
+}
+
+impl PassManager {
+    /// Returns a pass manager for the passes that check the program: cfg stripping, symbol table creation, and type checking.
+    pub fn frontend() -> Self {
+        Self::default()
+            .register(CompilerPass::CfgStripping)
+            .register(CompilerPass::SymbolTableCreation)
+            .register(CompilerPass::TypeChecking)
+    }
+
+    /// Returns a pass manager for the passes that lower a type checked program into a form ready for code generation.
+    pub fn lowering(options: &BuildOptions) -> Self {
+        let manager = Self::default()
+            .register(CompilerPass::EnumLowering)
+            .register(CompilerPass::Monomorphization)
+            .register(CompilerPass::LoopUnrolling)
+            .register_if(options.layout_suggestions, CompilerPass::LayoutAnalysis)
+            .register(CompilerPass::TupleLowering)
+            .register(CompilerPass::StaticSingleAssignment)
+            .register(CompilerPass::GatesAnalysis)
+            .register(CompilerPass::TaintAnalysis)
+            .register_if(options.assertion_checking, CompilerPass::AssertionChecking)
+            .register(CompilerPass::Flattening)
+            .register(CompilerPass::FunctionInlining(options.inlining_budget))
+            .register(CompilerPass::BooleanSimplification)
+            .register(CompilerPass::RangeAnalysis)
+            .register(CompilerPass::StrengthReduction);
+        let manager = match options.ternary_balancing {
+            Some(threshold) => manager.register(CompilerPass::TernaryBalancing(threshold)),
+            None => manager,
+        };
+        manager.register_if(options.function_outlining, CompilerPass::FunctionOutlining)
+    }
+
+    /// Registers `pass` to run after the passes registered so far.
+    pub fn register(mut self, pass: CompilerPass) -> Self {
+        self.passes.push(pass);
+        self
+    }
+
+    /// Registers `pass` to run after the passes registered so far, if `condition` holds.
+    pub fn register_if(self, condition: bool, pass: CompilerPass) -> Self {
+        match condition {
+            true => self.register(pass),
+            false => self,
+        }
+    }
+
+    /// Returns the registered passes, in the order that they run.
+    pub fn passes(&self) -> &[CompilerPass] {
+        &self.passes
+    }
+
+    /// Checks that every registered pass runs only once the invariants it requires hold,
+    /// given the invariants that hold before the first pass, and that no pass in between breaks them.
+    pub fn validate(&self, established: &IndexSet<Invariant>) -> Result<()> {
+        let mut established = established.clone();
+        for pass in &self.passes {
+            if let Some(requirement) = pass
+                .requires()
+                .iter()
+                .find(|invariant| !established.contains(*invariant))
+            {
+                return Err(CompilerError::pass_requirement_not_met(pass, requirement).into());
+            }
+            pass.update(&mut established);
+        }
+        Ok(())
+    }
+
+    /// Runs the registered passes on the program of `compiler`, continuing from `state`.
+    pub fn run(&self, compiler: &mut Compiler, mut state: PassState) -> Result<PassState> {
+        self.validate(&state.established)?;
+
+        for pass in &self.passes {
+            let input = compiler.count_nodes();
+            match *pass {
+                CompilerPass::CfgStripping => compiler.cfg_stripping_pass()?,
+                CompilerPass::SymbolTableCreation => state.symbol_table = Some(compiler.symbol_table_pass()?),
+                CompilerPass::TypeChecking => {
+                    let symbol_table = take(&mut state.symbol_table, pass, Invariant::SymbolTable)?;
+                    let (symbol_table, type_table, struct_graph, call_graph) =
+                        compiler.type_checker_pass(symbol_table)?;
+                    state.symbol_table = Some(symbol_table);
+                    state.type_table = Some(type_table);
+                    state.struct_graph = Some(struct_graph);
+                    state.call_graph = Some(call_graph);
+                }
+                CompilerPass::EnumLowering => {
+                    let symbol_table = take(&mut state.symbol_table, pass, Invariant::SymbolTable)?;
+                    state.symbol_table = Some(compiler.enum_lowering_pass(symbol_table)?);
+                }
+                CompilerPass::Monomorphization => {
+                    let symbol_table = take(&mut state.symbol_table, pass, Invariant::SymbolTable)?;
+                    let call_graph = take(&mut state.call_graph, pass, Invariant::TypeChecked)?;
+                    let struct_graph = take(&mut state.struct_graph, pass, Invariant::TypeChecked)?;
+                    let type_table = take(&mut state.type_table, pass, Invariant::TypeChecked)?;
+                    let (symbol_table, call_graph, struct_graph, type_table) =
+                        compiler.monomorphization_pass(symbol_table, call_graph, struct_graph, type_table)?;
+                    state.symbol_table = Some(symbol_table);
+                    state.call_graph = Some(call_graph);
+                    state.struct_graph = Some(struct_graph);
+                    state.type_table = Some(type_table);
+                }
+                CompilerPass::LoopUnrolling => {
+                    let symbol_table = take(&mut state.symbol_table, pass, Invariant::SymbolTable)?;
+                    let type_table = take(&mut state.type_table, pass, Invariant::TypeChecked)?;
+                    let (symbol_table, type_table) = compiler.loop_unrolling_pass(symbol_table, type_table)?;
+                    state.symbol_table = Some(symbol_table);
+                    state.type_table = Some(type_table);
+                }
+                CompilerPass::LayoutAnalysis => compiler.layout_analysis_pass()?,
+                CompilerPass::TupleLowering => {
+                    let type_table = state
+                        .type_table
+                        .as_mut()
+                        .ok_or_else(|| CompilerError::pass_requirement_not_met(pass, Invariant::TypeChecked))?;
+                    compiler.tuple_lowering_pass(type_table)?
+                }
+                CompilerPass::StaticSingleAssignment => {
+                    let type_table = state
+                        .type_table
+                        .as_mut()
+                        .ok_or_else(|| CompilerError::pass_requirement_not_met(pass, Invariant::TypeChecked))?;
+                    state.assigner = Some(compiler.static_single_assignment_pass(type_table)?)
+                }
+                CompilerPass::GatesAnalysis => compiler.gates_analysis_pass()?,
+                CompilerPass::TaintAnalysis => compiler.taint_analysis_pass()?,
+                CompilerPass::AssertionChecking => compiler.assertion_checking_pass()?,
+                CompilerPass::Flattening => {
+                    let assigner = take(&mut state.assigner, pass, Invariant::SingleAssignment)?;
+                    let symbol_table = state
+                        .symbol_table
+                        .as_ref()
+                        .ok_or_else(|| CompilerError::pass_requirement_not_met(pass, Invariant::SymbolTable))?;
+                    let type_table = state
+                        .type_table
+                        .as_mut()
+                        .ok_or_else(|| CompilerError::pass_requirement_not_met(pass, Invariant::TypeChecked))?;
+                    state.assigner = Some(compiler.flattening_pass(symbol_table, type_table, assigner)?);
+                }
+                CompilerPass::FunctionInlining(budget) => {
+                    let assigner = take(&mut state.assigner, pass, Invariant::SingleAssignment)?;
+                    let call_graph = state
+                        .call_graph
+                        .as_ref()
+                        .ok_or_else(|| CompilerError::pass_requirement_not_met(pass, Invariant::TypeChecked))?;
+                    state.assigner = Some(compiler.function_inlining_pass(call_graph, assigner, budget)?);
+                }
+                CompilerPass::BooleanSimplification => compiler.boolean_simplification_pass()?,
+                CompilerPass::RangeAnalysis => compiler.range_analysis_pass()?,
+                CompilerPass::StrengthReduction => compiler.strength_reduction_pass()?,
+                CompilerPass::TernaryBalancing(threshold) => {
+                    let assigner = take(&mut state.assigner, pass, Invariant::SingleAssignment)?;
+                    state.assigner = Some(compiler.ternary_balancing_pass(assigner, threshold)?);
+                }
+                CompilerPass::FunctionOutlining => {
+                    let assigner = take(&mut state.assigner, pass, Invariant::SingleAssignment)?;
+                    compiler.function_outlining_pass(assigner)?;
+                }
+            }
+            compiler.record_nodes(input, pass.transforms());
+            pass.update(&mut state.established);
+
+            if cfg!(debug_assertions) || compiler.debug_compiler() {
+                compiler
+                    .validation_pass(state.symbol_table.as_ref(), &state.established)
+                    .map_err(|_| CompilerError::pass_invariant_violated(pass))?;
+            }
+        }
+
+        Ok(state)
+    }
+}
+
+/// Takes the state that `pass` consumes, which is present once `invariant` holds.
+fn take<T>(value: &mut Option<T>, pass: &CompilerPass, invariant: Invariant) -> Result<T> {
+    value
+        .take()
+        .ok_or_else(|| CompilerError::pass_requirement_not_met(pass, invariant).into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_default_pipeline_is_valid() {
+        let options = BuildOptions {
+            layout_suggestions: true,
+            assertion_checking: true,
+            ternary_balancing: Some(4),
+            function_outlining: true,
+            ..Default::default()
+        };
+        let frontend = PassManager::frontend();
+        frontend.validate(&IndexSet::new()).unwrap();
+
+        let established = frontend
+            .passes()
+            .iter()
+            .flat_map(|pass| pass.establishes())
+            .copied()
+            .collect();
+        PassManager::lowering(&options).validate(&established).unwrap();
+    }
+
+    #[test]
+    fn test_misordered_passes_are_rejected() {
+        let manager = PassManager::frontend()
+            .register(CompilerPass::EnumLowering)
+            .register(CompilerPass::Flattening)
+            .register(CompilerPass::StaticSingleAssignment);
+        let error = manager.validate(&IndexSet::new()).unwrap_err();
+        assert!(error.to_string().contains("flattening"));
+
+        // Loops must be unrolled before the program is converted into static single assignment form.
+        let manager = PassManager::frontend()
+            .register(CompilerPass::EnumLowering)
+            .register(CompilerPass::StaticSingleAssignment);
+        assert!(manager.validate(&IndexSet::new()).is_err());
+
+        // Tuples must also be lowered before then.
+        let manager = PassManager::frontend()
+            .register(CompilerPass::EnumLowering)
+            .register(CompilerPass::Monomorphization)
+            .register(CompilerPass::LoopUnrolling)
+            .register(CompilerPass::StaticSingleAssignment);
+        let error = manager.validate(&IndexSet::new()).unwrap_err();
+        assert!(error.to_string().contains("no tuples remain"));
+    }
+
+    #[test]
+    fn test_monomorphization_precedes_loop_unrolling() {
+        let manager = PassManager::frontend()
+            .register(CompilerPass::LoopUnrolling)
+            .register(CompilerPass::Monomorphization);
+        let error = manager.validate(&IndexSet::new()).unwrap_err();
+        assert!(error.to_string().contains("loop unrolling"));
+        assert!(error.to_string().contains("no generic functions or structs remain"));
+
+        // Generic functions are instantiated with the types inferred by the type checker.
+        let manager = PassManager::default()
+            .register(CompilerPass::CfgStripping)
+            .register(CompilerPass::SymbolTableCreation)
+            .register(CompilerPass::Monomorphization)
+            .register(CompilerPass::TypeChecking);
+        let error = manager.validate(&IndexSet::new()).unwrap_err();
+        assert!(error.to_string().contains("monomorphization"));
+
+        let manager = PassManager::frontend()
+            .register(CompilerPass::Monomorphization)
+            .register(CompilerPass::LoopUnrolling);
+        manager.validate(&IndexSet::new()).unwrap();
+    }
+
+    #[test]
+    fn test_broken_invariants_are_not_established() {
+        let passes = [
+            CompilerPass::EnumLowering,
+            CompilerPass::Monomorphization,
+            CompilerPass::LoopUnrolling,
+            CompilerPass::TupleLowering,
+            CompilerPass::StaticSingleAssignment,
+            CompilerPass::Flattening,
+        ];
+        let lowering = |manager: PassManager| passes.into_iter().fold(manager, PassManager::register);
+
+        // Outlining adds functions that the call graph used by inlining does not know of.
+        let manager = lowering(PassManager::frontend())
+            .register(CompilerPass::FunctionOutlining)
+            .register(CompilerPass::FunctionInlining(None));
+        let error = manager.validate(&IndexSet::new()).unwrap_err();
+        assert!(error.to_string().contains("function inlining"));
+
+        let manager = lowering(PassManager::frontend())
+            .register(CompilerPass::FunctionInlining(None))
+            .register(CompilerPass::FunctionOutlining);
+        manager.validate(&IndexSet::new()).unwrap();
+
+        // Analyses preserve every invariant.
+        let mut established = IndexSet::new();
+        for pass in PassManager::frontend().passes() {
+            pass.update(&mut established);
+        }
+        CompilerPass::LayoutAnalysis.update(&mut established);
+        assert!(established.contains(&Invariant::TypeChecked));
+        CompilerPass::FunctionOutlining.update(&mut established);
+        assert!(!established.contains(&Invariant::TypeChecked));
+        assert!(established.contains(&Invariant::SymbolTable));
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_errors::{CompilerError, Result};
+use leo_ir::{Function, FunctionKind, Instruction, Program};
+
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// A profile of the estimated cost of proving a program, attributed to the lines of the Leo program.
+/// Profiles are written in the JSON format read by flame graph viewers, such as d3-flame-graph.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileNode {
+    /// The program, function, or line of the Leo program.
+    pub name: String,
+    /// The estimated cost of proving the instructions of the node and of its children.
+    pub value: usize,
+    /// The number of instructions of the node and of its children.
+    pub instructions: usize,
+    /// The functions of the program, the lines of a function, or the closures called from a line.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<ProfileNode>,
+}
+
+impl ProfileNode {
+    /// Returns the profile of `program`, whose Leo program is `source`.
+    /// The cost of a call to a closure of the program is attributed to the line of the call, under a node for the closure.
+    /// Finalize blocks are excluded, since they are executed on chain rather than proven.
+    pub fn new(program: &Program, source: &str) -> Self {
+        let lines: Vec<&str> = source.lines().collect();
+        let children: Vec<Self> = program
+            .functions
+            .iter()
+            .filter(|function| function.kind == FunctionKind::Function)
+            .map(|function| Self::function(program, function, &lines))
+            .collect();
+        Self {
+            name: format!("{}.{}", program.name, program.network),
+            value: children.iter().map(|child| child.value).sum(),
+            instructions: children.iter().map(|child| child.instructions).sum(),
+            children,
+        }
+    }
+
+    /// Returns the profile of a function or closure of `program`, split into the `lines` of the Leo program.
+    fn function(program: &Program, function: &Function, lines: &[&str]) -> Self {
+        // Lines are ordered by number, followed by the instructions that were not generated from a known line.
+        let mut nodes: BTreeMap<(bool, usize), Self> = BTreeMap::new();
+        let known_lines = function
+            .locations
+            .iter()
+            .map(|location| location.map(|location| location.line_start));
+        let known_lines = known_lines.chain(std::iter::repeat(None));
+        for (instruction, line) in function.instructions.iter().zip(known_lines) {
+            let node = nodes
+                .entry((line.is_none(), line.unwrap_or_default()))
+                .or_insert_with(|| Self {
+                    name: match line {
+                        Some(line) => format!("{}: {}", line, lines.get(line - 1).map_or("", |text| text.trim())),
+                        None => "generated".to_string(),
+                    },
+                    ..Default::default()
+                });
+            node.value += instruction.cost();
+            node.instructions += 1;
+
+            if let Instruction::Call {
+                program: None,
+                function: name,
+                ..
+            } = instruction
+            {
+                if let Some(closure) = program.functions.iter().find(|closure| closure.name == *name) {
+                    let child = Self::function(program, closure, lines);
+                    node.value += child.value;
+                    node.instructions += child.instructions;
+                    node.children.push(child);
+                }
+            }
+        }
+
+        let children: Vec<Self> = nodes.into_values().collect();
+        Self {
+            name: function.name.clone(),
+            value: children.iter().map(|child| child.value).sum(),
+            instructions: children.iter().map(|child| child.instructions).sum(),
+            children,
+        }
+    }
+
+    /// Writes the profile to `path`.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).expect("Failed to serialize the profile.");
+        fs::write(path, json).map_err(|e| CompilerError::failed_to_write_profile(path, e))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use leo_ir::{
+        testing::{function, mul, program},
+        Operand, Register,
+    };
+
+    #[test]
+    fn test_profile() {
+        let call = Instruction::Call {
+            program: None,
+            function: "square".to_string(),
+            arguments: vec![Operand::Register(Register(0))],
+            destinations: vec![Register(2)],
+        };
+        let program = program(vec![
+            function(FunctionKind::Closure, "square", vec![mul(1)], vec![Some(2)]),
+            function(
+                FunctionKind::Function,
+                "main",
+                vec![mul(1), call, mul(3)],
+                vec![Some(5), Some(6), None],
+            ),
+        ]);
+        let source =
+            "program test.aleo {\n    let y: u8 = x * x;\n\n\n    let a: u8 = b * b;\n    let c: u8 = square(a);\n}";
+
+        let leaf = |name: &str, value, instructions, children| ProfileNode {
+            name: name.to_string(),
+            value,
+            instructions,
+            children,
+        };
+        let square = leaf("square", 4, 1, vec![leaf("2: let y: u8 = x * x;", 4, 1, Vec::new())]);
+        let main = leaf(
+            "main",
+            12,
+            4,
+            vec![
+                leaf("5: let a: u8 = b * b;", 4, 1, Vec::new()),
+                leaf("6: let c: u8 = square(a);", 4, 2, vec![square]),
+                leaf("generated", 4, 1, Vec::new()),
+            ],
+        );
+        assert_eq!(ProfileNode::new(&program, source), leaf("test.aleo", 12, 4, vec![main]));
+    }
+}
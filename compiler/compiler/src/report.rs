@@ -0,0 +1,79 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_passes::PassSummary;
+
+use std::{fmt, time::Duration};
+
+/// A report of the work performed by each compiler pass, in the order that the passes were run.
+#[derive(Clone, Debug, Default)]
+pub struct CompileReport {
+    passes: Vec<PassSummary>,
+}
+
+impl CompileReport {
+    /// Returns the summaries of the passes that have been run.
+    pub fn passes(&self) -> &[PassSummary] {
+        &self.passes
+    }
+
+    /// Returns the time taken by all of the passes that have been run.
+    pub fn total_duration(&self) -> Duration {
+        self.passes.iter().map(|pass| pass.duration).sum()
+    }
+
+    /// Returns the number of errors and warnings emitted by all of the passes that have been run.
+    pub fn diagnostics_emitted(&self) -> usize {
+        self.passes.iter().filter_map(|pass| pass.diagnostics_emitted).sum()
+    }
+
+    /// Adds the summary of a pass to the report.
+    pub(crate) fn push(&mut self, summary: PassSummary) {
+        self.passes.push(summary);
+    }
+
+    /// Returns the summary of the pass that was run last, if any.
+    pub(crate) fn last_mut(&mut self) -> Option<&mut PassSummary> {
+        self.passes.last_mut()
+    }
+}
+
+impl fmt::Display for CompileReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<26} {:>12} {:>8} {:>10} {:>12}",
+            "pass", "time", "visited", "rewritten", "diagnostics"
+        )?;
+        for pass in &self.passes {
+            writeln!(
+                f,
+                "{:<26} {:>12} {:>8} {:>10} {:>12}",
+                pass.name,
+                format!("{:.2?}", pass.duration),
+                pass.nodes_visited,
+                pass.nodes_rewritten,
+                // Passes that return their errors instead of emitting them have no count.
+                pass.diagnostics_emitted
+                    .map_or_else(|| "-".to_string(), |count| count.to_string())
+            )?;
+            for decision in &pass.decisions {
+                writeln!(f, "    - {}", decision)?;
+            }
+        }
+        write!(f, "{:<26} {:>12}", "total", format!("{:.2?}", self.total_duration()))
+    }
+}
@@ -0,0 +1,197 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_errors::{CompilerError, Result};
+use leo_ir::{FunctionKind, Input, Instruction, Program, SourceLocation};
+
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+/// A map from the instructions of an Aleo program to the locations in the Leo program that they were generated from.
+/// Tools such as debuggers and explorers can use it to point an instruction, e.g. a failing assertion, back to the Leo program.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstructionSourceMap {
+    /// The Aleo program, e.g. `hello.aleo`.
+    pub program: String,
+    /// The path of the Leo program.
+    pub file: String,
+    /// The closures, functions, and finalize blocks, in the order that they appear in the Aleo program.
+    pub functions: Vec<FunctionSourceMap>,
+}
+
+/// The source map of a closure, function, or finalize block.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionSourceMap {
+    pub name: String,
+    /// One of `closure`, `function`, or `finalize`.
+    pub kind: String,
+    pub instructions: Vec<InstructionSpan>,
+}
+
+/// An instruction and the location in the Leo program that it was generated from, if known.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstructionSpan {
+    /// The index of the instruction in its closure, function, or finalize block.
+    pub index: usize,
+    pub instruction: String,
+    pub span: Option<LocationSpan>,
+}
+
+/// A range of the Leo program, given by 1-based lines and columns.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocationSpan {
+    pub line_start: usize,
+    pub col_start: usize,
+    pub line_stop: usize,
+    pub col_stop: usize,
+}
+
+impl From<SourceLocation> for LocationSpan {
+    fn from(location: SourceLocation) -> Self {
+        Self {
+            line_start: location.line_start,
+            col_start: location.col_start,
+            line_stop: location.line_stop,
+            col_stop: location.col_stop,
+        }
+    }
+}
+
+/// A closure, function, or finalize block of an Aleo program.
+pub(crate) struct Body<'a> {
+    pub(crate) name: &'a str,
+    /// One of `closure`, `function`, or `finalize`.
+    pub(crate) kind: String,
+    pub(crate) inputs: &'a [Input],
+    pub(crate) instructions: &'a [Instruction],
+    pub(crate) locations: &'a [Option<SourceLocation>],
+}
+
+impl<'a> Body<'a> {
+    /// Returns the closures, functions, and finalize blocks of `program`, in the order that they appear in the Aleo program.
+    pub(crate) fn all(program: &'a Program) -> Vec<Self> {
+        // Closures precede functions in the Aleo program.
+        let (closures, functions): (Vec<_>, Vec<_>) = program
+            .functions
+            .iter()
+            .partition(|function| function.kind == FunctionKind::Closure);
+
+        let mut bodies = Vec::new();
+        for function in closures.into_iter().chain(functions) {
+            bodies.push(Self {
+                name: &function.name,
+                kind: function.kind.to_string(),
+                inputs: &function.inputs,
+                instructions: &function.instructions,
+                locations: &function.locations,
+            });
+            if let Some(finalize) = &function.finalize {
+                bodies.push(Self {
+                    name: &finalize.name,
+                    kind: "finalize".to_string(),
+                    inputs: &finalize.inputs,
+                    instructions: &finalize.instructions,
+                    locations: &finalize.locations,
+                });
+            }
+        }
+        bodies
+    }
+}
+
+impl FunctionSourceMap {
+    pub(crate) fn new(body: &Body) -> Self {
+        let locations = body.locations.iter().copied().chain(std::iter::repeat(None));
+        Self {
+            name: body.name.to_string(),
+            kind: body.kind.clone(),
+            instructions: body
+                .instructions
+                .iter()
+                .zip(locations)
+                .enumerate()
+                .map(|(index, (instruction, location))| InstructionSpan {
+                    index,
+                    instruction: instruction.to_string(),
+                    span: location.map(LocationSpan::from),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl InstructionSourceMap {
+    /// Returns the source map of `program`, which was compiled from the Leo program at `file`.
+    pub fn new(program: &Program, file: &Path) -> Self {
+        Self {
+            program: format!("{}.{}", program.name, program.network),
+            file: file.display().to_string(),
+            functions: Body::all(program).iter().map(FunctionSourceMap::new).collect(),
+        }
+    }
+
+    /// Writes the source map to `path`.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).expect("Failed to serialize the source map.");
+        fs::write(path, json).map_err(|e| CompilerError::failed_to_write_source_map(path, e))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use leo_ir::{
+        testing::{function, location, program},
+        Finalize, Operand, Register,
+    };
+
+    #[test]
+    fn test_source_map() {
+        let assert = Instruction::AssertEq(Operand::Register(Register(0)), Operand::Register(Register(1)));
+        let instructions = vec![assert.clone(), assert.clone()];
+        let mut main = function(FunctionKind::Function, "main", instructions.clone(), vec![Some(4)]);
+        main.finalize = Some(Finalize {
+            name: "main".to_string(),
+            inputs: Vec::new(),
+            instructions: vec![assert],
+            locations: vec![None],
+        });
+        let program = program(vec![
+            main,
+            function(FunctionKind::Closure, "check", instructions, vec![Some(4)]),
+        ]);
+
+        let map = InstructionSourceMap::new(&program, Path::new("src/main.leo"));
+        assert_eq!(map.program, "test.aleo");
+        let names: Vec<_> = map
+            .functions
+            .iter()
+            .map(|function| (function.kind.as_str(), function.name.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            [("closure", "check"), ("function", "main"), ("finalize", "main")]
+        );
+
+        let main = &map.functions[1];
+        assert_eq!(main.instructions[0].instruction, "assert.eq r0 r1;");
+        assert_eq!(main.instructions[0].span, Some(location(4).into()));
+        assert_eq!(main.instructions[1].index, 1);
+        assert_eq!(main.instructions[1].span, None);
+        assert_eq!(map.functions[2].instructions[0].span, None);
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ir::{FunctionKind, Input, Instruction, Program};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt};
+
+/// Statistics of the Aleo instructions generated for a program, for users and for tracking regressions in benchmarks.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramStatistics {
+    /// The number of instructions of each closure, function and finalize block, e.g. `function main`, in the order they are printed.
+    pub instructions: IndexMap<String, usize>,
+    /// The number of instructions with each opcode, e.g. `add.w`.
+    pub opcodes: BTreeMap<String, usize>,
+    /// The number of registers, summed over the closures, functions and finalize blocks.
+    pub registers: usize,
+    /// The number of structs declared by the program.
+    pub structs: usize,
+    /// The number of records declared by the program.
+    pub records: usize,
+}
+
+impl ProgramStatistics {
+    /// Returns the statistics of the generated `program`.
+    pub fn new(program: &Program) -> Self {
+        let mut statistics = Self {
+            records: program
+                .composites
+                .iter()
+                .filter(|composite| composite.is_record)
+                .count(),
+            structs: program
+                .composites
+                .iter()
+                .filter(|composite| !composite.is_record)
+                .count(),
+            ..Default::default()
+        };
+
+        // Closures are printed before functions, and each finalize block after its function.
+        let (closures, functions): (Vec<_>, Vec<_>) = program
+            .functions
+            .iter()
+            .partition(|function| function.kind == FunctionKind::Closure);
+        for function in closures.into_iter().chain(functions) {
+            statistics.add_body(
+                format!("{} {}", function.kind, function.name),
+                &function.inputs,
+                &function.instructions,
+            );
+            if let Some(finalize) = &function.finalize {
+                statistics.add_body(
+                    format!("finalize {}", finalize.name),
+                    &finalize.inputs,
+                    &finalize.instructions,
+                );
+            }
+        }
+        statistics
+    }
+
+    /// Returns the number of instructions of the program.
+    pub fn total_instructions(&self) -> usize {
+        self.instructions.values().sum()
+    }
+
+    fn add_body(&mut self, name: String, inputs: &[Input], instructions: &[Instruction]) {
+        self.instructions.insert(name, instructions.len());
+        for instruction in instructions {
+            *self.opcodes.entry(instruction.opcode()).or_default() += 1;
+        }
+        // Each register is assigned exactly once, by an input or an instruction.
+        self.registers += inputs.len()
+            + instructions
+                .iter()
+                .map(|instruction| instruction.destinations().len())
+                .sum::<usize>();
+    }
+}
+
+impl fmt::Display for ProgramStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "instructions: {}", self.total_instructions())?;
+        for (name, count) in &self.instructions {
+            writeln!(f, "    {}: {}", name, count)?;
+        }
+        writeln!(f, "registers: {}", self.registers)?;
+        writeln!(f, "structs: {}", self.structs)?;
+        writeln!(f, "records: {}", self.records)?;
+        write!(f, "opcodes:")?;
+        for (opcode, count) in &self.opcodes {
+            write!(f, "\n    {}: {}", opcode, count)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use leo_ir::{
+        testing::{function, program},
+        BinaryOperation, Composite, Finalize, Operand, Register, ValueType,
+    };
+
+    fn add(destination: u64) -> Instruction {
+        Instruction::Binary {
+            operation: BinaryOperation::AddWrapped,
+            left: Operand::Register(Register(0)),
+            right: Operand::Literal("1u8".to_string()),
+            destination: Register(destination),
+        }
+    }
+
+    #[test]
+    fn test_statistics() {
+        let output = Instruction::Output(
+            Operand::Register(Register(2)),
+            ValueType::Plaintext("u8".to_string(), None),
+        );
+        let mut main = function(FunctionKind::Function, "main", vec![add(1), add(2), output], Vec::new());
+        main.finalize = Some(Finalize {
+            name: "main".to_string(),
+            inputs: main.inputs.clone(),
+            instructions: vec![Instruction::Increment {
+                mapping: "counts".to_string(),
+                key: Operand::Register(Register(0)),
+                amount: Operand::Literal("1u8".to_string()),
+            }],
+            locations: Vec::new(),
+        });
+        let mut program = program(vec![
+            main,
+            function(FunctionKind::Closure, "double", vec![add(1)], Vec::new()),
+        ]);
+        program.composites = vec![Composite {
+            name: "token".to_string(),
+            is_record: true,
+            members: Vec::new(),
+        }];
+
+        let statistics = ProgramStatistics::new(&program);
+        assert_eq!(
+            statistics.to_string(),
+            "instructions: 5
+    closure double: 1
+    function main: 3
+    finalize main: 1
+registers: 6
+structs: 0
+records: 1
+opcodes:
+    add.w: 3
+    increment: 1
+    output: 1"
+        );
+    }
+}
@@ -14,7 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Compiler, OutputOptions};
+use crate::{
+    input_generator::InputGenerator,
+    mock_ledger::{self, MockLedger},
+    Ast, AstSnapshot, BuildOptions, Compiler, OutputOptions, PassManager, PassState,
+};
 
 use leo_errors::{
     emitter::{Buffer, Emitter, Handler},
@@ -29,11 +33,12 @@ use snarkvm::file::Manifest;
 use snarkvm::package::Package;
 use snarkvm::prelude::*;
 
-use leo_passes::{CodeGenerator, Pass};
+use leo_passes::{CodeGenerator, Pass, RegisterAllocator};
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
 use std::{
     cell::RefCell,
+    collections::BTreeMap,
     fs,
     path::{Path, PathBuf},
     rc::Rc,
@@ -42,7 +47,7 @@ use std::{fs::File, io::Write};
 
 type CurrentNetwork = Testnet3;
 
-fn new_compiler(handler: &Handler, main_file_path: PathBuf) -> Compiler<'_> {
+fn new_compiler(handler: &Handler, main_file_path: PathBuf, build_options: BuildOptions) -> Compiler<'_> {
     let output_dir = PathBuf::from("/tmp/output/");
     fs::create_dir_all(output_dir.clone()).unwrap();
 
@@ -55,11 +60,21 @@ fn new_compiler(handler: &Handler, main_file_path: PathBuf) -> Compiler<'_> {
         Some(OutputOptions {
             spans_enabled: false,
             initial_input_ast: true,
-            initial_ast: true,
-            unrolled_ast: true,
-            ssa_ast: true,
-            flattened_ast: true,
+            ast_snapshots: vec![
+                AstSnapshot::Initial,
+                AstSnapshot::Unroll,
+                AstSnapshot::Ssa,
+                AstSnapshot::Flatten,
+            ],
+            source_names: false,
+            profile: false,
+            source_map: false,
+            disassembly: false,
+            cost_report: false,
+            pass_report: false,
+            symbol_table: false,
         }),
+        Some(build_options),
     )
 }
 
@@ -67,8 +82,13 @@ fn parse_program<'a>(
     handler: &'a Handler,
     program_string: &str,
     cwd: Option<PathBuf>,
+    build_options: BuildOptions,
 ) -> Result<Compiler<'a>, LeoError> {
-    let mut compiler = new_compiler(handler, cwd.clone().unwrap_or_else(|| "compiler-test".into()));
+    let mut compiler = new_compiler(
+        handler,
+        cwd.clone().unwrap_or_else(|| "compiler-test".into()),
+        build_options,
+    );
     let name = cwd.map_or_else(|| FileName::Custom("compiler-test".into()), FileName::Real);
     compiler.parse_program_from_string(program_string, name)?;
 
@@ -116,6 +136,8 @@ struct CompileOutput {
     pub unrolled_ast: String,
     pub ssa_ast: String,
     pub flattened_ast: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub warnings: String,
 }
 
 /// Get the path of the `input_file` given in `input` into `list`.
@@ -192,18 +214,84 @@ fn temp_dir() -> PathBuf {
         .into_path()
 }
 
-fn compile_and_process<'a>(parsed: &'a mut Compiler<'a>, handler: &Handler) -> Result<String, LeoError> {
-    let st = parsed.symbol_table_pass()?;
-    let st = parsed.type_checker_pass(st)?;
-    let st = parsed.loop_unrolling_pass(st)?;
-    let assigner = parsed.static_single_assignment_pass()?;
+fn compile_and_process<'a>(
+    parsed: &'a mut Compiler<'a>,
+    handler: &Handler,
+    build_options: &BuildOptions,
+) -> Result<leo_ir::Program, LeoError> {
+    let state = PassManager::frontend().run(parsed, PassState::default())?;
+
+    // Check that the type checked AST, including the types and values resolved by type checking, survives a round trip through JSON.
+    let json = parsed.ast.to_json_string()?;
+    assert_eq!(
+        Ast::from_json_string(&json)?,
+        parsed.ast,
+        "The AST changed when reloaded from JSON."
+    );
 
-    parsed.flattening_pass(&st, assigner)?;
+    let state = PassManager::lowering(build_options).run(parsed, state)?;
+    let struct_graph = state.struct_graph.expect("The frontend creates the struct graph.");
 
     // Compile Leo program to bytecode.
-    let bytecode = CodeGenerator::do_pass((&parsed.ast, handler))?;
+    let (program, _) = CodeGenerator::do_pass((&parsed.ast, handler, &struct_graph, &parsed.network))?;
+    let (program, _) = RegisterAllocator::do_pass(program);
 
-    Ok(bytecode)
+    Ok(program)
+}
+
+/// Reads the build options of a test from its config.
+fn build_options(test: &Test) -> BuildOptions {
+    // Check for build options:
+    // ``` assertion_checking: true ```
+    // When set, warnings are emitted for assertions that fail on some inputs.
+    // ``` deny_warnings: true ```
+    // When set, warnings are emitted as errors, unless they are allowed by an annotation.
+    // ``` function_outlining: true ```
+    // When set, repeated sequences of statements are outlined into helper functions.
+    // ``` inlining_budget: 16 ```
+    // When set, standard functions are inlined when doing so adds at most the given number of statements.
+    // ``` layout_suggestions: true ```
+    // When set, warnings are emitted for structs whose serialized size could be reduced.
+    // ``` privacy_warnings: true ```
+    // When set, warnings are emitted for public values that depend on private inputs.
+    // ``` sanitize: true ```
+    // When set, assertions checking the compiler's invariants are injected into the program.
+    // ``` ternary_balancing: 4 ```
+    // When set, chains of at least the given number of selections are rebalanced into trees.
+    // ``` features: [foo, bar] ```
+    // ``` test: true ```
+    // When set, functions and statements are compiled if their `@cfg(..)` annotations hold for the given features or for testing.
+    let enabled = |option: &str| test.config.get(option).map_or(false, |val| val.as_bool().unwrap());
+    BuildOptions {
+        assertion_checking: enabled("assertion_checking"),
+        debug_compiler: true,
+        deny_warnings: enabled("deny_warnings"),
+        features: test
+            .config
+            .get("features")
+            .map(|val| {
+                val.as_sequence()
+                    .unwrap()
+                    .iter()
+                    .map(|feature| feature.as_str().unwrap().to_string())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        function_outlining: enabled("function_outlining"),
+        inlining_budget: test
+            .config
+            .get("inlining_budget")
+            .map(|val| val.as_u64().unwrap() as usize),
+        layout_suggestions: enabled("layout_suggestions"),
+        privacy_warnings: enabled("privacy_warnings"),
+        sanitize: enabled("sanitize"),
+        ternary_balancing: test
+            .config
+            .get("ternary_balancing")
+            .map(|val| val.as_u64().unwrap() as usize),
+        test: enabled("test"),
+        ..Default::default()
+    }
 }
 
 fn run_test(test: Test, handler: &Handler, err_buf: &BufferEmitter) -> Result<Value, ()> {
@@ -217,7 +305,9 @@ fn run_test(test: Test, handler: &Handler, err_buf: &BufferEmitter) -> Result<Va
         cwd.join(val.as_str().unwrap())
     });
 
-    let mut parsed = handler.extend_if_error(parse_program(handler, &test.content, cwd))?;
+    let build_options = build_options(&test);
+
+    let mut parsed = handler.extend_if_error(parse_program(handler, &test.content, cwd, build_options.clone()))?;
 
     // (name, content)
     let inputs = buffer_if_err(err_buf, collect_all_inputs(&test))?;
@@ -241,7 +331,9 @@ fn run_test(test: Test, handler: &Handler, err_buf: &BufferEmitter) -> Result<Va
 
     // Compile the program to bytecode.
     let program_name = format!("{}.{}", parsed.program_name, parsed.network);
-    let bytecode = handler.extend_if_error(compile_and_process(&mut parsed, handler))?;
+    let bytecode = handler
+        .extend_if_error(compile_and_process(&mut parsed, handler, &build_options))?
+        .to_string();
 
     // Run snarkvm package.
     {
@@ -286,16 +378,155 @@ fn run_test(test: Test, handler: &Handler, err_buf: &BufferEmitter) -> Result<Va
         unrolled_ast,
         ssa_ast,
         flattened_ast,
+        warnings: err_buf.1.take().to_string(),
     };
     Ok(serde_yaml::to_value(&final_output).expect("serialization failed"))
 }
 
+struct ExecuteNamespace;
+
+impl Namespace for ExecuteNamespace {
+    fn parse_type(&self) -> ParseType {
+        ParseType::Whole
+    }
+
+    fn run_test(&self, test: Test) -> Result<Value, String> {
+        let buf = BufferEmitter(Rc::default(), Rc::default());
+        let handler = Handler::new(Box::new(buf.clone()));
+
+        create_session_if_not_set_then(|_| {
+            run_execute_test(test, &handler, &buf).map_err(|()| buf.0.take().to_string())
+        })
+    }
+}
+
+/// The address that executes a case, unless the case gives a `caller`.
+const DEFAULT_CALLER: &str = "aleo1qnr4dkkvkgfqph0vzc3y6z2eu975wnpz2925ntjccd5cfqxtyu8s7pyjh9";
+
+/// A transition to execute against the mock ledger, given in the `cases` of an `Execute` test.
+#[derive(Deserialize)]
+struct ExecuteCase {
+    function: String,
+    #[serde(default)]
+    inputs: Vec<String>,
+    /// If set, the function is executed this many times with generated inputs rather than with `inputs`.
+    random: Option<usize>,
+    caller: Option<String>,
+}
+
+#[derive(Deserialize, PartialEq, Eq, Serialize)]
+struct ExecuteCaseOutput {
+    pub function: String,
+    /// The generated inputs, if the case is `random`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outputs: Vec<String>,
+    /// The reason that execution halted, in which case the mappings are unchanged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub halted: Option<String>,
+    /// The mappings of each program after the case, keyed by program, mapping, and key.
+    pub mappings: BTreeMap<String, BTreeMap<String, BTreeMap<String, String>>>,
+}
+
+#[derive(Deserialize, PartialEq, Eq, Serialize)]
+struct ExecuteOutput {
+    pub cases: Vec<ExecuteCaseOutput>,
+}
+
+fn run_execute_test(test: Test, handler: &Handler, err_buf: &BufferEmitter) -> Result<Value, ()> {
+    // The transitions to execute, in order, against a ledger shared by the cases:
+    // ``` cases: [{ function: mint, inputs: [aleo1..., 1u64], caller: aleo1... }] ```
+    // A case may instead execute its function with inputs generated from its signature:
+    // ``` cases: [{ function: mint, random: 10 }] ```
+    let cases: Vec<ExecuteCase> = buffer_if_err(
+        err_buf,
+        test.config
+            .get("cases")
+            .cloned()
+            .ok_or_else(|| "An `Execute` test must have `cases`.".to_string())
+            .and_then(|cases| serde_yaml::from_value(cases).map_err(|e| e.to_string())),
+    )?;
+
+    let build_options = build_options(&test);
+    let mut parsed = handler.extend_if_error(parse_program(handler, &test.content, None, build_options.clone()))?;
+    let program = handler.extend_if_error(compile_and_process(&mut parsed, handler, &build_options))?;
+
+    // Inputs are generated from a seed, which defaults to one derived from the name of the test:
+    // ``` seed: 42 ```
+    let seed = match test.config.get("seed") {
+        Some(seed) => seed.as_u64().expect("seed was not an integer"),
+        None => u64::from_str_radix(&hash_content(&test.name)[..16], 16).unwrap(),
+    };
+    let mut generator = InputGenerator::new(seed);
+
+    let mut ledger = MockLedger::default();
+    let mut outputs = Vec::new();
+    for case in cases {
+        let caller = case.caller.as_deref().unwrap_or(DEFAULT_CALLER);
+        let executions = match case.random {
+            Some(count) => {
+                let transition = buffer_if_err(err_buf, mock_ledger::transition(&program, &case.function))?;
+                let inputs = (0..count)
+                    .map(|_| generator.inputs(&program, &transition.inputs))
+                    .collect::<Result<Vec<_>, _>>();
+                buffer_if_err(err_buf, inputs)?
+                    .into_iter()
+                    .map(|inputs| (true, inputs))
+                    .collect()
+            }
+            None => {
+                let inputs = case.inputs.iter().map(|input| input.parse()).collect();
+                vec![(false, buffer_if_err(err_buf, inputs)?)]
+            }
+        };
+
+        for (generated, inputs) in executions {
+            let printed_inputs = match generated {
+                true => inputs.iter().map(|input| input.to_string()).collect(),
+                false => Vec::new(),
+            };
+            let (printed_outputs, halted) = match ledger.execute(&program, &case.function, inputs, caller) {
+                Ok(outputs) => (outputs.iter().map(|output| output.to_string()).collect(), None),
+                Err(reason) => (Vec::new(), Some(reason)),
+            };
+            let mappings = ledger
+                .programs
+                .iter()
+                .map(|(program, mappings)| {
+                    let mappings = mappings
+                        .iter()
+                        .map(|(mapping, entries)| {
+                            let entries = entries
+                                .iter()
+                                .map(|(key, value)| (key.clone(), value.to_string()))
+                                .collect();
+                            (mapping.clone(), entries)
+                        })
+                        .collect();
+                    (program.clone(), mappings)
+                })
+                .collect();
+            outputs.push(ExecuteCaseOutput {
+                function: case.function.clone(),
+                inputs: printed_inputs,
+                outputs: printed_outputs,
+                halted,
+                mappings,
+            });
+        }
+    }
+
+    Ok(serde_yaml::to_value(&ExecuteOutput { cases: outputs }).expect("serialization failed"))
+}
+
 struct TestRunner;
 
 impl Runner for TestRunner {
     fn resolve_namespace(&self, name: &str) -> Option<Box<dyn Namespace>> {
         Some(match name {
             "Compile" => Box::new(CompileNamespace),
+            "Execute" => Box::new(ExecuteNamespace),
             _ => return None,
         })
     }
@@ -0,0 +1,52 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{Function, Program};
+use leo_span::{sym, Span, Symbol};
+
+/// A `@test` function of a program, which is only compiled when building for testing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnitTest {
+    /// The name of the transition that runs the test.
+    pub name: Symbol,
+    /// The span of the test function.
+    pub span: Span,
+}
+
+impl UnitTest {
+    /// Returns the `@test` functions of `program`, in the order that they are defined.
+    pub fn collect(program: &Program) -> Vec<Self> {
+        program
+            .program_scopes
+            .values()
+            .flat_map(|scope| scope.functions.values())
+            .filter(|function| {
+                function
+                    .annotations
+                    .iter()
+                    .any(|annotation| annotation.identifier.name == sym::test)
+            })
+            .map(Self::new)
+            .collect()
+    }
+
+    fn new(function: &Function) -> Self {
+        Self {
+            name: function.identifier.name,
+            span: function.span,
+        }
+    }
+}
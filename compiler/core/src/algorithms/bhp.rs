@@ -26,6 +26,10 @@ impl CoreFunction for BHP256Hash {
         !matches!(type_, Type::Mapping(_) | Type::Tuple(_) | Type::Err | Type::Unit)
     }
 
+    fn first_arg_types() -> &'static str {
+        "a value of any type other than a tuple or a mapping"
+    }
+
     fn return_type() -> Type {
         Type::Field
     }
@@ -40,10 +44,18 @@ impl CoreFunction for BHP256Commit {
         !matches!(type_, Type::Mapping(_) | Type::Tuple(_) | Type::Err | Type::Unit)
     }
 
+    fn first_arg_types() -> &'static str {
+        "a value of any type other than a tuple or a mapping"
+    }
+
     fn second_arg_is_allowed_type(type_: &Type) -> bool {
         matches!(type_, Type::Scalar)
     }
 
+    fn second_arg_types() -> &'static str {
+        "a scalar"
+    }
+
     fn return_type() -> Type {
         Type::Field
     }
@@ -58,6 +70,10 @@ impl CoreFunction for BHP512Hash {
         !matches!(type_, Type::Mapping(_) | Type::Tuple(_) | Type::Err | Type::Unit)
     }
 
+    fn first_arg_types() -> &'static str {
+        "a value of any type other than a tuple or a mapping"
+    }
+
     fn return_type() -> Type {
         Type::Field
     }
@@ -72,10 +88,18 @@ impl CoreFunction for BHP512Commit {
         !matches!(type_, Type::Mapping(_) | Type::Tuple(_) | Type::Err | Type::Unit)
     }
 
+    fn first_arg_types() -> &'static str {
+        "a value of any type other than a tuple or a mapping"
+    }
+
     fn second_arg_is_allowed_type(type_: &Type) -> bool {
         matches!(type_, Type::Scalar)
     }
 
+    fn second_arg_types() -> &'static str {
+        "a scalar"
+    }
+
     fn return_type() -> Type {
         Type::Field
     }
@@ -90,6 +114,10 @@ impl CoreFunction for BHP768Hash {
         !matches!(type_, Type::Mapping(_) | Type::Tuple(_) | Type::Err | Type::Unit)
     }
 
+    fn first_arg_types() -> &'static str {
+        "a value of any type other than a tuple or a mapping"
+    }
+
     fn return_type() -> Type {
         Type::Field
     }
@@ -104,10 +132,18 @@ impl CoreFunction for BHP768Commit {
         !matches!(type_, Type::Mapping(_) | Type::Tuple(_) | Type::Err | Type::Unit)
     }
 
+    fn first_arg_types() -> &'static str {
+        "a value of any type other than a tuple or a mapping"
+    }
+
     fn second_arg_is_allowed_type(type_: &Type) -> bool {
         matches!(type_, Type::Scalar)
     }
 
+    fn second_arg_types() -> &'static str {
+        "a scalar"
+    }
+
     fn return_type() -> Type {
         Type::Field
     }
@@ -122,6 +158,10 @@ impl CoreFunction for BHP1024Hash {
         !matches!(type_, Type::Mapping(_) | Type::Tuple(_) | Type::Err | Type::Unit)
     }
 
+    fn first_arg_types() -> &'static str {
+        "a value of any type other than a tuple or a mapping"
+    }
+
     fn return_type() -> Type {
         Type::Field
     }
@@ -136,10 +176,18 @@ impl CoreFunction for BHP1024Commit {
         !matches!(type_, Type::Mapping(_) | Type::Tuple(_) | Type::Err | Type::Unit)
     }
 
+    fn first_arg_types() -> &'static str {
+        "a value of any type other than a tuple or a mapping"
+    }
+
     fn second_arg_is_allowed_type(type_: &Type) -> bool {
         matches!(type_, Type::Scalar)
     }
 
+    fn second_arg_types() -> &'static str {
+        "a scalar"
+    }
+
     fn return_type() -> Type {
         Type::Field
     }
@@ -0,0 +1,168 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::algorithms::CoreFunction;
+use leo_ast::{IntegerType, Type};
+
+pub struct ChaChaRandAddress;
+
+impl CoreFunction for ChaChaRandAddress {
+    const NUM_ARGS: usize = 0;
+
+    fn return_type() -> Type {
+        Type::Address
+    }
+}
+
+pub struct ChaChaRandBool;
+
+impl CoreFunction for ChaChaRandBool {
+    const NUM_ARGS: usize = 0;
+
+    fn return_type() -> Type {
+        Type::Boolean
+    }
+}
+
+pub struct ChaChaRandField;
+
+impl CoreFunction for ChaChaRandField {
+    const NUM_ARGS: usize = 0;
+
+    fn return_type() -> Type {
+        Type::Field
+    }
+}
+
+pub struct ChaChaRandGroup;
+
+impl CoreFunction for ChaChaRandGroup {
+    const NUM_ARGS: usize = 0;
+
+    fn return_type() -> Type {
+        Type::Group
+    }
+}
+
+pub struct ChaChaRandI8;
+
+impl CoreFunction for ChaChaRandI8 {
+    const NUM_ARGS: usize = 0;
+
+    fn return_type() -> Type {
+        Type::Integer(IntegerType::I8)
+    }
+}
+
+pub struct ChaChaRandI16;
+
+impl CoreFunction for ChaChaRandI16 {
+    const NUM_ARGS: usize = 0;
+
+    fn return_type() -> Type {
+        Type::Integer(IntegerType::I16)
+    }
+}
+
+pub struct ChaChaRandI32;
+
+impl CoreFunction for ChaChaRandI32 {
+    const NUM_ARGS: usize = 0;
+
+    fn return_type() -> Type {
+        Type::Integer(IntegerType::I32)
+    }
+}
+
+pub struct ChaChaRandI64;
+
+impl CoreFunction for ChaChaRandI64 {
+    const NUM_ARGS: usize = 0;
+
+    fn return_type() -> Type {
+        Type::Integer(IntegerType::I64)
+    }
+}
+
+pub struct ChaChaRandI128;
+
+impl CoreFunction for ChaChaRandI128 {
+    const NUM_ARGS: usize = 0;
+
+    fn return_type() -> Type {
+        Type::Integer(IntegerType::I128)
+    }
+}
+
+pub struct ChaChaRandScalar;
+
+impl CoreFunction for ChaChaRandScalar {
+    const NUM_ARGS: usize = 0;
+
+    fn return_type() -> Type {
+        Type::Scalar
+    }
+}
+
+pub struct ChaChaRandU8;
+
+impl CoreFunction for ChaChaRandU8 {
+    const NUM_ARGS: usize = 0;
+
+    fn return_type() -> Type {
+        Type::Integer(IntegerType::U8)
+    }
+}
+
+pub struct ChaChaRandU16;
+
+impl CoreFunction for ChaChaRandU16 {
+    const NUM_ARGS: usize = 0;
+
+    fn return_type() -> Type {
+        Type::Integer(IntegerType::U16)
+    }
+}
+
+pub struct ChaChaRandU32;
+
+impl CoreFunction for ChaChaRandU32 {
+    const NUM_ARGS: usize = 0;
+
+    fn return_type() -> Type {
+        Type::Integer(IntegerType::U32)
+    }
+}
+
+pub struct ChaChaRandU64;
+
+impl CoreFunction for ChaChaRandU64 {
+    const NUM_ARGS: usize = 0;
+
+    fn return_type() -> Type {
+        Type::Integer(IntegerType::U64)
+    }
+}
+
+pub struct ChaChaRandU128;
+
+impl CoreFunction for ChaChaRandU128 {
+    const NUM_ARGS: usize = 0;
+
+    fn return_type() -> Type {
+        Type::Integer(IntegerType::U128)
+    }
+}
@@ -16,12 +16,18 @@
 mod bhp;
 pub use bhp::*;
 
+mod chacha;
+pub use chacha::*;
+
 mod pedersen;
 pub use pedersen::*;
 
 mod poseidon;
 pub use poseidon::*;
 
+mod signature;
+pub use signature::*;
+
 use leo_ast::Type;
 use leo_span::{sym, Symbol};
 
@@ -37,6 +43,22 @@ pub enum CoreInstruction {
     BHP1024Commit,
     BHP1024Hash,
 
+    ChaChaRandAddress,
+    ChaChaRandBool,
+    ChaChaRandField,
+    ChaChaRandGroup,
+    ChaChaRandI8,
+    ChaChaRandI16,
+    ChaChaRandI32,
+    ChaChaRandI64,
+    ChaChaRandI128,
+    ChaChaRandScalar,
+    ChaChaRandU8,
+    ChaChaRandU16,
+    ChaChaRandU32,
+    ChaChaRandU64,
+    ChaChaRandU128,
+
     Pedersen64Commit,
     Pedersen64Hash,
     Pedersen128Commit,
@@ -45,6 +67,8 @@ pub enum CoreInstruction {
     Poseidon2Hash,
     Poseidon4Hash,
     Poseidon8Hash,
+
+    SignatureVerify,
 }
 
 impl CoreInstruction {
@@ -60,6 +84,22 @@ impl CoreInstruction {
             (sym::BHP1024, sym::commit) => Self::BHP1024Commit,
             (sym::BHP1024, sym::hash) => Self::BHP1024Hash,
 
+            (sym::ChaCha, sym::rand_address) => Self::ChaChaRandAddress,
+            (sym::ChaCha, sym::rand_bool) => Self::ChaChaRandBool,
+            (sym::ChaCha, sym::rand_field) => Self::ChaChaRandField,
+            (sym::ChaCha, sym::rand_group) => Self::ChaChaRandGroup,
+            (sym::ChaCha, sym::rand_i8) => Self::ChaChaRandI8,
+            (sym::ChaCha, sym::rand_i16) => Self::ChaChaRandI16,
+            (sym::ChaCha, sym::rand_i32) => Self::ChaChaRandI32,
+            (sym::ChaCha, sym::rand_i64) => Self::ChaChaRandI64,
+            (sym::ChaCha, sym::rand_i128) => Self::ChaChaRandI128,
+            (sym::ChaCha, sym::rand_scalar) => Self::ChaChaRandScalar,
+            (sym::ChaCha, sym::rand_u8) => Self::ChaChaRandU8,
+            (sym::ChaCha, sym::rand_u16) => Self::ChaChaRandU16,
+            (sym::ChaCha, sym::rand_u32) => Self::ChaChaRandU32,
+            (sym::ChaCha, sym::rand_u64) => Self::ChaChaRandU64,
+            (sym::ChaCha, sym::rand_u128) => Self::ChaChaRandU128,
+
             (sym::Pedersen64, sym::commit) => Self::Pedersen64Commit,
             (sym::Pedersen64, sym::hash) => Self::Pedersen64Hash,
             (sym::Pedersen128, sym::commit) => Self::Pedersen128Commit,
@@ -68,6 +108,8 @@ impl CoreInstruction {
             (sym::Poseidon2, sym::hash) => Self::Poseidon2Hash,
             (sym::Poseidon4, sym::hash) => Self::Poseidon4Hash,
             (sym::Poseidon8, sym::hash) => Self::Poseidon8Hash,
+
+            (sym::signature, sym::verify) => Self::SignatureVerify,
             _ => return None,
         })
     }
@@ -84,6 +126,22 @@ impl CoreInstruction {
             Self::BHP1024Commit => BHP1024Commit::NUM_ARGS,
             Self::BHP1024Hash => BHP1024Hash::NUM_ARGS,
 
+            Self::ChaChaRandAddress => ChaChaRandAddress::NUM_ARGS,
+            Self::ChaChaRandBool => ChaChaRandBool::NUM_ARGS,
+            Self::ChaChaRandField => ChaChaRandField::NUM_ARGS,
+            Self::ChaChaRandGroup => ChaChaRandGroup::NUM_ARGS,
+            Self::ChaChaRandI8 => ChaChaRandI8::NUM_ARGS,
+            Self::ChaChaRandI16 => ChaChaRandI16::NUM_ARGS,
+            Self::ChaChaRandI32 => ChaChaRandI32::NUM_ARGS,
+            Self::ChaChaRandI64 => ChaChaRandI64::NUM_ARGS,
+            Self::ChaChaRandI128 => ChaChaRandI128::NUM_ARGS,
+            Self::ChaChaRandScalar => ChaChaRandScalar::NUM_ARGS,
+            Self::ChaChaRandU8 => ChaChaRandU8::NUM_ARGS,
+            Self::ChaChaRandU16 => ChaChaRandU16::NUM_ARGS,
+            Self::ChaChaRandU32 => ChaChaRandU32::NUM_ARGS,
+            Self::ChaChaRandU64 => ChaChaRandU64::NUM_ARGS,
+            Self::ChaChaRandU128 => ChaChaRandU128::NUM_ARGS,
+
             Self::Pedersen64Commit => Pedersen64Commit::NUM_ARGS,
             Self::Pedersen64Hash => Pedersen64Hash::NUM_ARGS,
             Self::Pedersen128Commit => Pedersen128Commit::NUM_ARGS,
@@ -92,6 +150,8 @@ impl CoreInstruction {
             Self::Poseidon2Hash => Poseidon2Hash::NUM_ARGS,
             Self::Poseidon4Hash => Poseidon4Hash::NUM_ARGS,
             Self::Poseidon8Hash => Poseidon8Hash::NUM_ARGS,
+
+            Self::SignatureVerify => SignatureVerify::NUM_ARGS,
         }
     }
 
@@ -106,6 +166,21 @@ impl CoreInstruction {
             CoreInstruction::BHP768Hash => BHP768Hash::first_arg_is_allowed_type(type_),
             CoreInstruction::BHP1024Commit => BHP1024Commit::first_arg_is_allowed_type(type_),
             CoreInstruction::BHP1024Hash => BHP1024Hash::first_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandAddress => ChaChaRandAddress::first_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandBool => ChaChaRandBool::first_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandField => ChaChaRandField::first_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandGroup => ChaChaRandGroup::first_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandI8 => ChaChaRandI8::first_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandI16 => ChaChaRandI16::first_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandI32 => ChaChaRandI32::first_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandI64 => ChaChaRandI64::first_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandI128 => ChaChaRandI128::first_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandScalar => ChaChaRandScalar::first_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandU8 => ChaChaRandU8::first_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandU16 => ChaChaRandU16::first_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandU32 => ChaChaRandU32::first_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandU64 => ChaChaRandU64::first_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandU128 => ChaChaRandU128::first_arg_is_allowed_type(type_),
             CoreInstruction::Pedersen64Commit => Pedersen64Commit::first_arg_is_allowed_type(type_),
             CoreInstruction::Pedersen64Hash => Pedersen64Hash::first_arg_is_allowed_type(type_),
             CoreInstruction::Pedersen128Commit => Pedersen128Commit::first_arg_is_allowed_type(type_),
@@ -113,6 +188,7 @@ impl CoreInstruction {
             CoreInstruction::Poseidon2Hash => Poseidon2Hash::first_arg_is_allowed_type(type_),
             CoreInstruction::Poseidon4Hash => Poseidon4Hash::first_arg_is_allowed_type(type_),
             CoreInstruction::Poseidon8Hash => Poseidon8Hash::first_arg_is_allowed_type(type_),
+            CoreInstruction::SignatureVerify => SignatureVerify::first_arg_is_allowed_type(type_),
         }
     }
 
@@ -127,6 +203,21 @@ impl CoreInstruction {
             CoreInstruction::BHP768Hash => BHP768Hash::second_arg_is_allowed_type(type_),
             CoreInstruction::BHP1024Commit => BHP1024Commit::second_arg_is_allowed_type(type_),
             CoreInstruction::BHP1024Hash => BHP1024Hash::second_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandAddress => ChaChaRandAddress::second_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandBool => ChaChaRandBool::second_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandField => ChaChaRandField::second_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandGroup => ChaChaRandGroup::second_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandI8 => ChaChaRandI8::second_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandI16 => ChaChaRandI16::second_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandI32 => ChaChaRandI32::second_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandI64 => ChaChaRandI64::second_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandI128 => ChaChaRandI128::second_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandScalar => ChaChaRandScalar::second_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandU8 => ChaChaRandU8::second_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandU16 => ChaChaRandU16::second_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandU32 => ChaChaRandU32::second_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandU64 => ChaChaRandU64::second_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandU128 => ChaChaRandU128::second_arg_is_allowed_type(type_),
             CoreInstruction::Pedersen64Commit => Pedersen64Commit::second_arg_is_allowed_type(type_),
             CoreInstruction::Pedersen64Hash => Pedersen64Hash::second_arg_is_allowed_type(type_),
             CoreInstruction::Pedersen128Commit => Pedersen128Commit::second_arg_is_allowed_type(type_),
@@ -134,9 +225,180 @@ impl CoreInstruction {
             CoreInstruction::Poseidon2Hash => Poseidon2Hash::second_arg_is_allowed_type(type_),
             CoreInstruction::Poseidon4Hash => Poseidon4Hash::second_arg_is_allowed_type(type_),
             CoreInstruction::Poseidon8Hash => Poseidon8Hash::second_arg_is_allowed_type(type_),
+            CoreInstruction::SignatureVerify => SignatureVerify::second_arg_is_allowed_type(type_),
         }
     }
 
+    /// Describes the types allowed for the first argument.
+    pub fn first_arg_types(&self) -> &'static str {
+        match self {
+            CoreInstruction::BHP256Commit => BHP256Commit::first_arg_types(),
+            CoreInstruction::BHP256Hash => BHP256Hash::first_arg_types(),
+            CoreInstruction::BHP512Commit => BHP512Commit::first_arg_types(),
+            CoreInstruction::BHP512Hash => BHP512Hash::first_arg_types(),
+            CoreInstruction::BHP768Commit => BHP768Commit::first_arg_types(),
+            CoreInstruction::BHP768Hash => BHP768Hash::first_arg_types(),
+            CoreInstruction::BHP1024Commit => BHP1024Commit::first_arg_types(),
+            CoreInstruction::BHP1024Hash => BHP1024Hash::first_arg_types(),
+            CoreInstruction::ChaChaRandAddress => ChaChaRandAddress::first_arg_types(),
+            CoreInstruction::ChaChaRandBool => ChaChaRandBool::first_arg_types(),
+            CoreInstruction::ChaChaRandField => ChaChaRandField::first_arg_types(),
+            CoreInstruction::ChaChaRandGroup => ChaChaRandGroup::first_arg_types(),
+            CoreInstruction::ChaChaRandI8 => ChaChaRandI8::first_arg_types(),
+            CoreInstruction::ChaChaRandI16 => ChaChaRandI16::first_arg_types(),
+            CoreInstruction::ChaChaRandI32 => ChaChaRandI32::first_arg_types(),
+            CoreInstruction::ChaChaRandI64 => ChaChaRandI64::first_arg_types(),
+            CoreInstruction::ChaChaRandI128 => ChaChaRandI128::first_arg_types(),
+            CoreInstruction::ChaChaRandScalar => ChaChaRandScalar::first_arg_types(),
+            CoreInstruction::ChaChaRandU8 => ChaChaRandU8::first_arg_types(),
+            CoreInstruction::ChaChaRandU16 => ChaChaRandU16::first_arg_types(),
+            CoreInstruction::ChaChaRandU32 => ChaChaRandU32::first_arg_types(),
+            CoreInstruction::ChaChaRandU64 => ChaChaRandU64::first_arg_types(),
+            CoreInstruction::ChaChaRandU128 => ChaChaRandU128::first_arg_types(),
+            CoreInstruction::Pedersen64Commit => Pedersen64Commit::first_arg_types(),
+            CoreInstruction::Pedersen64Hash => Pedersen64Hash::first_arg_types(),
+            CoreInstruction::Pedersen128Commit => Pedersen128Commit::first_arg_types(),
+            CoreInstruction::Pedersen128Hash => Pedersen128Hash::first_arg_types(),
+            CoreInstruction::Poseidon2Hash => Poseidon2Hash::first_arg_types(),
+            CoreInstruction::Poseidon4Hash => Poseidon4Hash::first_arg_types(),
+            CoreInstruction::Poseidon8Hash => Poseidon8Hash::first_arg_types(),
+            CoreInstruction::SignatureVerify => SignatureVerify::first_arg_types(),
+        }
+    }
+
+    /// Describes the types allowed for the second argument.
+    pub fn second_arg_types(&self) -> &'static str {
+        match self {
+            CoreInstruction::BHP256Commit => BHP256Commit::second_arg_types(),
+            CoreInstruction::BHP256Hash => BHP256Hash::second_arg_types(),
+            CoreInstruction::BHP512Commit => BHP512Commit::second_arg_types(),
+            CoreInstruction::BHP512Hash => BHP512Hash::second_arg_types(),
+            CoreInstruction::BHP768Commit => BHP768Commit::second_arg_types(),
+            CoreInstruction::BHP768Hash => BHP768Hash::second_arg_types(),
+            CoreInstruction::BHP1024Commit => BHP1024Commit::second_arg_types(),
+            CoreInstruction::BHP1024Hash => BHP1024Hash::second_arg_types(),
+            CoreInstruction::ChaChaRandAddress => ChaChaRandAddress::second_arg_types(),
+            CoreInstruction::ChaChaRandBool => ChaChaRandBool::second_arg_types(),
+            CoreInstruction::ChaChaRandField => ChaChaRandField::second_arg_types(),
+            CoreInstruction::ChaChaRandGroup => ChaChaRandGroup::second_arg_types(),
+            CoreInstruction::ChaChaRandI8 => ChaChaRandI8::second_arg_types(),
+            CoreInstruction::ChaChaRandI16 => ChaChaRandI16::second_arg_types(),
+            CoreInstruction::ChaChaRandI32 => ChaChaRandI32::second_arg_types(),
+            CoreInstruction::ChaChaRandI64 => ChaChaRandI64::second_arg_types(),
+            CoreInstruction::ChaChaRandI128 => ChaChaRandI128::second_arg_types(),
+            CoreInstruction::ChaChaRandScalar => ChaChaRandScalar::second_arg_types(),
+            CoreInstruction::ChaChaRandU8 => ChaChaRandU8::second_arg_types(),
+            CoreInstruction::ChaChaRandU16 => ChaChaRandU16::second_arg_types(),
+            CoreInstruction::ChaChaRandU32 => ChaChaRandU32::second_arg_types(),
+            CoreInstruction::ChaChaRandU64 => ChaChaRandU64::second_arg_types(),
+            CoreInstruction::ChaChaRandU128 => ChaChaRandU128::second_arg_types(),
+            CoreInstruction::Pedersen64Commit => Pedersen64Commit::second_arg_types(),
+            CoreInstruction::Pedersen64Hash => Pedersen64Hash::second_arg_types(),
+            CoreInstruction::Pedersen128Commit => Pedersen128Commit::second_arg_types(),
+            CoreInstruction::Pedersen128Hash => Pedersen128Hash::second_arg_types(),
+            CoreInstruction::Poseidon2Hash => Poseidon2Hash::second_arg_types(),
+            CoreInstruction::Poseidon4Hash => Poseidon4Hash::second_arg_types(),
+            CoreInstruction::Poseidon8Hash => Poseidon8Hash::second_arg_types(),
+            CoreInstruction::SignatureVerify => SignatureVerify::second_arg_types(),
+        }
+    }
+
+    /// Returns whether or not the third argument is an allowed type.
+    pub fn third_arg_is_allowed_type(&self, type_: &Type) -> bool {
+        match self {
+            CoreInstruction::BHP256Commit => BHP256Commit::third_arg_is_allowed_type(type_),
+            CoreInstruction::BHP256Hash => BHP256Hash::third_arg_is_allowed_type(type_),
+            CoreInstruction::BHP512Commit => BHP512Commit::third_arg_is_allowed_type(type_),
+            CoreInstruction::BHP512Hash => BHP512Hash::third_arg_is_allowed_type(type_),
+            CoreInstruction::BHP768Commit => BHP768Commit::third_arg_is_allowed_type(type_),
+            CoreInstruction::BHP768Hash => BHP768Hash::third_arg_is_allowed_type(type_),
+            CoreInstruction::BHP1024Commit => BHP1024Commit::third_arg_is_allowed_type(type_),
+            CoreInstruction::BHP1024Hash => BHP1024Hash::third_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandAddress => ChaChaRandAddress::third_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandBool => ChaChaRandBool::third_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandField => ChaChaRandField::third_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandGroup => ChaChaRandGroup::third_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandI8 => ChaChaRandI8::third_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandI16 => ChaChaRandI16::third_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandI32 => ChaChaRandI32::third_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandI64 => ChaChaRandI64::third_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandI128 => ChaChaRandI128::third_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandScalar => ChaChaRandScalar::third_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandU8 => ChaChaRandU8::third_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandU16 => ChaChaRandU16::third_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandU32 => ChaChaRandU32::third_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandU64 => ChaChaRandU64::third_arg_is_allowed_type(type_),
+            CoreInstruction::ChaChaRandU128 => ChaChaRandU128::third_arg_is_allowed_type(type_),
+            CoreInstruction::Pedersen64Commit => Pedersen64Commit::third_arg_is_allowed_type(type_),
+            CoreInstruction::Pedersen64Hash => Pedersen64Hash::third_arg_is_allowed_type(type_),
+            CoreInstruction::Pedersen128Commit => Pedersen128Commit::third_arg_is_allowed_type(type_),
+            CoreInstruction::Pedersen128Hash => Pedersen128Hash::third_arg_is_allowed_type(type_),
+            CoreInstruction::Poseidon2Hash => Poseidon2Hash::third_arg_is_allowed_type(type_),
+            CoreInstruction::Poseidon4Hash => Poseidon4Hash::third_arg_is_allowed_type(type_),
+            CoreInstruction::Poseidon8Hash => Poseidon8Hash::third_arg_is_allowed_type(type_),
+            CoreInstruction::SignatureVerify => SignatureVerify::third_arg_is_allowed_type(type_),
+        }
+    }
+
+    /// Describes the types allowed for the third argument.
+    pub fn third_arg_types(&self) -> &'static str {
+        match self {
+            CoreInstruction::BHP256Commit => BHP256Commit::third_arg_types(),
+            CoreInstruction::BHP256Hash => BHP256Hash::third_arg_types(),
+            CoreInstruction::BHP512Commit => BHP512Commit::third_arg_types(),
+            CoreInstruction::BHP512Hash => BHP512Hash::third_arg_types(),
+            CoreInstruction::BHP768Commit => BHP768Commit::third_arg_types(),
+            CoreInstruction::BHP768Hash => BHP768Hash::third_arg_types(),
+            CoreInstruction::BHP1024Commit => BHP1024Commit::third_arg_types(),
+            CoreInstruction::BHP1024Hash => BHP1024Hash::third_arg_types(),
+            CoreInstruction::ChaChaRandAddress => ChaChaRandAddress::third_arg_types(),
+            CoreInstruction::ChaChaRandBool => ChaChaRandBool::third_arg_types(),
+            CoreInstruction::ChaChaRandField => ChaChaRandField::third_arg_types(),
+            CoreInstruction::ChaChaRandGroup => ChaChaRandGroup::third_arg_types(),
+            CoreInstruction::ChaChaRandI8 => ChaChaRandI8::third_arg_types(),
+            CoreInstruction::ChaChaRandI16 => ChaChaRandI16::third_arg_types(),
+            CoreInstruction::ChaChaRandI32 => ChaChaRandI32::third_arg_types(),
+            CoreInstruction::ChaChaRandI64 => ChaChaRandI64::third_arg_types(),
+            CoreInstruction::ChaChaRandI128 => ChaChaRandI128::third_arg_types(),
+            CoreInstruction::ChaChaRandScalar => ChaChaRandScalar::third_arg_types(),
+            CoreInstruction::ChaChaRandU8 => ChaChaRandU8::third_arg_types(),
+            CoreInstruction::ChaChaRandU16 => ChaChaRandU16::third_arg_types(),
+            CoreInstruction::ChaChaRandU32 => ChaChaRandU32::third_arg_types(),
+            CoreInstruction::ChaChaRandU64 => ChaChaRandU64::third_arg_types(),
+            CoreInstruction::ChaChaRandU128 => ChaChaRandU128::third_arg_types(),
+            CoreInstruction::Pedersen64Commit => Pedersen64Commit::third_arg_types(),
+            CoreInstruction::Pedersen64Hash => Pedersen64Hash::third_arg_types(),
+            CoreInstruction::Pedersen128Commit => Pedersen128Commit::third_arg_types(),
+            CoreInstruction::Pedersen128Hash => Pedersen128Hash::third_arg_types(),
+            CoreInstruction::Poseidon2Hash => Poseidon2Hash::third_arg_types(),
+            CoreInstruction::Poseidon4Hash => Poseidon4Hash::third_arg_types(),
+            CoreInstruction::Poseidon8Hash => Poseidon8Hash::third_arg_types(),
+            CoreInstruction::SignatureVerify => SignatureVerify::third_arg_types(),
+        }
+    }
+
+    /// Returns whether or not the instruction can only be executed on chain, in a finalize block.
+    pub fn is_finalize_only(&self) -> bool {
+        matches!(
+            self,
+            Self::ChaChaRandAddress
+                | Self::ChaChaRandBool
+                | Self::ChaChaRandField
+                | Self::ChaChaRandGroup
+                | Self::ChaChaRandI8
+                | Self::ChaChaRandI16
+                | Self::ChaChaRandI32
+                | Self::ChaChaRandI64
+                | Self::ChaChaRandI128
+                | Self::ChaChaRandScalar
+                | Self::ChaChaRandU8
+                | Self::ChaChaRandU16
+                | Self::ChaChaRandU32
+                | Self::ChaChaRandU64
+                | Self::ChaChaRandU128
+        )
+    }
+
     /// The type of the instruction output.
     pub fn return_type(&self) -> Type {
         match self {
@@ -149,6 +411,22 @@ impl CoreInstruction {
             Self::BHP1024Commit => BHP1024Commit::return_type(),
             Self::BHP1024Hash => BHP1024Hash::return_type(),
 
+            Self::ChaChaRandAddress => ChaChaRandAddress::return_type(),
+            Self::ChaChaRandBool => ChaChaRandBool::return_type(),
+            Self::ChaChaRandField => ChaChaRandField::return_type(),
+            Self::ChaChaRandGroup => ChaChaRandGroup::return_type(),
+            Self::ChaChaRandI8 => ChaChaRandI8::return_type(),
+            Self::ChaChaRandI16 => ChaChaRandI16::return_type(),
+            Self::ChaChaRandI32 => ChaChaRandI32::return_type(),
+            Self::ChaChaRandI64 => ChaChaRandI64::return_type(),
+            Self::ChaChaRandI128 => ChaChaRandI128::return_type(),
+            Self::ChaChaRandScalar => ChaChaRandScalar::return_type(),
+            Self::ChaChaRandU8 => ChaChaRandU8::return_type(),
+            Self::ChaChaRandU16 => ChaChaRandU16::return_type(),
+            Self::ChaChaRandU32 => ChaChaRandU32::return_type(),
+            Self::ChaChaRandU64 => ChaChaRandU64::return_type(),
+            Self::ChaChaRandU128 => ChaChaRandU128::return_type(),
+
             Self::Pedersen64Commit => Pedersen64Commit::return_type(),
             Self::Pedersen64Hash => Pedersen64Hash::return_type(),
             Self::Pedersen128Commit => Pedersen128Commit::return_type(),
@@ -157,11 +435,13 @@ impl CoreInstruction {
             Self::Poseidon2Hash => Poseidon2Hash::return_type(),
             Self::Poseidon4Hash => Poseidon4Hash::return_type(),
             Self::Poseidon8Hash => Poseidon8Hash::return_type(),
+
+            Self::SignatureVerify => SignatureVerify::return_type(),
         }
     }
 }
 
-/// A core function of a core struct, e.g. `hash` or `commit`
+/// A core function of a core struct, e.g. `hash`, `commit`, `verify` or `rand_u64`
 /// Provides required type information to the type checker.
 trait CoreFunction {
     const NUM_ARGS: usize;
@@ -171,11 +451,31 @@ trait CoreFunction {
         false
     }
 
+    /// Describes the types allowed for the first argument, for error messages.
+    fn first_arg_types() -> &'static str {
+        "no type"
+    }
+
     /// Returns whether or not the second argument is an allowed type.
     fn second_arg_is_allowed_type(_: &Type) -> bool {
         false
     }
 
+    /// Describes the types allowed for the second argument, for error messages.
+    fn second_arg_types() -> &'static str {
+        "no type"
+    }
+
+    /// Returns whether or not the third argument is an allowed type.
+    fn third_arg_is_allowed_type(_: &Type) -> bool {
+        false
+    }
+
+    /// Describes the types allowed for the third argument, for error messages.
+    fn third_arg_types() -> &'static str {
+        "no type"
+    }
+
     /// The return type of the core function.
     fn return_type() -> Type;
 }
@@ -34,10 +34,14 @@ impl CoreFunction for Pedersen64Hash {
                 | Type::Integer(IntegerType::U16)
                 | Type::Integer(IntegerType::U32)
                 | Type::Integer(IntegerType::U64)
-                | Type::String
+                | Type::String(_)
         )
     }
 
+    fn first_arg_types() -> &'static str {
+        "a boolean, an integer of at most 64 bits, or a string"
+    }
+
     fn return_type() -> Type {
         Type::Field
     }
@@ -60,14 +64,22 @@ impl CoreFunction for Pedersen64Commit {
                 | Type::Integer(IntegerType::U16)
                 | Type::Integer(IntegerType::U32)
                 | Type::Integer(IntegerType::U64)
-                | Type::String
+                | Type::String(_)
         )
     }
 
+    fn first_arg_types() -> &'static str {
+        "a boolean, an integer of at most 64 bits, or a string"
+    }
+
     fn second_arg_is_allowed_type(type_: &Type) -> bool {
         matches!(type_, Type::Scalar)
     }
 
+    fn second_arg_types() -> &'static str {
+        "a scalar"
+    }
+
     fn return_type() -> Type {
         Type::Group
     }
@@ -79,7 +91,11 @@ impl CoreFunction for Pedersen128Hash {
     const NUM_ARGS: usize = 1;
 
     fn first_arg_is_allowed_type(type_: &Type) -> bool {
-        matches!(type_, Type::Boolean | Type::Integer(_) | Type::String)
+        matches!(type_, Type::Boolean | Type::Integer(_) | Type::String(_))
+    }
+
+    fn first_arg_types() -> &'static str {
+        "a boolean, an integer, or a string"
     }
 
     fn return_type() -> Type {
@@ -93,13 +109,21 @@ impl CoreFunction for Pedersen128Commit {
     const NUM_ARGS: usize = 2;
 
     fn first_arg_is_allowed_type(type_: &Type) -> bool {
-        matches!(type_, Type::Boolean | Type::Integer(_) | Type::String)
+        matches!(type_, Type::Boolean | Type::Integer(_) | Type::String(_))
+    }
+
+    fn first_arg_types() -> &'static str {
+        "a boolean, an integer, or a string"
     }
 
     fn second_arg_is_allowed_type(type_: &Type) -> bool {
         matches!(type_, Type::Scalar)
     }
 
+    fn second_arg_types() -> &'static str {
+        "a scalar"
+    }
+
     fn return_type() -> Type {
         Type::Group
     }
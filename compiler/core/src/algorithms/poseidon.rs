@@ -26,6 +26,10 @@ impl CoreFunction for Poseidon2Hash {
         !matches!(type_, Type::Mapping(_) | Type::Tuple(_) | Type::Err | Type::Unit)
     }
 
+    fn first_arg_types() -> &'static str {
+        "a value of any type other than a tuple or a mapping"
+    }
+
     fn return_type() -> Type {
         Type::Field
     }
@@ -40,6 +44,10 @@ impl CoreFunction for Poseidon4Hash {
         !matches!(type_, Type::Mapping(_) | Type::Tuple(_) | Type::Err | Type::Unit)
     }
 
+    fn first_arg_types() -> &'static str {
+        "a value of any type other than a tuple or a mapping"
+    }
+
     fn return_type() -> Type {
         Type::Field
     }
@@ -54,6 +62,10 @@ impl CoreFunction for Poseidon8Hash {
         !matches!(type_, Type::Mapping(_) | Type::Tuple(_) | Type::Err | Type::Unit)
     }
 
+    fn first_arg_types() -> &'static str {
+        "a value of any type other than a tuple or a mapping"
+    }
+
     fn return_type() -> Type {
         Type::Field
     }
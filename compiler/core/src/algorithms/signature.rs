@@ -0,0 +1,55 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::algorithms::CoreFunction;
+use leo_ast::Type;
+
+pub struct SignatureVerify;
+
+impl CoreFunction for SignatureVerify {
+    const NUM_ARGS: usize = 3;
+
+    fn first_arg_is_allowed_type(type_: &Type) -> bool {
+        matches!(type_, Type::Signature)
+    }
+
+    fn first_arg_types() -> &'static str {
+        "a signature"
+    }
+
+    fn second_arg_is_allowed_type(type_: &Type) -> bool {
+        matches!(type_, Type::Address)
+    }
+
+    fn second_arg_types() -> &'static str {
+        "an address"
+    }
+
+    fn third_arg_is_allowed_type(type_: &Type) -> bool {
+        !matches!(
+            type_,
+            Type::Mapping(_) | Type::String(_) | Type::Tuple(_) | Type::Err | Type::Unit
+        )
+    }
+
+    fn third_arg_types() -> &'static str {
+        "a value of any type other than a string, a tuple or a mapping"
+    }
+
+    fn return_type() -> Type {
+        Type::Boolean
+    }
+}
@@ -0,0 +1,305 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Interpreter, Value};
+
+use leo_ast::{
+    AccessExpression, AssociatedFunction, BinaryOperation, CallExpression, CastExpression, Expression,
+    StructExpression, Type, UnaryExpression, UnaryOperation, WideInteger,
+};
+use leo_errors::{InterpreterError, LeoError, Result};
+use leo_span::{sym, Span};
+
+use snarkvm_console::prelude::{Compare, Double, Inverse, Pow, Square, SquareRoot, Zero};
+use std::fmt::Display;
+
+impl<'a> Interpreter<'a> {
+    /// Evaluates an expression in the current scope.
+    pub(crate) fn evaluate_expression(&mut self, expression: &Expression) -> Result<Value> {
+        match expression {
+            Expression::Access(access) => self.evaluate_access(access),
+            Expression::Array(array) => Ok(Value::Array(self.evaluate_expressions(&array.elements)?)),
+            Expression::Binary(binary) => {
+                let left = self.evaluate_expression(&binary.left)?;
+                let right = self.evaluate_expression(&binary.right)?;
+                evaluate_binary(binary.op, left, right, binary, binary.span)
+            }
+            Expression::Call(call) => self.evaluate_call(call),
+            Expression::Cast(cast) => {
+                let value = self.evaluate_expression(&cast.expression)?;
+                evaluate_cast(cast, value)
+            }
+            Expression::Struct(struct_) => self.evaluate_struct(struct_),
+            Expression::Err(_) => {
+                unreachable!("`ErrExpression`s should not be in the AST at this phase of compilation.")
+            }
+            // Special variables, such as `self.caller`, are not bound to a value.
+            Expression::Identifier(identifier) => self.lookup(identifier.name).cloned().ok_or_else(|| {
+                InterpreterError::unsupported_expression("variable", identifier, identifier.span).into()
+            }),
+            Expression::Literal(literal) => Value::from_literal(literal),
+            Expression::Ternary(ternary) => match self.evaluate_expression(&ternary.condition)?.to_bool() {
+                true => self.evaluate_expression(&ternary.if_true),
+                false => self.evaluate_expression(&ternary.if_false),
+            },
+            Expression::Tuple(tuple) => Ok(Value::Tuple(self.evaluate_expressions(&tuple.elements)?)),
+            Expression::Unary(unary) => {
+                let receiver = self.evaluate_expression(&unary.receiver)?;
+                evaluate_unary(unary, receiver)
+            }
+        }
+    }
+
+    fn evaluate_expressions(&mut self, expressions: &[Expression]) -> Result<Vec<Value>> {
+        expressions
+            .iter()
+            .map(|expression| self.evaluate_expression(expression))
+            .collect()
+    }
+
+    fn evaluate_access(&mut self, access: &AccessExpression) -> Result<Value> {
+        match access {
+            AccessExpression::Array(array_access) => {
+                let array = self.evaluate_expression(&array_access.array)?;
+                let index = self.evaluate_expression(&array_access.index)?;
+                match array {
+                    Value::Array(mut elements) => {
+                        let index = array_index(&index, elements.len(), array_access, array_access.span)?;
+                        Ok(elements.swap_remove(index))
+                    }
+                    _ => unreachable!("Type checking guarantees that only arrays are indexed."),
+                }
+            }
+            AccessExpression::AssociatedConstant(constant) => {
+                Err(InterpreterError::unsupported_expression("associated constant", constant, constant.span).into())
+            }
+            AccessExpression::AssociatedFunction(function) => self.evaluate_associated_function(function),
+            AccessExpression::Member(member_access) => match self.evaluate_expression(&member_access.inner)? {
+                Value::Struct(_, mut members) => Ok(members
+                    .remove(&member_access.name.name)
+                    .expect("Type checking guarantees that the member exists.")),
+                _ => unreachable!("Type checking guarantees that only structs have members."),
+            },
+            AccessExpression::Tuple(tuple_access) => match self.evaluate_expression(&tuple_access.tuple)? {
+                Value::Tuple(mut elements) => Ok(elements.swap_remove(tuple_access.index.to_usize())),
+                _ => unreachable!("Type checking guarantees that only tuples are accessed by position."),
+            },
+        }
+    }
+
+    /// Evaluates a mapping operation on the mappings of the interpreter.
+    /// Other core functions are not supported.
+    fn evaluate_associated_function(&mut self, function: &AssociatedFunction) -> Result<Value> {
+        if !matches!(function.ty, Type::Identifier(identifier) if identifier.name == sym::Mapping) {
+            return Err(InterpreterError::unsupported_expression("core function", function, function.span).into());
+        }
+
+        // Note that the mapping is referred to by name.
+        let mapping = match &function.args[0] {
+            Expression::Identifier(identifier) => identifier.name,
+            _ => unreachable!("Type checking guarantees that the first argument of a mapping operation is a mapping."),
+        };
+        let mut arguments = self.evaluate_expressions(&function.args[1..])?.into_iter();
+        let key = arguments.next().unwrap().to_string();
+        let entries = self
+            .mappings
+            .get_mut(&mapping)
+            .expect("Type checking guarantees that the mapping exists.");
+
+        match function.name.name {
+            sym::get => entries.get(&key).cloned().ok_or_else(|| {
+                InterpreterError::halted(function, format!("the mapping has no entry for `{key}`"), function.span)
+                    .into()
+            }),
+            sym::get_or_use => Ok(entries.get(&key).cloned().unwrap_or_else(|| arguments.next().unwrap())),
+            sym::set => {
+                entries.insert(key, arguments.next().unwrap());
+                Ok(Value::Tuple(Vec::new()))
+            }
+            _ => unreachable!("Type checking guarantees that all mapping operations are known."),
+        }
+    }
+
+    fn evaluate_call(&mut self, call: &CallExpression) -> Result<Value> {
+        let name = match (call.function.as_ref(), &call.external) {
+            (Expression::Identifier(identifier), None) => identifier.name,
+            _ => return Err(InterpreterError::unsupported_expression("external call", call, call.span).into()),
+        };
+        let function = *self
+            .functions
+            .get(&name)
+            .expect("Type checking guarantees that the function exists.");
+        let arguments = self.evaluate_expressions(&call.arguments)?;
+        self.call_function(function, arguments)
+    }
+
+    fn evaluate_struct(&mut self, struct_: &StructExpression) -> Result<Value> {
+        let members = struct_
+            .members
+            .iter()
+            .map(|member| {
+                let value = match &member.expression {
+                    Some(expression) => self.evaluate_expression(expression)?,
+                    // The member is initialized with the variable of the same name, e.g. `Foo { x }`.
+                    None => self.evaluate_expression(&Expression::Identifier(member.identifier))?,
+                };
+                Ok((member.identifier.name, value))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Value::Struct(struct_.name.name, members))
+    }
+}
+
+/// Returns `index` as an index into an array of the given length, halting if it is out of bounds.
+fn array_index(index: &Value, length: usize, expression: impl Display, span: Span) -> Result<usize> {
+    let position = match index {
+        Value::Integer(integer) => WideInteger::from_value(integer).and_then(WideInteger::to_u32),
+        _ => unreachable!("Type checking guarantees that arrays are indexed by integers."),
+    };
+    match position {
+        Some(position) if (position as usize) < length => Ok(position as usize),
+        _ => Err(InterpreterError::halted(
+            expression,
+            format!("the index `{index}` is out of bounds for an array of length {length}"),
+            span,
+        )
+        .into()),
+    }
+}
+
+/// Applies a binary operation to two values, halting at `expression` if the operation halts.
+pub(crate) fn evaluate_binary(
+    op: BinaryOperation,
+    left: Value,
+    right: Value,
+    expression: impl Display,
+    span: Span,
+) -> Result<Value> {
+    use BinaryOperation::*;
+
+    let halted = |reason: String| -> LeoError { InterpreterError::halted(&expression, reason, span).into() };
+
+    Ok(match (op, left, right) {
+        (Eq, left, right) => Value::Boolean(left == right),
+        (Neq, left, right) => Value::Boolean(left != right),
+
+        (And | BitwiseAnd, Value::Boolean(left), Value::Boolean(right)) => Value::Boolean(left && right),
+        (Or | BitwiseOr, Value::Boolean(left), Value::Boolean(right)) => Value::Boolean(left || right),
+        (Nand, Value::Boolean(left), Value::Boolean(right)) => Value::Boolean(!(left && right)),
+        (Nor, Value::Boolean(left), Value::Boolean(right)) => Value::Boolean(!(left || right)),
+        (Xor, Value::Boolean(left), Value::Boolean(right)) => Value::Boolean(left != right),
+
+        (Add, Value::Field(left), Value::Field(right)) => Value::Field(left + right),
+        (Add, Value::Group(left), Value::Group(right)) => Value::Group(left + right),
+        (Add, Value::Scalar(left), Value::Scalar(right)) => Value::Scalar(left + right),
+        (Sub, Value::Field(left), Value::Field(right)) => Value::Field(left - right),
+        (Sub, Value::Group(left), Value::Group(right)) => Value::Group(left - right),
+        (Sub, Value::Scalar(left), Value::Scalar(right)) => Value::Scalar(left - right),
+        (Mul, Value::Field(left), Value::Field(right)) => Value::Field(left * right),
+        (Mul, Value::Group(group), Value::Scalar(scalar)) | (Mul, Value::Scalar(scalar), Value::Group(group)) => {
+            Value::Group(group * scalar)
+        }
+        (Div, Value::Field(left), Value::Field(right)) => match right.is_zero() {
+            true => return Err(halted(format!("`{left}` is divided by zero"))),
+            false => Value::Field(left / right),
+        },
+        (Pow, Value::Field(left), Value::Field(right)) => Value::Field(left.pow(right)),
+
+        (Lt, Value::Field(left), Value::Field(right)) => Value::Boolean(*left.is_less_than(&right)),
+        (Lte, Value::Field(left), Value::Field(right)) => Value::Boolean(*left.is_less_than_or_equal(&right)),
+        (Gt, Value::Field(left), Value::Field(right)) => Value::Boolean(*left.is_greater_than(&right)),
+        (Gte, Value::Field(left), Value::Field(right)) => Value::Boolean(*left.is_greater_than_or_equal(&right)),
+        (Lt, Value::Scalar(left), Value::Scalar(right)) => Value::Boolean(*left.is_less_than(&right)),
+        (Lte, Value::Scalar(left), Value::Scalar(right)) => Value::Boolean(*left.is_less_than_or_equal(&right)),
+        (Gt, Value::Scalar(left), Value::Scalar(right)) => Value::Boolean(*left.is_greater_than(&right)),
+        (Gte, Value::Scalar(left), Value::Scalar(right)) => Value::Boolean(*left.is_greater_than_or_equal(&right)),
+
+        (op, Value::Integer(left), Value::Integer(right)) => {
+            let operation = || {
+                format!(
+                    "{} {op} {}",
+                    Value::Integer(left.clone()),
+                    Value::Integer(right.clone())
+                )
+            };
+            if matches!(op, Div | DivWrapped | Rem | RemWrapped | Mod)
+                && WideInteger::from_value(&right) == WideInteger::parse("0")
+            {
+                return Err(halted(format!("`{}` divides by zero", operation())));
+            }
+            // Integers are always stored with the default span, so that equal integers compare as equal.
+            match left.clone().evaluate_binary(op, right.clone(), Span::default()) {
+                Ok(Some(leo_ast::Value::Boolean(boolean, _))) => Value::Boolean(boolean),
+                Ok(Some(integer)) => Value::Integer(integer),
+                Ok(None) => unreachable!("Type checking guarantees that `{}` is well-typed.", operation()),
+                Err(_) => return Err(halted(format!("`{}` overflows", operation()))),
+            }
+        }
+
+        (op, left, right) => unreachable!("Type checking guarantees that `{left} {op} {right}` is well-typed."),
+    })
+}
+
+fn evaluate_cast(cast: &CastExpression, value: Value) -> Result<Value> {
+    match (value, &cast.type_) {
+        (Value::Integer(integer), Type::Integer(integer_type)) => integer
+            .cast_integer(*integer_type, Span::default())
+            .map(Value::Integer)
+            .ok_or_else(|| {
+                let reason = format!("`{}` does not fit in `{integer_type}`", Value::Integer(integer));
+                InterpreterError::halted(cast, reason, cast.span).into()
+            }),
+        _ => Err(InterpreterError::unsupported_expression("cast", cast, cast.span).into()),
+    }
+}
+
+fn evaluate_unary(unary: &UnaryExpression, receiver: Value) -> Result<Value> {
+    use UnaryOperation::*;
+
+    let halted = |reason: String| -> LeoError { InterpreterError::halted(unary, reason, unary.span).into() };
+
+    Ok(match (unary.op, receiver) {
+        (Not, Value::Boolean(boolean)) => Value::Boolean(!boolean),
+        (Negate, Value::Field(field)) => Value::Field(-field),
+        (Negate, Value::Group(group)) => Value::Group(-group),
+        (Double, Value::Field(field)) => Value::Field(field.double()),
+        (Double, Value::Group(group)) => Value::Group(group.double()),
+        (Inverse, Value::Field(field)) => Value::Field(
+            field
+                .inverse()
+                .map_err(|_| halted(format!("`{field}` has no inverse")))?,
+        ),
+        (Square, Value::Field(field)) => Value::Field(field.square()),
+        (SquareRoot, Value::Field(field)) => Value::Field(
+            field
+                .square_root()
+                .map_err(|_| halted(format!("`{field}` has no square root")))?,
+        ),
+        (op, Value::Integer(integer)) => match integer.clone().evaluate_unary(op, Span::default()) {
+            Ok(Some(result)) => Value::Integer(result),
+            Ok(None) => unreachable!(
+                "Type checking guarantees that `{}` is not applied to `{}`.",
+                op.as_str(),
+                Value::Integer(integer)
+            ),
+            Err(_) => return Err(halted(format!("`{}` overflows", Value::Integer(integer)))),
+        },
+        (op, receiver) => unreachable!(
+            "Type checking guarantees that `{receiver}.{}()` is well-typed.",
+            op.as_str()
+        ),
+    })
+}
@@ -0,0 +1,224 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{evaluate::evaluate_binary, Interpreter, Value};
+
+use leo_ast::{
+    AssertStatement, AssertVariant, BinaryOperation, Block, Expression, Identifier, IterationStatement, Statement,
+};
+use leo_errors::{InterpreterError, Result};
+use leo_span::Span;
+
+use snarkvm_console::{
+    prelude::Zero,
+    types::{Field, Group, Scalar},
+};
+
+impl<'a> Interpreter<'a> {
+    /// Executes the statements of a block in a scope of its own.
+    /// Returns the value of the first return statement that is reached, if any.
+    pub(crate) fn execute_block(&mut self, block: &Block) -> Result<Option<Value>> {
+        self.scopes.push(Default::default());
+        let result = self.execute_statements(&block.statements);
+        self.scopes.pop();
+        result
+    }
+
+    fn execute_statements(&mut self, statements: &[Statement]) -> Result<Option<Value>> {
+        for statement in statements {
+            if let Some(output) = self.execute_statement(statement)? {
+                return Ok(Some(output));
+            }
+        }
+        Ok(None)
+    }
+
+    fn execute_statement(&mut self, statement: &Statement) -> Result<Option<Value>> {
+        match statement {
+            Statement::Assert(assert) => self.execute_assert(assert)?,
+            Statement::Assign(assign) => {
+                let value = self.evaluate_expression(&assign.value)?;
+                match &assign.place {
+                    Expression::Identifier(identifier) => {
+                        *self
+                            .lookup_mut(identifier.name)
+                            .expect("Type checking guarantees that the variable is defined.") = value
+                    }
+                    _ => unreachable!("Type checking guarantees that only variables are assigned to."),
+                }
+            }
+            Statement::Block(block) => return self.execute_block(block),
+            Statement::Cfg(_) => unreachable!("`CfgStatement`s should not be in the AST at this phase of compilation."),
+            Statement::Conditional(conditional) => {
+                return match self.evaluate_expression(&conditional.condition)?.to_bool() {
+                    true => self.execute_block(&conditional.then),
+                    false => match &conditional.otherwise {
+                        Some(otherwise) => self.execute_statement(otherwise),
+                        None => Ok(None),
+                    },
+                };
+            }
+            Statement::Decrement(decrement) => self.update_mapping(
+                decrement.mapping,
+                &decrement.index,
+                &decrement.amount,
+                BinaryOperation::Sub,
+                statement,
+                decrement.span,
+            )?,
+            Statement::Definition(definition) => {
+                let value = self.evaluate_expression(&definition.value)?;
+                match (&definition.place, value) {
+                    (Expression::Identifier(identifier), value) => self.define(identifier.name, value),
+                    (Expression::Tuple(tuple), Value::Tuple(values)) => {
+                        for (element, value) in tuple.elements.iter().zip(values) {
+                            match element {
+                                Expression::Identifier(identifier) => self.define(identifier.name, value),
+                                _ => unreachable!("The parser guarantees that destructured variables are identifiers."),
+                            }
+                        }
+                    }
+                    _ => unreachable!("Type checking guarantees that only tuples are destructured."),
+                }
+            }
+            Statement::Expression(expression) => {
+                self.evaluate_expression(&expression.expression)?;
+            }
+            // The finalize block is evaluated once the transition has returned.
+            Statement::Finalize(finalize) => {
+                let arguments = finalize
+                    .arguments
+                    .iter()
+                    .map(|argument| self.evaluate_expression(argument))
+                    .collect::<Result<_>>()?;
+                self.finalize_arguments = Some(arguments);
+            }
+            Statement::Increment(increment) => self.update_mapping(
+                increment.mapping,
+                &increment.index,
+                &increment.amount,
+                BinaryOperation::Add,
+                statement,
+                increment.span,
+            )?,
+            Statement::Iteration(iteration) => return self.execute_iteration(iteration),
+            Statement::Match(_) => {
+                unreachable!("`MatchStatement`s should not be in the AST at this phase of compilation.")
+            }
+            Statement::Return(return_) => return Ok(Some(self.evaluate_expression(&return_.expression)?)),
+        }
+        Ok(None)
+    }
+
+    fn execute_assert(&mut self, assert: &AssertStatement) -> Result<()> {
+        let holds = match &assert.variant {
+            AssertVariant::Assert(condition) => self.evaluate_expression(condition)?.to_bool(),
+            AssertVariant::AssertEq(left, right) => {
+                self.evaluate_expression(left)? == self.evaluate_expression(right)?
+            }
+            AssertVariant::AssertNeq(left, right) => {
+                self.evaluate_expression(left)? != self.evaluate_expression(right)?
+            }
+        };
+        match holds {
+            true => Ok(()),
+            false => Err(InterpreterError::assertion_failed(assert, assert.span).into()),
+        }
+    }
+
+    /// Adds `amount` to, or subtracts it from, the entry of a mapping.
+    /// An entry that does not exist yet starts out at zero.
+    fn update_mapping(
+        &mut self,
+        mapping: Identifier,
+        index: &Expression,
+        amount: &Expression,
+        op: BinaryOperation,
+        statement: &Statement,
+        span: Span,
+    ) -> Result<()> {
+        let key = self.evaluate_expression(index)?.to_string();
+        let amount = self.evaluate_expression(amount)?;
+        let entries = self
+            .mappings
+            .get_mut(&mapping.name)
+            .expect("Type checking guarantees that the mapping exists.");
+        let current = entries.get(&key).cloned().unwrap_or_else(|| zero(&amount));
+        let updated = evaluate_binary(op, current, amount, statement, span)?;
+        entries.insert(key, updated);
+        Ok(())
+    }
+
+    fn execute_iteration(&mut self, iteration: &IterationStatement) -> Result<Option<Value>> {
+        let start = self.evaluate_expression(&iteration.start)?;
+
+        // A loop without a `stop` iterates over the elements of an array.
+        let stop = match &iteration.stop {
+            Some(stop) => self.evaluate_expression(stop)?,
+            None => {
+                let elements = match start {
+                    Value::Array(elements) => elements,
+                    _ => unreachable!("Type checking guarantees that loops without bounds iterate over arrays."),
+                };
+                for element in elements {
+                    if let Some(output) = self.execute_iteration_body(iteration, element)? {
+                        return Ok(Some(output));
+                    }
+                }
+                return Ok(None);
+            }
+        };
+
+        let op = match iteration.inclusive {
+            true => BinaryOperation::Lte,
+            false => BinaryOperation::Lt,
+        };
+        let one = Value::integer_like(&start, 1);
+        let mut current = start;
+        loop {
+            if !evaluate_binary(op, current.clone(), stop.clone(), iteration, iteration.span)?.to_bool() {
+                return Ok(None);
+            }
+            if let Some(output) = self.execute_iteration_body(iteration, current.clone())? {
+                return Ok(Some(output));
+            }
+            // The loop ends before the variable is incremented past the end of an inclusive range, which may not fit in its type.
+            if current == stop {
+                return Ok(None);
+            }
+            current = evaluate_binary(BinaryOperation::Add, current, one.clone(), iteration, iteration.span)?;
+        }
+    }
+
+    fn execute_iteration_body(&mut self, iteration: &IterationStatement, value: Value) -> Result<Option<Value>> {
+        self.scopes.push(Default::default());
+        self.define(iteration.variable.name, value);
+        let result = self.execute_block(&iteration.block);
+        self.scopes.pop();
+        result
+    }
+}
+
+/// Returns the zero of the type of `value`, which is an integer, field, group, or scalar.
+fn zero(value: &Value) -> Value {
+    match value {
+        Value::Field(_) => Value::Field(Field::zero()),
+        Value::Group(_) => Value::Group(Group::zero()),
+        Value::Integer(_) => Value::integer_like(value, 0),
+        Value::Scalar(_) => Value::Scalar(Scalar::zero()),
+        _ => unreachable!("Type checking guarantees that mappings are only incremented by numbers."),
+    }
+}
@@ -0,0 +1,326 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Value;
+
+use leo_ast::{Function, Program};
+use leo_errors::{InterpreterError, Result};
+use leo_span::Symbol;
+
+use indexmap::IndexMap;
+
+/// Evaluates the functions of a program on concrete inputs.
+///
+/// The program is expected to be type checked, with its enums lowered and its generic functions instantiated.
+/// Only the branch that a conditional takes is evaluated, so unlike the compiled program,
+/// operations that would halt in the other branch do not halt the interpreter.
+pub struct Interpreter<'a> {
+    /// The functions of the program.
+    pub(crate) functions: IndexMap<Symbol, &'a Function>,
+    /// The values of the constants of the program.
+    pub(crate) constants: IndexMap<Symbol, Value>,
+    /// The entries of the mappings of the program, keyed by the string representation of their keys.
+    /// Mappings start out empty, and are updated by finalize blocks.
+    pub mappings: IndexMap<Symbol, IndexMap<String, Value>>,
+    /// The scopes of the function that is being evaluated, from the outermost to the innermost.
+    pub(crate) scopes: Vec<IndexMap<Symbol, Value>>,
+    /// The arguments of the finalize statement of the transition that is being evaluated, once it has been reached.
+    pub(crate) finalize_arguments: Option<Vec<Value>>,
+}
+
+impl<'a> Interpreter<'a> {
+    /// Returns an interpreter for the given program, evaluating its constants.
+    pub fn new(program: &'a Program) -> Result<Self> {
+        let mut interpreter = Self {
+            functions: IndexMap::new(),
+            constants: IndexMap::new(),
+            mappings: IndexMap::new(),
+            scopes: vec![IndexMap::new()],
+            finalize_arguments: None,
+        };
+        for scope in program.program_scopes.values() {
+            for (name, function) in scope.functions.iter() {
+                interpreter.functions.insert(name.name, function);
+            }
+            for name in scope.mappings.keys() {
+                interpreter.mappings.insert(name.name, IndexMap::new());
+            }
+            // Constants are evaluated in the order that they are declared, so that they can refer to earlier constants.
+            for (name, constant) in scope.consts.iter() {
+                let value = interpreter.evaluate_expression(&constant.value)?;
+                interpreter.constants.insert(name.name, value);
+            }
+        }
+        Ok(interpreter)
+    }
+
+    /// Evaluates the function with the given name on the given inputs, returning its output.
+    /// If the function is a transition with a finalize block, the finalize block is evaluated afterwards.
+    pub fn call(&mut self, function: Symbol, inputs: Vec<Value>) -> Result<Value> {
+        let function = self.function_taking(function, inputs.len())?;
+        self.call_function(function, inputs)
+    }
+
+    /// Parses the inputs of the function with the given name by the types of its parameters, and evaluates the function on them.
+    pub fn call_with_strings(&mut self, function: Symbol, inputs: &[String]) -> Result<Value> {
+        let function = self.function_taking(function, inputs.len())?;
+        let inputs = function
+            .input
+            .iter()
+            .zip(inputs.iter())
+            .map(|(parameter, input)| Value::parse(input, &parameter.type_()))
+            .collect::<Result<Vec<_>>>()?;
+        self.call_function(function, inputs)
+    }
+
+    /// Returns the function with the given name, if it takes the given number of inputs.
+    fn function_taking(&self, name: Symbol, inputs: usize) -> Result<&'a Function> {
+        let function = *self
+            .functions
+            .get(&name)
+            .ok_or_else(|| InterpreterError::unknown_function(name))?;
+        match function.input.len() == inputs {
+            true => Ok(function),
+            false => {
+                Err(InterpreterError::wrong_number_of_inputs(function.identifier, function.input.len(), inputs).into())
+            }
+        }
+    }
+
+    /// Evaluates a function in a scope of its own, binding its parameters to the given inputs.
+    pub(crate) fn call_function(&mut self, function: &'a Function, inputs: Vec<Value>) -> Result<Value> {
+        let scope = function
+            .input
+            .iter()
+            .map(|parameter| parameter.identifier().name)
+            .zip(inputs)
+            .collect();
+        let caller_scopes = std::mem::replace(&mut self.scopes, vec![scope]);
+        let caller_finalize_arguments = self.finalize_arguments.take();
+
+        let result = self.evaluate_body(function);
+
+        self.scopes = caller_scopes;
+        self.finalize_arguments = caller_finalize_arguments;
+        result
+    }
+
+    fn evaluate_body(&mut self, function: &'a Function) -> Result<Value> {
+        let output = self
+            .execute_block(&function.block)?
+            .unwrap_or_else(|| Value::Tuple(Vec::new()));
+
+        if let (Some(finalize), Some(arguments)) = (&function.finalize, self.finalize_arguments.take()) {
+            self.scopes = vec![finalize
+                .input
+                .iter()
+                .map(|parameter| parameter.identifier().name)
+                .zip(arguments)
+                .collect()];
+            self.execute_block(&finalize.block)?;
+        }
+
+        Ok(output)
+    }
+
+    /// Returns the value of the variable or constant with the given name.
+    pub(crate) fn lookup(&self, name: Symbol) -> Option<&Value> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(&name))
+            .or_else(|| self.constants.get(&name))
+    }
+
+    /// Returns the value of the variable with the given name, so that it can be assigned to.
+    pub(crate) fn lookup_mut(&mut self, name: Symbol) -> Option<&mut Value> {
+        self.scopes.iter_mut().rev().find_map(|scope| scope.get_mut(&name))
+    }
+
+    /// Defines a variable in the innermost scope.
+    pub(crate) fn define(&mut self, name: Symbol, value: Value) {
+        self.scopes
+            .last_mut()
+            .expect("There is always a scope.")
+            .insert(name, value);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use leo_compiler::Compiler;
+    use leo_errors::emitter::Handler;
+    use leo_span::symbol::create_session_if_not_set_then;
+
+    use std::fs;
+
+    /// Runs the passes that the interpreter depends on over `program`, and hands an interpreter for it to `check`.
+    fn interpret(program: &str, check: impl FnOnce(&mut Interpreter)) {
+        create_session_if_not_set_then(|_| {
+            let directory = tempfile::tempdir().expect("Failed to open temporary directory");
+            let main_file_path = directory.path().join("main.leo");
+            fs::write(&main_file_path, program).expect("Failed to write the program");
+            let handler = Handler::default();
+            let mut compiler = Compiler::new(
+                "test".to_string(),
+                "aleo".to_string(),
+                &handler,
+                main_file_path,
+                directory.path().to_path_buf(),
+                None,
+                None,
+            );
+            compiler.interpreter_stages().expect("Failed to type check the program");
+            let mut interpreter = Interpreter::new(compiler.ast.as_repr()).expect("Failed to evaluate the constants");
+            check(&mut interpreter);
+        });
+    }
+
+    /// Calls `function` on `inputs`, returning its output or its error, rendered as strings.
+    fn call(interpreter: &mut Interpreter, function: &str, inputs: &[&str]) -> Result<String, String> {
+        let inputs: Vec<String> = inputs.iter().map(|input| input.to_string()).collect();
+        interpreter
+            .call_with_strings(Symbol::intern(function), &inputs)
+            .map(|output| output.to_string())
+            .map_err(|error| error.to_string())
+    }
+
+    /// Asserts that calling `function` on `inputs` halts with an error containing `reason`.
+    fn assert_halts(interpreter: &mut Interpreter, function: &str, inputs: &[&str], reason: &str) {
+        match call(interpreter, function, inputs) {
+            Ok(output) => panic!("`{function}` returned `{output}` instead of halting"),
+            Err(error) => assert!(error.contains(reason), "`{error}` does not contain `{reason}`"),
+        }
+    }
+
+    #[test]
+    fn test_integer_overflow() {
+        let program = "program test.aleo {
+            transition add(a: u8, b: u8) -> u8 {
+                return a + b;
+            }
+            transition add_wrapped(a: u8, b: u8) -> u8 {
+                return a.add_wrapped(b);
+            }
+            transition rem(a: i8, b: i8) -> i8 {
+                return a % b;
+            }
+            transition narrow(a: u16) -> u8 {
+                return a as u8;
+            }
+        }";
+        interpret(program, |interpreter| {
+            assert_eq!(call(interpreter, "add", &["254u8", "1u8"]), Ok("255u8".to_string()));
+            assert_halts(interpreter, "add", &["255u8", "1u8"], "`255u8 + 1u8` overflows");
+            assert_eq!(
+                call(interpreter, "add_wrapped", &["255u8", "1u8"]),
+                Ok("0u8".to_string())
+            );
+            assert_eq!(call(interpreter, "rem", &["-7i8", "2i8"]), Ok("-1i8".to_string()));
+            assert_halts(interpreter, "rem", &["-128i8", "-1i8"], "overflows");
+            assert_halts(interpreter, "rem", &["1i8", "0i8"], "divides by zero");
+            assert_eq!(call(interpreter, "narrow", &["255u16"]), Ok("255u8".to_string()));
+            assert_halts(interpreter, "narrow", &["256u16"], "does not fit in `u8`");
+        });
+    }
+
+    #[test]
+    fn test_field_and_group_arithmetic() {
+        let program = "program test.aleo {
+            transition field_ops(a: field, b: field) -> field {
+                return a * b + a / b - a;
+            }
+            transition field_wraps(a: field) -> bool {
+                return 0field - a == -a && a.inv() * a == 1field;
+            }
+            transition group_ops(a: group, s: scalar) -> bool {
+                return a + a == a.double() && s * a + a == (s + 1scalar) * a && a - a == 0group;
+            }
+        }";
+        interpret(program, |interpreter| {
+            assert_eq!(
+                call(interpreter, "field_ops", &["6field", "3field"]),
+                Ok("14field".to_string())
+            );
+            assert_halts(interpreter, "field_ops", &["6field", "0field"], "divided by zero");
+            assert_eq!(call(interpreter, "field_wraps", &["5field"]), Ok("true".to_string()));
+            assert_halts(interpreter, "field_wraps", &["0field"], "has no inverse");
+            let point = "1817767092074430972953743941103352519057913259183777531581123188265134806220group";
+            assert_eq!(
+                call(interpreter, "group_ops", &[point, "3scalar"]),
+                Ok("true".to_string())
+            );
+        });
+    }
+
+    #[test]
+    fn test_finalize_updates_mappings() {
+        let program = "program test.aleo {
+            mapping counts: u8 => u64;
+            transition add(public key: u8, public amount: u64) {
+                async finalize(key, amount);
+            }
+            finalize add(public key: u8, public amount: u64) {
+                increment(counts, key, amount);
+            }
+            transition remove(public key: u8, public amount: u64) {
+                async finalize(key, amount);
+            }
+            finalize remove(public key: u8, public amount: u64) {
+                decrement(counts, key, amount);
+            }
+        }";
+        interpret(program, |interpreter| {
+            let counts = Symbol::intern("counts");
+            let entry = |interpreter: &Interpreter, key: &str| {
+                interpreter.mappings[&counts].get(key).map(|value| value.to_string())
+            };
+            assert_eq!(entry(interpreter, "0u8"), None);
+
+            // The entries persist across calls, and start out at zero.
+            call(interpreter, "add", &["0u8", "2u64"]).unwrap();
+            call(interpreter, "add", &["0u8", "3u64"]).unwrap();
+            call(interpreter, "add", &["1u8", "1u64"]).unwrap();
+            call(interpreter, "remove", &["0u8", "4u64"]).unwrap();
+            assert_eq!(entry(interpreter, "0u8"), Some("1u64".to_string()));
+            assert_eq!(entry(interpreter, "1u8"), Some("1u64".to_string()));
+
+            // A finalize block that halts leaves the mapping unchanged.
+            assert_halts(interpreter, "remove", &["1u8", "2u64"], "overflows");
+            assert_eq!(entry(interpreter, "1u8"), Some("1u64".to_string()));
+        });
+    }
+
+    #[test]
+    fn test_assertion_failure() {
+        let program = "program test.aleo {
+            transition check(a: u8, b: u8) -> u8 {
+                assert(a < 10u8);
+                assert_eq(a, b);
+                assert_neq(a, 0u8);
+                return a;
+            }
+        }";
+        interpret(program, |interpreter| {
+            assert_eq!(call(interpreter, "check", &["1u8", "1u8"]), Ok("1u8".to_string()));
+            assert_halts(interpreter, "check", &["10u8", "10u8"], "failed");
+            assert_halts(interpreter, "check", &["1u8", "2u8"], "failed");
+            assert_halts(interpreter, "check", &["0u8", "0u8"], "failed");
+        });
+    }
+}
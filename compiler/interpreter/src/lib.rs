@@ -0,0 +1,34 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! An interpreter for Leo programs, which evaluates functions on concrete inputs without compiling them to Aleo instructions.
+//!
+//! The interpreter walks the AST once enums have been lowered and generic functions have been instantiated.
+//! Integers and booleans are computed by the evaluation core of `leo-ast` (see `Value::evaluate_binary`),
+//! which also powers the `ConstEvaluator` of `leo-passes`, so that constants are folded to the values they have at runtime.
+//! Fields, groups, and scalars are computed with the console types of snarkVM, which the core does not depend on.
+
+#![forbid(unsafe_code)]
+
+mod evaluate;
+
+mod execute;
+
+pub mod interpreter;
+pub use interpreter::*;
+
+pub mod value;
+pub use value::*;
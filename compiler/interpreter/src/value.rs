@@ -0,0 +1,166 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{GroupLiteral, IntegerType, Literal, Type, WideInteger};
+use leo_errors::{InterpreterError, Result};
+use leo_span::{Span, Symbol};
+
+use indexmap::IndexMap;
+use snarkvm_console::{
+    account::Address,
+    network::Testnet3,
+    types::{Field, Group, Scalar},
+};
+use std::{fmt, str::FromStr};
+
+/// A value computed by the interpreter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    Address(Address<Testnet3>),
+    Array(Vec<Value>),
+    Boolean(bool),
+    Field(Field<Testnet3>),
+    Group(Group<Testnet3>),
+    /// An integer, represented as a constant of the AST.
+    /// Its span is always the default span, so that equal integers compare as equal.
+    Integer(leo_ast::Value),
+    Scalar(Scalar<Testnet3>),
+    String(String),
+    /// A struct or record, along with the values of its members.
+    Struct(Symbol, IndexMap<Symbol, Value>),
+    /// A tuple, which is empty for the output of a function that does not return a value.
+    Tuple(Vec<Value>),
+}
+
+impl Value {
+    /// Returns the integer value of an integer constant of the AST.
+    pub fn integer(value: leo_ast::Value) -> Self {
+        let span = Span::default();
+        match (WideInteger::from_value(&value), Type::from(&value)) {
+            (Some(integer), Type::Integer(integer_type)) => Self::Integer(
+                integer
+                    .to_value(integer_type, span)
+                    .expect("The value fits in its own type."),
+            ),
+            _ => unreachable!("The constant `{value}` is not an integer."),
+        }
+    }
+
+    /// Returns the integer `n`, with the type of the integer `value`.
+    pub(crate) fn integer_like(value: &Value, n: u8) -> Self {
+        let span = Span::default();
+        match value {
+            Self::Integer(integer) => match Type::from(integer) {
+                Type::Integer(integer_type) => Self::Integer(
+                    WideInteger::from_value(&leo_ast::Value::U8(n, span))
+                        .and_then(|n| n.to_value(integer_type, span))
+                        .expect("Small integers fit in every integer type."),
+                ),
+                _ => unreachable!("The constant `{integer}` is an integer."),
+            },
+            _ => unreachable!("The value `{value}` is not an integer."),
+        }
+    }
+
+    /// Returns the value of a literal.
+    /// The literal is expected to be type checked, so that the types of unsuffixed integers are known.
+    pub fn from_literal(literal: &Literal) -> Result<Self> {
+        let invalid = |span| InterpreterError::halted(literal, "the literal is not a valid value of its type", span);
+        Ok(match literal {
+            Literal::Address(address, span) => Self::Address(Address::from_str(address).map_err(|_| invalid(*span))?),
+            Literal::Boolean(boolean, _) => Self::Boolean(*boolean),
+            Literal::Field(_, span) => Self::Field(Field::from_str(&literal.to_string()).map_err(|_| invalid(*span))?),
+            Literal::Group(group) => match group.as_ref() {
+                GroupLiteral::Single(_, span) => {
+                    Self::Group(Group::from_str(&literal.to_string()).map_err(|_| invalid(*span))?)
+                }
+                GroupLiteral::Tuple(tuple) => {
+                    return Err(
+                        InterpreterError::unsupported_expression("affine group literal", literal, tuple.span).into(),
+                    )
+                }
+            },
+            Literal::Scalar(_, span) => {
+                Self::Scalar(Scalar::from_str(&literal.to_string()).map_err(|_| invalid(*span))?)
+            }
+            Literal::String(string, _) => Self::String(string.clone()),
+            Literal::Integer(..) | Literal::Unsuffixed(..) => Self::integer(leo_ast::Value::from(literal)),
+        })
+    }
+
+    /// Parses an input of the given type, written as a literal, e.g. `1u8`.
+    /// Only inputs of the primitive types are supported.
+    pub fn parse(input: &str, type_: &Type) -> Result<Self> {
+        let invalid = || InterpreterError::invalid_input(input, type_);
+        Ok(match type_ {
+            Type::Address => Self::Address(Address::from_str(input).map_err(|_| invalid())?),
+            Type::Boolean => Self::Boolean(bool::from_str(input).map_err(|_| invalid())?),
+            Type::Field => Self::Field(Field::from_str(input).map_err(|_| invalid())?),
+            Type::Group => Self::Group(Group::from_str(input).map_err(|_| invalid())?),
+            Type::Integer(integer_type) => Self::parse_integer(input, *integer_type).ok_or_else(invalid)?,
+            Type::Scalar => Self::Scalar(Scalar::from_str(input).map_err(|_| invalid())?),
+            _ => return Err(invalid().into()),
+        })
+    }
+
+    fn parse_integer(input: &str, integer_type: IntegerType) -> Option<Self> {
+        let digits = input.strip_suffix(&integer_type.to_string())?;
+        let value = WideInteger::parse(digits)?.to_value(integer_type, Span::default())?;
+        Some(Self::Integer(value))
+    }
+
+    /// Returns the value of a boolean.
+    /// Type checking guarantees that conditions and assertions are booleans.
+    pub(crate) fn to_bool(&self) -> bool {
+        match self {
+            Self::Boolean(boolean) => *boolean,
+            _ => unreachable!("Type checking guarantees that `{self}` is a boolean."),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn write_list(f: &mut fmt::Formatter, values: &[Value]) -> fmt::Result {
+            let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+            write!(f, "{}", values.join(", "))
+        }
+
+        match self {
+            Self::Address(address) => write!(f, "{address}"),
+            Self::Array(elements) => {
+                write!(f, "[")?;
+                write_list(f, elements)?;
+                write!(f, "]")
+            }
+            Self::Boolean(boolean) => write!(f, "{boolean}"),
+            Self::Field(field) => write!(f, "{field}"),
+            Self::Group(group) => write!(f, "{group}"),
+            Self::Integer(integer) => write!(f, "{integer}{}", Type::from(integer)),
+            Self::Scalar(scalar) => write!(f, "{scalar}"),
+            Self::String(string) => write!(f, "\"{string}\""),
+            Self::Struct(name, members) => {
+                let members: Vec<String> = members.iter().map(|(name, value)| format!("{name}: {value}")).collect();
+                write!(f, "{name} {{ {} }}", members.join(", "))
+            }
+            Self::Tuple(elements) => {
+                write!(f, "(")?;
+                write_list(f, elements)?;
+                write!(f, ")")
+            }
+        }
+    }
+}
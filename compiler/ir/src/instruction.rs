@@ -0,0 +1,485 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{CastType, Operand, Register, ValueType};
+
+use std::fmt;
+
+/// A unary operation, e.g. `not`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UnaryOperation {
+    Abs,
+    AbsWrapped,
+    Double,
+    Inverse,
+    Negate,
+    Not,
+    Square,
+    SquareRoot,
+}
+
+impl fmt::Display for UnaryOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let opcode = match self {
+            Self::Abs => "abs",
+            Self::AbsWrapped => "abs.w",
+            Self::Double => "double",
+            Self::Inverse => "inv",
+            Self::Negate => "neg",
+            Self::Not => "not",
+            Self::Square => "square",
+            Self::SquareRoot => "sqrt",
+        };
+        write!(f, "{}", opcode)
+    }
+}
+
+/// A binary operation, e.g. `add`.
+/// Note that logical and bitwise operations share an opcode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BinaryOperation {
+    Add,
+    AddWrapped,
+    And,
+    Div,
+    DivWrapped,
+    Gt,
+    Gte,
+    IsEq,
+    IsNeq,
+    Lt,
+    Lte,
+    Mod,
+    Mul,
+    MulWrapped,
+    Nand,
+    Nor,
+    Or,
+    Pow,
+    PowWrapped,
+    Rem,
+    RemWrapped,
+    Shl,
+    ShlWrapped,
+    Shr,
+    ShrWrapped,
+    Sub,
+    SubWrapped,
+    Xor,
+}
+
+impl fmt::Display for BinaryOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let opcode = match self {
+            Self::Add => "add",
+            Self::AddWrapped => "add.w",
+            Self::And => "and",
+            Self::Div => "div",
+            Self::DivWrapped => "div.w",
+            Self::Gt => "gt",
+            Self::Gte => "gte",
+            Self::IsEq => "is.eq",
+            Self::IsNeq => "is.neq",
+            Self::Lt => "lt",
+            Self::Lte => "lte",
+            Self::Mod => "mod",
+            Self::Mul => "mul",
+            Self::MulWrapped => "mul.w",
+            Self::Nand => "nand",
+            Self::Nor => "nor",
+            Self::Or => "or",
+            Self::Pow => "pow",
+            Self::PowWrapped => "pow.w",
+            Self::Rem => "rem",
+            Self::RemWrapped => "rem.w",
+            Self::Shl => "shl",
+            Self::ShlWrapped => "shl.w",
+            Self::Shr => "shr",
+            Self::ShrWrapped => "shr.w",
+            Self::Sub => "sub",
+            Self::SubWrapped => "sub.w",
+            Self::Xor => "xor",
+        };
+        write!(f, "{}", opcode)
+    }
+}
+
+/// A three-address instruction.
+/// Each instruction reads its operands and writes at most its destination registers, which are assigned exactly once.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Instruction {
+    /// `assert.eq r0 r1;`
+    AssertEq(Operand, Operand),
+    /// `assert.neq r0 r1;`
+    AssertNeq(Operand, Operand),
+    /// `add r0 r1 into r2;`
+    Binary {
+        operation: BinaryOperation,
+        left: Operand,
+        right: Operand,
+        destination: Register,
+    },
+    /// `call foo r0 r1 into r2 r3;`, or `call bar.aleo/foo r0 into r1;` if the function is declared by another program.
+    Call {
+        program: Option<String>,
+        function: String,
+        arguments: Vec<Operand>,
+        destinations: Vec<Register>,
+    },
+    /// `cast r0 r1 into r2 as foo;`
+    Cast {
+        operands: Vec<Operand>,
+        destination: Register,
+        type_: CastType,
+    },
+    /// `hash.bhp256 r0 into r1;`
+    Core {
+        opcode: String,
+        arguments: Vec<Operand>,
+        destination: Register,
+    },
+    /// `decrement balances[r0] by r1;`
+    Decrement {
+        mapping: String,
+        key: Operand,
+        amount: Operand,
+    },
+    /// `finalize r0 r1;`
+    Finalize(Vec<Operand>),
+    /// `get balances[r0] into r1;`
+    Get {
+        mapping: String,
+        key: Operand,
+        destination: Register,
+    },
+    /// `get.or_use balances[r0] 0u64 into r1;`
+    GetOrUse {
+        mapping: String,
+        key: Operand,
+        default: Operand,
+        destination: Register,
+    },
+    /// `increment balances[r0] by r1;`
+    Increment {
+        mapping: String,
+        key: Operand,
+        amount: Operand,
+    },
+    /// `output r0 as u8.private;`
+    Output(Operand, ValueType),
+    /// `rand.chacha into r0 as u64;`
+    RandChaCha { destination: Register, type_: String },
+    /// `set r1 into balances[r0];`
+    Set {
+        mapping: String,
+        key: Operand,
+        value: Operand,
+    },
+    /// `ternary r0 r1 r2 into r3;`
+    Ternary {
+        condition: Operand,
+        if_true: Operand,
+        if_false: Operand,
+        destination: Register,
+    },
+    /// `not r0 into r1;`
+    Unary {
+        operation: UnaryOperation,
+        operand: Operand,
+        destination: Register,
+    },
+}
+
+impl Instruction {
+    /// Returns the operands that the instruction reads.
+    pub fn operands(&self) -> Vec<&Operand> {
+        match self {
+            Self::AssertEq(left, right) | Self::AssertNeq(left, right) | Self::Binary { left, right, .. } => {
+                vec![left, right]
+            }
+            Self::Call { arguments, .. } | Self::Core { arguments, .. } | Self::Finalize(arguments) => {
+                arguments.iter().collect()
+            }
+            Self::Cast { operands, .. } => operands.iter().collect(),
+            Self::Decrement { key, amount, .. } | Self::Increment { key, amount, .. } => vec![key, amount],
+            Self::Get { key, .. } => vec![key],
+            Self::GetOrUse { key, default, .. } => vec![key, default],
+            Self::Output(operand, _) | Self::Unary { operand, .. } => vec![operand],
+            Self::RandChaCha { .. } => Vec::new(),
+            Self::Set { key, value, .. } => vec![key, value],
+            Self::Ternary {
+                condition,
+                if_true,
+                if_false,
+                ..
+            } => vec![condition, if_true, if_false],
+        }
+    }
+
+    /// Returns mutable references to the operands that the instruction reads.
+    pub fn operands_mut(&mut self) -> Vec<&mut Operand> {
+        match self {
+            Self::AssertEq(left, right) | Self::AssertNeq(left, right) | Self::Binary { left, right, .. } => {
+                vec![left, right]
+            }
+            Self::Call { arguments, .. } | Self::Core { arguments, .. } | Self::Finalize(arguments) => {
+                arguments.iter_mut().collect()
+            }
+            Self::Cast { operands, .. } => operands.iter_mut().collect(),
+            Self::Decrement { key, amount, .. } | Self::Increment { key, amount, .. } => vec![key, amount],
+            Self::Get { key, .. } => vec![key],
+            Self::GetOrUse { key, default, .. } => vec![key, default],
+            Self::Output(operand, _) | Self::Unary { operand, .. } => vec![operand],
+            Self::RandChaCha { .. } => Vec::new(),
+            Self::Set { key, value, .. } => vec![key, value],
+            Self::Ternary {
+                condition,
+                if_true,
+                if_false,
+                ..
+            } => vec![condition, if_true, if_false],
+        }
+    }
+
+    /// Returns the registers that the instruction writes.
+    pub fn destinations(&self) -> Vec<Register> {
+        match self {
+            Self::Binary { destination, .. }
+            | Self::Cast { destination, .. }
+            | Self::Core { destination, .. }
+            | Self::Get { destination, .. }
+            | Self::GetOrUse { destination, .. }
+            | Self::RandChaCha { destination, .. }
+            | Self::Ternary { destination, .. }
+            | Self::Unary { destination, .. } => vec![*destination],
+            Self::Call { destinations, .. } => destinations.clone(),
+            Self::AssertEq(..)
+            | Self::AssertNeq(..)
+            | Self::Decrement { .. }
+            | Self::Finalize(_)
+            | Self::Increment { .. }
+            | Self::Output(..)
+            | Self::Set { .. } => Vec::new(),
+        }
+    }
+
+    /// Returns mutable references to the registers that the instruction writes.
+    pub fn destinations_mut(&mut self) -> Vec<&mut Register> {
+        match self {
+            Self::Binary { destination, .. }
+            | Self::Cast { destination, .. }
+            | Self::Core { destination, .. }
+            | Self::Get { destination, .. }
+            | Self::GetOrUse { destination, .. }
+            | Self::RandChaCha { destination, .. }
+            | Self::Ternary { destination, .. }
+            | Self::Unary { destination, .. } => vec![destination],
+            Self::Call { destinations, .. } => destinations.iter_mut().collect(),
+            Self::AssertEq(..)
+            | Self::AssertNeq(..)
+            | Self::Decrement { .. }
+            | Self::Finalize(_)
+            | Self::Increment { .. }
+            | Self::Output(..)
+            | Self::Set { .. } => Vec::new(),
+        }
+    }
+
+    /// Returns the opcode of the instruction, e.g. `add.w` or `hash.bhp256`.
+    pub fn opcode(&self) -> String {
+        match self {
+            Self::AssertEq(..) => "assert.eq".to_string(),
+            Self::AssertNeq(..) => "assert.neq".to_string(),
+            Self::Binary { operation, .. } => operation.to_string(),
+            Self::Call { .. } => "call".to_string(),
+            Self::Cast { .. } => "cast".to_string(),
+            Self::Core { opcode, .. } => opcode.clone(),
+            Self::Decrement { .. } => "decrement".to_string(),
+            Self::Finalize(_) => "finalize".to_string(),
+            Self::Get { .. } => "get".to_string(),
+            Self::GetOrUse { .. } => "get.or_use".to_string(),
+            Self::Increment { .. } => "increment".to_string(),
+            Self::Output(..) => "output".to_string(),
+            Self::RandChaCha { .. } => "rand.chacha".to_string(),
+            Self::Set { .. } => "set".to_string(),
+            Self::Ternary { .. } => "ternary".to_string(),
+            Self::Unary { operation, .. } => operation.to_string(),
+        }
+    }
+
+    /// Returns true if the instruction only computes its destinations and cannot halt execution,
+    /// in which case it may be removed when its destinations are unused.
+    /// Operations that may overflow or divide by zero, Pedersen hashes and commitments, which fail on inputs that are too large,
+    /// calls, and accesses to mappings are not removable.
+    pub fn is_removable(&self) -> bool {
+        match self {
+            Self::Binary { operation, .. } => matches!(
+                operation,
+                BinaryOperation::AddWrapped
+                    | BinaryOperation::And
+                    | BinaryOperation::Gt
+                    | BinaryOperation::Gte
+                    | BinaryOperation::IsEq
+                    | BinaryOperation::IsNeq
+                    | BinaryOperation::Lt
+                    | BinaryOperation::Lte
+                    | BinaryOperation::MulWrapped
+                    | BinaryOperation::Nand
+                    | BinaryOperation::Nor
+                    | BinaryOperation::Or
+                    | BinaryOperation::PowWrapped
+                    | BinaryOperation::ShlWrapped
+                    | BinaryOperation::ShrWrapped
+                    | BinaryOperation::SubWrapped
+                    | BinaryOperation::Xor
+            ),
+            Self::Unary { operation, .. } => matches!(
+                operation,
+                UnaryOperation::AbsWrapped | UnaryOperation::Double | UnaryOperation::Not | UnaryOperation::Square
+            ),
+            Self::Core { opcode, .. } => ["hash.bhp", "hash.psd", "commit.bhp"]
+                .iter()
+                .any(|prefix| opcode.starts_with(prefix)),
+            Self::Cast { .. } | Self::Ternary { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the approximate cost of proving the instruction, in the units of the cost model used to flatten conditionals.
+    /// Calls are free, since the cost of a closure is counted at its own instructions, and the functions of other programs are proven separately.
+    /// Accesses to mappings and random values are free, since they are executed on chain rather than proven.
+    pub fn cost(&self) -> usize {
+        match self {
+            Self::Binary { operation, .. } => match operation {
+                BinaryOperation::Pow | BinaryOperation::PowWrapped => 8,
+                BinaryOperation::Mul
+                | BinaryOperation::MulWrapped
+                | BinaryOperation::Div
+                | BinaryOperation::DivWrapped
+                | BinaryOperation::Rem
+                | BinaryOperation::RemWrapped
+                | BinaryOperation::Mod => 4,
+                _ => 1,
+            },
+            Self::Unary { operation, .. } => match operation {
+                UnaryOperation::Inverse | UnaryOperation::Square | UnaryOperation::SquareRoot => 4,
+                _ => 1,
+            },
+            Self::Core { .. } => 16,
+            Self::AssertEq(..) | Self::AssertNeq(..) | Self::Cast { .. } | Self::Ternary { .. } => 1,
+            Self::Call { .. }
+            | Self::Decrement { .. }
+            | Self::Finalize(_)
+            | Self::Get { .. }
+            | Self::GetOrUse { .. }
+            | Self::Increment { .. }
+            | Self::Output(..)
+            | Self::RandChaCha { .. }
+            | Self::Set { .. } => 0,
+        }
+    }
+}
+
+/// Writes each item followed by a space.
+fn write_each<T: fmt::Display>(f: &mut fmt::Formatter, items: &[T]) -> fmt::Result {
+    items.iter().try_for_each(|item| write!(f, "{} ", item))
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::AssertEq(left, right) => write!(f, "assert.eq {} {};", left, right),
+            Self::AssertNeq(left, right) => write!(f, "assert.neq {} {};", left, right),
+            Self::Binary {
+                operation,
+                left,
+                right,
+                destination,
+            } => write!(f, "{} {} {} into {};", operation, left, right, destination),
+            Self::Call {
+                program,
+                function,
+                arguments,
+                destinations,
+            } => {
+                match program {
+                    Some(program) => write!(f, "call {}.aleo/{} ", program, function)?,
+                    None => write!(f, "call {} ", function)?,
+                }
+                write_each(f, arguments)?;
+                write!(f, "into")?;
+                destinations
+                    .iter()
+                    .try_for_each(|register| write!(f, " {}", register))?;
+                write!(f, ";")
+            }
+            Self::Cast {
+                operands,
+                destination,
+                type_,
+            } => {
+                write!(f, "cast ")?;
+                write_each(f, operands)?;
+                write!(f, "into {} as {};", destination, type_)
+            }
+            Self::Core {
+                opcode,
+                arguments,
+                destination,
+            } => {
+                write!(f, "{} ", opcode)?;
+                write_each(f, arguments)?;
+                write!(f, "into {};", destination)
+            }
+            Self::Decrement { mapping, key, amount } => write!(f, "decrement {}[{}] by {};", mapping, key, amount),
+            Self::Finalize(arguments) => {
+                write!(f, "finalize")?;
+                arguments.iter().try_for_each(|argument| write!(f, " {}", argument))?;
+                write!(f, ";")
+            }
+            Self::Get {
+                mapping,
+                key,
+                destination,
+            } => write!(f, "get {}[{}] into {};", mapping, key, destination),
+            Self::GetOrUse {
+                mapping,
+                key,
+                default,
+                destination,
+            } => write!(f, "get.or_use {}[{}] {} into {};", mapping, key, default, destination),
+            Self::Increment { mapping, key, amount } => write!(f, "increment {}[{}] by {};", mapping, key, amount),
+            Self::Output(operand, type_) => write!(f, "output {} as {};", operand, type_),
+            Self::RandChaCha { destination, type_ } => write!(f, "rand.chacha into {} as {};", destination, type_),
+            Self::Set { mapping, key, value } => write!(f, "set {} into {}[{}];", value, mapping, key),
+            Self::Ternary {
+                condition,
+                if_true,
+                if_false,
+                destination,
+            } => write!(
+                f,
+                "ternary {} {} {} into {};",
+                condition, if_true, if_false, destination
+            ),
+            Self::Unary {
+                operation,
+                operand,
+                destination,
+            } => write!(f, "{} {} into {};", operation, operand, destination),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The intermediate representation between the Leo AST and Aleo instructions.
+//!
+//! A program is lowered into three-address instructions over registers once it has been flattened,
+//! so that the backend does not depend on the shape of the AST.
+//! The `Display` implementation of each item prints it as Aleo instructions.
+
+#![forbid(unsafe_code)]
+
+pub mod instruction;
+pub use instruction::*;
+
+pub mod operand;
+pub use operand::*;
+
+pub mod program;
+pub use program::*;
+
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+pub mod types;
+pub use types::*;
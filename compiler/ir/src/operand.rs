@@ -0,0 +1,72 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt;
+
+/// A register, e.g. `r0`.
+/// Registers are numbered from zero in each function and finalize block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Register(pub u64);
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "r{}", self.0)
+    }
+}
+
+/// An operand of an instruction.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Operand {
+    /// A register, e.g. `r0`.
+    Register(Register),
+    /// A literal, e.g. `1u8` or `true`.
+    Literal(String),
+    /// A named operand, e.g. `self`.
+    Name(String),
+    /// A member of a struct, record or named operand, e.g. `r0.owner` or `self.caller`.
+    Member(Box<Operand>, String),
+}
+
+impl Operand {
+    /// Returns the register that the operand reads, if any.
+    pub fn register(&self) -> Option<Register> {
+        match self {
+            Self::Register(register) => Some(*register),
+            Self::Member(inner, _) => inner.register(),
+            Self::Literal(_) | Self::Name(_) => None,
+        }
+    }
+
+    /// Returns a mutable reference to the register that the operand reads, if any.
+    pub fn register_mut(&mut self) -> Option<&mut Register> {
+        match self {
+            Self::Register(register) => Some(register),
+            Self::Member(inner, _) => inner.register_mut(),
+            Self::Literal(_) | Self::Name(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Register(register) => write!(f, "{}", register),
+            Self::Literal(literal) => write!(f, "{}", literal),
+            Self::Name(name) => write!(f, "{}", name),
+            Self::Member(inner, member) => write!(f, "{}.{}", inner, member),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Instruction, Register, ValueType};
+
+use std::fmt;
+
+/// A program, whose items are printed in the order that Aleo instructions require.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Program {
+    /// The name of the program, e.g. `token`.
+    pub name: String,
+    /// The network that the program and its imports are deployed to, e.g. `aleo`.
+    pub network: String,
+    /// The names of the imported programs.
+    pub imports: Vec<String>,
+    /// The structs and records, each declared after the structs that it has members of.
+    pub composites: Vec<Composite>,
+    /// The mappings.
+    pub mappings: Vec<Mapping>,
+    /// The closures and functions. Note that closures are printed before functions.
+    pub functions: Vec<Function>,
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.imports.is_empty() {
+            for (i, import) in self.imports.iter().enumerate() {
+                if i > 0 {
+                    writeln!(f)?;
+                }
+                write!(f, "import {}.{};", import, self.network)?;
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(f, "program {}.{};", self.name, self.network)?;
+        writeln!(f)?;
+
+        for (i, composite) in self.composites.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", composite)?;
+        }
+        writeln!(f)?;
+
+        for (i, mapping) in self.mappings.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", mapping)?;
+        }
+
+        // Closures must precede functions in the Aleo program.
+        let (closures, functions): (Vec<_>, Vec<_>) = self
+            .functions
+            .iter()
+            .partition(|function| function.kind == FunctionKind::Closure);
+        closures.iter().try_for_each(|closure| writeln!(f, "{}", closure))?;
+        writeln!(f)?;
+        functions.iter().try_for_each(|function| writeln!(f, "{}", function))
+    }
+}
+
+/// A struct or record declaration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Composite {
+    pub name: String,
+    /// Whether the composite is a record, rather than a struct.
+    pub is_record: bool,
+    /// The names and types of the members, in declaration order.
+    pub members: Vec<(String, ValueType)>,
+}
+
+impl fmt::Display for Composite {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.is_record {
+            true => writeln!(f, "record {}:", self.name)?,
+            false => writeln!(f, "interface {}:", self.name)?,
+        }
+        self.members
+            .iter()
+            .try_for_each(|(name, type_)| writeln!(f, "    {} as {};", name, type_))
+    }
+}
+
+/// A mapping declaration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mapping {
+    pub name: String,
+    pub key: ValueType,
+    pub value: ValueType,
+}
+
+impl fmt::Display for Mapping {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "mapping {}:", self.name)?;
+        writeln!(f, "\tkey left as {};", self.key)?;
+        writeln!(f, "\tvalue right as {};", self.value)
+    }
+}
+
+/// Whether a function is an Aleo `closure` or `function`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FunctionKind {
+    Closure,
+    Function,
+}
+
+impl fmt::Display for FunctionKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Closure => write!(f, "closure"),
+            Self::Function => write!(f, "function"),
+        }
+    }
+}
+
+/// An input declaration, e.g. `input r0 as u8.private;`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Input {
+    pub register: Register,
+    pub type_: ValueType,
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "input {} as {};", self.register, self.type_)
+    }
+}
+
+/// Writes each input and instruction on its own indented line.
+fn write_body(f: &mut fmt::Formatter, inputs: &[Input], instructions: &[Instruction]) -> fmt::Result {
+    inputs.iter().try_for_each(|input| writeln!(f, "    {}", input))?;
+    instructions
+        .iter()
+        .try_for_each(|instruction| writeln!(f, "    {}", instruction))
+}
+
+/// A range of the Leo program, given by 1-based lines and columns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub line_start: usize,
+    pub col_start: usize,
+    pub line_stop: usize,
+    pub col_stop: usize,
+}
+
+/// A closure or function, with its finalize block, if any.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Function {
+    pub kind: FunctionKind,
+    pub name: String,
+    pub inputs: Vec<Input>,
+    pub instructions: Vec<Instruction>,
+    /// The location in the Leo program that each instruction was generated from, if known.
+    pub locations: Vec<Option<SourceLocation>>,
+    pub finalize: Option<Finalize>,
+}
+
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} {}:", self.kind, self.name)?;
+        write_body(f, &self.inputs, &self.instructions)?;
+        match &self.finalize {
+            Some(finalize) => write!(f, "\n{}", finalize),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A finalize block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finalize {
+    pub name: String,
+    pub inputs: Vec<Input>,
+    pub instructions: Vec<Instruction>,
+    /// The location in the Leo program that each instruction was generated from, if known.
+    pub locations: Vec<Option<SourceLocation>>,
+}
+
+impl fmt::Display for Finalize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "finalize {}:", self.name)?;
+        write_body(f, &self.inputs, &self.instructions)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{BinaryOperation, Operand, Visibility};
+
+    #[test]
+    fn test_print_program() {
+        let input = |register, type_: &str, visibility| Input {
+            register: Register(register),
+            type_: ValueType::Plaintext(type_.to_string(), Some(visibility)),
+        };
+        let program = Program {
+            name: "test".to_string(),
+            network: "aleo".to_string(),
+            imports: vec!["token".to_string()],
+            composites: vec![Composite {
+                name: "point".to_string(),
+                is_record: false,
+                members: vec![("x".to_string(), ValueType::Plaintext("u8".to_string(), None))],
+            }],
+            mappings: vec![Mapping {
+                name: "counts".to_string(),
+                key: ValueType::Plaintext("address".to_string(), Some(Visibility::Public)),
+                value: ValueType::Plaintext("u8".to_string(), Some(Visibility::Public)),
+            }],
+            functions: vec![Function {
+                kind: FunctionKind::Function,
+                name: "main".to_string(),
+                inputs: vec![input(0, "u8", Visibility::Private)],
+                instructions: vec![
+                    Instruction::Binary {
+                        operation: BinaryOperation::AddWrapped,
+                        left: Operand::Register(Register(0)),
+                        right: Operand::Literal("1u8".to_string()),
+                        destination: Register(1),
+                    },
+                    Instruction::Finalize(vec![
+                        Operand::Member(Box::new(Operand::Name("self".to_string())), "caller".to_string()),
+                        Operand::Register(Register(1)),
+                    ]),
+                ],
+                locations: vec![None, None],
+                finalize: Some(Finalize {
+                    name: "main".to_string(),
+                    inputs: vec![
+                        input(0, "address", Visibility::Public),
+                        input(1, "u8", Visibility::Public),
+                    ],
+                    instructions: vec![Instruction::Set {
+                        mapping: "counts".to_string(),
+                        key: Operand::Register(Register(0)),
+                        value: Operand::Register(Register(1)),
+                    }],
+                    locations: vec![None],
+                }),
+            }],
+        };
+
+        let expected = "\
+import token.aleo;
+program test.aleo;
+
+interface point:
+    x as u8;
+
+mapping counts:
+\tkey left as address.public;
+\tvalue right as u8.public;
+
+function main:
+    input r0 as u8.private;
+    add.w r0 1u8 into r1;
+    finalize self.caller r1;
+
+finalize main:
+    input r0 as address.public;
+    input r1 as u8.public;
+    set r1 into counts[r0];
+
+";
+        assert_eq!(program.to_string(), expected);
+    }
+}
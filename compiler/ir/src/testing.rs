@@ -0,0 +1,69 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Constructors of small programs, shared by the tests of the crates that consume the IR.
+//! The module is only compiled for tests, or when the `testing` feature is enabled.
+
+use crate::{
+    BinaryOperation, Function, FunctionKind, Input, Instruction, Operand, Program, Register, SourceLocation, ValueType,
+    Visibility,
+};
+
+/// Returns a program `test.aleo` with the given functions.
+pub fn program(functions: Vec<Function>) -> Program {
+    Program {
+        name: "test".to_string(),
+        network: "aleo".to_string(),
+        functions,
+        ..Default::default()
+    }
+}
+
+/// Returns a function with a private `u8` input in `r0`, whose instructions were generated from the given lines.
+/// An instruction whose line is `None` has no known location, as do the instructions beyond the end of `lines`.
+pub fn function(kind: FunctionKind, name: &str, instructions: Vec<Instruction>, lines: Vec<Option<usize>>) -> Function {
+    Function {
+        kind,
+        name: name.to_string(),
+        inputs: vec![Input {
+            register: Register(0),
+            type_: ValueType::Plaintext("u8".to_string(), Some(Visibility::Private)),
+        }],
+        instructions,
+        locations: lines.into_iter().map(|line| line.map(location)).collect(),
+        finalize: None,
+    }
+}
+
+/// Returns the location of a statement on `line`, indented by four spaces.
+pub fn location(line: usize) -> SourceLocation {
+    SourceLocation {
+        line_start: line,
+        col_start: 5,
+        line_stop: line,
+        col_stop: 23,
+    }
+}
+
+/// Returns an instruction that squares `r0` into the `destination` register by multiplication.
+pub fn mul(destination: u64) -> Instruction {
+    Instruction::Binary {
+        operation: BinaryOperation::Mul,
+        left: Operand::Register(Register(0)),
+        right: Operand::Register(Register(0)),
+        destination: Register(destination),
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt;
+
+/// The visibility of an input, output or record member.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Visibility {
+    Constant,
+    Private,
+    Public,
+}
+
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Constant => write!(f, "constant"),
+            Self::Private => write!(f, "private"),
+            Self::Public => write!(f, "public"),
+        }
+    }
+}
+
+/// The type of an input, output, mapping entry or struct member.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ValueType {
+    /// A literal or struct type, with a visibility if it is declared by a function or finalize block, e.g. `u8.public`.
+    Plaintext(String, Option<Visibility>),
+    /// A record declared by the program, e.g. `token.record`.
+    Record(String),
+    /// A record declared by another program, e.g. `token.aleo/token.record`.
+    ExternalRecord { program: String, record: String },
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Plaintext(type_, None) => write!(f, "{}", type_),
+            Self::Plaintext(type_, Some(visibility)) => write!(f, "{}.{}", type_, visibility),
+            Self::Record(record) => write!(f, "{}.record", record),
+            Self::ExternalRecord { program, record } => write!(f, "{}.aleo/{}.record", program, record),
+        }
+    }
+}
+
+/// The type produced by a `cast` instruction.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CastType {
+    /// A literal type, e.g. `u64`.
+    Literal(String),
+    Struct(String),
+    Record(String),
+}
+
+impl fmt::Display for CastType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Literal(literal) => write!(f, "{}", literal),
+            Self::Struct(struct_) => write!(f, "{}", struct_),
+            Self::Record(record) => write!(f, "{}.record", record),
+        }
+    }
+}
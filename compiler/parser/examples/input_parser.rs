@@ -53,7 +53,12 @@ fn main() -> Result<(), String> {
             .expect("failed to open an input file");
 
         Handler::with(|handler| {
-            let input = leo_parser::parse_program_inputs(handler, &input_string.src, input_string.start_pos)?;
+            let input = leo_parser::parse_program_inputs(
+                handler,
+                &Default::default(),
+                &input_string.src,
+                input_string.start_pos,
+            )?;
             input.to_json_string()
         })
         .map_err(|e| e.to_string())
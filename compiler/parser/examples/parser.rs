@@ -18,6 +18,7 @@
 
 use leo_ast::Ast;
 use leo_errors::emitter::Handler;
+use leo_parser::Edition;
 use leo_span::symbol::create_session_if_not_set_then;
 
 use clap::StructOpt;
@@ -49,7 +50,7 @@ fn main() -> Result<(), String> {
         let code = s.source_map.load_file(&opt.input_path).expect("failed to open file");
 
         Handler::with(|h| {
-            let ast = leo_parser::parse_ast(h, &code.src, code.start_pos)?;
+            let ast = leo_parser::parse_ast(h, &Default::default(), &code.src, code.start_pos, Edition::default())?;
             let json = Ast::to_json_string(&ast)?;
             println!("{}", json);
             Ok(json)
@@ -31,7 +31,7 @@ pub(crate) use tokenizer::*;
 pub mod parser;
 pub use parser::*;
 
-use leo_ast::{input::InputData, Ast, ProgramInput};
+use leo_ast::{input::InputData, Ast, NodeBuilder, ProgramInput};
 use leo_errors::emitter::Handler;
 use leo_errors::Result;
 
@@ -39,13 +39,32 @@ use leo_errors::Result;
 mod test;
 
 /// Creates a new AST from a given file path and source code text.
-pub fn parse_ast(handler: &Handler, source: &str, start_pos: BytePos) -> Result<Ast> {
-    Ok(Ast::new(parser::parse(handler, source, start_pos)?))
+/// The program is parsed as written for `edition`, unless it declares an edition itself.
+pub fn parse_ast(
+    handler: &Handler,
+    node_builder: &NodeBuilder,
+    source: &str,
+    start_pos: BytePos,
+    edition: Edition,
+) -> Result<Ast> {
+    Ok(Ast::new(parser::parse(
+        handler,
+        node_builder,
+        source,
+        start_pos,
+        edition,
+    )?))
 }
 
 /// Parses program inputs from from the input file path and state file path
-pub fn parse_program_inputs(handler: &Handler, input_string: &str, start_pos: BytePos) -> Result<InputData> {
-    let program_input: ProgramInput = parser::parse_input(handler, input_string, start_pos)?.try_into()?;
+pub fn parse_program_inputs(
+    handler: &Handler,
+    node_builder: &NodeBuilder,
+    input_string: &str,
+    start_pos: BytePos,
+) -> Result<InputData> {
+    let program_input: ProgramInput =
+        parser::parse_input(handler, node_builder, input_string, start_pos)?.try_into()?;
 
     Ok(InputData { program_input })
 }
@@ -14,12 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{tokenizer::*, Token};
+use crate::{tokenizer::*, Edition, Feature, Token};
 
 use leo_ast::*;
 use leo_errors::emitter::Handler;
 use leo_errors::{ParserError, ParserWarning, Result};
-use leo_span::{Span, Symbol};
+use leo_span::{sym, Span, Symbol};
 
 use std::fmt::Display;
 use std::mem;
@@ -29,6 +29,8 @@ use std::mem;
 pub(crate) struct ParserContext<'a> {
     /// Handler used to side-channel emit errors from the parser.
     pub(crate) handler: &'a Handler,
+    /// Counter used to generate unique node ids.
+    pub(crate) node_builder: &'a NodeBuilder,
     /// All un-bumped tokens.
     tokens: Vec<SpannedToken>,
     /// The current token, i.e., if `p.tokens = ['3', *, '4']`,
@@ -41,6 +43,12 @@ pub(crate) struct ParserContext<'a> {
     pub(crate) disallow_struct_construction: bool,
     /// true if parsing an identifier inside an input file.
     pub(crate) allow_identifier_underscores: bool,
+    /// The edition of Leo that the program is written for, which determines the syntax that may be used.
+    pub(crate) edition: Edition,
+    /// true if parsing a program written in legacy syntax in order to migrate it.
+    pub(crate) legacy: bool,
+    /// The rewrites of legacy constructs to current syntax, as the span to replace and its replacement.
+    pub(crate) rewrites: Vec<(Span, String)>,
 }
 
 /// Dummy span used to appease borrow checker.
@@ -51,7 +59,7 @@ const DUMMY_EOF: SpannedToken = SpannedToken {
 
 impl<'a> ParserContext<'a> {
     /// Returns a new [`ParserContext`] type given a vector of tokens.
-    pub fn new(handler: &'a Handler, mut tokens: Vec<SpannedToken>) -> Self {
+    pub fn new(handler: &'a Handler, node_builder: &'a NodeBuilder, mut tokens: Vec<SpannedToken>) -> Self {
         // Strip out comments.
         tokens.retain(|x| !matches!(x.token, Token::CommentLine(_) | Token::CommentBlock(_)));
         // For performance we reverse so that we get cheap `.pop()`s.
@@ -60,8 +68,12 @@ impl<'a> ParserContext<'a> {
         let token = SpannedToken::dummy();
         let mut p = Self {
             handler,
+            node_builder,
             disallow_struct_construction: false,
             allow_identifier_underscores: false,
+            edition: Edition::default(),
+            legacy: false,
+            rewrites: Vec::new(),
             prev_token: token.clone(),
             token,
             tokens,
@@ -126,6 +138,18 @@ impl<'a> ParserContext<'a> {
         self.handler.emit_err(err);
     }
 
+    /// Emits an error if `feature` was introduced in a later edition than the one the program is written for.
+    pub(super) fn check_edition(&self, feature: Feature, span: Span) {
+        if self.edition < feature.edition() {
+            self.emit_err(ParserError::feature_requires_edition(
+                feature,
+                feature.edition(),
+                self.edition,
+                span,
+            ));
+        }
+    }
+
     /// Emit the error `err`.
     pub(super) fn emit_warning(&self, warning: ParserWarning) {
         self.handler.emit_warning(warning.into());
@@ -139,7 +163,11 @@ impl<'a> ParserContext<'a> {
     /// At the previous token, return and make an identifier with `name`.
     fn mk_ident_prev(&self, name: Symbol) -> Identifier {
         let span = self.prev_token.span;
-        Identifier { name, span }
+        Identifier {
+            name,
+            span,
+            id: self.node_builder.next_id(),
+        }
     }
 
     /// Eats the next token if its an identifier and returns it.
@@ -248,4 +276,25 @@ impl<'a> ParserContext<'a> {
     pub(super) fn peek_is_left_par(&self) -> bool {
         matches!(self.token.token, Token::LeftParen)
     }
+
+    /// Returns `true` if the current token is the network of a program, `leo` or `aleo`, as in `foo.aleo`.
+    fn peek_is_network(&self) -> bool {
+        match &self.token.token {
+            Token::Leo => true,
+            Token::Identifier(name) => *name == sym::aleo,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the current token is the network of a program, `leo` or `aleo`.
+    /// Advances the parser past the network.
+    pub(super) fn eat_network(&mut self) -> bool {
+        self.peek_is_network().then(|| self.bump()).is_some()
+    }
+
+    /// Returns `true` if the next tokens are the network of an external program followed by a `/`, as in `foo.aleo/bar`.
+    /// Note that `aleo` is otherwise an identifier, e.g. the name of a struct member.
+    pub(super) fn peek_is_external_network(&self) -> bool {
+        self.peek_is_network() && self.look_ahead(1, |t| &t.token) == &Token::Div
+    }
 }
@@ -0,0 +1,89 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use serde::Serialize;
+use std::{fmt, str::FromStr};
+
+/// An edition of the Leo language.
+/// Each edition may introduce syntax, which programs written for an earlier edition cannot use.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum Edition {
+    /// The first edition, with structs, records, and transitions.
+    V2022,
+    /// Introduces tuples and mappings.
+    #[default]
+    V2023,
+}
+
+impl Edition {
+    /// All editions, from oldest to newest.
+    pub const ALL: [Edition; 2] = [Edition::V2022, Edition::V2023];
+}
+
+impl fmt::Display for Edition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::V2022 => write!(f, "2022"),
+            Self::V2023 => write!(f, "2023"),
+        }
+    }
+}
+
+impl FromStr for Edition {
+    type Err = String;
+
+    /// Parses an edition from its year, e.g. `2022`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|edition| edition.to_string() == s)
+            .ok_or_else(|| format!("`{}` is not an edition of Leo", s))
+    }
+}
+
+/// Syntax that is only available from the edition that introduced it onwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Feature {
+    /// Tuple types, expressions, accesses, and destructuring definitions.
+    Tuples,
+    /// Mapping declarations and the `increment` and `decrement` statements.
+    Mappings,
+    /// Functions with type parameters, e.g. `function max<T: integer>(a: T, b: T) -> T`,
+    /// and structs with const parameters, e.g. `struct Buffer<const N: u32> { data: [u8; N] }`.
+    Generics,
+    /// Enum declarations and the `match` statement.
+    Enums,
+}
+
+impl Feature {
+    /// Returns the edition that introduced the feature.
+    pub fn edition(self) -> Edition {
+        match self {
+            Self::Tuples | Self::Mappings | Self::Generics | Self::Enums => Edition::V2023,
+        }
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Tuples => write!(f, "Tuples"),
+            Self::Mappings => write!(f, "Mappings"),
+            Self::Generics => write!(f, "Generics"),
+            Self::Enums => write!(f, "Enums"),
+        }
+    }
+}
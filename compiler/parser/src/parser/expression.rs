@@ -73,18 +73,20 @@ impl ParserContext<'_> {
                 condition: Box::new(expr),
                 if_true: Box::new(if_true),
                 if_false: Box::new(if_false),
+                id: self.node_builder.next_id(),
             });
         }
         Ok(expr)
     }
 
     /// Constructs a binary expression `left op right`.
-    fn bin_expr(left: Expression, right: Expression, op: BinaryOperation) -> Expression {
+    fn bin_expr(&self, left: Expression, right: Expression, op: BinaryOperation) -> Expression {
         Expression::Binary(BinaryExpression {
             span: left.span() + right.span(),
             op,
             left: Box::new(left),
             right: Box::new(right),
+            id: self.node_builder.next_id(),
         })
     }
 
@@ -97,7 +99,8 @@ impl ParserContext<'_> {
     ) -> Result<Expression> {
         let mut expr = f(self)?;
         while let Some(op) = self.eat_bin_op(tokens) {
-            expr = Self::bin_expr(expr, f(self)?, op);
+            let right = f(self)?;
+            expr = self.bin_expr(expr, right, op);
         }
         Ok(expr)
     }
@@ -152,7 +155,7 @@ impl ParserContext<'_> {
         let mut expr = self.parse_bitwise_exclusive_or_expression()?;
         if let Some(op) = self.eat_bin_op(&[Token::Lt, Token::LtEq, Token::Gt, Token::GtEq]) {
             let right = self.parse_bitwise_exclusive_or_expression()?;
-            expr = Self::bin_expr(expr, right, op);
+            expr = self.bin_expr(expr, right, op);
         }
         Ok(expr)
     }
@@ -165,7 +168,7 @@ impl ParserContext<'_> {
         let mut expr = self.parse_ordering_expression()?;
         if let Some(op) = self.eat_bin_op(&[Token::Eq, Token::NotEq]) {
             let right = self.parse_ordering_expression()?;
-            expr = Self::bin_expr(expr, right, op);
+            expr = self.bin_expr(expr, right, op);
         }
         Ok(expr)
     }
@@ -224,13 +227,34 @@ impl ParserContext<'_> {
     /// Returns an [`Expression`] AST node if the next tokens represent a
     /// binary exponentiation expression.
     ///
-    /// Otherwise, tries to parse the next token using [`parse_unary_expression`].
+    /// Otherwise, tries to parse the next token using [`parse_cast_expression`].
     fn parse_exponential_expression(&mut self) -> Result<Expression> {
-        let mut expr = self.parse_unary_expression()?;
+        let mut expr = self.parse_cast_expression()?;
 
         if let Some(op) = self.eat_bin_op(&[Token::Pow]) {
             let right = self.parse_exponential_expression()?;
-            expr = Self::bin_expr(expr, right, op);
+            expr = self.bin_expr(expr, right, op);
+        }
+
+        Ok(expr)
+    }
+
+    /// Returns an [`Expression`] AST node if the next tokens represent a
+    /// cast expression, e.g. `x as u64`.
+    ///
+    /// Otherwise, tries to parse the next token using [`parse_unary_expression`].
+    fn parse_cast_expression(&mut self) -> Result<Expression> {
+        let mut expr = self.parse_unary_expression()?;
+
+        // `as` is not a reserved keyword, so that programs using it as an identifier still parse.
+        while self.eat(&Token::Identifier(sym::As)) {
+            let (type_, type_span) = self.parse_type()?;
+            expr = Expression::Cast(CastExpression {
+                span: expr.span() + type_span,
+                expression: Box::new(expr),
+                type_,
+                id: self.node_builder.next_id(),
+            });
         }
 
         Ok(expr)
@@ -260,16 +284,27 @@ impl ParserContext<'_> {
                 // If the unary operation is a negate, and the inner expression is a signed integer literal,
                 // then produce a negative integer literal.
                 // This helps handle a special case where -128i8, treated as a unary expression, overflows, but -128i8, treated as an integer literal doesn't.
-                Expression::Literal(Literal::Integer(integer_type, string, span))
+                Expression::Literal(Literal::Integer(integer_type, string, span, id))
                     if op == UnaryOperation::Negate && inner_is_integer =>
                 {
-                    Expression::Literal(Literal::Integer(integer_type, format!("-{}", string), op_span + span))
+                    Expression::Literal(Literal::Integer(
+                        integer_type,
+                        format!("-{}", string),
+                        op_span + span,
+                        id,
+                    ))
+                }
+                Expression::Literal(Literal::Unsuffixed(string, type_, span, id))
+                    if op == UnaryOperation::Negate && inner_is_integer =>
+                {
+                    Expression::Literal(Literal::Unsuffixed(format!("-{}", string), type_, op_span + span, id))
                 }
                 // Otherwise, produce a unary expression.
                 _ => Expression::Unary(UnaryExpression {
                     span: op_span + inner.span(),
                     op,
                     receiver: Box::new(inner),
+                    id: self.node_builder.next_id(),
                 }),
             };
         }
@@ -289,6 +324,7 @@ impl ParserContext<'_> {
                 span,
                 op,
                 receiver: Box::new(receiver),
+                id: self.node_builder.next_id(),
             }))
         } else if let (1, Some(op)) = (args.len(), BinaryOperation::from_symbol(method.name)) {
             // Found a binary operator and the argument list contains a single argument.
@@ -297,11 +333,15 @@ impl ParserContext<'_> {
                 op,
                 left: Box::new(receiver),
                 right: Box::new(args.swap_remove(0)),
+                id: self.node_builder.next_id(),
             }))
         } else {
             // Either an invalid unary/binary operator, or more arguments given.
             self.emit_err(ParserError::invalid_method_call(receiver, method, span));
-            Ok(Expression::Err(ErrExpression { span }))
+            Ok(Expression::Err(ErrExpression {
+                span,
+                id: self.node_builder.next_id(),
+            }))
         }
     }
 
@@ -329,6 +369,7 @@ impl ParserContext<'_> {
                 ty: type_,
                 name: member_name,
                 args,
+                id: self.node_builder.next_id(),
             })
         } else {
             // Return the struct constant.
@@ -336,6 +377,7 @@ impl ParserContext<'_> {
                 span: module_name.span() + member_name.span(),
                 ty: type_,
                 name: member_name,
+                id: self.node_builder.next_id(),
             })
         }))
     }
@@ -359,13 +401,16 @@ impl ParserContext<'_> {
                 if self.check_int() {
                     // Eat a tuple member access.
                     let (index, span) = self.eat_integer()?;
+                    self.check_edition(Feature::Tuples, span);
                     expr = Expression::Access(AccessExpression::Tuple(TupleAccess {
                         tuple: Box::new(expr),
                         index,
                         span,
+                        id: self.node_builder.next_id(),
                     }))
-                } else if self.eat(&Token::Leo) {
-                    // Eat an external function call.
+                } else if self.peek_is_external_network() {
+                    // Eat an external function call, e.g. `foo.aleo/bar(..)`.
+                    self.eat_network();
                     self.eat(&Token::Div); // todo: Make `/` a more general token.
 
                     // Parse function name.
@@ -378,6 +423,8 @@ impl ParserContext<'_> {
                         function: Box::new(Expression::Identifier(name)),
                         external: Some(Box::new(expr)),
                         arguments,
+                        type_arguments: Default::default(),
+                        id: self.node_builder.next_id(),
                     });
                 } else {
                     // Parse identifier name.
@@ -392,9 +439,20 @@ impl ParserContext<'_> {
                             span: expr.span(),
                             inner: Box::new(expr),
                             name,
+                            id: self.node_builder.next_id(),
                         }))
                     }
                 }
+            } else if self.eat(&Token::LeftSquare) {
+                // Eat an array access.
+                let index = self.parse_expression()?;
+                let end = self.expect(&Token::RightSquare)?;
+                expr = Expression::Access(AccessExpression::Array(ArrayAccess {
+                    span: expr.span() + end,
+                    array: Box::new(expr),
+                    index: Box::new(index),
+                    id: self.node_builder.next_id(),
+                }));
             } else if self.eat(&Token::DoubleColon) {
                 // Eat a core struct constant or core struct function call.
                 expr = self.parse_associated_access_expression(expr)?;
@@ -406,10 +464,12 @@ impl ParserContext<'_> {
                     function: Box::new(expr),
                     external: None,
                     arguments,
+                    type_arguments: Default::default(),
+                    id: self.node_builder.next_id(),
                 });
             }
-            // Check if next token is a dot to see if we are calling recursive method.
-            if !self.check(&Token::Dot) {
+            // Check if next token is a dot or a left bracket to see if we are calling recursive method or access.
+            if !self.check(&Token::Dot) && !self.check(&Token::LeftSquare) {
                 break;
             }
         }
@@ -428,7 +488,34 @@ impl ParserContext<'_> {
         if !trailing && tuple.len() == 1 {
             Ok(tuple.swap_remove(0))
         } else {
-            Ok(Expression::Tuple(TupleExpression { elements: tuple, span }))
+            self.check_edition(Feature::Tuples, span);
+            Ok(Expression::Tuple(TupleExpression {
+                elements: tuple,
+                span,
+                id: self.node_builder.next_id(),
+            }))
+        }
+    }
+
+    /// Returns an [`Expression`] AST node if the next tokens represent an
+    /// array initialization expression, e.g. `[1u8, 2u8, 3u8]`.
+    fn parse_array_expression(&mut self) -> Result<Expression> {
+        let (elements, _, span) = self.parse_list(Delimiter::Bracket, Some(Token::Comma), |p| {
+            p.parse_expression().map(Some)
+        })?;
+
+        if elements.is_empty() {
+            self.emit_err(ParserError::empty_array(span));
+            Ok(Expression::Err(ErrExpression {
+                span,
+                id: self.node_builder.next_id(),
+            }))
+        } else {
+            Ok(Expression::Array(ArrayExpression {
+                elements,
+                span,
+                id: self.node_builder.next_id(),
+            }))
         }
     }
 
@@ -480,6 +567,7 @@ impl ParserContext<'_> {
             span: start_span + &end_span,
             x: first_gc,
             y: second_gc,
+            id: self.node_builder.next_id(),
         };
 
         // Eat everything so that this isn't just peeking.
@@ -498,7 +586,10 @@ impl ParserContext<'_> {
         let identifier = if self.allow_identifier_underscores && self.eat(&Token::Underscore) {
             // Allow `_nonce` for struct records.
             let identifier_without_underscore = self.expect_identifier()?;
-            Identifier::new(Symbol::intern(&format!("_{}", identifier_without_underscore.name)))
+            Identifier::new(
+                Symbol::intern(&format!("_{}", identifier_without_underscore.name)),
+                self.node_builder.next_id(),
+            )
         } else {
             self.expect_identifier()?
         };
@@ -516,7 +607,11 @@ impl ParserContext<'_> {
     /// Returns an [`Expression`] AST node if the next tokens represent a
     /// struct initialization expression.
     /// let foo = Foo { x: 1u8 };
-    pub fn parse_struct_init_expression(&mut self, identifier: Identifier) -> Result<Expression> {
+    pub fn parse_struct_init_expression(
+        &mut self,
+        identifier: Identifier,
+        const_arguments: Vec<Size>,
+    ) -> Result<Expression> {
         let (members, _, end) = self.parse_list(Delimiter::Brace, Some(Token::Comma), |p| {
             p.parse_struct_member().map(Some)
         })?;
@@ -524,7 +619,9 @@ impl ParserContext<'_> {
         Ok(Expression::Struct(StructExpression {
             span: identifier.span + end,
             name: identifier,
+            const_arguments,
             members,
+            id: self.node_builder.next_id(),
         }))
     }
 
@@ -538,6 +635,8 @@ impl ParserContext<'_> {
     fn parse_primary_expression(&mut self) -> Result<Expression> {
         if let Token::LeftParen = self.token.token {
             return self.parse_tuple_expression();
+        } else if let Token::LeftSquare = self.token.token {
+            return self.parse_array_expression();
         }
 
         let SpannedToken { token, span } = self.token.clone();
@@ -552,42 +651,63 @@ impl ParserContext<'_> {
                     // Literal followed by `field`, e.g., `42field`.
                     Some(Token::Field) => {
                         assert_no_whitespace("field")?;
-                        Expression::Literal(Literal::Field(value, full_span))
+                        Expression::Literal(Literal::Field(value, full_span, self.node_builder.next_id()))
                     }
                     // Literal followed by `group`, e.g., `42group`.
                     Some(Token::Group) => {
                         assert_no_whitespace("group")?;
-                        Expression::Literal(Literal::Group(Box::new(GroupLiteral::Single(value, full_span))))
+                        Expression::Literal(Literal::Group(Box::new(GroupLiteral::Single(
+                            value,
+                            full_span,
+                            self.node_builder.next_id(),
+                        ))))
                     }
                     // Literal followed by `scalar` e.g., `42scalar`.
                     Some(Token::Scalar) => {
                         assert_no_whitespace("scalar")?;
-                        Expression::Literal(Literal::Scalar(value, full_span))
+                        Expression::Literal(Literal::Scalar(value, full_span, self.node_builder.next_id()))
                     }
                     // Literal followed by other type suffix, e.g., `42u8`.
                     Some(suffix) => {
                         assert_no_whitespace(&suffix.to_string())?;
                         let int_ty = Self::token_to_int_type(suffix).expect("unknown int type token");
-                        Expression::Literal(Literal::Integer(int_ty, value, full_span))
+                        Expression::Literal(Literal::Integer(int_ty, value, full_span, self.node_builder.next_id()))
                     }
-                    None => return Err(ParserError::implicit_values_not_allowed(value, span).into()),
+                    // Literal without a type suffix, e.g., `42`, whose type is inferred during type checking.
+                    None => Expression::Literal(Literal::Unsuffixed(
+                        value,
+                        Default::default(),
+                        span,
+                        self.node_builder.next_id(),
+                    )),
                 }
             }
-            Token::True => Expression::Literal(Literal::Boolean(true, span)),
-            Token::False => Expression::Literal(Literal::Boolean(false, span)),
+            Token::True => Expression::Literal(Literal::Boolean(true, span, self.node_builder.next_id())),
+            Token::False => Expression::Literal(Literal::Boolean(false, span, self.node_builder.next_id())),
             Token::AddressLit(address_string) => {
                 if address_string.parse::<Address<Testnet3>>().is_err() {
                     self.emit_err(ParserError::invalid_address_lit(&address_string, span));
                 }
-                Expression::Literal(Literal::Address(address_string, span))
+                Expression::Literal(Literal::Address(address_string, span, self.node_builder.next_id()))
+            }
+            Token::StaticString(value) => {
+                Expression::Literal(Literal::String(value, span, self.node_builder.next_id()))
             }
-            Token::StaticString(value) => Expression::Literal(Literal::String(value, span)),
             Token::Identifier(name) => {
-                let ident = Identifier { name, span };
-                if !self.disallow_struct_construction && self.check(&Token::LeftCurly) {
+                let ident = Identifier {
+                    name,
+                    span,
+                    id: self.node_builder.next_id(),
+                };
+                if self.check(&Token::DoubleColon) && self.look_ahead(1, |t| t.token == Token::Lt) {
+                    // Parse the init of a generic struct instance, e.g. `Buffer::<4> { ... }`.
+                    self.expect(&Token::DoubleColon)?;
+                    let (const_arguments, _) = self.parse_const_arguments()?;
+                    self.parse_struct_init_expression(ident, const_arguments)?
+                } else if !self.disallow_struct_construction && self.check(&Token::LeftCurly) {
                     // Parse struct and records inits as struct expressions.
                     // Enforce struct or record type later at type checking.
-                    self.parse_struct_init_expression(ident)?
+                    self.parse_struct_init_expression(ident, Vec::new())?
                 } else {
                     Expression::Identifier(ident)
                 }
@@ -595,10 +715,17 @@ impl ParserContext<'_> {
             Token::SelfLower => Expression::Identifier(Identifier {
                 name: sym::SelfLower,
                 span,
+                id: self.node_builder.next_id(),
+            }),
+            Token::Block => Expression::Identifier(Identifier {
+                name: sym::block,
+                span,
+                id: self.node_builder.next_id(),
             }),
             t if crate::type_::TYPE_TOKENS.contains(&t) => Expression::Identifier(Identifier {
                 name: t.keyword_to_symbol().unwrap(),
                 span,
+                id: self.node_builder.next_id(),
             }),
             token => {
                 return Err(ParserError::unexpected_str(token, "expression", span).into());
@@ -609,8 +736,7 @@ impl ParserContext<'_> {
 
 fn assert_no_whitespace(left_span: Span, right_span: Span, left: &str, right: &str) -> Result<()> {
     if left_span.hi != right_span.lo {
-        let error_span = Span::new(left_span.hi, right_span.lo); // The span between them.
-        return Err(ParserError::unexpected_whitespace(left, right, error_span).into());
+        return Err(ParserError::unexpected_whitespace(left, right, left_span.between(right_span)).into());
     }
 
     Ok(())
@@ -22,10 +22,24 @@ use leo_span::symbol::with_session_globals;
 
 use std::fs;
 
+/// The tokens that begin a definition in a program scope or a module.
+const DEFINITION_TOKENS: &[Token] = &[
+    Token::Const,
+    Token::Struct,
+    Token::Record,
+    Token::Enum,
+    Token::Mapping,
+    Token::At,
+    Token::Function,
+    Token::Inline,
+    Token::Transition,
+];
+
 impl ParserContext<'_> {
     /// Returns a [`Program`] AST if all tokens can be consumed and represent a valid Leo program.
     pub fn parse_program(&mut self) -> Result<Program> {
         let mut imports = IndexMap::new();
+        let mut modules = Vec::new();
         let mut program_scopes = IndexMap::new();
 
         // TODO: Remove restrictions on multiple program scopes
@@ -33,11 +47,14 @@ impl ParserContext<'_> {
 
         while self.has_next() {
             match &self.token.token {
+                Token::Import if self.look_ahead(2, |t| t.token == Token::Semicolon) => {
+                    modules.push(self.parse_module_import()?);
+                }
                 Token::Import => {
                     let (id, import) = self.parse_import()?;
                     imports.insert(id, import);
                 }
-                Token::Program => {
+                Token::At | Token::Program => {
                     match parsed_program_scope {
                         // Only one program scope is allowed per file.
                         true => return Err(ParserError::only_one_program_scope_is_allowed(self.token.span).into()),
@@ -59,10 +76,29 @@ impl ParserContext<'_> {
 
         Ok(Program {
             imports,
+            modules,
             program_scopes,
         })
     }
 
+    /// Returns a [`Module`] AST if all tokens can be consumed and represent a valid Leo module.
+    /// A module is a file of module imports followed by definitions, without a program scope around them.
+    pub fn parse_module(&mut self) -> Result<Module> {
+        let mut modules = Vec::new();
+        while self.check(&Token::Import) {
+            modules.push(self.parse_module_import()?);
+        }
+
+        let module = self.parse_definitions()?;
+
+        // Definitions stop at a `}`, which cannot close anything in a module.
+        if self.has_next() {
+            return Err(Self::unexpected_item(&self.token, DEFINITION_TOKENS).into());
+        }
+
+        Ok(Module { modules, ..module })
+    }
+
     fn unexpected_item(token: &SpannedToken, expected: &[Token]) -> ParserError {
         ParserError::unexpected(
             &token.token,
@@ -75,7 +111,17 @@ impl ParserContext<'_> {
         )
     }
 
-    /// Parses an import statement `import foo.leo;`.
+    /// Parses a module import `import foo;`, returning the name of the module.
+    /// The module is resolved by the compiler, which reads it from `foo.leo` in the `src/` directory of the package.
+    fn parse_module_import(&mut self) -> Result<Identifier> {
+        self.expect(&Token::Import)?;
+        let name = self.expect_identifier()?;
+        self.expect(&Token::Semicolon)?;
+        Ok(name)
+    }
+
+    /// Parses an import statement `import foo.leo;` or `import foo.aleo;`.
+    /// Either way, the imported program is read from the stub `imports/foo.leo`, which declares its records and transitions.
     pub(super) fn parse_import(&mut self) -> Result<(Identifier, Program)> {
         // Parse `import`.
         let _start = self.expect(&Token::Import)?;
@@ -83,9 +129,9 @@ impl ParserContext<'_> {
         // Parse `foo`.
         let import_name = self.expect_identifier()?;
 
-        // Parse `.leo`.
+        // Parse `.leo` or `.aleo`.
         self.expect(&Token::Dot)?;
-        if !self.eat(&Token::Leo) {
+        if !self.eat_network() {
             // Throw error for non-leo files.
             return Err(ParserError::leo_imports_only(self.token.span).into());
         }
@@ -116,13 +162,23 @@ impl ParserContext<'_> {
         let prg_sf = with_session_globals(|s| s.source_map.new_source(&program_string, name));
 
         // Use the parser to construct the imported abstract syntax tree (ast).
-        let program_ast = parse_ast(self.handler, &prg_sf.src, prg_sf.start_pos)?;
+        // Note that the import is written for the same edition as the importing program, unless it declares its own.
+        let program_ast = parse_ast(
+            self.handler,
+            self.node_builder,
+            &prg_sf.src,
+            prg_sf.start_pos,
+            self.edition,
+        )?;
 
         Ok((import_name, program_ast.into_repr()))
     }
 
     /// Parsers a program scope `program foo.aleo { ... }`.
     fn parse_program_scope(&mut self) -> Result<ProgramScope> {
+        // Parse the program annotations, if they exist.
+        let default_integer_type = self.parse_program_annotations()?;
+
         // Parse `program` keyword.
         let start = self.expect(&Token::Program)?;
 
@@ -145,55 +201,118 @@ impl ParserContext<'_> {
         self.expect(&Token::LeftCurly)?;
 
         // Parse the body of the program scope.
+        let Module {
+            consts,
+            structs,
+            enums,
+            mappings,
+            functions,
+            ..
+        } = self.parse_definitions()?;
+
+        // Parse `}`.
+        let end = self.expect(&Token::RightCurly)?;
+
+        Ok(ProgramScope {
+            program_id,
+            consts,
+            functions,
+            structs,
+            enums,
+            mappings,
+            default_integer_type,
+            span: start + end,
+        })
+    }
+
+    /// Parses the constant, struct, mapping, and function definitions of a program scope or a module.
+    /// Parsing stops at the end of the file or at a `}`, which is left for the caller.
+    fn parse_definitions(&mut self) -> Result<Module> {
+        let mut consts = IndexMap::new();
         let mut functions = IndexMap::new();
         let mut structs = IndexMap::new();
+        let mut enums = IndexMap::new();
         let mut mappings = IndexMap::new();
 
         while self.has_next() {
             match &self.token.token {
+                Token::Const => {
+                    let (id, const_) = self.parse_const_declaration()?;
+                    consts.insert(id, const_);
+                }
                 Token::Struct | Token::Record => {
                     let (id, struct_) = self.parse_struct()?;
                     structs.insert(id, struct_);
                 }
+                Token::Enum => {
+                    self.check_edition(Feature::Enums, self.token.span);
+                    let (id, enum_) = self.parse_enum()?;
+                    enums.insert(id, enum_);
+                }
                 Token::Mapping => {
+                    self.check_edition(Feature::Mappings, self.token.span);
                     let (id, mapping) = self.parse_mapping()?;
                     mappings.insert(id, mapping);
                 }
-                Token::At | Token::Function | Token::Transition => {
+                Token::At | Token::Function | Token::Inline | Token::Transition => {
                     let (id, function) = self.parse_function()?;
                     functions.insert(id, function);
                 }
+                Token::Circuit if self.legacy => {
+                    self.rewrites.push((self.token.span, "struct".to_string()));
+                    let (id, struct_) = self.parse_struct()?;
+                    structs.insert(id, struct_);
+                }
                 Token::Circuit => return Err(ParserError::circuit_is_deprecated(self.token.span).into()),
                 Token::RightCurly => break,
-                _ => {
-                    return Err(Self::unexpected_item(
-                        &self.token,
-                        &[
-                            Token::Struct,
-                            Token::Record,
-                            Token::Mapping,
-                            Token::At,
-                            Token::Function,
-                            Token::Transition,
-                        ],
-                    )
-                    .into())
-                }
+                _ => return Err(Self::unexpected_item(&self.token, DEFINITION_TOKENS).into()),
             }
         }
 
-        // Parse `}`.
-        let end = self.expect(&Token::RightCurly)?;
-
-        Ok(ProgramScope {
-            program_id,
-            functions,
+        Ok(Module {
+            modules: Vec::new(),
+            consts,
             structs,
+            enums,
             mappings,
-            span: start + end,
+            functions,
         })
     }
 
+    /// Parses the annotations preceding a program scope, returning the default integer type if one is given.
+    /// The program annotations are `@default_integer_type(<integer type>)` and `@edition(<edition>)`.
+    /// An edition annotation sets the edition that the rest of the program is parsed for.
+    fn parse_program_annotations(&mut self) -> Result<Option<IntegerType>> {
+        let mut default_integer_type = None;
+        while self.check(&Token::At) {
+            let annotation = self.parse_annotation()?;
+            if annotation.identifier.name == sym::edition {
+                // Parse the edition argument, e.g. `(2022)`.
+                self.expect(&Token::LeftParen)?;
+                let (edition, span) = self.eat_integer()?;
+                self.edition = edition.value.parse().map_err(|_| {
+                    let editions = Edition::ALL.map(|edition| format!("`{}`", edition)).join(", ");
+                    ParserError::unknown_edition(&edition.value, editions, span)
+                })?;
+                self.expect(&Token::RightParen)?;
+                continue;
+            }
+            if annotation.identifier.name != sym::default_integer_type {
+                return Err(ParserError::unknown_program_annotation(annotation.identifier, annotation.span).into());
+            }
+
+            // Parse the integer type argument, e.g. `(u32)`.
+            self.expect(&Token::LeftParen)?;
+            let (type_, span) = self.parse_primitive_type()?;
+            match type_ {
+                Type::Integer(integer_type) => default_integer_type = Some(integer_type),
+                type_ => return Err(ParserError::invalid_default_integer_type(type_, span).into()),
+            }
+            self.expect(&Token::RightParen)?;
+        }
+        Ok(default_integer_type)
+    }
+
     /// Returns a [`Vec<Member>`] AST node if the next tokens represent a struct member.
     fn parse_struct_members(&mut self) -> Result<(Vec<Member>, Span)> {
         let mut members = Vec::new();
@@ -241,21 +360,78 @@ impl ParserContext<'_> {
     }
 
     /// Parses a struct or record definition, e.g., `struct Foo { ... }` or `record Foo { ... }`.
+    /// Records are canonicalized by injecting their `owner` and `gates` members, if they are not declared.
     pub(super) fn parse_struct(&mut self) -> Result<(Identifier, Struct)> {
         let is_record = matches!(&self.token.token, Token::Record);
-        let start = self.expect_any(&[Token::Struct, Token::Record])?;
+        // `circuit` is only reachable here when migrating legacy programs.
+        let start = self.expect_any(&[Token::Struct, Token::Record, Token::Circuit])?;
         let struct_name = self.expect_identifier()?;
+        let const_parameters = match self.check(&Token::Lt) {
+            true => self.parse_const_parameters()?,
+            false => Vec::new(),
+        };
 
         self.expect(&Token::LeftCurly)?;
         let (members, end) = self.parse_struct_members()?;
 
+        let mut struct_ = Struct {
+            identifier: struct_name,
+            const_parameters,
+            members,
+            is_record,
+            injected_members: Vec::new(),
+            span: start + end,
+            id: self.node_builder.next_id(),
+        };
+        struct_.inject_record_members(self.node_builder);
+
+        Ok((struct_name, struct_))
+    }
+
+    /// Parses the const parameters of a generic struct, e.g. `<const N: u32>`.
+    fn parse_const_parameters(&mut self) -> Result<Vec<ConstParameter>> {
+        let start = self.expect(&Token::Lt)?;
+        let mut const_parameters = Vec::new();
+        loop {
+            let parameter_start = self.expect(&Token::Const)?;
+            let identifier = self.expect_identifier()?;
+            self.expect(&Token::Colon)?;
+            match self.parse_primitive_type()? {
+                (Type::Integer(type_), end) if !type_.is_signed() => const_parameters.push(ConstParameter {
+                    identifier,
+                    type_,
+                    span: parameter_start + end,
+                    id: self.node_builder.next_id(),
+                }),
+                (type_, span) => return Err(ParserError::invalid_const_parameter_type(type_, span).into()),
+            }
+            if !self.eat(&Token::Comma) {
+                break;
+            }
+        }
+        let end = self.expect(&Token::Gt)?;
+        self.check_edition(Feature::Generics, start + end);
+        Ok(const_parameters)
+    }
+
+    /// Parses an enum definition, e.g. `enum State { Idle, Running, Done }`.
+    pub(super) fn parse_enum(&mut self) -> Result<(Identifier, Enum)> {
+        let start = self.expect(&Token::Enum)?;
+        let identifier = self.expect_identifier()?;
+        let (variants, _, end) = self.parse_list(Delimiter::Brace, Some(Token::Comma), |p| {
+            p.expect_identifier().map(Some)
+        })?;
+        if variants.is_empty() {
+            return Err(ParserError::empty_enum(identifier, start + end).into());
+        }
+
         Ok((
-            struct_name,
-            Struct {
-                identifier: struct_name,
-                members,
-                is_record,
+            identifier,
+            Enum {
+                identifier,
+                variants,
                 span: start + end,
+                id: self.node_builder.next_id(),
             },
         ))
     }
@@ -276,6 +452,29 @@ impl ParserContext<'_> {
                 key_type,
                 value_type,
                 span: start + end,
+                id: self.node_builder.next_id(),
+            },
+        ))
+    }
+
+    /// Returns an [`(Identifier, ConstDeclaration)`] AST node if the next tokens represent a program constant.
+    pub(super) fn parse_const_declaration(&mut self) -> Result<(Identifier, ConstDeclaration)> {
+        let start = self.expect(&Token::Const)?;
+        let identifier = self.expect_identifier()?;
+        self.expect(&Token::Colon)?;
+        let (type_, _) = self.parse_type()?;
+        self.expect(&Token::Assign)?;
+        let value = self.parse_expression()?;
+        let end = self.expect(&Token::Semicolon)?;
+
+        Ok((
+            identifier,
+            ConstDeclaration {
+                identifier,
+                type_,
+                value,
+                span: start + end,
+                id: self.node_builder.next_id(),
             },
         ))
     }
@@ -315,9 +514,9 @@ impl ParserContext<'_> {
             let external = self.expect_identifier()?;
             let mut span = name.span + external.span;
 
-            // Parse `.leo/`.
+            // Parse `.leo/` or `.aleo/`.
             self.eat(&Token::Dot);
-            self.eat(&Token::Leo);
+            self.eat_network();
             self.eat(&Token::Div);
 
             // Parse record name.
@@ -333,6 +532,7 @@ impl ParserContext<'_> {
                 program_name: external,
                 record,
                 span,
+                id: self.node_builder.next_id(),
             }))
         } else {
             let type_ = self.parse_type()?.0;
@@ -342,6 +542,7 @@ impl ParserContext<'_> {
                 mode,
                 type_,
                 span: name.span,
+                id: self.node_builder.next_id(),
             }))
         }
     }
@@ -351,7 +552,12 @@ impl ParserContext<'_> {
         // TODO: Could this span be made more accurate?
         let mode = self.parse_mode()?;
         let (type_, span) = self.parse_type()?;
-        Ok(FunctionOutput { mode, type_, span })
+        Ok(FunctionOutput {
+            mode,
+            type_,
+            span,
+            id: self.node_builder.next_id(),
+        })
     }
 
     /// Returns a [`Output`] AST node if the next tokens represent a function output.
@@ -360,9 +566,9 @@ impl ParserContext<'_> {
             let external = self.expect_identifier()?;
             let mut span = external.span;
 
-            // Parse `.leo/`.
+            // Parse `.leo/` or `.aleo/`.
             self.eat(&Token::Dot);
-            self.eat(&Token::Leo);
+            self.eat_network();
             self.eat(&Token::Div);
 
             // Parse record name.
@@ -374,10 +580,11 @@ impl ParserContext<'_> {
             span = span + self.prev_token.span;
 
             Ok(Output::External(External {
-                identifier: Identifier::new(Symbol::intern("dummy")),
+                identifier: Identifier::new(Symbol::intern("dummy"), self.node_builder.next_id()),
                 program_name: external,
                 record,
                 span,
+                id: self.node_builder.next_id(),
             }))
         } else {
             Ok(Output::Internal(self.parse_function_output()?))
@@ -399,17 +606,123 @@ impl ParserContext<'_> {
             Token::Program => Identifier {
                 name: sym::program,
                 span: self.expect(&Token::Program)?,
+                id: self.node_builder.next_id(),
+            },
+            Token::Inline => Identifier {
+                name: sym::inline,
+                span: self.expect(&Token::Inline)?,
+                id: self.node_builder.next_id(),
             },
             _ => self.expect_identifier()?,
         };
         let span = start + identifier.span;
 
-        // TODO: Verify that this check is sound.
         // Check that there is no whitespace in between the `@` symbol and identifier.
-        match identifier.span.hi.0 - start.lo.0 > 1 + identifier.name.to_string().len() as u32 {
-            true => Err(ParserError::space_in_annotation(span).into()),
-            false => Ok(Annotation { identifier, span }),
+        match start.between(identifier.span).is_empty() {
+            true => Ok(Annotation {
+                identifier,
+                arguments: Vec::new(),
+                span,
+                id: self.node_builder.next_id(),
+            }),
+            false => Err(ParserError::space_in_annotation(span).into()),
+        }
+    }
+
+    /// Returns an [`Annotation`] AST node if the next tokens represent an annotation and its arguments, if any,
+    /// e.g. `@allow(empty_function)`.
+    /// The conditions of a `@cfg(..)` annotation are checked to be ones that the compiler can evaluate.
+    pub(super) fn parse_annotation_with_arguments(&mut self) -> Result<Annotation> {
+        let mut annotation = self.parse_annotation()?;
+        let is_cfg = annotation.identifier.name == sym::cfg;
+        // Parse the arguments of the annotation, if they exist, e.g. `(empty_function)`.
+        if self.peek_is_left_par() {
+            let (arguments, _, span) =
+                self.parse_paren_comma_list(|p| p.parse_annotation_argument(is_cfg).map(Some))?;
+            annotation.arguments = arguments;
+            annotation.span = annotation.span + span;
+        }
+        if is_cfg {
+            self.check_cfg(&annotation);
+        }
+        Ok(annotation)
+    }
+
+    /// Returns an [`AnnotationArgument`] AST node if the next tokens represent an argument of an annotation.
+    /// Only the arguments of a `@cfg(..)` annotation can be set to a string, e.g. `network = "testnet3"`.
+    fn parse_annotation_argument(&mut self, is_cfg: bool) -> Result<AnnotationArgument> {
+        let identifier = self.expect_identifier()?;
+        if !is_cfg || !self.eat(&Token::Assign) {
+            return Ok(AnnotationArgument {
+                identifier,
+                value: None,
+                span: identifier.span,
+                id: self.node_builder.next_id(),
+            });
+        }
+        let value = match &self.token.token {
+            Token::StaticString(value) => value.clone(),
+            _ => return self.unexpected("string"),
+        };
+        self.bump();
+        Ok(AnnotationArgument {
+            identifier,
+            value: Some(value),
+            span: identifier.span + self.prev_token.span,
+            id: self.node_builder.next_id(),
+        })
+    }
+
+    /// Checks that each condition of a `@cfg(..)` annotation is `test`, `network = "<name>"`, or `feature = "<name>"`.
+    fn check_cfg(&self, cfg: &Annotation) {
+        if cfg.arguments.is_empty() {
+            self.emit_err(ParserError::empty_cfg(cfg.span));
         }
+        for condition in cfg.arguments.iter() {
+            let is_valid = match condition.identifier.name {
+                sym::test => condition.value.is_none(),
+                sym::network | sym::feature => condition.value.is_some(),
+                _ => false,
+            };
+            if !is_valid {
+                self.emit_err(ParserError::invalid_cfg_condition(condition, condition.span));
+            }
+        }
+    }
+
+    /// Returns the type parameters of a generic function if the next tokens represent them, e.g. `<T: integer, U: integer>`.
+    fn parse_type_parameters(&mut self) -> Result<Vec<TypeParameter>> {
+        let start = self.expect(&Token::Lt)?;
+        let mut type_parameters = Vec::new();
+        loop {
+            let identifier = self.expect_identifier()?;
+            self.expect(&Token::Colon)?;
+            // Type keywords are accepted as bounds, so that the type checker can report them as unknown bounds.
+            let bound = match &self.token.token {
+                t if crate::type_::TYPE_TOKENS.contains(t) => {
+                    let bound = Identifier {
+                        name: t.keyword_to_symbol().unwrap(),
+                        span: self.token.span,
+                        id: self.node_builder.next_id(),
+                    };
+                    self.bump();
+                    bound
+                }
+                _ => self.expect_identifier()?,
+            };
+            type_parameters.push(TypeParameter {
+                identifier,
+                bound,
+                span: identifier.span + bound.span,
+                id: self.node_builder.next_id(),
+            });
+            if !self.eat(&Token::Comma) {
+                break;
+            }
+        }
+        let end = self.expect(&Token::Gt)?;
+        self.check_edition(Feature::Generics, start + end);
+        Ok(type_parameters)
     }
 
     /// Returns an [`(Identifier, Function)`] AST node if the next tokens represent a function name
@@ -419,16 +732,23 @@ impl ParserContext<'_> {
         // Parse annotations, if they exist.
         let mut annotations = Vec::new();
         while self.look_ahead(0, |t| &t.token) == &Token::At {
-            annotations.push(self.parse_annotation()?)
+            annotations.push(self.parse_annotation_with_arguments()?);
         }
-        // Parse `<call_type> IDENT`, where `<call_type>` is `function` or `transition`.
+        // Parse `<call_type> IDENT`, where `<call_type>` is `function`, `inline`, or `transition`.
         let (call_type, start) = match self.token.token {
             Token::Function => (CallType::Standard, self.expect(&Token::Function)?),
+            Token::Inline => (CallType::Inline, self.expect(&Token::Inline)?),
             Token::Transition => (CallType::Transition, self.expect(&Token::Transition)?),
-            _ => self.unexpected("'function', 'transition'")?,
+            _ => self.unexpected("'function', 'inline', 'transition'")?,
         };
         let name = self.expect_identifier()?;
 
+        // Parse type parameters, if they exist, e.g. `<T: integer>`.
+        let type_parameters = match self.check(&Token::Lt) {
+            true => self.parse_type_parameters()?,
+            false => Vec::new(),
+        };
+
         // Parse parameters.
         let (inputs, ..) = self.parse_paren_comma_list(|p| p.parse_input().map(Some))?;
 
@@ -438,7 +758,11 @@ impl ParserContext<'_> {
             true => {
                 self.disallow_struct_construction = true;
                 let output = match self.peek_is_left_par() {
-                    true => self.parse_paren_comma_list(|p| p.parse_output().map(Some))?.0,
+                    true => {
+                        let (output, _, span) = self.parse_paren_comma_list(|p| p.parse_output().map(Some))?;
+                        self.check_edition(Feature::Tuples, span);
+                        output
+                    }
                     false => vec![self.parse_output()?],
                 };
                 self.disallow_struct_construction = false;
@@ -468,7 +792,11 @@ impl ParserContext<'_> {
                     true => {
                         self.disallow_struct_construction = true;
                         let output = match self.peek_is_left_par() {
-                            true => self.parse_paren_comma_list(|p| p.parse_output().map(Some))?.0,
+                            true => {
+                                let (output, _, span) = self.parse_paren_comma_list(|p| p.parse_output().map(Some))?;
+                                self.check_edition(Feature::Tuples, span);
+                                output
+                            }
                             false => vec![self.parse_output()?],
                         };
                         self.disallow_struct_construction = false;
@@ -480,14 +808,32 @@ impl ParserContext<'_> {
                 let block = self.parse_block()?;
                 let span = start + block.span;
 
-                Some(Finalize::new(identifier, input, output, block, span))
+                Some(Finalize::new(
+                    identifier,
+                    input,
+                    output,
+                    block,
+                    span,
+                    self.node_builder.next_id(),
+                ))
             }
         };
 
         let span = start + block.span;
         Ok((
             name,
-            Function::new(annotations, call_type, name, inputs, output, block, finalize, span),
+            Function::new(
+                annotations,
+                call_type,
+                name,
+                type_parameters,
+                inputs,
+                output,
+                block,
+                finalize,
+                span,
+                self.node_builder.next_id(),
+            ),
         ))
     }
 }
@@ -33,6 +33,9 @@ use std::unreachable;
 mod context;
 pub(super) use context::ParserContext;
 
+pub mod edition;
+pub use edition::*;
+
 mod expression;
 mod file;
 mod input;
@@ -40,15 +43,92 @@ mod statement;
 pub(super) mod type_;
 
 /// Creates a new program from a given file path and source code text.
-pub fn parse(handler: &Handler, source: &str, start_pos: BytePos) -> Result<Program> {
-    let mut tokens = ParserContext::new(handler, crate::tokenize(source, start_pos)?);
+/// The program is parsed as written for `edition`, unless it declares an edition itself.
+pub fn parse(
+    handler: &Handler,
+    node_builder: &NodeBuilder,
+    source: &str,
+    start_pos: BytePos,
+    edition: Edition,
+) -> Result<Program> {
+    let mut tokens = ParserContext::new(handler, node_builder, crate::tokenize(source, start_pos)?);
+    tokens.edition = edition;
 
     tokens.parse_program()
 }
 
+/// Creates a new module from a given file path and source code text.
+/// The module is parsed as written for `edition`, the edition of the program that imports it.
+pub fn parse_module(
+    handler: &Handler,
+    node_builder: &NodeBuilder,
+    source: &str,
+    start_pos: BytePos,
+    edition: Edition,
+) -> Result<Module> {
+    let mut tokens = ParserContext::new(handler, node_builder, crate::tokenize(source, start_pos)?);
+    tokens.edition = edition;
+
+    tokens.parse_module()
+}
+
+/// Migrates a program written in legacy syntax to current syntax, returning the migrated source code.
+/// Legacy constructs are rewritten in place, so the rest of the program, including its comments, is unchanged.
+/// Constructs without an equivalent in current syntax are reported as warnings and left as written.
+pub fn migrate(handler: &Handler, source: &str, start_pos: BytePos) -> Result<String> {
+    let node_builder = NodeBuilder::default();
+    let mut tokens = ParserContext::new(handler, &node_builder, crate::tokenize(source, start_pos)?);
+    tokens.legacy = true;
+
+    tokens.parse_program()?;
+    handler.last_err()?;
+
+    // Rewrites are recorded in source order, so apply them from the end to keep earlier spans valid.
+    let mut migrated = source.to_string();
+    for (span, rewrite) in tokens.rewrites.into_iter().rev() {
+        let (lo, hi) = ((span.lo - start_pos).0 as usize, (span.hi - start_pos).0 as usize);
+        migrated.replace_range(lo..hi, &rewrite);
+    }
+
+    Ok(migrated)
+}
+
+/// Formats a program as canonical source code, as laid out by `formatter`.
+/// The comments of the program are kept, placed among the items and statements around them.
+pub fn format(handler: &Handler, source: &str, start_pos: BytePos, formatter: &Formatter) -> Result<String> {
+    let tokens = crate::tokenize(source, start_pos)?;
+
+    // A comment trails code if there is no line break between the previous token and the comment.
+    let mut comments = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if let Token::CommentLine(text) | Token::CommentBlock(text) = &token.token {
+            let trailing = i > 0 && {
+                let (lo, hi) = (tokens[i - 1].span.hi - start_pos, token.span.lo - start_pos);
+                !source[lo.0 as usize..hi.0 as usize].contains('\n')
+            };
+            comments.push(Comment {
+                text: text.clone(),
+                trailing,
+                span: token.span,
+            });
+        }
+    }
+
+    let node_builder = NodeBuilder::default();
+    let program = ParserContext::new(handler, &node_builder, tokens).parse_program()?;
+    handler.last_err()?;
+
+    Ok(formatter.format_with_comments(&program, comments))
+}
+
 /// Parses an input file at the given file `path` and `source` code text.
-pub fn parse_input(handler: &Handler, source: &str, start_pos: BytePos) -> Result<InputAst> {
-    let mut tokens = ParserContext::new(handler, crate::tokenize(source, start_pos)?);
+pub fn parse_input(
+    handler: &Handler,
+    node_builder: &NodeBuilder,
+    source: &str,
+    start_pos: BytePos,
+) -> Result<InputAst> {
+    let mut tokens = ParserContext::new(handler, node_builder, crate::tokenize(source, start_pos)?);
 
     tokens.parse_input_file()
 }
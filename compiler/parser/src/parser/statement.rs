@@ -16,9 +16,11 @@
 
 use super::*;
 
-use leo_errors::{ParserError, Result};
+use leo_errors::{ParserError, ParserWarning, Result};
 use leo_span::sym;
 
+use std::cell::RefCell;
+
 const ASSIGN_TOKENS: &[Token] = &[
     Token::Assign,
     Token::AddAssign,
@@ -42,13 +44,30 @@ impl ParserContext<'_> {
         match &self.token.token {
             Token::Return => Ok(Statement::Return(self.parse_return_statement()?)),
             Token::Async => Ok(Statement::Finalize(self.parse_finalize_statement()?)),
+            // Legacy programs call finalize without a preceding async token.
+            Token::Finalize if self.legacy => {
+                self.rewrites.push((self.token.span, "async finalize".to_string()));
+                Ok(Statement::Finalize(self.parse_finalize_statement()?))
+            }
             // If a finalize token is found without a preceding async token, return an error.
             Token::Finalize => Err(ParserError::finalize_without_async(self.token.span).into()),
-            Token::Increment => Ok(Statement::Increment(self.parse_increment_statement()?)),
-            Token::Decrement => Ok(Statement::Decrement(self.parse_decrement_statement()?)),
+            Token::Increment => {
+                self.check_edition(Feature::Mappings, self.token.span);
+                Ok(Statement::Increment(self.parse_increment_statement()?))
+            }
+            Token::Decrement => {
+                self.check_edition(Feature::Mappings, self.token.span);
+                Ok(Statement::Decrement(self.parse_decrement_statement()?))
+            }
             Token::If => Ok(Statement::Conditional(self.parse_conditional_statement()?)),
             Token::For => Ok(Statement::Iteration(Box::new(self.parse_loop_statement()?))),
-            Token::Console => Ok(Statement::Console(self.parse_console_statement()?)),
+            Token::Match => {
+                self.check_edition(Feature::Enums, self.token.span);
+                Ok(Statement::Match(self.parse_match_statement()?))
+            }
+            Token::Assert | Token::AssertEq | Token::AssertNeq => Ok(Statement::Assert(self.parse_assert_statement()?)),
+            Token::Console => Ok(Statement::Assert(self.parse_console_statement()?)),
+            Token::At => Ok(Statement::Cfg(self.parse_cfg_statement()?)),
             Token::Let | Token::Const => Ok(Statement::Definition(self.parse_definition_statement()?)),
             Token::LeftCurly => Ok(Statement::Block(self.parse_block()?)),
             _ => Ok(self.parse_assign_statement()?),
@@ -94,23 +113,44 @@ impl ParserContext<'_> {
                     right: Box::new(value),
                     op,
                     span,
+                    id: self.node_builder.next_id(),
                 }),
             };
 
-            Ok(Statement::Assign(Box::new(AssignStatement { span, place, value })))
+            Ok(Statement::Assign(Box::new(AssignStatement {
+                span,
+                place,
+                value,
+                id: self.node_builder.next_id(),
+            })))
         } else {
-            // Error on `expr;` but recover as an empty block `{}`.
             self.expect(&Token::Semicolon)?;
+
+            // Associated function calls, e.g. `Mapping::set(foo, bar, 1);`, may be evaluated for their effects.
+            if let Expression::Access(AccessExpression::AssociatedFunction(_)) = place {
+                let span = place.span();
+                return Ok(Statement::Expression(ExpressionStatement {
+                    expression: place,
+                    span,
+                    id: self.node_builder.next_id(),
+                }));
+            }
+
+            // Error on any other `expr;` but recover as an empty block `{}`.
             let span = place.span() + self.prev_token.span;
             self.emit_err(ParserError::expr_stmts_disallowed(span));
-            Ok(Statement::dummy(span))
+            Ok(Statement::dummy(span, self.node_builder.next_id()))
         }
     }
 
     /// Returns a [`Block`] AST node if the next tokens represent a block of statements.
     pub(super) fn parse_block(&mut self) -> Result<Block> {
         self.parse_list(Delimiter::Brace, None, |p| p.parse_statement().map(Some))
-            .map(|(statements, _, span)| Block { statements, span })
+            .map(|(statements, _, span)| Block {
+                statements,
+                span,
+                id: self.node_builder.next_id(),
+            })
     }
 
     /// Returns a [`ReturnStatement`] AST node if the next tokens represent a return statement.
@@ -119,17 +159,27 @@ impl ParserContext<'_> {
         let expression = self.parse_expression()?;
         self.expect(&Token::Semicolon)?;
         let span = start + expression.span();
-        Ok(ReturnStatement { span, expression })
+        Ok(ReturnStatement {
+            span,
+            expression,
+            id: self.node_builder.next_id(),
+        })
     }
 
     /// Returns a [`FinalizeStatement`] AST node if the next tokens represent a finalize statement.
     fn parse_finalize_statement(&mut self) -> Result<FinalizeStatement> {
-        self.expect(&Token::Async)?;
+        if !(self.legacy && self.check(&Token::Finalize)) {
+            self.expect(&Token::Async)?;
+        }
         let start = self.expect(&Token::Finalize)?;
         let (arguments, _, span) = self.parse_paren_comma_list(|p| p.parse_expression().map(Some))?;
         self.expect(&Token::Semicolon)?;
         let span = start + span;
-        Ok(FinalizeStatement { span, arguments })
+        Ok(FinalizeStatement {
+            span,
+            arguments,
+            id: self.node_builder.next_id(),
+        })
     }
 
     /// Returns a [`DecrementStatement`] AST node if the next tokens represent a decrement statement.
@@ -150,6 +200,7 @@ impl ParserContext<'_> {
             index,
             amount,
             span,
+            id: self.node_builder.next_id(),
         })
     }
 
@@ -171,6 +222,7 @@ impl ParserContext<'_> {
             index,
             amount,
             span,
+            id: self.node_builder.next_id(),
         })
     }
 
@@ -196,6 +248,46 @@ impl ParserContext<'_> {
             condition: expr,
             then: body,
             otherwise: next,
+            id: self.node_builder.next_id(),
+        })
+    }
+
+    /// Returns a [`MatchStatement`] AST node if the next tokens represent a match statement.
+    /// Each arm is a pattern followed by a block, e.g. `State::Idle => { ... }`, optionally followed by a comma.
+    fn parse_match_statement(&mut self) -> Result<MatchStatement> {
+        let start = self.expect(&Token::Match)?;
+        // Note that the expression is followed by the arms, which would otherwise be parsed as a struct construction.
+        self.disallow_struct_construction = true;
+        let expression = self.parse_conditional_expression()?;
+        self.disallow_struct_construction = false;
+
+        self.expect(&Token::LeftCurly)?;
+        let mut arms = Vec::new();
+        while !self.check(&Token::RightCurly) {
+            let pattern = if self.eat(&Token::Underscore) {
+                MatchPattern::Wildcard(self.prev_token.span)
+            } else {
+                let enum_ = self.expect_identifier()?;
+                self.expect(&Token::DoubleColon)?;
+                MatchPattern::Variant(enum_, self.expect_identifier()?)
+            };
+            self.expect(&Token::BigArrow)?;
+            let block = self.parse_block()?;
+            arms.push(MatchArm {
+                span: pattern.span() + block.span,
+                pattern,
+                block,
+                id: self.node_builder.next_id(),
+            });
+            self.eat(&Token::Comma);
+        }
+        let end = self.expect(&Token::RightCurly)?;
+
+        Ok(MatchStatement {
+            expression,
+            arms,
+            span: start + end,
+            id: self.node_builder.next_id(),
         })
     }
 
@@ -207,11 +299,15 @@ impl ParserContext<'_> {
         let type_ = self.parse_type()?;
         self.expect(&Token::In)?;
 
-        // Parse iteration range.
-        let start = self.parse_expression()?;
-        self.expect(&Token::DotDot)?;
+        // Parse iteration range, or the array to iterate over.
+        // Note that the array is followed by the loop body, which would otherwise be parsed as a struct construction.
         self.disallow_struct_construction = true;
-        let stop = self.parse_conditional_expression()?;
+        let start = self.parse_conditional_expression()?;
+        let inclusive = self.check(&Token::DotDotEq);
+        let stop = match self.eat_any(&[Token::DotDot, Token::DotDotEq]) {
+            true => Some(self.parse_conditional_expression()?),
+            false => None,
+        };
         self.disallow_struct_construction = false;
 
         let block = self.parse_block()?;
@@ -224,38 +320,81 @@ impl ParserContext<'_> {
             start_value: Default::default(),
             stop,
             stop_value: Default::default(),
-            inclusive: false,
+            inclusive,
             block,
+            id: self.node_builder.next_id(),
         })
     }
 
-    /// Returns a [`ConsoleStatement`] AST node if the next tokens represent a console statement.
-    fn parse_console_statement(&mut self) -> Result<ConsoleStatement> {
-        let keyword = self.expect(&Token::Console)?;
-        self.expect(&Token::Dot)?;
-        let identifier = self.expect_identifier()?;
-        let (span, function) = match identifier.name {
-            sym::assert => {
+    /// Returns an [`AssertStatement`] AST node if the next tokens represent an assert statement.
+    fn parse_assert_statement(&mut self) -> Result<AssertStatement> {
+        let keyword = self.expect_any(&[Token::Assert, Token::AssertEq, Token::AssertNeq])?;
+        let (span, variant) = match &self.prev_token.token {
+            Token::Assert => {
                 self.expect(&Token::LeftParen)?;
                 let expr = self.parse_expression()?;
                 self.expect(&Token::RightParen)?;
-                (keyword + expr.span(), ConsoleFunction::Assert(expr))
+                (expr.span(), AssertVariant::Assert(expr))
             }
-            sym::assert_eq => {
+            Token::AssertEq => {
                 self.expect(&Token::LeftParen)?;
                 let left = self.parse_expression()?;
                 self.expect(&Token::Comma)?;
                 let right = self.parse_expression()?;
                 self.expect(&Token::RightParen)?;
-                (left.span() + right.span(), ConsoleFunction::AssertEq(left, right))
+                (right.span(), AssertVariant::AssertEq(left, right))
             }
-            sym::assert_neq => {
+            Token::AssertNeq => {
                 self.expect(&Token::LeftParen)?;
                 let left = self.parse_expression()?;
                 self.expect(&Token::Comma)?;
                 let right = self.parse_expression()?;
                 self.expect(&Token::RightParen)?;
-                (left.span() + right.span(), ConsoleFunction::AssertNeq(left, right))
+                (right.span(), AssertVariant::AssertNeq(left, right))
+            }
+            _ => unreachable!("parse_assert_statement shouldn't produce this"),
+        };
+        self.expect(&Token::Semicolon)?;
+
+        Ok(AssertStatement {
+            span: keyword + span,
+            variant,
+            id: self.node_builder.next_id(),
+        })
+    }
+
+    /// Returns an [`AssertStatement`] AST node if the next tokens represent a console statement.
+    /// `console.assert`, `console.assert_eq`, and `console.assert_neq` are deprecated aliases of the assert statements.
+    fn parse_console_statement(&mut self) -> Result<AssertStatement> {
+        let keyword = self.expect(&Token::Console)?;
+        self.expect(&Token::Dot)?;
+        if matches!(self.token.token, Token::Assert | Token::AssertEq | Token::AssertNeq) {
+            let function_span = keyword + self.token.span;
+            if self.legacy {
+                self.rewrites.push((function_span, self.token.token.to_string()));
+            } else {
+                self.emit_warning(ParserWarning::console_assert_deprecated(
+                    &self.token.token,
+                    function_span,
+                ));
+            }
+            let statement = self.parse_assert_statement()?;
+            return Ok(AssertStatement {
+                span: keyword + statement.span,
+                variant: statement.variant,
+                id: self.node_builder.next_id(),
+            });
+        }
+        let identifier = self.expect_identifier()?;
+        let span = match identifier.name {
+            symbol if self.legacy => {
+                // Legacy functions such as `log` and `error` have no equivalent, so they are left as written.
+                let (_, _, span) = self.parse_paren_comma_list(|p| p.parse_expression().map(Some))?;
+                self.emit_warning(ParserWarning::could_not_migrate(
+                    format!("console.{symbol}"),
+                    keyword + span,
+                ));
+                span
             }
             symbol => {
                 // Not sure what it is, assume it's `log`.
@@ -264,19 +403,34 @@ impl ParserContext<'_> {
                     &["assert", "assert_eq", "assert_neq"],
                     identifier.span,
                 ));
-                (
-                    Default::default(),
-                    ConsoleFunction::Assert(Expression::Err(ErrExpression {
-                        span: Default::default(),
-                    })),
-                )
+                Default::default()
             }
         };
         self.expect(&Token::Semicolon)?;
 
-        Ok(ConsoleStatement {
+        Ok(AssertStatement {
             span: keyword + span,
-            function,
+            variant: AssertVariant::Assert(Expression::Err(ErrExpression {
+                span,
+                id: self.node_builder.next_id(),
+            })),
+            id: self.node_builder.next_id(),
+        })
+    }
+
+    /// Returns a [`CfgStatement`] AST node if the next tokens represent a statement annotated with `@cfg(..)`.
+    fn parse_cfg_statement(&mut self) -> Result<CfgStatement> {
+        let cfg = self.parse_annotation_with_arguments()?;
+        if cfg.identifier.name != sym::cfg {
+            self.emit_err(ParserError::invalid_statement_annotation(&cfg, cfg.span));
+        }
+        let statement = self.parse_statement()?;
+
+        Ok(CfgStatement {
+            span: cfg.span + statement.span(),
+            cfg,
+            statement: Box::new(statement),
+            id: self.node_builder.next_id(),
         })
     }
 
@@ -290,8 +444,41 @@ impl ParserContext<'_> {
             _ => unreachable!("parse_definition_statement_ shouldn't produce this"),
         };
 
-        // Parse variable name and type.
-        let (variable_name, type_) = self.parse_typed_ident()?;
+        // Parse variable name(s) and type.
+        let (place, type_) = match self.peek_is_left_par() {
+            // Parse a destructuring definition, e.g. `let (a, b): (u8, u8) = ...`.
+            true => {
+                let (elements, _, span) = self.parse_paren_comma_list(|p| {
+                    p.expect_identifier()
+                        .map(|identifier| Some(Expression::Identifier(identifier)))
+                })?;
+                let (type_, type_span) = match self.eat_definition_colon(decl_type)? {
+                    true => {
+                        let (types, _, type_span) =
+                            self.parse_paren_comma_list(|p| p.parse_type().map(|(type_, _)| Some(type_)))?;
+                        (Some(Tuple::try_new(types, type_span)?), type_span)
+                    }
+                    false => (None, span),
+                };
+                self.check_edition(Feature::Tuples, span + type_span);
+                (
+                    Expression::Tuple(TupleExpression {
+                        elements,
+                        span,
+                        id: self.node_builder.next_id(),
+                    }),
+                    type_,
+                )
+            }
+            false => {
+                let identifier = self.expect_identifier()?;
+                let type_ = match self.eat_definition_colon(decl_type)? {
+                    true => Some(self.parse_type()?.0),
+                    false => None,
+                };
+                (Expression::Identifier(identifier), type_)
+            }
+        };
 
         self.expect(&Token::Assign)?;
         let value = self.parse_expression()?;
@@ -300,9 +487,19 @@ impl ParserContext<'_> {
         Ok(DefinitionStatement {
             span: decl_span + value.span(),
             declaration_type: decl_type,
-            variable_name,
-            type_,
+            place,
+            type_: RefCell::new(type_),
             value,
+            id: self.node_builder.next_id(),
         })
     }
+
+    /// Eats the `:` that precedes the type of a definition, returning whether the type is annotated.
+    /// The type of a `let` binding may be omitted, in which case it is inferred from its value.
+    fn eat_definition_colon(&mut self, declaration_type: DeclarationType) -> Result<bool> {
+        match declaration_type {
+            DeclarationType::Let => Ok(self.eat(&Token::Colon)),
+            DeclarationType::Const => self.expect(&Token::Colon).map(|_| true),
+        }
+    }
 }
@@ -16,7 +16,9 @@
 
 use super::*;
 
-use leo_errors::Result;
+use leo_errors::{ParserError, Result};
+
+use std::num::NonZeroUsize;
 
 pub(super) const TYPE_TOKENS: &[Token] = &[
     Token::Address,
@@ -24,6 +26,7 @@ pub(super) const TYPE_TOKENS: &[Token] = &[
     Token::Field,
     Token::Group,
     Token::Scalar,
+    Token::Signature,
     Token::String,
     Token::I8,
     Token::I16,
@@ -66,18 +69,70 @@ impl ParserContext<'_> {
                 Token::Field => Type::Field,
                 Token::Group => Type::Group,
                 Token::Scalar => Type::Scalar,
-                Token::String => Type::String,
+                Token::Signature => Type::Signature,
+                Token::String => Type::String(None),
                 x => Type::Integer(Self::token_to_int_type(x).expect("invalid int type")),
             },
             span,
         ))
     }
 
+    /// Parses the const arguments of an instance of a generic struct, e.g. `<4, N>`.
+    /// Also returns the span of the parsed tokens.
+    pub(super) fn parse_const_arguments(&mut self) -> Result<(Vec<Size>, Span)> {
+        let start = self.expect(&Token::Lt)?;
+        let mut arguments = Vec::new();
+        loop {
+            arguments.push(self.parse_size()?);
+            if !self.eat(&Token::Comma) {
+                break;
+            }
+        }
+        let end = self.expect(&Token::Gt)?;
+        self.check_edition(Feature::Generics, start + end);
+        Ok((arguments, start + end))
+    }
+
+    /// Returns a [`(Type, Span)`] tuple of AST nodes if the next tokens represent an array type, e.g. `[u8; 4]`.
+    /// Also returns the span of the parsed tokens.
+    fn parse_array_type(&mut self) -> Result<(Type, Span)> {
+        let start = self.expect(&Token::LeftSquare)?;
+        let (element_type, _) = self.parse_type()?;
+        self.expect(&Token::Semicolon)?;
+        let length = self.parse_size()?;
+        let end = self.expect(&Token::RightSquare)?;
+
+        Ok((Type::Array(Box::new(element_type), length), start + end))
+    }
+
+    /// Returns a [`Size`] AST node if the next token is a positive integer literal or the name of a constant.
+    /// Sizes are the lengths of array types and the const arguments of generic structs.
+    fn parse_size(&mut self) -> Result<Size> {
+        if let Some(identifier) = self.eat_identifier() {
+            return Ok(Size::Constant(identifier));
+        }
+        let (length, span) = self.eat_integer()?;
+        match length.value.parse::<NonZeroUsize>() {
+            Ok(length) => Ok(Size::Literal(length)),
+            Err(_) => Err(ParserError::invalid_array_length(length, span).into()),
+        }
+    }
+
     /// Returns a [`(Type, Span)`] tuple of AST nodes if the next token represents a type.
     /// Also returns the span of the parsed token.
     pub fn parse_type(&mut self) -> Result<(Type, Span)> {
         if let Some(ident) = self.eat_identifier() {
+            if self.check(&Token::Lt) {
+                let (const_arguments, span) = self.parse_const_arguments()?;
+                let instance = StructInstance {
+                    identifier: ident,
+                    const_arguments,
+                };
+                return Ok((Type::StructInstance(instance), ident.span + span));
+            }
             Ok((Type::Identifier(ident), ident.span))
+        } else if self.check(&Token::LeftSquare) {
+            self.parse_array_type()
         } else {
             self.parse_primitive_type()
         }
@@ -15,7 +15,7 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{tokenizer, ParserContext, SpannedToken};
-use leo_ast::Statement;
+use leo_ast::{Formatter, NodeBuilder, Statement};
 use leo_errors::{emitter::Handler, LeoError};
 use leo_span::{
     source_map::FileName,
@@ -70,7 +70,8 @@ fn with_handler<T>(
     logic: impl FnOnce(&mut ParserContext<'_>) -> Result<T, LeoError>,
 ) -> Result<T, String> {
     let (handler, buf) = Handler::new_with_buf();
-    let mut tokens = ParserContext::new(&handler, tokens);
+    let node_builder = NodeBuilder::default();
+    let mut tokens = ParserContext::new(&handler, &node_builder, tokens);
     let parsed = handler
         .extend_if_error(logic(&mut tokens))
         .map_err(|_| buf.extract_errs().to_string())?;
@@ -93,6 +94,119 @@ fn yaml_or_fail<T: Serialize>(value: T) -> Value {
     serde_yaml::to_value(value).expect("serialization failed")
 }
 
+/// Checks that the source code that `format` renders a parsed AST node as parses back to the same node, up to spans,
+/// and is rendered the same again.
+/// The node is rendered both with the default layout and with a narrow one, so that lists are broken over lines.
+fn check_format_round_trip<T: Serialize>(
+    s: &SessionGlobals,
+    parsed: &T,
+    format: impl Fn(&Formatter, &T) -> String,
+    parse: impl Fn(&mut ParserContext<'_>) -> Result<T, LeoError>,
+) -> Result<(), String> {
+    for formatter in [Formatter::default(), Formatter::new(20, 2)] {
+        let formatted = format(&formatter, parsed);
+        let sf = s
+            .source_map
+            .new_source(&formatted, FileName::Custom("formatted".into()));
+        let tokens = tokenizer::tokenize(&sf.src, sf.start_pos).map_err(|err| err.to_string())?;
+        let reparsed = with_handler(tokens, &parse)
+            .map_err(|err| format!("the formatted source code does not parse:\n{formatted}\n{err}"))?;
+        if without_spans(parsed) != without_spans(&reparsed) {
+            return Err(format!(
+                "the formatted source code parses to a different AST:\n{formatted}"
+            ));
+        }
+        if format(&formatter, &reparsed) != formatted {
+            return Err(format!("formatting the source code again changes it:\n{formatted}"));
+        }
+    }
+    Ok(())
+}
+
+/// Formats the source code of a program with the default formatter.
+fn format_source(s: &SessionGlobals, source: &str) -> Result<String, String> {
+    let sf = s.source_map.new_source(source, FileName::Custom("formatted".into()));
+    let (handler, buf) = Handler::new_with_buf();
+    handler
+        .extend_if_error(crate::format(&handler, &sf.src, sf.start_pos, &Formatter::default()))
+        .map_err(|_| buf.extract_errs().to_string())
+}
+
+/// Checks that formatting the source code of a program keeps its comments, and that formatting it again leaves it unchanged.
+fn check_format_keeps_comments(s: &SessionGlobals, source: &str) -> Result<(), String> {
+    let format = |source: &str| format_source(s, source);
+    let comments = |source: &str| {
+        let mut comments = tokenizer::tokenize(source, Default::default())
+            .expect("the source code was tokenized before")
+            .into_iter()
+            .filter_map(|token| match token.token {
+                Token::CommentLine(text) | Token::CommentBlock(text) => Some(text.trim_end().to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        comments.sort();
+        comments
+    };
+
+    let formatted = format(source)?;
+    if comments(source) != comments(&formatted) {
+        return Err(format!("formatting the source code changes its comments:\n{formatted}"));
+    }
+    if format(&formatted)? != formatted {
+        return Err(format!("formatting the source code again changes it:\n{formatted}"));
+    }
+    Ok(())
+}
+
+/// Serializes an AST node to JSON without its spans and node ids, including those of identifiers and program ids,
+/// which are serialized as strings of JSON.
+/// The ids are removed because a reparsed node is numbered differently when its source code has a different shape.
+fn without_spans<T: Serialize>(node: &T) -> serde_json::Value {
+    fn remove_spans(value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                map.remove("span");
+                map.remove("id");
+                *map = std::mem::take(map)
+                    .into_iter()
+                    .map(|(key, mut value)| {
+                        remove_spans(&mut value);
+                        (remove_spans_from_string(key), value)
+                    })
+                    .collect();
+            }
+            serde_json::Value::Array(values) => {
+                // Tuple-like enum variants serialize their span as a `span` mapping followed by their node id.
+                let mut after_span = false;
+                values.retain(|value| {
+                    let is_id = after_span && value.is_u64();
+                    after_span = value
+                        .as_object()
+                        .map_or(false, |map| map.len() == 1 && map.contains_key("span"));
+                    !is_id
+                });
+                values.iter_mut().for_each(remove_spans)
+            }
+            serde_json::Value::String(string) => *string = remove_spans_from_string(std::mem::take(string)),
+            _ => (),
+        }
+    }
+
+    fn remove_spans_from_string(string: String) -> String {
+        match serde_json::from_str(&string) {
+            Ok(mut value @ (serde_json::Value::Object(_) | serde_json::Value::String(_))) => {
+                remove_spans(&mut value);
+                value.to_string()
+            }
+            _ => string,
+        }
+    }
+
+    let mut json = serde_json::to_value(node).expect("failed to convert to json value");
+    remove_spans(&mut json);
+    json
+}
+
 struct ParseExpressionNamespace;
 
 impl Namespace for ParseExpressionNamespace {
@@ -106,7 +220,9 @@ impl Namespace for ParseExpressionNamespace {
             if all_are_comments(&tokenizer) {
                 return Ok(yaml_or_fail(""));
             }
-            with_handler(tokenizer, |p| p.parse_expression()).map(yaml_or_fail)
+            let expression = with_handler(tokenizer, |p| p.parse_expression())?;
+            check_format_round_trip(s, &expression, Formatter::format_expression, |p| p.parse_expression())?;
+            Ok(yaml_or_fail(expression))
         })
     }
 }
@@ -122,9 +238,11 @@ impl Namespace for ParseStatementNamespace {
         create_session_if_not_set_then(|s| {
             let tokenizer = tokenize(test, s)?;
             if all_are_comments(&tokenizer) {
-                return Ok(yaml_or_fail(Statement::dummy(Span::default())));
+                return Ok(yaml_or_fail(Statement::dummy(Span::default(), Default::default())));
             }
-            with_handler(tokenizer, |p| p.parse_statement()).map(yaml_or_fail)
+            let statement = with_handler(tokenizer, |p| p.parse_statement())?;
+            check_format_round_trip(s, &statement, Formatter::format_statement, |p| p.parse_statement())?;
+            Ok(yaml_or_fail(statement))
         })
     }
 }
@@ -137,7 +255,13 @@ impl Namespace for ParseNamespace {
     }
 
     fn run_test(&self, test: Test) -> Result<Value, String> {
-        create_session_if_not_set_then(|s| with_handler(tokenize(test, s)?, |p| p.parse_program()).map(yaml_or_fail))
+        create_session_if_not_set_then(|s| {
+            let source = test.content.clone();
+            let program = with_handler(tokenize(test, s)?, |p| p.parse_program())?;
+            check_format_round_trip(s, &program, Formatter::format, |p| p.parse_program())?;
+            check_format_keeps_comments(s, &source)?;
+            Ok(yaml_or_fail(program))
+        })
     }
 }
 
@@ -218,6 +342,50 @@ impl Namespace for InputNamespace {
     }
 }
 
+struct MigrateNamespace;
+
+#[derive(Serialize)]
+struct MigrateOutput {
+    source: String,
+    warnings: String,
+}
+
+impl Namespace for MigrateNamespace {
+    fn parse_type(&self) -> ParseType {
+        ParseType::Whole
+    }
+
+    fn run_test(&self, test: Test) -> Result<Value, String> {
+        create_session_if_not_set_then(|s| {
+            let sf = s.source_map.new_source(&test.content, FileName::Custom("test".into()));
+            let (handler, buf) = Handler::new_with_buf();
+            let source = handler
+                .extend_if_error(crate::migrate(&handler, &sf.src, sf.start_pos))
+                .map_err(|_| buf.extract_errs().to_string())?;
+            let warnings = buf.extract_warnings().to_string();
+            Ok(yaml_or_fail(MigrateOutput { source, warnings }))
+        })
+    }
+}
+
+struct FormatNamespace;
+
+impl Namespace for FormatNamespace {
+    fn parse_type(&self) -> ParseType {
+        ParseType::Whole
+    }
+
+    fn run_test(&self, test: Test) -> Result<Value, String> {
+        create_session_if_not_set_then(|s| {
+            let formatted = format_source(s, &test.content)?;
+            if format_source(s, &formatted)? != formatted {
+                return Err(format!("formatting the source code again changes it:\n{formatted}"));
+            }
+            Ok(yaml_or_fail(formatted))
+        })
+    }
+}
+
 struct TestRunner;
 
 impl Runner for TestRunner {
@@ -229,6 +397,8 @@ impl Runner for TestRunner {
             "Serialize" => Box::new(SerializeNamespace),
             "Input" => Box::new(InputNamespace),
             "Token" => Box::new(TokenNamespace),
+            "Migrate" => Box::new(MigrateNamespace),
+            "Format" => Box::new(FormatNamespace),
             _ => return None,
         })
     }
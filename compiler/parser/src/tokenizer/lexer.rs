@@ -297,7 +297,19 @@ impl Token {
             '+' => return match_two(&mut input, Token::Add, '=', Token::AddAssign),
             ',' => return match_one(&mut input, Token::Comma),
             '-' => return match_three(&mut input, Token::Sub, '=', Token::SubAssign, '>', Token::Arrow),
-            '.' => return match_two(&mut input, Token::Dot, '.', Token::DotDot),
+            '.' => {
+                input.next();
+                if input.next_if_eq(&'.').is_some() {
+                    if input.next_if_eq(&'=').is_some() {
+                        // '..='
+                        return Ok((3, Token::DotDotEq));
+                    }
+                    // '..'
+                    return Ok((2, Token::DotDot));
+                }
+                // '.'
+                return Ok((1, Token::Dot));
+            }
             '/' => {
                 input.next();
                 if input.next_if_eq(&'/').is_some() {
@@ -394,7 +406,11 @@ impl Token {
                 match &*identifier {
                     x if x.starts_with("aleo1") => Token::AddressLit(identifier),
                     "address" => Token::Address,
+                    "assert" => Token::Assert,
+                    "assert_eq" => Token::AssertEq,
+                    "assert_neq" => Token::AssertNeq,
                     "async" => Token::Async,
+                    "block" => Token::Block,
                     "bool" => Token::Bool,
                     "circuit" => Token::Circuit,
                     "console" => Token::Console,
@@ -402,6 +418,7 @@ impl Token {
                     "constant" => Token::Constant,
                     "decrement" => Token::Decrement,
                     "else" => Token::Else,
+                    "enum" => Token::Enum,
                     "false" => Token::False,
                     "field" => Token::Field,
                     "finalize" => Token::Finalize,
@@ -416,16 +433,19 @@ impl Token {
                     "if" => Token::If,
                     "in" => Token::In,
                     "increment" => Token::Increment,
+                    "inline" => Token::Inline,
                     "import" => Token::Import,
                     "let" => Token::Let,
                     "leo" => Token::Leo,
                     "mapping" => Token::Mapping,
+                    "match" => Token::Match,
                     "program" => Token::Program,
                     "public" => Token::Public,
                     "record" => Token::Record,
                     "return" => Token::Return,
                     "scalar" => Token::Scalar,
                     "self" => Token::SelfLower,
+                    "signature" => Token::Signature,
                     "string" => Token::String,
                     "struct" => Token::Struct,
                     "transition" => Token::Transition,
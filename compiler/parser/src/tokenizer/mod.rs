@@ -83,6 +83,9 @@ mod tests {
     test_ident
     12345
     address
+    assert
+    assert_eq
+    assert_neq
     async
     bool
     const
@@ -133,6 +136,7 @@ mod tests {
     _
     .
     ..
+    ..=
     /
     :
     ;
@@ -161,7 +165,7 @@ mod tests {
 
             assert_eq!(
                 output,
-                r#""test" "test{}test" "test{}" "{}test" "test{" "test}" "test{test" "test}test" "te{{}}" test_ident 12345 address async bool const else false field finalize for function group i128 i64 i32 i16 i8 if in input let mut program return scalar self string struct test transition true u128 u64 u32 u16 u8 console ! != && ( ) * ** + , - -> => _ . .. / : ; < <= = == > >= [ ] { { } } || ? @ // test
+                r#""test" "test{}test" "test{}" "{}test" "test{" "test}" "test{test" "test}test" "te{{}}" test_ident 12345 address assert assert_eq assert_neq async bool const else false field finalize for function group i128 i64 i32 i16 i8 if in input let mut program return scalar self string struct test transition true u128 u64 u32 u16 u8 console ! != && ( ) * ** + , - -> => _ . .. ..= / : ; < <= = == > >= [ ] { { } } || ? @ // test
  /* test */ // "#
             );
         });
@@ -195,4 +199,38 @@ ppp            test
             }
         })
     }
+
+    #[test]
+    fn test_multibyte_spans() {
+        create_session_if_not_set_then(|s| {
+            let raw = "// é\nlet x = \"ü\";\n// 𝔸 test";
+
+            let sm = &s.source_map;
+            let sf = sm.new_source(raw, FileName::Custom("test".into()));
+            let tokens = tokenize(&sf.src, sf.start_pos).unwrap();
+            for token in tokens.iter() {
+                assert_eq!(token.token.to_string(), sm.contents_of_span(token.span).unwrap());
+            }
+
+            // Columns count characters, while positions count bytes.
+            let x = tokens.iter().find(|token| token.token.to_string() == "x").unwrap();
+            assert_eq!(sm.byte_pos_to_line_col(x.span.lo), Some((2, 5)));
+            assert_eq!(sf.line_col_to_byte_pos(2, 5), Some(x.span.lo));
+            let comment = tokens.last().unwrap();
+            assert_eq!(sf.byte_pos_to_line_col(comment.span.hi), Some((3, 10)));
+            assert_eq!(sf.line_col_to_byte_pos(3, 10), Some(comment.span.hi));
+            assert_eq!(sf.line_col_to_byte_pos(3, 100), None);
+
+            // Merging and slicing spans.
+            let merged = Span::merge_all(tokens.iter().map(|token| token.span));
+            assert_eq!(merged, Span::new(sf.start_pos, sf.end_pos));
+            assert!(merged.contains(x.span));
+            assert_eq!(x.span.merge(Span::dummy()), x.span);
+            assert_eq!(
+                merged.sub_span(3, 5),
+                Some(Span::new(sf.start_pos + BytePos(3), sf.start_pos + BytePos(5)))
+            );
+            assert_eq!(merged.sub_span(0, merged.len() + 1), None);
+        })
+    }
 }
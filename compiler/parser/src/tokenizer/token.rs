@@ -72,6 +72,7 @@ pub enum Token {
     Comma,
     Dot,
     DotDot,
+    DotDotEq,
     Semicolon,
     Colon,
     DoubleColon,
@@ -94,6 +95,7 @@ pub enum Token {
     Field,
     Group,
     Scalar,
+    Signature,
     String,
     I8,
     I16,
@@ -108,7 +110,11 @@ pub enum Token {
     Record,
 
     // Regular Keywords
+    Assert,
+    AssertEq,
+    AssertNeq,
     Async,
+    Block,
     Circuit,
     Console,
     // Const variable and a const function.
@@ -117,6 +123,7 @@ pub enum Token {
     Constant,
     Decrement,
     Else,
+    Enum,
     Finalize,
     For,
     Function,
@@ -124,8 +131,10 @@ pub enum Token {
     Import,
     In,
     Increment,
+    Inline,
     Let,
     Mapping,
+    Match,
     Program,
     // For public inputs.
     Public,
@@ -147,13 +156,18 @@ pub enum Token {
 /// because true and false are also boolean literals, which are different tokens from keywords
 pub const KEYWORD_TOKENS: &[Token] = &[
     Token::Address,
+    Token::Assert,
+    Token::AssertEq,
+    Token::AssertNeq,
     Token::Async,
+    Token::Block,
     Token::Bool,
     Token::Console,
     Token::Const,
     Token::Constant,
     Token::Decrement,
     Token::Else,
+    Token::Enum,
     Token::False,
     Token::Field,
     Token::Finalize,
@@ -169,14 +183,17 @@ pub const KEYWORD_TOKENS: &[Token] = &[
     Token::Import,
     Token::In,
     Token::Increment,
+    Token::Inline,
     Token::Let,
     Token::Mapping,
+    Token::Match,
     Token::Program,
     Token::Public,
     Token::Record,
     Token::Return,
     Token::SelfLower,
     Token::Scalar,
+    Token::Signature,
     Token::Static,
     Token::String,
     Token::Struct,
@@ -199,13 +216,18 @@ impl Token {
     pub fn keyword_to_symbol(&self) -> Option<Symbol> {
         Some(match self {
             Token::Address => sym::address,
+            Token::Assert => sym::assert,
+            Token::AssertEq => sym::assert_eq,
+            Token::AssertNeq => sym::assert_neq,
             Token::Async => sym::Async,
+            Token::Block => sym::block,
             Token::Bool => sym::bool,
             Token::Console => sym::console,
             Token::Const => sym::Const,
             Token::Constant => sym::Constant,
             Token::Decrement => sym::decrement,
             Token::Else => sym::Else,
+            Token::Enum => sym::Enum,
             Token::False => sym::False,
             Token::Field => sym::field,
             Token::Finalize => sym::finalize,
@@ -220,16 +242,19 @@ impl Token {
             Token::If => sym::If,
             Token::In => sym::In,
             Token::Increment => sym::increment,
+            Token::Inline => sym::inline,
             Token::Import => sym::import,
             Token::Let => sym::Let,
             Token::Leo => sym::leo,
             Token::Mapping => sym::mapping,
+            Token::Match => sym::Match,
             Token::Program => sym::program,
             Token::Public => sym::Public,
             Token::Record => sym::record,
             Token::Return => sym::Return,
             Token::Scalar => sym::scalar,
             Token::SelfLower => sym::SelfLower,
+            Token::Signature => sym::signature,
             Token::Static => sym::Static,
             Token::String => sym::string,
             Token::Struct => sym::Struct,
@@ -296,6 +321,7 @@ impl fmt::Display for Token {
             Comma => write!(f, ","),
             Dot => write!(f, "."),
             DotDot => write!(f, ".."),
+            DotDotEq => write!(f, "..="),
             Semicolon => write!(f, ";"),
             Colon => write!(f, ":"),
             DoubleColon => write!(f, "::"),
@@ -316,6 +342,7 @@ impl fmt::Display for Token {
             Field => write!(f, "field"),
             Group => write!(f, "group"),
             Scalar => write!(f, "scalar"),
+            Signature => write!(f, "signature"),
             String => write!(f, "string"),
             I8 => write!(f, "i8"),
             I16 => write!(f, "i16"),
@@ -329,13 +356,18 @@ impl fmt::Display for Token {
             U128 => write!(f, "u128"),
             Record => write!(f, "record"),
 
+            Assert => write!(f, "assert"),
+            AssertEq => write!(f, "assert_eq"),
+            AssertNeq => write!(f, "assert_neq"),
             Async => write!(f, "async"),
+            Block => write!(f, "block"),
             Circuit => write!(f, "circuit"),
             Console => write!(f, "console"),
             Const => write!(f, "const"),
             Constant => write!(f, "constant"),
             Decrement => write!(f, "decrement"),
             Else => write!(f, "else"),
+            Enum => write!(f, "enum"),
             Finalize => write!(f, "finalize"),
             For => write!(f, "for"),
             Function => write!(f, "function"),
@@ -343,8 +375,10 @@ impl fmt::Display for Token {
             Import => write!(f, "import"),
             In => write!(f, "in"),
             Increment => write!(f, "increment"),
+            Inline => write!(f, "inline"),
             Let => write!(f, "let"),
             Mapping => write!(f, "mapping"),
+            Match => write!(f, "match"),
             Program => write!(f, "program"),
             Public => write!(f, "public"),
             Return => write!(f, "return"),
@@ -365,6 +399,8 @@ pub enum Delimiter {
     Parenthesis,
     /// `{ ... }`
     Brace,
+    /// `[ ... ]`
+    Bracket,
 }
 
 impl Delimiter {
@@ -373,6 +409,7 @@ impl Delimiter {
         match self {
             Self::Parenthesis => (Token::LeftParen, Token::RightParen),
             Self::Brace => (Token::LeftCurly, Token::RightCurly),
+            Self::Bracket => (Token::LeftSquare, Token::RightSquare),
         }
     }
 }
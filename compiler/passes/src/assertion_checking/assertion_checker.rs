@@ -0,0 +1,165 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{bounds, Execution, Stop, Value};
+
+use leo_ast::{Block, Type};
+use leo_errors::{emitter::Handler, TypeCheckerWarning};
+use leo_span::{Span, Symbol};
+
+use indexmap::{IndexMap, IndexSet};
+use itertools::Itertools;
+
+/// The largest number of inputs that a function is executed on.
+const MAX_EXECUTIONS: u128 = 1 << 16;
+
+pub struct AssertionChecker<'a> {
+    /// The error handler.
+    pub(crate) handler: &'a Handler,
+    /// The spans of the assertions in the function being checked.
+    pub(crate) assertions: IndexSet<Span>,
+    /// The literals in the function being checked.
+    pub(crate) constants: IndexSet<Value>,
+    /// The outcome of checking each function.
+    pub(crate) decisions: Vec<String>,
+}
+
+impl<'a> AssertionChecker<'a> {
+    /// Returns a new assertion checker given an error handler.
+    pub fn new(handler: &'a Handler) -> Self {
+        Self {
+            handler,
+            assertions: IndexSet::new(),
+            constants: IndexSet::new(),
+            decisions: Vec::new(),
+        }
+    }
+
+    /// Returns the number of values of `type_`, or `None` if the checker cannot compute with them.
+    fn size(type_: &Type) -> Option<u128> {
+        match type_ {
+            Type::Boolean => Some(2),
+            Type::Integer(type_) => {
+                let (min, max) = bounds(*type_);
+                Some(max.abs_diff(min).saturating_add(1))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the values of `type_` that are tried as inputs, which are all of its values if `exhaustive` is set.
+    fn domain(&self, type_: &Type, exhaustive: bool) -> Vec<Value> {
+        let type_ = match type_ {
+            Type::Integer(type_) => *type_,
+            _ => return vec![Value::Boolean(false), Value::Boolean(true)],
+        };
+        let (min, max) = bounds(type_);
+        if exhaustive {
+            return (min..=max).map(|value| Value::Integer(type_, value)).collect();
+        }
+
+        // Boundary values, and the values adjacent to the constants of the function, are most likely to violate an assertion.
+        let constants = self.constants.iter().filter_map(|constant| match constant {
+            Value::Integer(constant_type, value) if *constant_type == type_ => Some(*value),
+            _ => None,
+        });
+        [min, max, -1, 0, 1, 2]
+            .into_iter()
+            .chain(constants)
+            .flat_map(|value| [value.checked_sub(1), Some(value), value.checked_add(1)])
+            .flatten()
+            .filter(|value| min <= *value && *value <= max)
+            .sorted()
+            .dedup()
+            .map(|value| Value::Integer(type_, value))
+            .collect()
+    }
+
+    /// Executes `block` on combinations of values of the `inputs`, and warns about each assertion that fails on one of them.
+    /// If the inputs have few enough values, every combination is tried, which proves that the remaining assertions hold.
+    pub(crate) fn check(&mut self, function: Symbol, inputs: &[(Symbol, &Type)], block: &Block) {
+        let sizes: Option<Vec<u128>> = inputs.iter().map(|(_, type_)| Self::size(type_)).collect();
+        let sizes = match sizes {
+            Some(sizes) => sizes,
+            None => {
+                self.decisions.push(format!(
+                    "`{function}`: not checked, since some of its inputs are not booleans or integers"
+                ));
+                return;
+            }
+        };
+        let exhaustive = sizes
+            .iter()
+            .try_fold(1u128, |total, size| total.checked_mul(*size))
+            .map_or(false, |combinations| combinations <= MAX_EXECUTIONS);
+        let domains: Vec<Vec<Value>> = inputs.iter().map(|(_, type_)| self.domain(type_, exhaustive)).collect();
+
+        let mut failures: IndexMap<Span, Vec<Value>> = IndexMap::new();
+        let mut supported = true;
+        let mut executions = 0;
+        let mut indices = vec![0; domains.len()];
+        loop {
+            let values: Vec<Value> = indices
+                .iter()
+                .zip(&domains)
+                .map(|(index, values)| values[*index])
+                .collect();
+            let mut execution = Execution::default();
+            execution
+                .values
+                .extend(inputs.iter().map(|(name, _)| *name).zip(values.iter().copied()));
+            match execution.execute_block(block) {
+                Err(Stop::Failed(span)) => {
+                    failures.entry(span).or_insert(values);
+                }
+                Err(Stop::Unknown) => supported = false,
+                Ok(()) | Err(Stop::Return | Stop::Halt) => {}
+            }
+            executions += 1;
+
+            // Advance to the next combination of values, as if counting with a digit per input.
+            let next = indices.iter_mut().zip(&domains).rev().any(|(index, values)| {
+                *index = (*index + 1) % values.len();
+                *index != 0
+            });
+            if !next || executions >= MAX_EXECUTIONS {
+                break;
+            }
+        }
+
+        for (span, values) in &failures {
+            let values = inputs
+                .iter()
+                .zip(values)
+                .map(|((name, _), value)| format!("{name} = {value}"))
+                .join(", ");
+            self.handler
+                .emit_warning(TypeCheckerWarning::assertion_can_fail(values, *span).into());
+        }
+
+        let assertions = self.assertions.len();
+        self.decisions.push(match exhaustive && supported {
+            true => format!(
+                "`{function}`: {} of {assertions} assertions hold for all {executions} inputs",
+                assertions - failures.len()
+            ),
+            false => format!(
+                "`{function}`: {} of {assertions} assertions fail on one of {executions} inputs",
+                failures.len()
+            ),
+        });
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{AssertionChecker, Value};
+
+use leo_ast::{
+    AssertStatement, AssertVariant, CallType, ExpressionVisitor, Function, Input, Literal, Program, ProgramVisitor,
+    StatementVisitor,
+};
+
+impl<'a> ExpressionVisitor<'a> for AssertionChecker<'a> {
+    type AdditionalInput = ();
+    type Output = ();
+
+    fn visit_literal(&mut self, input: &'a Literal, _additional: &Self::AdditionalInput) -> Self::Output {
+        if let Ok(value) = Value::literal(input) {
+            self.constants.insert(value);
+        }
+    }
+}
+
+impl<'a> StatementVisitor<'a> for AssertionChecker<'a> {
+    fn visit_assert(&mut self, input: &'a AssertStatement) {
+        self.assertions.insert(input.span);
+        match &input.variant {
+            AssertVariant::Assert(condition) => self.visit_expression(condition, &()),
+            AssertVariant::AssertEq(left, right) | AssertVariant::AssertNeq(left, right) => {
+                self.visit_expression(left, &());
+                self.visit_expression(right, &());
+            }
+        }
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for AssertionChecker<'a> {
+    /// Checks the assertions of each transition.
+    fn visit_function(&mut self, input: &'a Function) {
+        if !matches!(input.call_type, CallType::Transition) {
+            return;
+        }
+
+        self.assertions.clear();
+        self.constants.clear();
+        self.visit_block(&input.block);
+        if self.assertions.is_empty() {
+            return;
+        }
+
+        let inputs: Option<Vec<_>> = input
+            .input
+            .iter()
+            .map(|input| match input {
+                Input::Internal(input) => Some((input.identifier.name, &input.type_)),
+                Input::External(_) => None,
+            })
+            .collect();
+        match inputs {
+            Some(inputs) => self.check(input.identifier.name, &inputs, &input.block),
+            None => self.decisions.push(format!(
+                "`{}`: not checked, since some of its inputs are records of other programs",
+                input.identifier
+            )),
+        }
+    }
+
+    /// Imported programs are checked when they are compiled.
+    fn visit_import(&mut self, _input: &'a Program) {}
+}
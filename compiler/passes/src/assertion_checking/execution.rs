@@ -0,0 +1,309 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Interval;
+
+use leo_ast::{AssertVariant, BinaryOperation, Block, Expression, IntegerType, Literal, Statement, UnaryOperation};
+use leo_span::{Span, Symbol};
+
+use indexmap::IndexMap;
+use std::fmt;
+
+/// A value that the assertion checker can compute with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Value {
+    Boolean(bool),
+    /// An integer of the given type, whose value is tracked as an `i128`.
+    /// Note that the upper half of the `u128` range cannot be represented.
+    Integer(IntegerType, i128),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Boolean(value) => write!(f, "{}", value),
+            Self::Integer(type_, value) => write!(f, "{}{}", value, type_),
+        }
+    }
+}
+
+/// The reason that an execution stopped before reaching the end of the function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stop {
+    /// The function returned.
+    Return,
+    /// The execution halted, for example due to an overflow.
+    Halt,
+    /// The assertion at the given span failed.
+    Failed(Span),
+    /// The execution reached an expression or statement that the checker does not support.
+    Unknown,
+}
+
+/// Returns the smallest and largest values of `type_`.
+/// Note that the largest `u128` is approximated by the largest `i128`.
+pub fn bounds(type_: IntegerType) -> (i128, i128) {
+    Interval::full(type_).map_or((0, i128::MAX), |interval| (interval.min, interval.max))
+}
+
+/// Returns the number of bits in a value of `type_`.
+fn bits(type_: IntegerType) -> u32 {
+    use IntegerType::*;
+    match type_ {
+        U8 | I8 => 8,
+        U16 | I16 => 16,
+        U32 | I32 => 32,
+        U64 | I64 => 64,
+        U128 | I128 => 128,
+    }
+}
+
+/// Returns `value` as an integer of `type_`, halting if it is out of range.
+/// A value that cannot be computed as an `i128` is unknown for `u128`s, and an overflow for `i128`s.
+fn checked(type_: IntegerType, value: Option<i128>) -> Result<Value, Stop> {
+    let (min, max) = bounds(type_);
+    match value {
+        Some(value) if min <= value && value <= max => Ok(Value::Integer(type_, value)),
+        None if type_ == IntegerType::U128 => Err(Stop::Unknown),
+        _ => Err(Stop::Halt),
+    }
+}
+
+/// Returns `value` wrapped into the range of `type_`.
+fn wrapped(type_: IntegerType, value: Option<i128>) -> Result<Value, Stop> {
+    match (bits(type_), value) {
+        (128, _) | (_, None) => Err(Stop::Unknown),
+        (bits, Some(value)) => {
+            let (min, _) = bounds(type_);
+            Ok(Value::Integer(type_, (value - min).rem_euclid(1 << bits) + min))
+        }
+    }
+}
+
+/// Returns `base` raised to `exponent`, wrapped into the range of `type_`.
+fn wrapping_pow(type_: IntegerType, base: i128, mut exponent: i128) -> Result<Value, Stop> {
+    let mut result: i128 = 1;
+    let mut power = base;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = wrapped(type_, result.checked_mul(power))?.integer()?;
+        }
+        power = wrapped(type_, power.checked_mul(power))?.integer()?;
+        exponent >>= 1;
+    }
+    wrapped(type_, Some(result))
+}
+
+impl Value {
+    /// Returns the value of `literal`.
+    pub fn literal(literal: &Literal) -> Result<Self, Stop> {
+        let (type_, value) = match literal {
+            Literal::Boolean(value, _, _) => return Ok(Self::Boolean(*value)),
+            Literal::Integer(type_, value, _, _) => (*type_, value),
+            Literal::Unsuffixed(value, type_, _, _) => (type_.get().ok_or(Stop::Unknown)?, value),
+            _ => return Err(Stop::Unknown),
+        };
+        checked(type_, value.replace('_', "").parse().ok())
+    }
+
+    fn boolean(self) -> Result<bool, Stop> {
+        match self {
+            Self::Boolean(value) => Ok(value),
+            Self::Integer(..) => Err(Stop::Unknown),
+        }
+    }
+
+    fn integer(self) -> Result<i128, Stop> {
+        match self {
+            Self::Integer(_, value) => Ok(value),
+            Self::Boolean(_) => Err(Stop::Unknown),
+        }
+    }
+
+    /// Returns the result of applying `op` to `self` and `other`.
+    pub fn binary(self, op: BinaryOperation, other: Self) -> Result<Self, Stop> {
+        use BinaryOperation::*;
+        match (self, other) {
+            (Self::Boolean(left), Self::Boolean(right)) => Ok(Self::Boolean(match op {
+                And => left && right,
+                Or => left || right,
+                Nand => !(left && right),
+                Nor => !(left || right),
+                Xor | Neq => left != right,
+                Eq => left == right,
+                _ => return Err(Stop::Unknown),
+            })),
+            (Self::Integer(type_, left), Self::Integer(_, right)) => match op {
+                Eq => Ok(Self::Boolean(left == right)),
+                Neq => Ok(Self::Boolean(left != right)),
+                Lt => Ok(Self::Boolean(left < right)),
+                Lte => Ok(Self::Boolean(left <= right)),
+                Gt => Ok(Self::Boolean(left > right)),
+                Gte => Ok(Self::Boolean(left >= right)),
+                BitwiseAnd => Ok(Self::Integer(type_, left & right)),
+                BitwiseOr => Ok(Self::Integer(type_, left | right)),
+                Xor => Ok(Self::Integer(type_, left ^ right)),
+                Add => checked(type_, left.checked_add(right)),
+                AddWrapped => wrapped(type_, left.checked_add(right)),
+                Sub => checked(type_, left.checked_sub(right)),
+                SubWrapped => wrapped(type_, left.checked_sub(right)),
+                Mul => checked(type_, left.checked_mul(right)),
+                MulWrapped => wrapped(type_, left.checked_mul(right)),
+                Div | Rem | Mod | DivWrapped | RemWrapped if right == 0 => Err(Stop::Halt),
+                Div => checked(type_, left.checked_div(right)),
+                DivWrapped => wrapped(type_, left.checked_div(right)),
+                Rem => checked(type_, left.checked_rem(right)),
+                RemWrapped => wrapped(type_, left.checked_rem(right)),
+                Mod => checked(type_, left.checked_rem_euclid(right)),
+                Pow => checked(
+                    type_,
+                    u32::try_from(right).ok().and_then(|right| left.checked_pow(right)),
+                ),
+                PowWrapped => wrapping_pow(type_, left, right),
+                Shr if right >= bits(type_) as i128 => Err(Stop::Halt),
+                Shr => Ok(Self::Integer(type_, left >> right)),
+                ShrWrapped => Ok(Self::Integer(type_, left >> right.rem_euclid(bits(type_) as i128))),
+                ShlWrapped => wrapped(type_, left.checked_shl(right.rem_euclid(bits(type_) as i128) as u32)),
+                // Note that `left << right` may exceed an `i128` even if it is truncated to the type.
+                _ => Err(Stop::Unknown),
+            },
+            _ => Err(Stop::Unknown),
+        }
+    }
+
+    /// Returns the result of applying `op` to `self`.
+    pub fn unary(self, op: UnaryOperation) -> Result<Self, Stop> {
+        match (self, op) {
+            (Self::Boolean(value), UnaryOperation::Not) => Ok(Self::Boolean(!value)),
+            (Self::Integer(type_, value), UnaryOperation::Not) => wrapped(type_, Some(!value)),
+            (Self::Integer(type_, value), UnaryOperation::Abs) => checked(type_, value.checked_abs()),
+            (Self::Integer(type_, value), UnaryOperation::AbsWrapped) => wrapped(type_, value.checked_abs()),
+            (Self::Integer(type_, value), UnaryOperation::Negate) => checked(type_, value.checked_neg()),
+            _ => Err(Stop::Unknown),
+        }
+    }
+}
+
+/// A concrete execution of the body of a function.
+#[derive(Default)]
+pub struct Execution {
+    /// The value of each variable assigned so far.
+    pub(crate) values: IndexMap<Symbol, Value>,
+}
+
+impl Execution {
+    /// Returns the value of `expression`.
+    pub fn evaluate(&self, expression: &Expression) -> Result<Value, Stop> {
+        match expression {
+            Expression::Literal(literal) => Value::literal(literal),
+            Expression::Identifier(identifier) => self.values.get(&identifier.name).copied().ok_or(Stop::Unknown),
+            Expression::Binary(binary) => {
+                let left = self.evaluate(&binary.left)?;
+                let right = self.evaluate(&binary.right)?;
+                left.binary(binary.op, right)
+            }
+            Expression::Unary(unary) => self.evaluate(&unary.receiver)?.unary(unary.op),
+            Expression::Ternary(ternary) => match self.evaluate(&ternary.condition)?.boolean()? {
+                true => self.evaluate(&ternary.if_true),
+                false => self.evaluate(&ternary.if_false),
+            },
+            _ => Err(Stop::Unknown),
+        }
+    }
+
+    /// Executes the statements of `block` in order.
+    pub fn execute_block(&mut self, block: &Block) -> Result<(), Stop> {
+        block
+            .statements
+            .iter()
+            .try_for_each(|statement| self.execute_statement(statement))
+    }
+
+    fn execute_statement(&mut self, statement: &Statement) -> Result<(), Stop> {
+        match statement {
+            Statement::Assign(assign) => self.assign(&assign.place, &assign.value),
+            Statement::Definition(definition) => self.assign(&definition.place, &definition.value),
+            Statement::Block(block) => self.execute_block(block),
+            Statement::Conditional(conditional) => match self.evaluate(&conditional.condition)?.boolean()? {
+                true => self.execute_block(&conditional.then),
+                false => match &conditional.otherwise {
+                    Some(otherwise) => self.execute_statement(otherwise),
+                    None => Ok(()),
+                },
+            },
+            Statement::Assert(assert) => {
+                let holds = match &assert.variant {
+                    AssertVariant::Assert(condition) => self.evaluate(condition)?.boolean()?,
+                    AssertVariant::AssertEq(left, right) => self.evaluate(left)? == self.evaluate(right)?,
+                    AssertVariant::AssertNeq(left, right) => self.evaluate(left)? != self.evaluate(right)?,
+                };
+                match holds {
+                    true => Ok(()),
+                    false => Err(Stop::Failed(assert.span)),
+                }
+            }
+            Statement::Return(_) => Err(Stop::Return),
+            // Mappings are only accessed in finalize blocks, and the arguments of a finalize statement have no effect on assertions.
+            Statement::Decrement(_) | Statement::Finalize(_) | Statement::Increment(_) => Ok(()),
+            Statement::Cfg(_) | Statement::Expression(_) | Statement::Iteration(_) | Statement::Match(_) => {
+                Err(Stop::Unknown)
+            }
+        }
+    }
+
+    fn assign(&mut self, place: &Expression, value: &Expression) -> Result<(), Stop> {
+        match place {
+            Expression::Identifier(identifier) => {
+                let value = self.evaluate(value)?;
+                self.values.insert(identifier.name, value);
+                Ok(())
+            }
+            _ => Err(Stop::Unknown),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use BinaryOperation::*;
+    use IntegerType::*;
+
+    #[test]
+    fn test_integer_semantics() {
+        let int = Value::Integer;
+        assert_eq!(int(U8, 255).binary(Add, int(U8, 1)), Err(Stop::Halt));
+        assert_eq!(int(U8, 255).binary(AddWrapped, int(U8, 1)), Ok(int(U8, 0)));
+        assert_eq!(int(I8, -128).binary(Div, int(I8, -1)), Err(Stop::Halt));
+        assert_eq!(int(I8, -128).binary(DivWrapped, int(I8, -1)), Ok(int(I8, -128)));
+        assert_eq!(int(I8, -7).binary(Rem, int(I8, 2)), Ok(int(I8, -1)));
+        assert_eq!(int(U8, 1).binary(Div, int(U8, 0)), Err(Stop::Halt));
+        assert_eq!(int(U8, 3).binary(PowWrapped, int(U8, 5)), Ok(int(U8, 243)));
+        assert_eq!(int(U8, 3).binary(PowWrapped, int(U8, 6)), Ok(int(U8, 217)));
+        assert_eq!(int(U8, 3).binary(Pow, int(U8, 6)), Err(Stop::Halt));
+        assert_eq!(int(I8, -1).binary(ShlWrapped, int(U8, 9)), Ok(int(I8, -2)));
+        assert_eq!(int(U8, 200).binary(ShrWrapped, int(U8, 1)), Ok(int(U8, 100)));
+        assert_eq!(int(U8, 200).unary(UnaryOperation::Not), Ok(int(U8, 55)));
+        assert_eq!(int(I8, -128).unary(UnaryOperation::Abs), Err(Stop::Halt));
+        assert_eq!(int(I8, -128).unary(UnaryOperation::AbsWrapped), Ok(int(I8, -128)));
+        assert_eq!(int(U128, i128::MAX).binary(Add, int(U128, 1)), Err(Stop::Unknown));
+        assert_eq!(
+            Value::Boolean(true).binary(Nand, Value::Boolean(true)),
+            Ok(Value::Boolean(false))
+        );
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The assertion checking pass traverses the AST after static single assignment and searches for inputs on which the assertions of each transition fail.
+//! Each transition whose inputs are all booleans and integers is executed concretely, following the branch taken by each conditional.
+//! If the inputs have at most 65536 combinations of values, every combination is executed, so an assertion that fails on none of them always holds.
+//! Otherwise, the pass executes combinations of the boundary values of each input type and of the values adjacent to the constants in the transition.
+//! An execution that halts, for example due to an overflow, does not reach the assertions after the halting statement.
+//! Calls, loops, and operations on other types of values are not supported, so a transition that reaches one is never proven to satisfy its assertions.
+//!
+//! Consider the following Leo code.
+//! ```leo
+//! transition main(a: u8, b: bool) -> u8 {
+//!     if b {
+//!         assert(a != 255u8);
+//!     }
+//!     assert(a <= 255u8);
+//!     return a;
+//! }
+//! ```
+//!
+//! The pass executes `main` on all 512 combinations of values of `a` and `b`.
+//! It warns that the first assertion fails when `a = 255u8, b = true`, and proves that the second assertion holds.
+
+mod check_program;
+
+pub mod assertion_checker;
+pub use assertion_checker::*;
+
+pub mod execution;
+pub use execution::*;
+
+use crate::{Pass, PassSummary};
+
+use leo_ast::{Ast, ProgramVisitor};
+use leo_errors::{emitter::Handler, Result};
+
+impl<'a> Pass for AssertionChecker<'a> {
+    type Input = (&'a Ast, &'a Handler);
+    type Output = Result<PassSummary>;
+
+    fn do_pass((ast, handler): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("assertion checking", Some(handler));
+        let mut visitor = AssertionChecker::new(handler);
+        visitor.visit_program(ast.as_repr());
+
+        let mut summary = recorder.finish(Some(handler));
+        summary.decisions = visitor.decisions;
+
+        Ok(summary)
+    }
+}
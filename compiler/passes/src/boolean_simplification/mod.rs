@@ -0,0 +1,79 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The boolean simplification pass traverses the AST after the flattening pass and simplifies boolean expressions.
+//! Flattening conjoins the guards of nested branches and negates the guards of `else` branches, which often produces redundant expressions.
+//! Each boolean variable is tracked as a conjunction and as a disjunction of atoms, a variable or its negation, which allows the pass to
+//! fold `x && !x` to `false` and `x || !x` to `true`, and to drop an operand that is implied by the other.
+//! The pass also simplifies negations and double negations, and selections whose condition is known or whose branches are equal.
+//! A selection whose branch is itself a selection on a condition implied by, or contradicting, the outer condition selects that branch's arm directly,
+//! e.g. `c ? (c ? x : y) : z` is `c ? x : z`, which removes the redundant selections that flattening produces for nested conditionals.
+//!
+//! Consider the following flattened Leo code.
+//! ```leo
+//! function main(a: bool, b: bool, x: u8, y: u8) -> u8 {
+//!     $var$0 = a && b;
+//!     $var$1 = !a;
+//!     $var$2 = $var$0 && $var$1;
+//!     $var$3 = $var$2 ? x : y;
+//!     $var$4 = $var$0 && a;
+//!     $var$5 = $var$4 ? x : $var$3;
+//!     return $var$5;
+//! }
+//! ```
+//!
+//! The boolean simplification pass produces the following code.
+//! ```leo
+//! function main(a: bool, b: bool, x: u8, y: u8) -> u8 {
+//!     $var$0 = a && b;
+//!     $var$1 = !a;
+//!     $var$2 = false;
+//!     $var$3 = y;
+//!     $var$4 = $var$0;
+//!     $var$5 = $var$0 ? x : $var$3;
+//!     return $var$5;
+//! }
+//! ```
+
+mod simplify_expression;
+
+mod simplify_program;
+
+mod simplify_statement;
+
+pub mod simplifier;
+pub use simplifier::*;
+
+use crate::{Pass, PassSummary};
+
+use leo_ast::{Ast, NodeBuilder, ProgramReconstructor};
+use leo_errors::Result;
+
+impl<'a> Pass for BooleanSimplifier<'a> {
+    type Input = (Ast, &'a NodeBuilder);
+    type Output = Result<(Ast, PassSummary)>;
+
+    fn do_pass((ast, node_builder): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("boolean simplification", None);
+        let mut reconstructor = BooleanSimplifier::new(node_builder);
+        let program = reconstructor.reconstruct_program(ast.into_repr());
+
+        let ast = Ast::new(program);
+        let summary = recorder.finish(None);
+
+        Ok((ast, summary))
+    }
+}
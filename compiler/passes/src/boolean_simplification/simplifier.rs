@@ -0,0 +1,294 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{
+    BinaryExpression, BinaryOperation, Expression, Literal, NodeBuilder, TernaryExpression, UnaryExpression,
+    UnaryOperation,
+};
+use leo_span::Symbol;
+
+use indexmap::{IndexMap, IndexSet};
+
+/// A boolean variable, paired with `false` if it is negated.
+pub type Atom = (Symbol, bool);
+
+pub struct BooleanSimplifier<'a> {
+    /// A mapping from variables to their values, for variables assigned a copy of another variable, a boolean literal, a negation, or a selection.
+    pub(crate) values: IndexMap<Symbol, Expression>,
+    /// A mapping from boolean variables to a set of atoms whose conjunction is equal to the variable.
+    pub(crate) conjuncts: IndexMap<Symbol, IndexSet<Atom>>,
+    /// A mapping from boolean variables to a set of atoms whose disjunction is equal to the variable.
+    pub(crate) disjuncts: IndexMap<Symbol, IndexSet<Atom>>,
+    /// A counter used to generate unique ids for the nodes that the pass creates.
+    pub(crate) node_builder: &'a NodeBuilder,
+}
+
+impl<'a> BooleanSimplifier<'a> {
+    pub(crate) fn new(node_builder: &'a NodeBuilder) -> Self {
+        Self {
+            values: IndexMap::new(),
+            conjuncts: IndexMap::new(),
+            disjuncts: IndexMap::new(),
+            node_builder,
+        }
+    }
+
+    /// Clears the state associated with the previously traversed function body or finalize block.
+    pub(crate) fn clear(&mut self) {
+        self.values.clear();
+        self.conjuncts.clear();
+        self.disjuncts.clear();
+    }
+
+    /// Resolves an operand that is a copy of another variable or of a boolean literal to its source.
+    pub(crate) fn resolve(&self, operand: Expression) -> Expression {
+        match &operand {
+            Expression::Identifier(identifier) => match self.values.get(&identifier.name) {
+                Some(value @ (Expression::Identifier(_) | Expression::Literal(Literal::Boolean(..)))) => value.clone(),
+                _ => operand,
+            },
+            _ => operand,
+        }
+    }
+
+    /// Returns the atoms whose conjunction is equal to `expression`, if they can be determined.
+    pub(crate) fn conjuncts_of(&self, expression: &Expression) -> Option<IndexSet<Atom>> {
+        match expression {
+            Expression::Identifier(identifier) => Some(
+                self.conjuncts
+                    .get(&identifier.name)
+                    .cloned()
+                    .unwrap_or_else(|| IndexSet::from([(identifier.name, true)])),
+            ),
+            // By De Morgan's laws, the negation of a disjunction is the conjunction of the negated atoms.
+            Expression::Unary(UnaryExpression {
+                op: UnaryOperation::Not,
+                receiver,
+                ..
+            }) => self.disjuncts_of(receiver).map(negate),
+            Expression::Binary(BinaryExpression {
+                op: BinaryOperation::And,
+                left,
+                right,
+                ..
+            }) => {
+                let mut conjuncts = self.conjuncts_of(left)?;
+                conjuncts.extend(self.conjuncts_of(right)?);
+                Some(conjuncts)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the atoms whose disjunction is equal to `expression`, if they can be determined.
+    pub(crate) fn disjuncts_of(&self, expression: &Expression) -> Option<IndexSet<Atom>> {
+        match expression {
+            Expression::Identifier(identifier) => Some(
+                self.disjuncts
+                    .get(&identifier.name)
+                    .cloned()
+                    .unwrap_or_else(|| IndexSet::from([(identifier.name, true)])),
+            ),
+            // By De Morgan's laws, the negation of a conjunction is the disjunction of the negated atoms.
+            Expression::Unary(UnaryExpression {
+                op: UnaryOperation::Not,
+                receiver,
+                ..
+            }) => self.conjuncts_of(receiver).map(negate),
+            Expression::Binary(BinaryExpression {
+                op: BinaryOperation::Or,
+                left,
+                right,
+                ..
+            }) => {
+                let mut disjuncts = self.disjuncts_of(left)?;
+                disjuncts.extend(self.disjuncts_of(right)?);
+                Some(disjuncts)
+            }
+            _ => None,
+        }
+    }
+
+    /// Records the value assigned to the variable `name`.
+    pub(crate) fn record(&mut self, name: Symbol, value: &Expression) {
+        self.values.remove(&name);
+        self.conjuncts.remove(&name);
+        self.disjuncts.remove(&name);
+
+        let value = self.resolve(value.clone());
+        if let Some(conjuncts) = self.conjuncts_of(&value) {
+            self.conjuncts.insert(name, conjuncts);
+        }
+        if let Some(disjuncts) = self.disjuncts_of(&value) {
+            self.disjuncts.insert(name, disjuncts);
+        }
+        if let Expression::Identifier(_)
+        | Expression::Literal(Literal::Boolean(..))
+        | Expression::Unary(UnaryExpression {
+            op: UnaryOperation::Not,
+            ..
+        })
+        | Expression::Ternary(_) = value
+        {
+            self.values.insert(name, value);
+        }
+    }
+
+    /// Simplifies `left && right` or `left || right`, given operands that have already been resolved.
+    /// Returns `None` if the expression cannot be simplified.
+    pub(crate) fn simplify_logical(
+        &self,
+        op: BinaryOperation,
+        left: &Expression,
+        right: &Expression,
+    ) -> Option<Expression> {
+        let is_and = op == BinaryOperation::And;
+
+        // `true` is the identity of `&&` and absorbs `||`, and vice versa for `false`.
+        for (operand, other) in [(left, right), (right, left)] {
+            if let Expression::Literal(Literal::Boolean(value, _, _)) = operand {
+                return Some(if *value == is_and {
+                    other.clone()
+                } else {
+                    operand.clone()
+                });
+            }
+        }
+
+        // `x && x` and `x || x` are equal to `x`.
+        if is_same_operand(left, right) {
+            return Some(left.clone());
+        }
+
+        let (left_atoms, right_atoms) = match is_and {
+            true => (self.conjuncts_of(left)?, self.conjuncts_of(right)?),
+            false => (self.disjuncts_of(left)?, self.disjuncts_of(right)?),
+        };
+
+        // `x && !x` is `false` and `x || !x` is `true`.
+        let is_complementary = left_atoms.iter().chain(right_atoms.iter()).any(|(symbol, polarity)| {
+            left_atoms.contains(&(*symbol, !polarity)) || right_atoms.contains(&(*symbol, !polarity))
+        });
+        if is_complementary {
+            return Some(Expression::Literal(Literal::Boolean(
+                !is_and,
+                Default::default(),
+                self.node_builder.next_id(),
+            )));
+        }
+
+        // If one operand implies the other, the weaker operand is redundant in a conjunction, and the stronger one in a disjunction.
+        if left_atoms.is_subset(&right_atoms) {
+            Some(right.clone())
+        } else if right_atoms.is_subset(&left_atoms) {
+            Some(left.clone())
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> BooleanSimplifier<'a> {
+    /// Returns the selection that `expression` is, or that the variable `expression` is assigned.
+    fn selection_of(&self, expression: &Expression) -> Option<TernaryExpression> {
+        match self.resolve(expression.clone()) {
+            Expression::Ternary(ternary) => Some(ternary),
+            Expression::Identifier(identifier) => match self.values.get(&identifier.name) {
+                Some(Expression::Ternary(ternary)) => Some(ternary.clone()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if `left` and `right` are the same operand, or variables assigned the same selection.
+    pub(crate) fn is_same(&self, left: &Expression, right: &Expression) -> bool {
+        let same = |left: &Expression, right: &Expression| {
+            is_same_operand(&self.resolve(left.clone()), &self.resolve(right.clone()))
+        };
+        if same(left, right) {
+            return true;
+        }
+        match (left, right, self.selection_of(left), self.selection_of(right)) {
+            (Expression::Identifier(_), Expression::Identifier(_), Some(left), Some(right)) => {
+                same(&left.condition, &right.condition)
+                    && same(&left.if_true, &right.if_true)
+                    && same(&left.if_false, &right.if_false)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `Some(true)` if `condition` holds whenever all of the atoms in `known` hold, `Some(false)` if it cannot hold then,
+    /// and `None` if it cannot be determined.
+    fn decide(&self, known: &IndexSet<Atom>, condition: &Expression) -> Option<bool> {
+        let negated = |(symbol, polarity): &Atom| known.contains(&(*symbol, !polarity));
+        if let Some(conjuncts) = self.conjuncts_of(condition) {
+            if conjuncts.is_subset(known) {
+                return Some(true);
+            }
+            if conjuncts.iter().any(negated) {
+                return Some(false);
+            }
+        }
+        if let Some(disjuncts) = self.disjuncts_of(condition) {
+            if disjuncts.iter().any(|atom| known.contains(atom)) {
+                return Some(true);
+            }
+            if disjuncts.iter().all(negated) {
+                return Some(false);
+            }
+        }
+        None
+    }
+
+    /// Simplifies an arm of a selection, given the atoms that hold whenever the arm is selected.
+    /// If the arm is itself a selection whose condition is decided by those atoms, it is replaced by the arm that it selects,
+    /// e.g. `c ? (c ? x : y) : z` is `c ? x : z`.
+    pub(crate) fn simplify_arm(&self, mut arm: Expression, known: &Option<IndexSet<Atom>>) -> Expression {
+        let known = match known {
+            Some(known) => known,
+            None => return arm,
+        };
+        while let Some(selection) = self.selection_of(&arm) {
+            arm = match self.decide(known, &selection.condition) {
+                Some(true) => *selection.if_true,
+                Some(false) => *selection.if_false,
+                None => break,
+            };
+        }
+        arm
+    }
+}
+
+/// Negates each atom in `atoms`.
+pub(crate) fn negate(atoms: IndexSet<Atom>) -> IndexSet<Atom> {
+    atoms
+        .into_iter()
+        .map(|(symbol, polarity)| (symbol, !polarity))
+        .collect()
+}
+
+/// Returns `true` if `left` and `right` are the same variable or the same boolean literal.
+pub(crate) fn is_same_operand(left: &Expression, right: &Expression) -> bool {
+    match (left, right) {
+        (Expression::Identifier(left), Expression::Identifier(right)) => left.name == right.name,
+        (Expression::Literal(Literal::Boolean(left, _, _)), Expression::Literal(Literal::Boolean(right, _, _))) => {
+            left == right
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{negate, BooleanSimplifier};
+
+use leo_ast::{
+    BinaryExpression, BinaryOperation, Expression, ExpressionReconstructor, Literal, TernaryExpression,
+    UnaryExpression, UnaryOperation,
+};
+
+impl ExpressionReconstructor for BooleanSimplifier<'_> {
+    type AdditionalOutput = ();
+
+    /// Simplifies conjunctions and disjunctions, e.g. `x && !x` to `false` and `x && (x && y)` to `x && y`.
+    fn reconstruct_binary(&mut self, input: BinaryExpression) -> (Expression, Self::AdditionalOutput) {
+        let left = self.reconstruct_expression(*input.left).0;
+        let right = self.reconstruct_expression(*input.right).0;
+
+        let (left, right) = match input.op {
+            BinaryOperation::And | BinaryOperation::Or => {
+                let (left, right) = (self.resolve(left), self.resolve(right));
+                if let Some(simplified) = self.simplify_logical(input.op, &left, &right) {
+                    return (simplified, Default::default());
+                }
+                (left, right)
+            }
+            _ => (left, right),
+        };
+
+        (
+            Expression::Binary(BinaryExpression {
+                left: Box::new(left),
+                right: Box::new(right),
+                op: input.op,
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    /// Simplifies selections whose outcome does not depend on their condition, or that select between `true` and `false`.
+    /// Arms that are themselves selections decided by the condition are replaced by the arm that they select.
+    fn reconstruct_ternary(&mut self, input: TernaryExpression) -> (Expression, Self::AdditionalOutput) {
+        let condition = self.reconstruct_expression(*input.condition).0;
+        let condition = self.resolve(condition);
+        let if_true = self.reconstruct_expression(*input.if_true).0;
+        let if_false = self.reconstruct_expression(*input.if_false).0;
+
+        // A selection with a known condition is its selected branch.
+        if let Expression::Literal(Literal::Boolean(value, _, _)) = condition {
+            return (if value { if_true } else { if_false }, Default::default());
+        }
+
+        // The condition holds in the first arm, and its negation holds in the second.
+        let if_true = self.simplify_arm(if_true, &self.conjuncts_of(&condition));
+        let if_false = self.simplify_arm(if_false, &self.disjuncts_of(&condition).map(negate));
+
+        match (self.resolve(if_true.clone()), self.resolve(if_false.clone())) {
+            // A selection between equal branches is either branch.
+            (resolved_true, resolved_false) if self.is_same(&resolved_true, &resolved_false) => {
+                return (if_true, Default::default());
+            }
+            // `c ? true : false` is `c`.
+            (Expression::Literal(Literal::Boolean(true, _, _)), Expression::Literal(Literal::Boolean(false, _, _))) => {
+                return (condition, Default::default());
+            }
+            // `c ? false : true` is `!c`.
+            (Expression::Literal(Literal::Boolean(false, _, _)), Expression::Literal(Literal::Boolean(true, _, _))) => {
+                return (
+                    Expression::Unary(UnaryExpression {
+                        receiver: Box::new(condition),
+                        op: UnaryOperation::Not,
+                        span: input.span,
+                        id: input.id,
+                    }),
+                    Default::default(),
+                );
+            }
+            _ => {}
+        }
+
+        (
+            Expression::Ternary(TernaryExpression {
+                condition: Box::new(condition),
+                if_true: Box::new(if_true),
+                if_false: Box::new(if_false),
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    /// Simplifies negations of boolean literals and double negations.
+    fn reconstruct_unary(&mut self, input: UnaryExpression) -> (Expression, Self::AdditionalOutput) {
+        let receiver = self.reconstruct_expression(*input.receiver).0;
+
+        let receiver = match input.op {
+            UnaryOperation::Not => match self.resolve(receiver) {
+                Expression::Literal(Literal::Boolean(value, span, _)) => {
+                    return (
+                        Expression::Literal(Literal::Boolean(!value, span, self.node_builder.next_id())),
+                        Default::default(),
+                    );
+                }
+                // `!!x` is `x`.
+                Expression::Unary(UnaryExpression {
+                    op: UnaryOperation::Not,
+                    receiver,
+                    ..
+                }) => return (*receiver, Default::default()),
+                Expression::Identifier(identifier) => match self.values.get(&identifier.name) {
+                    Some(Expression::Unary(UnaryExpression {
+                        op: UnaryOperation::Not,
+                        receiver,
+                        ..
+                    })) => return (*receiver.clone(), Default::default()),
+                    _ => Expression::Identifier(identifier),
+                },
+                receiver => receiver,
+            },
+            _ => receiver,
+        };
+
+        (
+            Expression::Unary(UnaryExpression {
+                receiver: Box::new(receiver),
+                op: input.op,
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+}
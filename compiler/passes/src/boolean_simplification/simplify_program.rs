@@ -0,0 +1,51 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::BooleanSimplifier;
+
+use leo_ast::{Finalize, Function, ProgramReconstructor, StatementReconstructor};
+
+impl ProgramReconstructor for BooleanSimplifier<'_> {
+    fn reconstruct_function(&mut self, input: Function) -> Function {
+        let finalize = input.finalize.map(|finalize| {
+            self.clear();
+            Finalize {
+                identifier: finalize.identifier,
+                input: finalize.input,
+                output: finalize.output,
+                output_type: finalize.output_type,
+                block: self.reconstruct_block(finalize.block).0,
+                span: finalize.span,
+                id: finalize.id,
+            }
+        });
+
+        self.clear();
+        Function {
+            annotations: input.annotations,
+            call_type: input.call_type,
+            identifier: input.identifier,
+            type_parameters: input.type_parameters,
+            input: input.input,
+            output: input.output,
+            output_type: input.output_type,
+            block: self.reconstruct_block(input.block).0,
+            finalize,
+            span: input.span,
+            id: input.id,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::BooleanSimplifier;
+
+use leo_ast::{AssignStatement, Expression, ExpressionReconstructor, Statement, StatementReconstructor};
+
+impl StatementReconstructor for BooleanSimplifier<'_> {
+    /// Records the value assigned to a variable, so that later uses of the variable can be simplified.
+    fn reconstruct_assign(&mut self, input: AssignStatement) -> (Statement, Self::AdditionalOutput) {
+        let value = self.reconstruct_expression(input.value).0;
+
+        if let Expression::Identifier(identifier) = &input.place {
+            self.record(identifier.name, &value);
+        }
+
+        (
+            Statement::Assign(Box::new(AssignStatement {
+                place: input.place,
+                value,
+                span: input.span,
+                id: input.id,
+            })),
+            Default::default(),
+        )
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::Annotation;
+use leo_span::sym;
+
+/// The options that the conditions of `@cfg(..)` annotations are evaluated against.
+#[derive(Clone, Debug, Default)]
+pub struct CfgOptions {
+    /// The name of the network that the program is compiled for, e.g. `testnet3`.
+    pub network: String,
+    /// The names of the enabled features.
+    pub features: Vec<String>,
+    /// Whether the program is compiled for testing.
+    pub test: bool,
+}
+
+pub struct CfgStripper<'a> {
+    /// The options that the conditions of annotations are evaluated against.
+    pub(crate) options: &'a CfgOptions,
+    /// The functions that were removed, and the annotations that did not hold.
+    pub(crate) decisions: Vec<String>,
+}
+
+impl<'a> CfgStripper<'a> {
+    /// Initializes a new `CfgStripper`.
+    pub(crate) fn new(options: &'a CfgOptions) -> Self {
+        Self {
+            options,
+            decisions: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if the annotation is not a `@cfg(..)` or `@test` annotation, or if each of its conditions holds.
+    /// A `@test` annotation holds if the program is compiled for testing, like `@cfg(test)`.
+    /// Note that the parser guarantees that each condition is `test`, `network = "<name>"`, or `feature = "<name>"`.
+    pub(crate) fn holds(&self, annotation: &Annotation) -> bool {
+        match annotation.identifier.name {
+            sym::cfg => {}
+            sym::test => return self.options.test,
+            _ => return true,
+        }
+        annotation
+            .arguments
+            .iter()
+            .all(|condition| match (condition.identifier.name, &condition.value) {
+                (sym::test, None) => self.options.test,
+                (sym::network, Some(network)) => *network == self.options.network,
+                (sym::feature, Some(feature)) => self.options.features.contains(feature),
+                _ => false,
+            })
+    }
+}
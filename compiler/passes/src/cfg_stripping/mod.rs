@@ -0,0 +1,85 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The cfg stripping pass traverses the AST before the symbol table is created and removes the functions and statements whose `@cfg(..)` annotation does not hold.
+//! The conditions of an annotation are evaluated against the options that the program is compiled with, and the annotation holds if all of its conditions do.
+//! - `test` holds if the program is compiled for testing.
+//! - `network = "<name>"` holds if the program is compiled for the network `<name>`.
+//! - `feature = "<name>"` holds if the feature `<name>` is enabled.
+//!
+//! A function annotated with `@test` is a unit test, which is only kept if the program is compiled for testing.
+//!
+//! The `@cfg(..)` annotations of the functions and statements that are kept are removed, so later passes do not see them.
+//! Since functions are removed before their names are resolved, one program scope may define a function once for each network.
+//!
+//! Consider the following Leo code, compiled for `testnet3`.
+//! ```leo
+//! @cfg(network = "testnet3")
+//! function fee() -> u64 {
+//!     return 1u64;
+//! }
+//!
+//! @cfg(network = "mainnet")
+//! function fee() -> u64 {
+//!     return 10u64;
+//! }
+//!
+//! transition main(amount: u64) -> u64 {
+//!     @cfg(test)
+//!     assert(amount > 0u64);
+//!     return amount + fee();
+//! }
+//! ```
+//!
+//! The cfg stripping pass produces the following code.
+//! ```leo
+//! function fee() -> u64 {
+//!     return 1u64;
+//! }
+//!
+//! transition main(amount: u64) -> u64 {
+//!     return amount + fee();
+//! }
+//! ```
+
+mod strip_program;
+
+mod strip_statement;
+
+pub mod cfg_stripper;
+pub use cfg_stripper::*;
+
+use crate::{Pass, PassSummary};
+
+use leo_ast::{Ast, ProgramReconstructor};
+use leo_errors::Result;
+
+impl<'a> Pass for CfgStripper<'a> {
+    type Input = (Ast, &'a CfgOptions);
+    type Output = Result<(Ast, PassSummary)>;
+
+    fn do_pass((ast, options): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("cfg stripping", None);
+        let mut reconstructor = CfgStripper::new(options);
+        let program = reconstructor.reconstruct_program(ast.into_repr());
+
+        let ast = Ast::new(program);
+        let mut summary = recorder.finish(None);
+        summary.decisions = reconstructor.decisions;
+
+        Ok((ast, summary))
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::CfgStripper;
+
+use leo_ast::{Finalize, Function, ProgramReconstructor, ProgramScope, StatementReconstructor};
+use leo_span::sym;
+
+impl ProgramReconstructor for CfgStripper<'_> {
+    /// Removes the functions whose `@cfg(..)` annotations do not hold, and reconstructs the remaining functions.
+    fn reconstruct_program_scope(&mut self, input: ProgramScope) -> ProgramScope {
+        let mut functions = Vec::with_capacity(input.functions.len());
+        for (identifier, function) in input.functions {
+            match function.annotations.iter().find(|annotation| !self.holds(annotation)) {
+                None => functions.push((identifier, function)),
+                Some(annotation) => self
+                    .decisions
+                    .push(format!("removed `{identifier}`: `{annotation}` does not hold")),
+            }
+        }
+
+        ProgramScope {
+            program_id: input.program_id,
+            consts: input
+                .consts
+                .into_iter()
+                .map(|(i, c)| (i, self.reconstruct_const(c)))
+                .collect(),
+            structs: input
+                .structs
+                .into_iter()
+                .map(|(i, c)| (i, self.reconstruct_struct(c)))
+                .collect(),
+            enums: input.enums,
+            mappings: input
+                .mappings
+                .into_iter()
+                .map(|(id, mapping)| (id, self.reconstruct_mapping(mapping)))
+                .collect(),
+            functions: functions
+                .into_iter()
+                .map(|(i, f)| (i, self.reconstruct_function(f)))
+                .collect(),
+            default_integer_type: input.default_integer_type,
+            span: input.span,
+        }
+    }
+
+    /// Removes the `@cfg(..)` annotations of the function, and strips the statements of its body.
+    fn reconstruct_function(&mut self, input: Function) -> Function {
+        Function {
+            annotations: input
+                .annotations
+                .into_iter()
+                .filter(|annotation| annotation.identifier.name != sym::cfg)
+                .collect(),
+            call_type: input.call_type,
+            identifier: input.identifier,
+            type_parameters: input.type_parameters,
+            input: input.input,
+            output: input.output,
+            output_type: input.output_type,
+            block: self.reconstruct_block(input.block).0,
+            finalize: input.finalize.map(|finalize| Finalize {
+                identifier: finalize.identifier,
+                input: finalize.input,
+                output: finalize.output,
+                output_type: finalize.output_type,
+                block: self.reconstruct_block(finalize.block).0,
+                span: finalize.span,
+                id: finalize.id,
+            }),
+            span: input.span,
+            id: input.id,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::CfgStripper;
+
+use leo_ast::{Block, CfgStatement, ExpressionReconstructor, Statement, StatementReconstructor};
+
+impl ExpressionReconstructor for CfgStripper<'_> {
+    type AdditionalOutput = ();
+}
+
+impl StatementReconstructor for CfgStripper<'_> {
+    /// Removes the statements of the block whose `@cfg(..)` annotations do not hold, and unwraps the remaining ones.
+    fn reconstruct_block(&mut self, input: Block) -> (Block, Self::AdditionalOutput) {
+        let mut statements = Vec::with_capacity(input.statements.len());
+        for mut statement in input.statements {
+            // A statement may be preceded by several annotations, all of which must hold.
+            let statement = loop {
+                match statement {
+                    Statement::Cfg(cfg) if self.holds(&cfg.cfg) => statement = *cfg.statement,
+                    Statement::Cfg(cfg) => {
+                        self.decisions
+                            .push(format!("removed `{}`: `{}` does not hold", cfg.statement, cfg.cfg));
+                        break None;
+                    }
+                    statement => break Some(statement),
+                }
+            };
+            if let Some(statement) = statement {
+                statements.push(self.reconstruct_statement(statement).0);
+            }
+        }
+
+        (
+            Block {
+                statements,
+                span: input.span,
+                id: input.id,
+            },
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_cfg(&mut self, _: CfgStatement) -> (Statement, Self::AdditionalOutput) {
+        unreachable!("`CfgStatement`s are removed or unwrapped by `reconstruct_block`.")
+    }
+}
@@ -14,24 +14,38 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use leo_ast::Function;
+use crate::StructGraph;
+
+use leo_ast::{Function, Node, Statement};
 use leo_errors::emitter::Handler;
-use leo_span::Symbol;
+use leo_ir::{Instruction, Operand, Register, SourceLocation};
+use leo_span::{symbol::with_session_globals, Symbol};
 
 use indexmap::IndexMap;
 
+/// Lowers a flattened AST into the intermediate representation, which is printed as Aleo instructions.
 pub struct CodeGenerator<'a> {
-    _handler: &'a Handler,
+    /// The error handler.
+    pub(crate) handler: &'a Handler,
+    /// The struct graph of the program, used to declare each struct after the structs that it has members of.
+    pub(crate) struct_graph: &'a StructGraph,
+    /// The network that the program and its imports are deployed to, e.g. `aleo`.
+    pub(crate) network: &'a str,
     /// A counter to track the next available register.
     pub(crate) next_register: u64,
+    /// The instructions of the current function or finalize block.
+    pub(crate) instructions: Vec<Instruction>,
+    /// The location in the Leo program that each instruction of the current function or finalize block was generated from, if known.
+    pub(crate) locations: Vec<Option<SourceLocation>>,
     /// Reference to the current function.
     pub(crate) current_function: Option<&'a Function>,
-    /// Mapping of variables to registers.
-    pub(crate) variable_mapping: IndexMap<&'a Symbol, String>,
-    /// Mapping of composite names to a tuple containing metadata associated with the name.
-    /// The first element of the tuple indicate whether the composite is a record or not.
-    /// The second element of the tuple is a string modifier used for code generation.
-    pub(crate) composite_mapping: IndexMap<&'a Symbol, (bool, String)>,
+    /// Mapping of variables to operands.
+    /// Note that a tuple is mapped to the operands of its elements.
+    pub(crate) variable_mapping: IndexMap<&'a Symbol, Vec<Operand>>,
+    /// Mapping of composite names to whether the composite is a record or not.
+    pub(crate) composite_mapping: IndexMap<&'a Symbol, bool>,
+    /// Mapping of function names to the number of outputs of the function.
+    pub(crate) function_outputs: IndexMap<&'a Symbol, usize>,
     /// Are we traversing a transition function?
     pub(crate) is_transition_function: bool,
     /// Are we traversing a finalize block?
@@ -40,16 +54,56 @@ pub struct CodeGenerator<'a> {
 
 impl<'a> CodeGenerator<'a> {
     /// Initializes a new `CodeGenerator`.
-    pub fn new(handler: &'a Handler) -> Self {
+    pub fn new(handler: &'a Handler, struct_graph: &'a StructGraph, network: &'a str) -> Self {
         // Initialize variable mapping.
         Self {
-            _handler: handler,
+            handler,
+            struct_graph,
+            network,
             next_register: 0,
+            instructions: Vec::new(),
+            locations: Vec::new(),
             current_function: None,
             variable_mapping: IndexMap::new(),
             composite_mapping: IndexMap::new(),
+            function_outputs: IndexMap::new(),
             is_transition_function: false,
             in_finalize: false,
         }
     }
+
+    /// Returns the next available register.
+    pub(crate) fn fresh_register(&mut self) -> Register {
+        let register = Register(self.next_register);
+        self.next_register += 1;
+        register
+    }
+
+    /// Returns the location in the Leo program that `statement` was written at, if known.
+    /// Note that statements introduced by earlier passes carry the synthetic span of the code they were generated from.
+    /// If that code has no span either, the span of the assigned value is used.
+    pub(crate) fn location_of(statement: &Statement) -> Option<SourceLocation> {
+        let span = match statement {
+            Statement::Assign(assign) if assign.span.is_dummy() => assign.value.span(),
+            statement => statement.span(),
+        };
+        if span.is_dummy() {
+            return None;
+        }
+        with_session_globals(|s| s.source_map.span_to_location(span)).map(|location| SourceLocation {
+            line_start: location.line_start,
+            col_start: location.col_start,
+            line_stop: location.line_stop,
+            col_stop: location.col_stop,
+        })
+    }
+
+    /// Takes the instructions of the current function or finalize block, and the locations that they were generated from.
+    pub(crate) fn take_instructions(&mut self) -> (Vec<Instruction>, Vec<Option<SourceLocation>>) {
+        self.locations.resize(self.instructions.len(), None);
+        (
+            std::mem::take(&mut self.instructions),
+            std::mem::take(&mut self.locations),
+        )
+    }
 }
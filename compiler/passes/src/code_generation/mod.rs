@@ -25,21 +25,23 @@ mod visit_statements;
 
 mod visit_type;
 
-use crate::Pass;
+use crate::{Pass, PassSummary, StructGraph};
 
 use leo_ast::Ast;
 use leo_errors::emitter::Handler;
 use leo_errors::Result;
+use leo_ir::Program;
 
 impl<'a> Pass for CodeGenerator<'a> {
-    type Input = (&'a Ast, &'a Handler);
-    type Output = Result<String>;
+    type Input = (&'a Ast, &'a Handler, &'a StructGraph, &'a str);
+    type Output = Result<(Program, PassSummary)>;
 
-    fn do_pass((ast, handler): Self::Input) -> Self::Output {
-        let mut generator = Self::new(handler);
-        let bytecode = generator.visit_program(ast.as_repr());
+    fn do_pass((ast, handler, struct_graph, network): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("code generation", Some(handler));
+        let mut generator = Self::new(handler, struct_graph, network);
+        let program = generator.visit_program(ast.as_repr());
         handler.last_err()?;
 
-        Ok(bytecode)
+        Ok((program, recorder.finish(Some(handler))))
     }
 }
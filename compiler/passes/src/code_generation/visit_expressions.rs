@@ -16,24 +16,29 @@
 
 use crate::CodeGenerator;
 use leo_ast::{
-    AccessExpression, AssociatedFunction, BinaryExpression, BinaryOperation, CallExpression, ErrExpression, Expression,
-    Identifier, Literal, MemberAccess, StructExpression, TernaryExpression, TupleExpression, Type, UnaryExpression,
-    UnaryOperation,
+    AccessExpression, AssociatedFunction, BinaryExpression, BinaryOperation, CallExpression, CastExpression,
+    ErrExpression, Expression, Identifier, Literal, MemberAccess, StructExpression, TernaryExpression, TupleExpression,
+    Type, UnaryExpression, UnaryOperation,
 };
+use leo_core::CoreInstruction;
+use leo_errors::CodeGenError;
+use leo_ir::{self as ir, CastType, Instruction, Operand};
 use leo_span::sym;
 
-use std::fmt::Write as _;
-
 /// Implement the necessary methods to visit nodes in the AST.
 // Note: We opt for this option instead of using `Visitor` and `Director` because this pass requires
 // a post-order traversal of the AST. This is sufficient since this implementation is intended to be
 // a prototype. The production implementation will require a redesign of `Director`.
 impl<'a> CodeGenerator<'a> {
-    pub(crate) fn visit_expression(&mut self, input: &'a Expression) -> (String, String) {
+    /// Lowers an expression, appending the instructions that compute it to the current instructions.
+    /// Returns the operands holding its value; a tuple is lowered to the operands of its elements.
+    pub(crate) fn visit_expression(&mut self, input: &'a Expression) -> Vec<Operand> {
         match input {
             Expression::Access(expr) => self.visit_access(expr),
+            Expression::Array(_) => unreachable!("`ArrayExpression`s should be lowered during flattening."),
             Expression::Binary(expr) => self.visit_binary(expr),
             Expression::Call(expr) => self.visit_call(expr),
+            Expression::Cast(expr) => self.visit_cast(expr),
             Expression::Struct(expr) => self.visit_struct_init(expr),
             Expression::Err(expr) => self.visit_err(expr),
             Expression::Identifier(expr) => self.visit_identifier(expr),
@@ -44,190 +49,249 @@ impl<'a> CodeGenerator<'a> {
         }
     }
 
-    fn visit_identifier(&mut self, input: &'a Identifier) -> (String, String) {
-        (self.variable_mapping.get(&input.name).unwrap().clone(), String::new())
+    /// Lowers an expression that is not a tuple, returning the operand holding its value.
+    pub(crate) fn visit_operand(&mut self, input: &'a Expression) -> Operand {
+        self.visit_expression(input)
+            .pop()
+            .expect("Type checking guarantees that the expression has a value.")
+    }
+
+    fn visit_identifier(&mut self, input: &'a Identifier) -> Vec<Operand> {
+        self.variable_mapping.get(&input.name).unwrap().clone()
     }
 
-    fn visit_err(&mut self, _input: &'a ErrExpression) -> (String, String) {
+    fn visit_err(&mut self, _input: &'a ErrExpression) -> Vec<Operand> {
         unreachable!("`ErrExpression`s should not be in the AST at this phase of compilation.")
     }
 
-    fn visit_value(&mut self, input: &'a Literal) -> (String, String) {
-        (format!("{}", input), String::new())
+    fn visit_value(&mut self, input: &'a Literal) -> Vec<Operand> {
+        vec![Operand::Literal(input.to_string())]
     }
 
-    fn visit_binary(&mut self, input: &'a BinaryExpression) -> (String, String) {
-        let (left_operand, left_instructions) = self.visit_expression(&input.left);
-        let (right_operand, right_instructions) = self.visit_expression(&input.right);
-
-        let opcode = match input.op {
-            BinaryOperation::Add => String::from("add"),
-            BinaryOperation::AddWrapped => String::from("add.w"),
-            BinaryOperation::And => String::from("and"),
-            BinaryOperation::BitwiseAnd => String::from("and"),
-            BinaryOperation::Div => String::from("div"),
-            BinaryOperation::DivWrapped => String::from("div.w"),
-            BinaryOperation::Eq => String::from("is.eq"),
-            BinaryOperation::Gte => String::from("gte"),
-            BinaryOperation::Gt => String::from("gt"),
-            BinaryOperation::Lte => String::from("lte"),
-            BinaryOperation::Lt => String::from("lt"),
-            BinaryOperation::Mod => String::from("mod"),
-            BinaryOperation::Mul => String::from("mul"),
-            BinaryOperation::MulWrapped => String::from("mul.w"),
-            BinaryOperation::Nand => String::from("nand"),
-            BinaryOperation::Neq => String::from("is.neq"),
-            BinaryOperation::Nor => String::from("nor"),
-            BinaryOperation::Or => String::from("or"),
-            BinaryOperation::BitwiseOr => String::from("or"),
-            BinaryOperation::Pow => String::from("pow"),
-            BinaryOperation::PowWrapped => String::from("pow.w"),
-            BinaryOperation::Rem => String::from("rem"),
-            BinaryOperation::RemWrapped => String::from("rem.w"),
-            BinaryOperation::Shl => String::from("shl"),
-            BinaryOperation::ShlWrapped => String::from("shl.w"),
-            BinaryOperation::Shr => String::from("shr"),
-            BinaryOperation::ShrWrapped => String::from("shr.w"),
-            BinaryOperation::Sub => String::from("sub"),
-            BinaryOperation::SubWrapped => String::from("sub.w"),
-            BinaryOperation::Xor => String::from("xor"),
+    fn visit_binary(&mut self, input: &'a BinaryExpression) -> Vec<Operand> {
+        let left = self.visit_operand(&input.left);
+        let right = self.visit_operand(&input.right);
+
+        let operation = match input.op {
+            BinaryOperation::Add => ir::BinaryOperation::Add,
+            BinaryOperation::AddWrapped => ir::BinaryOperation::AddWrapped,
+            BinaryOperation::And => ir::BinaryOperation::And,
+            BinaryOperation::BitwiseAnd => ir::BinaryOperation::And,
+            BinaryOperation::Div => ir::BinaryOperation::Div,
+            BinaryOperation::DivWrapped => ir::BinaryOperation::DivWrapped,
+            BinaryOperation::Eq => ir::BinaryOperation::IsEq,
+            BinaryOperation::Gte => ir::BinaryOperation::Gte,
+            BinaryOperation::Gt => ir::BinaryOperation::Gt,
+            BinaryOperation::Lte => ir::BinaryOperation::Lte,
+            BinaryOperation::Lt => ir::BinaryOperation::Lt,
+            BinaryOperation::Mod => ir::BinaryOperation::Mod,
+            BinaryOperation::Mul => ir::BinaryOperation::Mul,
+            BinaryOperation::MulWrapped => ir::BinaryOperation::MulWrapped,
+            BinaryOperation::Nand => ir::BinaryOperation::Nand,
+            BinaryOperation::Neq => ir::BinaryOperation::IsNeq,
+            BinaryOperation::Nor => ir::BinaryOperation::Nor,
+            BinaryOperation::Or => ir::BinaryOperation::Or,
+            BinaryOperation::BitwiseOr => ir::BinaryOperation::Or,
+            BinaryOperation::Pow => ir::BinaryOperation::Pow,
+            BinaryOperation::PowWrapped => ir::BinaryOperation::PowWrapped,
+            BinaryOperation::Rem => ir::BinaryOperation::Rem,
+            BinaryOperation::RemWrapped => ir::BinaryOperation::RemWrapped,
+            BinaryOperation::Shl => ir::BinaryOperation::Shl,
+            BinaryOperation::ShlWrapped => ir::BinaryOperation::ShlWrapped,
+            BinaryOperation::Shr => ir::BinaryOperation::Shr,
+            BinaryOperation::ShrWrapped => ir::BinaryOperation::ShrWrapped,
+            BinaryOperation::Sub => ir::BinaryOperation::Sub,
+            BinaryOperation::SubWrapped => ir::BinaryOperation::SubWrapped,
+            BinaryOperation::Xor => ir::BinaryOperation::Xor,
         };
 
-        let destination_register = format!("r{}", self.next_register);
-        let binary_instruction = format!(
-            "    {} {} {} into {};\n",
-            opcode, left_operand, right_operand, destination_register
-        );
-
-        // Increment the register counter.
-        self.next_register += 1;
+        let destination = self.fresh_register();
+        self.instructions.push(Instruction::Binary {
+            operation,
+            left,
+            right,
+            destination,
+        });
 
-        // Concatenate the instructions.
-        let mut instructions = left_instructions;
-        instructions.push_str(&right_instructions);
-        instructions.push_str(&binary_instruction);
-
-        (destination_register, instructions)
+        vec![Operand::Register(destination)]
     }
 
-    fn visit_unary(&mut self, input: &'a UnaryExpression) -> (String, String) {
-        let (expression_operand, expression_instructions) = self.visit_expression(&input.receiver);
-
-        let opcode = match input.op {
-            UnaryOperation::Abs => String::from("abs"),
-            UnaryOperation::AbsWrapped => String::from("abs.w"),
-            UnaryOperation::Double => String::from("double"),
-            UnaryOperation::Inverse => String::from("inv"),
-            UnaryOperation::Not => String::from("not"),
-            UnaryOperation::Negate => String::from("neg"),
-            UnaryOperation::Square => String::from("square"),
-            UnaryOperation::SquareRoot => String::from("sqrt"),
+    fn visit_unary(&mut self, input: &'a UnaryExpression) -> Vec<Operand> {
+        let operand = self.visit_operand(&input.receiver);
+
+        let operation = match input.op {
+            UnaryOperation::Abs => ir::UnaryOperation::Abs,
+            UnaryOperation::AbsWrapped => ir::UnaryOperation::AbsWrapped,
+            UnaryOperation::Double => ir::UnaryOperation::Double,
+            UnaryOperation::Inverse => ir::UnaryOperation::Inverse,
+            UnaryOperation::Not => ir::UnaryOperation::Not,
+            UnaryOperation::Negate => ir::UnaryOperation::Negate,
+            UnaryOperation::Square => ir::UnaryOperation::Square,
+            UnaryOperation::SquareRoot => ir::UnaryOperation::SquareRoot,
         };
 
-        let destination_register = format!("r{}", self.next_register);
-        let unary_instruction = format!("    {} {} into {};\n", opcode, expression_operand, destination_register);
-
-        // Increment the register counter.
-        self.next_register += 1;
+        let destination = self.fresh_register();
+        self.instructions.push(Instruction::Unary {
+            operation,
+            operand,
+            destination,
+        });
 
-        // Concatenate the instructions.
-        let mut instructions = expression_instructions;
-        instructions.push_str(&unary_instruction);
-
-        (destination_register, instructions)
+        vec![Operand::Register(destination)]
     }
 
-    fn visit_ternary(&mut self, input: &'a TernaryExpression) -> (String, String) {
-        let (condition_operand, condition_instructions) = self.visit_expression(&input.condition);
-        let (if_true_operand, if_true_instructions) = self.visit_expression(&input.if_true);
-        let (if_false_operand, if_false_instructions) = self.visit_expression(&input.if_false);
+    fn visit_cast(&mut self, input: &'a CastExpression) -> Vec<Operand> {
+        let operand = self.visit_operand(&input.expression);
 
-        let destination_register = format!("r{}", self.next_register);
-        let ternary_instruction = format!(
-            "    ternary {} {} {} into {};\n",
-            condition_operand, if_true_operand, if_false_operand, destination_register
-        );
+        let destination = self.fresh_register();
+        let type_ = CastType::Literal(self.visit_type(&input.type_));
+        self.instructions.push(Instruction::Cast {
+            operands: vec![operand],
+            destination,
+            type_,
+        });
 
-        // Increment the register counter.
-        self.next_register += 1;
+        vec![Operand::Register(destination)]
+    }
 
-        // Concatenate the instructions.
-        let mut instructions = condition_instructions;
-        instructions.push_str(&if_true_instructions);
-        instructions.push_str(&if_false_instructions);
-        instructions.push_str(&ternary_instruction);
+    fn visit_ternary(&mut self, input: &'a TernaryExpression) -> Vec<Operand> {
+        let condition = self.visit_operand(&input.condition);
+        let if_true = self.visit_operand(&input.if_true);
+        let if_false = self.visit_operand(&input.if_false);
 
-        (destination_register, instructions)
+        let destination = self.fresh_register();
+        self.instructions.push(Instruction::Ternary {
+            condition,
+            if_true,
+            if_false,
+            destination,
+        });
+
+        vec![Operand::Register(destination)]
     }
 
-    fn visit_struct_init(&mut self, input: &'a StructExpression) -> (String, String) {
+    fn visit_struct_init(&mut self, input: &'a StructExpression) -> Vec<Operand> {
         // Lookup struct or record.
-        let name = if let Some((is_record, type_)) = self.composite_mapping.get(&input.name.name) {
-            if *is_record {
-                // record.private;
-                format!("{}.{}", input.name, type_)
-            } else {
-                // foo; // no visibility for interfaces
-                input.name.to_string()
+        let is_record = *self
+            .composite_mapping
+            .get(&input.name.name)
+            .expect("All composite types should be known at this phase of compilation");
+
+        // The `owner` and `gates` of a record precede its other members, matching the canonical order of its declaration.
+        let members = match is_record {
+            true => {
+                let (mut required, others): (Vec<_>, Vec<_>) = input
+                    .members
+                    .iter()
+                    .partition(|member| matches!(member.identifier.name, sym::owner | sym::gates));
+                required.sort_by_key(|member| member.identifier.name != sym::owner);
+                required.into_iter().chain(others).collect()
             }
-        } else {
-            unreachable!("All composite types should be known at this phase of compilation")
+            false => input.members.iter().collect::<Vec<_>>(),
         };
 
-        // Initialize instruction builder strings.
-        let mut instructions = String::new();
-        let mut struct_init_instruction = String::from("    cast ");
-
-        // Visit each struct member and accumulate instructions from expressions.
-        for member in input.members.iter() {
-            let operand = if let Some(expr) = member.expression.as_ref() {
-                // Visit variable expression.
-                let (variable_operand, variable_instructions) = self.visit_expression(expr);
-                instructions.push_str(&variable_instructions);
+        // Visit each struct member, using its identifier if it has no expression.
+        let operands = members
+            .into_iter()
+            .map(|member| match member.expression.as_ref() {
+                Some(expr) => self.visit_operand(expr),
+                None => self.visit_identifier(&member.identifier).pop().unwrap(),
+            })
+            .collect();
+
+        let type_ = match is_record {
+            true => CastType::Record(input.name.to_string()),
+            // Note that structs have no visibility.
+            false => CastType::Struct(input.name.to_string()),
+        };
 
-                variable_operand
-            } else {
-                // Push operand identifier.
-                let (ident_operand, ident_instructions) = self.visit_identifier(&member.identifier);
-                instructions.push_str(&ident_instructions);
+        let destination = self.fresh_register();
+        self.instructions.push(Instruction::Cast {
+            operands,
+            destination,
+            type_,
+        });
 
-                ident_operand
-            };
+        vec![Operand::Register(destination)]
+    }
 
-            // Push operand name to struct init instruction.
-            write!(struct_init_instruction, "{} ", operand).expect("failed to write to string");
-        }
+    fn visit_member_access(&mut self, input: &'a MemberAccess) -> Vec<Operand> {
+        let inner = self.visit_operand(&input.inner);
 
-        // Push destination register to struct init instruction.
-        let destination_register = format!("r{}", self.next_register);
-        writeln!(
-            struct_init_instruction,
-            "into {dest} as {name};",
-            dest = destination_register,
-            name = name,
-        )
-        .expect("failed to write to string");
+        vec![Operand::Member(Box::new(inner), input.name.to_string())]
+    }
 
-        instructions.push_str(&struct_init_instruction);
+    // Mapping::get(balances, addr) -> get balances[addr] into r0;
+    fn visit_mapping_operation(&mut self, input: &'a AssociatedFunction) -> Vec<Operand> {
+        // Note that the mapping is referred to by name, rather than by a register.
+        let mapping = match &input.args[0] {
+            Expression::Identifier(identifier) => identifier.to_string(),
+            _ => unreachable!("Type checking guarantees that the first argument of a mapping operation is a mapping."),
+        };
 
-        // Increment the register counter.
-        self.next_register += 1;
+        // Visit the remaining arguments.
+        let mut args = input.args[1..]
+            .iter()
+            .map(|arg| self.visit_operand(arg))
+            .collect::<Vec<_>>()
+            .into_iter();
+        let key = args.next().unwrap();
+
+        // `Mapping::set` does not produce an output.
+        if input.name.name == sym::set {
+            let value = args.next().unwrap();
+            self.instructions.push(Instruction::Set { mapping, key, value });
+            return Vec::new();
+        }
 
-        (destination_register, instructions)
+        let destination = self.fresh_register();
+        self.instructions.push(match input.name.name {
+            sym::get => Instruction::Get {
+                mapping,
+                key,
+                destination,
+            },
+            sym::get_or_use => Instruction::GetOrUse {
+                mapping,
+                key,
+                default: args.next().unwrap(),
+                destination,
+            },
+            _ => unreachable!("Type checking guarantees that all mapping operations are known."),
+        });
+
+        vec![Operand::Register(destination)]
     }
 
-    fn visit_member_access(&mut self, input: &'a MemberAccess) -> (String, String) {
-        let (inner_struct, _inner_instructions) = self.visit_expression(&input.inner);
-        let member_access_instruction = format!("{}.{}", inner_struct, input.name);
+    // ChaCha::rand_u64() -> rand.chacha into r0 as u64;
+    fn visit_random(&mut self, input: &'a AssociatedFunction) -> Vec<Operand> {
+        let type_ = CoreInstruction::from_symbols(sym::ChaCha, input.name.name)
+            .expect("All core function calls should be known at this time.")
+            .return_type();
 
-        (member_access_instruction, String::new())
+        let destination = self.fresh_register();
+        self.instructions.push(Instruction::RandChaCha {
+            destination,
+            type_: type_.to_string(),
+        });
+
+        vec![Operand::Register(destination)]
     }
 
     // Pedersen64::hash() -> hash.ped64
-    fn visit_associated_function(&mut self, input: &'a AssociatedFunction) -> (String, String) {
+    fn visit_associated_function(&mut self, input: &'a AssociatedFunction) -> Vec<Operand> {
+        if matches!(input.ty, Type::Identifier(identifier) if identifier.name == sym::Mapping) {
+            return self.visit_mapping_operation(input);
+        }
+        if matches!(input.ty, Type::Identifier(identifier) if identifier.name == sym::ChaCha) {
+            return self.visit_random(input);
+        }
+
         // Write identifier as opcode. `Pedersen64` -> `ped64`.
         let symbol: &str = if let Type::Identifier(identifier) = input.ty {
             match identifier.name {
+                // `signature::verify` is the `sign.verify` instruction.
+                sym::signature => "sign",
                 sym::BHP256 => "bhp256",
                 sym::BHP512 => "bhp512",
                 sym::BHP768 => "bhp768",
@@ -243,76 +307,79 @@ impl<'a> CodeGenerator<'a> {
             unreachable!("All core function should be known at this time.")
         };
 
-        // Construct associated function call.
-        let mut associated_function_call = format!("    {}.{} ", input.name, symbol);
-        let mut instructions = String::new();
-
-        // Visit each function argument and accumulate instructions from expressions.
-        for arg in input.args.iter() {
-            let (arg_string, arg_instructions) = self.visit_expression(arg);
-            write!(associated_function_call, "{} ", arg_string).expect("failed to write associated function argument");
-            instructions.push_str(&arg_instructions);
-        }
+        // Visit each function argument.
+        let arguments = input.args.iter().map(|arg| self.visit_operand(arg)).collect();
 
-        // Push destination register to associated function call instruction.
-        let destination_register = format!("r{}", self.next_register);
-        writeln!(associated_function_call, "into {};", destination_register)
-            .expect("failed to write dest register for associated function");
-        instructions.push_str(&associated_function_call);
+        let destination = self.fresh_register();
+        self.instructions.push(Instruction::Core {
+            opcode: match symbol {
+                "sign" => format!("{}.{}", symbol, input.name),
+                _ => format!("{}.{}", input.name, symbol),
+            },
+            arguments,
+            destination,
+        });
 
-        // Increment the register counter.
-        self.next_register += 1;
-
-        (destination_register, instructions)
+        vec![Operand::Register(destination)]
     }
 
-    fn visit_access(&mut self, input: &'a AccessExpression) -> (String, String) {
+    fn visit_access(&mut self, input: &'a AccessExpression) -> Vec<Operand> {
         match input {
+            AccessExpression::Array(_) => unreachable!("`ArrayAccess`es should be lowered during flattening."),
             AccessExpression::Member(access) => self.visit_member_access(access),
-            AccessExpression::AssociatedConstant(_) => todo!(), // Associated constants are not supported in AVM yet.
+            // Associated constants are not supported in AVM yet.
+            // Note that the returned operand is a placeholder, since no program is produced once an error is emitted.
+            AccessExpression::AssociatedConstant(constant) => {
+                self.handler.emit_err(CodeGenError::unsupported_expression(
+                    "associated constant",
+                    input,
+                    constant.span,
+                ));
+                vec![Operand::Name(input.to_string())]
+            }
             AccessExpression::AssociatedFunction(function) => self.visit_associated_function(function),
-            AccessExpression::Tuple(_) => todo!(), // Tuples are not supported in AVM yet.
+            AccessExpression::Tuple(_) => {
+                unreachable!("Tuple accesses are not supported at this phase of compilation")
+            }
         }
     }
 
-    fn visit_call(&mut self, input: &'a CallExpression) -> (String, String) {
-        let mut call_instruction = match &input.external {
-            Some(external) => format!("    call {}.aleo/{} ", external, input.function),
-            None => format!("    call {} ", input.function),
+    fn visit_call(&mut self, input: &'a CallExpression) -> Vec<Operand> {
+        let arguments = input
+            .arguments
+            .iter()
+            .map(|argument| self.visit_operand(argument))
+            .collect();
+
+        // Lookup the number of outputs of the function.
+        // Note that the functions of imported programs are recorded when their stubs are visited.
+        let num_outputs = match &*input.function {
+            Expression::Identifier(function) => self.function_outputs.get(&function.name).copied().unwrap_or(1),
+            _ => 1,
         };
-        let mut instructions = String::new();
 
-        for argument in input.arguments.iter() {
-            let (argument, argument_instructions) = self.visit_expression(argument);
-            write!(call_instruction, "{} ", argument).expect("failed to write to string");
-            instructions.push_str(&argument_instructions);
-        }
+        // Assign a destination register to each output.
+        let destinations = (0..num_outputs.max(1))
+            .map(|_| self.fresh_register())
+            .collect::<Vec<_>>();
 
-        // Push destination register to call instruction.
-        let destination_register = format!("r{}", self.next_register);
-        writeln!(call_instruction, "into {};", destination_register).expect("failed to write to string");
-        instructions.push_str(&call_instruction);
+        self.instructions.push(Instruction::Call {
+            program: input.external.as_ref().map(|external| external.to_string()),
+            function: input.function.to_string(),
+            arguments,
+            destinations: destinations.clone(),
+        });
 
-        // Increment the register counter.
-        self.next_register += 1;
-
-        (destination_register, instructions)
+        // Note that the destination registers of a call to a function with multiple outputs are represented as a tuple.
+        destinations.into_iter().map(Operand::Register).collect()
     }
 
-    fn visit_tuple(&mut self, input: &'a TupleExpression) -> (String, String) {
-        // Need to return a single string here so we will join the tuple elements with '\n'
-        // and split them after this method is called.
-        let mut tuple_elements = String::new();
-        let mut instructions = String::new();
-
-        // Visit each tuple element and accumulate instructions from expressions.
-        for element in input.elements.iter() {
-            let (element, element_instructions) = self.visit_expression(element);
-            writeln!(tuple_elements, "{}", element).expect("failed to write tuple to string");
-            instructions.push_str(&element_instructions);
-        }
-
-        // CAUTION: does not return the destination_register.
-        (tuple_elements, instructions)
+    fn visit_tuple(&mut self, input: &'a TupleExpression) -> Vec<Operand> {
+        // Visit each tuple element.
+        input
+            .elements
+            .iter()
+            .map(|element| self.visit_operand(element))
+            .collect()
     }
 }
@@ -17,269 +17,255 @@
 use crate::CodeGenerator;
 
 use leo_ast::{functions, CallType, Function, Identifier, Mapping, Mode, Program, ProgramScope, Struct, Type};
+use leo_ir::{self as ir, Composite, Finalize, FunctionKind, Input, Operand, ValueType, Visibility};
 
 use indexmap::IndexMap;
 use itertools::Itertools;
 use leo_span::sym;
-use std::fmt::Write as _;
 
 impl<'a> CodeGenerator<'a> {
-    pub(crate) fn visit_program(&mut self, input: &'a Program) -> String {
-        // Accumulate instructions into a program string.
-        let mut program_string = String::new();
-
-        if !input.imports.is_empty() {
-            // Visit each import statement and produce a Aleo import instruction.
-            program_string.push_str(
-                &input
-                    .imports
-                    .iter()
-                    .map(|(identifier, imported_program)| self.visit_import(identifier, imported_program))
-                    .join("\n"),
-            );
-
-            // Newline separator.
-            program_string.push('\n');
-        }
+    pub(crate) fn visit_program(&mut self, input: &'a Program) -> ir::Program {
+        // Visit each import statement.
+        let imports = input
+            .imports
+            .iter()
+            .map(|(identifier, imported_program)| self.visit_import(identifier, imported_program))
+            .collect();
 
         // Retrieve the program scope.
         // Note that type checking guarantees that there is exactly one program scope.
         let program_scope: &ProgramScope = input.program_scopes.values().next().unwrap();
 
-        // Print the program id.
-        writeln!(program_string, "program {};", program_scope.program_id)
-            .expect("Failed to write program id to string.");
-
-        // Newline separator.
-        program_string.push('\n');
-
-        // Visit each `Struct` or `Record` in the Leo AST and produce a Aleo interface instruction.
-        program_string.push_str(
-            &program_scope
-                .structs
-                .values()
-                .map(|struct_| self.visit_struct_or_record(struct_))
-                .join("\n"),
-        );
-
-        // Newline separator.
-        program_string.push('\n');
+        // Visit each `Struct` or `Record` in the Leo AST and produce an Aleo interface or record declaration.
+        // Structs are declared in post-order of the struct graph, so that every struct is declared after the structs that it has members of.
+        // Note that this unwrap is safe since type checking guarantees that the struct graph is acyclic.
+        let order = self.struct_graph.post_order().unwrap();
+        let composites = program_scope
+            .structs
+            .values()
+            .sorted_by_key(|struct_| order.get_index_of(&struct_.name()))
+            .map(|struct_| self.visit_struct_or_record(struct_))
+            .collect();
 
         // Visit each mapping in the Leo AST and produce an Aleo mapping declaration.
-        program_string.push_str(
-            &program_scope
-                .mappings
-                .values()
-                .map(|mapping| self.visit_mapping(mapping))
-                .join("\n"),
+        let mappings = program_scope
+            .mappings
+            .values()
+            .map(|mapping| self.visit_mapping(mapping))
+            .collect();
+
+        // Store the number of outputs of each function, so that calls are assigned the appropriate number of registers.
+        self.function_outputs.extend(
+            program_scope
+                .functions
+                .iter()
+                .map(|(identifier, function)| (&identifier.name, function.output.len())),
         );
 
-        // Store closures and functions in separate strings.
-        let mut closures = String::new();
-        let mut functions = String::new();
-
-        // Visit each `Function` in the Leo AST and produce Aleo instructions.
-        program_scope.functions.values().for_each(|function| {
-            self.is_transition_function = matches!(function.call_type, CallType::Transition);
-
-            let function_string = self.visit_function(function);
-
-            if self.is_transition_function {
-                functions.push_str(&function_string);
-                functions.push('\n');
-            } else {
-                closures.push_str(&function_string);
-                closures.push('\n');
-            }
-
-            // Unset the `is_transition_function` flag.
-            self.is_transition_function = false;
-        });
-
-        // Closures must precede functions in the Aleo program.
-        program_string.push_str(&closures);
-        program_string.push('\n');
-        program_string.push_str(&functions);
-
-        program_string
+        // Visit each `Function` in the Leo AST and produce an Aleo closure or function.
+        let functions = program_scope
+            .functions
+            .values()
+            .map(|function| {
+                self.is_transition_function = matches!(function.call_type, CallType::Transition);
+                let function = self.visit_function(function);
+
+                // Unset the `is_transition_function` flag.
+                self.is_transition_function = false;
+
+                function
+            })
+            .collect();
+
+        // Note that the compiler checks that the name of the program scope matches the name of the program.
+        ir::Program {
+            name: program_scope.program_id.name.to_string(),
+            network: self.network.to_string(),
+            imports,
+            composites,
+            mappings,
+            functions,
+        }
     }
 
     fn visit_import(&mut self, import_name: &'a Identifier, import_program: &'a Program) -> String {
         // Load symbols into composite mapping.
-        let _import_program_string = self.visit_program(import_program);
-        // todo: We do not need the import program string because we generate instructions for imports separately during leo build.
-
-        // Generate string for import statement.
-        format!("import {}.aleo;", import_name)
-    }
+        let _import_program = self.visit_program(import_program);
+        // todo: We do not need the import program because we generate instructions for imports separately during leo build.
 
-    fn visit_struct_or_record(&mut self, struct_: &'a Struct) -> String {
-        if struct_.is_record {
-            self.visit_record(struct_)
-        } else {
-            self.visit_struct(struct_)
-        }
-    }
-
-    fn visit_struct(&mut self, struct_: &'a Struct) -> String {
-        // Add private symbol to composite types.
-        self.composite_mapping
-            .insert(&struct_.identifier.name, (false, String::from("private"))); // todo: private by default here.
-
-        let mut output_string = format!("interface {}:\n", struct_.identifier); // todo: check if this is safe from name conflicts.
-
-        // Construct and append the record variables.
-        for var in struct_.members.iter() {
-            writeln!(output_string, "    {} as {};", var.identifier, var.type_,).expect("failed to write to string");
-        }
-
-        output_string
+        import_name.to_string()
     }
 
-    fn visit_record(&mut self, record: &'a Struct) -> String {
-        // Add record symbol to composite types.
-        let mut output_string = String::from("record");
+    fn visit_struct_or_record(&mut self, struct_: &'a Struct) -> Composite {
+        // Add the composite to the composite types.
         self.composite_mapping
-            .insert(&record.identifier.name, (true, output_string.clone()));
-        writeln!(output_string, " {}:", record.identifier).expect("failed to write to string"); // todo: check if this is safe from name conflicts.
-
-        // Construct and append the record variables.
-        for var in record.members.iter() {
-            writeln!(
-                output_string,
-                "    {} as {}.private;", // todo: CAUTION private record variables only.
-                var.identifier, var.type_,
-            )
-            .expect("failed to write to string");
+            .insert(&struct_.identifier.name, struct_.is_record);
+
+        // todo: check if this is safe from name conflicts.
+        // Note that struct members have no visibility, and record members are private.
+        let visibility = struct_.is_record.then_some(Visibility::Private); // todo: CAUTION private record variables only.
+        let members = struct_
+            .members
+            .iter()
+            .map(|member| {
+                (
+                    member.identifier.to_string(),
+                    ValueType::Plaintext(member.type_.to_string(), visibility),
+                )
+            })
+            .collect();
+
+        Composite {
+            name: struct_.identifier.to_string(),
+            is_record: struct_.is_record,
+            members,
         }
-
-        output_string
     }
 
-    fn visit_function(&mut self, function: &'a Function) -> String {
+    fn visit_function(&mut self, function: &'a Function) -> ir::Function {
         // Initialize the state of `self` with the appropriate values before visiting `function`.
         self.next_register = 0;
         self.variable_mapping = IndexMap::new();
         // TODO: Figure out a better way to initialize.
-        self.variable_mapping.insert(&sym::SelfLower, "self".to_string());
+        self.variable_mapping
+            .insert(&sym::SelfLower, vec![Operand::Name("self".to_string())]);
         self.current_function = Some(function);
 
-        // Construct the header of the function.
         // If a function is a program function, generate an Aleo `function`, otherwise generate an Aleo `closure`.
-        let mut function_string = match self.is_transition_function {
-            true => format!("function {}:\n", function.identifier),
-            false => format!("closure {}:\n", function.identifier),
+        let kind = match self.is_transition_function {
+            true => FunctionKind::Function,
+            false => FunctionKind::Closure,
         };
 
-        // Construct and append the input declarations of the function.
-        for input in function.input.iter() {
-            let register_string = format!("r{}", self.next_register);
-            self.next_register += 1;
-
-            let type_string = match input {
-                functions::Input::Internal(input) => {
-                    self.variable_mapping
-                        .insert(&input.identifier.name, register_string.clone());
-                    let visibility = match (self.is_transition_function, input.mode) {
-                        (true, Mode::None) => Mode::Private,
-                        _ => input.mode,
-                    };
-                    self.visit_type_with_visibility(&input.type_, visibility)
-                }
-                functions::Input::External(input) => {
-                    self.variable_mapping
-                        .insert(&input.identifier.name, register_string.clone());
-                    format!("{}.aleo/{}.record", input.program_name, input.record)
-                }
-            };
-
-            writeln!(function_string, "    input {} as {};", register_string, type_string,)
-                .expect("failed to write to string");
-        }
-
-        //  Construct and append the function body.
-        let block_string = self.visit_block(&function.block);
-        function_string.push_str(&block_string);
+        // Construct the input declarations of the function.
+        let inputs = function
+            .input
+            .iter()
+            .map(|input| {
+                let register = self.fresh_register();
 
-        // If the finalize block exists, generate the appropriate bytecode.
-        if let Some(finalize) = &function.finalize {
-            // Clear the register count.
-            self.next_register = 0;
-            self.in_finalize = true;
-
-            // Clear the variable mapping.
-            // TODO: Figure out a better way to initialize.
-            self.variable_mapping = IndexMap::new();
-            self.variable_mapping.insert(&sym::SelfLower, "self".to_string());
-
-            function_string.push_str(&format!("\nfinalize {}:\n", finalize.identifier));
-
-            // Construct and append the input declarations of the finalize block.
-            for input in finalize.input.iter() {
-                let register_string = format!("r{}", self.next_register);
-                self.next_register += 1;
-
-                // TODO: Dedup code.
-                let type_string = match input {
+                let type_ = match input {
                     functions::Input::Internal(input) => {
                         self.variable_mapping
-                            .insert(&input.identifier.name, register_string.clone());
-
+                            .insert(&input.identifier.name, vec![Operand::Register(register)]);
                         let visibility = match (self.is_transition_function, input.mode) {
-                            (true, Mode::None) => Mode::Public,
+                            (true, Mode::None) => Mode::Private,
                             _ => input.mode,
                         };
                         self.visit_type_with_visibility(&input.type_, visibility)
                     }
                     functions::Input::External(input) => {
                         self.variable_mapping
-                            .insert(&input.program_name.name, register_string.clone());
-                        format!("{}.aleo/{}.record", input.program_name, input.record)
+                            .insert(&input.identifier.name, vec![Operand::Register(register)]);
+                        ValueType::ExternalRecord {
+                            program: input.program_name.to_string(),
+                            record: input.record.to_string(),
+                        }
                     }
                 };
 
-                writeln!(function_string, "    input {} as {};", register_string, type_string,)
-                    .expect("failed to write to string");
-            }
+                Input { register, type_ }
+            })
+            .collect();
+
+        // Construct the function body.
+        self.visit_block(&function.block);
+        let (instructions, locations) = self.take_instructions();
 
-            // Construct and append the finalize block body.
-            function_string.push_str(&self.visit_block(&finalize.block));
+        // If the finalize block exists, generate the appropriate instructions.
+        let finalize = function.finalize.as_ref().map(|finalize| {
+            // Clear the register count.
+            self.next_register = 0;
+            self.in_finalize = true;
+
+            // Clear the variable mapping.
+            // TODO: Figure out a better way to initialize.
+            self.variable_mapping = IndexMap::new();
+            self.variable_mapping
+                .insert(&sym::SelfLower, vec![Operand::Name("self".to_string())]);
+            self.variable_mapping
+                .insert(&sym::block, vec![Operand::Name("block".to_string())]);
+
+            // Construct the input declarations of the finalize block.
+            let inputs = finalize
+                .input
+                .iter()
+                .map(|input| {
+                    let register = self.fresh_register();
+
+                    // TODO: Dedup code.
+                    let type_ = match input {
+                        functions::Input::Internal(input) => {
+                            self.variable_mapping
+                                .insert(&input.identifier.name, vec![Operand::Register(register)]);
+
+                            let visibility = match (self.is_transition_function, input.mode) {
+                                (true, Mode::None) => Mode::Public,
+                                _ => input.mode,
+                            };
+                            self.visit_type_with_visibility(&input.type_, visibility)
+                        }
+                        functions::Input::External(input) => {
+                            self.variable_mapping
+                                .insert(&input.program_name.name, vec![Operand::Register(register)]);
+                            ValueType::ExternalRecord {
+                                program: input.program_name.to_string(),
+                                record: input.record.to_string(),
+                            }
+                        }
+                    };
+
+                    Input { register, type_ }
+                })
+                .collect();
+
+            // Construct the finalize block body.
+            self.visit_block(&finalize.block);
 
             self.in_finalize = false;
-        }
 
-        function_string
-    }
+            let (instructions, locations) = self.take_instructions();
+            Finalize {
+                name: finalize.identifier.to_string(),
+                inputs,
+                instructions,
+                locations,
+            }
+        });
 
-    fn visit_mapping(&mut self, mapping: &'a Mapping) -> String {
-        // Create the prefix of the mapping string, e.g. `mapping foo:`.
-        let mut mapping_string = format!("mapping {}:\n", mapping.identifier);
+        ir::Function {
+            kind,
+            name: function.identifier.to_string(),
+            inputs,
+            instructions,
+            locations,
+            finalize,
+        }
+    }
 
-        // Helper to construct the string associated with the type.
+    fn visit_mapping(&mut self, mapping: &'a Mapping) -> ir::Mapping {
+        // Helper to construct the type of a key or value.
         let create_type = |type_: &Type| {
             match type_ {
                 Type::Mapping(_) | Type::Tuple(_) => unreachable!("Mappings cannot contain mappings or tuples."),
                 Type::Identifier(identifier) => {
                     // Lookup the type in the composite mapping.
                     // Note that this unwrap is safe since all struct and records have been added to the composite mapping.
-                    let (is_record, _) = self.composite_mapping.get(&identifier.name).unwrap();
-                    match is_record {
+                    match self.composite_mapping.get(&identifier.name).unwrap() {
                         // If the type is a record, then declare the type as is.
-                        true => format!("{}.record", identifier),
+                        true => ValueType::Record(identifier.to_string()),
                         // If the type is a struct, then add the public modifier.
-                        false => format!("{}.public", identifier),
+                        false => ValueType::Plaintext(identifier.to_string(), Some(Visibility::Public)),
                     }
                 }
-                type_ => format!("{}.public", type_),
+                type_ => ValueType::Plaintext(type_.to_string(), Some(Visibility::Public)),
             }
         };
 
-        // Create the key string, e.g. `    key as address.public`.
-        mapping_string.push_str(&format!("\tkey left as {};\n", create_type(&mapping.key_type)));
-
-        // Create the value string, e.g. `    value as address.public`.
-        mapping_string.push_str(&format!("\tvalue right as {};\n", create_type(&mapping.value_type)));
-
-        mapping_string
+        ir::Mapping {
+            name: mapping.identifier.to_string(),
+            key: create_type(&mapping.key_type),
+            value: create_type(&mapping.value_type),
+        }
     }
 }
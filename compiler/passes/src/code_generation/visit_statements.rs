@@ -17,174 +17,175 @@
 use crate::CodeGenerator;
 
 use leo_ast::{
-    AssignStatement, Block, ConditionalStatement, ConsoleFunction, ConsoleStatement, DecrementStatement,
-    DefinitionStatement, Expression, FinalizeStatement, IncrementStatement, IterationStatement, Mode, Output,
-    ReturnStatement, Statement,
+    AssertStatement, AssertVariant, AssignStatement, Block, CfgStatement, ConditionalStatement, DecrementStatement,
+    DefinitionStatement, Expression, ExpressionStatement, FinalizeStatement, IncrementStatement, IterationStatement,
+    MatchStatement, Mode, Output, ReturnStatement, Statement,
 };
+use leo_ir::{Instruction, Operand, ValueType};
 
 use itertools::Itertools;
-use std::fmt::Write as _;
 
 impl<'a> CodeGenerator<'a> {
-    fn visit_statement(&mut self, input: &'a Statement) -> String {
+    fn visit_statement(&mut self, input: &'a Statement) {
         match input {
+            Statement::Assert(stmt) => self.visit_assert(stmt),
             Statement::Assign(stmt) => self.visit_assign(stmt),
             Statement::Block(stmt) => self.visit_block(stmt),
+            Statement::Cfg(stmt) => self.visit_cfg(stmt),
             Statement::Conditional(stmt) => self.visit_conditional(stmt),
-            Statement::Console(stmt) => self.visit_console(stmt),
             Statement::Decrement(stmt) => self.visit_decrement(stmt),
             Statement::Definition(stmt) => self.visit_definition(stmt),
+            Statement::Expression(stmt) => self.visit_expression_statement(stmt),
             Statement::Finalize(stmt) => self.visit_finalize(stmt),
             Statement::Increment(stmt) => self.visit_increment(stmt),
             Statement::Iteration(stmt) => self.visit_iteration(stmt),
+            Statement::Match(stmt) => self.visit_match(stmt),
             Statement::Return(stmt) => self.visit_return(stmt),
         }
+
+        // Attribute the instructions generated by the statement to its location, unless a nested statement already has.
+        self.locations.resize(self.instructions.len(), Self::location_of(input));
     }
 
-    fn visit_return(&mut self, input: &'a ReturnStatement) -> String {
+    fn visit_return(&mut self, input: &'a ReturnStatement) {
         match input.expression {
             // Skip empty return statements.
-            Expression::Tuple(ref tuple) if tuple.elements.is_empty() => String::new(),
+            Expression::Tuple(ref tuple) if tuple.elements.is_empty() => {}
             _ => {
-                let (operand, mut expression_instructions) = self.visit_expression(&input.expression);
-                let instructions = operand
-                    .split('\n')
-                    .into_iter()
-                    .zip(self.current_function.unwrap().output.iter())
-                    .map(|(operand, output)| {
-                        match output {
-                            Output::Internal(output) => {
-                                let visibility = if self.is_transition_function {
-                                    match self.in_finalize {
-                                        // If in finalize block, the default visibility is public.
-                                        true => match output.mode {
-                                            Mode::None => Mode::Public,
-                                            mode => mode,
-                                        },
-                                        // If not in finalize block, the default visibility is private.
-                                        false => match output.mode {
-                                            Mode::None => Mode::Private,
-                                            mode => mode,
-                                        },
-                                    }
-                                } else {
-                                    // Only program functions have visibilities associated with their outputs.
-                                    Mode::None
-                                };
-                                format!(
-                                    "    output {} as {};\n",
-                                    operand,
-                                    self.visit_type_with_visibility(&output.type_, visibility)
-                                )
-                            }
-                            Output::External(output) => {
-                                format!(
-                                    "    output {} as {}.aleo/{}.record;\n",
-                                    operand, output.program_name, output.record,
-                                )
-                            }
+                let operands = self.visit_expression(&input.expression);
+                for (operand, output) in operands.into_iter().zip(self.current_function.unwrap().output.iter()) {
+                    let type_ = match output {
+                        Output::Internal(output) => {
+                            let visibility = if self.is_transition_function {
+                                match self.in_finalize {
+                                    // If in finalize block, the default visibility is public.
+                                    true => match output.mode {
+                                        Mode::None => Mode::Public,
+                                        mode => mode,
+                                    },
+                                    // If not in finalize block, the default visibility is private.
+                                    false => match output.mode {
+                                        Mode::None => Mode::Private,
+                                        mode => mode,
+                                    },
+                                }
+                            } else {
+                                // Only program functions have visibilities associated with their outputs.
+                                Mode::None
+                            };
+                            self.visit_type_with_visibility(&output.type_, visibility)
                         }
-                    })
-                    .join("");
-
-                expression_instructions.push_str(&instructions);
-
-                expression_instructions
+                        Output::External(output) => ValueType::ExternalRecord {
+                            program: output.program_name.to_string(),
+                            record: output.record.to_string(),
+                        },
+                    };
+                    self.instructions.push(Instruction::Output(operand, type_));
+                }
             }
         }
     }
 
-    fn visit_definition(&mut self, _input: &'a DefinitionStatement) -> String {
+    fn visit_definition(&mut self, _input: &'a DefinitionStatement) {
         // TODO: If SSA is made optional, then conditionally enable codegen for DefinitionStatement
-        // let (operand, expression_instructions) = self.visit_expression(&input.value);
-        // self.variable_mapping.insert(&input.variable_name.name, operand);
-        // expression_instructions
+        // let operands = self.visit_expression(&input.value);
+        // self.variable_mapping.insert(&input.variable_name.name, operands);
         unreachable!("DefinitionStatement's should not exist in SSA form.")
     }
 
-    fn visit_increment(&mut self, input: &'a IncrementStatement) -> String {
-        let (index, mut instructions) = self.visit_expression(&input.index);
-        let (amount, amount_instructions) = self.visit_expression(&input.amount);
-        instructions.push_str(&amount_instructions);
-        instructions.push_str(&format!("    increment {}[{}] by {};\n", input.mapping, index, amount));
-
-        instructions
+    fn visit_increment(&mut self, input: &'a IncrementStatement) {
+        let key = self.visit_operand(&input.index);
+        let amount = self.visit_operand(&input.amount);
+        self.instructions.push(Instruction::Increment {
+            mapping: input.mapping.to_string(),
+            key,
+            amount,
+        });
     }
 
-    fn visit_decrement(&mut self, input: &'a DecrementStatement) -> String {
-        let (index, mut instructions) = self.visit_expression(&input.index);
-        let (amount, amount_instructions) = self.visit_expression(&input.amount);
-        instructions.push_str(&amount_instructions);
-        instructions.push_str(&format!("    decrement {}[{}] by {};\n", input.mapping, index, amount));
-
-        instructions
+    fn visit_decrement(&mut self, input: &'a DecrementStatement) {
+        let key = self.visit_operand(&input.index);
+        let amount = self.visit_operand(&input.amount);
+        self.instructions.push(Instruction::Decrement {
+            mapping: input.mapping.to_string(),
+            key,
+            amount,
+        });
     }
 
-    fn visit_finalize(&mut self, input: &'a FinalizeStatement) -> String {
-        let mut instructions = String::new();
-        let mut finalize_instruction = "    finalize".to_string();
-
-        for argument in input.arguments.iter() {
-            let (argument, argument_instructions) = self.visit_expression(argument);
-            write!(finalize_instruction, " {}", argument).expect("failed to write to string");
-            instructions.push_str(&argument_instructions);
-        }
-        writeln!(finalize_instruction, ";").expect("failed to write to string");
+    fn visit_expression_statement(&mut self, input: &'a ExpressionStatement) {
+        // The operands produced by the expression, if any, are unused.
+        self.visit_expression(&input.expression);
+    }
 
-        finalize_instruction
+    fn visit_finalize(&mut self, input: &'a FinalizeStatement) {
+        let arguments = input
+            .arguments
+            .iter()
+            .map(|argument| self.visit_operand(argument))
+            .collect();
+        self.instructions.push(Instruction::Finalize(arguments));
     }
 
-    fn visit_assign(&mut self, input: &'a AssignStatement) -> String {
+    fn visit_assign(&mut self, input: &'a AssignStatement) {
         match &input.place {
             Expression::Identifier(identifier) => {
-                let (operand, expression_instructions) = self.visit_expression(&input.value);
-                self.variable_mapping.insert(&identifier.name, operand);
-                expression_instructions
+                let operands = self.visit_expression(&input.value);
+                self.variable_mapping.insert(&identifier.name, operands);
+            }
+            // Each output of a call is assigned to the corresponding variable.
+            Expression::Tuple(tuple) => {
+                let operands = self.visit_expression(&input.value);
+                for (element, operand) in tuple.elements.iter().zip_eq(operands) {
+                    match element {
+                        Expression::Identifier(identifier) => {
+                            self.variable_mapping.insert(&identifier.name, vec![operand]);
+                        }
+                        _ => unreachable!("The tuple lowering pass guarantees that calls are destructured into variables."),
+                    }
+                }
             }
             _ => unimplemented!(
-                "Code generation for the left-hand side of an assignment is only implemented for `Identifier`s."
+                "Code generation for the left-hand side of an assignment is only implemented for `Identifier`s and tuples of them."
             ),
         }
     }
 
-    fn visit_conditional(&mut self, _input: &'a ConditionalStatement) -> String {
+    fn visit_conditional(&mut self, _input: &'a ConditionalStatement) {
         // TODO: Once SSA is made optional, create a Leo error informing the user to enable the SSA pass.
         unreachable!("`ConditionalStatement`s should not be in the AST at this phase of compilation.")
     }
 
-    fn visit_iteration(&mut self, _input: &'a IterationStatement) -> String {
+    fn visit_iteration(&mut self, _input: &'a IterationStatement) {
         // TODO: Once loop unrolling is made optional, create a Leo error informing the user to enable the loop unrolling pass..
         unreachable!("`IterationStatement`s should not be in the AST at this phase of compilation.");
     }
 
-    fn visit_console(&mut self, input: &'a ConsoleStatement) -> String {
-        let mut generate_assert_instruction = |name: &str, left: &'a Expression, right: &'a Expression| {
-            let (left_operand, left_instructions) = self.visit_expression(left);
-            let (right_operand, right_instructions) = self.visit_expression(right);
-            let assert_instruction = format!("    {} {} {};\n", name, left_operand, right_operand);
-
-            // Concatenate the instructions.
-            let mut instructions = left_instructions;
-            instructions.push_str(&right_instructions);
-            instructions.push_str(&assert_instruction);
+    fn visit_cfg(&mut self, _input: &'a CfgStatement) {
+        unreachable!("`CfgStatement`s should not be in the AST at this phase of compilation.");
+    }
 
-            instructions
-        };
-        match &input.function {
-            ConsoleFunction::Assert(expr) => {
-                let (operand, mut instructions) = self.visit_expression(expr);
-                let assert_instruction = format!("    assert.eq {} true;\n", operand);
+    fn visit_match(&mut self, _input: &'a MatchStatement) {
+        unreachable!("`MatchStatement`s should not be in the AST at this phase of compilation.");
+    }
 
-                instructions.push_str(&assert_instruction);
-                instructions
+    fn visit_assert(&mut self, input: &'a AssertStatement) {
+        let instruction = match &input.variant {
+            AssertVariant::Assert(expr) => {
+                Instruction::AssertEq(self.visit_operand(expr), Operand::Literal("true".to_string()))
             }
-            ConsoleFunction::AssertEq(left, right) => generate_assert_instruction("assert.eq", left, right),
-            ConsoleFunction::AssertNeq(left, right) => generate_assert_instruction("assert.neq", left, right),
-        }
+            AssertVariant::AssertEq(left, right) => {
+                Instruction::AssertEq(self.visit_operand(left), self.visit_operand(right))
+            }
+            AssertVariant::AssertNeq(left, right) => {
+                Instruction::AssertNeq(self.visit_operand(left), self.visit_operand(right))
+            }
+        };
+        self.instructions.push(instruction);
     }
 
-    pub(crate) fn visit_block(&mut self, input: &'a Block) -> String {
-        // For each statement in the block, visit it and add its instructions to the list.
-        input.statements.iter().map(|stmt| self.visit_statement(stmt)).join("")
+    pub(crate) fn visit_block(&mut self, input: &'a Block) {
+        // Visit each statement in the block, appending its instructions to the current instructions.
+        input.statements.iter().for_each(|stmt| self.visit_statement(stmt));
     }
 }
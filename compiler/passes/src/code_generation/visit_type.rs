@@ -17,40 +17,57 @@
 use crate::CodeGenerator;
 
 use leo_ast::{Mode, Type};
+use leo_ir::{ValueType, Visibility};
 
 impl<'a> CodeGenerator<'a> {
-    fn visit_type(&mut self, input: &'a Type) -> String {
+    pub(crate) fn visit_type(&mut self, input: &'a Type) -> String {
         match input {
             Type::Address
             | Type::Boolean
             | Type::Field
             | Type::Group
             | Type::Scalar
-            | Type::String
-            | Type::Integer(..) => format!("{}", input),
+            | Type::Signature
+            | Type::Integer(..) => {
+                format!("{}", input)
+            }
             Type::Identifier(ident) => format!("{}", ident),
+            Type::Array(..) => {
+                unreachable!("Array types are not supported at this phase of compilation")
+            }
+            Type::String(_) => {
+                unreachable!("String types are lowered into fields before code generation")
+            }
             Type::Mapping(_) => {
                 unreachable!("Mapping types are not supported at this phase of compilation")
             }
             Type::Tuple(_) => {
                 unreachable!("Tuple types are not supported at this phase of compilation")
             }
+            Type::StructInstance(_) => {
+                unreachable!("Generic struct instances are monomorphized before code generation")
+            }
             Type::Err => unreachable!("Error types should not exist at this phase of compilation"),
             Type::Unit => unreachable!("Unit types are not supported at this phase of compilation"),
         }
     }
 
-    pub(crate) fn visit_type_with_visibility(&mut self, type_: &'a Type, visibility: Mode) -> String {
+    pub(crate) fn visit_type_with_visibility(&mut self, type_: &'a Type, visibility: Mode) -> ValueType {
         match type_ {
             // When the type is a record.
             // Note that this unwrap is safe because all composite types have been added to the mapping.
-            Type::Identifier(identifier) if self.composite_mapping.get(&identifier.name).unwrap().0 => {
-                format!("{}.record", identifier)
+            Type::Identifier(identifier) if *self.composite_mapping.get(&identifier.name).unwrap() => {
+                ValueType::Record(identifier.to_string())
+            }
+            _ => {
+                let visibility = match visibility {
+                    Mode::None => None,
+                    Mode::Const => Some(Visibility::Constant),
+                    Mode::Private => Some(Visibility::Private),
+                    Mode::Public => Some(Visibility::Public),
+                };
+                ValueType::Plaintext(self.visit_type(type_), visibility)
             }
-            _ => match visibility {
-                Mode::None => self.visit_type(type_),
-                _ => format!("{}.{}", self.visit_type(type_), visibility),
-            },
         }
     }
 }
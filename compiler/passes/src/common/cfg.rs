@@ -0,0 +1,424 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{
+    Block, ConditionalStatement, Expression, ExpressionVisitor, Identifier, Node, NodeId, Statement, StatementVisitor,
+    StructExpression,
+};
+use leo_span::Symbol;
+
+use indexmap::{IndexMap, IndexSet};
+
+/// The index of a basic block in a control-flow graph.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BasicBlockIndex(pub(crate) usize);
+
+impl BasicBlockIndex {
+    /// The block at which the function body is entered.
+    pub const ENTRY: BasicBlockIndex = BasicBlockIndex(0);
+}
+
+/// A straight-line sequence of statements in a control-flow graph.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BasicBlock {
+    /// The ids of the statements in the block, in order.
+    /// Note that conditional statements and blocks are not included, since they are split into other blocks.
+    pub(crate) statements: Vec<NodeId>,
+    /// The variables that are read in the block before they are written in it.
+    pub(crate) uses: IndexSet<Symbol>,
+    /// The variables that are written in the block.
+    pub(crate) defs: IndexSet<Symbol>,
+    /// The blocks that control flows to from the end of the block.
+    pub(crate) successors: Vec<BasicBlockIndex>,
+}
+
+impl BasicBlock {
+    /// Returns the ids of the statements in the block.
+    pub fn statements(&self) -> &[NodeId] {
+        &self.statements
+    }
+
+    /// Returns the variables that are read in the block before they are written in it.
+    pub fn uses(&self) -> &IndexSet<Symbol> {
+        &self.uses
+    }
+
+    /// Returns the variables that are written in the block.
+    pub fn defs(&self) -> &IndexSet<Symbol> {
+        &self.defs
+    }
+
+    /// Returns the blocks that control flows to from the end of the block.
+    pub fn successors(&self) -> &[BasicBlockIndex] {
+        &self.successors
+    }
+}
+
+/// The control-flow graph of a function body, built from its statements.
+/// Each conditional statement ends the block that evaluates its condition, branches to a block for each of its branches,
+/// and rejoins them in a new block. If a conditional statement has no otherwise-block, its condition also flows directly to the join.
+///
+/// Note that a return does not end its block, even if it is an early return in a branch of a conditional statement.
+/// The flattener folds the returns of a function into a single return at its end, guarded by the conditions under which each is reached,
+/// so the statements after a conditional statement are executed even if one of its branches returns.
+/// The graph is therefore acyclic, and its blocks are only ever given edges to blocks created after them,
+/// so the order of the blocks is a reverse post-order of the graph.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cfg {
+    /// The blocks of the graph, starting with the entry block.
+    blocks: Vec<BasicBlock>,
+    /// The block at which the branches of each conditional statement rejoin, by the id of the conditional statement.
+    joins: IndexMap<NodeId, BasicBlockIndex>,
+}
+
+impl Cfg {
+    /// Builds the control-flow graph of a function body.
+    /// Note that loops and match statements must have been lowered, and `@cfg(..)` annotations evaluated, before the graph is built.
+    pub fn new(body: &Block) -> Self {
+        let mut cfg = Self {
+            blocks: vec![BasicBlock::default()],
+            joins: IndexMap::new(),
+        };
+        cfg.add_statements(BasicBlockIndex::ENTRY, &body.statements);
+        cfg
+    }
+
+    /// Returns the indices of the blocks of the graph, in reverse post-order.
+    pub fn indices(&self) -> impl Iterator<Item = BasicBlockIndex> {
+        (0..self.blocks.len()).map(BasicBlockIndex)
+    }
+
+    /// Returns the block at `index`.
+    pub fn block(&self, index: BasicBlockIndex) -> &BasicBlock {
+        &self.blocks[index.0]
+    }
+
+    /// Returns the block at which the branches of a conditional statement rejoin, if the conditional statement is in the graph.
+    pub fn join(&self, conditional: NodeId) -> Option<BasicBlockIndex> {
+        self.joins.get(&conditional).copied()
+    }
+
+    /// Returns the conditional statements of the graph, with the blocks at which their branches rejoin.
+    pub fn joins(&self) -> impl Iterator<Item = (NodeId, BasicBlockIndex)> + '_ {
+        self.joins.iter().map(|(id, join)| (*id, *join))
+    }
+
+    /// Returns the predecessors of each block, by the index of the block.
+    pub fn predecessors(&self) -> Vec<Vec<BasicBlockIndex>> {
+        let mut predecessors = vec![Vec::new(); self.blocks.len()];
+        for index in self.indices() {
+            for successor in self.block(index).successors.iter() {
+                predecessors[successor.0].push(index);
+            }
+        }
+        predecessors
+    }
+
+    /// Returns the immediate dominator of each block, by the index of the block, where the entry block is its own immediate dominator.
+    /// See "A Simple, Fast Dominance Algorithm" by Cooper, Harvey, and Kennedy.
+    pub fn immediate_dominators(&self) -> Vec<BasicBlockIndex> {
+        let predecessors = self.predecessors();
+
+        // Note that every block is reachable from the entry block, and that the dominators of a block come before it in reverse post-order.
+        let intersect =
+            |dominators: &[Option<BasicBlockIndex>], mut first: BasicBlockIndex, mut second: BasicBlockIndex| {
+                while first != second {
+                    while first > second {
+                        first = dominators[first.0].unwrap();
+                    }
+                    while second > first {
+                        second = dominators[second.0].unwrap();
+                    }
+                }
+                first
+            };
+
+        let mut dominators = vec![None; self.blocks.len()];
+        dominators[0] = Some(BasicBlockIndex::ENTRY);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for index in self.indices().skip(1) {
+                let dominator = predecessors[index.0]
+                    .iter()
+                    .filter(|predecessor| dominators[predecessor.0].is_some())
+                    .fold(None, |dominator, predecessor| match dominator {
+                        None => Some(*predecessor),
+                        Some(dominator) => Some(intersect(&dominators, dominator, *predecessor)),
+                    });
+                if dominator != dominators[index.0] {
+                    dominators[index.0] = dominator;
+                    changed = true;
+                }
+            }
+        }
+        dominators.into_iter().map(Option::unwrap).collect()
+    }
+
+    /// Returns the dominance frontier of each block, by the index of the block.
+    /// The dominance frontier of a block consists of the blocks that it does not strictly dominate, but that have a predecessor that it dominates.
+    pub fn dominance_frontiers(&self) -> Vec<IndexSet<BasicBlockIndex>> {
+        let predecessors = self.predecessors();
+        let dominators = self.immediate_dominators();
+        let mut frontiers = vec![IndexSet::new(); self.blocks.len()];
+        for index in self.indices() {
+            let predecessors = &predecessors[index.0];
+            if predecessors.len() < 2 {
+                continue;
+            }
+            for predecessor in predecessors {
+                let mut runner = *predecessor;
+                while runner != dominators[index.0] {
+                    frontiers[runner.0].insert(index);
+                    runner = dominators[runner.0];
+                }
+            }
+        }
+        frontiers
+    }
+
+    /// Returns the variables that are live at the start of each block, by the index of the block.
+    /// A variable is live if it may be read before it is written again.
+    pub fn live_in(&self) -> Vec<IndexSet<Symbol>> {
+        let mut live_in: Vec<IndexSet<Symbol>> = vec![IndexSet::new(); self.blocks.len()];
+        // Since the graph is acyclic and its blocks are in reverse post-order, a single backwards pass suffices.
+        for (index, block) in self.blocks.iter().enumerate().rev() {
+            let live_out: IndexSet<Symbol> = block
+                .successors
+                .iter()
+                .flat_map(|successor| live_in[successor.0].iter().copied())
+                .collect();
+            live_in[index] = block
+                .uses
+                .iter()
+                .copied()
+                .chain(live_out.into_iter().filter(|symbol| !block.defs.contains(symbol)))
+                .collect();
+        }
+        live_in
+    }
+
+    /// Adds a new, empty block to the graph, returning its index.
+    fn add_block(&mut self) -> BasicBlockIndex {
+        self.blocks.push(BasicBlock::default());
+        BasicBlockIndex(self.blocks.len() - 1)
+    }
+
+    /// Adds an edge from the block at `from` to the block at `to`.
+    fn add_edge(&mut self, from: BasicBlockIndex, to: BasicBlockIndex) {
+        self.blocks[from.0].successors.push(to);
+    }
+
+    /// Adds the statements to the graph, starting in the block at `current`, and returns the block in which they end.
+    fn add_statements(&mut self, current: BasicBlockIndex, statements: &[Statement]) -> BasicBlockIndex {
+        statements
+            .iter()
+            .fold(current, |current, statement| self.add_statement(current, statement))
+    }
+
+    /// Adds a statement to the graph, starting in the block at `current`, and returns the block in which it ends.
+    fn add_statement(&mut self, current: BasicBlockIndex, statement: &Statement) -> BasicBlockIndex {
+        match statement {
+            Statement::Block(block) => return self.add_statements(current, &block.statements),
+            Statement::Conditional(conditional) => return self.add_conditional(current, conditional),
+            Statement::Assign(assign) => {
+                self.read(current, |collector| {
+                    collector.visit_expression(&assign.value, &Default::default())
+                });
+                self.write(current, &assign.place);
+            }
+            Statement::Definition(definition) => {
+                self.read(current, |collector| {
+                    collector.visit_expression(&definition.value, &Default::default())
+                });
+                self.write(current, &definition.place);
+            }
+            Statement::Cfg(_) | Statement::Iteration(_) | Statement::Match(_) => {
+                unreachable!("`CfgStatement`s, `IterationStatement`s, and `MatchStatement`s should not be in the AST at this phase of compilation.")
+            }
+            // The remaining statements only read variables.
+            _ => self.read(current, |collector| collector.visit_statement(statement)),
+        }
+        self.blocks[current.0].statements.push(statement.id());
+        current
+    }
+
+    /// Adds a conditional statement to the graph, ending the block at `current`, and returns the block at which its branches rejoin.
+    fn add_conditional(&mut self, current: BasicBlockIndex, conditional: &ConditionalStatement) -> BasicBlockIndex {
+        self.read(current, |collector| {
+            collector.visit_expression(&conditional.condition, &Default::default())
+        });
+
+        let then = self.add_block();
+        self.add_edge(current, then);
+        let then_end = self.add_statements(then, &conditional.then.statements);
+
+        // If there is no otherwise-block, then control flows directly from the condition to the join.
+        let otherwise_end = match &conditional.otherwise {
+            Some(otherwise) => {
+                let otherwise_start = self.add_block();
+                self.add_edge(current, otherwise_start);
+                self.add_statement(otherwise_start, otherwise)
+            }
+            None => current,
+        };
+
+        let join = self.add_block();
+        self.add_edge(then_end, join);
+        self.add_edge(otherwise_end, join);
+        self.joins.insert(conditional.id, join);
+        join
+    }
+
+    /// Records the variables read by `visit` in the block at `current`, unless they were already written in the block.
+    fn read(&mut self, current: BasicBlockIndex, visit: impl FnOnce(&mut UseCollector)) {
+        let mut collector = UseCollector::default();
+        visit(&mut collector);
+        let block = &mut self.blocks[current.0];
+        for symbol in collector.uses {
+            if !block.defs.contains(&symbol) {
+                block.uses.insert(symbol);
+            }
+        }
+    }
+
+    /// Records the variables written by an assignment or definition to `place` in the block at `current`.
+    fn write(&mut self, current: BasicBlockIndex, place: &Expression) {
+        match place {
+            Expression::Identifier(identifier) => {
+                self.blocks[current.0].defs.insert(identifier.name);
+            }
+            // Note that the outputs of a call may be assigned to a tuple of variables.
+            Expression::Tuple(tuple) => tuple.elements.iter().for_each(|element| self.write(current, element)),
+            _ => unreachable!(
+                "Type checking guarantees that the place of an assignment or definition is an identifier or a tuple."
+            ),
+        }
+    }
+}
+
+/// Collects the variables read by an expression or statement.
+/// Names that are not variables, such as the names of mappings, may also be collected, which only makes analyses more conservative.
+#[derive(Default)]
+struct UseCollector {
+    uses: IndexSet<Symbol>,
+}
+
+impl<'a> ExpressionVisitor<'a> for UseCollector {
+    type AdditionalInput = ();
+    type Output = ();
+
+    /// Visits the members of a struct expression, where a member without an expression reads the variable of the same name.
+    fn visit_struct_init(&mut self, input: &'a StructExpression, _additional: &Self::AdditionalInput) -> Self::Output {
+        for member in input.members.iter() {
+            match &member.expression {
+                Some(expression) => self.visit_expression(expression, &Default::default()),
+                None => self.visit_identifier(&member.identifier, &Default::default()),
+            }
+        }
+    }
+
+    fn visit_identifier(&mut self, input: &'a Identifier, _additional: &Self::AdditionalInput) -> Self::Output {
+        self.uses.insert(input.name);
+    }
+}
+
+impl<'a> StatementVisitor<'a> for UseCollector {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use leo_ast::{AssignStatement, BinaryExpression, BinaryOperation, ReturnStatement};
+    use leo_span::{symbol::create_session_if_not_set_then, Span};
+
+    fn identifier(name: &str) -> Expression {
+        Expression::Identifier(Identifier {
+            name: Symbol::intern(name),
+            span: Span::default(),
+            id: 0,
+        })
+    }
+
+    fn assign(place: &str, value: Expression, id: NodeId) -> Statement {
+        Statement::Assign(Box::new(AssignStatement {
+            place: identifier(place),
+            value,
+            span: Span::default(),
+            id,
+        }))
+    }
+
+    fn block(statements: Vec<Statement>) -> Block {
+        Block {
+            statements,
+            span: Span::default(),
+            id: 0,
+        }
+    }
+
+    #[test]
+    fn test_conditional() {
+        create_session_if_not_set_then(|_| {
+            let (x, y) = (Symbol::intern("x"), Symbol::intern("y"));
+            let increment = Expression::Binary(BinaryExpression {
+                left: Box::new(identifier("x")),
+                right: Box::new(identifier("c")),
+                op: BinaryOperation::Add,
+                span: Span::default(),
+                id: 0,
+            });
+            // x = a; if b { x = x + c; } else { y = x; } return x;
+            let body = block(vec![
+                assign("x", identifier("a"), 1),
+                Statement::Conditional(ConditionalStatement {
+                    condition: identifier("b"),
+                    then: block(vec![assign("x", increment, 3)]),
+                    otherwise: Some(Box::new(Statement::Block(block(vec![assign("y", identifier("x"), 4)])))),
+                    span: Span::default(),
+                    id: 2,
+                }),
+                Statement::Return(ReturnStatement {
+                    expression: identifier("x"),
+                    span: Span::default(),
+                    id: 5,
+                }),
+            ]);
+            let cfg = Cfg::new(&body);
+
+            let [entry, then, otherwise, join] = [0, 1, 2, 3].map(BasicBlockIndex);
+            assert_eq!(cfg.indices().collect::<Vec<_>>(), [entry, then, otherwise, join]);
+            assert_eq!(cfg.join(2), Some(join));
+            assert_eq!(cfg.block(entry).successors(), [then, otherwise]);
+            assert_eq!(cfg.block(entry).statements(), [1]);
+            assert_eq!(cfg.block(join).statements(), [5]);
+            assert_eq!(cfg.predecessors()[join.0], [then, otherwise]);
+
+            assert_eq!(cfg.immediate_dominators(), [entry, entry, entry, entry]);
+            let frontiers = cfg.dominance_frontiers();
+            assert!(frontiers[entry.0].is_empty());
+            assert_eq!(frontiers[then.0], IndexSet::from([join]));
+            assert_eq!(frontiers[otherwise.0], IndexSet::from([join]));
+
+            // `x` is read after the join, but `y` is not.
+            let live_in = cfg.live_in();
+            assert_eq!(live_in[join.0], IndexSet::from([x]));
+            assert_eq!(live_in[otherwise.0], IndexSet::from([x]));
+            assert_eq!(live_in[then.0], IndexSet::from([x, Symbol::intern("c")]));
+            assert!(!live_in.iter().any(|live| live.contains(&y)));
+        });
+    }
+}
@@ -0,0 +1,204 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::SymbolTable;
+
+use leo_ast::{
+    BinaryExpression, BinaryOperation, Expression, IntegerType, Literal, Node, Type, UnaryExpression, UnaryOperation,
+    Value, WideInteger,
+};
+use leo_errors::{Result, TypeCheckerError};
+use leo_span::Span;
+
+use std::fmt::Display;
+
+/// Evaluates expressions whose values are known at compile time.
+/// Only boolean and integer expressions over literals and known constants are supported, along with string literals.
+///
+/// Integer operations that fail at runtime on overflow are evaluated exactly using [`WideInteger`], so that the
+/// type checker can report overflows with the same logic that folds them.
+///
+/// The other operations on booleans and integers are evaluated by [`Value::evaluate_binary`] and
+/// [`Value::evaluate_unary`], which the interpreter of `leo-interpreter` uses as well.
+///
+/// Note that the evaluator assumes that the expression has been successfully type checked.
+pub struct ConstEvaluator<'a> {
+    /// The symbol table used to look up the values of constants.
+    symbol_table: &'a SymbolTable,
+}
+
+impl<'a> ConstEvaluator<'a> {
+    pub fn new(symbol_table: &'a SymbolTable) -> Self {
+        Self { symbol_table }
+    }
+
+    /// Evaluates `input`, returning `None` if its value cannot be determined at compile time.
+    /// Errors if a constant operation overflows.
+    pub fn evaluate(&self, input: &Expression) -> Result<Option<Value>> {
+        match input {
+            Expression::Literal(literal) => Ok(match (literal, literal.integer_type()) {
+                (Literal::Boolean(..) | Literal::String(..), _) => Some(Value::from(literal)),
+                // Literals that do not fit in their type have already been reported by the type checker.
+                (
+                    Literal::Integer(_, string, span, _) | Literal::Unsuffixed(string, _, span, _),
+                    Some(integer_type),
+                ) => WideInteger::parse(string).and_then(|value| value.to_value(integer_type, *span)),
+                _ => None,
+            }),
+            Expression::Identifier(identifier) => Ok(self.symbol_table.lookup_constant(identifier.name).cloned()),
+            Expression::Unary(unary) if Self::is_checked_unary(unary.op) => {
+                match self.evaluate_checked_unary(unary)? {
+                    Some((integer_type, result)) => {
+                        Self::checked_result(Self::display_checked_unary(unary), unary.span(), integer_type, result)
+                            .map(Some)
+                    }
+                    None => Ok(None),
+                }
+            }
+            Expression::Unary(unary) => match self.evaluate(&unary.receiver)? {
+                Some(receiver) => receiver.evaluate_unary(unary.op, unary.span()),
+                None => Ok(None),
+            },
+            Expression::Binary(binary) if Self::is_checked_binary(binary.op) => {
+                match self.evaluate_checked_binary(binary)? {
+                    Some((integer_type, result)) => {
+                        Self::checked_result(binary, binary.span(), integer_type, result).map(Some)
+                    }
+                    None => Ok(None),
+                }
+            }
+            Expression::Binary(binary) => match (self.evaluate(&binary.left)?, self.evaluate(&binary.right)?) {
+                (Some(left), Some(right)) => left.evaluate_binary(binary.op, right, binary.span()),
+                _ => Ok(None),
+            },
+            // A cast to an integer type is only evaluated if the value fits in that type, since it fails at runtime otherwise.
+            Expression::Cast(cast) => match (self.evaluate(&cast.expression)?, &cast.type_) {
+                (Some(value), Type::Integer(integer_type)) => Ok(value.cast_integer(*integer_type, cast.span())),
+                _ => Ok(None),
+            },
+            Expression::Ternary(ternary) => match self.evaluate(&ternary.condition)? {
+                Some(Value::Boolean(true, _)) => self.evaluate(&ternary.if_true),
+                Some(Value::Boolean(false, _)) => self.evaluate(&ternary.if_false),
+                _ => Ok(None),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns `true` if the unary operation fails at runtime when its result overflows.
+    pub fn is_checked_unary(op: UnaryOperation) -> bool {
+        matches!(op, UnaryOperation::Abs | UnaryOperation::Negate)
+    }
+
+    /// Returns `true` if the binary operation fails at runtime when its result overflows.
+    pub fn is_checked_binary(op: BinaryOperation) -> bool {
+        matches!(
+            op,
+            BinaryOperation::Add
+                | BinaryOperation::Sub
+                | BinaryOperation::Mul
+                | BinaryOperation::Div
+                | BinaryOperation::Pow
+        )
+    }
+
+    /// Evaluates a checked unary integer operation over a known signed operand.
+    /// Returns the type of the operation along with its exact result, whether or not it fits in that type.
+    pub fn evaluate_checked_unary(
+        &self,
+        input: &UnaryExpression,
+    ) -> Result<Option<(IntegerType, Option<WideInteger>)>> {
+        let receiver = match self.evaluate(&input.receiver)? {
+            Some(receiver) => receiver,
+            None => return Ok(None),
+        };
+        Ok(match (Type::from(&receiver), WideInteger::from_value(&receiver)) {
+            (Type::Integer(integer_type), Some(receiver)) if integer_type.is_signed() => match input.op {
+                UnaryOperation::Abs => Some((integer_type, Some(receiver.abs()))),
+                UnaryOperation::Negate => Some((integer_type, Some(receiver.negate()))),
+                _ => None,
+            },
+            _ => None,
+        })
+    }
+
+    /// Evaluates a checked binary integer operation over known operands.
+    /// Returns the type of the operation along with its exact result, whether or not it fits in that type.
+    /// The result is `None` if it is too large for any integer type.
+    pub fn evaluate_checked_binary(
+        &self,
+        input: &BinaryExpression,
+    ) -> Result<Option<(IntegerType, Option<WideInteger>)>> {
+        let (left, right) = match (self.evaluate(&input.left)?, self.evaluate(&input.right)?) {
+            (Some(left), Some(right)) => (left, right),
+            _ => return Ok(None),
+        };
+        let (left_type, right_type) = match (Type::from(&left), Type::from(&right)) {
+            (Type::Integer(left_type), Type::Integer(right_type)) => (left_type, right_type),
+            _ => return Ok(None),
+        };
+        let (left, right) = match (WideInteger::from_value(&left), WideInteger::from_value(&right)) {
+            (Some(left), Some(right)) => (left, right),
+            _ => return Ok(None),
+        };
+        Ok(match input.op {
+            // Exponentiation takes a magnitude as its right operand.
+            BinaryOperation::Pow => right.to_u32().map(|exponent| (left_type, left.checked_pow(exponent))),
+            _ if left_type != right_type => None,
+            BinaryOperation::Add => Some((left_type, left.checked_add(right))),
+            BinaryOperation::Sub => Some((left_type, left.checked_sub(right))),
+            BinaryOperation::Mul => Some((left_type, left.checked_mul(right))),
+            // Division by zero is a runtime error, not an overflow, so it is left unevaluated.
+            BinaryOperation::Div => left.checked_div(right).map(|result| (left_type, Some(result))),
+            _ => None,
+        })
+    }
+
+    /// Converts the exact result of a checked operation into a value, erroring if it overflows.
+    fn checked_result(
+        operation: impl Display,
+        span: Span,
+        integer_type: IntegerType,
+        result: Option<WideInteger>,
+    ) -> Result<Value> {
+        match result.and_then(|result| result.to_value(integer_type, span)) {
+            Some(value) => Ok(value),
+            None => Err(Self::overflow_error(operation, span, integer_type, result).into()),
+        }
+    }
+
+    /// Renders a checked unary operation as it is written in the source.
+    pub fn display_checked_unary(input: &UnaryExpression) -> String {
+        match input.op {
+            UnaryOperation::Negate => format!("-{}", input.receiver),
+            op => format!("{}.{}()", input.receiver, op.as_str()),
+        }
+    }
+
+    /// Returns the error for a checked operation whose exact result does not fit in its type.
+    pub fn overflow_error(
+        operation: impl Display,
+        span: Span,
+        integer_type: IntegerType,
+        result: Option<WideInteger>,
+    ) -> TypeCheckerError {
+        let result = match result {
+            Some(result) => format!("`{result}`"),
+            None => "outside of the range of every integer type".to_string(),
+        };
+        TypeCheckerError::integer_overflow(operation, integer_type, result, span)
+    }
+}
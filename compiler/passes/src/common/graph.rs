@@ -0,0 +1,275 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_span::Symbol;
+
+use indexmap::{IndexMap, IndexSet};
+use std::{fmt::Debug, hash::Hash};
+
+/// A call graph, with an edge from each function to every function that it calls.
+pub type CallGraph = DiGraph<Symbol>;
+
+/// A struct graph, with an edge from each struct to every struct that it has a member of.
+pub type StructGraph = DiGraph<Symbol>;
+
+/// An error produced when traversing a `DiGraph`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiGraphError<N: Node> {
+    /// The graph contains a cycle, given as the path of nodes that starts and ends at the same node.
+    CycleDetected(Vec<N>),
+}
+
+/// A node in a `DiGraph`.
+pub trait Node: Copy + Debug + Eq + Hash {}
+
+impl Node for Symbol {}
+
+/// A directed graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiGraph<N: Node> {
+    /// The set of nodes in the graph.
+    nodes: IndexSet<N>,
+    /// The directed edges of the graph, from each node to its successors.
+    edges: IndexMap<N, IndexSet<N>>,
+}
+
+impl<N: Node> Default for DiGraph<N> {
+    fn default() -> Self {
+        Self::new(IndexSet::new())
+    }
+}
+
+impl<N: Node> DiGraph<N> {
+    /// Initializes a new `DiGraph` from a set of nodes.
+    pub fn new(nodes: IndexSet<N>) -> Self {
+        Self {
+            nodes,
+            edges: IndexMap::new(),
+        }
+    }
+
+    /// Adds a node to the graph.
+    pub fn add_node(&mut self, node: N) {
+        self.nodes.insert(node);
+    }
+
+    /// Adds an edge from `from` to `to`, adding either node to the graph if it is not already present.
+    pub fn add_edge(&mut self, from: N, to: N) {
+        self.add_node(from);
+        self.add_node(to);
+        self.edges.entry(from).or_default().insert(to);
+    }
+
+    /// Removes a node from the graph, along with every edge from or to it.
+    pub fn remove_node(&mut self, node: N) {
+        self.nodes.shift_remove(&node);
+        self.edges.shift_remove(&node);
+        self.edges.values_mut().for_each(|successors| {
+            successors.shift_remove(&node);
+        });
+    }
+
+    /// Returns `true` if the graph contains the given node.
+    pub fn contains_node(&self, node: N) -> bool {
+        self.nodes.contains(&node)
+    }
+
+    /// Returns `true` if the graph contains an edge from `from` to `to`.
+    pub fn contains_edge(&self, from: N, to: N) -> bool {
+        self.edges
+            .get(&from)
+            .map_or(false, |successors| successors.contains(&to))
+    }
+
+    /// Returns a path of nodes from `from` to `to`, if one exists.
+    /// A node always has a path, of a single node, to itself.
+    pub fn path(&self, from: N, to: N) -> Option<Vec<N>> {
+        let mut path = vec![from];
+        let mut visited = IndexSet::new();
+        self.find_path(to, &mut path, &mut visited).then_some(path)
+    }
+
+    /// Extends `path` with a path from its last node to `to`, returning `false` if there is no such path.
+    fn find_path(&self, to: N, path: &mut Vec<N>, visited: &mut IndexSet<N>) -> bool {
+        // Note that `path` is never empty.
+        let node = *path.last().unwrap();
+        if node == to {
+            return true;
+        }
+        if !visited.insert(node) {
+            return false;
+        }
+        for successor in self.edges.get(&node).into_iter().flatten() {
+            path.push(*successor);
+            if self.find_path(to, path, visited) {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+
+    /// Returns the nodes of the graph in post-order, so that every node appears after all of its successors.
+    /// Errors if the graph contains a cycle.
+    pub fn post_order(&self) -> Result<IndexSet<N>, DiGraphError<N>> {
+        let mut finished = IndexSet::with_capacity(self.nodes.len());
+        for node in self.nodes.iter() {
+            let mut discovered = IndexSet::new();
+            self.visit(*node, &mut discovered, &mut finished)?;
+        }
+        Ok(finished)
+    }
+
+    /// Visits `node` and its unfinished successors in depth-first order, adding each node to `finished` after its successors.
+    /// `discovered` holds the nodes on the current path, in order.
+    fn visit(&self, node: N, discovered: &mut IndexSet<N>, finished: &mut IndexSet<N>) -> Result<(), DiGraphError<N>> {
+        if finished.contains(&node) {
+            return Ok(());
+        }
+        // If the node is already on the current path, then the graph contains a cycle.
+        if let Some(index) = discovered.get_index_of(&node) {
+            let mut cycle = discovered.iter().skip(index).copied().collect::<Vec<_>>();
+            cycle.push(node);
+            return Err(DiGraphError::CycleDetected(cycle));
+        }
+        discovered.insert(node);
+        for successor in self.edges.get(&node).into_iter().flatten() {
+            self.visit(*successor, discovered, finished)?;
+        }
+        discovered.pop();
+        finished.insert(node);
+        Ok(())
+    }
+
+    /// Returns the strongly connected components of the graph, using Tarjan's algorithm.
+    /// Every component appears after all of the components that it has an edge to, and the nodes of each component are in the order in which they were added to the graph.
+    pub fn strongly_connected_components(&self) -> Vec<IndexSet<N>> {
+        let mut state = ComponentState {
+            indices: IndexMap::with_capacity(self.nodes.len()),
+            low_links: IndexMap::with_capacity(self.nodes.len()),
+            stack: IndexSet::new(),
+            components: Vec::new(),
+        };
+        for node in self.nodes.iter() {
+            if !state.indices.contains_key(node) {
+                self.connect(*node, &mut state);
+            }
+        }
+        state.components
+    }
+
+    /// Visits `node` and its unvisited successors in depth-first order, adding a component to `state` whenever the root of one is finished.
+    fn connect(&self, node: N, state: &mut ComponentState<N>) {
+        let index = state.indices.len();
+        state.indices.insert(node, index);
+        state.low_links.insert(node, index);
+        state.stack.insert(node);
+
+        for successor in self.edges.get(&node).into_iter().flatten() {
+            let low_link = if !state.indices.contains_key(successor) {
+                self.connect(*successor, state);
+                state.low_links[successor]
+            } else if state.stack.contains(successor) {
+                state.indices[successor]
+            } else {
+                // The successor belongs to a component that has already been found.
+                continue;
+            };
+            if low_link < state.low_links[&node] {
+                state.low_links.insert(node, low_link);
+            }
+        }
+
+        // If `node` is the root of a component, then the component consists of the nodes above it on the stack.
+        if state.low_links[&node] == index {
+            let position = state.stack.get_index_of(&node).unwrap();
+            let mut component = state.stack.drain(position..).collect::<IndexSet<_>>();
+            component.sort_by(|a, b| self.nodes.get_index_of(a).cmp(&self.nodes.get_index_of(b)));
+            state.components.push(component);
+        }
+    }
+}
+
+/// The state of a search for the strongly connected components of a `DiGraph`.
+struct ComponentState<N: Node> {
+    /// The order in which each node was visited.
+    indices: IndexMap<N, usize>,
+    /// The smallest index of a node on the stack that is reachable from each node.
+    low_links: IndexMap<N, usize>,
+    /// The visited nodes that have not yet been assigned to a component.
+    stack: IndexSet<N>,
+    /// The components that have been found.
+    components: Vec<IndexSet<N>>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    impl Node for u32 {}
+
+    fn graph(edges: &[(u32, u32)]) -> DiGraph<u32> {
+        let mut graph = DiGraph::default();
+        for (from, to) in edges {
+            graph.add_edge(*from, *to);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_post_order() {
+        let graph = graph(&[(1, 2), (1, 3), (2, 4), (3, 4)]);
+        let order = graph.post_order().unwrap().into_iter().collect::<Vec<_>>();
+        assert_eq!(order, vec![4, 2, 3, 1]);
+    }
+
+    #[test]
+    fn test_post_order_detects_cycle() {
+        let graph = graph(&[(1, 2), (2, 3), (3, 2)]);
+        assert_eq!(graph.post_order(), Err(DiGraphError::CycleDetected(vec![2, 3, 2])));
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let graph = graph(&[(1, 2), (2, 3), (3, 1), (3, 4), (4, 4), (5, 4)]);
+        let components = graph
+            .strongly_connected_components()
+            .into_iter()
+            .map(|component| component.into_iter().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(components, vec![vec![4], vec![1, 2, 3], vec![5]]);
+        assert!(graph.contains_edge(4, 4));
+        assert!(!graph.contains_edge(1, 1));
+    }
+
+    #[test]
+    fn test_path() {
+        let graph = graph(&[(1, 2), (2, 3), (3, 4)]);
+        assert_eq!(graph.path(1, 4), Some(vec![1, 2, 3, 4]));
+        assert_eq!(graph.path(2, 2), Some(vec![2]));
+        assert_eq!(graph.path(4, 1), None);
+    }
+
+    #[test]
+    fn test_remove_node() {
+        let mut graph = graph(&[(1, 2), (2, 3), (1, 3)]);
+        graph.remove_node(2);
+        assert!(!graph.contains_node(2));
+        assert!(!graph.contains_edge(1, 2));
+        assert!(graph.contains_edge(1, 3));
+        assert_eq!(graph.post_order().unwrap().into_iter().collect::<Vec<_>>(), vec![3, 1]);
+    }
+}
@@ -0,0 +1,240 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::TypeTable;
+
+use leo_ast::*;
+use leo_span::Symbol;
+
+use indexmap::IndexMap;
+
+/// Gives the nodes of a copied subtree fresh IDs, so that the copy and the original may both be part of the AST.
+/// Every expression, statement, and block of the subtree is given a new ID, as are the inputs of a copied function.
+/// The types that the type table records for the original nodes are recorded for their copies.
+pub struct NodeRefresher<'a> {
+    /// A counter used to generate the fresh IDs.
+    node_builder: &'a NodeBuilder,
+    /// The types of the expressions and variables, to which the types of the copies are added.
+    type_table: &'a mut TypeTable,
+    /// The types that the type parameters in the types of the copies are replaced by, if the copy is an instance of a generic function.
+    substitution: Option<&'a IndexMap<Symbol, Type>>,
+}
+
+impl<'a> NodeRefresher<'a> {
+    /// Returns a refresher that takes its IDs from `node_builder` and records the types of the copies in `type_table`.
+    pub fn new(node_builder: &'a NodeBuilder, type_table: &'a mut TypeTable) -> Self {
+        Self {
+            node_builder,
+            type_table,
+            substitution: None,
+        }
+    }
+
+    /// Returns a refresher for an instance of a generic function, which records the types of the copies with
+    /// their type parameters replaced by the types in `substitution`.
+    pub fn with_substitution(
+        node_builder: &'a NodeBuilder,
+        type_table: &'a mut TypeTable,
+        substitution: &'a IndexMap<Symbol, Type>,
+    ) -> Self {
+        Self {
+            node_builder,
+            type_table,
+            substitution: Some(substitution),
+        }
+    }
+
+    /// Returns `node` with a fresh ID, recording the type of the original node for it, if there is one.
+    pub fn refresh<N: Node>(&mut self, mut node: N) -> N {
+        let type_ = self.type_table.get(&node).map(|type_| match self.substitution {
+            Some(substitution) => type_.substitute(substitution),
+            None => type_.clone(),
+        });
+        node.set_id(self.node_builder.next_id());
+        if let Some(type_) = type_ {
+            self.type_table.insert(&node, type_);
+        }
+        node
+    }
+
+    /// Returns the input with a fresh ID, as is the ID of the variable that it binds.
+    fn refresh_input(&mut self, input: Input) -> Input {
+        match input {
+            Input::Internal(input) => Input::Internal(FunctionInput {
+                identifier: self.refresh(input.identifier),
+                id: self.node_builder.next_id(),
+                ..input
+            }),
+            input => self.refresh(input),
+        }
+    }
+}
+
+impl ExpressionReconstructor for NodeRefresher<'_> {
+    type AdditionalOutput = ();
+
+    fn reconstruct_expression(&mut self, input: Expression) -> (Expression, Self::AdditionalOutput) {
+        let expression = match input {
+            Expression::Access(access) => self.reconstruct_access(access).0,
+            Expression::Array(array) => self.reconstruct_array(array).0,
+            Expression::Binary(binary) => self.reconstruct_binary(binary).0,
+            Expression::Call(call) => self.reconstruct_call(call).0,
+            Expression::Cast(cast) => self.reconstruct_cast(cast).0,
+            Expression::Struct(struct_) => self.reconstruct_struct_init(struct_).0,
+            Expression::Err(err) => self.reconstruct_err(err).0,
+            Expression::Identifier(identifier) => self.reconstruct_identifier(identifier).0,
+            Expression::Literal(literal) => self.reconstruct_literal(literal).0,
+            Expression::Ternary(ternary) => self.reconstruct_ternary(ternary).0,
+            Expression::Tuple(tuple) => self.reconstruct_tuple(tuple).0,
+            Expression::Unary(unary) => self.reconstruct_unary(unary).0,
+        };
+        (self.refresh(expression), Default::default())
+    }
+
+    fn reconstruct_struct_init(&mut self, input: StructExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Struct(StructExpression {
+                members: input
+                    .members
+                    .into_iter()
+                    .map(|member| StructVariableInitializer {
+                        identifier: self.refresh(member.identifier),
+                        expression: member
+                            .expression
+                            .map(|expression| self.reconstruct_expression(expression).0),
+                    })
+                    .collect(),
+                ..input
+            }),
+            Default::default(),
+        )
+    }
+}
+
+impl StatementReconstructor for NodeRefresher<'_> {
+    fn reconstruct_statement(&mut self, input: Statement) -> (Statement, Self::AdditionalOutput) {
+        let statement = match input {
+            // The block is given a fresh ID as it is reconstructed.
+            Statement::Block(block) => return self.reconstruct_block_statement(block),
+            Statement::Assert(assert) => self.reconstruct_assert(assert).0,
+            Statement::Assign(assign) => self.reconstruct_assign(*assign).0,
+            Statement::Cfg(cfg) => self.reconstruct_cfg(cfg).0,
+            Statement::Conditional(conditional) => self.reconstruct_conditional(conditional).0,
+            Statement::Decrement(decrement) => self.reconstruct_decrement(decrement).0,
+            Statement::Definition(definition) => self.reconstruct_definition(definition).0,
+            Statement::Expression(expression) => self.reconstruct_expression_statement(expression).0,
+            Statement::Finalize(finalize) => self.reconstruct_finalize(finalize).0,
+            Statement::Increment(increment) => self.reconstruct_increment(increment).0,
+            Statement::Iteration(iteration) => self.reconstruct_iteration(*iteration).0,
+            Statement::Match(match_) => self.reconstruct_match(match_).0,
+            Statement::Return(return_) => self.reconstruct_return(return_).0,
+        };
+        (self.refresh(statement), Default::default())
+    }
+
+    fn reconstruct_assign(&mut self, input: AssignStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Assign(Box::new(AssignStatement {
+                place: self.reconstruct_expression(input.place).0,
+                value: self.reconstruct_expression(input.value).0,
+                ..input
+            })),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_block(&mut self, input: Block) -> (Block, Self::AdditionalOutput) {
+        let block = Block {
+            statements: input
+                .statements
+                .into_iter()
+                .map(|statement| self.reconstruct_statement(statement).0)
+                .collect(),
+            ..input
+        };
+        (self.refresh(block), Default::default())
+    }
+
+    fn reconstruct_decrement(&mut self, input: DecrementStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Decrement(DecrementStatement {
+                mapping: self.refresh(input.mapping),
+                index: self.reconstruct_expression(input.index).0,
+                amount: self.reconstruct_expression(input.amount).0,
+                ..input
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_definition(&mut self, input: DefinitionStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Definition(DefinitionStatement {
+                place: self.reconstruct_expression(input.place).0,
+                value: self.reconstruct_expression(input.value).0,
+                ..input
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_increment(&mut self, input: IncrementStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Increment(IncrementStatement {
+                mapping: self.refresh(input.mapping),
+                index: self.reconstruct_expression(input.index).0,
+                amount: self.reconstruct_expression(input.amount).0,
+                ..input
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_iteration(&mut self, input: IterationStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Iteration(Box::new(IterationStatement {
+                variable: self.refresh(input.variable),
+                start: self.reconstruct_expression(input.start).0,
+                stop: input.stop.map(|stop| self.reconstruct_expression(stop).0),
+                block: self.reconstruct_block(input.block).0,
+                ..input
+            })),
+            Default::default(),
+        )
+    }
+}
+
+impl ProgramReconstructor for NodeRefresher<'_> {
+    fn reconstruct_function(&mut self, input: Function) -> Function {
+        Function {
+            identifier: self.refresh(input.identifier),
+            input: input.input.into_iter().map(|input| self.refresh_input(input)).collect(),
+            block: self.reconstruct_block(input.block).0,
+            finalize: input.finalize.map(|finalize| Finalize {
+                input: finalize
+                    .input
+                    .into_iter()
+                    .map(|input| self.refresh_input(input))
+                    .collect(),
+                block: self.reconstruct_block(finalize.block).0,
+                id: self.node_builder.next_id(),
+                ..finalize
+            }),
+            id: self.node_builder.next_id(),
+            ..input
+        }
+    }
+}
@@ -0,0 +1,301 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{estimated_constraints, OperandType};
+
+use leo_ir::{BinaryOperation, CastType, Function, FunctionKind, Instruction, Operand, Program, Register, ValueType};
+
+use indexmap::IndexMap;
+use std::{collections::BTreeMap, fmt};
+
+/// The estimated number of constraints of the instructions generated from a line of the Leo program.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LineCost {
+    /// The line, or `None` for instructions that were not generated from a known line.
+    pub line: Option<usize>,
+    /// The estimated number of constraints, including those of the closures called from the line.
+    pub constraints: usize,
+    /// The number of instructions generated from the line.
+    pub instructions: usize,
+}
+
+/// The estimated number of constraints of a closure or function, broken down by line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionCost {
+    pub kind: FunctionKind,
+    pub name: String,
+    /// The estimated number of constraints, including those of the closures that it calls.
+    pub constraints: usize,
+    /// The lines of the function in order, followed by the instructions that were not generated from a known line.
+    pub lines: Vec<LineCost>,
+}
+
+/// The estimated number of constraints of each closure and function of a program.
+/// Finalize blocks are excluded, since they are executed on chain rather than proven.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CostReport {
+    /// The closures of the program, followed by its functions.
+    pub functions: Vec<FunctionCost>,
+}
+
+impl fmt::Display for CostReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for function in self.functions.iter() {
+            writeln!(
+                f,
+                "{} {}: {} constraints",
+                function.kind, function.name, function.constraints
+            )?;
+            for line in function.lines.iter() {
+                match line.line {
+                    Some(number) => write!(f, "    line {}", number)?,
+                    None => write!(f, "    generated")?,
+                }
+                writeln!(
+                    f,
+                    ": {} constraints ({} instructions)",
+                    line.constraints, line.instructions
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct CostEstimator {
+    /// The number of instructions given to the pass.
+    pub(crate) instructions_visited: usize,
+    /// The estimated number of constraints of each function.
+    pub(crate) decisions: Vec<String>,
+    /// The estimated number of constraints of the closures that have been estimated, by name.
+    closures: IndexMap<String, usize>,
+}
+
+impl CostEstimator {
+    /// Estimates the number of constraints of the closures and functions of the program.
+    /// Closures are estimated first, since Aleo requires a closure to be declared before it is called.
+    pub fn estimate_program(&mut self, program: &Program) -> CostReport {
+        let (closures, functions): (Vec<&Function>, Vec<&Function>) = program
+            .functions
+            .iter()
+            .partition(|function| function.kind == FunctionKind::Closure);
+        CostReport {
+            functions: closures
+                .into_iter()
+                .chain(functions)
+                .map(|function| self.estimate_function(function))
+                .collect(),
+        }
+    }
+
+    fn estimate_function(&mut self, function: &Function) -> FunctionCost {
+        self.instructions_visited += function.instructions.len();
+
+        // The types of the registers, as far as they are known.
+        let mut types: IndexMap<Register, OperandType> = function
+            .inputs
+            .iter()
+            .map(|input| {
+                let type_ = match &input.type_ {
+                    ValueType::Plaintext(name, _) => OperandType::from_name(name),
+                    ValueType::Record(_) | ValueType::ExternalRecord { .. } => OperandType::Other,
+                };
+                (input.register, type_)
+            })
+            .collect();
+
+        // Lines are ordered by number, followed by the instructions that were not generated from a known line.
+        let mut lines: BTreeMap<(bool, usize), LineCost> = BTreeMap::new();
+        let known_lines = function
+            .locations
+            .iter()
+            .map(|location| location.map(|location| location.line_start))
+            .chain(std::iter::repeat(None));
+        for (instruction, line) in function.instructions.iter().zip(known_lines) {
+            let operands: Vec<OperandType> = instruction
+                .operands()
+                .into_iter()
+                .map(|operand| operand_type(operand, &types))
+                .collect();
+            let first = operands.first().copied().unwrap_or(OperandType::Other);
+            let second = operands.get(1).copied().unwrap_or(OperandType::Other);
+
+            let mut constraints = estimated_constraints(instruction, first, second);
+            if let Instruction::Call {
+                program: None,
+                function: name,
+                ..
+            } = instruction
+            {
+                constraints += self.closures.get(name).copied().unwrap_or_default();
+            }
+            if let Some((destination, type_)) = destination_type(instruction, first, second) {
+                types.insert(destination, type_);
+            }
+
+            let entry = lines
+                .entry((line.is_none(), line.unwrap_or_default()))
+                .or_insert(LineCost {
+                    line,
+                    ..Default::default()
+                });
+            entry.constraints += constraints;
+            entry.instructions += 1;
+        }
+
+        let lines: Vec<LineCost> = lines.into_values().collect();
+        let constraints = lines.iter().map(|line| line.constraints).sum();
+        if function.kind == FunctionKind::Closure {
+            self.closures.insert(function.name.clone(), constraints);
+        }
+        self.decisions.push(format!(
+            "`{}` is estimated at {} constraints",
+            function.name, constraints
+        ));
+
+        FunctionCost {
+            kind: function.kind,
+            name: function.name.clone(),
+            constraints,
+            lines,
+        }
+    }
+}
+
+/// Returns the type of an operand, as far as it is known.
+fn operand_type(operand: &Operand, types: &IndexMap<Register, OperandType>) -> OperandType {
+    match operand {
+        Operand::Register(register) => types.get(register).copied().unwrap_or(OperandType::Other),
+        Operand::Literal(literal) => OperandType::of_literal(literal),
+        Operand::Member(inner, member) if matches!(inner.as_ref(), Operand::Name(name) if name == "self") => {
+            match member.as_str() {
+                "caller" | "signer" => OperandType::Address,
+                _ => OperandType::Other,
+            }
+        }
+        Operand::Member(..) | Operand::Name(_) => OperandType::Other,
+    }
+}
+
+/// Returns the register written by an instruction and the type of its value, if the type is known.
+fn destination_type(
+    instruction: &Instruction,
+    first: OperandType,
+    second: OperandType,
+) -> Option<(Register, OperandType)> {
+    match instruction {
+        Instruction::Binary {
+            operation, destination, ..
+        } => {
+            let type_ = match operation {
+                BinaryOperation::Gt
+                | BinaryOperation::Gte
+                | BinaryOperation::IsEq
+                | BinaryOperation::IsNeq
+                | BinaryOperation::Lt
+                | BinaryOperation::Lte => OperandType::Boolean,
+                // A scalar multiplication produces a group element.
+                BinaryOperation::Mul if second == OperandType::Group => OperandType::Group,
+                _ => first,
+            };
+            Some((*destination, type_))
+        }
+        Instruction::Cast {
+            type_: CastType::Literal(name),
+            destination,
+            ..
+        } => Some((*destination, OperandType::from_name(name))),
+        // Hashes and commitments produce fields, while signature verification produces a boolean.
+        Instruction::Core {
+            opcode, destination, ..
+        } => match opcode.starts_with("sign") {
+            true => Some((*destination, OperandType::Boolean)),
+            false => Some((*destination, OperandType::Field)),
+        },
+        Instruction::RandChaCha { destination, type_ } => Some((*destination, OperandType::from_name(type_))),
+        Instruction::Ternary { destination, .. } => Some((*destination, second)),
+        Instruction::Unary { destination, .. } => Some((*destination, first)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use leo_ir::{
+        testing::{function, mul, program},
+        UnaryOperation,
+    };
+
+    #[test]
+    fn test_estimate_program() {
+        let call = Instruction::Call {
+            program: None,
+            function: "square".to_string(),
+            arguments: vec![Operand::Register(Register(0))],
+            destinations: vec![Register(2)],
+        };
+        let cast = Instruction::Cast {
+            operands: vec![Operand::Register(Register(1))],
+            destination: Register(3),
+            type_: CastType::Literal("field".to_string()),
+        };
+        let square = Instruction::Unary {
+            operation: UnaryOperation::Square,
+            operand: Operand::Register(Register(3)),
+            destination: Register(4),
+        };
+        let program = program(vec![
+            function(
+                FunctionKind::Function,
+                "main",
+                vec![mul(1), call, cast, square],
+                vec![Some(5), Some(6), None, None],
+            ),
+            function(FunctionKind::Closure, "square", vec![mul(1)], vec![Some(2)]),
+        ]);
+
+        let mut estimator = CostEstimator::default();
+        let report = estimator.estimate_program(&program);
+
+        // A `u8` multiplication is estimated at 24 constraints, a cast from `u8` at 8, and squaring a field at 1.
+        let line = |line, constraints, instructions| LineCost {
+            line,
+            constraints,
+            instructions,
+        };
+        assert_eq!(
+            report.functions,
+            [
+                FunctionCost {
+                    kind: FunctionKind::Closure,
+                    name: "square".to_string(),
+                    constraints: 24,
+                    lines: vec![line(Some(2), 24, 1)],
+                },
+                FunctionCost {
+                    kind: FunctionKind::Function,
+                    name: "main".to_string(),
+                    constraints: 57,
+                    lines: vec![line(Some(5), 24, 1), line(Some(6), 24, 1), line(None, 9, 2)],
+                },
+            ]
+        );
+        assert_eq!(estimator.instructions_visited, 5);
+    }
+}
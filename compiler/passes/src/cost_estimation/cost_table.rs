@@ -0,0 +1,185 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ir::{BinaryOperation, CastType, Instruction, UnaryOperation};
+
+/// The type of an operand, as far as it matters to the cost of the instructions that read it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OperandType {
+    Address,
+    Boolean,
+    Field,
+    Group,
+    /// An integer type with the given number of bits.
+    Integer(usize),
+    Scalar,
+    /// A struct or record, or a value whose type is not known, such as the output of a call.
+    Other,
+}
+
+impl OperandType {
+    /// Returns the literal type with the given name, e.g. `u8`.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "address" => Self::Address,
+            "boolean" => Self::Boolean,
+            "field" => Self::Field,
+            "group" => Self::Group,
+            "scalar" => Self::Scalar,
+            _ => match name
+                .strip_prefix('u')
+                .or_else(|| name.strip_prefix('i'))
+                .and_then(|bits| bits.parse().ok())
+            {
+                Some(bits) => Self::Integer(bits),
+                None => Self::Other,
+            },
+        }
+    }
+
+    /// Returns the type of a literal operand, e.g. `1u8`, from its suffix.
+    pub fn of_literal(literal: &str) -> Self {
+        match literal {
+            "true" | "false" => Self::Boolean,
+            _ if literal.starts_with("aleo1") => Self::Address,
+            _ => Self::from_name(literal.trim_start_matches(|c: char| c.is_ascii_digit() || c == '-')),
+        }
+    }
+
+    /// Returns the number of bits that a value of the type is decomposed into by operations that need its bits.
+    fn bits(self) -> usize {
+        match self {
+            Self::Boolean => 1,
+            Self::Integer(bits) => bits,
+            Self::Address | Self::Field | Self::Group | Self::Scalar => 253,
+            // Values of unknown types are costed as 64-bit integers.
+            Self::Other => 64,
+        }
+    }
+}
+
+/// Returns the estimated number of constraints that snarkVM synthesizes to prove `instruction`,
+/// given the types of its first and second operands, or of its only operand.
+///
+/// The estimates approximate the circuits of snarkVM for each opcode, and are meant to compare the parts of a program rather than to predict its exact size.
+/// Calls are free, since the constraints of a closure are counted at its own instructions,
+/// and finalize, mapping, and output instructions are free, since they are not proven.
+pub fn estimated_constraints(instruction: &Instruction, first: OperandType, second: OperandType) -> usize {
+    use OperandType::*;
+
+    let bits = first.bits();
+    match instruction {
+        Instruction::Binary { operation, .. } => match (operation, first, second) {
+            (BinaryOperation::Add | BinaryOperation::Sub, Field, _) => 0,
+            (BinaryOperation::Add | BinaryOperation::Sub, Group, _) => 6,
+            (BinaryOperation::Add | BinaryOperation::Sub, _, _)
+            | (BinaryOperation::AddWrapped | BinaryOperation::SubWrapped, _, _) => bits + 1,
+            (BinaryOperation::Mul, Field, _) => 1,
+            // A scalar multiplication doubles and adds once for each bit of the scalar.
+            (BinaryOperation::Mul, Group, _) | (BinaryOperation::Mul, _, Group) => 3 * 253,
+            (BinaryOperation::Mul, _, _) => 3 * bits,
+            (BinaryOperation::MulWrapped, _, _) => 2 * bits,
+            (BinaryOperation::Div, Field, _) => 2,
+            (
+                BinaryOperation::Div
+                | BinaryOperation::DivWrapped
+                | BinaryOperation::Rem
+                | BinaryOperation::RemWrapped
+                | BinaryOperation::Mod,
+                _,
+                _,
+            ) => 4 * bits,
+            // An exponentiation squares and multiplies once for each bit of the exponent.
+            (BinaryOperation::Pow, Field, _) => 2 * 253,
+            (BinaryOperation::Pow, _, _) => second.bits() * 3 * bits,
+            (BinaryOperation::PowWrapped, _, _) => second.bits() * 2 * bits,
+            (
+                BinaryOperation::Shl | BinaryOperation::ShlWrapped | BinaryOperation::Shr | BinaryOperation::ShrWrapped,
+                _,
+                _,
+            ) => 2 * bits,
+            (
+                BinaryOperation::Gt | BinaryOperation::Gte | BinaryOperation::Lt | BinaryOperation::Lte,
+                Field | Scalar,
+                _,
+            ) => 2 * bits,
+            (BinaryOperation::Gt | BinaryOperation::Gte | BinaryOperation::Lt | BinaryOperation::Lte, _, _) => bits + 1,
+            (BinaryOperation::IsEq | BinaryOperation::IsNeq, Address | Group, _) => 4,
+            (BinaryOperation::IsEq | BinaryOperation::IsNeq, _, _) => 2,
+            (
+                BinaryOperation::And
+                | BinaryOperation::Nand
+                | BinaryOperation::Nor
+                | BinaryOperation::Or
+                | BinaryOperation::Xor,
+                _,
+                _,
+            ) => bits,
+        },
+        Instruction::Unary { operation, .. } => match (operation, first) {
+            // Negating a field or group element, and inverting bits, are linear.
+            (UnaryOperation::Negate, Field | Group) | (UnaryOperation::Not, _) => 0,
+            (UnaryOperation::Abs | UnaryOperation::AbsWrapped | UnaryOperation::Negate, _) => bits + 1,
+            (UnaryOperation::Double, Group) => 3,
+            (UnaryOperation::Double, _) => 0,
+            (UnaryOperation::Inverse, _) => 2,
+            (UnaryOperation::Square, _) => 1,
+            (UnaryOperation::SquareRoot, _) => 2 * bits,
+        },
+        // A cast to a literal type decomposes its operand, while a cast to a struct or record only groups its operands.
+        Instruction::Cast { type_, .. } => match type_ {
+            CastType::Literal(_) => bits,
+            CastType::Struct(_) | CastType::Record(_) => 0,
+        },
+        Instruction::Core { opcode, .. } => {
+            let function = opcode.rsplit('.').next().unwrap_or_default();
+            match function {
+                "bhp256" => 1_200,
+                "bhp512" => 2_400,
+                "bhp768" => 3_600,
+                "bhp1024" => 4_800,
+                "ped64" => 130,
+                "ped128" => 260,
+                "psd2" => 300,
+                "psd4" => 400,
+                "psd8" => 600,
+                // `sign.verify`.
+                _ => 3_000,
+            }
+        }
+        Instruction::AssertEq(..) => match first {
+            Address | Group => 2,
+            _ => 1,
+        },
+        Instruction::AssertNeq(..) => match first {
+            Address | Group => 4,
+            _ => 2,
+        },
+        Instruction::Ternary { .. } => match second {
+            Address | Group => 2,
+            _ => 1,
+        },
+        Instruction::Call { .. }
+        | Instruction::Decrement { .. }
+        | Instruction::Finalize(_)
+        | Instruction::Get { .. }
+        | Instruction::GetOrUse { .. }
+        | Instruction::Increment { .. }
+        | Instruction::Output(..)
+        | Instruction::RandChaCha { .. }
+        | Instruction::Set { .. } => 0,
+    }
+}
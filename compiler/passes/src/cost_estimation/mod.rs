@@ -0,0 +1,72 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The cost estimation pass estimates the number of constraints that snarkVM synthesizes to prove each closure and function
+//! of the Aleo instructions produced by register allocation, so that the expensive parts of a program can be found without proving it.
+//!
+//! Each instruction is estimated from a cost table of snarkVM opcodes, given the types of its operands,
+//! which are read from the declared inputs and literals and propagated through the destination registers.
+//! A call to a closure is estimated at the constraints of the closure, and the estimates are attributed to the line of the Leo program
+//! that each instruction was generated from. Finalize blocks are executed on chain rather than proven, so they are not estimated.
+//!
+//! Consider the following Aleo instructions, generated from lines 2 and 3 of a Leo program.
+//! ```aleo
+//! function main:
+//!     input r0 as u8.private;
+//!     mul r0 r0 into r1;
+//!     hash.bhp256 r1 into r2;
+//!     output r2 as field.private;
+//! ```
+//!
+//! The multiplication of `u8`s on line 2 is estimated at 24 constraints, and the BHP hash on line 3 at 1200 constraints.
+//! ```text
+//! function main: 1224 constraints
+//!     line 2: 24 constraints (1 instructions)
+//!     line 3: 1200 constraints (2 instructions)
+//! ```
+
+pub mod cost_estimator;
+pub use cost_estimator::*;
+
+pub mod cost_table;
+pub use cost_table::*;
+
+use crate::{Pass, PassSummary};
+
+use leo_ir::Program;
+
+use std::time::Instant;
+
+impl Pass for CostEstimator {
+    type Input = Program;
+    type Output = (Program, CostReport, PassSummary);
+
+    fn do_pass(program: Self::Input) -> Self::Output {
+        let start = Instant::now();
+        let mut estimator = CostEstimator::default();
+        let report = estimator.estimate_program(&program);
+
+        let summary = PassSummary {
+            name: "cost estimation",
+            nodes_visited: estimator.instructions_visited,
+            nodes_rewritten: 0,
+            diagnostics_emitted: None,
+            duration: start.elapsed(),
+            decisions: estimator.decisions,
+        };
+        (program, report, summary)
+    }
+}
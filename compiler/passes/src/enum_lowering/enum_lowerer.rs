@@ -0,0 +1,83 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::SymbolTable;
+
+use leo_ast::{Enum, Identifier, IntegerType, Literal, NodeBuilder, Type};
+use leo_span::{Span, Symbol};
+
+use indexmap::IndexMap;
+
+pub struct EnumLowerer<'a> {
+    /// The symbol table, in which enums are replaced by the integer types that they are lowered to.
+    pub(crate) symbol_table: SymbolTable,
+    /// The enums of the program, by name.
+    pub(crate) enums: IndexMap<Symbol, Enum>,
+    /// The integer type that each enum is lowered to.
+    pub(crate) substitution: IndexMap<Symbol, Type>,
+    /// A counter used to name the variables that matched values are assigned to.
+    pub(crate) counter: usize,
+    /// A counter used to generate unique ids for the nodes that the pass creates.
+    pub(crate) node_builder: &'a NodeBuilder,
+}
+
+impl<'a> EnumLowerer<'a> {
+    /// Initializes a new `EnumLowerer`.
+    pub(crate) fn new(symbol_table: SymbolTable, node_builder: &'a NodeBuilder) -> Self {
+        Self {
+            symbol_table,
+            enums: IndexMap::new(),
+            substitution: IndexMap::new(),
+            counter: 0,
+            node_builder,
+        }
+    }
+
+    /// Returns the smallest unsigned integer type with a value for each variant of the enum.
+    pub(crate) fn integer_type(enum_: &Enum) -> IntegerType {
+        match enum_.variants.len() {
+            0..=0x100 => IntegerType::U8,
+            0x101..=0x1_0000 => IntegerType::U16,
+            _ => IntegerType::U32,
+        }
+    }
+
+    /// Returns the literal that the variant of an enum is lowered to, if `enum_` is an enum.
+    /// Note that type checking guarantees that the variant is a variant of the enum.
+    pub(crate) fn variant_literal(&self, enum_: Symbol, variant: Identifier, span: Span) -> Option<Literal> {
+        let enum_ = self.enums.get(&enum_)?;
+        let index = enum_
+            .variant_index(variant.name)
+            .expect("Type checking guarantees that the variants of enums exist.");
+        Some(Literal::Integer(
+            Self::integer_type(enum_),
+            index.to_string(),
+            span,
+            self.node_builder.next_id(),
+        ))
+    }
+
+    /// Returns a new identifier for the variable that a matched value is assigned to.
+    pub(crate) fn unique_identifier(&mut self, span: Span) -> Identifier {
+        let name = Symbol::intern_internal(&format!("$match${}", self.counter));
+        self.counter += 1;
+        Identifier {
+            name,
+            span,
+            id: self.node_builder.next_id(),
+        }
+    }
+}
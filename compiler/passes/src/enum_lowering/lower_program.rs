@@ -0,0 +1,142 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::EnumLowerer;
+
+use leo_ast::{
+    ConstDeclaration, ExpressionReconstructor, Finalize, Function, Mapping, Member, ProgramReconstructor, ProgramScope,
+    StatementReconstructor, Struct, Type,
+};
+
+impl ProgramReconstructor for EnumLowerer<'_> {
+    /// Lowers the enums of the program scope into integer types, removing their definitions.
+    fn reconstruct_program_scope(&mut self, input: ProgramScope) -> ProgramScope {
+        self.enums = input
+            .enums
+            .values()
+            .map(|enum_| (enum_.name(), enum_.clone()))
+            .collect();
+        self.substitution = self
+            .enums
+            .values()
+            .map(|enum_| (enum_.name(), Type::Integer(Self::integer_type(enum_))))
+            .collect();
+        self.symbol_table.substitute_types(&self.substitution);
+
+        ProgramScope {
+            program_id: input.program_id,
+            consts: input
+                .consts
+                .into_iter()
+                .map(|(i, c)| (i, self.reconstruct_const(c)))
+                .collect(),
+            structs: input
+                .structs
+                .into_iter()
+                .map(|(i, c)| (i, self.reconstruct_struct(c)))
+                .collect(),
+            enums: Default::default(),
+            mappings: input
+                .mappings
+                .into_iter()
+                .map(|(id, mapping)| (id, self.reconstruct_mapping(mapping)))
+                .collect(),
+            functions: input
+                .functions
+                .into_iter()
+                .map(|(i, f)| (i, self.reconstruct_function(f)))
+                .collect(),
+            default_integer_type: input.default_integer_type,
+            span: input.span,
+        }
+    }
+
+    /// Lowers the enums in the types of the function's inputs and outputs, and reconstructs its body.
+    fn reconstruct_function(&mut self, input: Function) -> Function {
+        Function {
+            annotations: input.annotations,
+            call_type: input.call_type,
+            identifier: input.identifier,
+            type_parameters: input.type_parameters,
+            input: input
+                .input
+                .iter()
+                .map(|input| input.substitute(&self.substitution))
+                .collect(),
+            output: input
+                .output
+                .iter()
+                .map(|output| output.substitute(&self.substitution))
+                .collect(),
+            output_type: input.output_type.substitute(&self.substitution),
+            block: self.reconstruct_block(input.block).0,
+            finalize: input.finalize.map(|finalize| Finalize {
+                identifier: finalize.identifier,
+                input: finalize
+                    .input
+                    .iter()
+                    .map(|input| input.substitute(&self.substitution))
+                    .collect(),
+                output: finalize
+                    .output
+                    .iter()
+                    .map(|output| output.substitute(&self.substitution))
+                    .collect(),
+                output_type: finalize.output_type.substitute(&self.substitution),
+                block: self.reconstruct_block(finalize.block).0,
+                span: finalize.span,
+                id: finalize.id,
+            }),
+            span: input.span,
+            id: input.id,
+        }
+    }
+
+    /// Lowers the enums in the type of the constant and in its value.
+    fn reconstruct_const(&mut self, input: ConstDeclaration) -> ConstDeclaration {
+        ConstDeclaration {
+            identifier: input.identifier,
+            type_: input.type_.substitute(&self.substitution),
+            value: self.reconstruct_expression(input.value).0,
+            span: input.span,
+            id: input.id,
+        }
+    }
+
+    /// Lowers the enums in the types of the struct's members.
+    fn reconstruct_struct(&mut self, input: Struct) -> Struct {
+        Struct {
+            members: input
+                .members
+                .into_iter()
+                .map(|member| Member {
+                    type_: member.type_.substitute(&self.substitution),
+                    ..member
+                })
+                .collect(),
+            ..input
+        }
+    }
+
+    /// Lowers the enums in the key and value types of the mapping.
+    fn reconstruct_mapping(&mut self, input: Mapping) -> Mapping {
+        Mapping {
+            key_type: input.key_type.substitute(&self.substitution),
+            value_type: input.value_type.substitute(&self.substitution),
+            ..input
+        }
+    }
+}
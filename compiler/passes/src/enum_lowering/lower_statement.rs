@@ -0,0 +1,159 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::EnumLowerer;
+
+use leo_ast::{
+    BinaryExpression, BinaryOperation, Block, ConditionalStatement, DeclarationType, DefinitionStatement, Expression,
+    ExpressionReconstructor, ExpressionStatement, IterationStatement, MatchPattern, MatchStatement, Node, Statement,
+    StatementReconstructor,
+};
+
+use std::cell::RefCell;
+
+impl StatementReconstructor for EnumLowerer<'_> {
+    /// Reconstructs the statements of a block, inserting the statements produced by lowering a `match` statement before it.
+    fn reconstruct_block(&mut self, input: Block) -> (Block, Self::AdditionalOutput) {
+        let mut statements = Vec::with_capacity(input.statements.len());
+        for statement in input.statements {
+            let (statement, additional_statements) = self.reconstruct_statement(statement);
+            statements.extend(additional_statements);
+            statements.push(statement);
+        }
+
+        (
+            Block {
+                statements,
+                span: input.span,
+                id: input.id,
+            },
+            Default::default(),
+        )
+    }
+
+    /// Lowers the enums in the type of the binding.
+    fn reconstruct_definition(&mut self, input: DefinitionStatement) -> (Statement, Self::AdditionalOutput) {
+        let type_ = input.type_().map(|type_| type_.substitute(&self.substitution));
+        (
+            Statement::Definition(DefinitionStatement {
+                declaration_type: input.declaration_type,
+                place: input.place,
+                type_: RefCell::new(type_),
+                value: self.reconstruct_expression(input.value).0,
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    /// Lowers the enums in the type of the iteration variable.
+    fn reconstruct_iteration(&mut self, input: IterationStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Iteration(Box::new(IterationStatement {
+                variable: input.variable,
+                type_: input.type_.substitute(&self.substitution),
+                start: self.reconstruct_expression(input.start).0,
+                start_value: input.start_value,
+                stop: input.stop.map(|stop| self.reconstruct_expression(stop).0),
+                stop_value: input.stop_value,
+                block: self.reconstruct_block(input.block).0,
+                inclusive: input.inclusive,
+                span: input.span,
+                id: input.id,
+            })),
+            Default::default(),
+        )
+    }
+
+    /// Lowers a `match` statement into a chain of conditional statements, one for each arm.
+    /// Note that type checking guarantees that the arms cover every variant, and that only the last arm can be a wildcard.
+    fn reconstruct_match(&mut self, input: MatchStatement) -> (Statement, Self::AdditionalOutput) {
+        let mut statements = Vec::new();
+        let expression = self.reconstruct_expression(input.expression).0;
+
+        // The integer type of the matched enum, which is known from any arm that matches a variant.
+        let type_ = input.arms.iter().find_map(|arm| match &arm.pattern {
+            MatchPattern::Variant(enum_, _) => self.substitution.get(&enum_.name).cloned(),
+            MatchPattern::Wildcard(_) => None,
+        });
+
+        // Unless the matched value is a variable, assign it to a new variable, so that it is evaluated once.
+        let value = match (expression, type_) {
+            (Expression::Identifier(identifier), _) => Some(Expression::Identifier(identifier)),
+            (expression, Some(type_)) => {
+                let identifier = self.unique_identifier(expression.span());
+                statements.push(Statement::Definition(DefinitionStatement {
+                    declaration_type: DeclarationType::Let,
+                    place: Expression::Identifier(identifier),
+                    type_: RefCell::new(Some(type_)),
+                    value: expression,
+                    span: input.span,
+                    id: input.id,
+                }));
+                Some(Expression::Identifier(identifier))
+            }
+            // If every arm is a wildcard, the value is never compared, so it is only evaluated if it is a call.
+            (expression @ Expression::Call(_), None) => {
+                statements.push(Statement::Expression(ExpressionStatement {
+                    expression,
+                    span: input.span,
+                    id: input.id,
+                }));
+                None
+            }
+            (_, None) => None,
+        };
+
+        let mut arms: Vec<_> = input
+            .arms
+            .into_iter()
+            .map(|arm| (arm.pattern, self.reconstruct_block(arm.block).0))
+            .collect();
+
+        // The last arm is taken whenever the arms before it are not, so its pattern is not compared.
+        let (_, last) = arms
+            .pop()
+            .expect("Type checking guarantees that a `match` statement has an arm.");
+        let statement = arms
+            .into_iter()
+            .rev()
+            .fold(Statement::Block(last), |otherwise, (pattern, then)| {
+                let condition = match (pattern, &value) {
+                    (MatchPattern::Variant(enum_, variant), Some(value)) => Expression::Binary(BinaryExpression {
+                        left: Box::new(value.clone()),
+                        right: Box::new(Expression::Literal(
+                            self.variant_literal(enum_.name, variant, variant.span)
+                                .expect("Type checking guarantees that the pattern is a variant of an enum."),
+                        )),
+                        op: BinaryOperation::Eq,
+                        span: enum_.span + variant.span,
+                        id: enum_.id,
+                    }),
+                    _ => unreachable!("Type checking guarantees that only the last arm can be a wildcard."),
+                };
+                Statement::Conditional(ConditionalStatement {
+                    condition,
+                    then,
+                    otherwise: Some(Box::new(otherwise)),
+                    span: input.span,
+                    id: input.id,
+                })
+            });
+
+        (statement, statements)
+    }
+}
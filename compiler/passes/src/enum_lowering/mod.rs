@@ -0,0 +1,77 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The enum lowering pass traverses the AST after type checking and lowers enums into unsigned integers.
+//! Each enum is lowered into the smallest unsigned integer type that has a value for each of its variants, and each variant into its position among them.
+//! Each `match` statement is lowered into a chain of conditional statements, one for each arm, in the order of the arms.
+//! Type checking guarantees that the arms cover every variant, so the last arm is taken whenever the others are not, and its pattern is not compared.
+//! If the matched value is not a variable, it is first assigned to a new variable, so that it is evaluated once.
+//!
+//! Consider the following Leo code.
+//! ```leo
+//! enum State { Idle, Running, Done }
+//!
+//! transition step(state: State) -> State {
+//!     match state {
+//!         State::Idle => { return State::Running; }
+//!         State::Running => { return State::Done; }
+//!         _ => { return state; }
+//!     }
+//! }
+//! ```
+//!
+//! The enum lowering pass produces the following code.
+//! ```leo
+//! transition step(state: u8) -> u8 {
+//!     if state == 0u8 {
+//!         return 1u8;
+//!     } else if state == 1u8 {
+//!         return 2u8;
+//!     } else {
+//!         return state;
+//!     }
+//! }
+//! ```
+
+mod lower_expression;
+
+mod lower_program;
+
+mod lower_statement;
+
+pub mod enum_lowerer;
+pub use enum_lowerer::*;
+
+use crate::{Pass, PassSummary, SymbolTable};
+
+use leo_ast::{Ast, NodeBuilder, ProgramReconstructor};
+use leo_errors::Result;
+
+impl<'a> Pass for EnumLowerer<'a> {
+    type Input = (Ast, SymbolTable, &'a NodeBuilder);
+    type Output = Result<(Ast, SymbolTable, PassSummary)>;
+
+    fn do_pass((ast, symbol_table, node_builder): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("enum lowering", None);
+        let mut reconstructor = EnumLowerer::new(symbol_table, node_builder);
+        let program = reconstructor.reconstruct_program(ast.into_repr());
+
+        let ast = Ast::new(program);
+        let summary = recorder.finish(None);
+
+        Ok((ast, reconstructor.symbol_table, summary))
+    }
+}
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::Flattener;
+use crate::{array_member_element, lower_struct_members, Flattener};
 use itertools::Itertools;
 
 use leo_ast::{
@@ -46,7 +46,8 @@ impl ExpressionReconstructor for Flattener<'_> {
         let mut statements = Vec::new();
         match (*input.if_true, *input.if_false) {
             // Folds ternary expressions over tuples into a tuple of ternary expression.
-            // Note that this branch is only invoked when folding a conditional returns.
+            // Note that the tuple lowering pass removes the ternary expressions over tuples in the program,
+            // so this branch is only invoked when folding conditional returns or selecting between lowered arrays.
             (Expression::Tuple(first), Expression::Tuple(second)) => {
                 let tuple = Expression::Tuple(TupleExpression {
                     elements: first
@@ -68,157 +69,83 @@ impl ExpressionReconstructor for Flattener<'_> {
                                 if_true: Box::new(if_true),
                                 if_false: Box::new(if_false),
                                 span: input.span,
+                                id: self.node_builder.next_id(),
                             });
 
                             // Accumulate any statements generated.
                             statements.extend(stmts);
 
-                            // Create and accumulate an intermediate assignment statement for the ternary expression corresponding to the tuple element.
-                            let (identifier, statement) = self.unique_simple_assign_statement(ternary);
-                            statements.push(statement);
+                            match ternary {
+                                // If the element is itself a tuple, e.g. a lowered nested array, then use it directly.
+                                Expression::Tuple(_) => ternary,
+                                _ => {
+                                    // Create and accumulate an intermediate assignment statement for the ternary expression corresponding to the tuple element.
+                                    let (identifier, statement) = self.unique_simple_assign_statement(ternary);
+                                    statements.push(statement);
 
-                            // Return the identifier associated with the folded tuple element.
-                            Expression::Identifier(identifier)
+                                    // Return the identifier associated with the folded tuple element.
+                                    Expression::Identifier(identifier)
+                                }
+                            }
                         })
                         .collect(),
                     span: Default::default(),
+                    id: input.id,
                 });
                 (tuple, statements)
             }
-            // If both expressions are access expressions which themselves are structs, construct ternary expression for nested struct member.
-            (
-                Expression::Access(AccessExpression::Member(first)),
-                Expression::Access(AccessExpression::Member(second)),
-            ) => {
-                // Lookup the struct symbols associated with the expressions.
-                // TODO: Remove clones
-                let first_struct_symbol =
-                    self.lookup_struct_symbol(&Expression::Access(AccessExpression::Member(first.clone())));
-                let second_struct_symbol =
-                    self.lookup_struct_symbol(&Expression::Access(AccessExpression::Member(second.clone())));
-
-                match (first_struct_symbol, second_struct_symbol) {
-                    (Some(first_struct_symbol), Some(second_struct_symbol)) => {
-                        let first_member_struct = self.symbol_table.lookup_struct(first_struct_symbol).unwrap();
-                        let second_member_struct = self.symbol_table.lookup_struct(second_struct_symbol).unwrap();
-                        // Note that type checking guarantees that both expressions have the same same type. This is a sanity check.
-                        assert_eq!(first_member_struct, second_member_struct);
-
-                        // For each struct member, construct a new ternary expression.
-                        let members = first_member_struct
-                            .members
-                            .iter()
-                            .map(|Member { identifier, .. }| {
-                                // Construct a new ternary expression for the struct member.
-                                let (expression, stmts) = self.reconstruct_ternary(TernaryExpression {
-                                    condition: input.condition.clone(),
-                                    if_true: Box::new(Expression::Access(AccessExpression::Member(MemberAccess {
-                                        inner: Box::new(Expression::Access(AccessExpression::Member(first.clone()))),
-                                        name: *identifier,
-                                        span: Default::default(),
-                                    }))),
-                                    if_false: Box::new(Expression::Access(AccessExpression::Member(MemberAccess {
-                                        inner: Box::new(Expression::Access(AccessExpression::Member(second.clone()))),
-                                        name: *identifier,
-                                        span: Default::default(),
-                                    }))),
-                                    span: Default::default(),
-                                });
-
-                                // Accumulate any statements generated.
-                                statements.extend(stmts);
-
-                                // Create and accumulate an intermediate assignment statement for the ternary expression corresponding to the struct member.
-                                let (identifier, statement) = self.unique_simple_assign_statement(expression);
-                                statements.push(statement);
-
-                                StructVariableInitializer {
-                                    identifier,
-                                    expression: Some(Expression::Identifier(identifier)),
-                                }
-                            })
-                            .collect();
-
-                        let (expr, stmts) = self.reconstruct_struct_init(StructExpression {
-                            name: first_member_struct.identifier,
-                            members,
-                            span: Default::default(),
-                        });
-
-                        // Accumulate any statements generated.
-                        statements.extend(stmts);
-
-                        // Create a new assignment statement for the struct expression.
-                        let (identifier, statement) = self.unique_simple_assign_statement(expr);
-
-                        // Mark the lhs of the assignment as a struct.
-                        self.structs
-                            .insert(identifier.name, first_member_struct.identifier.name);
-
-                        statements.push(statement);
-
-                        (Expression::Identifier(identifier), statements)
-                    }
-                    _ => {
-                        let if_true = Expression::Access(AccessExpression::Member(first));
-                        let if_false = Expression::Access(AccessExpression::Member(second));
-                        // Reconstruct the true case.
-                        let (if_true, stmts) = self.reconstruct_expression(if_true);
-                        statements.extend(stmts);
-
-                        // Reconstruct the false case.
-                        let (if_false, stmts) = self.reconstruct_expression(if_false);
-                        statements.extend(stmts);
-
-                        let (identifier, statement) =
-                            self.unique_simple_assign_statement(Expression::Ternary(TernaryExpression {
-                                condition: input.condition,
-                                if_true: Box::new(if_true),
-                                if_false: Box::new(if_false),
-                                span: input.span,
-                            }));
-
-                        // Accumulate the new assignment statement.
-                        statements.push(statement);
-
-                        (Expression::Identifier(identifier), statements)
-                    }
-                }
-            }
-            // If both expressions are identifiers which are structs, construct ternary expression for each of the members and a struct expression for the result.
+            // If both expressions are identifiers which are arrays, construct a ternary expression over their lowered tuples.
             (Expression::Identifier(first), Expression::Identifier(second))
-                if self.structs.contains_key(&first.name) && self.structs.contains_key(&second.name) =>
+                if self.arrays.contains_key(&first.name) && self.arrays.contains_key(&second.name) =>
+            {
+                let first = self.arrays.get(&first.name).unwrap().clone();
+                let second = self.arrays.get(&second.name).unwrap().clone();
+                self.reconstruct_ternary(TernaryExpression {
+                    condition: input.condition,
+                    if_true: Box::new(Expression::Tuple(first)),
+                    if_false: Box::new(Expression::Tuple(second)),
+                    span: input.span,
+                    id: input.id,
+                })
+            }
+            // If both expressions are structs, construct a ternary expression for each of the members and a struct expression for the result.
+            // Note that the members are accessed through the original expressions, so members that are themselves structs,
+            // e.g. `a.b` in `cond ? a.b : c`, are recursively expanded, since their types are found through the type table.
+            (first, second)
+                if self.lookup_struct_symbol(&first).is_some() && self.lookup_struct_symbol(&second).is_some() =>
             {
                 let first_struct = self
-                    .symbol_table
-                    .lookup_struct(*self.structs.get(&first.name).unwrap())
+                    .lookup_struct_symbol(&first)
+                    .and_then(|name| self.symbol_table.lookup_struct(name))
                     .unwrap();
                 let second_struct = self
-                    .symbol_table
-                    .lookup_struct(*self.structs.get(&second.name).unwrap())
+                    .lookup_struct_symbol(&second)
+                    .and_then(|name| self.symbol_table.lookup_struct(name))
                     .unwrap();
                 // Note that type checking guarantees that both expressions have the same same type. This is a sanity check.
                 assert_eq!(first_struct, second_struct);
 
                 // For each struct member, construct a new ternary expression.
-                let members = first_struct
-                    .members
-                    .iter()
+                let members = lower_struct_members(&first_struct.members)
+                    .into_iter()
                     .map(|Member { identifier, .. }| {
                         // Construct a new ternary expression for the struct member.
                         let (expression, stmts) = self.reconstruct_ternary(TernaryExpression {
                             condition: input.condition.clone(),
                             if_true: Box::new(Expression::Access(AccessExpression::Member(MemberAccess {
-                                inner: Box::new(Expression::Identifier(first)),
-                                name: *identifier,
+                                inner: Box::new(first.clone()),
+                                name: identifier,
                                 span: Default::default(),
+                                id: self.node_builder.next_id(),
                             }))),
                             if_false: Box::new(Expression::Access(AccessExpression::Member(MemberAccess {
-                                inner: Box::new(Expression::Identifier(second)),
-                                name: *identifier,
+                                inner: Box::new(second.clone()),
+                                name: identifier,
                                 span: Default::default(),
+                                id: self.node_builder.next_id(),
                             }))),
                             span: Default::default(),
+                            id: self.node_builder.next_id(),
                         });
 
                         // Accumulate any statements generated.
@@ -237,8 +164,10 @@ impl ExpressionReconstructor for Flattener<'_> {
 
                 let (expr, stmts) = self.reconstruct_struct_init(StructExpression {
                     name: first_struct.identifier,
+                    const_arguments: Vec::new(),
                     members,
                     span: Default::default(),
+                    id: self.node_builder.next_id(),
                 });
 
                 // Accumulate any statements generated.
@@ -247,9 +176,6 @@ impl ExpressionReconstructor for Flattener<'_> {
                 // Create a new assignment statement for the struct expression.
                 let (identifier, statement) = self.unique_simple_assign_statement(expr);
 
-                // Mark the lhs of the assignment as a struct.
-                self.structs.insert(identifier.name, first_struct.identifier.name);
-
                 statements.push(statement);
 
                 (Expression::Identifier(identifier), statements)
@@ -271,6 +197,7 @@ impl ExpressionReconstructor for Flattener<'_> {
                         if_true: Box::new(if_true),
                         if_false: Box::new(if_false),
                         span: input.span,
+                        id: input.id,
                     }));
 
                 // Accumulate the new assignment statement.
@@ -280,4 +207,39 @@ impl ExpressionReconstructor for Flattener<'_> {
             }
         }
     }
+
+    /// Lowers each array member of a struct expression into a member per element.
+    /// Note that SSA guarantees that the array members are identifiers, whose lowered elements are tracked in `self.arrays`.
+    fn reconstruct_struct_init(&mut self, input: StructExpression) -> (Expression, Self::AdditionalOutput) {
+        let members = input
+            .members
+            .into_iter()
+            .flat_map(|member| match &member.expression {
+                Some(Expression::Identifier(identifier)) if self.arrays.contains_key(&identifier.name) => self
+                    .arrays
+                    .get(&identifier.name)
+                    .unwrap()
+                    .elements
+                    .iter()
+                    .enumerate()
+                    .map(|(index, element)| StructVariableInitializer {
+                        identifier: array_member_element(member.identifier, index),
+                        expression: Some(element.clone()),
+                    })
+                    .collect(),
+                _ => vec![member],
+            })
+            .collect();
+
+        (
+            Expression::Struct(StructExpression {
+                name: input.name,
+                const_arguments: input.const_arguments,
+                members,
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
 }
@@ -14,27 +14,21 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::Flattener;
+use crate::{lower_struct_members, Flattener};
 
 use leo_ast::{
     Finalize, FinalizeStatement, Function, ProgramReconstructor, ReturnStatement, Statement, StatementReconstructor,
-    Type,
+    Struct,
 };
 
 impl ProgramReconstructor for Flattener<'_> {
     /// Flattens a function's body and finalize block, if it exists.
     fn reconstruct_function(&mut self, function: Function) -> Function {
+        self.function = Some(function.name());
+
         // First, flatten the finalize block. This allows us to initialize self.finalizes correctly.
         // Note that this is safe since the finalize block is independent of the function body.
         let finalize = function.finalize.map(|finalize| {
-            // Initialize `self.structs` with the finalize's input as necessary.
-            self.structs = Default::default();
-            for input in &finalize.input {
-                if let Type::Identifier(struct_name) = input.type_() {
-                    self.structs.insert(input.identifier().name, struct_name.name);
-                }
-            }
-
             // Flatten the finalize block.
             let mut block = self.reconstruct_block(finalize.block).0;
 
@@ -43,6 +37,8 @@ impl ProgramReconstructor for Flattener<'_> {
 
             // If the finalize block contains return statements, then we fold them into a single return statement.
             if !returns.is_empty() {
+                let assertions = self.assert_some_guard_holds(&returns);
+                block.statements.extend(assertions);
                 let (expression, stmts) = self.fold_guards("ret$", returns);
 
                 // Add all of the accumulated statements to the end of the block.
@@ -52,6 +48,7 @@ impl ProgramReconstructor for Flattener<'_> {
                 block.statements.push(Statement::Return(ReturnStatement {
                     expression,
                     span: Default::default(),
+                    id: self.node_builder.next_id(),
                 }));
             }
 
@@ -65,17 +62,10 @@ impl ProgramReconstructor for Flattener<'_> {
                 output_type: finalize.output_type,
                 block,
                 span: finalize.span,
+                id: finalize.id,
             }
         });
 
-        // Initialize `self.structs` with the function's input as necessary.
-        self.structs = Default::default();
-        for input in &function.input {
-            if let Type::Identifier(struct_name) = input.type_() {
-                self.structs.insert(input.identifier().name, struct_name.name);
-            }
-        }
-
         // Flatten the function body.
         let mut block = self.reconstruct_block(function.block).0;
 
@@ -84,6 +74,8 @@ impl ProgramReconstructor for Flattener<'_> {
 
         // If the function contains return statements, then we fold them into a single return statement.
         if !returns.is_empty() {
+            let assertions = self.assert_some_guard_holds(&returns);
+            block.statements.extend(assertions);
             let (expression, stmts) = self.fold_guards("ret$", returns);
 
             // Add all of the accumulated statements to the end of the block.
@@ -93,6 +85,7 @@ impl ProgramReconstructor for Flattener<'_> {
             block.statements.push(Statement::Return(ReturnStatement {
                 expression,
                 span: Default::default(),
+                id: self.node_builder.next_id(),
             }));
         }
 
@@ -106,6 +99,10 @@ impl ProgramReconstructor for Flattener<'_> {
                 // If the function contains finalize statements with at least one argument, then we fold them into a vector of arguments.
                 // Note that `finalizes` is always initialized to the appropriate number of vectors.
                 false => {
+                    // Every argument is folded over the same guards, so it suffices to check the guards of the first.
+                    let assertions = self.assert_some_guard_holds(&finalize_arguments[0]);
+                    block.statements.extend(assertions);
+
                     // Construct an expression for each argument to the finalize statement.
                     finalize_arguments
                         .into_iter()
@@ -126,6 +123,7 @@ impl ProgramReconstructor for Flattener<'_> {
             block.statements.push(Statement::Finalize(FinalizeStatement {
                 arguments,
                 span: Default::default(),
+                id: self.node_builder.next_id(),
             }));
         }
 
@@ -133,12 +131,22 @@ impl ProgramReconstructor for Flattener<'_> {
             annotations: function.annotations,
             call_type: function.call_type,
             identifier: function.identifier,
+            type_parameters: function.type_parameters,
             input: function.input,
             output: function.output,
             output_type: function.output_type,
             block,
             finalize,
             span: function.span,
+            id: function.id,
+        }
+    }
+
+    /// Lowers each array member of a struct into a member per element.
+    fn reconstruct_struct(&mut self, input: Struct) -> Struct {
+        Struct {
+            members: lower_struct_members(&input.members),
+            ..input
         }
     }
 }
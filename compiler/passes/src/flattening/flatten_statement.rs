@@ -17,37 +17,147 @@
 use crate::Flattener;
 
 use leo_ast::{
-    AssignStatement, BinaryExpression, BinaryOperation, Block, ConditionalStatement, DefinitionStatement, Expression,
-    ExpressionReconstructor, FinalizeStatement, IterationStatement, Node, ReturnStatement, Statement,
-    StatementReconstructor, UnaryExpression, UnaryOperation,
+    AccessExpression, AssertStatement, AssertVariant, AssignStatement, AssociatedFunction, BinaryOperation, Block,
+    ConditionalStatement, DefinitionStatement, Expression, ExpressionReconstructor, FinalizeStatement,
+    IterationStatement, Node, ReturnStatement, Statement, StatementReconstructor, TupleExpression, UnaryExpression,
+    UnaryOperation,
 };
 
 impl StatementReconstructor for Flattener<'_> {
+    /// Lowers an assertion that compares strings into an assertion over the comparison of the fields that they are lowered to.
+    /// Otherwise, the statement is returned as is.
+    fn reconstruct_assert(&mut self, input: AssertStatement) -> (Statement, Self::AdditionalOutput) {
+        let (left, right, op) = match &input.variant {
+            AssertVariant::AssertEq(left, right) => (left, right, BinaryOperation::Eq),
+            AssertVariant::AssertNeq(left, right) => (left, right, BinaryOperation::Neq),
+            AssertVariant::Assert(_) => return (Statement::Assert(input), Default::default()),
+        };
+        let (left, right) = match (self.lower_string(left), self.lower_string(right)) {
+            (Some(left), Some(right)) => (left, right),
+            _ => return (Statement::Assert(input), Default::default()),
+        };
+
+        let (comparison, mut statements) = self.reconstruct_string_comparison(left, right, op);
+        let (place, statement) = self.unique_simple_assign_statement(comparison);
+        statements.push(statement);
+
+        (
+            Statement::Assert(AssertStatement {
+                variant: AssertVariant::Assert(Expression::Identifier(place)),
+                span: input.span,
+                id: input.id,
+            }),
+            statements,
+        )
+    }
+
     /// Flattens an assign statement, if necessary.
-    /// Marks variables as structs as necessary.
+    /// Records the type of the assigned variable.
     /// Note that new statements are only produced if the right hand side is a ternary expression over structs.
-    /// Otherwise, the statement is returned as is.
+    /// Arrays are lowered into tuples, which are tracked in `self.arrays` instead of being assigned.
+    /// Strings are similarly lowered into tuples of fields, which are tracked in `self.strings`.
+    /// Otherwise, the statement is returned as is, as is an assignment of the outputs of a call to multiple variables.
     fn reconstruct_assign(&mut self, assign: AssignStatement) -> (Statement, Self::AdditionalOutput) {
         let lhs = match assign.place {
             Expression::Identifier(identifier) => identifier,
-            _ => unreachable!("`AssignStatement`s can only have `Identifier`s on the left hand side."),
+            Expression::Tuple(_) => return (Statement::Assign(Box::new(assign)), Default::default()),
+            _ => {
+                unreachable!("`AssignStatement`s can only have `Identifier`s or tuples of them on the left hand side.")
+            }
         };
 
         let (value, statements) = match assign.value {
             // If the rhs of the assignment is ternary expression, reconstruct it.
             Expression::Ternary(ternary) => self.reconstruct_ternary(ternary),
+            // If the rhs of the assignment is an array expression, lower it into a tuple.
+            Expression::Array(array) => (
+                Expression::Tuple(TupleExpression {
+                    elements: array.elements,
+                    span: array.span,
+                    id: array.id,
+                }),
+                Default::default(),
+            ),
+            // If the rhs of the assignment is an array access, replace it with the accessed element.
+            Expression::Access(AccessExpression::Array(access)) => {
+                (self.lookup_array_element(&access), Default::default())
+            }
+            // If the rhs of the assignment accesses an array member of a struct, lower it into a tuple of the members its elements are lowered to.
+            Expression::Access(AccessExpression::Member(access)) if self.lower_array_member(&access).is_some() => (
+                Expression::Tuple(self.lower_array_member(&access).unwrap()),
+                Default::default(),
+            ),
+            // If the rhs of the assignment is a struct expression, lower its array members.
+            Expression::Struct(struct_) => self.reconstruct_struct_init(struct_),
+            // If the rhs of the assignment compares strings, compare the fields that they are lowered to.
+            Expression::Binary(binary)
+                if matches!(binary.op, BinaryOperation::Eq | BinaryOperation::Neq)
+                    && self.lower_string(&binary.left).is_some() =>
+            {
+                let left = self.lower_string(&binary.left).unwrap();
+                let right = self
+                    .lower_string(&binary.right)
+                    .expect("Type checking guarantees that strings are only compared with strings.");
+                self.reconstruct_string_comparison(left, right, binary.op)
+            }
+            // If the rhs of the assignment is a call to a core function, replace each string argument with the field it is lowered to.
+            // Note that type checking guarantees that the hashed strings are lowered to a single field.
+            Expression::Access(AccessExpression::AssociatedFunction(function)) => (
+                Expression::Access(AccessExpression::AssociatedFunction(AssociatedFunction {
+                    args: function
+                        .args
+                        .into_iter()
+                        .map(|arg| match self.lower_string(&arg) {
+                            Some(fields) => fields.elements[0].clone(),
+                            None => arg,
+                        })
+                        .collect(),
+                    ..function
+                })),
+                Default::default(),
+            ),
             // Otherwise return the original statement.
             value => (value, Default::default()),
         };
 
-        // Update the `self.structs` if the rhs is a struct.
-        self.update_structs(&lhs, &value);
+        // Record the type of the lhs, since the rhs may have been replaced by a new expression.
+        self.record_type(&lhs, &value);
+
+        // If the rhs is a string, track its lowered fields instead of producing an assignment.
+        if let Some(fields) = self.lower_string(&value) {
+            self.strings.insert(lhs.name, fields);
+            return (
+                Statement::dummy(Default::default(), self.node_builder.next_id()),
+                statements,
+            );
+        }
+
+        // If the rhs is an array, track its lowered elements instead of producing an assignment.
+        match &value {
+            Expression::Tuple(tuple) => {
+                self.arrays.insert(lhs.name, tuple.clone());
+                return (
+                    Statement::dummy(Default::default(), self.node_builder.next_id()),
+                    statements,
+                );
+            }
+            Expression::Identifier(identifier) if self.arrays.contains_key(&identifier.name) => {
+                let tuple = self.arrays.get(&identifier.name).unwrap().clone();
+                self.arrays.insert(lhs.name, tuple);
+                return (
+                    Statement::dummy(Default::default(), self.node_builder.next_id()),
+                    statements,
+                );
+            }
+            _ => {}
+        }
 
         (
             Statement::Assign(Box::new(AssignStatement {
                 place: Expression::Identifier(lhs),
                 value,
                 span: assign.span,
+                id: assign.id,
             })),
             statements,
         )
@@ -70,32 +180,45 @@ impl StatementReconstructor for Flattener<'_> {
             Block {
                 span: block.span,
                 statements,
+                id: block.id,
             },
             Default::default(),
         )
     }
 
     /// Flatten a conditional statement into a list of statements.
+    /// Each branch is predicated on a guard that conjoins all of the conditions on its path,
+    /// so that the branches of an `if`/`else if`/`else` chain remain mutually exclusive.
     fn reconstruct_conditional(&mut self, conditional: ConditionalStatement) -> (Statement, Self::AdditionalOutput) {
+        // If the branches perform the same operations, the cost model may choose to evaluate them once over selected operands.
+        if let Some(statements) = self.select_operands(&conditional) {
+            return (
+                Statement::dummy(Default::default(), self.node_builder.next_id()),
+                statements,
+            );
+        }
+
         let mut statements = Vec::with_capacity(conditional.then.statements.len());
 
-        // Add condition to the condition stack.
-        self.condition_stack.push(conditional.condition.clone());
+        // Add the guard for the then-block to the condition stack.
+        statements.extend(self.push_guard(conditional.condition.clone()));
 
         // Reconstruct the then-block and accumulate it constituent statements.
         statements.extend(self.reconstruct_block(conditional.then).0.statements);
 
-        // Remove condition from the condition stack.
+        // Remove the guard from the condition stack.
         self.condition_stack.pop();
 
         // Consume the otherwise-block and flatten its constituent statements into the current block.
+        // Note that SSA wraps an `else if` in a block, so nested conditionals are flattened with the negated guard on the stack.
         if let Some(statement) = conditional.otherwise {
-            // Add the negated condition to the condition stack.
-            self.condition_stack.push(Expression::Unary(UnaryExpression {
+            // Add the guard for the otherwise-block, using the negated condition, to the condition stack.
+            statements.extend(self.push_guard(Expression::Unary(UnaryExpression {
                 op: UnaryOperation::Not,
                 receiver: Box::new(conditional.condition.clone()),
                 span: conditional.condition.span(),
-            }));
+                id: self.node_builder.next_id(),
+            })));
 
             // Reconstruct the otherwise-block and accumulate it constituent statements.
             match *statement {
@@ -103,11 +226,14 @@ impl StatementReconstructor for Flattener<'_> {
                 _ => unreachable!("SSA guarantees that the `otherwise` is always a `Block`"),
             }
 
-            // Remove the negated condition from the condition stack.
+            // Remove the guard from the condition stack.
             self.condition_stack.pop();
         };
 
-        (Statement::dummy(Default::default()), statements)
+        (
+            Statement::dummy(Default::default(), self.node_builder.next_id()),
+            statements,
+        )
     }
 
     /// Static single assignment converts definition statements into assignment statements.
@@ -118,21 +244,8 @@ impl StatementReconstructor for Flattener<'_> {
     /// Replaces a finalize statement with an empty block statement.
     /// Stores the arguments to the finalize statement, which are later folded into a single finalize statement at the end of the function.
     fn reconstruct_finalize(&mut self, input: FinalizeStatement) -> (Statement, Self::AdditionalOutput) {
-        // Construct the associated guard.
-        let guard = match self.condition_stack.is_empty() {
-            true => None,
-            false => {
-                let (first, rest) = self.condition_stack.split_first().unwrap();
-                Some(rest.iter().cloned().fold(first.clone(), |acc, condition| {
-                    Expression::Binary(BinaryExpression {
-                        op: BinaryOperation::And,
-                        left: Box::new(acc),
-                        right: Box::new(condition),
-                        span: Default::default(),
-                    })
-                }))
-            }
-        };
+        // Get the associated guard.
+        let guard = self.construct_guard();
 
         // For each finalize argument, add it and its associated guard to the appropriate list of finalize arguments.
         // Note that type checking guarantees that the number of arguments in a finalize statement is equal to the number of arguments in to the finalize block.
@@ -141,7 +254,10 @@ impl StatementReconstructor for Flattener<'_> {
             self.finalizes.get_mut(i).unwrap().push((guard.clone(), argument));
         }
 
-        (Statement::dummy(Default::default()), Default::default())
+        (
+            Statement::dummy(Default::default(), self.node_builder.next_id()),
+            Default::default(),
+        )
     }
 
     // TODO: Error message requesting the user to enable loop-unrolling.
@@ -152,25 +268,15 @@ impl StatementReconstructor for Flattener<'_> {
     /// Transforms a return statement into an empty block statement.
     /// Stores the arguments to the return statement, which are later folded into a single return statement at the end of the function.
     fn reconstruct_return(&mut self, input: ReturnStatement) -> (Statement, Self::AdditionalOutput) {
-        // Construct the associated guard.
-        let guard = match self.condition_stack.is_empty() {
-            true => None,
-            false => {
-                let (first, rest) = self.condition_stack.split_first().unwrap();
-                Some(rest.iter().cloned().fold(first.clone(), |acc, condition| {
-                    Expression::Binary(BinaryExpression {
-                        op: BinaryOperation::And,
-                        left: Box::new(acc),
-                        right: Box::new(condition),
-                        span: Default::default(),
-                    })
-                }))
-            }
-        };
+        // Get the associated guard.
+        let guard = self.construct_guard();
 
         // Add it to the list of return statements.
         self.returns.push((guard, input.expression));
 
-        (Statement::dummy(Default::default()), Default::default())
+        (
+            Statement::dummy(Default::default(), self.node_builder.next_id()),
+            Default::default(),
+        )
     }
 }
@@ -14,12 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Assigner, SymbolTable};
+use crate::{Assigner, SymbolTable, TypeTable};
 
 use leo_ast::{
-    AccessExpression, Expression, ExpressionReconstructor, Identifier, Member, Statement, TernaryExpression, Type,
+    AccessExpression, ArrayAccess, AssertStatement, AssertVariant, BinaryExpression, BinaryOperation, Expression,
+    ExpressionReconstructor, Identifier, Literal, Member, MemberAccess, Node, NodeBuilder, Statement,
+    TernaryExpression, TupleExpression, Type,
 };
-use leo_span::Symbol;
+use leo_span::{Span, Symbol};
 
 use indexmap::IndexMap;
 
@@ -29,9 +31,18 @@ pub struct Flattener<'a> {
     pub(crate) symbol_table: &'a SymbolTable,
     /// An struct used to construct (unique) assignment statements.
     pub(crate) assigner: Assigner,
-    /// The set of variables that are structs.
-    pub(crate) structs: IndexMap<Symbol, Symbol>,
-    /// A stack of condition `Expression`s visited up to the current point in the AST.
+    /// A counter used to generate unique node ids for the nodes the flattener creates.
+    pub(crate) node_builder: &'a NodeBuilder,
+    /// The types of the nodes in the program, which are used to look up the structs that expressions evaluate to.
+    /// The types of the variables introduced by the flattener are added to it.
+    pub(crate) type_table: &'a mut TypeTable,
+    /// A mapping between variables that are arrays and the tuples that their elements are lowered to.
+    pub(crate) arrays: IndexMap<Symbol, TupleExpression>,
+    /// A mapping between variables that are strings and the tuples of fields that they are lowered to.
+    pub(crate) strings: IndexMap<Symbol, TupleExpression>,
+    /// A stack of guards for the conditional branches visited up to the current point in the AST.
+    /// Each guard is a variable holding the conjunction of all conditions on the path to its branch,
+    /// so the top of the stack alone predicates the statements in the current branch.
     pub(crate) condition_stack: Vec<Expression>,
     /// A list containing tuples of guards and expressions associated `ReturnStatement`s.
     /// A guard is an expression that evaluates to true on the execution path of the `ReturnStatement`.
@@ -43,17 +54,35 @@ pub struct Flattener<'a> {
     /// Note that finalizes are inserted in the order they are encountered during a pre-order traversal of the AST.
     /// Note that type checking guarantees that there is at most one finalize in a basic block.
     pub(crate) finalizes: Vec<Vec<(Option<Expression>, Expression)>>,
+    /// The name of the function being flattened.
+    pub(crate) function: Option<Symbol>,
+    /// The strategy chosen to flatten each conditional statement, reported in the pass summary.
+    pub(crate) decisions: Vec<String>,
+    /// Whether to assert that the guards of the folded returns and finalizes cover every execution path.
+    pub(crate) sanitize: bool,
 }
 
 impl<'a> Flattener<'a> {
-    pub(crate) fn new(symbol_table: &'a SymbolTable, assigner: Assigner) -> Self {
+    pub(crate) fn new(
+        symbol_table: &'a SymbolTable,
+        assigner: Assigner,
+        node_builder: &'a NodeBuilder,
+        type_table: &'a mut TypeTable,
+        sanitize: bool,
+    ) -> Self {
         Self {
             symbol_table,
             assigner,
-            structs: IndexMap::new(),
+            node_builder,
+            type_table,
+            arrays: IndexMap::new(),
+            strings: IndexMap::new(),
             condition_stack: Vec::new(),
             returns: Vec::new(),
             finalizes: Vec::new(),
+            function: None,
+            decisions: Vec::new(),
+            sanitize,
         }
     }
 
@@ -67,6 +96,67 @@ impl<'a> Flattener<'a> {
         core::mem::take(&mut self.finalizes)
     }
 
+    /// Returns the guard for the current point in the AST, if any.
+    pub(crate) fn construct_guard(&self) -> Option<Expression> {
+        self.condition_stack.last().cloned()
+    }
+
+    /// Pushes a guard for a branch taken when `condition` holds, conjoined with the enclosing guard.
+    /// Unless the guard is already a simple operand, it is assigned to a new variable and the assignment is returned.
+    pub(crate) fn push_guard(&mut self, condition: Expression) -> Vec<Statement> {
+        let guard = match self.construct_guard() {
+            // Note that SSA guarantees that the condition of a conditional statement is an identifier or a literal.
+            None if matches!(condition, Expression::Identifier(_) | Expression::Literal(_)) => {
+                self.condition_stack.push(condition);
+                return Vec::new();
+            }
+            None => condition,
+            Some(parent) => Expression::Binary(BinaryExpression {
+                op: BinaryOperation::And,
+                left: Box::new(parent),
+                right: Box::new(condition),
+                span: Default::default(),
+                id: self.node_builder.next_id(),
+            }),
+        };
+        let (identifier, statement) = self.unique_simple_assign_statement(guard);
+        self.condition_stack.push(Expression::Identifier(identifier));
+        vec![statement]
+    }
+
+    /// If sanitizing, returns statements asserting that at least one of the guards holds.
+    /// Folding drops the guard of the last expression, which is only sound if some guard holds on every execution path.
+    pub(crate) fn assert_some_guard_holds(&mut self, guards: &[(Option<Expression>, Expression)]) -> Vec<Statement> {
+        // An expression without a guard is reached on every execution path.
+        let guards: Option<Vec<_>> = guards.iter().map(|(guard, _)| guard.clone()).collect();
+        let guards = match guards {
+            Some(guards) if self.sanitize && !guards.is_empty() => guards,
+            _ => return Vec::new(),
+        };
+
+        let mut statements = Vec::with_capacity(guards.len());
+        let mut guards = guards.into_iter();
+        let first = guards.next().unwrap();
+        let condition = guards.fold(first, |acc, guard| {
+            let (place, statement) = self.unique_simple_assign_statement(Expression::Binary(BinaryExpression {
+                op: BinaryOperation::Or,
+                left: Box::new(acc),
+                right: Box::new(guard),
+                span: Default::default(),
+                id: self.node_builder.next_id(),
+            }));
+            statements.push(statement);
+            Expression::Identifier(place)
+        });
+        statements.push(Statement::Assert(AssertStatement {
+            variant: AssertVariant::Assert(condition),
+            span: Default::default(),
+            id: self.node_builder.next_id(),
+        }));
+
+        statements
+    }
+
     /// Fold guards and expressions into a single expression.
     /// Note that this function assumes that at least one guard is present.
     pub(crate) fn fold_guards(
@@ -82,15 +172,19 @@ impl<'a> Flattener<'a> {
 
         // Helper to construct and store ternary assignments. e.g `$ret$0 = $var$0 ? $var$1 : $var$2`
         let mut construct_ternary_assignment = |guard: Expression, if_true: Expression, if_false: Expression| {
+            // The assignment is attributed to the guarded expression that it selects.
+            let span = if_true.span().synthesized();
             let place = Identifier {
                 name: self.assigner.unique_symbol(prefix),
-                span: Default::default(),
+                span,
+                id: self.node_builder.next_id(),
             };
             let (value, stmts) = self.reconstruct_ternary(TernaryExpression {
                 condition: Box::new(guard),
                 if_true: Box::new(if_true),
                 if_false: Box::new(if_false),
-                span: Default::default(),
+                span,
+                id: self.node_builder.next_id(),
             });
             statements.extend(stmts);
 
@@ -100,7 +194,7 @@ impl<'a> Flattener<'a> {
                 Expression::Tuple(_) => value,
                 // Otherwise, assign the expression to a variable and return the variable.
                 _ => {
-                    statements.push(self.simple_assign_statement(place, value));
+                    statements.push(self.simple_assign_statement(place, value, span));
                     Expression::Identifier(place)
                 }
             }
@@ -118,60 +212,207 @@ impl<'a> Flattener<'a> {
         (expression, statements)
     }
 
+    /// Returns the type of an expression, if it is known.
+    /// The types of the member accesses and struct expressions that the flattener creates are derived from their operands.
+    pub(crate) fn type_of(&self, expression: &Expression) -> Option<Type> {
+        if let Some(type_) = self.type_table.get(expression) {
+            return Some(type_.clone());
+        }
+        match expression {
+            Expression::Access(AccessExpression::Member(access)) => self.member_type(access),
+            Expression::Struct(struct_) => Some(Type::Identifier(struct_.name)),
+            Expression::Ternary(ternary) => self.type_of(&ternary.if_true),
+            _ => None,
+        }
+    }
+
+    /// Returns the type of a member access, if it is known.
+    pub(crate) fn member_type(&self, access: &MemberAccess) -> Option<Type> {
+        if let Some(type_) = self.type_table.get(access) {
+            return Some(type_.clone());
+        }
+        // The inner expression of an access expression is either an identifier or another access expression.
+        let name = self.lookup_struct_symbol(&access.inner)?;
+        let struct_ = self.symbol_table.lookup_struct(name)?;
+        // Note that the elements of a lowered array member are not found, since they are never structs.
+        struct_
+            .members
+            .iter()
+            .find(|member| member.name() == access.name.name)
+            .map(|Member { type_, .. }| type_.clone())
+    }
+
+    /// Returns the name of the struct that values of `type_` are instances of, if it is a struct type.
+    fn struct_symbol(&self, type_: Type) -> Option<Symbol> {
+        match type_ {
+            // Note that the type parameters of generic functions are also identifiers, but never name structs.
+            Type::Identifier(identifier) if self.symbol_table.lookup_struct(identifier.name).is_some() => {
+                Some(identifier.name)
+            }
+            _ => None,
+        }
+    }
+
     /// Looks up the name of the struct associated with an identifier or access expression, if it exists.
     pub(crate) fn lookup_struct_symbol(&self, expression: &Expression) -> Option<Symbol> {
-        match expression {
-            Expression::Identifier(identifier) => self.structs.get(&identifier.name).copied(),
-            Expression::Access(AccessExpression::Member(access)) => {
-                // The inner expression of an access expression is either an identifier or another access expression.
-                let name = self.lookup_struct_symbol(&access.inner).unwrap();
-                let struct_ = self.symbol_table.lookup_struct(name).unwrap();
-                let Member { type_, .. } = struct_
-                    .members
-                    .iter()
-                    .find(|member| member.name() == access.name.name)
-                    .unwrap();
-                match type_ {
-                    Type::Identifier(identifier) => Some(identifier.name),
-                    _ => None,
-                }
+        self.type_of(expression).and_then(|type_| self.struct_symbol(type_))
+    }
+
+    /// Lowers an access of an array member of a struct into a tuple of accesses of the members its elements are lowered to.
+    /// Returns `None` if the accessed member is not an array.
+    pub(crate) fn lower_array_member(&self, access: &MemberAccess) -> Option<TupleExpression> {
+        let name = self.lookup_struct_symbol(&access.inner)?;
+        let struct_ = self.symbol_table.lookup_struct(name)?;
+        let length = match struct_
+            .members
+            .iter()
+            .find(|member| member.name() == access.name.name)?
+            .type_
+        {
+            Type::Array(_, length) => length.get(),
+            _ => return None,
+        };
+        Some(TupleExpression {
+            elements: (0..length)
+                .map(|index| {
+                    Expression::Access(AccessExpression::Member(MemberAccess {
+                        inner: access.inner.clone(),
+                        name: array_member_element(access.name, index),
+                        span: access.span,
+                        id: self.node_builder.next_id(),
+                    }))
+                })
+                .collect(),
+            span: access.span,
+            id: access.id,
+        })
+    }
+
+    /// Looks up the element of a lowered array associated with an array access expression.
+    /// Note that loop unrolling resolves the index into an in-bounds integer literal.
+    pub(crate) fn lookup_array_element(&self, access: &ArrayAccess) -> Expression {
+        let tuple = match &*access.array {
+            Expression::Identifier(identifier) => self.arrays.get(&identifier.name).cloned(),
+            Expression::Access(AccessExpression::Member(member)) => self.lower_array_member(member),
+            _ => None,
+        }
+        .expect("SSA guarantees that arrays are assigned to variables before they are accessed.");
+        let index = match &*access.index {
+            Expression::Literal(Literal::Integer(_, value, _, _) | Literal::Unsuffixed(value, _, _, _)) => {
+                value.parse::<usize>().unwrap()
             }
+            _ => unreachable!("Loop unrolling resolves array indices into integer literals."),
+        };
+        tuple.elements[index].clone()
+    }
+
+    /// Returns the tuple of fields that a string literal or string variable is lowered to, if the expression is a string.
+    pub(crate) fn lower_string(&self, expression: &Expression) -> Option<TupleExpression> {
+        match expression {
+            Expression::Literal(literal) => literal.string_fields(self.node_builder).map(|fields| TupleExpression {
+                elements: fields.into_iter().map(Expression::Literal).collect(),
+                span: literal.span(),
+                id: self.node_builder.next_id(),
+            }),
+            Expression::Identifier(identifier) => self.strings.get(&identifier.name).cloned(),
             _ => None,
         }
     }
 
-    /// Updates `self.structs` for new assignment statements.
+    /// Lowers the comparison of two strings, with `==` or `!=`, into comparisons of the fields that they are lowered to.
+    /// Strings are equal if all of their fields are equal, and unequal if any of their fields are unequal.
+    /// Note that type checking guarantees that both strings have the same length, and so the same number of fields.
+    pub(crate) fn reconstruct_string_comparison(
+        &mut self,
+        left: TupleExpression,
+        right: TupleExpression,
+        op: BinaryOperation,
+    ) -> (Expression, Vec<Statement>) {
+        let combine = match op {
+            BinaryOperation::Eq => BinaryOperation::And,
+            BinaryOperation::Neq => BinaryOperation::Or,
+            _ => unreachable!("Type checking guarantees that strings are only compared with `==` or `!=`."),
+        };
+
+        let mut statements = Vec::new();
+        let mut comparisons = left.elements.into_iter().zip(right.elements).map(|(left, right)| {
+            Expression::Binary(BinaryExpression {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
+                span: Default::default(),
+                id: self.node_builder.next_id(),
+            })
+        });
+        // Note that every string is lowered to at least one field.
+        let first = comparisons.next().unwrap();
+        let expression = comparisons.fold(first, |acc, comparison| {
+            let (acc, statement) = self.unique_simple_assign_statement(acc);
+            statements.push(statement);
+            let (comparison, statement) = self.unique_simple_assign_statement(comparison);
+            statements.push(statement);
+            Expression::Binary(BinaryExpression {
+                op: combine,
+                left: Box::new(Expression::Identifier(acc)),
+                right: Box::new(Expression::Identifier(comparison)),
+                span: Default::default(),
+                id: self.node_builder.next_id(),
+            })
+        });
+
+        (expression, statements)
+    }
+
+    /// Records the type of the right hand side of a new assignment statement as the type of its left hand side.
     /// Expects the left hand side of the assignment to be an identifier.
-    pub(crate) fn update_structs(&mut self, lhs: &Identifier, rhs: &Expression) {
-        match rhs {
-            Expression::Struct(rhs) => {
-                self.structs.insert(lhs.name, rhs.name.name);
-            }
-            // If the rhs of the assignment is an identifier that is a struct, add it to `self.structs`.
-            Expression::Identifier(rhs) if self.structs.contains_key(&rhs.name) => {
-                // Note that this unwrap is safe because we just checked that the key exists.
-                self.structs.insert(lhs.name, *self.structs.get(&rhs.name).unwrap());
-            }
-            // Otherwise, do nothing.
-            _ => (),
+    pub(crate) fn record_type(&mut self, lhs: &Identifier, rhs: &Expression) {
+        if let Some(type_) = self.type_of(rhs) {
+            self.type_table.insert(lhs, type_);
         }
     }
 
-    /// A wrapper around `assigner.unique_simple_assign_statement` that updates `self.structs`.
+    /// A wrapper around `assigner.unique_simple_assign_statement` that records the type of the new variable.
     pub(crate) fn unique_simple_assign_statement(&mut self, expr: Expression) -> (Identifier, Statement) {
-        let (place, statement) = self.assigner.unique_simple_assign_statement(expr);
+        let (place, statement) = self.assigner.unique_simple_assign_statement(expr, self.node_builder);
         match &statement {
             Statement::Assign(assign) => {
-                self.update_structs(&place, &assign.value);
+                self.record_type(&place, &assign.value);
             }
             _ => unreachable!("`assigner.unique_simple_assign_statement` always returns an assignment statement."),
         }
         (place, statement)
     }
 
-    /// A wrapper around `assigner.simple_assign_statement` that updates `self.structs`.
-    pub(crate) fn simple_assign_statement(&mut self, lhs: Identifier, rhs: Expression) -> Statement {
-        self.update_structs(&lhs, &rhs);
-        self.assigner.simple_assign_statement(lhs, rhs)
+    /// A wrapper around `assigner.simple_assign_statement` that records the type of the assigned variable.
+    pub(crate) fn simple_assign_statement(&mut self, lhs: Identifier, rhs: Expression, span: Span) -> Statement {
+        self.record_type(&lhs, &rhs);
+        self.assigner
+            .simple_assign_statement(lhs, rhs, span, self.node_builder.next_id())
+    }
+}
+
+/// Returns the members of a struct, with each array member lowered into a member per element.
+/// For example, the member `data: [u8; 2]` is lowered into the members `data_0: u8` and `data_1: u8`.
+pub(crate) fn lower_struct_members(members: &[Member]) -> Vec<Member> {
+    members
+        .iter()
+        .flat_map(|member| match &member.type_ {
+            Type::Array(element_type, length) => (0..length.get())
+                .map(|index| Member {
+                    identifier: array_member_element(member.identifier, index),
+                    type_: (**element_type).clone(),
+                })
+                .collect(),
+            _ => vec![member.clone()],
+        })
+        .collect()
+}
+
+/// Returns the name of the member that the element of an array member at `index` is lowered to.
+pub(crate) fn array_member_element(member: Identifier, index: usize) -> Identifier {
+    Identifier {
+        name: Symbol::intern(&format!("{}_{index}", member.name)),
+        span: member.span,
+        id: member.id,
     }
 }
@@ -0,0 +1,398 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! A cost model that chooses how each conditional statement is flattened.
+//!
+//! By default, both branches of a conditional are evaluated, and the phi functions that follow the conditional select between their results.
+//! When both branches perform the same sequence of operations, each operation can instead be evaluated once, over operands selected by the guard.
+//! For example, the branches `$var$1 = a * b;` and `$var$2 = c * b;` are flattened into `$var$3 = $var$0 ? a : c; $var$1 = $var$3 * b; $var$2 = $var$1;`.
+//! Operands that are the same in both branches, including the results of earlier shared operations, do not need to be selected.
+//! Operand selection is only chosen when its estimated cost is lower than the cost of evaluating both branches.
+
+use crate::Flattener;
+
+use leo_ast::{
+    AccessExpression, AssignStatement, AssociatedFunction, BinaryExpression, BinaryOperation, Block,
+    ConditionalStatement, Expression, Identifier, MemberAccess, Statement, StatementReconstructor, TernaryExpression,
+    Type, UnaryExpression, UnaryOperation,
+};
+use leo_span::{sym, Symbol};
+
+use indexmap::IndexMap;
+
+/// The approximate cost of a ternary instruction, which selects between two operands.
+const SELECT_COST: usize = 1;
+
+/// A statement in a branch of a conditional statement, as seen by the cost model.
+enum Entry {
+    /// An assignment of an operand, which does not produce an instruction.
+    Copy(AssignStatement),
+    /// An assignment of an operation, which produces an instruction.
+    Operation(Identifier, Expression),
+}
+
+/// Splits the statements of a branch into copies and operations.
+/// Returns `None` if the branch contains any other statement, such as a nested conditional or a return.
+fn entries(block: &Block) -> Option<Vec<Entry>> {
+    block
+        .statements
+        .iter()
+        .map(|statement| match statement {
+            Statement::Assign(assign) => match (&assign.place, &assign.value) {
+                (_, value) if is_operand(value) => Some(Entry::Copy(*assign.clone())),
+                (Expression::Identifier(place), value) if operation_cost(value).is_some() => {
+                    Some(Entry::Operation(*place, value.clone()))
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns `true` if `expression` is a variable, a literal, or a member of an operand.
+fn is_operand(expression: &Expression) -> bool {
+    match expression {
+        Expression::Identifier(_) | Expression::Literal(_) => true,
+        Expression::Access(AccessExpression::Member(access)) => is_operand(&access.inner),
+        _ => false,
+    }
+}
+
+/// Returns the approximate cost of evaluating `expression`, or `None` if it is not an operation that can share its evaluation.
+fn operation_cost(expression: &Expression) -> Option<usize> {
+    match expression {
+        Expression::Binary(binary) => Some(match binary.op {
+            BinaryOperation::Pow | BinaryOperation::PowWrapped => 8,
+            BinaryOperation::Mul
+            | BinaryOperation::MulWrapped
+            | BinaryOperation::Div
+            | BinaryOperation::DivWrapped
+            | BinaryOperation::Rem
+            | BinaryOperation::RemWrapped
+            | BinaryOperation::Mod => 4,
+            _ => 1,
+        }),
+        Expression::Unary(unary) => Some(match unary.op {
+            UnaryOperation::Inverse | UnaryOperation::Square | UnaryOperation::SquareRoot => 4,
+            _ => 1,
+        }),
+        // Mapping operations are excluded, since their first argument names a mapping rather than a value.
+        Expression::Access(AccessExpression::AssociatedFunction(function)) if !matches!(&function.ty, Type::Identifier(identifier) if identifier.name == sym::Mapping) => {
+            Some(16)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the operands of an operation.
+fn operands(expression: &Expression) -> Vec<&Expression> {
+    match expression {
+        Expression::Binary(binary) => vec![&binary.left, &binary.right],
+        Expression::Unary(unary) => vec![&unary.receiver],
+        Expression::Access(AccessExpression::AssociatedFunction(function)) => function.args.iter().collect(),
+        _ => unreachable!("`operands` is only called on operations."),
+    }
+}
+
+/// Returns a copy of an operation with its operands replaced.
+fn with_operands(expression: &Expression, mut operands: impl Iterator<Item = Expression>) -> Expression {
+    let mut next = || {
+        operands
+            .next()
+            .expect("An operand is given for each operand of the operation.")
+    };
+    match expression {
+        Expression::Binary(binary) => Expression::Binary(BinaryExpression {
+            left: Box::new(next()),
+            right: Box::new(next()),
+            op: binary.op,
+            span: binary.span,
+            id: binary.id,
+        }),
+        Expression::Unary(unary) => Expression::Unary(UnaryExpression {
+            receiver: Box::new(next()),
+            op: unary.op,
+            span: unary.span,
+            id: unary.id,
+        }),
+        Expression::Access(AccessExpression::AssociatedFunction(function)) => {
+            Expression::Access(AccessExpression::AssociatedFunction(AssociatedFunction {
+                ty: function.ty.clone(),
+                name: function.name,
+                args: function.args.iter().map(|_| next()).collect(),
+                span: function.span,
+                id: function.id,
+            }))
+        }
+        _ => unreachable!("`with_operands` is only called on operations."),
+    }
+}
+
+/// Returns `true` if `first` and `second` perform the same operation, possibly on different operands.
+fn is_same_operation(first: &Expression, second: &Expression) -> bool {
+    match (first, second) {
+        (Expression::Binary(first), Expression::Binary(second)) => first.op == second.op,
+        (Expression::Unary(first), Expression::Unary(second)) => first.op == second.op,
+        (
+            Expression::Access(AccessExpression::AssociatedFunction(first)),
+            Expression::Access(AccessExpression::AssociatedFunction(second)),
+        ) => first.ty == second.ty && first.name.name == second.name.name && first.args.len() == second.args.len(),
+        _ => false,
+    }
+}
+
+/// Returns `true` if `first` and `second` are the same operand.
+fn is_same_operand(first: &Expression, second: &Expression) -> bool {
+    match (first, second) {
+        (Expression::Identifier(first), Expression::Identifier(second)) => first.name == second.name,
+        (Expression::Literal(first), Expression::Literal(second)) => first.to_string() == second.to_string(),
+        (Expression::Access(AccessExpression::Member(first)), Expression::Access(AccessExpression::Member(second))) => {
+            first.name.name == second.name.name && is_same_operand(&first.inner, &second.inner)
+        }
+        _ => false,
+    }
+}
+
+/// Resolves the variables in an operand to the operands they were assigned, if any.
+fn resolve(aliases: &IndexMap<Symbol, Expression>, operand: &Expression) -> Expression {
+    match operand {
+        Expression::Identifier(identifier) => aliases
+            .get(&identifier.name)
+            .cloned()
+            .unwrap_or_else(|| operand.clone()),
+        Expression::Access(AccessExpression::Member(access)) => {
+            Expression::Access(AccessExpression::Member(MemberAccess {
+                inner: Box::new(resolve(aliases, &access.inner)),
+                name: access.name,
+                span: access.span,
+                id: access.id,
+            }))
+        }
+        _ => operand.clone(),
+    }
+}
+
+/// Records the copies in `entries` that precede the next operation, returning the operation, if any.
+fn advance<'a>(
+    aliases: &mut IndexMap<Symbol, Expression>,
+    entries: &mut impl Iterator<Item = &'a Entry>,
+) -> Option<(Identifier, &'a Expression)> {
+    for entry in entries {
+        match entry {
+            Entry::Copy(AssignStatement {
+                place: Expression::Identifier(place),
+                value,
+                ..
+            }) => {
+                let value = resolve(aliases, value);
+                aliases.insert(place.name, value);
+            }
+            Entry::Copy(_) => {}
+            Entry::Operation(place, value) => return Some((*place, value)),
+        }
+    }
+    None
+}
+
+/// An operation shared by both branches, with the operands it is evaluated over.
+struct SharedOperation {
+    /// The variable assigned the operation in the then-block, which holds the shared result.
+    then_place: Identifier,
+    /// The variable assigned the operation in the otherwise-block.
+    otherwise_place: Identifier,
+    /// The operation, as written in the then-block.
+    operation: Expression,
+    /// The operands of the operation in each branch, with copies resolved.
+    operands: Vec<(Expression, Expression)>,
+}
+
+/// A plan for flattening a conditional by selecting operands.
+struct OperandSelection {
+    /// The operations performed by both branches, in order.
+    operations: Vec<SharedOperation>,
+    /// The copies in the then-block and the otherwise-block, which are assigned after the shared operations.
+    copies: Vec<AssignStatement>,
+    /// The estimated cost of evaluating both branches.
+    flattening_cost: usize,
+    /// The estimated cost of evaluating each shared operation once, over selected operands.
+    selection_cost: usize,
+}
+
+impl OperandSelection {
+    /// Plans the selection of operands for `then` and `otherwise`.
+    /// Returns `None` if the branches do not perform the same sequence of operations.
+    fn new(then: &Block, otherwise: &Block) -> Option<Self> {
+        let (then, otherwise) = (entries(then)?, entries(otherwise)?);
+        let (mut then_entries, mut otherwise_entries) = (then.iter(), otherwise.iter());
+
+        let mut aliases = IndexMap::new();
+        let mut operations = Vec::new();
+        let mut flattening_cost = 0;
+        let mut selection_cost = 0;
+        loop {
+            match (
+                advance(&mut aliases, &mut then_entries),
+                advance(&mut aliases, &mut otherwise_entries),
+            ) {
+                (None, None) => break,
+                (Some((then_place, first)), Some((otherwise_place, second))) if is_same_operation(first, second) => {
+                    let operands = operands(first)
+                        .into_iter()
+                        .zip(operands(second))
+                        .map(|(first, second)| (resolve(&aliases, first), resolve(&aliases, second)))
+                        .collect::<Vec<_>>();
+
+                    // Note that `operation_cost` is defined for every operation in a branch.
+                    let cost = operation_cost(first).unwrap();
+                    flattening_cost += 2 * cost;
+                    selection_cost += cost
+                        + SELECT_COST
+                            * operands
+                                .iter()
+                                .filter(|(first, second)| !is_same_operand(first, second))
+                                .count();
+
+                    // Both variables hold the shared result.
+                    aliases.insert(then_place.name, Expression::Identifier(then_place));
+                    aliases.insert(otherwise_place.name, Expression::Identifier(then_place));
+                    operations.push(SharedOperation {
+                        then_place,
+                        otherwise_place,
+                        operation: first.clone(),
+                        operands,
+                    });
+                }
+                _ => return None,
+            }
+        }
+
+        let copies = then
+            .into_iter()
+            .chain(otherwise)
+            .filter_map(|entry| match entry {
+                Entry::Copy(assign) => Some(assign),
+                Entry::Operation(..) => None,
+            })
+            .collect();
+
+        Some(Self {
+            operations,
+            copies,
+            flattening_cost,
+            selection_cost,
+        })
+    }
+}
+
+impl Flattener<'_> {
+    /// Flattens `conditional` by evaluating each operation of its branches once, over operands selected by its guard.
+    /// Returns `None` if the branches do not perform the same operations, or if evaluating both branches is estimated to be cheaper.
+    /// The decision is recorded in `self.decisions`.
+    pub(crate) fn select_operands(&mut self, conditional: &ConditionalStatement) -> Option<Vec<Statement>> {
+        let plan = match &conditional.otherwise {
+            Some(otherwise) => match &**otherwise {
+                Statement::Block(otherwise) => OperandSelection::new(&conditional.then, otherwise),
+                _ => None,
+            },
+            None => None,
+        };
+
+        let function = self
+            .function
+            .map_or_else(String::new, |function| format!("`{function}`: "));
+        let plan = match plan {
+            Some(plan) if plan.selection_cost < plan.flattening_cost => {
+                self.decisions.push(format!(
+                    "{function}selected the operands of the conditional on `{}` (cost {}, instead of {})",
+                    conditional.condition, plan.selection_cost, plan.flattening_cost
+                ));
+                plan
+            }
+            Some(plan) => {
+                self.decisions.push(format!(
+                    "{function}flattened both branches of the conditional on `{}` (cost {}, instead of {})",
+                    conditional.condition, plan.flattening_cost, plan.selection_cost
+                ));
+                return None;
+            }
+            None => {
+                self.decisions.push(format!(
+                    "{function}flattened both branches of the conditional on `{}`, since they perform different operations",
+                    conditional.condition
+                ));
+                return None;
+            }
+        };
+
+        // Note that the results of the branches are only used when the conditional is reached, so its condition alone selects the operands.
+        let mut statements = Vec::new();
+        for operation in plan.operations {
+            let operands = operation
+                .operands
+                .into_iter()
+                .map(|(first, second)| match is_same_operand(&first, &second) {
+                    true => first,
+                    false => {
+                        let place = Identifier {
+                            name: self.assigner.unique_symbol("$var"),
+                            span: Default::default(),
+                            id: self.node_builder.next_id(),
+                        };
+                        let (statement, stmts) = self.reconstruct_assign(AssignStatement {
+                            place: Expression::Identifier(place),
+                            value: Expression::Ternary(TernaryExpression {
+                                condition: Box::new(conditional.condition.clone()),
+                                if_true: Box::new(first),
+                                if_false: Box::new(second),
+                                span: Default::default(),
+                                id: self.node_builder.next_id(),
+                            }),
+                            span: Default::default(),
+                            id: self.node_builder.next_id(),
+                        });
+                        statements.extend(stmts);
+                        statements.push(statement);
+                        Expression::Identifier(place)
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let value = with_operands(&operation.operation, operands.into_iter());
+            for (place, value) in [
+                (operation.then_place, value),
+                (operation.otherwise_place, Expression::Identifier(operation.then_place)),
+            ] {
+                let (statement, stmts) = self.reconstruct_assign(AssignStatement {
+                    place: Expression::Identifier(place),
+                    value,
+                    span: Default::default(),
+                    id: self.node_builder.next_id(),
+                });
+                statements.extend(stmts);
+                statements.push(statement);
+            }
+        }
+
+        for copy in plan.copies {
+            let (statement, stmts) = self.reconstruct_assign(copy);
+            statements.extend(stmts);
+            statements.push(statement);
+        }
+
+        Some(statements)
+    }
+}
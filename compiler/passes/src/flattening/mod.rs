@@ -16,8 +16,13 @@
 
 //! The flattening pass traverses the AST after the SSA pass and converts into a sequential code.
 //! The pass flattens `ConditionalStatement`s into a sequence of `AssignStatement`s.
+//! Each branch is predicated on a guard, the conjunction of the conditions on its path, so `else if` chains are flattened correctly.
 //! The pass rewrites `ReturnStatement`s into `AssignStatement`s and consolidates the returned values as a single `ReturnStatement` at the end of the function.
 //! The pass rewrites ternary expressions over composite data types, into ternary expressions over the individual fields of the composite data type, followed by an expression constructing the composite data type.
+//! The pass lowers arrays into tuples of known length, replacing array accesses with the accessed elements, and lowers each array member of a struct into a member per element.
+//! A cost model chooses how each conditional is flattened; if both branches perform the same operations, they may instead be evaluated once, over operands selected by the condition.
+//! Folding the returns selects the last return whenever the guards of the others do not hold.
+//! If sanitizing, the pass asserts that the guard of some return holds, so that a path that reaches no return is caught at runtime.
 //!
 //! Consider the following Leo code, output by the SSA pass.
 //! ```leo
@@ -42,11 +47,12 @@
 //!     $var$0 = flag == 0u8;
 //!     $var$1 = value + 1u8;
 //!     value$1 = $var$1;
+//!     $var$4 = !$var$0;
 //!     $var$2 = value + 2u8;
 //!     value$2 = $var$2;
 //!     value$3 = $var$0 ? value$1 : value$2;
-//!     ret$4 = $var$0 ? value$1 : value$3;
-//!     return ret$4;
+//!     ret$5 = $var$0 ? value$1 : value$3;
+//!     return ret$5;
 //! }
 //! ```
 
@@ -56,22 +62,29 @@ mod flatten_program;
 
 mod flatten_statement;
 
+mod if_conversion;
+
 pub mod flattener;
 pub use flattener::*;
 
-use crate::{Assigner, Pass, SymbolTable};
+use crate::{Assigner, Pass, PassSummary, SymbolTable, TypeTable};
 
-use leo_ast::{Ast, ProgramReconstructor};
+use leo_ast::{Ast, NodeBuilder, ProgramReconstructor};
 use leo_errors::Result;
 
 impl<'a> Pass for Flattener<'a> {
-    type Input = (Ast, &'a SymbolTable, Assigner);
-    type Output = Result<Ast>;
+    type Input = (Ast, &'a SymbolTable, Assigner, &'a NodeBuilder, &'a mut TypeTable, bool);
+    type Output = Result<(Ast, Assigner, PassSummary)>;
 
-    fn do_pass((ast, st, assigner): Self::Input) -> Self::Output {
-        let mut reconstructor = Flattener::new(st, assigner);
+    fn do_pass((ast, st, assigner, node_builder, type_table, sanitize): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("flattening", None);
+        let mut reconstructor = Flattener::new(st, assigner, node_builder, type_table, sanitize);
         let program = reconstructor.reconstruct_program(ast.into_repr());
 
-        Ok(Ast::new(program))
+        let ast = Ast::new(program);
+        let mut summary = recorder.finish(None);
+        summary.decisions = reconstructor.decisions;
+
+        Ok((ast, reconstructor.assigner, summary))
     }
 }
@@ -0,0 +1,115 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Assigner, RenameTable};
+
+use leo_ast::{
+    AssignStatement, Expression, ExpressionReconstructor, Identifier, Statement, StatementReconstructor,
+    StructExpression, StructVariableInitializer,
+};
+
+/// Renames the variables of a function body that is being inlined.
+/// Every assigned variable is given a fresh name, and every use of a variable is replaced with its new name.
+/// Variables that are not in the `RenameTable`, such as the names of mappings, are left unchanged.
+pub(crate) struct AssignmentRenamer<'a> {
+    /// The struct used to produce fresh names.
+    pub(crate) assigner: &'a mut Assigner,
+    /// The mapping from the names in the inlined function to their new names.
+    pub(crate) rename_table: RenameTable,
+    /// A flag to determine whether or not the traversal is on the left-hand side of an assignment.
+    pub(crate) is_lhs: bool,
+}
+
+impl<'a> AssignmentRenamer<'a> {
+    /// Initializes a new `AssignmentRenamer` with an empty `RenameTable`.
+    pub(crate) fn new(assigner: &'a mut Assigner) -> Self {
+        Self {
+            assigner,
+            rename_table: RenameTable::default(),
+            is_lhs: false,
+        }
+    }
+}
+
+impl ExpressionReconstructor for AssignmentRenamer<'_> {
+    type AdditionalOutput = ();
+
+    /// Renames an identifier, introducing a new name if it is on the left-hand side of an assignment.
+    fn reconstruct_identifier(&mut self, input: Identifier) -> (Expression, Self::AdditionalOutput) {
+        let name = match self.is_lhs {
+            true => {
+                let new_name = self.assigner.unique_symbol(input.name);
+                self.rename_table.update(input.name, new_name);
+                new_name
+            }
+            false => *self.rename_table.lookup(input.name).unwrap_or(&input.name),
+        };
+
+        (
+            Expression::Identifier(Identifier {
+                name,
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    /// Renames the variables used to initialize the members of a struct.
+    fn reconstruct_struct_init(&mut self, input: StructExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Struct(StructExpression {
+                name: input.name,
+                const_arguments: input.const_arguments,
+                members: input
+                    .members
+                    .into_iter()
+                    .map(|member| StructVariableInitializer {
+                        identifier: member.identifier,
+                        expression: Some(match member.expression {
+                            Some(expression) => self.reconstruct_expression(expression).0,
+                            None => self.reconstruct_identifier(member.identifier).0,
+                        }),
+                    })
+                    .collect(),
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+}
+
+impl StatementReconstructor for AssignmentRenamer<'_> {
+    /// Renames the variables used on the right-hand side of an assignment, and then the assigned variable.
+    fn reconstruct_assign(&mut self, input: AssignStatement) -> (Statement, Self::AdditionalOutput) {
+        let value = self.reconstruct_expression(input.value).0;
+
+        self.is_lhs = true;
+        let place = self.reconstruct_expression(input.place).0;
+        self.is_lhs = false;
+
+        (
+            Statement::Assign(Box::new(AssignStatement {
+                place,
+                value,
+                span: input.span,
+                id: input.id,
+            })),
+            Default::default(),
+        )
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Assigner, CallGraph};
+
+use leo_ast::{AssignStatement, Block, CallType, Expression, Function, NodeBuilder, Statement};
+use leo_span::{sym, Symbol};
+
+use indexmap::{IndexMap, IndexSet};
+
+pub struct FunctionInliner<'a> {
+    /// The call graph of the program, used to inline callees before their callers.
+    pub(crate) call_graph: &'a CallGraph,
+    /// A struct used to construct (unique) assignment statements.
+    pub(crate) assigner: Assigner,
+    /// A counter used to generate unique ids for the nodes that the pass creates.
+    pub(crate) node_builder: &'a NodeBuilder,
+    /// The number of statements a standard function may add to the program when it is inlined, if any.
+    pub(crate) budget: Option<usize>,
+    /// The number of call sites of each function in the program.
+    pub(crate) call_counts: IndexMap<Symbol, usize>,
+    /// The functions that have already been reconstructed, whose calls have been inlined.
+    pub(crate) reconstructed_functions: IndexMap<Symbol, Function>,
+    /// The functions whose calls are inlined, and which are removed from the program.
+    pub(crate) inlined: IndexSet<Symbol>,
+}
+
+impl<'a> FunctionInliner<'a> {
+    /// Initializes a new `FunctionInliner`.
+    pub(crate) fn new(
+        call_graph: &'a CallGraph,
+        assigner: Assigner,
+        node_builder: &'a NodeBuilder,
+        budget: Option<usize>,
+    ) -> Self {
+        Self {
+            call_graph,
+            assigner,
+            node_builder,
+            budget,
+            call_counts: IndexMap::new(),
+            reconstructed_functions: IndexMap::new(),
+            inlined: IndexSet::new(),
+        }
+    }
+
+    /// Returns `true` if the calls to the given reconstructed function should be inlined.
+    /// `inline` functions are always inlined, and transitions never are.
+    /// A standard function is inlined if it is annotated with `@inline(always)`,
+    /// or if it is not annotated with `@inline(never)` and inlining each of its calls adds at most `budget` statements.
+    pub(crate) fn should_inline(&self, function: &Function) -> bool {
+        match function.call_type {
+            CallType::Inline => return true,
+            CallType::Transition => return false,
+            CallType::Standard => {}
+        }
+
+        let annotation = function
+            .annotations
+            .iter()
+            .find(|annotation| annotation.identifier.name == sym::inline)
+            .and_then(|annotation| annotation.arguments.first());
+        match annotation.map(|argument| argument.identifier.name) {
+            Some(sym::always) => true,
+            Some(_) => false,
+            None => match (self.budget, self.call_counts.get(&function.identifier.name)) {
+                // Note that the function body is still emitted once, so the first call is free.
+                (Some(budget), Some(calls)) => statement_count(&function.block) * (calls - 1) <= budget,
+                _ => false,
+            },
+        }
+    }
+}
+
+/// Returns the number of statements in a flattened block, excluding the return statement and empty blocks.
+pub(crate) fn statement_count(block: &Block) -> usize {
+    block
+        .statements
+        .iter()
+        .map(|statement| match statement {
+            Statement::Block(block) => statement_count(block),
+            Statement::Return(_) => 0,
+            _ => 1,
+        })
+        .sum()
+}
+
+/// Counts the calls to local functions in a block, adding them to `call_counts`.
+/// Note that the SSA pass guarantees that every call is the right-hand side of an assignment.
+pub(crate) fn count_calls(block: &Block, call_counts: &mut IndexMap<Symbol, usize>) {
+    for statement in block.statements.iter() {
+        match statement {
+            Statement::Block(block) => count_calls(block, call_counts),
+            Statement::Assign(assign) => {
+                if let AssignStatement {
+                    value: Expression::Call(call),
+                    ..
+                } = &**assign
+                {
+                    if let (None, Expression::Identifier(identifier)) = (&call.external, &*call.function) {
+                        *call_counts.entry(identifier.name).or_insert(0) += 1;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{count_calls, FunctionInliner};
+
+use leo_ast::{ProgramReconstructor, ProgramScope};
+
+impl ProgramReconstructor for FunctionInliner<'_> {
+    /// Inlines the calls in each function, visiting callees before their callers.
+    /// Inlined functions are removed from the program once they have been copied to their call sites.
+    fn reconstruct_program_scope(&mut self, input: ProgramScope) -> ProgramScope {
+        let mut functions = input.functions;
+
+        // Count the call sites of each function, which determine the cost of inlining it.
+        functions
+            .values()
+            .for_each(|function| count_calls(&function.block, &mut self.call_counts));
+
+        // Note that type checking guarantees that the call graph is acyclic.
+        let order = self
+            .call_graph
+            .post_order()
+            .unwrap_or_else(|_| unreachable!("Type checking guarantees that the call graph is acyclic."));
+
+        for name in order {
+            if let Some((_, function)) = functions.iter().find(|(identifier, _)| identifier.name == name) {
+                let function = self.reconstruct_function(function.clone());
+                if self.should_inline(&function) {
+                    self.inlined.insert(name);
+                }
+                self.reconstructed_functions.insert(name, function);
+            }
+        }
+
+        // Replace each function with its reconstruction, preserving the order of the program.
+        functions.iter_mut().for_each(|(identifier, function)| {
+            if let Some(reconstructed) = self.reconstructed_functions.remove(&identifier.name) {
+                *function = reconstructed;
+            }
+        });
+        functions.retain(|identifier, _| !self.inlined.contains(&identifier.name));
+
+        ProgramScope { functions, ..input }
+    }
+}
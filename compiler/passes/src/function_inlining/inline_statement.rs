@@ -0,0 +1,149 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{AssignmentRenamer, FunctionInliner};
+
+use leo_ast::{AssignStatement, Block, Expression, ReturnStatement, Statement, StatementReconstructor};
+
+use itertools::Itertools;
+
+impl StatementReconstructor for FunctionInliner<'_> {
+    /// Inlines the call on the right-hand side of an assignment, if the callee is inlined.
+    /// Note that the SSA pass guarantees that every call is the right-hand side of an assignment, and that its arguments are identifiers or literals.
+    fn reconstruct_assign(&mut self, input: AssignStatement) -> (Statement, Self::AdditionalOutput) {
+        let call = match input.value {
+            Expression::Call(call) if call.external.is_none() => call,
+            value => {
+                return (
+                    Statement::Assign(Box::new(AssignStatement {
+                        place: input.place,
+                        value,
+                        span: input.span,
+                        id: input.id,
+                    })),
+                    Default::default(),
+                )
+            }
+        };
+
+        // Note that the parser guarantees that `call.function` is always an identifier.
+        let callee = match &*call.function {
+            Expression::Identifier(identifier) => self
+                .reconstructed_functions
+                .get(&identifier.name)
+                .filter(|_| self.inlined.contains(&identifier.name)),
+            _ => unreachable!("Parser guarantees that `input.function` is always an identifier."),
+        };
+        let callee = match callee {
+            Some(callee) => callee,
+            None => {
+                return (
+                    Statement::Assign(Box::new(AssignStatement {
+                        place: input.place,
+                        value: Expression::Call(call),
+                        span: input.span,
+                        id: input.id,
+                    })),
+                    Default::default(),
+                )
+            }
+        };
+
+        let mut statements = Vec::new();
+        let mut renamer = AssignmentRenamer::new(&mut self.assigner);
+
+        // Rename each parameter of the callee to its argument, assigning literal arguments to fresh variables.
+        for (parameter, argument) in callee.input.iter().zip_eq(call.arguments) {
+            let name = match argument {
+                Expression::Identifier(identifier) => identifier.name,
+                argument => {
+                    let (place, statement) = renamer
+                        .assigner
+                        .unique_simple_assign_statement(argument, self.node_builder);
+                    statements.push(statement);
+                    place.name
+                }
+            };
+            renamer.rename_table.update(parameter.identifier().name, name);
+        }
+
+        // Copy the body of the callee, assigning its return value to the variable that held the result of the call.
+        // If the outputs of the call are assigned to multiple variables, each returned element is assigned to its variable.
+        // Note that the flattening pass guarantees that the only return statement is the last statement of the body.
+        let mut result = Statement::dummy(Default::default(), self.node_builder.next_id());
+        for statement in callee.block.statements.iter().cloned() {
+            match (renamer.reconstruct_statement(statement).0, &input.place) {
+                (
+                    Statement::Return(ReturnStatement {
+                        expression: Expression::Tuple(tuple),
+                        ..
+                    }),
+                    Expression::Tuple(place),
+                ) => {
+                    for (place, value) in place.elements.iter().zip_eq(tuple.elements) {
+                        let place = match place {
+                            Expression::Identifier(identifier) => *identifier,
+                            _ => unreachable!(
+                                "The tuple lowering pass guarantees that calls are destructured into variables."
+                            ),
+                        };
+                        statements.push(renamer.assigner.simple_assign_statement(
+                            place,
+                            value,
+                            input.span,
+                            self.node_builder.next_id(),
+                        ));
+                    }
+                    // Note that the statement that assigned the last output is returned in place of the call.
+                    if let Some(statement) = statements.pop() {
+                        result = statement;
+                    }
+                }
+                (Statement::Return(ReturnStatement { expression, .. }), _) => {
+                    result = Statement::Assign(Box::new(AssignStatement {
+                        place: input.place.clone(),
+                        value: expression,
+                        span: input.span,
+                        id: input.id,
+                    }))
+                }
+                (statement, _) => statements.push(statement),
+            }
+        }
+
+        (result, statements)
+    }
+
+    /// Reconstructs the statements inside a basic block, accumulating any statements produced by inlining.
+    fn reconstruct_block(&mut self, block: Block) -> (Block, Self::AdditionalOutput) {
+        let mut statements = Vec::with_capacity(block.statements.len());
+
+        for statement in block.statements {
+            let (reconstructed_statement, additional_statements) = self.reconstruct_statement(statement);
+            statements.extend(additional_statements);
+            statements.push(reconstructed_statement);
+        }
+
+        (
+            Block {
+                span: block.span,
+                statements,
+                id: block.id,
+            },
+            Default::default(),
+        )
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The function inlining pass traverses the AST after the flattening pass and copies the bodies of inlined functions to their call sites.
+//! Functions are visited in post-order of the call graph, so the body of a callee has already had its own calls inlined when it is copied.
+//! The parameters of the callee are renamed to its arguments, and every variable it assigns is given a fresh name, so that a function can be inlined more than once in the same scope.
+//! The value returned by the callee is assigned to the variable that held the result of the call.
+//! Once all calls have been inlined, the inlined functions are removed from the program.
+//!
+//! `inline` functions are always inlined, and transitions never are.
+//! Standard functions are inlined when annotated with `@inline(always)`.
+//! Otherwise, if an inlining budget is configured, a standard function that is not annotated with `@inline(never)` is inlined
+//! when the number of statements in its body, times the number of its call sites beyond the first, is at most the budget.
+//!
+//! Consider the following flattened Leo code.
+//! ```leo
+//! inline double(x: u8) -> u8 {
+//!     $var$0 = x + x;
+//!     return $var$0;
+//! }
+//!
+//! transition main(a: u8) -> u8 {
+//!     $var$1 = double(a);
+//!     $var$2 = double($var$1);
+//!     return $var$2;
+//! }
+//! ```
+//!
+//! The function inlining pass produces the following code.
+//! ```leo
+//! transition main(a: u8) -> u8 {
+//!     $var$0$3 = a + a;
+//!     $var$1 = $var$0$3;
+//!     $var$0$4 = $var$1 + $var$1;
+//!     $var$2 = $var$0$4;
+//!     return $var$2;
+//! }
+//! ```
+
+mod assignment_renamer;
+pub(crate) use assignment_renamer::*;
+
+mod inline_expression;
+
+mod inline_program;
+
+mod inline_statement;
+
+pub mod function_inliner;
+pub use function_inliner::*;
+
+use crate::{Assigner, CallGraph, Pass, PassSummary};
+
+use leo_ast::{Ast, NodeBuilder, ProgramReconstructor};
+use leo_errors::Result;
+
+impl<'a> Pass for FunctionInliner<'a> {
+    type Input = (Ast, &'a CallGraph, Assigner, &'a NodeBuilder, Option<usize>);
+    type Output = Result<(Ast, Assigner, PassSummary)>;
+
+    fn do_pass((ast, call_graph, assigner, node_builder, budget): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("function inlining", None);
+        let mut reconstructor = FunctionInliner::new(call_graph, assigner, node_builder, budget);
+        let program = reconstructor.reconstruct_program(ast.into_repr());
+
+        let ast = Ast::new(program);
+        let summary = recorder.finish(None);
+
+        Ok((ast, reconstructor.assigner, summary))
+    }
+}
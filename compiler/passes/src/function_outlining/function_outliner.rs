@@ -0,0 +1,138 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Assigner;
+
+use leo_ast::{AccessExpression, BinaryOperation, Expression, Literal, NodeBuilder, Struct, Tuple, Type};
+use leo_span::Symbol;
+
+use indexmap::IndexMap;
+
+/// The minimum number of statements in a sequence that may be outlined.
+pub(crate) const MIN_SEQUENCE_LENGTH: usize = 3;
+
+/// The maximum number of statements in a sequence that may be outlined.
+/// Longer repeated sequences are outlined in several pieces, which bounds the time spent comparing sequences.
+pub(crate) const MAX_SEQUENCE_LENGTH: usize = 64;
+
+pub struct FunctionOutliner<'a> {
+    /// A struct used to construct (unique) assignment statements.
+    pub(crate) assigner: Assigner,
+    /// The structs declared in the program, used to determine the types of member accesses.
+    pub(crate) structs: IndexMap<Symbol, Struct>,
+    /// The output types of the functions in the program, used to determine the types of calls.
+    pub(crate) function_types: IndexMap<Symbol, Type>,
+    /// A description of each sequence that was outlined.
+    pub(crate) decisions: Vec<String>,
+    /// A counter used to generate unique ids for the nodes that the pass creates.
+    pub(crate) node_builder: &'a NodeBuilder,
+}
+
+impl<'a> FunctionOutliner<'a> {
+    /// Initializes a new `FunctionOutliner`.
+    pub(crate) fn new(assigner: Assigner, node_builder: &'a NodeBuilder) -> Self {
+        Self {
+            assigner,
+            structs: IndexMap::new(),
+            function_types: IndexMap::new(),
+            decisions: Vec::new(),
+            node_builder,
+        }
+    }
+
+    /// Returns `true` if a value of type `type_` can be passed to or returned from a helper.
+    /// Note that closures cannot take or return records.
+    pub(crate) fn is_helper_type(&self, type_: &Type) -> bool {
+        match type_ {
+            Type::Address
+            | Type::Boolean
+            | Type::Field
+            | Type::Group
+            | Type::Integer(_)
+            | Type::Scalar
+            | Type::Signature => true,
+            Type::Identifier(identifier) => self
+                .structs
+                .get(&identifier.name)
+                .map_or(false, |struct_| !struct_.is_record),
+            _ => false,
+        }
+    }
+
+    /// Returns the type of `expression`, given the types of the variables that are known, if it can be determined.
+    /// Note that the AST has been type checked, so the operands of an operation are assumed to have the appropriate types.
+    pub(crate) fn infer_type(&self, expression: &Expression, types: &IndexMap<Symbol, Type>) -> Option<Type> {
+        match expression {
+            Expression::Identifier(identifier) => types.get(&identifier.name).cloned(),
+            Expression::Literal(literal) => match literal {
+                Literal::Address(..) => Some(Type::Address),
+                Literal::Boolean(..) => Some(Type::Boolean),
+                Literal::Field(..) => Some(Type::Field),
+                Literal::Group(..) => Some(Type::Group),
+                Literal::Scalar(..) => Some(Type::Scalar),
+                Literal::Integer(..) | Literal::Unsuffixed(..) => literal.integer_type().map(Type::Integer),
+                Literal::String(..) => None,
+            },
+            Expression::Binary(binary) => match binary.op {
+                BinaryOperation::And
+                | BinaryOperation::Or
+                | BinaryOperation::Nand
+                | BinaryOperation::Nor
+                | BinaryOperation::Eq
+                | BinaryOperation::Neq
+                | BinaryOperation::Lt
+                | BinaryOperation::Lte
+                | BinaryOperation::Gt
+                | BinaryOperation::Gte => Some(Type::Boolean),
+                // The product of a group element and a scalar is a group element.
+                BinaryOperation::Mul => {
+                    let left = self.infer_type(&binary.left, types);
+                    let right = self.infer_type(&binary.right, types);
+                    match (left, right) {
+                        (Some(Type::Group), _) | (_, Some(Type::Group)) => Some(Type::Group),
+                        (left, _) => left,
+                    }
+                }
+                _ => self.infer_type(&binary.left, types),
+            },
+            Expression::Unary(unary) => self.infer_type(&unary.receiver, types),
+            Expression::Cast(cast) => Some(cast.type_.clone()),
+            Expression::Ternary(ternary) => self.infer_type(&ternary.if_true, types),
+            Expression::Struct(struct_) => Some(Type::Identifier(struct_.name)),
+            Expression::Access(AccessExpression::Member(access)) => match self.infer_type(&access.inner, types)? {
+                Type::Identifier(identifier) => self
+                    .structs
+                    .get(&identifier.name)?
+                    .members
+                    .iter()
+                    .find(|member| member.name() == access.name.name)
+                    .map(|member| member.type_.clone()),
+                _ => None,
+            },
+            Expression::Call(call) => match (&*call.function, &call.external) {
+                (Expression::Identifier(function), None) => self.function_types.get(&function.name).cloned(),
+                _ => None,
+            },
+            Expression::Tuple(tuple) => tuple
+                .elements
+                .iter()
+                .map(|element| self.infer_type(element, types))
+                .collect::<Option<Vec<_>>>()
+                .map(|elements| Type::Tuple(Tuple(elements))),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The function outlining pass traverses the AST after all other passes and replaces identical sequences of statements with calls to a shared helper function.
+//! Sequences are searched for in the bodies of transitions, which are the only functions that may call other functions.
+//! Two sequences are identical if they perform the same operations on inputs of the same types, up to the names of their variables.
+//! Each helper takes the variables used by the sequence as its parameters, and returns the variables defined by the sequence that are used after it.
+//! A sequence is only outlined if doing so reduces the number of instructions in the program, counting the helper's input and output declarations and one instruction per call.
+//! The pass is optional, since every call to a helper adds overhead, but it is useful for programs that exceed the size limits of snarkVM.
+//!
+//! Consider the following flattened Leo code.
+//! ```leo
+//! transition foo(a: u8) -> u8 {
+//!     $var$0 = a * a;
+//!     $var$1 = $var$0 * a;
+//!     $var$2 = $var$1 + a;
+//!     $var$3 = $var$2 * $var$2;
+//!     $var$4 = $var$3 - a;
+//!     return $var$4;
+//! }
+//!
+//! transition bar(b: u8) -> u8 {
+//!     $var$5 = b * b;
+//!     $var$6 = $var$5 * b;
+//!     $var$7 = $var$6 + b;
+//!     $var$8 = $var$7 * $var$7;
+//!     $var$9 = $var$8 - b;
+//!     $var$10 = $var$9 + 1u8;
+//!     return $var$10;
+//! }
+//! ```
+//!
+//! The function outlining pass produces the following code.
+//! ```leo
+//! transition foo(a: u8) -> u8 {
+//!     $var$4 = outlined_0(a);
+//!     return $var$4;
+//! }
+//!
+//! transition bar(b: u8) -> u8 {
+//!     $var$9 = outlined_0(b);
+//!     $var$10 = $var$9 + 1u8;
+//!     return $var$10;
+//! }
+//!
+//! function outlined_0(a: u8) -> u8 {
+//!     $var$0 = a * a;
+//!     $var$1 = $var$0 * a;
+//!     $var$2 = $var$1 + a;
+//!     $var$3 = $var$2 * $var$2;
+//!     $var$4 = $var$3 - a;
+//!     return $var$4;
+//! }
+//! ```
+
+mod outline_program;
+
+mod variables;
+pub(crate) use variables::*;
+
+pub mod function_outliner;
+pub use function_outliner::*;
+
+use crate::{Assigner, Pass, PassSummary};
+
+use leo_ast::{Ast, NodeBuilder, ProgramReconstructor};
+use leo_errors::Result;
+
+impl<'a> Pass for FunctionOutliner<'a> {
+    type Input = (Ast, Assigner, &'a NodeBuilder);
+    type Output = Result<(Ast, PassSummary)>;
+
+    fn do_pass((ast, assigner, node_builder): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("function outlining", None);
+        let mut reconstructor = FunctionOutliner::new(assigner, node_builder);
+        let program = reconstructor.reconstruct_program(ast.into_repr());
+
+        let ast = Ast::new(program);
+        let mut summary = recorder.finish(None);
+        summary.decisions = reconstructor.decisions;
+
+        Ok((ast, summary))
+    }
+}
@@ -0,0 +1,488 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{FunctionOutliner, VariableCollector, VariableRenamer, MAX_SEQUENCE_LENGTH, MIN_SEQUENCE_LENGTH};
+
+use leo_ast::{
+    AccessExpression, Block, CallExpression, CallType, Expression, ExpressionReconstructor, ExpressionVisitor,
+    Function, FunctionInput, FunctionOutput, Identifier, Input, Mode, Node, Output, ProgramReconstructor, ProgramScope,
+    ReturnStatement, Statement, StatementReconstructor, StatementVisitor, Tuple, TupleExpression, Type,
+};
+use leo_span::Symbol;
+
+use indexmap::{IndexMap, IndexSet};
+use itertools::Itertools;
+use std::fmt::Write as _;
+
+/// The body of a transition, in which repeated sequences of statements are searched for.
+struct Body {
+    /// The name of the transition.
+    function: Symbol,
+    /// The statements of the transition's body.
+    statements: Vec<Statement>,
+    /// The types of the variables of the transition, where they can be determined.
+    types: IndexMap<Symbol, Type>,
+    /// The index of the last statement that uses each variable.
+    last_use: IndexMap<Symbol, usize>,
+    /// Whether each statement belongs to a sequence that has been outlined.
+    outlined: Vec<bool>,
+    /// The calls that replace the outlined sequences, indexed by the first statement of each sequence.
+    replacements: IndexMap<usize, Replacement>,
+}
+
+/// An occurrence of a sequence of statements in a transition body.
+struct Occurrence {
+    /// The index of the body containing the sequence.
+    body: usize,
+    /// The index of the first statement of the sequence.
+    start: usize,
+    /// The variables used but not defined by the sequence, in the order in which they are first used.
+    inputs: Vec<Symbol>,
+    /// The variables defined by the sequence, in order.
+    defined: Vec<Symbol>,
+}
+
+/// A call to a helper that replaces an outlined sequence of statements.
+struct Replacement {
+    /// The number of statements that are replaced.
+    length: usize,
+    /// The name of the helper.
+    helper: Symbol,
+    /// The variables passed to the helper.
+    arguments: Vec<Symbol>,
+    /// The variables assigned the outputs of the helper.
+    outputs: Vec<Symbol>,
+}
+
+impl<'a> FunctionOutliner<'a> {
+    /// Returns `true` if an assignment of `value` may be part of an outlined sequence.
+    fn is_outlinable(value: &Expression) -> bool {
+        matches!(
+            value,
+            Expression::Access(AccessExpression::Member(_))
+                | Expression::Binary(_)
+                | Expression::Cast(_)
+                | Expression::Identifier(_)
+                | Expression::Struct(_)
+                | Expression::Ternary(_)
+                | Expression::Unary(_)
+        )
+    }
+
+    /// Returns `true` if an assignment of `value` produces an instruction.
+    /// Copies and member accesses only rename operands.
+    fn produces_instruction(value: &Expression) -> bool {
+        !matches!(value, Expression::Access(_) | Expression::Identifier(_))
+    }
+
+    /// Determines the types of the variables in `function` and where each of them is last used.
+    fn analyze_body(&self, function: &Function) -> Body {
+        let mut types: IndexMap<Symbol, Type> = function
+            .input
+            .iter()
+            .map(|input| (input.identifier().name, input.type_()))
+            .collect();
+        let mut last_use = IndexMap::new();
+
+        for (index, statement) in function.block.statements.iter().enumerate() {
+            let mut collector = VariableCollector::default();
+            collector.visit_statement(statement);
+            for variable in collector.variables {
+                last_use.insert(variable, index);
+            }
+
+            if let Statement::Assign(assign) = statement {
+                match (&assign.place, self.infer_type(&assign.value, &types)) {
+                    (Expression::Identifier(place), Some(type_)) => {
+                        types.insert(place.name, type_);
+                    }
+                    // The outputs of a call are assigned to multiple variables.
+                    (Expression::Tuple(places), Some(Type::Tuple(Tuple(elements)))) => {
+                        for (place, type_) in places.elements.iter().zip(elements) {
+                            if let Expression::Identifier(place) = place {
+                                types.insert(place.name, type_);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Body {
+            function: function.name(),
+            statements: function.block.statements.clone(),
+            types,
+            last_use,
+            outlined: vec![false; function.block.statements.len()],
+            replacements: IndexMap::new(),
+        }
+    }
+
+    /// Returns a canonical form of the `length` statements of `bodies[index]` that begin at `start`, along with their occurrence.
+    /// Two sequences have the same canonical form if they perform the same operations on inputs of the same types, up to the names of their variables.
+    /// Returns `None` if the sequence cannot be outlined.
+    fn canonicalize(&self, index: usize, body: &Body, start: usize, length: usize) -> Option<(String, Occurrence)> {
+        if body.outlined[start..start + length].contains(&true) {
+            return None;
+        }
+
+        let mut key = String::new();
+        let mut names = IndexMap::new();
+        let mut inputs = Vec::new();
+        let mut defined = Vec::new();
+
+        for statement in &body.statements[start..start + length] {
+            let (place, value) = match statement {
+                Statement::Assign(assign) if Self::is_outlinable(&assign.value) => match &assign.place {
+                    Expression::Identifier(place) => (place.name, &assign.value),
+                    _ => return None,
+                },
+                _ => return None,
+            };
+
+            // Name each input by the order in which it is first used, recording its type.
+            let mut collector = VariableCollector::default();
+            collector.visit_expression(value, &());
+            for variable in collector.variables {
+                if !names.contains_key(&variable) {
+                    let type_ = body.types.get(&variable).filter(|type_| self.is_helper_type(type_))?;
+                    let name = Symbol::intern_internal(&format!("$input${}", inputs.len()));
+                    writeln!(key, "{}: {}", name, type_).expect("failed to write to string");
+                    names.insert(variable, name);
+                    inputs.push(variable);
+                }
+            }
+
+            // Name each defined variable by the order in which it is defined.
+            let value = VariableRenamer { names: &names }
+                .reconstruct_expression(value.clone())
+                .0;
+            let name = Symbol::intern_internal(&format!("$defined${}", defined.len()));
+            writeln!(key, "{} = {};", name, value).expect("failed to write to string");
+            names.insert(place, name);
+            defined.push(place);
+        }
+
+        Some((
+            key,
+            Occurrence {
+                body: index,
+                start,
+                inputs,
+                defined,
+            },
+        ))
+    }
+
+    /// Outlines the `length` statements of each occurrence of a sequence into a helper, if doing so reduces the size of the program.
+    /// Occurrences that overlap an earlier occurrence, or a sequence that has already been outlined, are skipped.
+    fn outline(
+        &mut self,
+        bodies: &mut [Body],
+        occurrences: Vec<Occurrence>,
+        length: usize,
+        helper_name: &mut dyn FnMut() -> Symbol,
+    ) -> Option<Function> {
+        let mut selected: Vec<Occurrence> = Vec::new();
+        for occurrence in occurrences {
+            let overlaps_previous = selected.last().map_or(false, |previous| {
+                previous.body == occurrence.body && occurrence.start < previous.start + length
+            });
+            let overlaps_outlined =
+                bodies[occurrence.body].outlined[occurrence.start..occurrence.start + length].contains(&true);
+            if !overlaps_previous && !overlaps_outlined {
+                selected.push(occurrence);
+            }
+        }
+        if selected.len() < 2 {
+            return None;
+        }
+
+        // The outputs of the helper are the variables defined by the sequence that are used after any of its occurrences.
+        let outputs = (0..length)
+            .filter(|position| {
+                selected.iter().any(|occurrence| {
+                    bodies[occurrence.body]
+                        .last_use
+                        .get(&occurrence.defined[*position])
+                        .map_or(false, |last_use| *last_use >= occurrence.start + length)
+                })
+            })
+            .collect::<Vec<_>>();
+        if outputs.is_empty() {
+            return None;
+        }
+
+        // The helper is constructed from the first occurrence.
+        let first = &selected[0];
+        let body = &bodies[first.body];
+        let input_types = first
+            .inputs
+            .iter()
+            .map(|input| body.types[input].clone())
+            .collect::<Vec<_>>();
+        let output_types = outputs
+            .iter()
+            .map(|position| {
+                body.types
+                    .get(&first.defined[*position])
+                    .filter(|type_| self.is_helper_type(type_))
+                    .cloned()
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let statements = &body.statements[first.start..first.start + length];
+
+        let instructions = statements
+            .iter()
+            .filter(
+                |statement| matches!(statement, Statement::Assign(assign) if Self::produces_instruction(&assign.value)),
+            )
+            .count();
+        let inlined_cost = selected.len() * instructions;
+        let outlined_cost = selected.len() + instructions + input_types.len() + output_types.len();
+        if outlined_cost >= inlined_cost {
+            return None;
+        }
+
+        let name = helper_name();
+        let mut returned = outputs
+            .iter()
+            .map(|position| {
+                Expression::Identifier(Identifier::new(first.defined[*position], self.node_builder.next_id()))
+            })
+            .collect::<Vec<_>>();
+        let returned = match returned.len() {
+            1 => returned.remove(0),
+            _ => Expression::Tuple(TupleExpression {
+                elements: returned,
+                span: Default::default(),
+                id: self.node_builder.next_id(),
+            }),
+        };
+        let mut helper_statements = statements.to_vec();
+        helper_statements.push(Statement::Return(ReturnStatement {
+            expression: returned,
+            span: Default::default(),
+            id: self.node_builder.next_id(),
+        }));
+
+        let helper = Function::new(
+            Vec::new(),
+            CallType::Standard,
+            Identifier::new(name, self.node_builder.next_id()),
+            Vec::new(),
+            first
+                .inputs
+                .iter()
+                .zip_eq(input_types)
+                .map(|(input, type_)| {
+                    Input::Internal(FunctionInput {
+                        identifier: Identifier::new(*input, self.node_builder.next_id()),
+                        mode: Mode::None,
+                        type_,
+                        span: Default::default(),
+                        id: self.node_builder.next_id(),
+                    })
+                })
+                .collect(),
+            output_types
+                .into_iter()
+                .map(|type_| {
+                    Output::Internal(FunctionOutput {
+                        mode: Mode::None,
+                        type_,
+                        span: Default::default(),
+                        id: self.node_builder.next_id(),
+                    })
+                })
+                .collect(),
+            Block {
+                statements: helper_statements,
+                span: Default::default(),
+                id: self.node_builder.next_id(),
+            },
+            None,
+            Default::default(),
+            self.node_builder.next_id(),
+        );
+
+        self.decisions.push(format!(
+            "outlined {} statements from {} into `{}`, reducing {} instructions to {}",
+            length,
+            selected
+                .iter()
+                .map(|occurrence| format!("`{}`", bodies[occurrence.body].function))
+                .join(", "),
+            name,
+            inlined_cost,
+            outlined_cost
+        ));
+
+        for occurrence in selected {
+            let body = &mut bodies[occurrence.body];
+            body.outlined[occurrence.start..occurrence.start + length].fill(true);
+            body.replacements.insert(
+                occurrence.start,
+                Replacement {
+                    length,
+                    helper: name,
+                    arguments: occurrence.inputs,
+                    outputs: outputs.iter().map(|position| occurrence.defined[*position]).collect(),
+                },
+            );
+        }
+
+        Some(helper)
+    }
+
+    /// Replaces each outlined sequence in `body` with a call to its helper.
+    fn replace_sequences(&mut self, body: Body) -> Vec<Statement> {
+        let mut statements = Vec::with_capacity(body.statements.len());
+        let mut replaced = 0;
+
+        for (index, statement) in body.statements.into_iter().enumerate() {
+            if let Some(replacement) = body.replacements.get(&index) {
+                // The call to the helper is attributed to the first statement that it replaces.
+                let span = statement.span().synthesized();
+                let call = Expression::Call(CallExpression {
+                    function: Box::new(Expression::Identifier(Identifier::new(
+                        replacement.helper,
+                        self.node_builder.next_id(),
+                    ))),
+                    arguments: replacement
+                        .arguments
+                        .iter()
+                        .map(|argument| Expression::Identifier(Identifier::new(*argument, self.node_builder.next_id())))
+                        .collect(),
+                    type_arguments: Default::default(),
+                    external: None,
+                    span,
+                    id: self.node_builder.next_id(),
+                });
+
+                match replacement.outputs.as_slice() {
+                    [output] => statements.push(self.assigner.simple_assign_statement(
+                        Identifier::new(*output, self.node_builder.next_id()),
+                        call,
+                        span,
+                        self.node_builder.next_id(),
+                    )),
+                    // Assign the outputs of the helper to the original variables directly.
+                    outputs => statements.push(
+                        self.assigner.tuple_assign_statement(
+                            outputs
+                                .iter()
+                                .map(|output| Identifier::new(*output, self.node_builder.next_id()))
+                                .collect(),
+                            call,
+                            span,
+                            self.node_builder.next_id(),
+                            self.node_builder,
+                        ),
+                    ),
+                }
+                replaced = replacement.length;
+            }
+
+            match replaced {
+                0 => statements.push(statement),
+                _ => replaced -= 1,
+            }
+        }
+
+        statements
+    }
+}
+
+// Statements and expressions are only rewritten as part of the sequences that are outlined.
+impl ExpressionReconstructor for FunctionOutliner<'_> {
+    type AdditionalOutput = ();
+}
+
+impl StatementReconstructor for FunctionOutliner<'_> {}
+
+impl ProgramReconstructor for FunctionOutliner<'_> {
+    /// Outlines the repeated sequences of statements in the transitions of the program.
+    /// Longer sequences are outlined first, so that they are not split by shorter sequences that they contain.
+    fn reconstruct_program_scope(&mut self, input: ProgramScope) -> ProgramScope {
+        self.structs = input
+            .structs
+            .values()
+            .map(|struct_| (struct_.identifier.name, struct_.clone()))
+            .collect();
+        self.function_types = input
+            .functions
+            .values()
+            .map(|function| (function.name(), function.output_type.clone()))
+            .collect();
+
+        let mut bodies = input
+            .functions
+            .values()
+            .filter(|function| function.call_type == CallType::Transition)
+            .map(|function| self.analyze_body(function))
+            .collect::<Vec<_>>();
+
+        // Helpers are named `outlined_0`, `outlined_1`, and so on, skipping names that are already declared.
+        let declared = input
+            .structs
+            .keys()
+            .chain(input.mappings.keys())
+            .chain(input.functions.keys())
+            .map(|identifier| identifier.name)
+            .collect::<IndexSet<_>>();
+        let mut counter = 0;
+        let mut helper_name = || loop {
+            let name = Symbol::intern(&format!("outlined_{}", counter));
+            counter += 1;
+            if !declared.contains(&name) {
+                break name;
+            }
+        };
+
+        let mut helpers = Vec::new();
+        for length in (MIN_SEQUENCE_LENGTH..=MAX_SEQUENCE_LENGTH).rev() {
+            // Group the sequences of `length` statements by their canonical form.
+            let mut sequences: IndexMap<String, Vec<Occurrence>> = IndexMap::new();
+            for (index, body) in bodies.iter().enumerate() {
+                for start in 0..(body.statements.len() + 1).saturating_sub(length) {
+                    if let Some((key, occurrence)) = self.canonicalize(index, body, start, length) {
+                        sequences.entry(key).or_default().push(occurrence);
+                    }
+                }
+            }
+
+            for occurrences in sequences.into_values().filter(|occurrences| occurrences.len() > 1) {
+                if let Some(helper) = self.outline(&mut bodies, occurrences, length, &mut helper_name) {
+                    helpers.push(helper);
+                }
+            }
+        }
+
+        let mut functions = input.functions;
+        for body in bodies {
+            let function = body.function;
+            let statements = self.replace_sequences(body);
+            if let Some(function) = functions.values_mut().find(|candidate| candidate.name() == function) {
+                function.block.statements = statements;
+            }
+        }
+        functions.extend(helpers.into_iter().map(|helper| (helper.identifier, helper)));
+
+        ProgramScope { functions, ..input }
+    }
+}
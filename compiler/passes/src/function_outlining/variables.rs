@@ -0,0 +1,94 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{
+    Expression, ExpressionReconstructor, ExpressionVisitor, Identifier, StatementVisitor, StructExpression,
+    StructVariableInitializer,
+};
+use leo_span::Symbol;
+
+use indexmap::{IndexMap, IndexSet};
+
+/// Collects the variables used by statements, in the order in which they are first used.
+#[derive(Default)]
+pub(crate) struct VariableCollector {
+    pub(crate) variables: IndexSet<Symbol>,
+}
+
+impl<'a> ExpressionVisitor<'a> for VariableCollector {
+    type AdditionalInput = ();
+    type Output = ();
+
+    fn visit_identifier(&mut self, input: &'a Identifier, _additional: &Self::AdditionalInput) -> Self::Output {
+        self.variables.insert(input.name);
+    }
+
+    fn visit_struct_init(&mut self, input: &'a StructExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        for member in input.members.iter() {
+            match &member.expression {
+                Some(expression) => self.visit_expression(expression, additional),
+                None => self.visit_identifier(&member.identifier, additional),
+            }
+        }
+    }
+}
+
+impl<'a> StatementVisitor<'a> for VariableCollector {}
+
+/// Replaces the variables in an expression with the names given in `names`.
+/// Variables that are not in `names` are left unchanged.
+pub(crate) struct VariableRenamer<'a> {
+    pub(crate) names: &'a IndexMap<Symbol, Symbol>,
+}
+
+impl ExpressionReconstructor for VariableRenamer<'_> {
+    type AdditionalOutput = ();
+
+    fn reconstruct_identifier(&mut self, input: Identifier) -> (Expression, Self::AdditionalOutput) {
+        let name = *self.names.get(&input.name).unwrap_or(&input.name);
+        (
+            Expression::Identifier(Identifier {
+                name,
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    fn reconstruct_struct_init(&mut self, input: StructExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Struct(StructExpression {
+                name: input.name,
+                const_arguments: input.const_arguments,
+                members: input
+                    .members
+                    .into_iter()
+                    .map(|member| StructVariableInitializer {
+                        identifier: member.identifier,
+                        expression: Some(match member.expression {
+                            Some(expression) => self.reconstruct_expression(expression).0,
+                            None => self.reconstruct_identifier(member.identifier).0,
+                        }),
+                    })
+                    .collect(),
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use indexmap::IndexMap;
+use std::fmt;
+
+/// An affine combination of `u64` values that the analysis does not track further, each named by its source text,
+/// e.g. `t.gates - amount + 1`. Since every such value is nonnegative, a form can only be positive if it has a positive coefficient or a positive constant.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AffineForm {
+    /// The coefficient of each value, none of which are zero.
+    pub terms: IndexMap<String, i128>,
+    /// The constant term.
+    pub constant: i128,
+}
+
+impl AffineForm {
+    /// Returns the form of a constant.
+    pub fn constant(constant: i128) -> Self {
+        Self {
+            terms: IndexMap::new(),
+            constant,
+        }
+    }
+
+    /// Returns the form of a single value, named `name`.
+    pub fn value(name: String) -> Self {
+        Self {
+            terms: IndexMap::from([(name, 1)]),
+            constant: 0,
+        }
+    }
+
+    /// Returns the constant term, if the form has no other terms.
+    pub fn as_constant(&self) -> Option<i128> {
+        self.terms.is_empty().then_some(self.constant)
+    }
+
+    /// Returns the sum of `self` and `other`.
+    pub fn add(&self, other: &Self) -> Self {
+        let mut sum = self.clone();
+        for (name, coefficient) in other.terms.iter() {
+            let entry = sum.terms.entry(name.clone()).or_default();
+            *entry = entry.saturating_add(*coefficient);
+        }
+        sum.terms.retain(|_, coefficient| *coefficient != 0);
+        sum.constant = sum.constant.saturating_add(other.constant);
+        sum
+    }
+
+    /// Returns `self` minus `other`.
+    pub fn sub(&self, other: &Self) -> Self {
+        self.add(&other.scale(-1))
+    }
+
+    /// Returns `self` multiplied by `factor`.
+    pub fn scale(&self, factor: i128) -> Self {
+        Self {
+            terms: self
+                .terms
+                .iter()
+                .filter(|_| factor != 0)
+                .map(|(name, coefficient)| (name.clone(), coefficient.saturating_mul(factor)))
+                .collect(),
+            constant: self.constant.saturating_mul(factor),
+        }
+    }
+
+    /// Returns true if the form is positive for some nonnegative values of its terms.
+    pub fn can_be_positive(&self) -> bool {
+        self.constant > 0 || self.terms.values().any(|coefficient| *coefficient > 0)
+    }
+}
+
+impl fmt::Display for AffineForm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let terms = self
+            .terms
+            .iter()
+            .map(|(name, coefficient)| (*coefficient, Some(name)))
+            .chain((self.constant != 0 || self.terms.is_empty()).then_some((self.constant, None)));
+        for (i, (coefficient, name)) in terms.enumerate() {
+            let magnitude = coefficient.unsigned_abs();
+            match (i, coefficient < 0) {
+                (0, true) => write!(f, "-")?,
+                (0, false) => {}
+                (_, true) => write!(f, " - ")?,
+                (_, false) => write!(f, " + ")?,
+            }
+            match name {
+                Some(name) if magnitude == 1 => write!(f, "{}", name)?,
+                Some(name) => write!(f, "{} * {}", magnitude, name)?,
+                None => write!(f, "{}", magnitude)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_affine_form() {
+        let gates = AffineForm::value("t.gates".to_string());
+        let amount = AffineForm::value("amount".to_string());
+
+        let change = gates.sub(&amount);
+        assert_eq!(change.to_string(), "t.gates - amount");
+        assert!(change.can_be_positive());
+
+        // Splitting a record conserves its gates.
+        assert_eq!(change.add(&amount).sub(&gates), AffineForm::constant(0));
+        assert!(!AffineForm::constant(0).can_be_positive());
+
+        let minted = amount.scale(2).add(&AffineForm::constant(1)).sub(&gates);
+        assert_eq!(minted.to_string(), "2 * amount - t.gates + 1");
+        assert_eq!(gates.scale(0).as_constant(), Some(0));
+        assert_eq!(
+            gates.scale(-1).sub(&AffineForm::constant(3)).to_string(),
+            "-t.gates - 3"
+        );
+    }
+}
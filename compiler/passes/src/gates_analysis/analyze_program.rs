@@ -0,0 +1,107 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{AffineForm, GatesAnalyzer};
+
+use leo_ast::{
+    AssignStatement, CallType, DefinitionStatement, Expression, ExpressionVisitor, Function, Input, Output, Program,
+    ProgramScope, ProgramVisitor, ReturnStatement, StatementVisitor, Type,
+};
+use leo_span::sym;
+
+impl<'a> ExpressionVisitor<'a> for GatesAnalyzer<'a> {
+    type AdditionalInput = ();
+    type Output = ();
+}
+
+impl<'a> GatesAnalyzer<'a> {
+    /// Records the possible values of a variable, and its possible gates in case it is a record.
+    fn assign(&mut self, place: &Expression, value: &Expression) {
+        if let Expression::Identifier(identifier) = place {
+            self.values.insert(identifier.name, self.value_of(value));
+            self.gates.insert(identifier.name, self.gates_of(value));
+        }
+    }
+}
+
+impl<'a> StatementVisitor<'a> for GatesAnalyzer<'a> {
+    fn visit_assign(&mut self, input: &'a AssignStatement) {
+        self.assign(&input.place, &input.value);
+    }
+
+    fn visit_definition(&mut self, input: &'a DefinitionStatement) {
+        self.assign(&input.place, &input.value);
+    }
+
+    fn visit_return(&mut self, input: &'a ReturnStatement) {
+        self.check_return(input);
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for GatesAnalyzer<'a> {
+    fn visit_program_scope(&mut self, input: &'a ProgramScope) {
+        self.records = input
+            .structs
+            .values()
+            .filter(|struct_| struct_.is_record)
+            .map(|struct_| struct_.identifier.name)
+            .collect();
+
+        input
+            .functions
+            .values()
+            .for_each(|function| self.visit_function(function));
+    }
+
+    /// Analyzes the transitions that output records, unless they are annotated with `@mint`.
+    fn visit_function(&mut self, input: &'a Function) {
+        let is_record =
+            |type_: &Type| matches!(type_, Type::Identifier(identifier) if self.records.contains(&identifier.name));
+        self.record_outputs = input
+            .output
+            .iter()
+            .map(|output| matches!(output, Output::Internal(output) if is_record(&output.type_)))
+            .collect();
+
+        let mints = input
+            .annotations
+            .iter()
+            .any(|annotation| annotation.identifier.name == sym::mint);
+        if !matches!(input.call_type, CallType::Transition) || mints || !self.record_outputs.contains(&true) {
+            return;
+        }
+
+        self.function = input.identifier.name;
+        self.input_gates = input
+            .input
+            .iter()
+            .filter_map(|input| match input {
+                Input::Internal(input) if is_record(&input.type_) => Some(input.identifier),
+                Input::Internal(_) => None,
+                Input::External(input) => Some(input.identifier),
+            })
+            .fold(AffineForm::default(), |total, identifier| {
+                total.add(&AffineForm::value(format!("{}.gates", identifier)))
+            });
+        self.values.clear();
+        self.gates.clear();
+
+        self.visit_block(&input.block);
+    }
+
+    /// Imported programs are analyzed when they are compiled.
+    fn visit_import(&mut self, _input: &'a Program) {}
+}
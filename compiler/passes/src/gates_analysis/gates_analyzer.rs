@@ -0,0 +1,167 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::AffineForm;
+
+use leo_ast::{AccessExpression, BinaryOperation, Expression, Literal, ReturnStatement};
+use leo_errors::{emitter::Handler, TypeCheckerWarning};
+use leo_span::{sym, Symbol};
+
+use indexmap::{IndexMap, IndexSet};
+
+/// The maximum number of possible values tracked for an expression, beyond which its value is not tracked.
+const MAX_ALTERNATIVES: usize = 16;
+
+pub struct GatesAnalyzer<'a> {
+    /// The error handler.
+    pub(crate) handler: &'a Handler,
+    /// The names of the records declared in the program.
+    pub(crate) records: IndexSet<Symbol>,
+    /// The name of the function being analyzed.
+    pub(crate) function: Symbol,
+    /// Whether each output of the function being analyzed is a record.
+    pub(crate) record_outputs: Vec<bool>,
+    /// The total gates of the record inputs of the function being analyzed.
+    pub(crate) input_gates: AffineForm,
+    /// The possible values of each variable assigned so far in the function being analyzed.
+    pub(crate) values: IndexMap<Symbol, Vec<AffineForm>>,
+    /// The possible gates of each variable assigned so far in the function being analyzed, if it is a record.
+    pub(crate) gates: IndexMap<Symbol, Vec<AffineForm>>,
+}
+
+impl<'a> GatesAnalyzer<'a> {
+    /// Returns a new gates analyzer given an error handler.
+    pub fn new(handler: &'a Handler) -> Self {
+        Self {
+            handler,
+            records: IndexSet::new(),
+            function: Symbol::intern(""),
+            record_outputs: Vec::new(),
+            input_gates: AffineForm::default(),
+            values: IndexMap::new(),
+            gates: IndexMap::new(),
+        }
+    }
+
+    /// Returns `forms`, or a single untracked value named `name()` if they are unknown or there are too many of them.
+    fn bounded(forms: Option<Vec<AffineForm>>, name: impl FnOnce() -> String) -> Vec<AffineForm> {
+        forms
+            .filter(|forms| forms.len() <= MAX_ALTERNATIVES)
+            .unwrap_or_else(|| vec![AffineForm::value(name())])
+    }
+
+    /// Combines each possible value of `left` with each possible value of `right`, unless `combine` fails for some pair.
+    fn pairwise(
+        left: &[AffineForm],
+        right: &[AffineForm],
+        combine: impl Fn(&AffineForm, &AffineForm) -> Option<AffineForm>,
+    ) -> Option<Vec<AffineForm>> {
+        left.iter()
+            .flat_map(|left| right.iter().map(move |right| (left, right)))
+            .map(|(left, right)| combine(left, right))
+            .collect()
+    }
+
+    /// Returns the possible values of an integer expression.
+    pub(crate) fn value_of(&self, expression: &Expression) -> Vec<AffineForm> {
+        let forms = match expression {
+            Expression::Literal(Literal::Integer(_, value, _, _) | Literal::Unsuffixed(value, ..)) => value
+                .replace('_', "")
+                .parse()
+                .ok()
+                .map(|value| vec![AffineForm::constant(value)]),
+            Expression::Identifier(identifier) => self.values.get(&identifier.name).cloned(),
+            Expression::Access(AccessExpression::Member(access)) if access.name.name == sym::gates => {
+                Some(self.gates_of(&access.inner))
+            }
+            Expression::Binary(binary) => {
+                let (left, right) = (self.value_of(&binary.left), self.value_of(&binary.right));
+                match binary.op {
+                    BinaryOperation::Add => Self::pairwise(&left, &right, |left, right| Some(left.add(right))),
+                    BinaryOperation::Sub => Self::pairwise(&left, &right, |left, right| Some(left.sub(right))),
+                    BinaryOperation::Mul => Self::pairwise(&left, &right, |left, right| {
+                        match (left.as_constant(), right.as_constant()) {
+                            (_, Some(factor)) => Some(left.scale(factor)),
+                            (Some(factor), _) => Some(right.scale(factor)),
+                            _ => None,
+                        }
+                    }),
+                    // Note that wrapping operations may produce values larger than their operands.
+                    _ => None,
+                }
+            }
+            Expression::Ternary(ternary) => {
+                let mut forms = self.value_of(&ternary.if_true);
+                forms.extend(self.value_of(&ternary.if_false));
+                Some(forms)
+            }
+            _ => None,
+        };
+        Self::bounded(forms, || expression.to_string())
+    }
+
+    /// Returns the possible gates of a record expression.
+    pub(crate) fn gates_of(&self, expression: &Expression) -> Vec<AffineForm> {
+        let forms = match expression {
+            Expression::Identifier(identifier) => self.gates.get(&identifier.name).cloned(),
+            Expression::Struct(struct_) if self.records.contains(&struct_.name.name) => struct_
+                .members
+                .iter()
+                .find(|member| member.identifier.name == sym::gates)
+                .map(|member| match &member.expression {
+                    Some(expression) => self.value_of(expression),
+                    None => self.value_of(&Expression::Identifier(member.identifier)),
+                }),
+            Expression::Ternary(ternary) => {
+                let mut forms = self.gates_of(&ternary.if_true);
+                forms.extend(self.gates_of(&ternary.if_false));
+                Some(forms)
+            }
+            _ => None,
+        };
+        Self::bounded(forms, || format!("{}.gates", expression))
+    }
+
+    /// Emits a warning if the gates of the records returned by `input` may exceed the gates of the record inputs.
+    pub(crate) fn check_return(&self, input: &ReturnStatement) {
+        let outputs = match &input.expression {
+            Expression::Tuple(tuple) if self.record_outputs.len() > 1 => tuple.elements.iter().collect(),
+            expression => vec![expression],
+        };
+
+        let mut totals = vec![AffineForm::default()];
+        for (output, _) in outputs
+            .into_iter()
+            .zip(self.record_outputs.iter())
+            .filter(|(_, is_record)| **is_record)
+        {
+            let gates = self.gates_of(output);
+            totals = Self::bounded(
+                Self::pairwise(&totals, &gates, |total, gates| Some(total.add(gates))),
+                || input.expression.to_string(),
+            );
+        }
+
+        if let Some(excess) = totals
+            .iter()
+            .map(|total| total.sub(&self.input_gates))
+            .find(AffineForm::can_be_positive)
+        {
+            self.handler
+                .emit_warning(TypeCheckerWarning::record_gates_can_increase(self.function, excess, input.span).into());
+        }
+    }
+}
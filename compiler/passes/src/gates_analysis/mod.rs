@@ -0,0 +1,59 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The gates analysis pass traverses the AST after static single assignment and warns about transitions whose record outputs may hold more gates than their record inputs.
+//! Such a transition mints gates, which is rarely intended, so transitions that are meant to mint are annotated with `@mint` to silence the warning.
+//!
+//! The gates of each record, and the values of the integer variables that they are computed from, are tracked as affine combinations of values that the pass does not track further,
+//! such as the gates of the record inputs, the other inputs, and the results of calls and wrapping operations.
+//! Each return statement is checked separately, and the possible values of a ternary expression are the values of both of its branches.
+//! Since every untracked value is a nonnegative `u64`, the total gates of the outputs can only exceed the total gates of the inputs if their difference has a positive coefficient or constant.
+//!
+//! Consider the following Leo code.
+//! ```leo
+//! transition split(token: Token, amount: u64) -> (Token, Token) {
+//!     let change: u64 = token.gates - amount;
+//!     return (Token { owner: token.owner, gates: amount }, Token { owner: self.caller, gates: change + 1u64 });
+//! }
+//! ```
+//!
+//! The outputs hold `amount + token.gates - amount + 1` gates, which exceeds the `token.gates` gates of the input by `1`, so the pass warns about `split`.
+
+mod affine_form;
+pub use affine_form::*;
+
+mod analyze_program;
+
+pub mod gates_analyzer;
+pub use gates_analyzer::*;
+
+use crate::{Pass, PassSummary};
+
+use leo_ast::{Ast, ProgramVisitor};
+use leo_errors::{emitter::Handler, Result};
+
+impl<'a> Pass for GatesAnalyzer<'a> {
+    type Input = (&'a Ast, &'a Handler);
+    type Output = Result<PassSummary>;
+
+    fn do_pass((ast, handler): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("gates analysis", Some(handler));
+        let mut visitor = GatesAnalyzer::new(handler);
+        visitor.visit_program(ast.as_repr());
+
+        Ok(recorder.finish(Some(handler)))
+    }
+}
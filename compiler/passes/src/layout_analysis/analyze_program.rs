@@ -0,0 +1,66 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::LayoutAnalyzer;
+
+use leo_ast::{ExpressionVisitor, Program, ProgramScope, ProgramVisitor, StatementVisitor, StructExpression};
+
+impl<'a> ExpressionVisitor<'a> for LayoutAnalyzer<'a> {
+    type AdditionalInput = ();
+    type Output = ();
+
+    /// Records the value assigned to each member of the struct, if it is an integer literal.
+    fn visit_struct_init(&mut self, input: &'a StructExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        for member in input.members.iter() {
+            let value = member.expression.as_ref().and_then(Self::literal_value);
+            let range = self
+                .member_values
+                .entry((input.name.name, member.identifier.name))
+                .or_insert(value);
+            *range = match (*range, value) {
+                (Some(range), Some(value)) => range.union(&value),
+                _ => None,
+            };
+
+            if let Some(expression) = &member.expression {
+                self.visit_expression(expression, additional);
+            }
+        }
+    }
+}
+
+impl<'a> StatementVisitor<'a> for LayoutAnalyzer<'a> {}
+
+impl<'a> ProgramVisitor<'a> for LayoutAnalyzer<'a> {
+    /// Collects the values assigned to the members of each struct, and then checks the layout of each struct.
+    fn visit_program_scope(&mut self, input: &'a ProgramScope) {
+        self.structs = input
+            .structs
+            .values()
+            .map(|struct_| (struct_.identifier.name, struct_))
+            .collect();
+
+        input
+            .functions
+            .values()
+            .for_each(|function| self.visit_function(function));
+
+        input.structs.values().for_each(|struct_| self.check_struct(struct_));
+    }
+
+    /// The layouts of imported structs cannot be changed by the program, so they are not checked.
+    fn visit_import(&mut self, _input: &'a Program) {}
+}
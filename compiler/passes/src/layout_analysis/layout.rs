@@ -0,0 +1,129 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+/// The number of bits of data that fit in a single field element.
+pub const FIELD_DATA_BITS: usize = 252;
+
+/// The number of bytes in a serialized field element.
+pub const FIELD_BYTES: usize = 32;
+
+/// The space taken by a member of a struct or record.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemberSize {
+    /// A value of the given number of bits, which may share a field element with other members.
+    Bits(usize),
+    /// A value that occupies the given number of whole field elements.
+    Elements(usize),
+}
+
+/// Returns the number of field elements taken by members of the given sizes, laid out in order.
+/// A member that does not fit in the remainder of the current field element begins a new one.
+pub fn field_elements(sizes: impl IntoIterator<Item = MemberSize>) -> usize {
+    let mut elements = 0;
+    // The number of bits that are still available in the current field element.
+    let mut available = 0;
+    for size in sizes {
+        match size {
+            MemberSize::Bits(bits) if bits <= available => available -= bits,
+            MemberSize::Bits(bits) => {
+                elements += 1;
+                available = FIELD_DATA_BITS.saturating_sub(bits);
+            }
+            MemberSize::Elements(count) => {
+                elements += count;
+                available = 0;
+            }
+        }
+    }
+    elements
+}
+
+/// Returns the number of bytes taken by members of the given sizes, laid out in order.
+pub fn serialized_bytes(sizes: impl IntoIterator<Item = MemberSize>) -> usize {
+    field_elements(sizes) * FIELD_BYTES
+}
+
+/// Returns an order of the members with the given sizes that packs them into few field elements, as a list of indices into `sizes`.
+/// Members that occupy whole field elements come first, followed by the remaining members, which are packed using the first-fit decreasing heuristic.
+/// Members that are placed in the same field element keep their relative order.
+pub fn packed_order(sizes: &[MemberSize]) -> Vec<usize> {
+    let mut order = (0..sizes.len())
+        .filter(|index| matches!(sizes[*index], MemberSize::Elements(_)))
+        .collect::<Vec<_>>();
+
+    // Place the largest members first, each in the first field element that has room for it.
+    let mut members = sizes
+        .iter()
+        .enumerate()
+        .filter_map(|(index, size)| match size {
+            MemberSize::Bits(bits) => Some((index, *bits)),
+            MemberSize::Elements(_) => None,
+        })
+        .collect::<Vec<_>>();
+    members.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+    // Each field element is given as the number of bits that are still available, and the members that it contains.
+    let mut elements: Vec<(usize, Vec<usize>)> = Vec::new();
+    for (index, bits) in members {
+        match elements.iter_mut().find(|(available, _)| *available >= bits) {
+            Some((available, contents)) => {
+                *available -= bits;
+                contents.push(index);
+            }
+            None => elements.push((FIELD_DATA_BITS.saturating_sub(bits), vec![index])),
+        }
+    }
+
+    for (_, mut contents) in elements {
+        contents.sort_unstable();
+        order.extend(contents);
+    }
+    order
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use MemberSize::*;
+
+    #[test]
+    fn test_field_elements() {
+        assert_eq!(field_elements([]), 0);
+        assert_eq!(field_elements([Bits(8), Bits(64), Bits(128)]), 1);
+        assert_eq!(field_elements([Bits(128), Bits(128)]), 2);
+        assert_eq!(field_elements([Elements(1), Bits(8), Elements(1), Bits(8)]), 4);
+        assert_eq!(serialized_bytes([Bits(1), Elements(2)]), 3 * FIELD_BYTES);
+    }
+
+    #[test]
+    fn test_packed_order() {
+        let sizes = [Bits(8), Elements(1), Bits(8), Elements(1)];
+        assert_eq!(packed_order(&sizes), vec![1, 3, 0, 2]);
+        assert_eq!(
+            field_elements(packed_order(&sizes).into_iter().map(|index| sizes[index])),
+            3
+        );
+
+        let sizes = [Bits(128), Bits(8), Bits(128), Bits(64), Bits(64)];
+        assert_eq!(field_elements(sizes), 3);
+        assert_eq!(packed_order(&sizes), vec![0, 1, 3, 2, 4]);
+        assert_eq!(
+            field_elements(packed_order(&sizes).into_iter().map(|index| sizes[index])),
+            2
+        );
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{field_elements, packed_order, serialized_bytes, Interval, MemberSize};
+
+use leo_ast::{Expression, IntegerType, Literal, Node, Struct, Type, UnaryOperation};
+use leo_errors::{emitter::Handler, TypeCheckerWarning};
+use leo_span::{sym, Symbol};
+
+use indexmap::IndexMap;
+use itertools::Itertools;
+use std::iter;
+
+pub struct LayoutAnalyzer<'a> {
+    /// The error handler.
+    pub(crate) handler: &'a Handler,
+    /// The structs and records declared in the program.
+    pub(crate) structs: IndexMap<Symbol, &'a Struct>,
+    /// The range of the values assigned to each integer member of a struct, indexed by the names of the struct and the member.
+    /// The range is `None` if some value assigned to the member is not an integer literal.
+    pub(crate) member_values: IndexMap<(Symbol, Symbol), Option<Interval>>,
+}
+
+impl<'a> LayoutAnalyzer<'a> {
+    /// Returns a new layout analyzer given an error handler.
+    pub fn new(handler: &'a Handler) -> Self {
+        Self {
+            handler,
+            structs: IndexMap::new(),
+            member_values: IndexMap::new(),
+        }
+    }
+
+    /// Returns the number of bits in a value of the given integer type.
+    fn integer_bits(type_: IntegerType) -> usize {
+        use IntegerType::*;
+        match type_ {
+            U8 | I8 => 8,
+            U16 | I16 => 16,
+            U32 | I32 => 32,
+            U64 | I64 => 64,
+            U128 | I128 => 128,
+        }
+    }
+
+    /// Returns the space taken by a member of type `type_`, if it can be determined.
+    pub(crate) fn member_size(&self, type_: &Type) -> Option<MemberSize> {
+        match type_ {
+            Type::Boolean => Some(MemberSize::Bits(1)),
+            Type::Integer(type_) => Some(MemberSize::Bits(Self::integer_bits(*type_))),
+            Type::Address | Type::Field | Type::Group | Type::Scalar => Some(MemberSize::Elements(1)),
+            // A nested struct begins in a new field element.
+            Type::Identifier(identifier) => {
+                let struct_ = self.structs.get(&identifier.name)?;
+                Some(MemberSize::Elements(field_elements(self.member_sizes(struct_)?)))
+            }
+            Type::Array(element, length) => {
+                let element = self.member_size(element)?;
+                Some(MemberSize::Elements(field_elements(
+                    iter::repeat(element).take(length.get()),
+                )))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the space taken by each member of `struct_`, in order, if it can be determined.
+    pub(crate) fn member_sizes(&self, struct_: &Struct) -> Option<Vec<MemberSize>> {
+        struct_
+            .members
+            .iter()
+            .map(|member| self.member_size(&member.type_))
+            .collect()
+    }
+
+    /// Returns the value of an integer literal, or of a negated integer literal.
+    pub(crate) fn literal_value(expression: &Expression) -> Option<Interval> {
+        match expression {
+            Expression::Literal(literal @ (Literal::Integer(_, value, _, _) | Literal::Unsuffixed(value, _, _, _))) => {
+                Some(Interval::constant(literal.integer_type()?, value.parse().ok()?))
+            }
+            Expression::Unary(unary) if unary.op == UnaryOperation::Negate => {
+                let value = Self::literal_value(&unary.receiver)?;
+                Some(Interval::constant(value.type_, value.min.checked_neg()?))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the narrowest integer type with the same signedness as `range` that holds every value in `range`.
+    fn narrowest_type(range: &Interval) -> IntegerType {
+        use IntegerType::*;
+        let candidates = match range.type_.is_signed() {
+            true => [I8, I16, I32, I64, I128],
+            false => [U8, U16, U32, U64, U128],
+        };
+        candidates
+            .into_iter()
+            .find(|type_| Interval::full(*type_).map_or(true, |full| full.min <= range.min && range.max <= full.max))
+            .unwrap_or(range.type_)
+    }
+
+    /// Warns if the members of `struct_` can be reordered, or if any of its integer members can be narrowed, to reduce its serialized size.
+    pub(crate) fn check_struct(&self, struct_: &Struct) {
+        let sizes = match self.member_sizes(struct_) {
+            Some(sizes) => sizes,
+            None => return,
+        };
+        let current = serialized_bytes(sizes.iter().copied());
+
+        // The `owner` and `gates` members of a record always come first.
+        let fixed = match struct_.is_record {
+            true => struct_
+                .members
+                .iter()
+                .take_while(|member| matches!(member.name(), sym::owner | sym::gates))
+                .count(),
+            false => 0,
+        };
+        let order = (0..fixed)
+            .chain(packed_order(&sizes[fixed..]).into_iter().map(|index| index + fixed))
+            .collect::<Vec<_>>();
+        let reordered = serialized_bytes(order.iter().map(|index| sizes[*index]));
+        if reordered < current {
+            self.handler.emit_warning(
+                TypeCheckerWarning::struct_members_can_be_reordered(
+                    struct_.identifier,
+                    order
+                        .iter()
+                        .map(|index| format!("`{}`", struct_.members[*index].identifier))
+                        .join(", "),
+                    current,
+                    reordered,
+                    struct_.span(),
+                )
+                .into(),
+            );
+        }
+
+        // Narrow each integer member whose assigned values all fit in a narrower type, keeping the other members unchanged.
+        for (index, member) in struct_.members.iter().enumerate() {
+            let range = match self.member_values.get(&(struct_.identifier.name, member.name())) {
+                Some(Some(range)) if Type::Integer(range.type_) == member.type_ => range,
+                _ => continue,
+            };
+            let narrowed_type = Self::narrowest_type(range);
+            let mut narrowed_sizes = sizes.clone();
+            narrowed_sizes[index] = MemberSize::Bits(Self::integer_bits(narrowed_type));
+            let narrowed = serialized_bytes(narrowed_sizes);
+            if narrowed < current {
+                self.handler.emit_warning(
+                    TypeCheckerWarning::struct_member_can_be_narrowed(
+                        struct_.identifier,
+                        member.identifier,
+                        narrowed_type,
+                        current,
+                        narrowed,
+                        member.identifier.span,
+                    )
+                    .into(),
+                );
+            }
+        }
+    }
+}
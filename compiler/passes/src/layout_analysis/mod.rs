@@ -0,0 +1,64 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The layout analysis pass traverses the AST after loop unrolling and warns about structs and records whose serialized size could be reduced.
+//! Members are serialized in order into field elements of 252 bits of data each, and a member that does not fit in the remainder of the current field element begins a new one.
+//! Booleans take a single bit and integers take their width, while fields, groups, scalars, addresses, and nested structs occupy whole field elements.
+//!
+//! The pass suggests two kinds of changes, each of which is only reported if it reduces the number of field elements.
+//! - Reordering the members, so that small members share field elements. The `owner` and `gates` members of a record keep their positions.
+//! - Narrowing an integer member, when every value that the program assigns to it is a literal that fits in a narrower type of the same signedness.
+//!
+//! Consider the following Leo code.
+//! ```leo
+//! struct Point {
+//!     x: field,
+//!     flag: bool,
+//!     y: field,
+//!     count: u128,
+//!     total: u128,
+//! }
+//! ```
+//!
+//! `Point` takes five field elements, since `flag` and `count` each begin a field element after `x` and `y`, and `total` does not fit alongside `count`.
+//! The pass suggests the order `x`, `y`, `flag`, `count`, `total`, which takes four field elements.
+//! If every value assigned to `total` fits in a `u64`, the pass also suggests narrowing it, which lets it share a field element with `count`.
+
+mod analyze_program;
+
+pub mod layout;
+pub use layout::*;
+
+pub mod layout_analyzer;
+pub use layout_analyzer::*;
+
+use crate::{Pass, PassSummary};
+
+use leo_ast::{Ast, ProgramVisitor};
+use leo_errors::{emitter::Handler, Result};
+
+impl<'a> Pass for LayoutAnalyzer<'a> {
+    type Input = (&'a Ast, &'a Handler);
+    type Output = Result<PassSummary>;
+
+    fn do_pass((ast, handler): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("layout analysis", Some(handler));
+        let mut visitor = LayoutAnalyzer::new(handler);
+        visitor.visit_program(ast.as_repr());
+
+        Ok(recorder.finish(Some(handler)))
+    }
+}
@@ -17,23 +17,77 @@
 #![forbid(unsafe_code)]
 #![doc = include_str!("../README.md")]
 
+pub mod assertion_checking;
+pub use assertion_checking::*;
+
+pub mod boolean_simplification;
+pub use boolean_simplification::*;
+
+pub mod cfg_stripping;
+pub use cfg_stripping::*;
+
 pub mod code_generation;
 pub use code_generation::*;
 
+pub mod common;
+pub use common::*;
+
+pub mod cost_estimation;
+pub use cost_estimation::*;
+
+pub mod enum_lowering;
+pub use enum_lowering::*;
+
 pub mod flattening;
 pub use flattening::*;
 
+pub mod function_inlining;
+pub use function_inlining::*;
+
+pub mod function_outlining;
+pub use function_outlining::*;
+
+pub mod gates_analysis;
+pub use gates_analysis::*;
+
+pub mod layout_analysis;
+pub use layout_analysis::*;
+
 pub mod loop_unrolling;
 pub use self::loop_unrolling::*;
 
+pub mod monomorphization;
+pub use monomorphization::*;
+
 pub mod pass;
 pub use self::pass::*;
 
+pub mod range_analysis;
+pub use range_analysis::*;
+
+pub mod register_allocation;
+pub use register_allocation::*;
+
 pub mod static_single_assignment;
 pub use static_single_assignment::*;
 
+pub mod strength_reduction;
+pub use strength_reduction::*;
+
 pub mod symbol_table;
 pub use symbol_table::*;
 
+pub mod taint_analysis;
+pub use taint_analysis::*;
+
+pub mod ternary_balancing;
+pub use ternary_balancing::*;
+
+pub mod tuple_lowering;
+pub use tuple_lowering::*;
+
 pub mod type_checking;
 pub use type_checking::*;
+
+pub mod validation;
+pub use validation::*;
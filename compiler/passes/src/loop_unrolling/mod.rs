@@ -20,6 +20,9 @@ pub(crate) use range_iterator::*;
 pub mod unroller;
 pub use unroller::*;
 
+pub mod unroll_loop;
+pub use unroll_loop::*;
+
 pub mod unroll_expression;
 pub use unroll_expression::*;
 
@@ -29,21 +32,31 @@ pub use unroll_program::*;
 pub mod unroll_statement;
 pub use unroll_statement::*;
 
-use crate::{Pass, SymbolTable};
+use crate::{Pass, PassSummary, SymbolTable, TypeTable};
 
-use leo_ast::{Ast, ProgramReconstructor};
+use leo_ast::{Ast, NodeBuilder, ProgramReconstructor};
 use leo_errors::{emitter::Handler, Result};
 
 impl<'a> Pass for Unroller<'a> {
-    type Input = (Ast, &'a Handler, SymbolTable);
-    type Output = Result<(Ast, SymbolTable)>;
+    type Input = (Ast, &'a Handler, SymbolTable, TypeTable, &'a NodeBuilder);
+    type Output = Result<(Ast, SymbolTable, TypeTable, PassSummary)>;
+
+    fn do_pass((ast, handler, st, type_table, node_builder): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("loop unrolling", Some(handler));
 
-    fn do_pass((ast, handler, st): Self::Input) -> Self::Output {
         // Reconstructs the AST based off any flattening work that is done.
-        let mut reconstructor = Self::new(st, handler);
+        let mut reconstructor = Self::new(st, type_table, handler, node_builder);
         let program = reconstructor.reconstruct_program(ast.into_repr());
         handler.last_err()?;
 
-        Ok((Ast::new(program), reconstructor.symbol_table.take()))
+        let ast = Ast::new(program);
+        let summary = recorder.finish(Some(handler));
+
+        Ok((
+            ast,
+            reconstructor.symbol_table.take(),
+            reconstructor.type_table,
+            summary,
+        ))
     }
 }
@@ -68,8 +68,9 @@ impl<I: LoopBound> Iterator for RangeIterator<I> {
             Some(value) => {
                 self.current = None;
                 match self.clusivity {
-                    Clusivity::Exclusive => None,
-                    Clusivity::Inclusive => Some(value),
+                    // The end of an inclusive range is only reached if the range is not empty.
+                    Clusivity::Inclusive if value == self.end => Some(value),
+                    _ => None,
                 }
             }
         }
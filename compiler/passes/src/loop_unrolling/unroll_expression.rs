@@ -20,4 +20,51 @@ use crate::Unroller;
 
 impl ExpressionReconstructor for Unroller<'_> {
     type AdditionalOutput = ();
+
+    fn reconstruct_access(&mut self, input: AccessExpression) -> (Expression, Self::AdditionalOutput) {
+        let access = match input {
+            AccessExpression::Array(access) => {
+                let array = self.reconstruct_expression(*access.array).0;
+                let index = self.reconstruct_expression(*access.index).0;
+                AccessExpression::Array(ArrayAccess {
+                    index: Box::new(self.resolve_array_index(&array, index)),
+                    array: Box::new(array),
+                    span: access.span,
+                    id: access.id,
+                })
+            }
+            AccessExpression::AssociatedFunction(function) => {
+                AccessExpression::AssociatedFunction(AssociatedFunction {
+                    args: function
+                        .args
+                        .into_iter()
+                        .map(|arg| self.reconstruct_expression(arg).0)
+                        .collect(),
+                    ..function
+                })
+            }
+            AccessExpression::Member(member) => AccessExpression::Member(MemberAccess {
+                inner: Box::new(self.reconstruct_expression(*member.inner).0),
+                ..member
+            }),
+            AccessExpression::Tuple(tuple) => AccessExpression::Tuple(TupleAccess {
+                tuple: Box::new(self.reconstruct_expression(*tuple.tuple).0),
+                ..tuple
+            }),
+            expr => expr,
+        };
+        (Expression::Access(access), Default::default())
+    }
+
+    fn reconstruct_identifier(&mut self, input: Identifier) -> (Expression, Self::AdditionalOutput) {
+        // Substitute the value of a program constant for its use.
+        match self.constants.get(&input.name) {
+            Some(value) => {
+                let mut literal = Literal::from((value.clone(), self.node_builder.next_id()));
+                literal.set_span(input.span);
+                (Expression::Literal(literal), Default::default())
+            }
+            None => (Expression::Identifier(input), Default::default()),
+        }
+    }
 }
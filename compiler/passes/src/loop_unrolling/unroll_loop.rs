@@ -0,0 +1,130 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{
+    Block, DeclarationType, DefinitionStatement, Expression, IntegerType, IterationStatement, Literal, Node,
+    NodeBuilder, Statement, StatementReconstructor, Type, Value,
+};
+use leo_errors::{FlattenError, Result};
+use leo_span::Span;
+
+use std::cell::RefCell;
+
+use crate::{Clusivity, LoopBound, NodeRefresher, RangeIterator, Unroller};
+
+/// The values that the variable of a loop takes, one per iteration.
+pub enum UnrollStrategy {
+    /// The integers from `start` to `stop`, for a loop over a range whose bounds evaluate to `start` and `stop`.
+    Range(Value, Value),
+    /// The given elements, in order, for a loop over an array.
+    Elements(Vec<Expression>),
+}
+
+/// Unrolls the loop `input` into a block per iteration, with the values of the loop variable given by `strategy`.
+/// Each block defines the loop variable as a constant equal to the value for its iteration, followed by the loop body.
+/// The blocks are passed through `unroller`, which, for example, unrolls the loops nested in the body.
+/// The nodes of each block, including the copy of the body, are given fresh ids, and their types are recorded in the type table of `unroller`.
+pub fn unroll_loop(
+    input: &IterationStatement,
+    strategy: UnrollStrategy,
+    unroller: &mut Unroller,
+) -> Result<Vec<Statement>> {
+    let node_builder = unroller.node_builder;
+    let values = match strategy {
+        UnrollStrategy::Range(start, stop) => iteration_counts(input, start, stop, node_builder)?
+            .into_iter()
+            .map(Expression::Literal)
+            .collect(),
+        UnrollStrategy::Elements(elements) => elements,
+    };
+
+    Ok(values
+        .into_iter()
+        .map(|value| {
+            // The first statement in the block is the assignment of the loop variable to the value for the current iteration.
+            let mut statements = vec![Statement::Definition(DefinitionStatement {
+                declaration_type: DeclarationType::Const,
+                type_: RefCell::new(Some(input.type_.clone())),
+                value,
+                span: Default::default(),
+                place: Expression::Identifier(input.variable),
+                id: node_builder.next_id(),
+            })];
+            statements.extend(input.block.statements.iter().cloned());
+
+            let block = Block {
+                statements,
+                span: input.block.span,
+                id: node_builder.next_id(),
+            };
+            let block = NodeRefresher::new(node_builder, &mut unroller.type_table)
+                .reconstruct_block(block)
+                .0;
+            Statement::Block(unroller.reconstruct_block(block).0)
+        })
+        .collect())
+}
+
+fn iteration_counts(
+    input: &IterationStatement,
+    start: Value,
+    stop: Value,
+    node_builder: &NodeBuilder,
+) -> Result<Vec<Literal>> {
+    let clusivity = match input.inclusive {
+        true => Clusivity::Inclusive,
+        false => Clusivity::Exclusive,
+    };
+    let span = input
+        .stop
+        .as_ref()
+        .map_or(input.start.span(), |stop| input.start.span() + stop.span());
+    match input.type_ {
+        Type::Integer(integer_type) if integer_type.is_signed() => {
+            range::<i128>(integer_type, start, stop, clusivity, span, node_builder)
+        }
+        Type::Integer(integer_type) => range::<u128>(integer_type, start, stop, clusivity, span, node_builder),
+        _ => unreachable!("The iteration variable must be an integer type. This should be enforced by type checking."),
+    }
+}
+
+/// Returns the values in the range from `start` to `stop` as literals of type `integer_type`.
+/// Bounds that are known during type checking are checked there, so this only reports reversed ranges whose bounds depend on an enclosing loop.
+fn range<I: LoopBound>(
+    integer_type: IntegerType,
+    start: Value,
+    stop: Value,
+    clusivity: Clusivity,
+    span: Span,
+    node_builder: &NodeBuilder,
+) -> Result<Vec<Literal>> {
+    // We already know the bounds are integers since loop unrolling occurs after type checking.
+    let start: I = start.try_into()?;
+    let stop: I = stop.try_into()?;
+    if start > stop {
+        return Err(FlattenError::loop_range_reversed(start, stop, span).into());
+    }
+    Ok(RangeIterator::new(start, stop, clusivity)
+        .map(|count| {
+            Literal::Integer(
+                integer_type,
+                count.to_string(),
+                Default::default(),
+                node_builder.next_id(),
+            )
+        })
+        .collect())
+}
@@ -19,6 +19,24 @@ use leo_ast::*;
 use crate::Unroller;
 
 impl ProgramReconstructor for Unroller<'_> {
+    fn reconstruct_program_scope(&mut self, input: ProgramScope) -> ProgramScope {
+        ProgramScope {
+            program_id: input.program_id,
+            // Program constants are substituted for their uses, so their declarations are no longer needed.
+            consts: Default::default(),
+            structs: input.structs,
+            enums: input.enums,
+            mappings: input.mappings,
+            functions: input
+                .functions
+                .into_iter()
+                .map(|(i, f)| (i, self.reconstruct_function(f)))
+                .collect(),
+            default_integer_type: input.default_integer_type,
+            span: input.span,
+        }
+    }
+
     fn reconstruct_function(&mut self, function: Function) -> Function {
         // Lookup function metadata in the symbol table.
         // Note that this unwrap is safe since function metadata is stored in a prior pass.
@@ -32,14 +50,16 @@ impl ProgramReconstructor for Unroller<'_> {
         // Enter the function's scope.
         let previous_function_index = self.enter_scope(function_index);
 
-        let previous_scope_index = self.enter_scope(self.scope_index);
+        let scope_index = self.current_scope_index();
+        let previous_scope_index = self.enter_scope(scope_index);
 
         let block = self.reconstruct_block(function.block).0;
 
         self.exit_scope(previous_scope_index);
 
         let finalize = function.finalize.map(|finalize| {
-            let previous_scope_index = self.enter_scope(self.scope_index);
+            let scope_index = self.current_scope_index();
+            let previous_scope_index = self.enter_scope(scope_index);
 
             let block = self.reconstruct_block(finalize.block).0;
 
@@ -52,6 +72,7 @@ impl ProgramReconstructor for Unroller<'_> {
                 output_type: finalize.output_type,
                 block,
                 span: finalize.span,
+                id: finalize.id,
             }
         });
 
@@ -60,12 +81,14 @@ impl ProgramReconstructor for Unroller<'_> {
             annotations: function.annotations,
             call_type: function.call_type,
             identifier: function.identifier,
+            type_parameters: function.type_parameters,
             input: function.input,
             output: function.output,
             output_type: function.output_type,
             block,
             finalize,
             span: function.span,
+            id: function.id,
         };
 
         // Exit the function's scope.
@@ -15,9 +15,10 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use leo_ast::*;
+use leo_errors::FlattenError;
 
 use crate::unroller::Unroller;
-use crate::{VariableSymbol, VariableType};
+use crate::{UnrollStrategy, VariableSymbol, VariableType};
 
 impl StatementReconstructor for Unroller<'_> {
     fn reconstruct_block(&mut self, input: Block) -> (Block, Self::AdditionalOutput) {
@@ -33,6 +34,7 @@ impl StatementReconstructor for Unroller<'_> {
                 .map(|s| self.reconstruct_statement(s).0)
                 .collect(),
             span: input.span,
+            id: input.id,
         };
 
         // Exit the block scope.
@@ -50,48 +52,93 @@ impl StatementReconstructor for Unroller<'_> {
                 VariableType::Mut
             };
 
-            if let Err(err) = self.symbol_table.borrow_mut().insert_variable(
-                input.variable_name.name,
-                VariableSymbol {
-                    type_: input.type_.clone(),
-                    span: input.span(),
-                    declaration,
-                },
-            ) {
-                self.handler.emit_err(err);
+            let insert_variable = |identifier: &Identifier, type_: &Type| {
+                if let Err(err) = self.symbol_table.borrow_mut().insert_variable(
+                    identifier.name,
+                    VariableSymbol {
+                        type_: type_.clone(),
+                        span: input.span(),
+                        declaration: declaration.clone(),
+                    },
+                ) {
+                    self.handler.emit_err(err);
+                }
+            };
+
+            // Note that type checking guarantees that the place is an identifier or a tuple of identifiers with a matching tuple type,
+            // and that the types of unannotated definitions have been inferred.
+            match (&input.place, input.type_()) {
+                (Expression::Identifier(identifier), Some(type_)) => {
+                    insert_variable(identifier, &type_);
+
+                    // Record the value of the constant, since it may be used in the bounds of a nested loop.
+                    if input.declaration_type == DeclarationType::Const {
+                        if let Some(value) = self.evaluate_constant(&input.value) {
+                            self.symbol_table.borrow_mut().insert_constant(identifier.name, value);
+                        }
+                    }
+                }
+                (Expression::Tuple(tuple), Some(Type::Tuple(tuple_type))) => {
+                    for (element, type_) in tuple.elements.iter().zip(tuple_type.iter()) {
+                        if let Expression::Identifier(identifier) = element {
+                            insert_variable(identifier, type_);
+                        }
+                    }
+                }
+                _ => unreachable!("Type checking guarantees that the place of a definition is well-formed."),
             }
         }
-        (Statement::Definition(input), Default::default())
+        (
+            Statement::Definition(DefinitionStatement {
+                value: self.reconstruct_expression(input.value).0,
+                ..input
+            }),
+            Default::default(),
+        )
     }
 
     fn reconstruct_iteration(&mut self, input: IterationStatement) -> (Statement, Self::AdditionalOutput) {
-        // We match on start and stop cause loops require
-        // bounds to be constants.
-        match (
-            input.start_value.clone().into_inner(),
-            input.stop_value.clone().into_inner(),
-        ) {
-            (Some(start), Some(stop)) => match (Type::from(&start), Type::from(&stop)) {
-                (Type::Integer(IntegerType::I8), Type::Integer(IntegerType::I8))
-                | (Type::Integer(IntegerType::I16), Type::Integer(IntegerType::I16))
-                | (Type::Integer(IntegerType::I32), Type::Integer(IntegerType::I32))
-                | (Type::Integer(IntegerType::I64), Type::Integer(IntegerType::I64))
-                | (Type::Integer(IntegerType::I128), Type::Integer(IntegerType::I128)) => (
-                    self.unroll_iteration_statement::<i128>(input, start, stop),
-                    Default::default(),
-                ),
-                (Type::Integer(IntegerType::U8), Type::Integer(IntegerType::U8))
-                | (Type::Integer(IntegerType::U16), Type::Integer(IntegerType::U16))
-                | (Type::Integer(IntegerType::U32), Type::Integer(IntegerType::U32))
-                | (Type::Integer(IntegerType::U64), Type::Integer(IntegerType::U64))
-                | (Type::Integer(IntegerType::U128), Type::Integer(IntegerType::U128)) => (
-                    self.unroll_iteration_statement::<u128>(input, start, stop),
-                    Default::default(),
-                ),
-                _ => unreachable!("Type checking ensures that `start` and `stop` have the same type."),
+        let strategy = match &input.stop {
+            Some(stop) => {
+                // Bounds that could not be evaluated during type checking may depend on the variables of an enclosing loop,
+                // whose values are only known once that loop is unrolled.
+                let start_value = input
+                    .start_value
+                    .clone()
+                    .into_inner()
+                    .or_else(|| self.evaluate_constant(&input.start));
+                let stop_value = input
+                    .stop_value
+                    .clone()
+                    .into_inner()
+                    .or_else(|| self.evaluate_constant(stop));
+
+                // We match on start and stop cause loops require
+                // bounds to be constants.
+                match (start_value, stop_value) {
+                    (Some(start), Some(stop)) => UnrollStrategy::Range(start, stop),
+                    // If either loop bound is not constant, then the loop cannot be unrolled.
+                    (start, _) => {
+                        let bound = if start.is_none() { &input.start } else { stop };
+                        self.handler
+                            .emit_err(FlattenError::loop_bound_not_constant(bound, bound.span()));
+                        return (Statement::Iteration(Box::from(input)), Default::default());
+                    }
+                }
+            }
+            // A loop over an array is unrolled into an iteration per element.
+            None => match self.array_elements(&input.start) {
+                Some(elements) => UnrollStrategy::Elements(elements),
+                None => {
+                    self.handler.emit_err(FlattenError::loop_array_length_unknown(
+                        &input.start,
+                        input.start.span(),
+                    ));
+                    return (Statement::Iteration(Box::from(input)), Default::default());
+                }
             },
-            // If both loop bounds are not constant, then the loop is not unrolled.
-            _ => (Statement::Iteration(Box::from(input)), Default::default()),
-        }
+        };
+
+        (self.unroll_iteration_statement(input, strategy), Default::default())
     }
 }
@@ -15,93 +15,188 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use leo_ast::{
-    Block, DeclarationType, DefinitionStatement, Expression, IntegerType, IterationStatement, Literal, Statement,
-    StatementReconstructor, Type, Value,
+    AccessExpression, ArrayAccess, Block, Expression, IntegerType, IterationStatement, Literal, Node, NodeBuilder,
+    Statement, Type, Value,
 };
 use std::cell::RefCell;
 
-use leo_errors::emitter::Handler;
+use leo_errors::{emitter::Handler, FlattenError};
+use leo_span::Symbol;
 
-use crate::{Clusivity, LoopBound, RangeIterator, SymbolTable};
+use indexmap::IndexMap;
+
+use crate::{unroll_loop, ConstEvaluator, ScopeIndex, SymbolTable, TypeTable, UnrollStrategy};
 
 pub struct Unroller<'a> {
     /// The symbol table for the function being processed.
     pub(crate) symbol_table: RefCell<SymbolTable>,
-    /// The index of the current scope.
+    /// The types of the expressions and variables, to which the types of the copies of loop bodies are added.
+    pub(crate) type_table: TypeTable,
+    /// The number of sub-scopes of the current scope that have been traversed.
     pub(crate) scope_index: usize,
     /// An error handler used for any errors found during unrolling.
     pub(crate) handler: &'a Handler,
     /// Are we in the midst of unrolling a loop?
     pub(crate) is_unrolling: bool,
+    /// The values of the program constants, which are substituted for their uses.
+    pub(crate) constants: IndexMap<Symbol, Value>,
+    /// A counter used to generate unique ids for the nodes that the pass creates.
+    pub(crate) node_builder: &'a NodeBuilder,
 }
 
 impl<'a> Unroller<'a> {
-    pub(crate) fn new(symbol_table: SymbolTable, handler: &'a Handler) -> Self {
+    /// Returns a new unroller for a program whose symbol table is `symbol_table` and whose type table is `type_table`.
+    pub fn new(
+        symbol_table: SymbolTable,
+        type_table: TypeTable,
+        handler: &'a Handler,
+        node_builder: &'a NodeBuilder,
+    ) -> Self {
         Self {
+            constants: symbol_table.scope(ScopeIndex::PROGRAM).constants.clone(),
             symbol_table: RefCell::new(symbol_table),
+            type_table,
             scope_index: 0,
             handler,
             is_unrolling: false,
+            node_builder,
         }
     }
 
-    /// Returns the index of the current scope.
+    /// Returns the index of the next sub-scope of the current scope, which type checking created in traversal order.
     /// Note that if we are in the midst of unrolling an IterationStatement, a new scope is created.
-    pub(crate) fn current_scope_index(&mut self) -> usize {
+    pub(crate) fn current_scope_index(&mut self) -> ScopeIndex {
         if self.is_unrolling {
             self.symbol_table.borrow_mut().insert_block()
         } else {
-            self.scope_index
+            self.symbol_table.borrow().child_scope(self.scope_index).unwrap()
         }
     }
 
-    /// Enters a child scope.
-    pub(crate) fn enter_scope(&mut self, index: usize) -> usize {
-        let previous_symbol_table = std::mem::take(&mut self.symbol_table);
-        self.symbol_table
-            .swap(previous_symbol_table.borrow().lookup_scope_by_index(index).unwrap());
-        self.symbol_table.borrow_mut().parent = Some(Box::new(previous_symbol_table.into_inner()));
+    /// Enters a child scope, returning the number of sub-scopes of the previous scope that have been traversed.
+    pub(crate) fn enter_scope(&mut self, index: ScopeIndex) -> usize {
+        self.symbol_table.borrow_mut().enter_scope(index);
         core::mem::replace(&mut self.scope_index, 0)
     }
 
     /// Exits the current block scope.
     pub(crate) fn exit_scope(&mut self, index: usize) {
-        let prev_st = *self.symbol_table.borrow_mut().parent.take().unwrap();
-        self.symbol_table.swap(prev_st.lookup_scope_by_index(index).unwrap());
-        self.symbol_table = RefCell::new(prev_st);
+        self.symbol_table.borrow_mut().exit_scope();
         self.scope_index = index + 1;
     }
 
-    /// Unrolls an IterationStatement.
-    pub(crate) fn unroll_iteration_statement<I: LoopBound>(
-        &mut self,
-        input: IterationStatement,
-        start: Value,
-        stop: Value,
-    ) -> Statement {
-        // Closure to check that the constant values are valid u128.
-        // We already know these are integers since loop unrolling occurs after type checking.
-        let cast_to_number = |v: Value| -> Result<I, Statement> {
-            match v.try_into() {
-                Ok(val_as_u128) => Ok(val_as_u128),
-                Err(err) => {
-                    self.handler.emit_err(err);
-                    Err(Statement::dummy(input.span))
-                }
+    /// Evaluates an expression whose value may be known at compile time, e.g. a loop bound.
+    pub(crate) fn evaluate_constant(&self, expression: &Expression) -> Option<Value> {
+        match ConstEvaluator::new(&self.symbol_table.borrow()).evaluate(expression) {
+            Ok(value) => value,
+            Err(err) => {
+                self.handler.emit_err(err);
+                None
             }
+        }
+    }
+
+    /// Returns the elements of an array that a loop iterates over, if its length is known.
+    /// The elements of an array expression are used directly, and the elements of other arrays are accessed by their index.
+    pub(crate) fn array_elements(&self, array: &Expression) -> Option<Vec<Expression>> {
+        if let Expression::Array(array) = array {
+            return Some(array.elements.clone());
+        }
+        let length = match self.array_type(array)? {
+            Type::Array(_, length) => length.get(),
+            _ => return None,
         };
+        Some(
+            (0..length)
+                .map(|index| {
+                    Expression::Access(AccessExpression::Array(ArrayAccess {
+                        array: Box::new(array.clone()),
+                        index: Box::new(Expression::Literal(Literal::Integer(
+                            IntegerType::U32,
+                            index.to_string(),
+                            Default::default(),
+                            self.node_builder.next_id(),
+                        ))),
+                        span: array.span(),
+                        id: self.node_builder.next_id(),
+                    }))
+                })
+                .collect(),
+        )
+    }
 
-        // Cast `start` to `I`.
-        let start = match cast_to_number(start) {
-            Ok(v) => v,
-            Err(s) => return s,
+    /// Resolves the index of an access to `array` into an integer literal, since arrays are lowered into their elements.
+    /// Indices that are not literals, e.g. loop variables and constants, are only known once the loops around them are unrolled,
+    /// so they are evaluated and checked against the length of the array here.
+    pub(crate) fn resolve_array_index(&self, array: &Expression, index: Expression) -> Expression {
+        if matches!(index, Expression::Literal(_)) {
+            return index;
+        }
+        let value = match self.evaluate_constant(&index) {
+            Some(value) => value,
+            None => {
+                self.handler
+                    .emit_err(FlattenError::array_index_not_constant(&index, index.span()));
+                return index;
+            }
         };
-        // Cast `stop` to `I`.
-        let stop = match cast_to_number(stop) {
-            Ok(v) => v,
-            Err(s) => return s,
+
+        let length = match array {
+            Expression::Array(array) => Some(array.elements.len()),
+            _ => match self.array_type(array) {
+                Some(Type::Array(_, length)) => Some(length.get()),
+                _ => None,
+            },
         };
+        // Note that type checking guarantees that the index is an unsigned integer.
+        let position = u128::try_from(&value)
+            .ok()
+            .and_then(|value| usize::try_from(value).ok());
+        if let Some(length) = length {
+            if !matches!(position, Some(position) if position < length) {
+                self.handler
+                    .emit_err(FlattenError::array_index_out_of_range(&value, length, index.span()));
+            }
+        }
+
+        let mut literal = Literal::from((value, self.node_builder.next_id()));
+        literal.set_span(index.span());
+        Expression::Literal(literal)
+    }
 
+    /// Returns the type of an array variable, of an element of an array of arrays, or of an array member of a struct.
+    fn array_type(&self, array: &Expression) -> Option<Type> {
+        match array {
+            Expression::Identifier(identifier) => self
+                .symbol_table
+                .borrow()
+                .lookup_variable(identifier.name)
+                .map(|variable| variable.type_.clone()),
+            Expression::Access(AccessExpression::Array(access)) => match self.array_type(&access.array)? {
+                Type::Array(element_type, _) => Some(*element_type),
+                _ => None,
+            },
+            Expression::Access(AccessExpression::Member(access)) => match self.array_type(&access.inner)? {
+                Type::Identifier(struct_) => self
+                    .symbol_table
+                    .borrow()
+                    .lookup_struct(struct_.name)?
+                    .members
+                    .iter()
+                    .find(|member| member.name() == access.name.name)
+                    .map(|member| member.type_.clone()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Unrolls an IterationStatement.
+    pub(crate) fn unroll_iteration_statement(
+        &mut self,
+        input: IterationStatement,
+        strategy: UnrollStrategy,
+    ) -> Statement {
         // Get the index of the current scope.
         let scope_index = self.current_scope_index();
 
@@ -110,107 +205,39 @@ impl<'a> Unroller<'a> {
 
         // Clear the symbol table for the loop body.
         // This is necessary because loop unrolling transforms the program, which requires reconstructing the symbol table.
-        self.symbol_table.borrow_mut().variables.clear();
-        self.symbol_table.borrow_mut().scopes.clear();
-        self.symbol_table.borrow_mut().scope_index = 0;
-
-        // Create a block statement to replace the iteration statement.
-        // Creates a new block per iteration inside the outer block statement.
-        let iter_blocks = Statement::Block(Block {
-            span: input.span,
-            statements: match input.inclusive {
-                true => {
-                    let iter = RangeIterator::new(start, stop, Clusivity::Inclusive);
-                    iter.map(|iteration_count| self.unroll_single_iteration(&input, iteration_count))
-                        .collect()
-                }
-                false => {
-                    let iter = RangeIterator::new(start, stop, Clusivity::Exclusive);
-                    iter.map(|iteration_count| self.unroll_single_iteration(&input, iteration_count))
-                        .collect()
-                }
-            },
-        });
-
-        // Exit the scope of the loop body.
-        self.exit_scope(previous_scope_index);
-
-        iter_blocks
-    }
-
-    /// A helper function to unroll a single iteration an IterationStatement.
-    fn unroll_single_iteration<I: LoopBound>(&mut self, input: &IterationStatement, iteration_count: I) -> Statement {
-        // Create a scope for a single unrolling of the `IterationStatement`.
-        let scope_index = self.symbol_table.borrow_mut().insert_block();
-        let previous_scope_index = self.enter_scope(scope_index);
+        // The previous sub-scopes of the loop body remain in the symbol table, but are no longer reachable.
+        {
+            let mut symbol_table = self.symbol_table.borrow_mut();
+            let scope = symbol_table.current_scope_mut();
+            scope.variables.clear();
+            scope.constants.clear();
+            scope.children.clear();
+        }
 
+        // Each iteration is reconstructed as a block, which creates its own scope while unrolling.
         let prior_is_unrolling = self.is_unrolling;
         self.is_unrolling = true;
 
-        // Reconstruct `iteration_count` as a `Literal`.
-        let value = match input.type_ {
-            Type::Integer(IntegerType::I8) => {
-                Literal::Integer(IntegerType::I8, iteration_count.to_string(), Default::default())
-            }
-            Type::Integer(IntegerType::I16) => {
-                Literal::Integer(IntegerType::I16, iteration_count.to_string(), Default::default())
-            }
-            Type::Integer(IntegerType::I32) => {
-                Literal::Integer(IntegerType::I32, iteration_count.to_string(), Default::default())
-            }
-            Type::Integer(IntegerType::I64) => {
-                Literal::Integer(IntegerType::I64, iteration_count.to_string(), Default::default())
-            }
-            Type::Integer(IntegerType::I128) => {
-                Literal::Integer(IntegerType::I128, iteration_count.to_string(), Default::default())
-            }
-            Type::Integer(IntegerType::U8) => {
-                Literal::Integer(IntegerType::U8, iteration_count.to_string(), Default::default())
-            }
-            Type::Integer(IntegerType::U16) => {
-                Literal::Integer(IntegerType::U16, iteration_count.to_string(), Default::default())
-            }
-            Type::Integer(IntegerType::U32) => {
-                Literal::Integer(IntegerType::U32, iteration_count.to_string(), Default::default())
-            }
-            Type::Integer(IntegerType::U64) => {
-                Literal::Integer(IntegerType::U64, iteration_count.to_string(), Default::default())
-            }
-            Type::Integer(IntegerType::U128) => {
-                Literal::Integer(IntegerType::U128, iteration_count.to_string(), Default::default())
+        // Create a block statement to replace the iteration statement.
+        // Creates a new block per iteration inside the outer block statement.
+        let span = input.span;
+        let iter_blocks = match unroll_loop(&input, strategy, self) {
+            Ok(statements) => Statement::Block(Block {
+                span,
+                statements,
+                id: self.node_builder.next_id(),
+            }),
+            Err(err) => {
+                self.handler.emit_err(err);
+                Statement::dummy(span, self.node_builder.next_id())
             }
-            _ => unreachable!(
-                "The iteration variable must be an integer type. This should be enforced by type checking."
-            ),
         };
 
-        // The first statement in the block is the assignment of the loop variable to the current iteration count.
-        let mut statements = vec![
-            self.reconstruct_definition(DefinitionStatement {
-                declaration_type: DeclarationType::Const,
-                type_: input.type_.clone(),
-                value: Expression::Literal(value),
-                span: Default::default(),
-                variable_name: input.variable,
-            })
-            .0,
-        ];
-
-        // Reconstruct the statements in the loop body.
-        input.block.statements.clone().into_iter().for_each(|s| {
-            statements.push(self.reconstruct_statement(s).0);
-        });
-
-        let block = Statement::Block(Block {
-            statements,
-            span: input.block.span,
-        });
-
         self.is_unrolling = prior_is_unrolling;
 
-        // Exit the scope.
+        // Exit the scope of the loop body.
         self.exit_scope(previous_scope_index);
 
-        block
+        iter_blocks
     }
 }
@@ -0,0 +1,88 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The monomorphization pass traverses the AST after type checking and replaces each generic function with an instance for each list of types that it is called with.
+//! Type checking infers the types that the type parameters of each call are instantiated with, and records them in the call.
+//! Each instance is a copy of the generic function whose type parameters are replaced by these types, and whose name is derived from them.
+//! The nodes of each instance are given fresh ids, so that the instances of a function do not share nodes, and their types in the type table are those of the instance.
+//! Calls in generic functions may themselves depend on type parameters, so their instances are constructed as the instances that contain them are.
+//! Generic functions that are never called have no instances, and are removed from the program.
+//! Likewise, each generic struct is replaced by an instance for each list of const arguments that it is used with, e.g. `Buffer<4>` by `Buffer_4`.
+//! Type checking has validated the const arguments, and named arguments are replaced by the values of the constants that they name.
+//! Array sizes that name program constants are replaced by the values of the constants, which type checking has evaluated.
+//!
+//! Consider the following Leo code.
+//! ```leo
+//! inline max<T: integer>(a: T, b: T) -> T {
+//!     return a > b ? a : b;
+//! }
+//!
+//! transition main(a: u8, b: u8, c: u32, d: u32) -> (u8, u32) {
+//!     return (max(a, b), max(c, d));
+//! }
+//! ```
+//!
+//! The monomorphization pass produces the following code.
+//! ```leo
+//! inline max_u8(a: u8, b: u8) -> u8 {
+//!     return a > b ? a : b;
+//! }
+//!
+//! inline max_u32(a: u32, b: u32) -> u32 {
+//!     return a > b ? a : b;
+//! }
+//!
+//! transition main(a: u8, b: u8, c: u32, d: u32) -> (u8, u32) {
+//!     return (max_u8(a, b), max_u32(c, d));
+//! }
+//! ```
+
+mod monomorphize_expression;
+
+mod monomorphize_program;
+
+mod monomorphize_statement;
+
+pub mod monomorphizer;
+pub use monomorphizer::*;
+
+use crate::{CallGraph, Pass, PassSummary, StructGraph, SymbolTable, TypeTable};
+
+use leo_ast::{Ast, NodeBuilder, ProgramReconstructor};
+use leo_errors::Result;
+
+impl<'a> Pass for Monomorphizer<'a> {
+    type Input = (Ast, SymbolTable, CallGraph, StructGraph, TypeTable, &'a NodeBuilder);
+    type Output = Result<(Ast, SymbolTable, CallGraph, StructGraph, TypeTable, PassSummary)>;
+
+    fn do_pass((ast, symbol_table, call_graph, struct_graph, type_table, node_builder): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("monomorphization", None);
+        let mut reconstructor = Monomorphizer::new(symbol_table, call_graph, struct_graph, type_table, node_builder);
+        let program = reconstructor.reconstruct_program(ast.into_repr());
+
+        let ast = Ast::new(program);
+        let summary = recorder.finish(None);
+
+        Ok((
+            ast,
+            reconstructor.symbol_table,
+            reconstructor.call_graph,
+            reconstructor.struct_graph,
+            reconstructor.type_table,
+            summary,
+        ))
+    }
+}
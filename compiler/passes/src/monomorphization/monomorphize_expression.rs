@@ -0,0 +1,120 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Monomorphizer;
+
+use leo_ast::{
+    CallExpression, CastExpression, Expression, ExpressionReconstructor, Identifier, StructExpression,
+    StructVariableInitializer, Type,
+};
+
+impl ExpressionReconstructor for Monomorphizer<'_> {
+    type AdditionalOutput = ();
+
+    /// Replaces a call to a generic function with a call to its instance for the call's type arguments.
+    fn reconstruct_call(&mut self, input: CallExpression) -> (Expression, Self::AdditionalOutput) {
+        // Note that the type arguments of a call in a generic function may themselves be type parameters.
+        let type_arguments: Vec<Type> = input
+            .type_arguments
+            .into_inner()
+            .iter()
+            .map(|type_| type_.substitute(&self.substitution))
+            .collect();
+
+        // Note that the parser guarantees that `input.function` is always an identifier.
+        let function = match *input.function {
+            Expression::Identifier(identifier) if !type_arguments.is_empty() => Identifier {
+                name: self.instance_name(identifier.name, type_arguments),
+                span: identifier.span,
+                id: identifier.id,
+            },
+            Expression::Identifier(identifier) => identifier,
+            _ => unreachable!("Parser guarantees that `input.function` is always an identifier."),
+        };
+
+        // Add the call to the call graph, since either the caller or the callee may be an instance.
+        if let (Some(caller), None) = (self.function, &input.external) {
+            self.call_graph.add_edge(caller, function.name);
+        }
+
+        (
+            Expression::Call(CallExpression {
+                function: Box::new(Expression::Identifier(function)),
+                arguments: input
+                    .arguments
+                    .into_iter()
+                    .map(|argument| self.reconstruct_expression(argument).0)
+                    .collect(),
+                type_arguments: Default::default(),
+                external: input.external,
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    /// Replaces the type parameters and named sizes in the type that the value is cast to.
+    fn reconstruct_cast(&mut self, input: CastExpression) -> (Expression, Self::AdditionalOutput) {
+        (
+            Expression::Cast(CastExpression {
+                expression: Box::new(self.reconstruct_expression(*input.expression).0),
+                type_: self.monomorphize_type(&input.type_),
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    /// Replaces the initialization of an instance of a generic struct with the initialization of the struct that instantiates it.
+    fn reconstruct_struct_init(&mut self, input: StructExpression) -> (Expression, Self::AdditionalOutput) {
+        let name = match input.const_arguments.is_empty() {
+            true => input.name,
+            false => {
+                let const_arguments = input
+                    .const_arguments
+                    .iter()
+                    .map(|argument| argument.substitute(&self.sizes).get())
+                    .collect();
+                Identifier {
+                    name: self.struct_instance_name(input.name.name, const_arguments),
+                    ..input.name
+                }
+            }
+        };
+
+        (
+            Expression::Struct(StructExpression {
+                name,
+                const_arguments: Vec::new(),
+                members: input
+                    .members
+                    .into_iter()
+                    .map(|member| StructVariableInitializer {
+                        identifier: member.identifier,
+                        expression: member
+                            .expression
+                            .map(|expression| self.reconstruct_expression(expression).0),
+                    })
+                    .collect(),
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+}
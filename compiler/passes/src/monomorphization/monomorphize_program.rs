@@ -0,0 +1,313 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Monomorphizer, NodeRefresher};
+
+use leo_ast::{
+    ConstDeclaration, ExpressionReconstructor, Finalize, Function, FunctionInput, FunctionOutput, Identifier, Input,
+    Mapping, Member, Output, ProgramReconstructor, ProgramScope, Size, StatementReconstructor, Struct, Type,
+};
+
+use indexmap::IndexMap;
+use std::num::NonZeroUsize;
+
+impl ProgramReconstructor for Monomorphizer<'_> {
+    /// Replaces each generic function and generic struct with its instances, in the position of the generic definition.
+    /// Named sizes are replaced by the values of the program constants that they name, which type checking recorded.
+    fn reconstruct_program_scope(&mut self, input: ProgramScope) -> ProgramScope {
+        self.sizes = input
+            .consts
+            .keys()
+            .filter_map(|identifier| {
+                let size = self.symbol_table.lookup_constant(identifier.name)?.to_size()?;
+                Some((identifier.name, size))
+            })
+            .collect();
+
+        self.declared = input
+            .structs
+            .keys()
+            .chain(input.mappings.keys())
+            .chain(input.functions.keys())
+            .map(|identifier| identifier.name)
+            .collect();
+        self.generic_structs = input
+            .structs
+            .values()
+            .filter(|struct_| struct_.is_generic())
+            .map(|struct_| (struct_.name(), struct_.clone()))
+            .collect();
+        self.generic_functions = input
+            .functions
+            .values()
+            .filter(|function| function.is_generic())
+            .map(|function| (function.name(), function.clone()))
+            .collect();
+
+        let consts = input
+            .consts
+            .into_iter()
+            .map(|(identifier, const_)| (identifier, self.reconstruct_const(const_)))
+            .collect();
+        let mut reconstructed_structs = IndexMap::new();
+        for (identifier, struct_) in input.structs.iter() {
+            if !struct_.is_generic() {
+                reconstructed_structs.insert(identifier.name, self.reconstruct_struct(struct_.clone()));
+            }
+        }
+        let mappings = input
+            .mappings
+            .into_iter()
+            .map(|(identifier, mapping)| (identifier, self.reconstruct_mapping(mapping)))
+            .collect();
+
+        let mut reconstructed_functions = IndexMap::new();
+        for (identifier, function) in input.functions.iter() {
+            if !function.is_generic() {
+                reconstructed_functions.insert(identifier.name, self.reconstruct_function(function.clone()));
+            }
+        }
+
+        // Construct the instances of the generic functions.
+        // Note that constructing an instance may name further instances, which are appended to `self.instances`.
+        let mut instances: IndexMap<_, Vec<_>> = IndexMap::new();
+        let mut index = 0;
+        while let Some(((generic, type_arguments), name)) = self.instances.get_index(index) {
+            let (generic, type_arguments, name) = (*generic, type_arguments.clone(), *name);
+            let function = self.generic_functions[&generic].clone();
+            self.substitution = function
+                .type_parameters
+                .iter()
+                .map(|parameter| parameter.identifier.name)
+                .zip(type_arguments)
+                .collect();
+            // Each instance is a copy of the generic function, so its nodes are given fresh ids,
+            // whose types are those of the original nodes with the type parameters replaced.
+            let function =
+                NodeRefresher::with_substitution(self.node_builder, &mut self.type_table, &self.substitution)
+                    .reconstruct_function(function);
+            let instance = self.reconstruct_function(Function {
+                identifier: Identifier {
+                    name,
+                    ..function.identifier
+                },
+                type_parameters: Vec::new(),
+                ..function
+            });
+            self.substitution.clear();
+
+            self.symbol_table.insert_fn_instance(generic, &instance);
+            instances.entry(generic).or_default().push(instance);
+            index += 1;
+        }
+
+        // Generic functions are replaced by their instances.
+        for generic in self.generic_functions.keys() {
+            self.call_graph.remove_node(*generic);
+        }
+        let mut functions = IndexMap::new();
+        for (identifier, _) in input.functions.iter() {
+            match reconstructed_functions.remove(&identifier.name) {
+                Some(function) => {
+                    functions.insert(*identifier, function);
+                }
+                None => {
+                    for instance in instances.remove(&identifier.name).unwrap_or_default() {
+                        functions.insert(instance.identifier, instance);
+                    }
+                }
+            }
+        }
+
+        // Construct the instances of the generic structs, which the functions and structs have named.
+        // Note that the members of an instance may name further instances, which are appended to `self.struct_instances`.
+        let mut struct_instances: IndexMap<_, Vec<_>> = IndexMap::new();
+        let mut index = 0;
+        while let Some(((generic, const_arguments), name)) = self.struct_instances.get_index(index) {
+            let (generic, const_arguments, name) = (*generic, const_arguments.clone(), *name);
+            let struct_ = self.generic_structs[&generic].clone();
+            let const_arguments = const_arguments
+                .into_iter()
+                .filter_map(NonZeroUsize::new)
+                .map(Size::Literal)
+                .collect::<Vec<_>>();
+            // Each instance is a copy of the generic struct, so its nodes are given fresh ids.
+            let instance = self.reconstruct_struct(Struct {
+                identifier: Identifier {
+                    name,
+                    span: struct_.identifier.span,
+                    id: self.node_builder.next_id(),
+                },
+                const_parameters: Vec::new(),
+                members: struct_
+                    .instantiate_members(&const_arguments)
+                    .into_iter()
+                    .map(|member| Member {
+                        identifier: Identifier {
+                            id: self.node_builder.next_id(),
+                            ..member.identifier
+                        },
+                        ..member
+                    })
+                    .collect(),
+                id: self.node_builder.next_id(),
+                ..struct_
+            });
+
+            self.symbol_table.insert_struct_instance(&instance);
+            struct_instances.entry(generic).or_default().push(instance);
+            index += 1;
+        }
+
+        // Generic structs are replaced by their instances, and the struct graph is updated to match.
+        for generic in self.generic_structs.keys() {
+            self.struct_graph.remove_node(*generic);
+        }
+        let mut structs = IndexMap::new();
+        for (identifier, _) in input.structs.iter() {
+            match reconstructed_structs.remove(&identifier.name) {
+                Some(struct_) => {
+                    structs.insert(*identifier, struct_);
+                }
+                None => {
+                    for instance in struct_instances.remove(&identifier.name).unwrap_or_default() {
+                        structs.insert(instance.identifier, instance);
+                    }
+                }
+            }
+        }
+        for struct_ in structs.values() {
+            self.struct_graph.add_node(struct_.name());
+            for member in struct_.members.iter() {
+                if let Type::Identifier(member_type) = member.type_ {
+                    if self.symbol_table.lookup_struct(member_type.name).is_some() {
+                        self.struct_graph.add_edge(struct_.name(), member_type.name);
+                    }
+                }
+            }
+        }
+
+        self.replace_struct_instances_in_tables();
+
+        ProgramScope {
+            consts,
+            structs,
+            mappings,
+            functions,
+            ..input
+        }
+    }
+
+    /// Replaces the named sizes in the type of the constant.
+    fn reconstruct_const(&mut self, input: ConstDeclaration) -> ConstDeclaration {
+        ConstDeclaration {
+            identifier: input.identifier,
+            type_: self.monomorphize_type(&input.type_),
+            value: self.reconstruct_expression(input.value).0,
+            span: input.span,
+            id: input.id,
+        }
+    }
+
+    /// Replaces the named sizes and instances of generic structs in the types of the struct's members.
+    fn reconstruct_struct(&mut self, input: Struct) -> Struct {
+        Struct {
+            members: input
+                .members
+                .into_iter()
+                .map(|member| Member {
+                    type_: self.monomorphize_type(&member.type_),
+                    ..member
+                })
+                .collect(),
+            ..input
+        }
+    }
+
+    /// Replaces the instances of generic structs in the key and value types of the mapping.
+    fn reconstruct_mapping(&mut self, input: Mapping) -> Mapping {
+        Mapping {
+            key_type: self.monomorphize_type(&input.key_type),
+            value_type: self.monomorphize_type(&input.value_type),
+            ..input
+        }
+    }
+
+    /// Replaces the type parameters and named sizes in the types of the function's inputs and outputs, and reconstructs its body.
+    fn reconstruct_function(&mut self, input: Function) -> Function {
+        self.function = Some(input.name());
+
+        Function {
+            annotations: input.annotations,
+            call_type: input.call_type,
+            identifier: input.identifier,
+            type_parameters: input.type_parameters,
+            input: input
+                .input
+                .into_iter()
+                .map(|input| match input {
+                    Input::Internal(input) => Input::Internal(FunctionInput {
+                        type_: self.monomorphize_type(&input.type_),
+                        ..input
+                    }),
+                    input => input,
+                })
+                .collect(),
+            output: input
+                .output
+                .into_iter()
+                .map(|output| match output {
+                    Output::Internal(output) => Output::Internal(FunctionOutput {
+                        type_: self.monomorphize_type(&output.type_),
+                        ..output
+                    }),
+                    output => output,
+                })
+                .collect(),
+            output_type: self.monomorphize_type(&input.output_type),
+            block: self.reconstruct_block(input.block).0,
+            finalize: input.finalize.map(|finalize| Finalize {
+                input: finalize
+                    .input
+                    .into_iter()
+                    .map(|input| match input {
+                        Input::Internal(input) => Input::Internal(FunctionInput {
+                            type_: self.monomorphize_type(&input.type_),
+                            ..input
+                        }),
+                        input => input,
+                    })
+                    .collect(),
+                output: finalize
+                    .output
+                    .into_iter()
+                    .map(|output| match output {
+                        Output::Internal(output) => Output::Internal(FunctionOutput {
+                            type_: self.monomorphize_type(&output.type_),
+                            ..output
+                        }),
+                        output => output,
+                    })
+                    .collect(),
+                output_type: self.monomorphize_type(&finalize.output_type),
+                block: self.reconstruct_block(finalize.block).0,
+                ..finalize
+            }),
+            span: input.span,
+            id: input.id,
+        }
+    }
+}
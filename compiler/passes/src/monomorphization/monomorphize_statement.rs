@@ -0,0 +1,58 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Monomorphizer;
+
+use leo_ast::{DefinitionStatement, ExpressionReconstructor, IterationStatement, Statement, StatementReconstructor};
+
+use std::cell::RefCell;
+
+impl StatementReconstructor for Monomorphizer<'_> {
+    /// Replaces the type parameters and named sizes in the types of the bindings.
+    fn reconstruct_definition(&mut self, input: DefinitionStatement) -> (Statement, Self::AdditionalOutput) {
+        let type_ = input.type_().map(|type_| self.monomorphize_type(&type_));
+        (
+            Statement::Definition(DefinitionStatement {
+                declaration_type: input.declaration_type,
+                place: input.place,
+                type_: RefCell::new(type_),
+                value: self.reconstruct_expression(input.value).0,
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+
+    /// Replaces the type parameters and named sizes in the type of the iteration variable.
+    fn reconstruct_iteration(&mut self, input: IterationStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Iteration(Box::new(IterationStatement {
+                variable: input.variable,
+                type_: self.monomorphize_type(&input.type_),
+                start: self.reconstruct_expression(input.start).0,
+                start_value: input.start_value,
+                stop: input.stop.map(|stop| self.reconstruct_expression(stop).0),
+                stop_value: input.stop_value,
+                block: self.reconstruct_block(input.block).0,
+                inclusive: input.inclusive,
+                span: input.span,
+                id: input.id,
+            })),
+            Default::default(),
+        )
+    }
+}
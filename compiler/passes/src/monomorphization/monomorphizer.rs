@@ -0,0 +1,177 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{CallGraph, StructGraph, SymbolTable, TypeTable};
+
+use leo_ast::{Function, Identifier, MappingType, NodeBuilder, Size, Struct, StructInstance, Tuple, Type};
+use leo_span::Symbol;
+
+use indexmap::{IndexMap, IndexSet};
+use itertools::Itertools;
+use std::num::NonZeroUsize;
+
+pub struct Monomorphizer<'a> {
+    /// The symbol table, to which the instances of generic functions are added.
+    pub(crate) symbol_table: SymbolTable,
+    /// The call graph, in which generic functions are replaced by their instances.
+    pub(crate) call_graph: CallGraph,
+    /// The struct graph, in which generic structs are replaced by their instances.
+    pub(crate) struct_graph: StructGraph,
+    /// The types of the expressions and variables, in which instances of generic structs are replaced by their structs.
+    pub(crate) type_table: TypeTable,
+    /// The generic functions of the program, by name.
+    pub(crate) generic_functions: IndexMap<Symbol, Function>,
+    /// The name of the instance of each generic function for each list of type arguments, in the order they are first called.
+    pub(crate) instances: IndexMap<(Symbol, Vec<Type>), Symbol>,
+    /// The generic structs of the program, by name.
+    pub(crate) generic_structs: IndexMap<Symbol, Struct>,
+    /// The name of the instance of each generic struct for each list of const arguments, in the order they are first used.
+    pub(crate) struct_instances: IndexMap<(Symbol, Vec<usize>), Symbol>,
+    /// The names declared in the program scope, which the names of instances must not shadow.
+    pub(crate) declared: IndexSet<Symbol>,
+    /// The types that the type parameters of the instance being constructed are instantiated with.
+    pub(crate) substitution: IndexMap<Symbol, Type>,
+    /// The values of the program constants that are used as sizes, by name.
+    pub(crate) sizes: IndexMap<Symbol, NonZeroUsize>,
+    /// The name of the function being reconstructed.
+    pub(crate) function: Option<Symbol>,
+    /// A counter used to generate unique ids for the nodes of the instances.
+    pub(crate) node_builder: &'a NodeBuilder,
+}
+
+impl<'a> Monomorphizer<'a> {
+    /// Initializes a new `Monomorphizer`.
+    pub(crate) fn new(
+        symbol_table: SymbolTable,
+        call_graph: CallGraph,
+        struct_graph: StructGraph,
+        type_table: TypeTable,
+        node_builder: &'a NodeBuilder,
+    ) -> Self {
+        Self {
+            symbol_table,
+            call_graph,
+            struct_graph,
+            type_table,
+            generic_functions: IndexMap::new(),
+            instances: IndexMap::new(),
+            generic_structs: IndexMap::new(),
+            struct_instances: IndexMap::new(),
+            declared: IndexSet::new(),
+            substitution: IndexMap::new(),
+            sizes: IndexMap::new(),
+            function: None,
+            node_builder,
+        }
+    }
+
+    /// Returns the type with the type parameters of the instance being constructed replaced by their types,
+    /// each named size replaced by the value of the program constant that it names,
+    /// and each instance of a generic struct replaced by the struct that instantiates it.
+    pub(crate) fn monomorphize_type(&mut self, type_: &Type) -> Type {
+        let type_ = type_.substitute(&self.substitution).substitute_sizes(&self.sizes);
+        replace_struct_instances(&type_, &mut |instance| {
+            let const_arguments = instance.const_arguments.iter().map(Size::get).collect();
+            Some(self.struct_instance_name(instance.identifier.name, const_arguments))
+        })
+    }
+
+    /// Returns the name of the instance of the generic function for the given type arguments, naming it if it is new.
+    /// Instances are named after the function and their type arguments, e.g. `max_u8`, skipping names that are already declared.
+    pub(crate) fn instance_name(&mut self, generic: Symbol, type_arguments: Vec<Type>) -> Symbol {
+        if let Some(name) = self.instances.get(&(generic, type_arguments.clone())) {
+            return *name;
+        }
+
+        let name = self.fresh_name(format!("{}_{}", generic, type_arguments.iter().join("_")));
+        self.instances.insert((generic, type_arguments), name);
+        name
+    }
+
+    /// Returns the name of the instance of the generic struct for the given const arguments, naming it if it is new.
+    /// Instances are named after the struct and their const arguments, e.g. `Buffer_4`, skipping names that are already declared.
+    pub(crate) fn struct_instance_name(&mut self, generic: Symbol, const_arguments: Vec<usize>) -> Symbol {
+        if let Some(name) = self.struct_instances.get(&(generic, const_arguments.clone())) {
+            return *name;
+        }
+
+        let name = self.fresh_name(format!("{}_{}", generic, const_arguments.iter().join("_")));
+        self.struct_instances.insert((generic, const_arguments), name);
+        name
+    }
+
+    /// Returns `base`, or `base` followed by a counter if `base` is already declared, and declares it.
+    fn fresh_name(&mut self, base: String) -> Symbol {
+        let mut name = Symbol::intern(&base);
+        let mut counter = 1;
+        while self.declared.contains(&name) {
+            name = Symbol::intern(&format!("{}_{}", base, counter));
+            counter += 1;
+        }
+
+        self.declared.insert(name);
+        name
+    }
+
+    /// Replaces each instance of a generic struct in the types of the symbol table and the type table by its struct.
+    /// Every instance has been named by the time the program is reconstructed, since the types of the tables are those of the program.
+    pub(crate) fn replace_struct_instances_in_tables(&mut self) {
+        let (sizes, struct_instances) = (&self.sizes, &self.struct_instances);
+        let replace = |type_: &Type| {
+            replace_struct_instances(&type_.substitute_sizes(sizes), &mut |instance| {
+                let const_arguments = instance
+                    .const_arguments
+                    .iter()
+                    .map(|argument| argument.value().map(NonZeroUsize::get))
+                    .collect::<Option<Vec<_>>>()?;
+                struct_instances
+                    .get(&(instance.identifier.name, const_arguments))
+                    .copied()
+            })
+        };
+        self.symbol_table.map_types(replace);
+        for type_ in self.type_table.values_mut() {
+            *type_ = replace(type_);
+        }
+    }
+}
+
+/// Returns the type with each instance of a generic struct replaced by the struct named by `instantiate`, if any.
+fn replace_struct_instances(type_: &Type, instantiate: &mut impl FnMut(&StructInstance) -> Option<Symbol>) -> Type {
+    match type_ {
+        Type::StructInstance(instance) => match instantiate(instance) {
+            Some(name) => Type::Identifier(Identifier {
+                name,
+                ..instance.identifier
+            }),
+            None => type_.clone(),
+        },
+        Type::Array(element_type, length) => {
+            Type::Array(Box::new(replace_struct_instances(element_type, instantiate)), *length)
+        }
+        Type::Mapping(mapping) => Type::Mapping(MappingType {
+            key: Box::new(replace_struct_instances(&mapping.key, instantiate)),
+            value: Box::new(replace_struct_instances(&mapping.value, instantiate)),
+        }),
+        Type::Tuple(tuple) => Type::Tuple(Tuple(
+            tuple
+                .iter()
+                .map(|type_| replace_struct_instances(type_, instantiate))
+                .collect(),
+        )),
+        _ => type_.clone(),
+    }
+}
@@ -14,7 +14,16 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+use leo_ast::{
+    Ast, ErrExpression, Expression, ExpressionVisitor, ProgramVisitor, Statement, StatementVisitor, StructExpression,
+};
+use leo_errors::emitter::Handler;
+
+use indexmap::IndexMap;
+use std::time::{Duration, Instant};
+
 /// A compiler pass consuming `Self::Input` and returning `Self::Output`.
+/// Each pass returns a `PassSummary` of the work it performed alongside its output.
 pub trait Pass {
     type Input;
     type Output;
@@ -22,3 +31,170 @@ pub trait Pass {
     /// Runs the compiler pass.
     fn do_pass(input: Self::Input) -> Self::Output;
 }
+
+/// A summary of the work performed by a single run of a compiler pass.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PassSummary {
+    /// The name of the pass.
+    pub name: &'static str,
+    /// The number of statements and expressions in the AST given to the pass.
+    /// Nodes are only counted if the pass report is enabled, since counting them traverses the AST.
+    pub nodes_visited: usize,
+    /// The number of statements in the AST produced by the pass that do not appear in the AST given to the pass.
+    /// Nodes are only counted if the pass report is enabled, since counting them traverses the AST.
+    pub nodes_rewritten: usize,
+    /// The number of errors and warnings emitted by the pass, if it emits them through a handler.
+    /// Passes without a handler report their errors by returning them instead.
+    pub diagnostics_emitted: Option<usize>,
+    /// The time taken to run the pass.
+    pub duration: Duration,
+    /// The decisions made by the pass, such as the strategy chosen to flatten each conditional.
+    pub decisions: Vec<String>,
+}
+
+impl PassSummary {
+    /// Begins recording a summary for the pass `name`, which is about to run.
+    pub(crate) fn start(name: &'static str, handler: Option<&Handler>) -> PassRecorder {
+        PassRecorder {
+            name,
+            diagnostics: handler.map(diagnostic_count),
+            start: Instant::now(),
+        }
+    }
+
+    /// Records the nodes visited and rewritten by the pass, given the nodes of the AST it was given and the AST it produced, if any.
+    pub fn count_nodes(&mut self, input: &NodeCount, output: Option<&Ast>) {
+        self.nodes_visited = input.nodes;
+        self.nodes_rewritten = match output {
+            None => 0,
+            Some(ast) => {
+                let mut remaining = input.statements.clone();
+                NodeCount::new(ast)
+                    .statements
+                    .into_iter()
+                    .filter(|(statement, count)| {
+                        let existing = remaining.entry(statement.clone()).or_default();
+                        let rewritten = count.saturating_sub(*existing);
+                        *existing = existing.saturating_sub(*count);
+                        rewritten > 0
+                    })
+                    .count()
+            }
+        };
+    }
+}
+
+/// Records the state needed to construct a `PassSummary` while a pass runs.
+pub(crate) struct PassRecorder {
+    name: &'static str,
+    diagnostics: Option<usize>,
+    start: Instant,
+}
+
+impl PassRecorder {
+    /// Completes the summary, given the handler that the pass was given, if any.
+    pub(crate) fn finish(self, handler: Option<&Handler>) -> PassSummary {
+        PassSummary {
+            name: self.name,
+            nodes_visited: 0,
+            nodes_rewritten: 0,
+            diagnostics_emitted: self
+                .diagnostics
+                .zip(handler)
+                .map(|(before, handler)| diagnostic_count(handler).saturating_sub(before)),
+            duration: self.start.elapsed(),
+            decisions: Vec::new(),
+        }
+    }
+}
+
+/// Returns the number of errors and warnings emitted by `handler` so far.
+fn diagnostic_count(handler: &Handler) -> usize {
+    handler.err_count() + handler.warning_count()
+}
+
+/// The statements and expressions in an AST, counted so that the work performed by a pass over the AST can be reported.
+#[derive(Debug, Default)]
+pub struct NodeCount {
+    /// The total number of statements and expressions.
+    nodes: usize,
+    /// The number of occurrences of each statement that does not contain other statements.
+    statements: IndexMap<String, usize>,
+}
+
+impl NodeCount {
+    /// Counts the statements and expressions in `ast`.
+    pub fn new(ast: &Ast) -> Self {
+        let mut counter = Self::default();
+        counter.visit_program(ast.as_repr());
+        counter
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for NodeCount {
+    type AdditionalInput = ();
+    type Output = ();
+
+    fn visit_expression(&mut self, input: &'a Expression, additional: &Self::AdditionalInput) -> Self::Output {
+        self.nodes += 1;
+        match input {
+            Expression::Access(access) => self.visit_access(access, additional),
+            Expression::Array(array) => self.visit_array(array, additional),
+            Expression::Binary(binary) => self.visit_binary(binary, additional),
+            Expression::Call(call) => self.visit_call(call, additional),
+            Expression::Cast(cast) => self.visit_cast(cast, additional),
+            Expression::Struct(struct_) => self.visit_struct_init(struct_, additional),
+            Expression::Err(err) => self.visit_err(err, additional),
+            Expression::Identifier(identifier) => self.visit_identifier(identifier, additional),
+            Expression::Literal(literal) => self.visit_literal(literal, additional),
+            Expression::Ternary(ternary) => self.visit_ternary(ternary, additional),
+            Expression::Tuple(tuple) => self.visit_tuple(tuple, additional),
+            Expression::Unary(unary) => self.visit_unary(unary, additional),
+        }
+    }
+
+    fn visit_struct_init(&mut self, input: &'a StructExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        input
+            .members
+            .iter()
+            .filter_map(|member| member.expression.as_ref())
+            .for_each(|expression| self.visit_expression(expression, additional));
+    }
+
+    fn visit_err(&mut self, _input: &'a ErrExpression, _additional: &Self::AdditionalInput) -> Self::Output {}
+}
+
+impl<'a> StatementVisitor<'a> for NodeCount {
+    fn visit_statement(&mut self, input: &'a Statement) {
+        self.nodes += 1;
+        match input {
+            Statement::Block(stmt) => self.visit_block(stmt),
+            Statement::Cfg(stmt) => self.visit_cfg(stmt),
+            Statement::Conditional(stmt) => self.visit_conditional(stmt),
+            Statement::Iteration(stmt) => self.visit_iteration(stmt),
+            Statement::Match(stmt) => self.visit_match(stmt),
+            stmt => {
+                *self.statements.entry(stmt.to_string()).or_default() += 1;
+                match stmt {
+                    Statement::Assert(stmt) => self.visit_assert(stmt),
+                    Statement::Assign(stmt) => self.visit_assign(stmt),
+                    Statement::Decrement(stmt) => self.visit_decrement(stmt),
+                    Statement::Definition(stmt) => self.visit_definition(stmt),
+                    Statement::Expression(stmt) => self.visit_expression_statement(stmt),
+                    Statement::Finalize(stmt) => self.visit_finalize(stmt),
+                    Statement::Increment(stmt) => self.visit_increment(stmt),
+                    Statement::Return(stmt) => self.visit_return(stmt),
+                    Statement::Block(_)
+                    | Statement::Cfg(_)
+                    | Statement::Conditional(_)
+                    | Statement::Iteration(_)
+                    | Statement::Match(_) => {
+                        unreachable!("Statements that contain other statements are visited above.")
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for NodeCount {}
@@ -0,0 +1,104 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Interval, RangeAnalyzer};
+
+use leo_ast::{
+    BinaryExpression, BinaryOperation, Expression, ExpressionReconstructor, Identifier, Literal, TernaryExpression,
+};
+
+impl ExpressionReconstructor for RangeAnalyzer<'_> {
+    type AdditionalOutput = Option<Interval>;
+
+    /// Computes the range of a binary expression.
+    /// If an addition or multiplication provably cannot overflow, it is replaced with its wrapping counterpart,
+    /// which produces the same result without checking for overflow.
+    fn reconstruct_binary(&mut self, input: BinaryExpression) -> (Expression, Self::AdditionalOutput) {
+        let (left, left_range) = self.reconstruct_expression(*input.left);
+        let (right, right_range) = self.reconstruct_expression(*input.right);
+
+        let mut op = input.op;
+        let result = match (&left_range, &right_range) {
+            (Some(left_range), Some(right_range)) => left_range.apply(op, right_range),
+            _ => None,
+        };
+        let range = match result {
+            Some(result) if result.is_within_bounds() => {
+                op = match op {
+                    BinaryOperation::Add => BinaryOperation::AddWrapped,
+                    BinaryOperation::Mul => BinaryOperation::MulWrapped,
+                    op => op,
+                };
+                Some(result)
+            }
+            // Otherwise, the result may be any value of its type.
+            _ => match left_range {
+                Some(left_range) if Self::preserves_type(op) => Interval::full(left_range.type_),
+                _ => None,
+            },
+        };
+
+        (
+            Expression::Binary(BinaryExpression {
+                left: Box::new(left),
+                right: Box::new(right),
+                op,
+                span: input.span,
+                id: input.id,
+            }),
+            range,
+        )
+    }
+
+    fn reconstruct_identifier(&mut self, input: Identifier) -> (Expression, Self::AdditionalOutput) {
+        let range = self.ranges.get(&input.name).copied();
+        (Expression::Identifier(input), range)
+    }
+
+    fn reconstruct_literal(&mut self, input: Literal) -> (Expression, Self::AdditionalOutput) {
+        let range = match &input {
+            Literal::Integer(_, value, _, _) | Literal::Unsuffixed(value, _, _, _) => input
+                .integer_type()
+                .zip(value.parse::<i128>().ok())
+                .map(|(type_, value)| Interval::constant(type_, value)),
+            _ => None,
+        };
+        (Expression::Literal(input), range)
+    }
+
+    /// The range of a ternary expression is the union of the ranges of its branches.
+    fn reconstruct_ternary(&mut self, input: TernaryExpression) -> (Expression, Self::AdditionalOutput) {
+        let (condition, _) = self.reconstruct_expression(*input.condition);
+        let (if_true, if_true_range) = self.reconstruct_expression(*input.if_true);
+        let (if_false, if_false_range) = self.reconstruct_expression(*input.if_false);
+
+        let range = match (if_true_range, if_false_range) {
+            (Some(if_true_range), Some(if_false_range)) => if_true_range.union(&if_false_range),
+            _ => None,
+        };
+
+        (
+            Expression::Ternary(TernaryExpression {
+                condition: Box::new(condition),
+                if_true: Box::new(if_true),
+                if_false: Box::new(if_false),
+                span: input.span,
+                id: input.id,
+            }),
+            range,
+        )
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::RangeAnalyzer;
+
+use leo_ast::{AssignStatement, Block, Expression, ExpressionReconstructor, Statement, StatementReconstructor};
+
+impl StatementReconstructor for RangeAnalyzer<'_> {
+    /// Records the range of the value assigned to a variable.
+    /// If sanitizing, the range of a computed value is asserted after the assignment.
+    fn reconstruct_assign(&mut self, input: AssignStatement) -> (Statement, Self::AdditionalOutput) {
+        let (value, range) = self.reconstruct_expression(input.value);
+
+        if let Expression::Identifier(identifier) = &input.place {
+            match range {
+                Some(range) => self.ranges.insert(identifier.name, range),
+                None => self.ranges.remove(&identifier.name),
+            };
+            if let (true, Some(range), Expression::Binary(_)) = (self.sanitize, range, &value) {
+                let assertions = self.assert_within(*identifier, &range);
+                self.assertions.extend(assertions);
+            }
+        }
+
+        (
+            Statement::Assign(Box::new(AssignStatement {
+                place: input.place,
+                value,
+                span: input.span,
+                id: input.id,
+            })),
+            Default::default(),
+        )
+    }
+
+    /// Reconstructs each statement in the block, followed by the assertions it produced.
+    fn reconstruct_block(&mut self, input: Block) -> (Block, Self::AdditionalOutput) {
+        let mut statements = Vec::with_capacity(input.statements.len());
+        for statement in input.statements {
+            statements.push(self.reconstruct_statement(statement).0);
+            statements.append(&mut self.assertions);
+        }
+
+        (
+            Block {
+                statements,
+                span: input.span,
+                id: input.id,
+            },
+            Default::default(),
+        )
+    }
+}
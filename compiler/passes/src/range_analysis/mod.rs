@@ -0,0 +1,74 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The range analysis pass traverses the AST after the flattening pass and computes the range of values each integer variable may take.
+//! Function inputs may take any value of their type, literals take exactly their value, and loop variables, which are literals once loops are unrolled, take their iteration's value.
+//! Ranges are propagated through additions, subtractions, multiplications, and ternary expressions.
+//! Additions and multiplications that provably cannot overflow are replaced with their wrapping counterparts, which are cheaper to evaluate.
+//!
+//! Consider the following flattened Leo code.
+//! ```leo
+//! function main(a: u8) -> u16 {
+//!     i$1 = 0u16;
+//!     $var$2 = i$1 + 1u16;
+//!     $var$3 = a + 1u8;
+//!     return $var$2;
+//! }
+//! ```
+//!
+//! The range analysis pass produces the following code.
+//! ```leo
+//! function main(a: u8) -> u16 {
+//!     i$1 = 0u16;
+//!     $var$2 = i$1.add_wrapped(1u16);
+//!     $var$3 = a + 1u8;
+//!     return $var$2;
+//! }
+//! ```
+//! Note that `a + 1u8` may overflow, so it is left unchanged.
+//!
+//! If sanitizing, the pass also asserts that each variable computed by a binary expression lies within its range.
+//! For example, `$var$2` is followed by `assert($var$2 >= 1u16)` and `assert($var$2 <= 1u16)`, so an unsound wrapping operation is caught at runtime.
+
+mod analyze_expression;
+
+mod analyze_program;
+
+mod analyze_statement;
+
+pub mod range_analyzer;
+pub use range_analyzer::*;
+
+use crate::{Pass, PassSummary};
+
+use leo_ast::{Ast, NodeBuilder, ProgramReconstructor};
+use leo_errors::Result;
+
+impl<'a> Pass for RangeAnalyzer<'a> {
+    type Input = (Ast, &'a NodeBuilder, bool);
+    type Output = Result<(Ast, PassSummary)>;
+
+    fn do_pass((ast, node_builder, sanitize): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("range analysis", None);
+        let mut reconstructor = RangeAnalyzer::new(node_builder, sanitize);
+        let program = reconstructor.reconstruct_program(ast.into_repr());
+
+        let ast = Ast::new(program);
+        let summary = recorder.finish(None);
+
+        Ok((ast, summary))
+    }
+}
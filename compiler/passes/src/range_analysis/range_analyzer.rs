@@ -0,0 +1,197 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{
+    AssertStatement, AssertVariant, BinaryExpression, BinaryOperation, Expression, Identifier, IntegerType, Literal,
+    NodeBuilder, Statement,
+};
+use leo_span::Symbol;
+
+use indexmap::IndexMap;
+
+/// A closed range `[min, max]` of the values that an integer of type `type_` may take.
+/// Ranges are tracked as `i128`s, so the upper half of the `u128` range cannot be represented.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Interval {
+    pub type_: IntegerType,
+    pub min: i128,
+    pub max: i128,
+}
+
+impl Interval {
+    /// Returns the interval containing only `value`.
+    pub fn constant(type_: IntegerType, value: i128) -> Self {
+        Self {
+            type_,
+            min: value,
+            max: value,
+        }
+    }
+
+    /// Returns the interval of all values of `type_`, if it can be represented.
+    pub fn full(type_: IntegerType) -> Option<Self> {
+        use IntegerType::*;
+        let (min, max) = match type_ {
+            U8 => (0, u8::MAX as i128),
+            U16 => (0, u16::MAX as i128),
+            U32 => (0, u32::MAX as i128),
+            U64 => (0, u64::MAX as i128),
+            U128 => return None,
+            I8 => (i8::MIN as i128, i8::MAX as i128),
+            I16 => (i16::MIN as i128, i16::MAX as i128),
+            I32 => (i32::MIN as i128, i32::MAX as i128),
+            I64 => (i64::MIN as i128, i64::MAX as i128),
+            I128 => (i128::MIN, i128::MAX),
+        };
+        Some(Self { type_, min, max })
+    }
+
+    /// Returns `true` if every value in the interval is a valid value of its type.
+    pub fn is_within_bounds(&self) -> bool {
+        match Self::full(self.type_) {
+            Some(bounds) => bounds.min <= self.min && self.max <= bounds.max,
+            // Every non-negative `i128` is a valid `u128`.
+            None => self.min >= 0,
+        }
+    }
+
+    /// Returns the smallest interval containing both `self` and `other`, if they have the same type.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        (self.type_ == other.type_).then(|| Self {
+            type_: self.type_,
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        })
+    }
+
+    /// Returns the interval of the results of applying `op` to values in `self` and `other`, ignoring the bounds of the type.
+    /// Returns `None` if the operation is not supported, the operands have different types, or the result cannot be represented.
+    pub fn apply(&self, op: BinaryOperation, other: &Self) -> Option<Self> {
+        if self.type_ != other.type_ {
+            return None;
+        }
+        let (min, max) = match op {
+            BinaryOperation::Add | BinaryOperation::AddWrapped => {
+                (self.min.checked_add(other.min)?, self.max.checked_add(other.max)?)
+            }
+            BinaryOperation::Sub | BinaryOperation::SubWrapped => {
+                (self.min.checked_sub(other.max)?, self.max.checked_sub(other.min)?)
+            }
+            BinaryOperation::Mul | BinaryOperation::MulWrapped => {
+                // The extremes of a product are attained at the endpoints of its operands.
+                let products = [
+                    self.min.checked_mul(other.min)?,
+                    self.min.checked_mul(other.max)?,
+                    self.max.checked_mul(other.min)?,
+                    self.max.checked_mul(other.max)?,
+                ];
+                (*products.iter().min()?, *products.iter().max()?)
+            }
+            _ => return None,
+        };
+        Some(Self {
+            type_: self.type_,
+            min,
+            max,
+        })
+    }
+}
+
+pub struct RangeAnalyzer<'a> {
+    /// A mapping from integer variables to the range of values they may take.
+    /// Note that the AST is in SSA form, so each variable is assigned exactly once.
+    pub(crate) ranges: IndexMap<Symbol, Interval>,
+    /// Whether to assert that each computed variable lies within its range, so that an unsound range is caught at runtime.
+    pub(crate) sanitize: bool,
+    /// The assertions to insert after the statement currently being reconstructed.
+    pub(crate) assertions: Vec<Statement>,
+    /// A counter used to generate unique ids for the nodes that the pass creates.
+    pub(crate) node_builder: &'a NodeBuilder,
+}
+
+impl<'a> RangeAnalyzer<'a> {
+    /// Returns a new range analyzer, which injects assertions on the computed ranges if `sanitize` is set.
+    pub fn new(node_builder: &'a NodeBuilder, sanitize: bool) -> Self {
+        Self {
+            ranges: IndexMap::new(),
+            sanitize,
+            assertions: Vec::new(),
+            node_builder,
+        }
+    }
+
+    /// Returns assertions that `variable` lies within `range`, omitting the bounds that are implied by its type.
+    pub(crate) fn assert_within(&self, variable: Identifier, range: &Interval) -> Vec<Statement> {
+        // Note that the full range of a `u128` cannot be represented, but its lower bound is zero.
+        let full = Interval::full(range.type_);
+        let lower_implied = range.min <= full.map_or(0, |full| full.min);
+        let upper_implied = full.map_or(false, |full| range.max >= full.max);
+
+        let assert = |op: BinaryOperation, bound: i128| {
+            Statement::Assert(AssertStatement {
+                variant: AssertVariant::Assert(Expression::Binary(BinaryExpression {
+                    left: Box::new(Expression::Identifier(variable)),
+                    right: Box::new(Expression::Literal(Literal::Integer(
+                        range.type_,
+                        bound.to_string(),
+                        Default::default(),
+                        self.node_builder.next_id(),
+                    ))),
+                    op,
+                    span: Default::default(),
+                    id: self.node_builder.next_id(),
+                })),
+                span: Default::default(),
+                id: self.node_builder.next_id(),
+            })
+        };
+
+        let mut assertions = Vec::new();
+        if !lower_implied {
+            assertions.push(assert(BinaryOperation::Gte, range.min));
+        }
+        if !upper_implied {
+            assertions.push(assert(BinaryOperation::Lte, range.max));
+        }
+        assertions
+    }
+
+    /// Returns `true` if the result of `op` has the same type as its left operand.
+    pub(crate) fn preserves_type(op: BinaryOperation) -> bool {
+        use BinaryOperation::*;
+        matches!(
+            op,
+            Add | AddWrapped
+                | BitwiseAnd
+                | BitwiseOr
+                | Div
+                | DivWrapped
+                | Mul
+                | MulWrapped
+                | Pow
+                | PowWrapped
+                | Rem
+                | RemWrapped
+                | Shl
+                | ShlWrapped
+                | Shr
+                | ShrWrapped
+                | Sub
+                | SubWrapped
+                | Xor
+        )
+    }
+}
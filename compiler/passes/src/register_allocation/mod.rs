@@ -0,0 +1,70 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The register allocation pass minimizes the number of registers used by the Aleo instructions produced by code generation.
+//! Since every intermediate value of the flattened program is assigned its own register, and registers grow the constraint system,
+//! the pass removes the temporaries that are never read and numbers the remaining registers consecutively.
+//!
+//! Registers in Aleo instructions are assigned exactly once, in increasing order, so temporaries with disjoint lifetimes cannot share a register.
+//! An instruction is only removed if it cannot halt execution, so that removing it does not change which executions succeed.
+//!
+//! Consider the following Aleo instructions.
+//! ```aleo
+//! function main:
+//!     input r0 as u8.private;
+//!     add.w r0 1u8 into r1;
+//!     is.eq r1 0u8 into r2;
+//!     mul.w r0 2u8 into r3;
+//!     output r3 as u8.private;
+//! ```
+//!
+//! `r2` is never read, and the instruction computing `r1` is only used by the instruction computing `r2`, so both are removed.
+//! ```aleo
+//! function main:
+//!     input r0 as u8.private;
+//!     mul.w r0 2u8 into r1;
+//!     output r1 as u8.private;
+//! ```
+
+pub mod register_allocator;
+pub use register_allocator::*;
+
+use crate::{Pass, PassSummary};
+
+use leo_ir::Program;
+
+use std::time::Instant;
+
+impl Pass for RegisterAllocator {
+    type Input = Program;
+    type Output = (Program, PassSummary);
+
+    fn do_pass(mut program: Self::Input) -> Self::Output {
+        let start = Instant::now();
+        let mut allocator = RegisterAllocator::default();
+        allocator.allocate_program(&mut program);
+
+        let summary = PassSummary {
+            name: "register allocation",
+            nodes_visited: allocator.instructions_visited,
+            nodes_rewritten: allocator.instructions_removed,
+            diagnostics_emitted: None,
+            duration: start.elapsed(),
+            decisions: allocator.decisions,
+        };
+        (program, summary)
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ir::{Function, Input, Instruction, Program, Register, SourceLocation};
+
+use indexmap::{IndexMap, IndexSet};
+
+#[derive(Default)]
+pub struct RegisterAllocator {
+    /// The number of instructions given to the pass.
+    pub(crate) instructions_visited: usize,
+    /// The number of instructions removed by the pass.
+    pub(crate) instructions_removed: usize,
+    /// The number of registers used by each function and finalize block whose registers were reduced.
+    pub(crate) decisions: Vec<String>,
+}
+
+impl RegisterAllocator {
+    /// Allocates the registers of every function and finalize block in the program.
+    pub fn allocate_program(&mut self, program: &mut Program) {
+        program
+            .functions
+            .iter_mut()
+            .for_each(|function| self.allocate_function(function));
+    }
+
+    fn allocate_function(&mut self, function: &mut Function) {
+        self.allocate_body(
+            &function.name,
+            &mut function.inputs,
+            &mut function.instructions,
+            &mut function.locations,
+        );
+        if let Some(finalize) = &mut function.finalize {
+            let name = format!("{}/finalize", finalize.name);
+            self.allocate_body(
+                &name,
+                &mut finalize.inputs,
+                &mut finalize.instructions,
+                &mut finalize.locations,
+            );
+        }
+    }
+
+    /// Removes the unused temporaries of a function or finalize block named `name`, and renumbers its registers consecutively.
+    /// The `locations` that the instructions were generated from are kept alongside the remaining instructions.
+    pub(crate) fn allocate_body(
+        &mut self,
+        name: &str,
+        inputs: &mut [Input],
+        instructions: &mut Vec<Instruction>,
+        locations: &mut Vec<Option<SourceLocation>>,
+    ) {
+        self.instructions_visited += instructions.len();
+        locations.resize(instructions.len(), None);
+        let mut registers_before = inputs.len();
+
+        // Traverse the instructions in reverse, so that an instruction is known to be unused once every later instruction has been seen.
+        let mut live = IndexSet::new();
+        let mut kept = Vec::with_capacity(instructions.len());
+        let mut kept_locations = Vec::with_capacity(locations.len());
+        for (mut instruction, location) in std::mem::take(instructions)
+            .into_iter()
+            .zip(std::mem::take(locations))
+            .rev()
+        {
+            let destinations = instruction.destinations_mut();
+            registers_before += destinations.len();
+            let unused = destinations.into_iter().all(|destination| !live.contains(destination));
+            if unused && instruction.is_removable() {
+                self.instructions_removed += 1;
+                continue;
+            }
+            live.extend(
+                instruction
+                    .operands_mut()
+                    .into_iter()
+                    .filter_map(|operand| operand.register()),
+            );
+            kept.push(instruction);
+            kept_locations.push(location);
+        }
+        kept.reverse();
+        kept_locations.reverse();
+        *instructions = kept;
+        *locations = kept_locations;
+
+        // Inputs occupy the first registers, followed by the destinations in the order they are assigned.
+        let mut renumbering = IndexMap::new();
+        for input in inputs.iter_mut() {
+            input.register = Self::renumber(&mut renumbering, input.register);
+        }
+        for instruction in instructions.iter_mut() {
+            for operand in instruction.operands_mut() {
+                if let Some(register) = operand.register_mut() {
+                    *register = renumbering[&*register];
+                }
+            }
+            for destination in instruction.destinations_mut() {
+                *destination = Self::renumber(&mut renumbering, *destination);
+            }
+        }
+
+        let registers_after = renumbering.len();
+        if registers_after < registers_before {
+            self.decisions.push(format!(
+                "`{}` uses {} registers rather than {}",
+                name, registers_after, registers_before
+            ));
+        }
+    }
+
+    /// Assigns the next register to `register`, returning the new register.
+    fn renumber(renumbering: &mut IndexMap<Register, Register>, register: Register) -> Register {
+        let next = Register(renumbering.len() as u64);
+        *renumbering.entry(register).or_insert(next)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use leo_ir::{BinaryOperation, Operand, ValueType, Visibility};
+
+    fn binary(operation: BinaryOperation, left: Operand, right: &str, destination: u64) -> Instruction {
+        Instruction::Binary {
+            operation,
+            left,
+            right: Operand::Literal(right.to_string()),
+            destination: Register(destination),
+        }
+    }
+
+    #[test]
+    fn test_allocate_body() {
+        let register = |register| Operand::Register(Register(register));
+        let type_ = ValueType::Plaintext("u8".to_string(), Some(Visibility::Private));
+        let mut inputs = vec![Input {
+            register: Register(0),
+            type_: type_.clone(),
+        }];
+        let mut instructions = vec![
+            binary(BinaryOperation::AddWrapped, register(0), "1u8", 1),
+            binary(BinaryOperation::IsEq, register(1), "0u8", 2),
+            // Unused, but may overflow.
+            binary(BinaryOperation::Add, register(0), "1u8", 3),
+            binary(BinaryOperation::MulWrapped, register(0), "2u8", 4),
+            Instruction::Output(register(4), type_),
+        ];
+
+        let location = |line| {
+            Some(SourceLocation {
+                line_start: line,
+                col_start: 1,
+                line_stop: line,
+                col_stop: 2,
+            })
+        };
+        let mut locations = vec![location(1), location(2), location(3), location(4), None];
+
+        let mut allocator = RegisterAllocator::default();
+        allocator.allocate_body("main", &mut inputs, &mut instructions, &mut locations);
+
+        let printed = instructions
+            .iter()
+            .map(|instruction| instruction.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            printed,
+            [
+                "add r0 1u8 into r1;",
+                "mul.w r0 2u8 into r2;",
+                "output r2 as u8.private;"
+            ]
+        );
+        assert_eq!(locations, [location(3), location(4), None]);
+        assert_eq!(allocator.instructions_removed, 2);
+        assert_eq!(allocator.decisions, ["`main` uses 3 registers rather than 5"]);
+    }
+}
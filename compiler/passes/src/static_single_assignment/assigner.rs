@@ -14,8 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use leo_ast::{AssignStatement, Expression, Identifier, Statement};
-use leo_span::Symbol;
+use leo_ast::{AssignStatement, Expression, Identifier, Node, NodeBuilder, NodeId, Statement, TupleExpression};
+use leo_span::{Span, Symbol};
 use std::fmt::Display;
 
 /// A struct used to create assignment statements.
@@ -27,32 +27,72 @@ pub struct Assigner {
 
 impl Assigner {
     /// Return a new unique `Symbol` from a `&str`.
+    /// The symbol is internal, so it cannot collide with an identifier in the program.
     pub(crate) fn unique_symbol(&mut self, arg: impl Display) -> Symbol {
         self.counter += 1;
-        Symbol::intern(&format!("{}${}", arg, self.counter - 1))
+        Symbol::intern_internal(&format!("{}${}", arg, self.counter - 1))
     }
 
-    /// Constructs the assignment statement `place = expr;`.
+    /// Constructs the assignment statement `place = expr;`, which was lowered from the statement at `span`.
     /// This function should be the only place where `AssignStatement`s are constructed.
-    pub(crate) fn simple_assign_statement(&mut self, identifier: Identifier, value: Expression) -> Statement {
+    pub(crate) fn simple_assign_statement(
+        &mut self,
+        identifier: Identifier,
+        value: Expression,
+        span: Span,
+        id: NodeId,
+    ) -> Statement {
         Statement::Assign(Box::new(AssignStatement {
             place: Expression::Identifier(identifier),
             value,
-            span: Default::default(),
+            span,
+            id,
+        }))
+    }
+
+    /// Constructs the assignment statement `(place_0, ..., place_n) = call;`, which assigns the outputs of a call to multiple variables.
+    /// The statement was lowered from the statement at `span`, and the tuple of variables is given a fresh id from `node_builder`.
+    pub(crate) fn tuple_assign_statement(
+        &mut self,
+        identifiers: Vec<Identifier>,
+        call: Expression,
+        span: Span,
+        id: NodeId,
+        node_builder: &NodeBuilder,
+    ) -> Statement {
+        Statement::Assign(Box::new(AssignStatement {
+            place: Expression::Tuple(TupleExpression {
+                elements: identifiers.into_iter().map(Expression::Identifier).collect(),
+                span,
+                id: node_builder.next_id(),
+            }),
+            value: call,
+            span,
+            id,
         }))
     }
 
     /// Constructs a simple assign statement for `expr` with a unique name.
     /// For example, `expr` is transformed into `$var$0 = expr;`.
-    pub(crate) fn unique_simple_assign_statement(&mut self, expr: Expression) -> (Identifier, Statement) {
+    /// The new variable and statement are given the span of `expr`, marked as synthetic, and fresh ids from `node_builder`.
+    pub(crate) fn unique_simple_assign_statement(
+        &mut self,
+        expr: Expression,
+        node_builder: &NodeBuilder,
+    ) -> (Identifier, Statement) {
         // Create a new variable for the expression.
         let name = self.unique_symbol("$var");
+        let span = expr.span().synthesized();
 
         let place = Identifier {
             name,
-            span: Default::default(),
+            span,
+            id: node_builder.next_id(),
         };
 
-        (place, self.simple_assign_statement(place, expr))
+        (
+            place,
+            self.simple_assign_statement(place, expr, span, node_builder.next_id()),
+        )
     }
 }
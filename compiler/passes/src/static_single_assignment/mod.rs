@@ -38,21 +38,31 @@
 //!     $var$0 = flag == 0u8;
 //!     if ($var$0) {
 //!         $var$1 = value + 1u8;
-//!         value$1 = $var$1;
-//!         return value$1;
+//!         value$0 = $var$1;
+//!         return value$0;
 //!     } else {
 //!         $var$2 = value + 2u8;
-//!         value$2 = $var$2;
+//!         value$1 = $var$2;
 //!     }
-//!     value$3 = $var$0 ? value$1 : value$2;
-//!     return value$3;
+//!     value$2 = $var$0 ? value$0 : value$1;
+//!     return value$2;
 //! }
 //! ```
 //! Note that the redundant assignments have no effect on the bytecode generated by the compiler.
+//!
+//! Phi functions are placed at the joins of conditional statements in the iterated dominance frontiers of the writes to each variable,
+//! computed over the control-flow graph of the function. Joins at which a variable is not live, i.e. cannot be read before it is written again, are pruned.
+//! For example, if `value` were not returned at the end of `main`, then no phi function would be produced for it.
+//!
+//! Each variable is numbered separately, so that its versions can be correlated with the source code.
+//! If global numbering is enabled, the versions of all variables are numbered by a single counter instead, e.g. `value$5`.
 
 pub mod assigner;
 pub use assigner::*;
 
+mod phi_placement;
+pub(crate) use phi_placement::*;
+
 mod rename_expression;
 
 mod rename_program;
@@ -62,22 +72,29 @@ mod rename_statement;
 mod rename_table;
 pub(crate) use rename_table::*;
 
+pub mod source_names;
+pub use source_names::*;
+
 pub mod static_single_assigner;
 pub use static_single_assigner::*;
 
-use crate::Pass;
+use crate::{Pass, PassSummary, TypeTable};
 
-use leo_ast::{Ast, ProgramConsumer};
+use leo_ast::{Ast, NodeBuilder, ProgramConsumer};
 use leo_errors::Result;
 
-impl Pass for StaticSingleAssigner {
-    type Input = Ast;
-    type Output = Result<(Ast, Assigner)>;
+impl<'a> Pass for StaticSingleAssigner<'a> {
+    type Input = (Ast, &'a NodeBuilder, &'a mut TypeTable, bool);
+    type Output = Result<(Ast, Assigner, PassSummary)>;
 
-    fn do_pass(ast: Self::Input) -> Self::Output {
-        let mut consumer = StaticSingleAssigner::new();
+    fn do_pass((ast, node_builder, type_table, global_numbering): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("static single assignment", None);
+        let mut consumer = StaticSingleAssigner::new(node_builder, type_table, global_numbering);
         let program = consumer.consume_program(ast.into_repr());
 
-        Ok((Ast::new(program), consumer.assigner))
+        let ast = Ast::new(program);
+        let summary = recorder.finish(None);
+
+        Ok((ast, consumer.assigner, summary))
     }
 }
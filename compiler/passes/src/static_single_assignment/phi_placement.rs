@@ -0,0 +1,61 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{BasicBlockIndex, Cfg};
+
+use leo_ast::NodeId;
+use leo_span::Symbol;
+
+use indexmap::{IndexMap, IndexSet};
+
+/// Returns the variables that need a phi function after each conditional statement in a control-flow graph, by the id of the conditional statement.
+/// A variable needs a phi function at a join if the join is in the iterated dominance frontier of the blocks that write the variable,
+/// and if the variable is live at the join, i.e. it may be read before it is written again.
+pub(crate) fn place_phis(cfg: &Cfg) -> IndexMap<NodeId, IndexSet<Symbol>> {
+    let frontiers = cfg.dominance_frontiers();
+    let live_in = cfg.live_in();
+
+    // Collect the blocks that write each variable.
+    let mut definitions: IndexMap<Symbol, Vec<BasicBlockIndex>> = IndexMap::new();
+    for index in cfg.indices() {
+        for symbol in cfg.block(index).defs().iter() {
+            definitions.entry(*symbol).or_default().push(index);
+        }
+    }
+
+    // Place phi functions at the iterated dominance frontier of the definitions of each variable, where the variable is live.
+    // Note that a phi function is itself a definition, so its block is added to the worklist.
+    let mut phis: IndexMap<BasicBlockIndex, IndexSet<Symbol>> = IndexMap::new();
+    for (symbol, mut worklist) in definitions {
+        let mut frontier = IndexSet::new();
+        while let Some(block) = worklist.pop() {
+            for join in frontiers[block.0].iter() {
+                if frontier.insert(*join) {
+                    worklist.push(*join);
+                }
+            }
+        }
+        for join in frontier {
+            if live_in[join.0].contains(&symbol) {
+                phis.entry(join).or_default().insert(symbol);
+            }
+        }
+    }
+
+    cfg.joins()
+        .map(|(id, join)| (id, phis.get(&join).cloned().unwrap_or_default()))
+        .collect()
+}
@@ -17,18 +17,32 @@
 use crate::StaticSingleAssigner;
 
 use leo_ast::{
-    AccessExpression, AssociatedFunction, BinaryExpression, CallExpression, Expression, ExpressionConsumer, Identifier,
-    Literal, MemberAccess, Statement, StructExpression, StructVariableInitializer, TernaryExpression, TupleAccess,
-    TupleExpression, UnaryExpression,
+    AccessExpression, ArrayAccess, ArrayExpression, AssociatedFunction, BinaryExpression, CallExpression,
+    CastExpression, Expression, ExpressionConsumer, Identifier, Literal, MemberAccess, Statement, StructExpression,
+    StructVariableInitializer, TernaryExpression, TupleAccess, TupleExpression, UnaryExpression,
 };
 use leo_span::sym;
 
-impl ExpressionConsumer for StaticSingleAssigner {
+impl ExpressionConsumer for StaticSingleAssigner<'_> {
     type Output = (Expression, Vec<Statement>);
 
     /// Consumes an access expression, accumulating any statements that are generated.
     fn consume_access(&mut self, input: AccessExpression) -> Self::Output {
         let (expr, mut statements) = match input {
+            AccessExpression::Array(array) => {
+                let (expr, mut statements) = self.consume_expression(*array.array);
+                let (index, mut index_statements) = self.consume_expression(*array.index);
+                statements.append(&mut index_statements);
+                (
+                    AccessExpression::Array(ArrayAccess {
+                        array: Box::new(expr),
+                        index: Box::new(index),
+                        span: array.span,
+                        id: array.id,
+                    }),
+                    statements,
+                )
+            }
             AccessExpression::AssociatedFunction(function) => {
                 let mut statements = Vec::new();
                 (
@@ -45,15 +59,16 @@ impl ExpressionConsumer for StaticSingleAssigner {
                             })
                             .collect(),
                         span: function.span,
+                        id: function.id,
                     }),
                     statements,
                 )
             }
             AccessExpression::Member(member) => {
                 // TODO: Create AST node for native access expressions?
-                // If the access expression is of the form `self.<name>`, then don't rename it.
+                // If the access expression is of the form `self.<name>` or `block.<name>`, then don't rename it.
                 if let Expression::Identifier(Identifier { name, .. }) = *member.inner {
-                    if name == sym::SelfLower {
+                    if name == sym::SelfLower || name == sym::block {
                         return (Expression::Access(AccessExpression::Member(member)), Vec::new());
                     }
                 }
@@ -64,6 +79,7 @@ impl ExpressionConsumer for StaticSingleAssigner {
                         inner: Box::new(expr),
                         name: member.name,
                         span: member.span,
+                        id: member.id,
                     }),
                     statements,
                 )
@@ -75,13 +91,40 @@ impl ExpressionConsumer for StaticSingleAssigner {
                         tuple: Box::new(expr),
                         index: tuple.index,
                         span: tuple.span,
+                        id: tuple.id,
                     }),
                     statements,
                 )
             }
             expr => (expr, Vec::new()),
         };
-        let (place, statement) = self.assigner.unique_simple_assign_statement(Expression::Access(expr));
+        let (place, statement) = self.unique_simple_assign_statement(Expression::Access(expr));
+        statements.push(statement);
+
+        (Expression::Identifier(place), statements)
+    }
+
+    /// Consumes an array expression, accumulating any statements that are generated.
+    fn consume_array(&mut self, input: ArrayExpression) -> Self::Output {
+        let mut statements = Vec::new();
+
+        // Process the elements, accumulating any statements produced.
+        let elements = input
+            .elements
+            .into_iter()
+            .map(|element| {
+                let (element, mut stmts) = self.consume_expression(element);
+                statements.append(&mut stmts);
+                element
+            })
+            .collect();
+
+        // Construct and accumulate a new assignment statement for the array expression.
+        let (place, statement) = self.unique_simple_assign_statement(Expression::Array(ArrayExpression {
+            elements,
+            span: input.span,
+            id: input.id,
+        }));
         statements.push(statement);
 
         (Expression::Identifier(place), statements)
@@ -97,14 +140,13 @@ impl ExpressionConsumer for StaticSingleAssigner {
         statements.append(&mut right_statements);
 
         // Construct and accumulate a unique assignment statement storing the result of the binary expression.
-        let (place, statement) = self
-            .assigner
-            .unique_simple_assign_statement(Expression::Binary(BinaryExpression {
-                left: Box::new(left_expression),
-                right: Box::new(right_expression),
-                op: input.op,
-                span: input.span,
-            }));
+        let (place, statement) = self.unique_simple_assign_statement(Expression::Binary(BinaryExpression {
+            left: Box::new(left_expression),
+            right: Box::new(right_expression),
+            op: input.op,
+            span: input.span,
+            id: input.id,
+        }));
         statements.push(statement);
 
         (Expression::Identifier(place), statements)
@@ -112,30 +154,27 @@ impl ExpressionConsumer for StaticSingleAssigner {
 
     /// Consumes a call expression without visiting the function name, accumulating any statements that are generated.
     fn consume_call(&mut self, input: CallExpression) -> Self::Output {
-        let mut statements = Vec::new();
-
-        // Process the arguments, accumulating any statements produced.
-        let arguments = input
-            .arguments
-            .into_iter()
-            .map(|argument| {
-                let (argument, mut stmts) = self.consume_expression(argument);
-                statements.append(&mut stmts);
-                argument
-            })
-            .collect();
+        let (call, mut statements) = self.consume_call_arguments(input);
 
         // Construct and accumulate a new assignment statement for the call expression.
-        let (place, statement) = self
-            .assigner
-            .unique_simple_assign_statement(Expression::Call(CallExpression {
-                // Note that we do not rename the function name.
-                function: input.function,
-                // Consume the arguments.
-                arguments,
-                external: input.external,
-                span: input.span,
-            }));
+        let (place, statement) = self.unique_simple_assign_statement(Expression::Call(call));
+        statements.push(statement);
+
+        (Expression::Identifier(place), statements)
+    }
+
+    /// Consumes a cast expression, accumulating any statements that are generated.
+    fn consume_cast(&mut self, input: CastExpression) -> Self::Output {
+        // Reconstruct the expression being cast.
+        let (expression, mut statements) = self.consume_expression(*input.expression);
+
+        // Construct and accumulate a new assignment statement for the cast expression.
+        let (place, statement) = self.unique_simple_assign_statement(Expression::Cast(CastExpression {
+            expression: Box::new(expression),
+            type_: input.type_,
+            span: input.span,
+            id: input.id,
+        }));
         statements.push(statement);
 
         (Expression::Identifier(place), statements)
@@ -170,13 +209,13 @@ impl ExpressionConsumer for StaticSingleAssigner {
             .collect();
 
         // Construct and accumulate a new assignment statement for the struct expression.
-        let (place, statement) = self
-            .assigner
-            .unique_simple_assign_statement(Expression::Struct(StructExpression {
-                name: input.name,
-                span: input.span,
-                members,
-            }));
+        let (place, statement) = self.unique_simple_assign_statement(Expression::Struct(StructExpression {
+            name: input.name,
+            const_arguments: input.const_arguments,
+            span: input.span,
+            members,
+            id: input.id,
+        }));
         statements.push(statement);
 
         (Expression::Identifier(place), statements)
@@ -187,8 +226,15 @@ impl ExpressionConsumer for StaticSingleAssigner {
         let name = match self.is_lhs {
             // If consuming the left-hand side of a definition or assignment, a new unique name is introduced.
             true => {
-                let new_name = self.assigner.unique_symbol(identifier.name);
+                let new_name = self.new_name(identifier.name);
                 self.rename_table.update(identifier.name, new_name);
+                self.definitions.insert(
+                    new_name,
+                    Identifier {
+                        name: new_name,
+                        ..identifier
+                    },
+                );
                 new_name
             }
             // Otherwise, we look up the previous name in the `RenameTable`.
@@ -202,6 +248,7 @@ impl ExpressionConsumer for StaticSingleAssigner {
             Expression::Identifier(Identifier {
                 name,
                 span: identifier.span,
+                id: identifier.id,
             }),
             Default::default(),
         )
@@ -226,14 +273,13 @@ impl ExpressionConsumer for StaticSingleAssigner {
         statements.append(&mut if_false_statements);
 
         // Construct and accumulate a unique assignment statement storing the result of the ternary expression.
-        let (place, statement) = self
-            .assigner
-            .unique_simple_assign_statement(Expression::Ternary(TernaryExpression {
-                condition: Box::new(cond_expr),
-                if_true: Box::new(if_true_expr),
-                if_false: Box::new(if_false_expr),
-                span: input.span,
-            }));
+        let (place, statement) = self.unique_simple_assign_statement(Expression::Ternary(TernaryExpression {
+            condition: Box::new(cond_expr),
+            if_true: Box::new(if_true_expr),
+            if_false: Box::new(if_false_expr),
+            span: input.span,
+            id: input.id,
+        }));
         statements.push(statement);
 
         (Expression::Identifier(place), statements)
@@ -260,6 +306,7 @@ impl ExpressionConsumer for StaticSingleAssigner {
             Expression::Tuple(TupleExpression {
                 elements,
                 span: input.span,
+                id: input.id,
             }),
             statements,
         )
@@ -271,13 +318,12 @@ impl ExpressionConsumer for StaticSingleAssigner {
         let (receiver, mut statements) = self.consume_expression(*input.receiver);
 
         // Construct and accumulate a new assignment statement for the unary expression.
-        let (place, statement) = self
-            .assigner
-            .unique_simple_assign_statement(Expression::Unary(UnaryExpression {
-                op: input.op,
-                receiver: Box::new(receiver),
-                span: input.span,
-            }));
+        let (place, statement) = self.unique_simple_assign_statement(Expression::Unary(UnaryExpression {
+            op: input.op,
+            receiver: Box::new(receiver),
+            span: input.span,
+            id: input.id,
+        }));
         statements.push(statement);
 
         (Expression::Identifier(place), statements)
@@ -14,14 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::StaticSingleAssigner;
+use crate::{place_phis, Cfg, StaticSingleAssigner};
 
 use leo_ast::{
     Block, Finalize, Function, FunctionConsumer, Program, ProgramConsumer, ProgramScope, ProgramScopeConsumer,
     StatementConsumer,
 };
 
-impl FunctionConsumer for StaticSingleAssigner {
+impl FunctionConsumer for StaticSingleAssigner<'_> {
     type Output = Function;
 
     /// Reconstructs the `Function`s in the `Program`, while allocating the appropriate `RenameTable`s.
@@ -34,10 +34,16 @@ impl FunctionConsumer for StaticSingleAssigner {
         for input_variable in function.input.iter() {
             self.rename_table
                 .update(input_variable.identifier().name, input_variable.identifier().name);
+            self.definitions
+                .insert(input_variable.identifier().name, input_variable.identifier());
         }
 
+        // Decide where phi functions are placed in the function body.
+        self.phis = place_phis(&Cfg::new(&function.block));
+
         let block = Block {
             span: function.block.span,
+            id: function.block.id,
             statements: self.consume_block(function.block),
         };
 
@@ -53,10 +59,16 @@ impl FunctionConsumer for StaticSingleAssigner {
             for input_variable in finalize.input.iter() {
                 self.rename_table
                     .update(input_variable.identifier().name, input_variable.identifier().name);
+                self.definitions
+                    .insert(input_variable.identifier().name, input_variable.identifier());
             }
 
+            // Decide where phi functions are placed in the finalize block.
+            self.phis = place_phis(&Cfg::new(&finalize.block));
+
             let block = Block {
                 span: finalize.block.span,
+                id: finalize.block.id,
                 statements: self.consume_block(finalize.block),
             };
 
@@ -70,6 +82,7 @@ impl FunctionConsumer for StaticSingleAssigner {
                 output_type: finalize.output_type,
                 block,
                 span: finalize.span,
+                id: finalize.id,
             }
         });
 
@@ -77,35 +90,40 @@ impl FunctionConsumer for StaticSingleAssigner {
             annotations: function.annotations,
             call_type: function.call_type,
             identifier: function.identifier,
+            type_parameters: function.type_parameters,
             input: function.input,
             output: function.output,
             output_type: function.output_type,
             block,
             finalize,
             span: function.span,
+            id: function.id,
         }
     }
 }
 
-impl ProgramScopeConsumer for StaticSingleAssigner {
+impl ProgramScopeConsumer for StaticSingleAssigner<'_> {
     type Output = ProgramScope;
 
     fn consume_program_scope(&mut self, input: ProgramScope) -> Self::Output {
         ProgramScope {
             program_id: input.program_id,
+            consts: input.consts,
             structs: input.structs,
+            enums: input.enums,
             mappings: input.mappings,
             functions: input
                 .functions
                 .into_iter()
                 .map(|(i, f)| (i, self.consume_function(f)))
                 .collect(),
+            default_integer_type: input.default_integer_type,
             span: input.span,
         }
     }
 }
 
-impl ProgramConsumer for StaticSingleAssigner {
+impl ProgramConsumer for StaticSingleAssigner<'_> {
     type Output = Program;
 
     fn consume_program(&mut self, input: Program) -> Self::Output {
@@ -115,6 +133,7 @@ impl ProgramConsumer for StaticSingleAssigner {
                 .into_iter()
                 .map(|(name, import)| (name, self.consume_program(import)))
                 .collect(),
+            modules: input.modules,
             program_scopes: input
                 .program_scopes
                 .into_iter()
@@ -14,20 +14,60 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{RenameTable, StaticSingleAssigner};
+use crate::{ScopeIndex, StaticSingleAssigner};
 
 use leo_ast::{
-    AssignStatement, Block, ConditionalStatement, ConsoleFunction, ConsoleStatement, DecrementStatement,
-    DefinitionStatement, Expression, ExpressionConsumer, FinalizeStatement, Identifier, IncrementStatement,
-    IterationStatement, ReturnStatement, Statement, StatementConsumer, TernaryExpression,
+    AccessExpression, AssertStatement, AssertVariant, AssignStatement, AssociatedFunction, Block, CfgStatement,
+    ConditionalStatement, DecrementStatement, DefinitionStatement, Expression, ExpressionConsumer, ExpressionStatement,
+    FinalizeStatement, Identifier, IncrementStatement, IterationStatement, MatchStatement, ReturnStatement, Statement,
+    StatementConsumer, TernaryExpression,
 };
 use leo_span::Symbol;
 
 use indexmap::IndexSet;
 
-impl StatementConsumer for StaticSingleAssigner {
+impl StatementConsumer for StaticSingleAssigner<'_> {
     type Output = Vec<Statement>;
 
+    /// Consumes the expressions in an `AssertStatement`, returning the list of simplified statements.
+    fn consume_assert(&mut self, input: AssertStatement) -> Self::Output {
+        let (variant, mut statements) = match input.variant {
+            AssertVariant::Assert(expr) => {
+                let (expr, statements) = self.consume_expression(expr);
+                (AssertVariant::Assert(expr), statements)
+            }
+            AssertVariant::AssertEq(left, right) => {
+                // Reconstruct the lhs of the binary expression.
+                let (left, mut statements) = self.consume_expression(left);
+                // Reconstruct the rhs of the binary expression.
+                let (right, right_statements) = self.consume_expression(right);
+                // Accumulate any statements produced.
+                statements.extend(right_statements);
+
+                (AssertVariant::AssertEq(left, right), statements)
+            }
+            AssertVariant::AssertNeq(left, right) => {
+                // Reconstruct the lhs of the binary expression.
+                let (left, mut statements) = self.consume_expression(left);
+                // Reconstruct the rhs of the binary expression.
+                let (right, right_statements) = self.consume_expression(right);
+                // Accumulate any statements produced.
+                statements.extend(right_statements);
+
+                (AssertVariant::AssertNeq(left, right), statements)
+            }
+        };
+
+        // Add the assert statement to the list of produced statements.
+        statements.push(Statement::Assert(AssertStatement {
+            variant,
+            span: input.span,
+            id: input.id,
+        }));
+
+        statements
+    }
+
     /// Consume all `AssignStatement`s, renaming as necessary.
     fn consume_assign(&mut self, assign: AssignStatement) -> Self::Output {
         // First consume the right-hand-side of the assignment.
@@ -42,7 +82,10 @@ impl StatementConsumer for StaticSingleAssigner {
         };
         self.is_lhs = false;
 
-        statements.push(self.assigner.simple_assign_statement(place, value));
+        statements.push(
+            self.assigner
+                .simple_assign_statement(place, value, assign.span, assign.id),
+        );
 
         statements
     }
@@ -57,6 +100,7 @@ impl StatementConsumer for StaticSingleAssigner {
     }
 
     /// Consumes a `ConditionalStatement`, producing phi functions (assign statements) for variables written in the then-block and otherwise-block.
+    /// Phi functions are only produced for the variables that may be read after the `ConditionalStatement`, as decided by the `Cfg` of the function.
     /// For more information on phi functions, see https://en.wikipedia.org/wiki/Static_single_assignment_form.
     /// Furthermore a new `AssignStatement` is introduced for non-trivial expressions in the condition of `ConditionalStatement`s.
     /// For example,
@@ -73,6 +117,7 @@ impl StatementConsumer for StaticSingleAssigner {
         // Consume the then-block.
         let then = Block {
             span: conditional.then.span,
+            id: conditional.then.id,
             statements: self.consume_block(conditional.then),
         };
 
@@ -86,10 +131,12 @@ impl StatementConsumer for StaticSingleAssigner {
         let otherwise = conditional.otherwise.map(|otherwise| Box::new(Statement::Block(match *otherwise {
             Statement::Block(block) => Block {
                 span: block.span,
+                id: block.id,
                 statements: self.consume_block(block),
             },
             Statement::Conditional(conditional) => Block {
                 span: conditional.span,
+                id: conditional.id,
                 statements: self.consume_conditional(conditional),
             },
             _ => unreachable!("Type checking guarantees that the otherwise-block of a conditional statement is a block or another conditional statement."),
@@ -104,51 +151,80 @@ impl StatementConsumer for StaticSingleAssigner {
             condition: condition.clone(),
             then,
             otherwise,
+            id: conditional.id,
         }));
 
         // Compute the write set for the variables written in the then-block or otherwise-block.
-        let if_write_set: IndexSet<&Symbol> = IndexSet::from_iter(if_table.local_names());
-        let else_write_set: IndexSet<&Symbol> = IndexSet::from_iter(else_table.local_names());
-        let write_set = if_write_set.union(&else_write_set);
+        let if_write_set: IndexSet<Symbol> = self.rename_table.local_names(if_table).copied().collect();
+        let else_write_set: IndexSet<Symbol> = self.rename_table.local_names(else_table).copied().collect();
+        let write_set: Vec<Symbol> = if_write_set.union(&else_write_set).copied().collect();
+
+        // The phi functions are generated from the conditional statement, so they are given its span, marked as synthetic.
+        let span = conditional.span.synthesized();
+
+        // The variables that may be read after the `ConditionalStatement` before they are written again.
+        let phis = self.phis.get(&conditional.id).cloned().unwrap_or_default();
 
         // For each variable in the write set, instantiate and add a phi function to the list of produced statements.
         for symbol in write_set {
-            // Note that phi functions only need to be instantiated if the variable exists before the `ConditionalStatement`.
-            if self.rename_table.lookup(**symbol).is_some() {
+            // Note that phi functions only need to be instantiated if the variable exists before the `ConditionalStatement`,
+            // and if its value may be read afterwards.
+            if self.rename_table.lookup(symbol).is_some() && phis.contains(&symbol) {
+                // Create a new name for the variable written to in the `ConditionalStatement`.
+                let new_name = self.new_name(symbol);
+
                 // Helper to lookup a symbol and create an argument for the phi function.
-                let create_phi_argument = |table: &RenameTable, symbol: Symbol| {
-                    let name = *table
-                        .lookup(symbol)
+                // The argument is a new use of the variable, which is given the type of the identifier that defines it.
+                let mut create_phi_argument = |table: ScopeIndex, symbol: Symbol| {
+                    let name = self
+                        .rename_table
+                        .lookup_from(table, symbol)
                         .unwrap_or_else(|| panic!("Symbol {} should exist in the program.", symbol));
-                    Box::new(Expression::Identifier(Identifier {
-                        name,
-                        span: Default::default(),
-                    }))
+                    let definition = self.definitions[name];
+                    let argument = Identifier {
+                        name: definition.name,
+                        span,
+                        id: self.node_builder.next_id(),
+                    };
+                    if let Some(type_) = self.type_table.get(&definition).cloned() {
+                        self.type_table.insert(&argument, type_);
+                    }
+                    Box::new(Expression::Identifier(argument))
                 };
 
-                // Create a new name for the variable written to in the `ConditionalStatement`.
-                let new_name = self.assigner.unique_symbol(symbol);
-
-                let (value, stmts) = self.consume_ternary(TernaryExpression {
+                let if_true = create_phi_argument(if_table, symbol);
+                let if_false = create_phi_argument(else_table, symbol);
+                let ternary = TernaryExpression {
                     condition: Box::new(condition.clone()),
-                    if_true: create_phi_argument(&if_table, **symbol),
-                    if_false: create_phi_argument(&else_table, **symbol),
-                    span: Default::default(),
-                });
+                    if_true,
+                    if_false,
+                    span,
+                    id: self.node_builder.next_id(),
+                };
+                let type_ = self.type_table.get(&*ternary.if_true).cloned();
+                if let Some(type_) = &type_ {
+                    self.type_table.insert(&ternary, type_.clone());
+                }
+                let (value, stmts) = self.consume_ternary(ternary);
 
                 statements.extend(stmts);
 
                 // Create a new `AssignStatement` for the phi function.
-                let assignment = self.assigner.simple_assign_statement(
-                    Identifier {
-                        name: new_name,
-                        span: Default::default(),
-                    },
-                    value,
-                );
+                let place = Identifier {
+                    name: new_name,
+                    span,
+                    id: self.node_builder.next_id(),
+                };
+                if let Some(type_) = type_ {
+                    self.type_table.insert(&place, type_);
+                }
+                self.definitions.insert(new_name, place);
+                let assignment = self
+                    .assigner
+                    .simple_assign_statement(place, value, span, self.node_builder.next_id());
 
                 // Update the `RenameTable` with the new name of the variable.
-                self.rename_table.update(*(*symbol), new_name);
+                self.rename_table.update(symbol, new_name);
 
                 // Store the generated phi function.
                 statements.push(assignment);
@@ -158,44 +234,6 @@ impl StatementConsumer for StaticSingleAssigner {
         statements
     }
 
-    /// Consumes the expressions in a `ConsoleStatement`, returning the list of simplified statements.
-    fn consume_console(&mut self, input: ConsoleStatement) -> Self::Output {
-        let (function, mut statements) = match input.function {
-            ConsoleFunction::Assert(expr) => {
-                let (expr, statements) = self.consume_expression(expr);
-                (ConsoleFunction::Assert(expr), statements)
-            }
-            ConsoleFunction::AssertEq(left, right) => {
-                // Reconstruct the lhs of the binary expression.
-                let (left, mut statements) = self.consume_expression(left);
-                // Reconstruct the rhs of the binary expression.
-                let (right, right_statements) = self.consume_expression(right);
-                // Accumulate any statements produced.
-                statements.extend(right_statements);
-
-                (ConsoleFunction::AssertEq(left, right), statements)
-            }
-            ConsoleFunction::AssertNeq(left, right) => {
-                // Reconstruct the lhs of the binary expression.
-                let (left, mut statements) = self.consume_expression(left);
-                // Reconstruct the rhs of the binary expression.
-                let (right, right_statements) = self.consume_expression(right);
-                // Accumulate any statements produced.
-                statements.extend(right_statements);
-
-                (ConsoleFunction::AssertNeq(left, right), statements)
-            }
-        };
-
-        // Add the console statement to the list of produced statements.
-        statements.push(Statement::Console(ConsoleStatement {
-            function,
-            span: input.span,
-        }));
-
-        statements
-    }
-
     /// Consumes the expressions associated with the `DecrementStatement`, returning the simplified `DecrementStatement`.
     fn consume_decrement(&mut self, input: DecrementStatement) -> Self::Output {
         // First consume the expression associated with the amount.
@@ -210,26 +248,103 @@ impl StatementConsumer for StaticSingleAssigner {
             index,
             amount,
             span: input.span,
+            id: input.id,
         }));
 
         statements
     }
 
     /// Consumes the `DefinitionStatement` into an `AssignStatement`, renaming the left-hand-side as appropriate.
+    /// The tuple lowering pass guarantees that a destructuring definition destructures a call, whose outputs are assigned to the variables directly.
     fn consume_definition(&mut self, definition: DefinitionStatement) -> Self::Output {
-        // First consume the right-hand-side of the definition.
-        let (value, mut statements) = self.consume_expression(definition.value);
+        match definition.place {
+            Expression::Identifier(identifier) => {
+                // First consume the right-hand-side of the definition.
+                let (value, mut statements) = self.consume_expression(definition.value);
+
+                // Then assign a new unique name to the left-hand-side of the definition.
+                // Note that this order is necessary to ensure that the right-hand-side uses the correct name when consuming a complex assignment.
+                self.is_lhs = true;
+                let identifier = match self.consume_identifier(identifier).0 {
+                    Expression::Identifier(identifier) => identifier,
+                    _ => unreachable!("`self.consume_identifier` will always return an `Identifier`."),
+                };
+                self.is_lhs = false;
+                statements.push(self.assigner.simple_assign_statement(
+                    identifier,
+                    value,
+                    definition.span,
+                    definition.id,
+                ));
 
-        // Then assign a new unique name to the left-hand-side of the definition.
-        // Note that this order is necessary to ensure that the right-hand-side uses the correct name when consuming a complex assignment.
-        self.is_lhs = true;
-        let identifier = match self.consume_identifier(definition.variable_name).0 {
-            Expression::Identifier(identifier) => identifier,
-            _ => unreachable!("`self.consume_identifier` will always return an `Identifier`."),
+                statements
+            }
+            Expression::Tuple(tuple) => {
+                let call = match definition.value {
+                    Expression::Call(call) => call,
+                    _ => unreachable!("The tuple lowering pass guarantees that only calls are destructured."),
+                };
+                // First consume the arguments of the call.
+                let (call, mut statements) = self.consume_call_arguments(call);
+
+                // Then assign a new unique name to each of the variables.
+                self.is_lhs = true;
+                let identifiers = tuple
+                    .elements
+                    .into_iter()
+                    .map(|element| match element {
+                        Expression::Identifier(identifier) => match self.consume_identifier(identifier).0 {
+                            Expression::Identifier(identifier) => identifier,
+                            _ => unreachable!("`self.consume_identifier` will always return an `Identifier`."),
+                        },
+                        _ => unreachable!("The parser guarantees that destructured variables are identifiers."),
+                    })
+                    .collect();
+                self.is_lhs = false;
+                statements.push(self.assigner.tuple_assign_statement(
+                    identifiers,
+                    Expression::Call(call),
+                    definition.span,
+                    definition.id,
+                    self.node_builder,
+                ));
+
+                statements
+            }
+            _ => unreachable!("Type checking guarantees that the left-hand side of a definition is well-formed."),
+        }
+    }
+
+    /// Consumes the arguments of the associated function call in an `ExpressionStatement`, returning a simplified `ExpressionStatement`.
+    /// Note that the call itself is not assigned to a variable, since it is only evaluated for its effects.
+    fn consume_expression_statement(&mut self, input: ExpressionStatement) -> Self::Output {
+        let function = match input.expression {
+            Expression::Access(AccessExpression::AssociatedFunction(function)) => function,
+            _ => unreachable!("The parser guarantees that expression statements are associated function calls."),
         };
-        self.is_lhs = false;
 
-        statements.push(self.assigner.simple_assign_statement(identifier, value));
+        let mut statements = Vec::new();
+        let args = function
+            .args
+            .into_iter()
+            .map(|arg| {
+                let (arg, stmts) = self.consume_expression(arg);
+                statements.extend(stmts);
+                arg
+            })
+            .collect();
+
+        statements.push(Statement::Expression(ExpressionStatement {
+            expression: Expression::Access(AccessExpression::AssociatedFunction(AssociatedFunction {
+                ty: function.ty,
+                name: function.name,
+                args,
+                span: function.span,
+                id: function.id,
+            })),
+            span: input.span,
+            id: input.id,
+        }));
 
         statements
     }
@@ -253,6 +368,7 @@ impl StatementConsumer for StaticSingleAssigner {
         statements.push(Statement::Finalize(FinalizeStatement {
             arguments,
             span: input.span,
+            id: input.id,
         }));
 
         statements
@@ -272,6 +388,7 @@ impl StatementConsumer for StaticSingleAssigner {
             index,
             amount,
             span: input.span,
+            id: input.id,
         }));
 
         statements
@@ -282,6 +399,14 @@ impl StatementConsumer for StaticSingleAssigner {
         unreachable!("`IterationStatement`s should not be in the AST at this phase of compilation.");
     }
 
+    fn consume_cfg(&mut self, _input: CfgStatement) -> Self::Output {
+        unreachable!("`CfgStatement`s should not be in the AST at this phase of compilation.");
+    }
+
+    fn consume_match(&mut self, _input: MatchStatement) -> Self::Output {
+        unreachable!("`MatchStatement`s should not be in the AST at this phase of compilation.");
+    }
+
     /// Reconstructs the expression associated with the return statement, returning a simplified `ReturnStatement`.
     /// Note that type checking guarantees that there is at most one `ReturnStatement` in a block.
     fn consume_return(&mut self, input: ReturnStatement) -> Self::Output {
@@ -292,6 +417,7 @@ impl StatementConsumer for StaticSingleAssigner {
         statements.push(Statement::Return(ReturnStatement {
             expression,
             span: input.span,
+            id: input.id,
         }));
 
         statements
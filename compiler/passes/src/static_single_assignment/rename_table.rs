@@ -14,45 +14,93 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::ScopeIndex;
+
 use leo_span::Symbol;
 
 use indexmap::IndexMap;
 
-/// `RenameTable` tracks the names assigned by static single assignment in a single scope.
+/// The names assigned by static single assignment in a single scope.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
-pub(crate) struct RenameTable {
-    /// The `RenameTable` of the parent scope.
-    pub(crate) parent: Option<Box<RenameTable>>,
+struct RenameScope {
+    /// The index of the parent scope, if any.
+    parent: Option<ScopeIndex>,
     /// The mapping from names in the original AST to new names in the renamed AST.
     mapping: IndexMap<Symbol, Symbol>,
 }
 
-impl RenameTable {
-    /// Create a new `RenameTable` with the given parent.
-    pub(crate) fn new(parent: Option<Box<RenameTable>>) -> Self {
+/// `RenameTable` tracks the names assigned by static single assignment in an arena of scopes, which are addressed by their index.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct RenameTable {
+    /// The scopes that have been created. The first scope is the outermost scope.
+    scopes: Vec<RenameScope>,
+    /// The scope that is currently being traversed.
+    current: ScopeIndex,
+    /// The number of new names created for each symbol, which is shared by all scopes so that every new name is unique.
+    counters: IndexMap<Symbol, usize>,
+}
+
+impl Default for RenameTable {
+    fn default() -> Self {
         Self {
-            parent,
-            mapping: IndexMap::new(),
+            scopes: vec![RenameScope::default()],
+            current: ScopeIndex::PROGRAM,
+            counters: IndexMap::new(),
         }
     }
+}
+
+impl RenameTable {
+    /// Creates a new scope whose parent is the current scope, and enters it.
+    pub(crate) fn push(&mut self) -> ScopeIndex {
+        let index = ScopeIndex(self.scopes.len());
+        self.scopes.push(RenameScope {
+            parent: Some(self.current),
+            mapping: IndexMap::new(),
+        });
+        self.current = index;
+        index
+    }
+
+    /// Exits the current scope, returning its index so that its names can still be looked up.
+    /// The outermost scope is never exited.
+    pub(crate) fn pop(&mut self) -> ScopeIndex {
+        let index = self.current;
+        self.current = self.scopes[index.0].parent.unwrap_or(ScopeIndex::PROGRAM);
+        index
+    }
 
-    /// Returns the symbols that were renamed in the current scope.
-    pub(crate) fn local_names(&self) -> impl Iterator<Item = &Symbol> {
-        self.mapping.keys()
+    /// Returns the symbols that were renamed in the scope at `index`.
+    pub(crate) fn local_names(&self, index: ScopeIndex) -> impl Iterator<Item = &Symbol> {
+        self.scopes[index.0].mapping.keys()
     }
 
-    /// Updates `self.mapping` with the desired entry.
-    /// Creates a new entry if `symbol` is not already in `self.mapping`.
+    /// Returns a new name for `symbol`, numbered by how many names were created for it before, e.g. `x$0`, `x$1`, and so on.
+    /// The name is internal, so it cannot collide with an identifier in the program.
+    pub(crate) fn fresh_name(&mut self, symbol: Symbol) -> Symbol {
+        let counter = self.counters.entry(symbol).or_default();
+        *counter += 1;
+        Symbol::intern_internal(&format!("{}${}", symbol, *counter - 1))
+    }
+
+    /// Updates the mapping of the current scope with the desired entry.
+    /// Creates a new entry if `symbol` is not already in the mapping.
     pub(crate) fn update(&mut self, symbol: Symbol, new_symbol: Symbol) {
-        self.mapping.insert(symbol, new_symbol);
+        self.scopes[self.current.0].mapping.insert(symbol, new_symbol);
     }
 
-    /// Looks up the new name for `symbol`, recursively checking the parent if it is not found.
+    /// Looks up the new name for `symbol` in the current scope, recursively checking the parent if it is not found.
     pub(crate) fn lookup(&self, symbol: Symbol) -> Option<&Symbol> {
-        if let Some(var) = self.mapping.get(&symbol) {
+        self.lookup_from(self.current, symbol)
+    }
+
+    /// Looks up the new name for `symbol` in the scope at `index`, recursively checking the parent if it is not found.
+    pub(crate) fn lookup_from(&self, index: ScopeIndex, symbol: Symbol) -> Option<&Symbol> {
+        let scope = &self.scopes[index.0];
+        if let Some(var) = scope.mapping.get(&symbol) {
             Some(var)
-        } else if let Some(parent) = &self.parent {
-            parent.lookup(symbol)
+        } else if let Some(parent) = scope.parent {
+            self.lookup_from(parent, symbol)
         } else {
             None
         }
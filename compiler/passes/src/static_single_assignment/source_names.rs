@@ -0,0 +1,131 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{
+    AssignStatement, Block, Expression, ExpressionReconstructor, Finalize, Function, Identifier, Input, Program,
+    ProgramReconstructor, Statement, StatementReconstructor,
+};
+use leo_span::Symbol;
+
+use indexmap::{IndexMap, IndexSet};
+
+/// Returns the name in the source code of a variable renamed by static single assignment, e.g. `x` for `x$7`.
+/// Returns `None` for variables that were not renamed, and for variables generated by the compiler, e.g. `$var$3`.
+pub fn source_name(name: Symbol) -> Option<String> {
+    if !name.is_internal() {
+        return None;
+    }
+    match name.to_string().split_once('$') {
+        Some((source, _)) if !source.is_empty() => Some(source.to_string()),
+        _ => None,
+    }
+}
+
+/// Renames the variables of `program` that were renamed by static single assignment back to their names in the source code,
+/// so that ASTs written after static single assignment can be read without knowing how the compiler names variables.
+/// If a function has several versions of a variable `x`, they are named `x#1`, `x#2`, and so on, in the order that they appear,
+/// since `#` cannot occur in a Leo identifier. The result is intended for display only, and should not be compiled further.
+pub fn restore_source_names(program: Program) -> Program {
+    SourceNamer::default().reconstruct_program(program)
+}
+
+/// Renames variables back to their names in the source code, one function body or finalize block at a time.
+#[derive(Default)]
+struct SourceNamer {
+    /// The variables of the current block, in the order that they appear, if they are being collected.
+    collected: Option<IndexSet<Symbol>>,
+    /// A mapping from the variables of the current block to their display names.
+    renames: IndexMap<Symbol, Symbol>,
+}
+
+impl SourceNamer {
+    /// Renames the variables of `block`, whose inputs are `inputs`.
+    fn rename_block(&mut self, inputs: &[Input], block: Block) -> Block {
+        // Collect the variables of the block, starting with its inputs, which are never renamed.
+        self.collected = Some(inputs.iter().map(|input| input.identifier().name).collect());
+        self.reconstruct_block(block.clone());
+        let variables = self.collected.take().unwrap_or_default();
+
+        // Group the renamed variables by their name in the source code.
+        let mut versions: IndexMap<String, Vec<Symbol>> = IndexMap::new();
+        for variable in &variables {
+            if let Some(source) = source_name(*variable) {
+                versions.entry(source).or_default().push(*variable);
+            }
+        }
+
+        self.renames = versions
+            .into_iter()
+            .flat_map(|(source, symbols)| {
+                // A variable keeps its source name if no other variable of the block has the same source name.
+                let is_unique = symbols.len() == 1 && !variables.contains(&Symbol::intern(&source));
+                symbols
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(index, symbol)| match is_unique {
+                        true => (symbol, Symbol::intern(&source)),
+                        false => (symbol, Symbol::intern(&format!("{source}#{}", index + 1))),
+                    })
+            })
+            .collect();
+
+        let block = self.reconstruct_block(block).0;
+        self.renames.clear();
+        block
+    }
+}
+
+impl ExpressionReconstructor for SourceNamer {
+    type AdditionalOutput = ();
+
+    fn reconstruct_identifier(&mut self, input: Identifier) -> (Expression, Self::AdditionalOutput) {
+        if let Some(collected) = &mut self.collected {
+            collected.insert(input.name);
+        }
+        let name = self.renames.get(&input.name).copied().unwrap_or(input.name);
+        (Expression::Identifier(Identifier { name, ..input }), Default::default())
+    }
+}
+
+impl StatementReconstructor for SourceNamer {
+    /// Renames the variables that are assigned, as well as those that are read.
+    fn reconstruct_assign(&mut self, input: AssignStatement) -> (Statement, Self::AdditionalOutput) {
+        (
+            Statement::Assign(Box::new(AssignStatement {
+                place: self.reconstruct_expression(input.place).0,
+                value: self.reconstruct_expression(input.value).0,
+                span: input.span,
+                id: input.id,
+            })),
+            Default::default(),
+        )
+    }
+}
+
+impl ProgramReconstructor for SourceNamer {
+    fn reconstruct_function(&mut self, input: Function) -> Function {
+        let finalize = input.finalize.map(|finalize| Finalize {
+            block: self.rename_block(&finalize.input, finalize.block),
+            ..finalize
+        });
+
+        Function {
+            block: self.rename_block(&input.input, input.block),
+            finalize,
+            ..input
+        }
+    }
+}
@@ -14,36 +14,104 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Assigner, RenameTable};
+use crate::{Assigner, RenameTable, ScopeIndex, TypeTable};
 
-pub struct StaticSingleAssigner {
+use leo_ast::{CallExpression, Expression, ExpressionConsumer, Identifier, NodeBuilder, NodeId, Statement};
+use leo_span::Symbol;
+
+use indexmap::{IndexMap, IndexSet};
+
+pub struct StaticSingleAssigner<'a> {
     /// The `RenameTable` for the current basic block in the AST
     pub(crate) rename_table: RenameTable,
     /// A flag to determine whether or not the traversal is on the left-hand side of a definition or an assignment.
     pub(crate) is_lhs: bool,
     /// An struct used to construct (unique) assignment statements.
     pub(crate) assigner: Assigner,
+    /// A counter used to generate unique ids for the nodes that the pass creates.
+    pub(crate) node_builder: &'a NodeBuilder,
+    /// The types of the nodes in the program, to which the types of the variables introduced by the pass are added.
+    pub(crate) type_table: &'a mut TypeTable,
+    /// The identifier that defines each variable of the renamed program.
+    /// The arguments of phi functions are copies of these identifiers, so that they share their types.
+    pub(crate) definitions: IndexMap<Symbol, Identifier>,
+    /// Whether renamed variables are numbered by the counter of the `Assigner`, which is shared by all variables, e.g. `x$37`,
+    /// rather than by a counter for each variable, e.g. `x$2`.
+    pub(crate) global_numbering: bool,
+    /// The variables that need a phi function after each conditional statement of the current function, by the id of the conditional statement.
+    pub(crate) phis: IndexMap<NodeId, IndexSet<Symbol>>,
 }
 
-impl StaticSingleAssigner {
+impl<'a> StaticSingleAssigner<'a> {
     /// Initializes a new `StaticSingleAssigner` with an empty `RenameTable`.
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(node_builder: &'a NodeBuilder, type_table: &'a mut TypeTable, global_numbering: bool) -> Self {
         Self {
-            rename_table: RenameTable::new(None),
+            rename_table: RenameTable::default(),
             is_lhs: false,
             assigner: Assigner::default(),
+            node_builder,
+            type_table,
+            definitions: IndexMap::new(),
+            global_numbering,
+            phis: IndexMap::new(),
         }
     }
 
     /// Pushes a new scope, setting the current scope as the new scope's parent.
-    pub(crate) fn push(&mut self) {
-        let parent_table = core::mem::take(&mut self.rename_table);
-        self.rename_table = RenameTable::new(Some(Box::from(parent_table)));
+    pub(crate) fn push(&mut self) -> ScopeIndex {
+        self.rename_table.push()
+    }
+
+    /// Returns to the parent of the current scope, returning the index of the scope that was exited.
+    pub(crate) fn pop(&mut self) -> ScopeIndex {
+        self.rename_table.pop()
+    }
+
+    /// Returns a new unique name for a variable that is assigned to.
+    pub(crate) fn new_name(&mut self, symbol: Symbol) -> Symbol {
+        match self.global_numbering {
+            true => self.assigner.unique_symbol(symbol),
+            false => self.rename_table.fresh_name(symbol),
+        }
+    }
+
+    /// Constructs a simple assign statement for `expr` with a unique name, giving the new variable the type of `expr`.
+    pub(crate) fn unique_simple_assign_statement(&mut self, expr: Expression) -> (Identifier, Statement) {
+        let type_ = self.type_table.get(&expr).cloned();
+        let (place, statement) = self.assigner.unique_simple_assign_statement(expr, self.node_builder);
+        if let Some(type_) = type_ {
+            self.type_table.insert(&place, type_);
+        }
+        (place, statement)
     }
 
-    /// If the RenameTable has a parent, then `self.rename_table` is set to the parent, otherwise it is set to a default `RenameTable`.
-    pub(crate) fn pop(&mut self) -> RenameTable {
-        let parent = self.rename_table.parent.clone().unwrap_or_default();
-        core::mem::replace(&mut self.rename_table, *parent)
+    /// Consumes the arguments of a call without visiting the function name, accumulating any statements that are generated.
+    /// Note that the call itself is not assigned to a new variable.
+    pub(crate) fn consume_call_arguments(&mut self, input: CallExpression) -> (CallExpression, Vec<Statement>) {
+        let mut statements = Vec::new();
+
+        // Process the arguments, accumulating any statements produced.
+        let arguments = input
+            .arguments
+            .into_iter()
+            .map(|argument| {
+                let (argument, mut stmts) = self.consume_expression(argument);
+                statements.append(&mut stmts);
+                argument
+            })
+            .collect();
+
+        (
+            CallExpression {
+                // Note that we do not rename the function name.
+                function: input.function,
+                arguments,
+                type_arguments: input.type_arguments,
+                external: input.external,
+                span: input.span,
+                id: input.id,
+            },
+            statements,
+        )
     }
 }
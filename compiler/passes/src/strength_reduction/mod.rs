@@ -0,0 +1,72 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+//! The strength reduction pass traverses the AST after the range analysis pass and rewrites integer operations with a constant operand into cheaper ones.
+//! The rewrites are listed in the `REDUCTIONS` table, which pairs an operation and the constants it applies to with the operation that replaces it.
+//! Each rewrite produces the same result as the operation it replaces, and halts on the same inputs.
+//! For this reason, only wrapping multiplications by powers of two are rewritten into shifts, since a shift does not halt when bits are shifted out.
+//! Checked multiplications that provably cannot overflow are made wrapping by range analysis, so that they can be shifted too.
+//!
+//! Consider the following Leo code, after range analysis.
+//! ```leo
+//! function main(a: u8, b: u8) -> u8 {
+//!     $var$0 = a * 2u8;
+//!     $var$1 = b ** 2u8;
+//!     $var$2 = a.mul_wrapped(8u8);
+//!     $var$3 = $var$0 + $var$1;
+//!     $var$4 = $var$3 + $var$2;
+//!     return $var$4;
+//! }
+//! ```
+//!
+//! The strength reduction pass produces the following code.
+//! ```leo
+//! function main(a: u8, b: u8) -> u8 {
+//!     $var$0 = a + a;
+//!     $var$1 = b * b;
+//!     $var$2 = a.shl_wrapped(3u8);
+//!     $var$3 = $var$0 + $var$1;
+//!     $var$4 = $var$3 + $var$2;
+//!     return $var$4;
+//! }
+//! ```
+
+mod reduce_expression;
+
+mod reduce_program;
+
+pub mod reducer;
+pub use reducer::*;
+
+use crate::{Pass, PassSummary};
+
+use leo_ast::{Ast, NodeBuilder, ProgramReconstructor};
+use leo_errors::Result;
+
+impl<'a> Pass for StrengthReducer<'a> {
+    type Input = (Ast, &'a NodeBuilder);
+    type Output = Result<(Ast, PassSummary)>;
+
+    fn do_pass((ast, node_builder): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("strength reduction", None);
+        let mut reconstructor = StrengthReducer { node_builder };
+        let program = reconstructor.reconstruct_program(ast.into_repr());
+
+        let ast = Ast::new(program);
+        let summary = recorder.finish(None);
+
+        Ok((ast, summary))
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::StrengthReducer;
+
+use leo_ast::{BinaryExpression, Expression, ExpressionReconstructor};
+
+impl ExpressionReconstructor for StrengthReducer<'_> {
+    type AdditionalOutput = ();
+
+    /// Rewrites binary expressions with a constant operand into cheaper ones, e.g. `x * 2u32` into `x + x`.
+    fn reconstruct_binary(&mut self, input: BinaryExpression) -> (Expression, Self::AdditionalOutput) {
+        let left = self.reconstruct_expression(*input.left).0;
+        let right = self.reconstruct_expression(*input.right).0;
+
+        if let Some(reduced) = self.reduce(input.op, &left, &right, input.span) {
+            return (reduced, Default::default());
+        }
+
+        (
+            Expression::Binary(BinaryExpression {
+                left: Box::new(left),
+                right: Box::new(right),
+                op: input.op,
+                span: input.span,
+                id: input.id,
+            }),
+            Default::default(),
+        )
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{BinaryExpression, BinaryOperation, Expression, IntegerType, Literal, NodeBuilder};
+use leo_span::Span;
+
+/// The constants of an operand that a reduction applies to.
+pub enum Constant {
+    /// Exactly the given value.
+    Equals(u128),
+    /// Any power of two.
+    PowerOfTwo,
+}
+
+/// The right operand of the operation that a reduction produces.
+pub enum Operand {
+    /// The variable operand of the reduced operation, e.g. `x` in `x + x`.
+    Copy,
+    /// The base two logarithm of the constant operand, as a `u8`, e.g. `3u8` in `x.shl_wrapped(3u8)`.
+    Log2,
+}
+
+/// A rewrite of `x op c`, where `c` is an integer constant, into `x into operand`.
+pub struct Reduction {
+    /// The operation that is rewritten.
+    pub op: BinaryOperation,
+    /// Whether the constant may also be the left operand, as in `c op x`.
+    pub commutative: bool,
+    /// The constants that the rewrite applies to.
+    pub constant: Constant,
+    /// The operation that replaces `op`.
+    pub into: BinaryOperation,
+    /// The right operand of the replacing operation.
+    pub operand: Operand,
+}
+
+/// The strength reductions, in the order that they are tried.
+/// A reduction must produce the same result as the operation it rewrites, and halt on the same inputs.
+pub const REDUCTIONS: &[Reduction] = &[
+    // `x * 2` is `x + x`.
+    Reduction {
+        op: BinaryOperation::Mul,
+        commutative: true,
+        constant: Constant::Equals(2),
+        into: BinaryOperation::Add,
+        operand: Operand::Copy,
+    },
+    // `x.mul_wrapped(2)` is `x.add_wrapped(x)`.
+    Reduction {
+        op: BinaryOperation::MulWrapped,
+        commutative: true,
+        constant: Constant::Equals(2),
+        into: BinaryOperation::AddWrapped,
+        operand: Operand::Copy,
+    },
+    // `x.mul_wrapped(2 ** k)` is `x.shl_wrapped(k)`.
+    Reduction {
+        op: BinaryOperation::MulWrapped,
+        commutative: true,
+        constant: Constant::PowerOfTwo,
+        into: BinaryOperation::ShlWrapped,
+        operand: Operand::Log2,
+    },
+    // `x ** 2` is `x * x`.
+    Reduction {
+        op: BinaryOperation::Pow,
+        commutative: false,
+        constant: Constant::Equals(2),
+        into: BinaryOperation::Mul,
+        operand: Operand::Copy,
+    },
+    // `x.pow_wrapped(2)` is `x.mul_wrapped(x)`.
+    Reduction {
+        op: BinaryOperation::PowWrapped,
+        commutative: false,
+        constant: Constant::Equals(2),
+        into: BinaryOperation::MulWrapped,
+        operand: Operand::Copy,
+    },
+];
+
+pub struct StrengthReducer<'a> {
+    /// A counter used to generate unique ids for the nodes that the pass creates.
+    pub(crate) node_builder: &'a NodeBuilder,
+}
+
+impl<'a> StrengthReducer<'a> {
+    /// Rewrites `left op right` using the first reduction in `REDUCTIONS` that applies to it.
+    /// Returns `None` if no reduction applies.
+    pub(crate) fn reduce(
+        &self,
+        op: BinaryOperation,
+        left: &Expression,
+        right: &Expression,
+        span: Span,
+    ) -> Option<Expression> {
+        REDUCTIONS
+            .iter()
+            .filter(|reduction| reduction.op == op)
+            .find_map(|reduction| {
+                let mut operands = vec![(left, right)];
+                if reduction.commutative {
+                    operands.push((right, left));
+                }
+                operands.into_iter().find_map(|(variable, constant)| {
+                    let value = integer_constant(constant)?;
+                    let applies = match reduction.constant {
+                        Constant::Equals(expected) => value == expected,
+                        Constant::PowerOfTwo => value.is_power_of_two(),
+                    };
+                    if !applies {
+                        return None;
+                    }
+                    let operand = match reduction.operand {
+                        // The variable operand is duplicated, so it must not be a compound expression.
+                        Operand::Copy => match variable {
+                            Expression::Identifier(_) | Expression::Literal(_) => variable.clone(),
+                            _ => return None,
+                        },
+                        Operand::Log2 => Expression::Literal(Literal::Integer(
+                            IntegerType::U8,
+                            value.trailing_zeros().to_string(),
+                            Default::default(),
+                            self.node_builder.next_id(),
+                        )),
+                    };
+                    Some(Expression::Binary(BinaryExpression {
+                        left: Box::new(variable.clone()),
+                        right: Box::new(operand),
+                        op: reduction.into,
+                        span,
+                        id: self.node_builder.next_id(),
+                    }))
+                })
+            })
+    }
+}
+
+/// Returns the value of `expression` if it is a non-negative integer literal.
+fn integer_constant(expression: &Expression) -> Option<u128> {
+    match expression {
+        Expression::Literal(literal @ (Literal::Integer(_, value, _, _) | Literal::Unsuffixed(value, _, _, _))) => {
+            literal.integer_type()?;
+            value.replace('_', "").parse().ok()
+        }
+        _ => None,
+    }
+}
@@ -16,6 +16,7 @@
 
 use leo_ast::*;
 use leo_errors::emitter::Handler;
+use leo_span::Symbol;
 
 use crate::{SymbolTable, VariableSymbol, VariableType};
 
@@ -27,6 +28,8 @@ pub struct CreateSymbolTable<'a> {
     pub(crate) symbol_table: SymbolTable,
     /// The error handler.
     handler: &'a Handler,
+    /// The name of the program whose scope is being visited.
+    program: Option<Symbol>,
 }
 
 impl<'a> CreateSymbolTable<'a> {
@@ -34,6 +37,7 @@ impl<'a> CreateSymbolTable<'a> {
         Self {
             symbol_table: Default::default(),
             handler,
+            program: None,
         }
     }
 }
@@ -50,12 +54,32 @@ impl<'a> ProgramVisitor<'a> for CreateSymbolTable<'a> {
         self.visit_program(input)
     }
 
+    fn visit_program_scope(&mut self, input: &'a ProgramScope) {
+        // Record the program that declares the functions of the scope.
+        self.program = Some(input.program_id.name.name);
+
+        input.consts.values().for_each(|const_| self.visit_const(const_));
+        input.structs.values().for_each(|struct_| self.visit_struct(struct_));
+        input.enums.values().for_each(|enum_| self.visit_enum(enum_));
+        input.mappings.values().for_each(|mapping| self.visit_mapping(mapping));
+        input
+            .functions
+            .values()
+            .for_each(|function| self.visit_function(function));
+    }
+
     fn visit_struct(&mut self, input: &'a Struct) {
         if let Err(err) = self.symbol_table.insert_struct(input.name(), input) {
             self.handler.emit_err(err);
         }
     }
 
+    fn visit_enum(&mut self, input: &'a Enum) {
+        if let Err(err) = self.symbol_table.insert_enum(input.name(), input) {
+            self.handler.emit_err(err);
+        }
+    }
+
     fn visit_mapping(&mut self, input: &'a Mapping) {
         // Add the variable associated with the mapping to the symbol table.
         if let Err(err) = self.symbol_table.insert_variable(
@@ -74,7 +98,8 @@ impl<'a> ProgramVisitor<'a> for CreateSymbolTable<'a> {
     }
 
     fn visit_function(&mut self, input: &'a Function) {
-        if let Err(err) = self.symbol_table.insert_fn(input.name(), input) {
+        let program = self.program.expect("Functions are only declared in program scopes.");
+        if let Err(err) = self.symbol_table.insert_fn(input.name(), input, program) {
             self.handler.emit_err(err);
         }
     }
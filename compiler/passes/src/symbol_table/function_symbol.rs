@@ -15,12 +15,14 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use leo_ast::{CallType, Function, Input, Type};
-use leo_span::Span;
+use leo_span::{Span, Symbol};
 
-use crate::SymbolTable;
+use crate::{ScopeIndex, SymbolTable};
+
+use serde::{Deserialize, Serialize};
 
 /// Metadata associated with the finalize block.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FinalizeData {
     /// The inputs to the finalize block.
     pub(crate) input: Vec<Input>,
@@ -29,16 +31,20 @@ pub struct FinalizeData {
 }
 
 /// An entry for a function in the symbol table.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FunctionSymbol {
-    /// The index associated with the scope in the parent symbol table.
-    pub(crate) id: usize,
+    /// The index of the scope of the function in the symbol table.
+    pub(crate) id: ScopeIndex,
     /// The output type of the function.
     pub(crate) output_type: Type,
     /// Is this function a transition, inlined, or a regular function?.
     pub call_type: CallType,
     /// The `Span` associated with the function.
-    pub(crate) span: Span,
+    pub span: Span,
+    /// The name of the program that declares the function, e.g. `foo` in `foo.aleo`.
+    pub(crate) program: Symbol,
+    /// The names of the type parameters of a generic function.
+    pub(crate) type_parameters: Vec<Symbol>,
     /// The inputs to the function.
     pub(crate) input: Vec<Input>,
     /// Metadata associated with the finalize block.
@@ -46,12 +52,18 @@ pub struct FunctionSymbol {
 }
 
 impl SymbolTable {
-    pub(crate) fn new_function_symbol(id: usize, func: &Function) -> FunctionSymbol {
+    pub(crate) fn new_function_symbol(id: ScopeIndex, func: &Function, program: Symbol) -> FunctionSymbol {
         FunctionSymbol {
             id,
             output_type: func.output_type.clone(),
             call_type: func.call_type,
             span: func.span,
+            program,
+            type_parameters: func
+                .type_parameters
+                .iter()
+                .map(|parameter| parameter.identifier.name)
+                .collect(),
             input: func.input.clone(),
             finalize: func.finalize.as_ref().map(|finalize| FinalizeData {
                 input: finalize.input.clone(),
@@ -26,21 +26,22 @@ pub use table::*;
 pub mod variable_symbol;
 pub use variable_symbol::*;
 
-use crate::Pass;
+use crate::{Pass, PassSummary};
 
 use leo_ast::{Ast, ProgramVisitor};
 use leo_errors::{emitter::Handler, Result};
 
 impl<'a> Pass for CreateSymbolTable<'a> {
     type Input = (&'a Ast, &'a Handler);
-    type Output = Result<SymbolTable>;
+    type Output = Result<(SymbolTable, PassSummary)>;
 
     /// Runs the compiler pass.
     fn do_pass((ast, handler): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("symbol table", Some(handler));
         let mut visitor = CreateSymbolTable::new(handler);
         visitor.visit_program(ast.as_repr());
         handler.last_err()?;
 
-        Ok(visitor.symbol_table)
+        Ok((visitor.symbol_table, recorder.finish(Some(handler))))
     }
 }
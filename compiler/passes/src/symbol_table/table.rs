@@ -14,41 +14,136 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use std::cell::RefCell;
-
-use leo_ast::{Function, Struct};
+use leo_ast::{Enum, Function, Member, Struct, Type, Value};
 use leo_errors::{AstError, Result};
 use leo_span::{Span, Symbol};
 
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 
 use crate::{FunctionSymbol, VariableSymbol};
 
-#[derive(Clone, Debug, Default)]
-pub struct SymbolTable {
+/// The index of a scope in the arena of scopes of a symbol table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ScopeIndex(pub(crate) usize);
+
+impl ScopeIndex {
+    /// The scope of the program, which contains the mappings and constants, and the scope of each function.
+    pub const PROGRAM: ScopeIndex = ScopeIndex(0);
+}
+
+/// A scope of the symbol table, such as the scope of a function or a block.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Scope {
     /// The parent scope if it exists.
     /// For example, the parent scope of a then-block is the scope containing the associated ConditionalStatement.
-    pub(crate) parent: Option<Box<SymbolTable>>,
+    pub(crate) parent: Option<ScopeIndex>,
+    /// The sub-scopes of this scope, in the order that they were created.
+    pub(crate) children: Vec<ScopeIndex>,
+    /// The variables defined in the scope.
+    /// This field is populated as necessary.
+    pub(crate) variables: IndexMap<Symbol, VariableSymbol>,
+    /// The values of the constants defined in the scope whose values are known at compile time.
+    /// This field is populated as necessary.
+    pub(crate) constants: IndexMap<Symbol, Value>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SymbolTable {
     /// Functions represents the name of each function mapped to the AST's function definition.
     /// This field is populated at a first pass.
     pub functions: IndexMap<Symbol, FunctionSymbol>,
     /// Maps struct names to struct definitions.
     /// This field is populated at a first pass.
     pub structs: IndexMap<Symbol, Struct>,
-    /// The variables defined in a scope.
-    /// This field is populated as necessary.
-    pub(crate) variables: IndexMap<Symbol, VariableSymbol>,
-    /// The index of the current scope.
-    pub(crate) scope_index: usize,
-    /// The sub-scopes of this scope.
-    pub(crate) scopes: Vec<RefCell<SymbolTable>>,
+    /// Maps enum names to enum definitions.
+    /// This field is populated at a first pass.
+    pub enums: IndexMap<Symbol, Enum>,
+    /// The scopes of the program, addressed by their index. The first scope is the scope of the program.
+    pub(crate) scopes: Vec<Scope>,
+    /// The scope that is currently being traversed.
+    /// The current scope only changes while the program is traversed, so it is not serialized.
+    #[serde(skip)]
+    pub(crate) current: ScopeIndex,
+}
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        Self {
+            functions: IndexMap::new(),
+            structs: IndexMap::new(),
+            enums: IndexMap::new(),
+            scopes: vec![Scope::default()],
+            current: ScopeIndex::PROGRAM,
+        }
+    }
 }
 
 impl SymbolTable {
+    /// Serializes the symbol table into a JSON string, including the variables of every sub-scope.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)
+            .map_err(|e| AstError::failed_to_convert_symbol_table_to_json_string(&e))?)
+    }
+
+    /// Deserializes a symbol table from a JSON string produced by `to_json`.
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json).map_err(|e| AstError::failed_to_read_json_string_to_symbol_table(&e))?)
+    }
+
+    /// Returns the scope at `index`.
+    pub fn scope(&self, index: ScopeIndex) -> &Scope {
+        &self.scopes[index.0]
+    }
+
+    /// Returns the scope that is currently being traversed.
+    pub(crate) fn current_scope(&self) -> &Scope {
+        self.scope(self.current)
+    }
+
+    /// Returns a mutable reference to the scope that is currently being traversed.
+    pub(crate) fn current_scope_mut(&mut self) -> &mut Scope {
+        &mut self.scopes[self.current.0]
+    }
+
+    /// Returns the current scope followed by its ancestors, ending with the scope of the program.
+    fn ancestors(&self) -> impl Iterator<Item = &Scope> {
+        self.ancestors_of(self.current)
+    }
+
+    /// Returns the scope at `index` followed by its ancestors, ending with the scope of the program.
+    fn ancestors_of(&self, index: ScopeIndex) -> impl Iterator<Item = &Scope> {
+        std::iter::successors(Some(self.scope(index)), |scope| {
+            scope.parent.map(|parent| self.scope(parent))
+        })
+    }
+
+    /// Adds a new sub-scope to the scope at `parent`, returning its index.
+    fn add_scope(&mut self, parent: ScopeIndex) -> ScopeIndex {
+        let index = ScopeIndex(self.scopes.len());
+        self.scopes.push(Scope {
+            parent: Some(parent),
+            ..Default::default()
+        });
+        self.scopes[parent.0].children.push(index);
+        index
+    }
+
+    /// Adds a copy of the scope at `index` and its sub-scopes as a new sub-scope of `parent`, returning the index of the copy.
+    fn copy_scope(&mut self, index: ScopeIndex, parent: ScopeIndex) -> ScopeIndex {
+        let copy = self.add_scope(parent);
+        self.scopes[copy.0].variables = self.scope(index).variables.clone();
+        self.scopes[copy.0].constants = self.scope(index).constants.clone();
+        for child in self.scope(index).children.clone() {
+            self.copy_scope(child, copy);
+        }
+        copy
+    }
+
     /// Recursively checks if the symbol table contains an entry for the given symbol.
     /// Leo does not allow any variable shadowing or overlap between different symbols.
     pub fn check_shadowing(&self, symbol: Symbol, span: Span) -> Result<()> {
-        if self.variables.contains_key(&symbol) {
+        if self.ancestors().any(|scope| scope.variables.contains_key(&symbol)) {
             Err(AstError::shadowed_variable(symbol, span).into())
         } else if self.functions.contains_key(&symbol) {
             Err(AstError::shadowed_function(symbol, span).into())
@@ -57,30 +152,34 @@ impl SymbolTable {
                 true => Err(AstError::shadowed_record(symbol, span).into()),
                 false => Err(AstError::shadowed_struct(symbol, span).into()),
             }
-        } else if let Some(parent) = self.parent.as_ref() {
-            parent.check_shadowing(symbol, span)
+        } else if self.enums.contains_key(&symbol) {
+            Err(AstError::shadowed_enum(symbol, span).into())
         } else {
             Ok(())
         }
     }
 
-    /// Returns the current scope index.
-    /// Increments the scope index.
-    pub fn scope_index(&mut self) -> usize {
-        let index = self.scope_index;
-        self.scope_index += 1;
-        index
-    }
-
-    /// Inserts a function into the symbol table.
-    pub fn insert_fn(&mut self, symbol: Symbol, insert: &Function) -> Result<()> {
+    /// Inserts a function, declared by `program`, into the symbol table.
+    pub fn insert_fn(&mut self, symbol: Symbol, insert: &Function, program: Symbol) -> Result<()> {
         self.check_shadowing(symbol, insert.span)?;
-        let id = self.scope_index();
-        self.functions.insert(symbol, Self::new_function_symbol(id, insert));
-        self.scopes.push(Default::default());
+        let id = self.add_scope(self.current);
+        self.functions
+            .insert(symbol, Self::new_function_symbol(id, insert, program));
         Ok(())
     }
 
+    /// Inserts an instance of the generic function `generic` into the symbol table.
+    /// The instance is given a copy of the scopes of the generic function, which type checking has populated.
+    pub fn insert_fn_instance(&mut self, generic: Symbol, instance: &Function) {
+        let (scope, program) = match self.functions.get(&generic) {
+            Some(function) => (function.id, function.program),
+            None => unreachable!("Type checking guarantees that generic functions are declared."),
+        };
+        let id = self.copy_scope(scope, self.current);
+        self.functions
+            .insert(instance.name(), Self::new_function_symbol(id, instance, program));
+    }
+
     /// Inserts a struct into the symbol table.
     pub fn insert_struct(&mut self, symbol: Symbol, insert: &Struct) -> Result<()> {
         self.check_shadowing(symbol, insert.span)?;
@@ -88,88 +187,225 @@ impl SymbolTable {
         Ok(())
     }
 
-    /// Inserts a variable into the symbol table.
+    /// Inserts an instance of a generic struct into the symbol table.
+    /// Instances are named by monomorphization, which guarantees that their names are fresh.
+    pub fn insert_struct_instance(&mut self, instance: &Struct) {
+        self.structs.insert(instance.name(), instance.clone());
+    }
+
+    /// Inserts an enum into the symbol table.
+    pub fn insert_enum(&mut self, symbol: Symbol, insert: &Enum) -> Result<()> {
+        self.check_shadowing(symbol, insert.span)?;
+        self.enums.insert(symbol, insert.clone());
+        Ok(())
+    }
+
+    /// Inserts a variable into the current scope.
     pub fn insert_variable(&mut self, symbol: Symbol, insert: VariableSymbol) -> Result<()> {
         self.check_shadowing(symbol, insert.span)?;
-        self.variables.insert(symbol, insert);
+        self.current_scope_mut().variables.insert(symbol, insert);
         Ok(())
     }
 
-    /// Creates a new scope for the block and stores it in the symbol table.
-    pub fn insert_block(&mut self) -> usize {
-        self.scopes.push(RefCell::new(Default::default()));
-        self.scope_index()
+    /// Records the compile-time value of a constant variable of the current scope.
+    pub fn insert_constant(&mut self, symbol: Symbol, value: Value) {
+        self.current_scope_mut().constants.insert(symbol, value);
+    }
+
+    /// Creates a new sub-scope of the current scope for a block, returning its index.
+    pub fn insert_block(&mut self) -> ScopeIndex {
+        self.add_scope(self.current)
+    }
+
+    /// Enters the scope at `index`, which must be a sub-scope of the current scope.
+    pub fn enter_scope(&mut self, index: ScopeIndex) {
+        debug_assert_eq!(self.scope(index).parent, Some(self.current));
+        self.current = index;
+    }
+
+    /// Exits the current scope, returning to its parent.
+    pub fn exit_scope(&mut self) {
+        self.current = self
+            .current_scope()
+            .parent
+            .expect("The scope of the program is never exited.");
     }
 
     /// Attempts to lookup a function in the symbol table.
     pub fn lookup_fn_symbol(&self, symbol: Symbol) -> Option<&FunctionSymbol> {
-        if let Some(func) = self.functions.get(&symbol) {
-            Some(func)
-        } else if let Some(parent) = self.parent.as_ref() {
-            parent.lookup_fn_symbol(symbol)
-        } else {
-            None
-        }
+        self.functions.get(&symbol)
     }
 
     /// Attempts to lookup a struct in the symbol table.
     pub fn lookup_struct(&self, symbol: Symbol) -> Option<&Struct> {
-        if let Some(struct_) = self.structs.get(&symbol) {
-            Some(struct_)
-        } else if let Some(parent) = self.parent.as_ref() {
-            parent.lookup_struct(symbol)
-        } else {
-            None
-        }
+        self.structs.get(&symbol)
     }
 
-    /// Attempts to lookup a variable in the symbol table.
+    /// Attempts to lookup an enum in the symbol table.
+    pub fn lookup_enum(&self, symbol: Symbol) -> Option<&Enum> {
+        self.enums.get(&symbol)
+    }
+
+    /// Attempts to lookup a variable in the current scope and its ancestors.
     pub fn lookup_variable(&self, symbol: Symbol) -> Option<&VariableSymbol> {
-        if let Some(var) = self.variables.get(&symbol) {
-            Some(var)
-        } else if let Some(parent) = self.parent.as_ref() {
-            parent.lookup_variable(symbol)
-        } else {
-            None
-        }
+        self.ancestors().find_map(|scope| scope.variables.get(&symbol))
+    }
+
+    /// Attempts to lookup the compile-time value of a constant variable in the current scope and its ancestors.
+    pub fn lookup_constant(&self, symbol: Symbol) -> Option<&Value> {
+        self.ancestors().find_map(|scope| scope.constants.get(&symbol))
     }
 
     /// Returns true if the variable exists in the local scope
     pub fn variable_in_local_scope(&self, symbol: Symbol) -> bool {
-        self.variables.contains_key(&symbol)
+        self.current_scope().variables.contains_key(&symbol)
     }
 
     /// Returns true if the variable exists in any parent scope
     pub fn variable_in_parent_scope(&self, symbol: Symbol) -> bool {
-        if let Some(parent) = self.parent.as_ref() {
-            if parent.variables.contains_key(&symbol) {
-                true
-            } else {
-                parent.variable_in_parent_scope(symbol)
+        self.ancestors()
+            .skip(1)
+            .any(|scope| scope.variables.contains_key(&symbol))
+    }
+
+    /// Returns true if the variable exists in the scope at `index` or its ancestors.
+    pub fn variable_in_scope(&self, index: ScopeIndex, symbol: Symbol) -> bool {
+        self.ancestors_of(index)
+            .any(|scope| scope.variables.contains_key(&symbol))
+    }
+
+    /// Returns true if the variable exists in any scope, regardless of the scope that is currently being traversed.
+    pub(crate) fn variable_in_any_scope(&self, symbol: Symbol) -> bool {
+        self.scopes.iter().any(|scope| scope.variables.contains_key(&symbol))
+    }
+
+    /// Returns a mutable reference to the `VariableSymbol` if it exists in the current scope or its ancestors.
+    pub fn lookup_variable_mut(&mut self, symbol: Symbol) -> Option<&mut VariableSymbol> {
+        let mut index = Some(self.current);
+        while let Some(ScopeIndex(i)) = index {
+            if self.scopes[i].variables.contains_key(&symbol) {
+                return self.scopes[i].variables.get_mut(&symbol);
             }
-        } else {
-            false
+            index = self.scopes[i].parent;
         }
+        None
     }
 
-    /// Returns a mutable reference to the `VariableSymbol` if it exists in the symbol table.
-    pub fn lookup_variable_mut(&mut self, symbol: Symbol) -> Option<&mut VariableSymbol> {
-        if let Some(var) = self.variables.get_mut(&symbol) {
-            Some(var)
-        } else if let Some(parent) = self.parent.as_mut() {
-            parent.lookup_variable_mut(symbol)
-        } else {
-            None
+    /// Returns the index of the scope associated with the function symbol, if it exists in the symbol table.
+    pub fn lookup_fn_scope(&self, symbol: Symbol) -> Option<ScopeIndex> {
+        self.lookup_fn_symbol(symbol).map(|function| function.id)
+    }
+
+    /// Returns the index of the `n`th child of the current scope, counting the children in the order that they were created.
+    pub fn child_scope(&self, n: usize) -> Option<ScopeIndex> {
+        self.current_scope().children.get(n).copied()
+    }
+
+    /// Replaces the named types in the structs, functions, and variables of the symbol table by the types in `substitution`.
+    /// For example, this replaces each enum by the integer type that it is lowered to.
+    pub fn substitute_types(&mut self, substitution: &IndexMap<Symbol, Type>) {
+        self.map_types(|type_| type_.substitute(substitution));
+    }
+
+    /// Replaces each type in the structs, functions, and variables of the symbol table by the result of `map`.
+    pub fn map_types(&mut self, map: impl Fn(&Type) -> Type) {
+        for struct_ in self.structs.values_mut() {
+            for Member { type_, .. } in struct_.members.iter_mut() {
+                *type_ = map(type_);
+            }
+        }
+        for function in self.functions.values_mut() {
+            function.output_type = map(&function.output_type);
+            function.input = function.input.iter().map(|input| input.map_type(&map)).collect();
+            if let Some(finalize) = function.finalize.as_mut() {
+                finalize.output_type = map(&finalize.output_type);
+                finalize.input = finalize.input.iter().map(|input| input.map_type(&map)).collect();
+            }
+        }
+        for variable in self.scopes.iter_mut().flat_map(|scope| scope.variables.values_mut()) {
+            variable.type_ = map(&variable.type_);
+        }
+    }
+
+    /// Returns the variables defined in the sub-scopes of the current scope, recursively.
+    /// Once type checking has run, these are the inputs and local variables of every function.
+    pub fn variables_in_sub_scopes(&self) -> Vec<(Symbol, VariableSymbol)> {
+        let mut variables = Vec::new();
+        let mut pending: Vec<ScopeIndex> = self.current_scope().children.iter().rev().copied().collect();
+        while let Some(index) = pending.pop() {
+            let scope = self.scope(index);
+            variables.extend(
+                scope
+                    .variables
+                    .iter()
+                    .map(|(symbol, variable)| (*symbol, variable.clone())),
+            );
+            pending.extend(scope.children.iter().rev());
+        }
+        variables
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::VariableType;
+
+    use leo_ast::IntegerType;
+    use leo_span::symbol::{create_session_if_not_set_then, sym};
+
+    fn variable() -> VariableSymbol {
+        VariableSymbol {
+            type_: Type::Integer(IntegerType::U8),
+            span: Span::default(),
+            declaration: VariableType::Mut,
         }
     }
 
-    /// Returns the scope associated with the function symbol, if it exists in the symbol table.
-    pub fn lookup_fn_scope(&self, symbol: Symbol) -> Option<&RefCell<Self>> {
-        self.lookup_fn_symbol(symbol).and_then(|func| self.scopes.get(func.id))
+    #[test]
+    fn test_scopes() {
+        create_session_if_not_set_then(|_| {
+            let (a, b) = (Symbol::intern("a"), Symbol::intern("b"));
+            let mut symbol_table = SymbolTable::default();
+            symbol_table.insert_variable(a, variable()).unwrap();
+
+            let block = symbol_table.insert_block();
+            symbol_table.enter_scope(block);
+            symbol_table.insert_variable(b, variable()).unwrap();
+            assert!(symbol_table.insert_variable(a, variable()).is_err());
+            assert!(symbol_table.variable_in_parent_scope(a));
+            assert!(symbol_table.variable_in_local_scope(b));
+            symbol_table.exit_scope();
+
+            assert_eq!(symbol_table.child_scope(0), Some(block));
+            assert!(symbol_table.lookup_variable(a).is_some());
+            assert!(symbol_table.lookup_variable(b).is_none());
+            assert_eq!(symbol_table.variables_in_sub_scopes(), [(b, variable())]);
+        });
     }
 
-    /// Returns the scope associated with `index`, if it exists in the symbol table.
-    pub fn lookup_scope_by_index(&self, index: usize) -> Option<&RefCell<Self>> {
-        self.scopes.get(index)
+    #[test]
+    fn test_json_round_trip() {
+        create_session_if_not_set_then(|_| {
+            let mut symbol_table = SymbolTable::default();
+            let block = symbol_table.insert_block();
+            symbol_table.enter_scope(block);
+            symbol_table.insert_variable(Symbol::intern("a"), variable()).unwrap();
+            symbol_table.exit_scope();
+            symbol_table.insert_constant(sym::main, Value::Boolean(true, Span::default()));
+
+            let json = symbol_table.to_json().unwrap();
+            let deserialized = SymbolTable::from_json(&json).unwrap();
+            assert_eq!(deserialized.to_json().unwrap(), json);
+            assert_eq!(
+                deserialized.variables_in_sub_scopes(),
+                symbol_table.variables_in_sub_scopes()
+            );
+            assert_eq!(
+                deserialized.lookup_constant(sym::main),
+                Some(&Value::Boolean(true, Span::default()))
+            );
+        });
     }
 }
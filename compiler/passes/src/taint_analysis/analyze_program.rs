@@ -0,0 +1,143 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::TaintAnalyzer;
+
+use leo_ast::{
+    AssignStatement, CallType, ConditionalStatement, DefinitionStatement, Expression, ExpressionVisitor,
+    FinalizeStatement, Function, Input, Mode, Output, Program, ProgramScope, ProgramVisitor, ReturnStatement,
+    StatementVisitor, Type,
+};
+
+use indexmap::IndexSet;
+
+impl<'a> ExpressionVisitor<'a> for TaintAnalyzer<'a> {
+    type AdditionalInput = ();
+    type Output = ();
+}
+
+impl<'a> TaintAnalyzer<'a> {
+    /// Records the private inputs that a variable depends on, including those that determine whether it is assigned.
+    fn assign(&mut self, place: &Expression, value: &Expression) {
+        let mut taint = self.taint_of(value);
+        taint.extend(self.guard_taint());
+        match place {
+            Expression::Identifier(identifier) => {
+                self.taints.insert(identifier.name, taint);
+            }
+            Expression::Tuple(tuple) => tuple.elements.iter().for_each(|element| {
+                if let Expression::Identifier(identifier) = element {
+                    self.taints.insert(identifier.name, taint.clone());
+                }
+            }),
+            _ => {}
+        }
+    }
+}
+
+impl<'a> StatementVisitor<'a> for TaintAnalyzer<'a> {
+    fn visit_assign(&mut self, input: &'a AssignStatement) {
+        self.assign(&input.place, &input.value);
+    }
+
+    fn visit_conditional(&mut self, input: &'a ConditionalStatement) {
+        self.guards.push(self.taint_of(&input.condition));
+        self.visit_block(&input.then);
+        if let Some(otherwise) = input.otherwise.as_ref() {
+            self.visit_statement(otherwise);
+        }
+        self.guards.pop();
+    }
+
+    fn visit_definition(&mut self, input: &'a DefinitionStatement) {
+        self.assign(&input.place, &input.value);
+    }
+
+    fn visit_finalize(&mut self, input: &'a FinalizeStatement) {
+        for (index, argument) in input.arguments.iter().enumerate() {
+            let mut taint = self.taint_of(argument);
+            taint.extend(self.guard_taint());
+            self.record_leak(format!("finalize argument {index}"), taint, input.span);
+        }
+    }
+
+    fn visit_return(&mut self, input: &'a ReturnStatement) {
+        let elements = match &input.expression {
+            Expression::Tuple(tuple) if self.public_outputs.len() != 1 => tuple.elements.iter().collect(),
+            expression => vec![expression; self.public_outputs.len()],
+        };
+        for (index, (element, public)) in elements.into_iter().zip(self.public_outputs.clone()).enumerate() {
+            if public {
+                let mut taint = self.taint_of(element);
+                taint.extend(self.guard_taint());
+                self.record_leak(format!("output {index}"), taint, input.span);
+            }
+        }
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for TaintAnalyzer<'a> {
+    fn visit_program_scope(&mut self, input: &'a ProgramScope) {
+        self.records = input
+            .structs
+            .values()
+            .filter(|struct_| struct_.is_record)
+            .map(|struct_| struct_.identifier.name)
+            .collect();
+
+        input
+            .functions
+            .values()
+            .for_each(|function| self.visit_function(function));
+    }
+
+    /// Analyzes the transitions that have public outputs or a finalize block.
+    /// The inputs of a transition are private unless they are declared public or constant, and records are always private.
+    fn visit_function(&mut self, input: &'a Function) {
+        let is_record =
+            |type_: &Type| matches!(type_, Type::Identifier(identifier) if self.records.contains(&identifier.name));
+        self.public_outputs = input
+            .output
+            .iter()
+            .map(|output| matches!(output, Output::Internal(output) if output.mode == Mode::Public && !is_record(&output.type_)))
+            .collect();
+        if !matches!(input.call_type, CallType::Transition)
+            || !(self.public_outputs.contains(&true) || input.finalize.is_some())
+        {
+            return;
+        }
+
+        self.taints = input
+            .input
+            .iter()
+            .map(|input| match input {
+                Input::Internal(input) if matches!(input.mode, Mode::Public | Mode::Const) => {
+                    (input.identifier.name, IndexSet::new())
+                }
+                Input::Internal(input) => (input.identifier.name, IndexSet::from([input.identifier.name])),
+                Input::External(input) => (input.identifier.name, IndexSet::from([input.identifier.name])),
+            })
+            .collect();
+        self.guards.clear();
+        self.leaks.clear();
+
+        self.visit_block(&input.block);
+        self.report(input.identifier.name);
+    }
+
+    /// Imported programs are analyzed when they are compiled.
+    fn visit_import(&mut self, _input: &'a Program) {}
+}
@@ -0,0 +1,60 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The taint analysis pass traverses the AST after static single assignment and reports which public values of each transition depend on its private inputs.
+//! The public values of a transition are its outputs declared public and the arguments to its finalize block, which are revealed on chain.
+//! A value depends on a private input if it is computed from the input, or if it is assigned, returned, or finalized under a condition computed from the input.
+//! The result of a call or a hash depends on all of its arguments, although a hash may not reveal them.
+//! The dependencies of each transition are listed in the pass summary and, if enabled, reported as warnings.
+//!
+//! Consider the following Leo code.
+//! ```leo
+//! transition main(secret: u8, public offset: u8) -> public u8 {
+//!     let total: u8 = offset;
+//!     if secret > 10u8 {
+//!         total = total + 1u8;
+//!     }
+//!     return total;
+//! }
+//! ```
+//!
+//! The public output of `main` depends on the private input `secret`, since `total` is only incremented if `secret > 10u8`.
+
+mod analyze_program;
+
+pub mod taint_analyzer;
+pub use taint_analyzer::*;
+
+use crate::{Pass, PassSummary};
+
+use leo_ast::{Ast, ProgramVisitor};
+use leo_errors::{emitter::Handler, Result};
+
+impl<'a> Pass for TaintAnalyzer<'a> {
+    type Input = (&'a Ast, &'a Handler, bool);
+    type Output = Result<PassSummary>;
+
+    fn do_pass((ast, handler, warn): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("taint analysis", Some(handler));
+        let mut visitor = TaintAnalyzer::new(handler, warn);
+        visitor.visit_program(ast.as_repr());
+
+        let mut summary = recorder.finish(Some(handler));
+        summary.decisions = visitor.decisions;
+
+        Ok(summary)
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_ast::{AccessExpression, Expression};
+use leo_errors::{emitter::Handler, TypeCheckerWarning};
+use leo_span::{Span, Symbol};
+
+use indexmap::{IndexMap, IndexSet};
+use itertools::Itertools;
+
+pub struct TaintAnalyzer<'a> {
+    /// The error handler.
+    pub(crate) handler: &'a Handler,
+    /// Whether to warn about each public value that depends on private inputs.
+    pub(crate) warn: bool,
+    /// The names of the records declared in the program.
+    pub(crate) records: IndexSet<Symbol>,
+    /// The private inputs that each variable assigned so far in the function being analyzed depends on.
+    pub(crate) taints: IndexMap<Symbol, IndexSet<Symbol>>,
+    /// The private inputs that the conditions of the enclosing conditional statements depend on.
+    pub(crate) guards: Vec<IndexSet<Symbol>>,
+    /// Whether each output of the function being analyzed is public.
+    pub(crate) public_outputs: Vec<bool>,
+    /// The private inputs that each public value of the function being analyzed depends on, and where the value is first made public.
+    pub(crate) leaks: IndexMap<String, (IndexSet<Symbol>, Span)>,
+    /// The public values of each function that depend on private inputs.
+    pub(crate) decisions: Vec<String>,
+}
+
+impl<'a> TaintAnalyzer<'a> {
+    /// Returns a new taint analyzer given an error handler, and whether to warn about leaks.
+    pub fn new(handler: &'a Handler, warn: bool) -> Self {
+        Self {
+            handler,
+            warn,
+            records: IndexSet::new(),
+            taints: IndexMap::new(),
+            guards: Vec::new(),
+            public_outputs: Vec::new(),
+            leaks: IndexMap::new(),
+            decisions: Vec::new(),
+        }
+    }
+
+    /// Returns the private inputs that any of `expressions` depend on.
+    fn taint_of_all<'b>(&self, expressions: impl IntoIterator<Item = &'b Expression>) -> IndexSet<Symbol> {
+        expressions
+            .into_iter()
+            .flat_map(|expression| self.taint_of(expression))
+            .collect()
+    }
+
+    /// Returns the private inputs that `expression` depends on.
+    /// The result of a call or an associated function, such as a hash, depends on all of its arguments.
+    pub(crate) fn taint_of(&self, expression: &Expression) -> IndexSet<Symbol> {
+        match expression {
+            Expression::Identifier(identifier) => self.taints.get(&identifier.name).cloned().unwrap_or_default(),
+            Expression::Access(AccessExpression::Array(access)) => self.taint_of_all([&*access.array, &*access.index]),
+            Expression::Access(AccessExpression::AssociatedFunction(function)) => self.taint_of_all(&function.args),
+            Expression::Access(AccessExpression::Member(access)) => self.taint_of(&access.inner),
+            Expression::Access(AccessExpression::Tuple(access)) => self.taint_of(&access.tuple),
+            Expression::Array(array) => self.taint_of_all(&array.elements),
+            Expression::Binary(binary) => self.taint_of_all([&*binary.left, &*binary.right]),
+            Expression::Call(call) => self.taint_of_all(&call.arguments),
+            Expression::Cast(cast) => self.taint_of(&cast.expression),
+            Expression::Struct(struct_) => struct_
+                .members
+                .iter()
+                .flat_map(|member| match &member.expression {
+                    Some(expression) => self.taint_of(expression),
+                    None => self.taints.get(&member.identifier.name).cloned().unwrap_or_default(),
+                })
+                .collect(),
+            Expression::Ternary(ternary) => {
+                self.taint_of_all([&*ternary.condition, &*ternary.if_true, &*ternary.if_false])
+            }
+            Expression::Tuple(tuple) => self.taint_of_all(&tuple.elements),
+            Expression::Unary(unary) => self.taint_of(&unary.receiver),
+            Expression::Access(AccessExpression::AssociatedConstant(_))
+            | Expression::Err(_)
+            | Expression::Literal(_) => IndexSet::new(),
+        }
+    }
+
+    /// Returns the private inputs that the enclosing conditions depend on, and hence whether a statement is reached.
+    pub(crate) fn guard_taint(&self) -> IndexSet<Symbol> {
+        self.guards.iter().flatten().copied().collect()
+    }
+
+    /// Records that the public value described by `value` depends on the private inputs in `taint`.
+    pub(crate) fn record_leak(&mut self, value: String, taint: IndexSet<Symbol>, span: Span) {
+        if !taint.is_empty() {
+            self.leaks
+                .entry(value)
+                .or_insert_with(|| (IndexSet::new(), span))
+                .0
+                .extend(taint);
+        }
+    }
+
+    /// Reports the public values of the function `function` that depend on private inputs.
+    pub(crate) fn report(&mut self, function: Symbol) {
+        if self.leaks.is_empty() {
+            self.decisions.push(format!(
+                "`{function}`: no public output or finalize argument depends on private inputs"
+            ));
+        }
+        for (value, (taint, span)) in std::mem::take(&mut self.leaks) {
+            let inputs = taint.iter().map(|input| format!("`{input}`")).join(", ");
+            self.decisions.push(format!(
+                "`{function}`: public {value} depends on the private inputs {inputs}"
+            ));
+            if self.warn {
+                self.handler.emit_warning(
+                    TypeCheckerWarning::public_value_depends_on_private_inputs(value, inputs, span).into(),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::TernaryBalancer;
+
+use leo_ast::{ExpressionReconstructor, Finalize, Function, ProgramReconstructor, StatementReconstructor};
+
+impl ProgramReconstructor for TernaryBalancer<'_> {
+    fn reconstruct_function(&mut self, input: Function) -> Function {
+        let function = input.identifier.name;
+        let finalize = input.finalize.map(|finalize| Finalize {
+            identifier: finalize.identifier,
+            input: finalize.input,
+            output: finalize.output,
+            output_type: finalize.output_type,
+            block: self.balance_block(function, finalize.block),
+            span: finalize.span,
+            id: finalize.id,
+        });
+
+        Function {
+            annotations: input.annotations,
+            call_type: input.call_type,
+            identifier: input.identifier,
+            type_parameters: input.type_parameters,
+            input: input.input,
+            output: input.output,
+            output_type: input.output_type,
+            block: self.balance_block(function, input.block),
+            finalize,
+            span: input.span,
+            id: input.id,
+        }
+    }
+}
+
+impl StatementReconstructor for TernaryBalancer<'_> {}
+
+impl ExpressionReconstructor for TernaryBalancer<'_> {
+    type AdditionalOutput = ();
+}
@@ -0,0 +1,77 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The ternary balancing pass traverses the AST after the strength reduction pass and rebalances long chains of selections into trees.
+//! Flattening an `if`/`else if` chain, or folding the early returns of a function, produces a chain of selections `c0 ? x0 : (c1 ? x1 : (... : y))`,
+//! whose arms are selected under mutually exclusive conditions, so the depth of the chain grows linearly with the number of branches.
+//! The pass splits each chain in half, selecting the first half if any of its conditions hold, and the second half otherwise,
+//! which preserves the arm that is selected and reduces the depth of the chain to the logarithm of its length, at the cost of the disjunctions of the conditions.
+//! Only chains of at least `threshold` selections are rebalanced, and only if rebalancing reduces their depth.
+//! A chain is formed by selections whose operands are variables or literals, where each selection is the only use of the one that follows it.
+//!
+//! Consider the following flattened Leo code.
+//! ```leo
+//! function main(x: u8, a: u8, b: u8, c: u8, d: u8) -> u8 {
+//!     $var$0 = x == 0u8;
+//!     $var$1 = x == 1u8;
+//!     $var$2 = x == 2u8;
+//!     $var$3 = $var$2 ? c : d;
+//!     $var$4 = $var$1 ? b : $var$3;
+//!     $var$5 = $var$0 ? a : $var$4;
+//!     return $var$5;
+//! }
+//! ```
+//!
+//! With a threshold of three, the ternary balancing pass produces the following code.
+//! ```leo
+//! function main(x: u8, a: u8, b: u8, c: u8, d: u8) -> u8 {
+//!     $var$0 = x == 0u8;
+//!     $var$1 = x == 1u8;
+//!     $var$2 = x == 2u8;
+//!     $var$6 = $var$0 || $var$1;
+//!     $var$7 = $var$0 ? a : b;
+//!     $var$8 = $var$2 ? c : d;
+//!     $var$5 = $var$6 ? $var$7 : $var$8;
+//!     return $var$5;
+//! }
+//! ```
+
+mod balance_program;
+
+pub mod ternary_balancer;
+pub use ternary_balancer::*;
+
+use crate::{Assigner, Pass, PassSummary};
+
+use leo_ast::{Ast, NodeBuilder, ProgramReconstructor};
+use leo_errors::Result;
+
+impl<'a> Pass for TernaryBalancer<'a> {
+    type Input = (Ast, Assigner, &'a NodeBuilder, usize);
+    type Output = Result<(Ast, Assigner, PassSummary)>;
+
+    fn do_pass((ast, assigner, node_builder, threshold): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("ternary balancing", None);
+        let mut reconstructor = TernaryBalancer::new(assigner, node_builder, threshold);
+        let program = reconstructor.reconstruct_program(ast.into_repr());
+
+        let ast = Ast::new(program);
+        let mut summary = recorder.finish(None);
+        summary.decisions = reconstructor.decisions;
+
+        Ok((ast, reconstructor.assigner, summary))
+    }
+}
@@ -0,0 +1,270 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Assigner;
+
+use leo_ast::{
+    BinaryExpression, BinaryOperation, Block, Expression, ExpressionVisitor, Identifier, Node, NodeBuilder, Statement,
+    StatementVisitor, StructExpression, TernaryExpression,
+};
+use leo_span::Symbol;
+
+use indexmap::{IndexMap, IndexSet};
+
+/// An arm of a chain of selections, paired with the condition under which it is selected, or `None` if it is the last arm.
+pub type Arm = (Option<Expression>, Expression);
+
+pub struct TernaryBalancer<'a> {
+    /// A struct used to construct (unique) assignment statements.
+    pub(crate) assigner: Assigner,
+    /// The minimum number of selections in a chain for the chain to be rebalanced.
+    pub(crate) threshold: usize,
+    /// The chains that were rebalanced, reported in the pass summary.
+    pub(crate) decisions: Vec<String>,
+    /// A counter used to generate unique ids for the nodes that the pass creates.
+    pub(crate) node_builder: &'a NodeBuilder,
+}
+
+impl<'a> TernaryBalancer<'a> {
+    pub(crate) fn new(assigner: Assigner, node_builder: &'a NodeBuilder, threshold: usize) -> Self {
+        Self {
+            assigner,
+            threshold,
+            decisions: Vec::new(),
+            node_builder,
+        }
+    }
+
+    /// Rebalances the chains of selections in a flattened function body or finalize block.
+    pub(crate) fn balance_block(&mut self, function: Symbol, block: Block) -> Block {
+        let mut counter = UseCounter::default();
+        counter.visit_block(&block);
+
+        // The index of the statement assigning each selection.
+        let selections: IndexMap<Symbol, usize> = block
+            .statements
+            .iter()
+            .enumerate()
+            .filter_map(|(index, statement)| selection_of(statement).map(|(place, _)| (place.name, index)))
+            .collect();
+
+        // A selection is the next link of a chain if it is only used as the `else` arm of the previous link.
+        let next = |ternary: &TernaryExpression| match &*ternary.if_false {
+            Expression::Identifier(identifier) if counter.uses.get(&identifier.name) == Some(&1) => {
+                selections.get(&identifier.name).copied()
+            }
+            _ => None,
+        };
+        let links: IndexSet<usize> = selections
+            .values()
+            .filter_map(|index| next(selection_of(&block.statements[*index]).unwrap().1))
+            .collect();
+
+        // The chains to rebalance, by the index of their first link, and the indices of their other links.
+        let mut chains = IndexMap::new();
+        let mut removed = IndexSet::new();
+        for index in selections.values().copied().filter(|index| !links.contains(index)) {
+            let (place, mut ternary) = selection_of(&block.statements[index]).unwrap();
+            let mut arms = Vec::new();
+            let mut indices = Vec::new();
+            loop {
+                arms.push((Some(*ternary.condition.clone()), *ternary.if_true.clone()));
+                match next(ternary) {
+                    Some(link) => {
+                        indices.push(link);
+                        ternary = selection_of(&block.statements[link]).unwrap().1;
+                    }
+                    None => {
+                        arms.push((None, *ternary.if_false.clone()));
+                        break;
+                    }
+                }
+            }
+
+            let length = arms.len() - 1;
+            let depth = balanced_depth(arms.len());
+            if length >= self.threshold && depth < length {
+                self.decisions.push(format!(
+                    "`{function}`: balanced the chain of {length} selections assigned to `{}` into a tree of depth {depth}",
+                    place.name
+                ));
+                chains.insert(index, (*place, arms));
+                removed.extend(indices);
+            }
+        }
+
+        let mut statements = Vec::with_capacity(block.statements.len());
+        for (index, statement) in block.statements.into_iter().enumerate() {
+            match chains.remove(&index) {
+                Some((place, arms)) => {
+                    let value = self.select(&arms, 0, arms.len(), &mut IndexMap::new(), &mut statements);
+                    let span = statement.span().synthesized();
+                    statements.push(self.assigner.simple_assign_statement(
+                        place,
+                        value,
+                        span,
+                        self.node_builder.next_id(),
+                    ));
+                }
+                None if removed.contains(&index) => (),
+                None => statements.push(statement),
+            }
+        }
+
+        Block {
+            statements,
+            span: block.span,
+            id: block.id,
+        }
+    }
+
+    /// Returns an expression selecting among `arms[start..end]`, whose last arm is selected whenever the conditions of the others do not hold.
+    /// The disjunctions of the conditions of ranges of arms are memoized in `disjunctions`, and the statements computing them are appended to `statements`.
+    fn select(
+        &mut self,
+        arms: &[Arm],
+        start: usize,
+        end: usize,
+        disjunctions: &mut IndexMap<(usize, usize), Expression>,
+        statements: &mut Vec<Statement>,
+    ) -> Expression {
+        if end - start == 1 {
+            return arms[start].1.clone();
+        }
+
+        // The first half is selected if the condition of one of its arms holds, in which case its last arm is selected if the others do not hold.
+        let middle = split(start, end);
+        let condition = self.disjunction(arms, start, middle, disjunctions, statements);
+        let if_true = self.select(arms, start, middle, disjunctions, statements);
+        let if_true = self.operand(if_true, statements);
+        let if_false = self.select(arms, middle, end, disjunctions, statements);
+        let if_false = self.operand(if_false, statements);
+
+        Expression::Ternary(TernaryExpression {
+            condition: Box::new(condition),
+            if_true: Box::new(if_true),
+            if_false: Box::new(if_false),
+            span: Default::default(),
+            id: self.node_builder.next_id(),
+        })
+    }
+
+    /// Returns an operand holding the disjunction of the conditions of `arms[start..end]`.
+    /// The disjunction is split in the same way as the selection, so that the disjunctions of its halves are shared with the selections of its halves.
+    fn disjunction(
+        &mut self,
+        arms: &[Arm],
+        start: usize,
+        end: usize,
+        disjunctions: &mut IndexMap<(usize, usize), Expression>,
+        statements: &mut Vec<Statement>,
+    ) -> Expression {
+        if let Some(disjunction) = disjunctions.get(&(start, end)) {
+            return disjunction.clone();
+        }
+
+        let disjunction = match end - start {
+            1 => arms[start]
+                .0
+                .clone()
+                .expect("The last arm of a chain is never in the first half of a selection."),
+            _ => {
+                let middle = split(start, end);
+                let left = self.disjunction(arms, start, middle, disjunctions, statements);
+                let right = self.disjunction(arms, middle, end, disjunctions, statements);
+                let value = Expression::Binary(BinaryExpression {
+                    op: BinaryOperation::Or,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    span: Default::default(),
+                    id: self.node_builder.next_id(),
+                });
+                self.operand(value, statements)
+            }
+        };
+        disjunctions.insert((start, end), disjunction.clone());
+
+        disjunction
+    }
+
+    /// Assigns `expression` to a new variable, unless it is already a variable or a literal, and returns the operand holding its value.
+    fn operand(&mut self, expression: Expression, statements: &mut Vec<Statement>) -> Expression {
+        if is_operand(&expression) {
+            return expression;
+        }
+        let (place, statement) = self
+            .assigner
+            .unique_simple_assign_statement(expression, self.node_builder);
+        statements.push(statement);
+        Expression::Identifier(place)
+    }
+}
+
+/// Returns the variable and the selection assigned by `statement`, if it assigns a selection over variables or literals.
+fn selection_of(statement: &Statement) -> Option<(&Identifier, &TernaryExpression)> {
+    match statement {
+        Statement::Assign(assign) => match (&assign.place, &assign.value) {
+            (Expression::Identifier(place), Expression::Ternary(ternary))
+                if is_operand(&ternary.condition) && is_operand(&ternary.if_true) && is_operand(&ternary.if_false) =>
+            {
+                Some((place, ternary))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns `true` if `expression` is a variable or a literal.
+fn is_operand(expression: &Expression) -> bool {
+    matches!(expression, Expression::Identifier(_) | Expression::Literal(_))
+}
+
+/// Returns the index at which the arms in `start..end` are split into halves, with the larger half first.
+fn split(start: usize, end: usize) -> usize {
+    start + (end - start + 1) / 2
+}
+
+/// Returns the depth of a balanced tree of selections among `arms` arms, which is the base two logarithm of `arms`, rounded up.
+fn balanced_depth(arms: usize) -> usize {
+    (usize::BITS - (arms - 1).leading_zeros()) as usize
+}
+
+/// Counts the uses of each variable.
+#[derive(Default)]
+struct UseCounter {
+    uses: IndexMap<Symbol, usize>,
+}
+
+impl<'a> ExpressionVisitor<'a> for UseCounter {
+    type AdditionalInput = ();
+    type Output = ();
+
+    fn visit_identifier(&mut self, input: &'a Identifier, _additional: &Self::AdditionalInput) -> Self::Output {
+        *self.uses.entry(input.name).or_default() += 1;
+    }
+
+    fn visit_struct_init(&mut self, input: &'a StructExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        for member in input.members.iter() {
+            match &member.expression {
+                Some(expression) => self.visit_expression(expression, additional),
+                None => self.visit_identifier(&member.identifier, additional),
+            }
+        }
+    }
+}
+
+impl<'a> StatementVisitor<'a> for UseCounter {}
@@ -0,0 +1,162 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::TupleLowerer;
+
+use leo_ast::{
+    AccessExpression, ArrayAccess, ArrayExpression, AssociatedFunction, BinaryExpression, CallExpression,
+    CastExpression, Expression, ExpressionReconstructor, MemberAccess, Statement, StructExpression,
+    StructVariableInitializer, TernaryExpression, TupleExpression, UnaryExpression,
+};
+
+impl ExpressionReconstructor for TupleLowerer<'_> {
+    type AdditionalOutput = Vec<Statement>;
+
+    /// Replaces an access into a tuple with the accessed element, accumulating any statements that are generated.
+    fn reconstruct_access(&mut self, input: AccessExpression) -> (Expression, Self::AdditionalOutput) {
+        let mut statements = Vec::new();
+        let access = match input {
+            AccessExpression::Tuple(access) => {
+                let (mut elements, statements) = self.lower_tuple(*access.tuple);
+                // Note that type checking guarantees that the index is in bounds.
+                return (elements.swap_remove(access.index.to_usize()), statements);
+            }
+            AccessExpression::Array(access) => {
+                let (array, stmts) = self.reconstruct_expression(*access.array);
+                statements.extend(stmts);
+                let (index, stmts) = self.reconstruct_expression(*access.index);
+                statements.extend(stmts);
+                AccessExpression::Array(ArrayAccess {
+                    array: Box::new(array),
+                    index: Box::new(index),
+                    ..access
+                })
+            }
+            AccessExpression::AssociatedFunction(function) => {
+                let (args, stmts) = self.reconstruct_elements(function.args);
+                statements.extend(stmts);
+                AccessExpression::AssociatedFunction(AssociatedFunction { args, ..function })
+            }
+            AccessExpression::Member(access) => {
+                let (inner, stmts) = self.reconstruct_expression(*access.inner);
+                statements.extend(stmts);
+                AccessExpression::Member(MemberAccess {
+                    inner: Box::new(inner),
+                    ..access
+                })
+            }
+            access => access,
+        };
+
+        (Expression::Access(access), statements)
+    }
+
+    /// Reconstructs the elements of an array expression, accumulating any statements that are generated.
+    fn reconstruct_array(&mut self, input: ArrayExpression) -> (Expression, Self::AdditionalOutput) {
+        let (elements, statements) = self.reconstruct_elements(input.elements);
+        (Expression::Array(ArrayExpression { elements, ..input }), statements)
+    }
+
+    /// Reconstructs the operands of a binary expression, accumulating any statements that are generated.
+    fn reconstruct_binary(&mut self, input: BinaryExpression) -> (Expression, Self::AdditionalOutput) {
+        let (left, mut statements) = self.reconstruct_expression(*input.left);
+        let (right, stmts) = self.reconstruct_expression(*input.right);
+        statements.extend(stmts);
+        (
+            Expression::Binary(BinaryExpression {
+                left: Box::new(left),
+                right: Box::new(right),
+                ..input
+            }),
+            statements,
+        )
+    }
+
+    /// Reconstructs the arguments of a call, accumulating any statements that are generated.
+    /// Note that a call to a function with multiple outputs is only reconstructed where it is destructured.
+    fn reconstruct_call(&mut self, input: CallExpression) -> (Expression, Self::AdditionalOutput) {
+        let (arguments, statements) = self.reconstruct_elements(input.arguments);
+        (Expression::Call(CallExpression { arguments, ..input }), statements)
+    }
+
+    /// Reconstructs the value of a cast expression, accumulating any statements that are generated.
+    fn reconstruct_cast(&mut self, input: CastExpression) -> (Expression, Self::AdditionalOutput) {
+        let (expression, statements) = self.reconstruct_expression(*input.expression);
+        (
+            Expression::Cast(CastExpression {
+                expression: Box::new(expression),
+                ..input
+            }),
+            statements,
+        )
+    }
+
+    /// Reconstructs the values of the struct's members, accumulating any statements that are generated.
+    fn reconstruct_struct_init(&mut self, input: StructExpression) -> (Expression, Self::AdditionalOutput) {
+        let mut statements = Vec::new();
+        let members = input
+            .members
+            .into_iter()
+            .map(|member| StructVariableInitializer {
+                identifier: member.identifier,
+                expression: member.expression.map(|expression| {
+                    let (expression, stmts) = self.reconstruct_expression(expression);
+                    statements.extend(stmts);
+                    expression
+                }),
+            })
+            .collect();
+        (Expression::Struct(StructExpression { members, ..input }), statements)
+    }
+
+    /// Reconstructs the operands of a ternary expression, accumulating any statements that are generated.
+    /// Note that ternary expressions over tuples are lowered by `lower_tuple` instead.
+    fn reconstruct_ternary(&mut self, input: TernaryExpression) -> (Expression, Self::AdditionalOutput) {
+        let (condition, mut statements) = self.reconstruct_expression(*input.condition);
+        let (if_true, stmts) = self.reconstruct_expression(*input.if_true);
+        statements.extend(stmts);
+        let (if_false, stmts) = self.reconstruct_expression(*input.if_false);
+        statements.extend(stmts);
+        (
+            Expression::Ternary(TernaryExpression {
+                condition: Box::new(condition),
+                if_true: Box::new(if_true),
+                if_false: Box::new(if_false),
+                ..input
+            }),
+            statements,
+        )
+    }
+
+    /// Reconstructs the elements of a tuple expression, accumulating any statements that are generated.
+    /// Note that the only tuple expressions that remain are the values of return statements and the unit value.
+    fn reconstruct_tuple(&mut self, input: TupleExpression) -> (Expression, Self::AdditionalOutput) {
+        let (elements, statements) = self.reconstruct_elements(input.elements);
+        (Expression::Tuple(TupleExpression { elements, ..input }), statements)
+    }
+
+    /// Reconstructs the operand of a unary expression, accumulating any statements that are generated.
+    fn reconstruct_unary(&mut self, input: UnaryExpression) -> (Expression, Self::AdditionalOutput) {
+        let (receiver, statements) = self.reconstruct_expression(*input.receiver);
+        (
+            Expression::Unary(UnaryExpression {
+                receiver: Box::new(receiver),
+                ..input
+            }),
+            statements,
+        )
+    }
+}
@@ -14,8 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-pub mod console_function;
-pub use console_function::*;
+use crate::TupleLowerer;
 
-pub mod console_statement;
-pub use console_statement::*;
+use leo_ast::ProgramReconstructor;
+
+impl ProgramReconstructor for TupleLowerer<'_> {}
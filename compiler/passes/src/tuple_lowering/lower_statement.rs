@@ -0,0 +1,234 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::TupleLowerer;
+
+use leo_ast::{
+    AssertStatement, AssertVariant, AssignStatement, Block, ConditionalStatement, DecrementStatement,
+    DefinitionStatement, Expression, ExpressionReconstructor, ExpressionStatement, FinalizeStatement,
+    IncrementStatement, IterationStatement, MatchStatement, Node, ReturnStatement, Statement, StatementReconstructor,
+    TupleExpression, Type,
+};
+
+use itertools::Itertools;
+
+impl StatementReconstructor for TupleLowerer<'_> {
+    /// Reconstructs the operands of an assert statement, accumulating any statements that are generated.
+    fn reconstruct_assert(&mut self, input: AssertStatement) -> (Statement, Self::AdditionalOutput) {
+        let mut statements = Vec::new();
+        let variant = match input.variant {
+            AssertVariant::Assert(expression) => {
+                let (expression, stmts) = self.reconstruct_expression(expression);
+                statements.extend(stmts);
+                AssertVariant::Assert(expression)
+            }
+            AssertVariant::AssertEq(left, right) => {
+                let (left, stmts) = self.reconstruct_expression(left);
+                statements.extend(stmts);
+                let (right, stmts) = self.reconstruct_expression(right);
+                statements.extend(stmts);
+                AssertVariant::AssertEq(left, right)
+            }
+            AssertVariant::AssertNeq(left, right) => {
+                let (left, stmts) = self.reconstruct_expression(left);
+                statements.extend(stmts);
+                let (right, stmts) = self.reconstruct_expression(right);
+                statements.extend(stmts);
+                AssertVariant::AssertNeq(left, right)
+            }
+        };
+
+        (Statement::Assert(AssertStatement { variant, ..input }), statements)
+    }
+
+    /// Reconstructs the value of an assignment, accumulating any statements that are generated.
+    /// Note that type checking guarantees that the assigned variable is not a tuple.
+    fn reconstruct_assign(&mut self, input: AssignStatement) -> (Statement, Self::AdditionalOutput) {
+        let (value, statements) = self.reconstruct_expression(input.value);
+        (
+            Statement::Assign(Box::new(AssignStatement { value, ..input })),
+            statements,
+        )
+    }
+
+    /// Reconstructs the statements of a block, inserting the statements generated by lowering each statement before it.
+    fn reconstruct_block(&mut self, input: Block) -> (Block, Self::AdditionalOutput) {
+        let mut statements = Vec::with_capacity(input.statements.len());
+        for statement in input.statements {
+            let (statement, additional_statements) = self.reconstruct_statement(statement);
+            statements.extend(additional_statements);
+            statements.push(statement);
+        }
+
+        (
+            Block {
+                statements,
+                span: input.span,
+                id: input.id,
+            },
+            Default::default(),
+        )
+    }
+
+    /// Reconstructs a conditional statement, accumulating the statements generated by lowering its condition.
+    /// The statements generated by lowering the condition of an `else if` branch are placed in the `else` branch, so that they precede it.
+    fn reconstruct_conditional(&mut self, input: ConditionalStatement) -> (Statement, Self::AdditionalOutput) {
+        let (condition, statements) = self.reconstruct_expression(input.condition);
+        let then = self.reconstruct_block(input.then).0;
+        let otherwise = input.otherwise.map(|otherwise| {
+            let span = otherwise.span();
+            let (otherwise, mut statements) = self.reconstruct_statement(*otherwise);
+            Box::new(match statements.is_empty() {
+                true => otherwise,
+                false => {
+                    statements.push(otherwise);
+                    Statement::Block(Block {
+                        statements,
+                        span,
+                        id: self.node_builder.next_id(),
+                    })
+                }
+            })
+        });
+
+        (
+            Statement::Conditional(ConditionalStatement {
+                condition,
+                then,
+                otherwise,
+                span: input.span,
+                id: input.id,
+            }),
+            statements,
+        )
+    }
+
+    /// Reconstructs the operands of a decrement statement, accumulating any statements that are generated.
+    fn reconstruct_decrement(&mut self, input: DecrementStatement) -> (Statement, Self::AdditionalOutput) {
+        let (index, mut statements) = self.reconstruct_expression(input.index);
+        let (amount, stmts) = self.reconstruct_expression(input.amount);
+        statements.extend(stmts);
+        (
+            Statement::Decrement(DecrementStatement { index, amount, ..input }),
+            statements,
+        )
+    }
+
+    /// Splits a destructuring definition into a definition for each of its variables, unless its value is a call.
+    /// For example, `let (a, b): (u8, u8) = flag ? (c, d) : (d, c);` is lowered into the following:
+    /// ```leo
+    /// let a: u8 = flag ? c : d;
+    /// let b: u8 = flag ? d : c;
+    /// ```
+    fn reconstruct_definition(&mut self, input: DefinitionStatement) -> (Statement, Self::AdditionalOutput) {
+        let (places, value) = match (input.place, input.value) {
+            // A destructured call remains, since its outputs are assigned to the variables directly.
+            (place @ Expression::Identifier(_), value)
+            | (place @ Expression::Tuple(_), value @ Expression::Call(_)) => {
+                let (value, statements) = self.reconstruct_expression(value);
+                return (
+                    Statement::Definition(DefinitionStatement {
+                        declaration_type: input.declaration_type,
+                        place,
+                        type_: input.type_,
+                        value,
+                        span: input.span,
+                        id: input.id,
+                    }),
+                    statements,
+                );
+            }
+            (Expression::Tuple(tuple), value) => (tuple.elements, value),
+            _ => unreachable!(
+                "The parser guarantees that the left-hand side of a definition is an identifier or a tuple."
+            ),
+        };
+
+        let types = match input.type_.into_inner() {
+            Some(Type::Tuple(tuple)) => tuple.0,
+            _ => unreachable!("Type checking guarantees that a destructured value is a tuple."),
+        };
+        let (values, mut statements) = self.lower_tuple(value);
+        statements.extend(
+            places
+                .into_iter()
+                .zip_eq(types)
+                .zip_eq(values)
+                .map(|((place, type_), value)| self.definition(place, type_, value, input.span)),
+        );
+
+        // Note that the last definition is returned as the statement, since every destructuring definition has at least two variables.
+        let statement = statements.pop().unwrap();
+        (statement, statements)
+    }
+
+    /// Reconstructs the expression of an expression statement, accumulating any statements that are generated.
+    fn reconstruct_expression_statement(&mut self, input: ExpressionStatement) -> (Statement, Self::AdditionalOutput) {
+        let (expression, statements) = self.reconstruct_expression(input.expression);
+        (
+            Statement::Expression(ExpressionStatement { expression, ..input }),
+            statements,
+        )
+    }
+
+    /// Reconstructs the arguments of a finalize statement, accumulating any statements that are generated.
+    fn reconstruct_finalize(&mut self, input: FinalizeStatement) -> (Statement, Self::AdditionalOutput) {
+        let (arguments, statements) = self.reconstruct_elements(input.arguments);
+        (
+            Statement::Finalize(FinalizeStatement { arguments, ..input }),
+            statements,
+        )
+    }
+
+    /// Reconstructs the operands of an increment statement, accumulating any statements that are generated.
+    fn reconstruct_increment(&mut self, input: IncrementStatement) -> (Statement, Self::AdditionalOutput) {
+        let (index, mut statements) = self.reconstruct_expression(input.index);
+        let (amount, stmts) = self.reconstruct_expression(input.amount);
+        statements.extend(stmts);
+        (
+            Statement::Increment(IncrementStatement { index, amount, ..input }),
+            statements,
+        )
+    }
+
+    fn reconstruct_iteration(&mut self, _input: IterationStatement) -> (Statement, Self::AdditionalOutput) {
+        unreachable!("`IterationStatement`s should not be in the AST at this phase of compilation.");
+    }
+
+    fn reconstruct_match(&mut self, _input: MatchStatement) -> (Statement, Self::AdditionalOutput) {
+        unreachable!("`MatchStatement`s should not be in the AST at this phase of compilation.");
+    }
+
+    /// Lowers the value of a return statement into a tuple expression of its elements, if it is a tuple.
+    /// For example, `return flag ? (a, b) : (b, a);` is lowered into `return (flag ? a : b, flag ? b : a);`.
+    fn reconstruct_return(&mut self, input: ReturnStatement) -> (Statement, Self::AdditionalOutput) {
+        let (expression, statements) = match self.tuple_type(&input.expression) {
+            Some(_) => {
+                let span = input.expression.span();
+                let (elements, statements) = self.lower_tuple(input.expression);
+                let tuple = TupleExpression {
+                    elements,
+                    span,
+                    id: self.node_builder.next_id(),
+                };
+                (Expression::Tuple(tuple), statements)
+            }
+            None => self.reconstruct_expression(input.expression),
+        };
+
+        (Statement::Return(ReturnStatement { expression, ..input }), statements)
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The tuple lowering pass traverses the AST after type checking and eliminates the values of tuple type.
+//! A tuple expression is replaced by its elements, a ternary expression over tuples by a tuple of ternary expressions over the elements, and an access into a tuple by the accessed element.
+//! If the condition of a ternary expression over tuples is not a variable or a literal, it is first assigned to a new variable, so that it is evaluated once.
+//! A call to a function with multiple outputs that is not destructured is destructured into new variables, one for each output.
+//! Afterwards, the only tuples in the program are the destructured calls and the values of return statements, so no variable has a tuple type.
+//!
+//! Consider the following Leo code.
+//! ```leo
+//! function swap(a: u8, b: u8) -> (u8, u8) {
+//!     return (b, a);
+//! }
+//!
+//! transition main(flag: bool, a: u8, b: u8) -> (u8, u8) {
+//!     let (c, d): (u8, u8) = flag ? (a, b) : swap(a, b);
+//!     return (swap(c, d).0, c);
+//! }
+//! ```
+//!
+//! The tuple lowering pass produces the following code.
+//! ```leo
+//! function swap(a: u8, b: u8) -> (u8, u8) {
+//!     return (b, a);
+//! }
+//!
+//! transition main(flag: bool, a: u8, b: u8) -> (u8, u8) {
+//!     let ($tuple$0, $tuple$1): (u8, u8) = swap(a, b);
+//!     let c: u8 = flag ? a : $tuple$0;
+//!     let d: u8 = flag ? b : $tuple$1;
+//!     let ($tuple$2, $tuple$3): (u8, u8) = swap(c, d);
+//!     return ($tuple$2, c);
+//! }
+//! ```
+
+mod lower_expression;
+
+mod lower_program;
+
+mod lower_statement;
+
+pub mod tuple_lowerer;
+pub use tuple_lowerer::*;
+
+use crate::{Pass, PassSummary, TypeTable};
+
+use leo_ast::{Ast, NodeBuilder, ProgramReconstructor};
+use leo_errors::Result;
+
+impl<'a> Pass for TupleLowerer<'a> {
+    type Input = (Ast, &'a NodeBuilder, &'a mut TypeTable);
+    type Output = Result<(Ast, PassSummary)>;
+
+    fn do_pass((ast, node_builder, type_table): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("tuple lowering", None);
+        let mut reconstructor = TupleLowerer::new(node_builder, type_table);
+        let program = reconstructor.reconstruct_program(ast.into_repr());
+
+        let ast = Ast::new(program);
+        let summary = recorder.finish(None);
+
+        Ok((ast, summary))
+    }
+}
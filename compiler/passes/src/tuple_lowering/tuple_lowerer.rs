@@ -0,0 +1,175 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::TypeTable;
+
+use leo_ast::{
+    AccessExpression, DeclarationType, DefinitionStatement, Expression, ExpressionReconstructor, Identifier, Node,
+    NodeBuilder, Statement, TernaryExpression, Tuple, TupleExpression, Type,
+};
+use leo_span::{Span, Symbol};
+
+use itertools::Itertools;
+use std::cell::RefCell;
+
+pub struct TupleLowerer<'a> {
+    /// A counter used to generate unique ids for the nodes that the pass creates.
+    pub(crate) node_builder: &'a NodeBuilder,
+    /// The types of the nodes in the program, to which the types of the variables and expressions introduced by the pass are added.
+    pub(crate) type_table: &'a mut TypeTable,
+    /// A counter used to name the variables that the pass introduces.
+    pub(crate) counter: usize,
+}
+
+impl<'a> TupleLowerer<'a> {
+    /// Initializes a new `TupleLowerer`.
+    pub(crate) fn new(node_builder: &'a NodeBuilder, type_table: &'a mut TypeTable) -> Self {
+        Self {
+            node_builder,
+            type_table,
+            counter: 0,
+        }
+    }
+
+    /// Returns the types of the elements of `node`, if it is a tuple.
+    pub(crate) fn tuple_type(&self, node: &impl Node) -> Option<Vec<Type>> {
+        match self.type_table.get(node) {
+            Some(Type::Tuple(tuple)) => Some(tuple.0.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns a new variable of type `type_`, for a value computed at `span`.
+    pub(crate) fn unique_identifier(&mut self, type_: Type, span: Span) -> Identifier {
+        let name = Symbol::intern_internal(&format!("$tuple${}", self.counter));
+        self.counter += 1;
+        let identifier = Identifier {
+            name,
+            span,
+            id: self.node_builder.next_id(),
+        };
+        self.type_table.insert(&identifier, type_);
+        identifier
+    }
+
+    /// Constructs the definition `let place: type_ = value;`, which was lowered from the statement at `span`.
+    pub(crate) fn definition(&self, place: Expression, type_: Type, value: Expression, span: Span) -> Statement {
+        Statement::Definition(DefinitionStatement {
+            declaration_type: DeclarationType::Let,
+            place,
+            type_: RefCell::new(Some(type_)),
+            value,
+            span,
+            id: self.node_builder.next_id(),
+        })
+    }
+
+    /// Reconstructs each of `expressions`, accumulating any statements that are generated.
+    pub(crate) fn reconstruct_elements(&mut self, expressions: Vec<Expression>) -> (Vec<Expression>, Vec<Statement>) {
+        let mut statements = Vec::new();
+        let expressions = expressions
+            .into_iter()
+            .map(|expression| {
+                let (expression, stmts) = self.reconstruct_expression(expression);
+                statements.extend(stmts);
+                expression
+            })
+            .collect();
+        (expressions, statements)
+    }
+
+    /// Returns the elements of an expression of tuple type, along with the statements that compute them.
+    /// Since variables cannot be tuples, the expression is a tuple expression, a ternary expression over tuples, or a call to a function with multiple outputs.
+    /// Note that type checking guarantees that tuples are not nested, so none of the elements is a tuple.
+    pub(crate) fn lower_tuple(&mut self, expression: Expression) -> (Vec<Expression>, Vec<Statement>) {
+        match expression {
+            Expression::Tuple(tuple) => self.reconstruct_elements(tuple.elements),
+            // A ternary expression over tuples is lowered into a ternary expression over each pair of elements.
+            Expression::Ternary(ternary) => {
+                let (condition, mut statements) = self.reconstruct_expression(*ternary.condition);
+                // Unless the condition is a variable or a literal, assign it to a new variable, so that it is evaluated once.
+                let condition = match condition {
+                    Expression::Identifier(_) | Expression::Literal(_) => condition,
+                    condition => {
+                        let span = condition.span().synthesized();
+                        let identifier = self.unique_identifier(Type::Boolean, span);
+                        statements.push(self.definition(
+                            Expression::Identifier(identifier),
+                            Type::Boolean,
+                            condition,
+                            span,
+                        ));
+                        Expression::Identifier(identifier)
+                    }
+                };
+                let (if_true, stmts) = self.lower_tuple(*ternary.if_true);
+                statements.extend(stmts);
+                let (if_false, stmts) = self.lower_tuple(*ternary.if_false);
+                statements.extend(stmts);
+
+                let elements = if_true
+                    .into_iter()
+                    .zip_eq(if_false)
+                    .map(|(if_true, if_false)| {
+                        let element = TernaryExpression {
+                            condition: Box::new(condition.clone()),
+                            if_true: Box::new(if_true),
+                            if_false: Box::new(if_false),
+                            span: ternary.span,
+                            id: self.node_builder.next_id(),
+                        };
+                        // The element has the type of its operands, which later passes rely on to lower ternary expressions over structs.
+                        if let Some(type_) = self.type_table.get(&*element.if_true).cloned() {
+                            self.type_table.insert(&element, type_);
+                        }
+                        Expression::Ternary(element)
+                    })
+                    .collect();
+                (elements, statements)
+            }
+            // A call to a function with multiple outputs is destructured into a new variable for each output.
+            Expression::Call(call) => {
+                let types = self
+                    .tuple_type(&call)
+                    .expect("Type checking records the output types of every call.");
+                let span = call.span.synthesized();
+                let (call, mut statements) = self.reconstruct_call(call);
+
+                let identifiers = types
+                    .iter()
+                    .map(|type_| self.unique_identifier(type_.clone(), span))
+                    .collect::<Vec<_>>();
+                let place = Expression::Tuple(TupleExpression {
+                    elements: identifiers.iter().copied().map(Expression::Identifier).collect(),
+                    span,
+                    id: self.node_builder.next_id(),
+                });
+                statements.push(self.definition(place, Type::Tuple(Tuple(types)), call, span));
+
+                (
+                    identifiers.into_iter().map(Expression::Identifier).collect(),
+                    statements,
+                )
+            }
+            Expression::Access(AccessExpression::Tuple(_)) => {
+                unreachable!("Type checking guarantees that tuples are not nested.")
+            }
+            _ => unreachable!(
+                "Type checking guarantees that only tuples, ternary expressions, and calls have tuple types."
+            ),
+        }
+    }
+}
@@ -18,9 +18,9 @@ use leo_ast::*;
 use leo_errors::emitter::Handler;
 use leo_errors::TypeCheckerError;
 use leo_span::{sym, Span};
-use std::str::FromStr;
+use std::{num::NonZeroUsize, str::FromStr};
 
-use crate::TypeChecker;
+use crate::{ConstEvaluator, TypeChecker};
 
 fn return_incorrect_type(t1: Option<Type>, t2: Option<Type>, expected: &Option<Type>) -> Option<Type> {
     match (t1, t2) {
@@ -44,11 +44,84 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
     type AdditionalInput = Option<Type>;
     type Output = Option<Type>;
 
+    /// Checks an expression, recording its type in the type table if it could be determined.
+    fn visit_expression(&mut self, input: &'a Expression, additional: &Self::AdditionalInput) -> Self::Output {
+        let type_ = match input {
+            Expression::Access(access) => self.visit_access(access, additional),
+            Expression::Array(array) => self.visit_array(array, additional),
+            Expression::Binary(binary) => self.visit_binary(binary, additional),
+            Expression::Call(call) => self.visit_call(call, additional),
+            Expression::Cast(cast) => self.visit_cast(cast, additional),
+            Expression::Struct(struct_) => self.visit_struct_init(struct_, additional),
+            Expression::Err(err) => self.visit_err(err, additional),
+            Expression::Identifier(identifier) => self.visit_identifier(identifier, additional),
+            Expression::Literal(literal) => self.visit_literal(literal, additional),
+            Expression::Ternary(ternary) => self.visit_ternary(ternary, additional),
+            Expression::Tuple(tuple) => self.visit_tuple(tuple, additional),
+            Expression::Unary(unary) => self.visit_unary(unary, additional),
+        };
+        if let Some(type_) = type_.as_ref().filter(|type_| !matches!(type_, Type::Err)) {
+            self.type_table.borrow_mut().insert(input, type_.clone());
+        }
+        type_
+    }
+
     fn visit_access(&mut self, input: &'a AccessExpression, expected: &Self::AdditionalInput) -> Self::Output {
         match input {
+            AccessExpression::Array(access) => {
+                // Check that the index is an unsigned integer, where unsuffixed indices are inferred to be `u32`s.
+                // Note that indices need not be known yet, e.g. loop variables, since they are resolved while unrolling loops.
+                let index_type = match &*access.index {
+                    Expression::Literal(Literal::Unsuffixed(..)) => {
+                        self.visit_expression(&access.index, &Some(Type::Integer(IntegerType::U32)))
+                    }
+                    _ => self.visit_expression(&access.index, &None),
+                };
+                self.assert_unsigned_int_type(&index_type, access.index.span());
+                let index = self
+                    .evaluate_constant(&access.index)
+                    .and_then(|value| u128::try_from(value).ok())
+                    .and_then(|value| usize::try_from(value).ok());
+
+                match self.visit_expression(&access.array, &None) {
+                    Some(Type::Array(element_type, length)) => {
+                        // Check out of range access.
+                        if let (Some(index), Some(length)) = (index, length.value()) {
+                            if index >= length.get() {
+                                self.emit_err(TypeCheckerError::array_out_of_range(index, length, access.span()));
+                            }
+                        }
+
+                        // Return the type of the array element.
+                        return Some(self.assert_and_return_type(*element_type, expected, access.span()));
+                    }
+                    Some(type_) => {
+                        self.emit_err(TypeCheckerError::type_should_be(type_, "array", access.array.span()));
+                    }
+                    None => {
+                        self.emit_err(TypeCheckerError::could_not_determine_type(
+                            &access.array,
+                            access.array.span(),
+                        ));
+                    }
+                }
+            }
             AccessExpression::AssociatedFunction(access) => {
+                // Check mapping operations, e.g. `Mapping::get(balances, addr)`.
+                if matches!(access.ty, Type::Identifier(identifier) if identifier.name == sym::Mapping) {
+                    return self.check_mapping_operation(access, expected, false);
+                }
+
                 // Check core struct name and function.
                 if let Some(core_instruction) = self.check_core_function_call(&access.ty, &access.name) {
+                    // Random values can only be generated on chain.
+                    if core_instruction.is_finalize_only() && !self.is_finalize {
+                        self.emit_err(TypeCheckerError::random_outside_finalize(
+                            format!("{}::{}", access.ty, access.name),
+                            access.span(),
+                        ));
+                    }
+
                     // Check num input arguments.
                     if core_instruction.num_args() != access.args.len() {
                         // TODO: Better error messages.
@@ -63,12 +136,24 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                     if let Some(first_arg) = access.args.get(0usize) {
                         if let Some(first_arg_type) = self.visit_expression(first_arg, &None) {
                             if !core_instruction.first_arg_is_allowed_type(&first_arg_type) {
-                                // TODO: Better error messages.
-                                self.emit_err(TypeCheckerError::invalid_type(
-                                    &first_arg_type,
-                                    access.args.get(0).unwrap().span(),
+                                self.emit_err(TypeCheckerError::invalid_core_function_argument(
+                                    "first",
+                                    format!("{}::{}", access.ty, access.name),
+                                    self.display_type(&first_arg_type),
+                                    core_instruction.first_arg_types(),
+                                    first_arg.span(),
                                 ));
                             }
+                            // A string is hashed as the field it is lowered to, so it must fit in a single field.
+                            if let Type::String(Some(length)) = first_arg_type {
+                                if Literal::string_field_count(length) > 1 {
+                                    self.emit_err(TypeCheckerError::string_too_long_to_hash(
+                                        length,
+                                        Literal::STRING_BYTES_PER_FIELD,
+                                        first_arg.span(),
+                                    ));
+                                }
+                            }
                         }
                     }
 
@@ -76,10 +161,27 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                     if let Some(second_arg) = access.args.get(1usize) {
                         if let Some(second_arg_type) = self.visit_expression(second_arg, &None) {
                             if !core_instruction.second_arg_is_allowed_type(&second_arg_type) {
-                                // TODO: Better error messages.
-                                self.emit_err(TypeCheckerError::invalid_type(
-                                    &second_arg_type,
-                                    access.args.get(1).unwrap().span(),
+                                self.emit_err(TypeCheckerError::invalid_core_function_argument(
+                                    "second",
+                                    format!("{}::{}", access.ty, access.name),
+                                    self.display_type(&second_arg_type),
+                                    core_instruction.second_arg_types(),
+                                    second_arg.span(),
+                                ));
+                            }
+                        }
+                    }
+
+                    // Check third argument type.
+                    if let Some(third_arg) = access.args.get(2usize) {
+                        if let Some(third_arg_type) = self.visit_expression(third_arg, &None) {
+                            if !core_instruction.third_arg_is_allowed_type(&third_arg_type) {
+                                self.emit_err(TypeCheckerError::invalid_core_function_argument(
+                                    "third",
+                                    format!("{}::{}", access.ty, access.name),
+                                    self.display_type(&third_arg_type),
+                                    core_instruction.third_arg_types(),
+                                    third_arg.span(),
                                 ));
                             }
                         }
@@ -128,22 +230,42 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                 match *access.inner {
                     // If the access expression is of the form `self.<name>`, then check the <name> is valid.
                     Expression::Identifier(identifier) if identifier.name == sym::SelfLower => match access.name.name {
-                        sym::caller => return Some(Type::Address),
+                        sym::caller | sym::signer => {
+                            return Some(self.assert_and_return_type(Type::Address, expected, access.span()))
+                        }
                         _ => {
                             self.emit_err(TypeCheckerError::invalid_self_access(access.name.span()));
                         }
                     },
+                    // If the access expression is of the form `block.<name>`, then check the <name> is valid.
+                    Expression::Identifier(identifier) if identifier.name == sym::block => match access.name.name {
+                        sym::height => {
+                            // The block height is only known on chain.
+                            if !self.is_finalize {
+                                self.emit_err(TypeCheckerError::block_height_outside_finalize(
+                                    access.span() + access.name.span,
+                                ));
+                            }
+                            return Some(self.assert_and_return_type(
+                                Type::Integer(IntegerType::U32),
+                                expected,
+                                access.span(),
+                            ));
+                        }
+                        _ => {
+                            self.emit_err(TypeCheckerError::invalid_block_access(access.name.span()));
+                        }
+                    },
                     _ => {
                         // Check that the type of `inner` in `inner.name` is a struct.
                         match self.visit_expression(&access.inner, &None) {
-                            Some(Type::Identifier(identifier)) => {
-                                // Retrieve the struct definition associated with `identifier`.
-                                let struct_ = self.symbol_table.borrow().lookup_struct(identifier.name).cloned();
-                                if let Some(struct_) = struct_ {
+                            Some(type_ @ (Type::Identifier(_) | Type::StructInstance(_))) => {
+                                // Retrieve the struct definition associated with the type.
+                                if let Some(struct_) = self.lookup_struct_type(&type_) {
                                     // Check that `access.name` is a member of the struct.
                                     match struct_.members.iter().find(|member| member.name() == access.name.name) {
                                         // Case where `access.name` is a member of the struct.
-                                        Some(Member { type_, .. }) => return Some(type_.clone()),
+                                        Some(Member { type_, .. }) => return Some(self.resolve_sizes(type_)),
                                         // Case where `access.name` is not a member of the struct.
                                         None => {
                                             self.emit_err(TypeCheckerError::invalid_struct_variable(
@@ -170,13 +292,74 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                     }
                 }
             }
-            AccessExpression::AssociatedConstant(..) => {} // todo: Add support for associated constants (u8::MAX).
+            AccessExpression::AssociatedConstant(constant) => {
+                // The variants of an enum are its associated constants, e.g. `State::Idle`.
+                if let Type::Identifier(identifier) = &constant.ty {
+                    let variant = self
+                        .symbol_table
+                        .borrow()
+                        .lookup_enum(identifier.name)
+                        .map(|enum_| enum_.variant_index(constant.name.name));
+                    match variant {
+                        Some(Some(_)) => {
+                            return Some(self.assert_and_return_type(constant.ty.clone(), expected, constant.span()));
+                        }
+                        Some(None) => {
+                            self.emit_err(TypeCheckerError::unknown_variant(
+                                identifier,
+                                constant.name,
+                                constant.name.span,
+                            ));
+                        }
+                        None => {}
+                    }
+                }
+                // todo: Add support for associated constants (u8::MAX).
+            }
         }
         None
     }
 
+    fn visit_array(&mut self, input: &'a ArrayExpression, expected: &Self::AdditionalInput) -> Self::Output {
+        // Use the expected element type if it is known, otherwise use the type of the first element.
+        let expected_element_type = match expected {
+            Some(Type::Array(element_type, _)) => Some(*element_type.clone()),
+            _ => None,
+        };
+
+        let mut element_type = None;
+        for element in input.elements.iter() {
+            let type_ = self.visit_expression(element, &expected_element_type.clone().or_else(|| element_type.clone()));
+            if element_type.is_none() {
+                element_type = type_;
+            }
+        }
+
+        // Note that the parser guarantees that arrays are non-empty.
+        let length = NonZeroUsize::new(input.elements.len()).expect("arrays must be non-empty");
+        let element_type = element_type?;
+
+        // Strings are only allowed as the values of constants, so they cannot be stored in arrays.
+        self.assert_not_string(input.span(), &element_type);
+
+        let actual = Type::Array(Box::new(element_type), Size::Literal(length));
+
+        match expected {
+            // Check actual length is equal to expected length.
+            Some(Type::Array(_, expected_length)) if *expected_length != Size::Literal(length) => {
+                self.emit_err(TypeCheckerError::incorrect_array_length(
+                    expected_length,
+                    length,
+                    input.span(),
+                ));
+                Some(actual)
+            }
+            _ => Some(self.assert_and_return_type(actual, expected, input.span())),
+        }
+    }
+
     fn visit_binary(&mut self, input: &'a BinaryExpression, destination: &Self::AdditionalInput) -> Self::Output {
-        match input.op {
+        let type_ = match input.op {
             BinaryOperation::And | BinaryOperation::Or | BinaryOperation::Nand | BinaryOperation::Nor => {
                 // Only boolean types.
                 self.assert_bool_type(destination, input.span());
@@ -225,8 +408,7 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                 // Operation returns field, group or integer types.
                 self.assert_field_group_int_type(destination, input.span());
 
-                let t1 = self.visit_expression(&input.left, &None);
-                let t2 = self.visit_expression(&input.right, &None);
+                let (t1, t2) = self.visit_operands(&input.left, &input.right);
 
                 // Allow group * scalar multiplication.
                 match (t1, input.left.span(), t2, input.right.span()) {
@@ -360,12 +542,16 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
             }
             BinaryOperation::Eq | BinaryOperation::Neq => {
                 // Assert first and second address, boolean, field, group, scalar, or integer types.
-                let t1 = self.visit_expression(&input.left, &None);
-                let t2 = self.visit_expression(&input.right, &None);
+                let (t1, t2) = self.visit_operands(&input.left, &input.right);
 
                 // Check that the types of the operands are equal.
                 self.check_eq_types(&t1, &t2, input.span());
 
+                // Check that the operands are not arrays.
+                if let Some(type_) = &t1 {
+                    self.assert_not_array(input.span(), type_);
+                }
+
                 // Operation returns a boolean.
                 self.assert_bool_type(destination, input.span());
 
@@ -373,8 +559,7 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
             }
             BinaryOperation::Lt | BinaryOperation::Gt | BinaryOperation::Lte | BinaryOperation::Gte => {
                 // Assert left and right are equal field, scalar, or integer types.
-                let t1 = self.visit_expression(&input.left, &None);
-                let t2 = self.visit_expression(&input.right, &None);
+                let (t1, t2) = self.visit_operands(&input.left, &input.right);
 
                 match (&t1, &t2) {
                     (Some(Type::Address), _) | (_, Some(Type::Address)) => {
@@ -426,7 +611,14 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
 
                 return_incorrect_type(t1, t2, destination)
             }
+        };
+
+        if ConstEvaluator::is_checked_binary(input.op) {
+            let evaluation = ConstEvaluator::new(&self.symbol_table.borrow()).evaluate_checked_binary(input);
+            self.check_integer_overflow(input, input.span(), evaluation);
         }
+
+        type_
     }
 
     fn visit_call(&mut self, input: &'a CallExpression, expected: &Self::AdditionalInput) -> Self::Output {
@@ -440,6 +632,8 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                 if let Some(func) = func {
                     // Check that the call is valid.
                     match self.is_transition_function {
+                        // Inline functions are copied to the call site, so any function can call them.
+                        _ if matches!(func.call_type, CallType::Inline) && input.external.is_none() => {}
                         // If the function is not a transition function, it cannot call any other functions.
                         false => {
                             self.emit_err(TypeCheckerError::cannot_invoke_call_from_standard_function(input.span));
@@ -454,6 +648,33 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                         }
                     }
 
+                    // Check that an external call is to a transition of the imported program that it names.
+                    if let Some(external) = &input.external {
+                        let program = match &**external {
+                            Expression::Identifier(program) => program.name,
+                            _ => unreachable!("The parser guarantees that the external program is an identifier."),
+                        };
+                        if func.program != program || !matches!(func.call_type, CallType::Transition) {
+                            self.emit_err(TypeCheckerError::unknown_external_transition(
+                                program,
+                                ident.name,
+                                input.span(),
+                            ));
+                        }
+                    }
+
+                    // Add the call to the call graph.
+                    // Note that cycles are reported once every function has been checked.
+                    if let (Some(caller), None) = (self.function, &input.external) {
+                        self.call_graph.add_edge(caller, ident.name);
+                        self.call_sites.entry((caller, ident.name)).or_insert(input.span);
+                    }
+
+                    // The types of a generic function depend on the types of the arguments it is called with.
+                    if !func.type_parameters.is_empty() {
+                        return self.check_generic_call(&func, input, expected);
+                    }
+
                     let ret = self.assert_and_return_type(func.output_type, expected, func.span);
 
                     // Check number of function arguments.
@@ -483,11 +704,32 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
         }
     }
 
+    fn visit_cast(&mut self, input: &'a CastExpression, expected: &Self::AdditionalInput) -> Self::Output {
+        // Check that the type being cast to is valid.
+        self.assert_type_is_valid(input.span, &input.type_);
+
+        // The type of the value being cast is independent of the type it is cast to.
+        if let Some(type_) = self.visit_expression(&input.expression, &None) {
+            self.assert_castable(&type_, &input.type_, input.span);
+        }
+
+        Some(self.assert_and_return_type(input.type_.clone(), expected, input.span))
+    }
+
     fn visit_struct_init(&mut self, input: &'a StructExpression, additional: &Self::AdditionalInput) -> Self::Output {
-        let struct_ = self.symbol_table.borrow().lookup_struct(input.name.name).cloned();
+        // The init of an instance of a generic struct gives its const arguments, e.g. `Buffer::<4> { ... }`.
+        let type_ = match input.const_arguments.is_empty() {
+            true => Type::Identifier(input.name),
+            false => Type::StructInstance(StructInstance {
+                identifier: input.name,
+                const_arguments: input.const_arguments.clone(),
+            }),
+        };
+        let struct_ = self.lookup_struct_type(&self.resolve_sizes(&type_));
         if let Some(struct_) = struct_ {
-            // Check struct type name.
-            let ret = self.check_expected_struct(struct_.identifier, additional, input.name.span());
+            // Check struct type name and const arguments.
+            self.assert_type_is_valid(input.name.span(), &type_);
+            let ret = self.check_expected_struct(self.resolve_sizes(&type_), additional, input.name.span());
 
             // Check number of struct members.
             if struct_.members.len() != input.members.len() {
@@ -506,8 +748,14 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                     .iter()
                     .find(|member| member.identifier.name == identifier.name)
                 {
-                    if let Some(expr) = &actual.expression {
-                        self.visit_expression(expr, &Some(type_.clone()));
+                    match &actual.expression {
+                        Some(expr) => {
+                            self.visit_expression(expr, &Some(self.resolve_sizes(type_)));
+                        }
+                        // A member initialized by shorthand, e.g. `Foo { x }`, reads the variable of the same name.
+                        None => {
+                            self.unused_variables.borrow_mut().shift_remove(&actual.identifier.name);
+                        }
                     }
                 } else {
                     self.emit_err(TypeCheckerError::missing_struct_member(
@@ -535,8 +783,9 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
     }
 
     fn visit_identifier(&mut self, var: &'a Identifier, expected: &Self::AdditionalInput) -> Self::Output {
-        if let Some(var) = self.symbol_table.borrow().lookup_variable(var.name) {
-            Some(self.assert_and_return_type(var.type_.clone(), expected, var.span))
+        if let Some(symbol) = self.symbol_table.borrow().lookup_variable(var.name) {
+            self.unused_variables.borrow_mut().shift_remove(&var.name);
+            Some(self.assert_and_return_type(symbol.type_.clone(), expected, symbol.span))
         } else {
             self.emit_err(TypeCheckerError::unknown_sym("variable", var.name, var.span()));
             None
@@ -551,54 +800,77 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
         }
 
         Some(match input {
-            Literal::Address(_, _) => self.assert_and_return_type(Type::Address, expected, input.span()),
-            Literal::Boolean(_, _) => self.assert_and_return_type(Type::Boolean, expected, input.span()),
-            Literal::Field(_, _) => self.assert_and_return_type(Type::Field, expected, input.span()),
-            Literal::Integer(integer_type, string, _) => match integer_type {
-                IntegerType::U8 => {
-                    parse_integer_literal::<u8>(self.handler, string, input.span(), "u8");
-                    self.assert_and_return_type(Type::Integer(IntegerType::U8), expected, input.span())
-                }
-                IntegerType::U16 => {
-                    parse_integer_literal::<u16>(self.handler, string, input.span(), "u16");
-                    self.assert_and_return_type(Type::Integer(IntegerType::U16), expected, input.span())
-                }
-                IntegerType::U32 => {
-                    parse_integer_literal::<u32>(self.handler, string, input.span(), "u32");
-                    self.assert_and_return_type(Type::Integer(IntegerType::U32), expected, input.span())
-                }
-                IntegerType::U64 => {
-                    parse_integer_literal::<u64>(self.handler, string, input.span(), "u64");
-                    self.assert_and_return_type(Type::Integer(IntegerType::U64), expected, input.span())
-                }
-                IntegerType::U128 => {
-                    parse_integer_literal::<u128>(self.handler, string, input.span(), "u128");
-                    self.assert_and_return_type(Type::Integer(IntegerType::U128), expected, input.span())
-                }
-                IntegerType::I8 => {
-                    parse_integer_literal::<i8>(self.handler, string, input.span(), "i8");
-                    self.assert_and_return_type(Type::Integer(IntegerType::I8), expected, input.span())
-                }
-                IntegerType::I16 => {
-                    parse_integer_literal::<i16>(self.handler, string, input.span(), "i16");
-                    self.assert_and_return_type(Type::Integer(IntegerType::I16), expected, input.span())
-                }
-                IntegerType::I32 => {
-                    parse_integer_literal::<i32>(self.handler, string, input.span(), "i32");
-                    self.assert_and_return_type(Type::Integer(IntegerType::I32), expected, input.span())
-                }
-                IntegerType::I64 => {
-                    parse_integer_literal::<i64>(self.handler, string, input.span(), "i64");
-                    self.assert_and_return_type(Type::Integer(IntegerType::I64), expected, input.span())
-                }
-                IntegerType::I128 => {
-                    parse_integer_literal::<i128>(self.handler, string, input.span(), "i128");
-                    self.assert_and_return_type(Type::Integer(IntegerType::I128), expected, input.span())
+            Literal::Address(_, _, _) => self.assert_and_return_type(Type::Address, expected, input.span()),
+            Literal::Boolean(_, _, _) => self.assert_and_return_type(Type::Boolean, expected, input.span()),
+            Literal::Field(_, _, _) => self.assert_and_return_type(Type::Field, expected, input.span()),
+            Literal::Integer(..) | Literal::Unsuffixed(..) => {
+                let (integer_type, string) = match input {
+                    Literal::Integer(integer_type, string, _, _) => (*integer_type, string),
+                    Literal::Unsuffixed(string, type_, _, _) => {
+                        // Infer the type from the context, falling back to the default integer type.
+                        let integer_type = match expected {
+                            Some(Type::Integer(integer_type)) => *integer_type,
+                            _ => match self.default_integer_type {
+                                Some(integer_type) => integer_type,
+                                None => {
+                                    self.emit_err(TypeCheckerError::cannot_infer_integer_type(string, input.span()));
+                                    return None;
+                                }
+                            },
+                        };
+                        type_.set(Some(integer_type));
+                        (integer_type, string)
+                    }
+                    _ => unreachable!(),
+                };
+                match integer_type {
+                    IntegerType::U8 => {
+                        parse_integer_literal::<u8>(self.handler, string, input.span(), "u8");
+                        self.assert_and_return_type(Type::Integer(IntegerType::U8), expected, input.span())
+                    }
+                    IntegerType::U16 => {
+                        parse_integer_literal::<u16>(self.handler, string, input.span(), "u16");
+                        self.assert_and_return_type(Type::Integer(IntegerType::U16), expected, input.span())
+                    }
+                    IntegerType::U32 => {
+                        parse_integer_literal::<u32>(self.handler, string, input.span(), "u32");
+                        self.assert_and_return_type(Type::Integer(IntegerType::U32), expected, input.span())
+                    }
+                    IntegerType::U64 => {
+                        parse_integer_literal::<u64>(self.handler, string, input.span(), "u64");
+                        self.assert_and_return_type(Type::Integer(IntegerType::U64), expected, input.span())
+                    }
+                    IntegerType::U128 => {
+                        parse_integer_literal::<u128>(self.handler, string, input.span(), "u128");
+                        self.assert_and_return_type(Type::Integer(IntegerType::U128), expected, input.span())
+                    }
+                    IntegerType::I8 => {
+                        parse_integer_literal::<i8>(self.handler, string, input.span(), "i8");
+                        self.assert_and_return_type(Type::Integer(IntegerType::I8), expected, input.span())
+                    }
+                    IntegerType::I16 => {
+                        parse_integer_literal::<i16>(self.handler, string, input.span(), "i16");
+                        self.assert_and_return_type(Type::Integer(IntegerType::I16), expected, input.span())
+                    }
+                    IntegerType::I32 => {
+                        parse_integer_literal::<i32>(self.handler, string, input.span(), "i32");
+                        self.assert_and_return_type(Type::Integer(IntegerType::I32), expected, input.span())
+                    }
+                    IntegerType::I64 => {
+                        parse_integer_literal::<i64>(self.handler, string, input.span(), "i64");
+                        self.assert_and_return_type(Type::Integer(IntegerType::I64), expected, input.span())
+                    }
+                    IntegerType::I128 => {
+                        parse_integer_literal::<i128>(self.handler, string, input.span(), "i128");
+                        self.assert_and_return_type(Type::Integer(IntegerType::I128), expected, input.span())
+                    }
                 }
-            },
+            }
             Literal::Group(_) => self.assert_and_return_type(Type::Group, expected, input.span()),
-            Literal::Scalar(_, _) => self.assert_and_return_type(Type::Scalar, expected, input.span()),
-            Literal::String(_, _) => self.assert_and_return_type(Type::String, expected, input.span()),
+            Literal::Scalar(_, _, _) => self.assert_and_return_type(Type::Scalar, expected, input.span()),
+            Literal::String(string, _, _) => {
+                self.assert_and_return_type(Type::String(Some(string.len())), expected, input.span())
+            }
         })
     }
 
@@ -608,6 +880,11 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
         let t1 = self.visit_expression(&input.if_true, expected);
         let t2 = self.visit_expression(&input.if_false, expected);
 
+        // Strings are only allowed as the values of constants, so they cannot be selected at runtime.
+        if let Some(type_) = &t1 {
+            self.assert_not_string(input.span(), type_);
+        }
+
         return_incorrect_type(t1, t2, expected)
     }
 
@@ -647,7 +924,7 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
     }
 
     fn visit_unary(&mut self, input: &'a UnaryExpression, destination: &Self::AdditionalInput) -> Self::Output {
-        match input.op {
+        let type_ = match input.op {
             UnaryOperation::Abs => {
                 // Only signed integer types.
                 self.assert_signed_int_type(destination, input.span());
@@ -690,6 +967,13 @@ impl<'a> ExpressionVisitor<'a> for TypeChecker<'a> {
                 self.assert_field_type(destination, input.span());
                 self.visit_expression(&input.receiver, destination)
             }
+        };
+
+        if ConstEvaluator::is_checked_unary(input.op) {
+            let evaluation = ConstEvaluator::new(&self.symbol_table.borrow()).evaluate_checked_unary(input);
+            self.check_integer_overflow(ConstEvaluator::display_checked_unary(input), input.span(), evaluation);
         }
+
+        type_
     }
 }
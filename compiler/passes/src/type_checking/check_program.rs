@@ -17,7 +17,7 @@
 use crate::{TypeChecker, VariableSymbol, VariableType};
 
 use leo_ast::*;
-use leo_errors::TypeCheckerError;
+use leo_errors::{LeoWarning, TypeCheckerError, TypeCheckerWarning};
 
 use leo_span::sym;
 
@@ -26,7 +26,123 @@ use std::collections::HashSet;
 // TODO: Generally, cleanup tyc logic.
 
 impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
+    fn visit_program_scope(&mut self, input: &'a ProgramScope) {
+        // Warn if the program scope does not contain any functions.
+        if input.functions.is_empty() {
+            self.emit_warning(TypeCheckerWarning::program_has_no_functions(
+                input.program_id,
+                input.span,
+            ));
+        }
+
+        // A `@default_integer_type(..)` annotation on the program scope takes precedence over the compiler option.
+        let previous_default_integer_type = self.default_integer_type;
+        if input.default_integer_type.is_some() {
+            self.default_integer_type = input.default_integer_type;
+        }
+
+        input.consts.values().for_each(|const_| self.visit_const(const_));
+
+        // Resolve the sizes in the declared types of the program, now that the values of the constants are known.
+        let sizes = self.sizes.clone();
+        self.symbol_table
+            .borrow_mut()
+            .map_types(|type_| type_.substitute_sizes(&sizes));
+
+        input.structs.values().for_each(|struct_| self.visit_struct(struct_));
+
+        self.check_for_struct_cycles();
+
+        input.enums.values().for_each(|enum_| self.visit_enum(enum_));
+
+        input.mappings.values().for_each(|mapping| self.visit_mapping(mapping));
+
+        input
+            .functions
+            .values()
+            .for_each(|function| self.visit_function(function));
+
+        self.check_for_recursion();
+
+        // Warn about the functions that are never called.
+        // Note that transitions are the entry points of the program, so they are never unused.
+        input
+            .functions
+            .values()
+            .filter(|function| !matches!(function.call_type, CallType::Transition))
+            .filter(|function| {
+                !input.functions.values().any(|caller| {
+                    caller.name() != function.name() && self.call_graph.contains_edge(caller.name(), function.name())
+                })
+            })
+            .for_each(|function| {
+                self.emit_warning(TypeCheckerWarning::unused_function(
+                    function.identifier,
+                    function.identifier.span,
+                ))
+            });
+
+        self.default_integer_type = previous_default_integer_type;
+    }
+
+    fn visit_const(&mut self, input: &'a ConstDeclaration) {
+        // Check that the type of the constant is valid.
+        self.assert_type_is_valid(input.span, &input.type_);
+
+        let declared_type = self.resolve_sizes(&input.type_);
+        let value_type = self.visit_expression(&input.value, &Some(declared_type.clone()));
+
+        // A constant may be a string, in which case its length is given by its value.
+        let type_ = match (&declared_type, value_type) {
+            (Type::String(None), Some(value_type @ Type::String(Some(_)))) => value_type,
+            (type_, _) => {
+                if !matches!(type_, Type::String(_)) {
+                    self.assert_not_string(input.span, type_);
+                }
+                type_.clone()
+            }
+        };
+
+        // Insert the constant into the global scope, so that it is visible in every function.
+        if let Err(err) = self.symbol_table.borrow_mut().insert_variable(
+            input.identifier.name,
+            VariableSymbol {
+                type_,
+                span: input.span,
+                declaration: VariableType::Const,
+            },
+        ) {
+            self.handler.emit_err(err);
+        }
+
+        // Record the value of the constant, which is substituted for its uses before loop unrolling.
+        // A constant whose value is a positive integer may also be used as a size.
+        match self.evaluate_constant(&input.value) {
+            Some(value) => {
+                if let Some(size) = value.to_size() {
+                    self.sizes.insert(input.identifier.name, size);
+                }
+                self.symbol_table
+                    .borrow_mut()
+                    .insert_constant(input.identifier.name, value)
+            }
+            // Only report the constant if its value is not already known to be erroneous.
+            None if !self.handler.had_errors() => self.emit_err(TypeCheckerError::const_value_not_constant(
+                input.identifier,
+                input.value.span(),
+            )),
+            None => {}
+        }
+    }
+
     fn visit_struct(&mut self, input: &'a Struct) {
+        // The const parameters of a generic struct may be used as sizes in the types of its members.
+        self.const_parameters = input
+            .const_parameters
+            .iter()
+            .map(|parameter| parameter.identifier.name)
+            .collect();
+
         // Check for conflicting struct/record member names.
         let mut used = HashSet::new();
         if !input.members.iter().all(|Member { identifier, type_ }| {
@@ -42,47 +158,94 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
             });
         }
 
-        // For records, enforce presence of `owner: Address` and `gates: u64` members.
+        // For records, enforce that the `owner` and `gates` members have types `address` and `u64`.
+        // Note that the parser injects these members if they are not declared, so only conflicting declarations are rejected.
         if input.is_record {
-            let check_has_field = |need, expected_ty: Type| match input
-                .members
-                .iter()
-                .find_map(|Member { identifier, type_ }| (identifier.name == need).then_some((identifier, type_)))
-            {
-                Some((_, actual_ty)) if expected_ty.eq_flat(actual_ty) => {} // All good, found + right type!
-                Some((field, _)) => {
-                    self.emit_err(TypeCheckerError::record_var_wrong_type(
-                        field,
-                        expected_ty,
-                        input.span(),
-                    ));
-                }
-                None => {
-                    self.emit_err(TypeCheckerError::required_record_variable(
-                        need,
-                        expected_ty,
-                        input.span(),
-                    ));
+            let check_field_type = |need, expected_ty: Type| {
+                if let Some(Member { identifier, type_ }) = input.members.iter().find(|member| member.name() == need) {
+                    if !expected_ty.eq_flat(type_) {
+                        self.emit_err(TypeCheckerError::record_var_wrong_type(
+                            identifier,
+                            expected_ty,
+                            input.span(),
+                        ));
+                    }
                 }
             };
-            check_has_field(sym::owner, Type::Address);
-            check_has_field(sym::gates, Type::Integer(IntegerType::U64));
+            check_field_type(sym::owner, Type::Address);
+            check_field_type(sym::gates, Type::Integer(IntegerType::U64));
         }
 
         for Member { identifier, type_ } in input.members.iter() {
             // Ensure there are no tuple typed members.
             self.assert_not_tuple(identifier.span, type_);
+            // Ensure there are no array typed members, other than struct members that are arrays of primitive types.
+            // Note that these members are lowered into a member per element.
+            match type_ {
+                Type::Array(element_type, _)
+                    if !input.is_record
+                        && !matches!(
+                            **element_type,
+                            Type::Array(..)
+                                | Type::Identifier(_)
+                                | Type::StructInstance(_)
+                                | Type::Tuple(_)
+                                | Type::String(_)
+                        ) => {}
+                _ => self.assert_not_array(identifier.span, type_),
+            }
+            // Ensure there are no string typed members.
+            self.assert_not_string(identifier.span, type_);
             // Ensure that there are no record members.
             self.assert_member_is_not_record(identifier.span, input.identifier.name, type_);
         }
+
+        // Add an edge to the struct graph for each member whose type is a struct.
+        self.struct_graph.add_node(input.name());
+        for Member { identifier, type_ } in input.members.iter() {
+            // Note that enums are lowered into integers, so they are not nodes of the struct graph.
+            // Instances of a generic struct are represented by the generic struct.
+            let member_type = match type_ {
+                Type::Identifier(identifier) => Some(identifier),
+                Type::StructInstance(instance) => Some(&instance.identifier),
+                _ => None,
+            };
+            if let Some(member_type) = member_type {
+                if self.symbol_table.borrow().lookup_enum(member_type.name).is_some() {
+                    continue;
+                }
+                self.struct_graph.add_edge(input.name(), member_type.name);
+                self.struct_members
+                    .entry((input.name(), member_type.name))
+                    .or_insert(*identifier);
+            }
+        }
+
+        self.const_parameters.clear();
+    }
+
+    fn visit_enum(&mut self, input: &'a Enum) {
+        // Check for conflicting variant names.
+        let mut used = HashSet::new();
+        for variant in input.variants.iter() {
+            if !used.insert(variant.name) {
+                self.emit_err(TypeCheckerError::duplicate_enum_variant(
+                    input.name(),
+                    variant,
+                    variant.span,
+                ));
+            }
+        }
     }
 
     fn visit_mapping(&mut self, input: &'a Mapping) {
         // Check that a mapping's key type is valid.
         self.assert_type_is_valid(input.span, &input.key_type);
+        self.assert_not_string(input.span, &input.key_type);
         // Check that a mapping's key type is not tuple types or mapping types.
         match input.key_type {
             Type::Tuple(_) => self.emit_err(TypeCheckerError::invalid_mapping_type("key", "tuple", input.span)),
+            Type::Array(..) => self.emit_err(TypeCheckerError::invalid_mapping_type("key", "array", input.span)),
             // Note that this is not possible since the parser does not currently accept mapping types.
             Type::Mapping(_) => self.emit_err(TypeCheckerError::invalid_mapping_type("key", "mapping", input.span)),
             _ => {}
@@ -90,9 +253,11 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
 
         // Check that a mapping's value type is valid.
         self.assert_type_is_valid(input.span, &input.value_type);
+        self.assert_not_string(input.span, &input.value_type);
         // Check that a mapping's value type is not tuple types or mapping types.
         match input.value_type {
             Type::Tuple(_) => self.emit_err(TypeCheckerError::invalid_mapping_type("value", "tuple", input.span)),
+            Type::Array(..) => self.emit_err(TypeCheckerError::invalid_mapping_type("value", "array", input.span)),
             // Note that this is not possible since the parser does not currently accept mapping types.
             Type::Mapping(_) => self.emit_err(TypeCheckerError::invalid_mapping_type("value", "mapping", input.span)),
             _ => {}
@@ -101,13 +266,75 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
 
     fn visit_function(&mut self, function: &'a Function) {
         // Check that the function's annotations are valid.
-        // Note that Leo does not natively support any specific annotations.
+        // Note that `@mint` marks transitions that are allowed to create gates,
+        // `@allow(..)` allows the named lints within the function,
+        // `@inline(always)` or `@inline(never)` overrides the inlining heuristic for a standard function,
+        // and `@test` marks a transition without inputs as a unit test.
         for annotation in function.annotations.iter() {
-            self.emit_err(TypeCheckerError::unknown_annotation(annotation, annotation.span))
+            match annotation.identifier.name {
+                sym::mint if annotation.arguments.is_empty() && matches!(function.call_type, CallType::Transition) => {}
+                sym::allow if !annotation.arguments.is_empty() => {
+                    for lint in annotation.arguments.iter().map(|argument| argument.identifier) {
+                        match LeoWarning::names().any(|name| lint.name.to_string() == name) {
+                            true => self.handler.allow_lint_in(lint.name.to_string(), function.span),
+                            false => {
+                                let lints: Vec<String> =
+                                    LeoWarning::names().map(|name| format!("`{}`", name)).collect();
+                                self.emit_err(TypeCheckerError::unknown_lint(lint, lints.join(", "), lint.span))
+                            }
+                        }
+                    }
+                }
+                sym::inline
+                    if matches!(function.call_type, CallType::Standard)
+                        && annotation.arguments.len() == 1
+                        && matches!(annotation.arguments[0].identifier.name, sym::always | sym::never) => {}
+                sym::test if annotation.arguments.is_empty() => {
+                    if !matches!(function.call_type, CallType::Transition) || !function.input.is_empty() {
+                        self.emit_err(TypeCheckerError::invalid_test_function(
+                            function.identifier,
+                            function.identifier.span,
+                        ));
+                    }
+                }
+                _ => self.emit_err(TypeCheckerError::unknown_annotation(annotation, annotation.span)),
+            }
         }
 
         self.is_transition_function = matches!(function.call_type, CallType::Transition);
 
+        // Check that the function's type parameters are valid.
+        // Note that each type parameter must be the type of an input, so that it can be inferred from the arguments of each call.
+        if function.is_generic() && self.is_transition_function {
+            self.emit_err(TypeCheckerError::generic_transition(
+                function.identifier,
+                function.identifier.span,
+            ));
+        }
+        for parameter in function.type_parameters.iter() {
+            if parameter.bound.name != sym::integer {
+                self.emit_err(TypeCheckerError::unknown_type_bound(
+                    parameter.bound,
+                    parameter.bound.span,
+                ));
+            }
+            let is_input_type = |input: &Input| match input.type_() {
+                Type::Identifier(identifier) => identifier.name == parameter.identifier.name,
+                _ => false,
+            };
+            if !function.input.iter().any(is_input_type) {
+                self.emit_err(TypeCheckerError::uninferable_type_parameter(
+                    parameter.identifier,
+                    parameter.span,
+                ));
+            }
+        }
+        self.type_parameters = function
+            .type_parameters
+            .iter()
+            .map(|parameter| parameter.identifier.name)
+            .collect();
+
         // Lookup function metadata in the symbol table.
         // Note that this unwrap is safe since function metadata is stored in a prior pass.
         let function_index = self
@@ -129,6 +356,9 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
         // Store the name of the function.
         self.function = Some(function.name());
 
+        // Add the function to the call graph.
+        self.call_graph.add_node(function.name());
+
         // Create a new child scope for the function's parameters and body.
         let scope_index = self.create_child_scope();
 
@@ -137,6 +367,8 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
             // Check that the type of input parameter is valid.
             self.assert_type_is_valid(input_var.span(), &input_var.type_());
             self.assert_not_tuple(input_var.span(), &input_var.type_());
+            self.assert_not_array(input_var.span(), &input_var.type_());
+            self.assert_not_string(input_var.span(), &input_var.type_());
 
             match self.is_transition_function {
                 // If the function is a transition function, then check that the parameter mode is not a constant.
@@ -150,16 +382,29 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
                 _ => {} // Do nothing.
             }
 
+            // Only transitions can take records.
+            if !self.is_transition_function {
+                self.assert_not_record(input_var.span(), &input_var.type_());
+            }
+
             // Check for conflicting variable names.
-            if let Err(err) = self.symbol_table.borrow_mut().insert_variable(
+            // Note that the inputs of transitions are part of the program's interface, so they may be unused.
+            let type_ = self.resolve_sizes(&input_var.type_());
+            match self.symbol_table.borrow_mut().insert_variable(
                 input_var.identifier().name,
                 VariableSymbol {
-                    type_: input_var.type_(),
+                    type_: type_.clone(),
                     span: input_var.identifier().span(),
                     declaration: VariableType::Input(input_var.mode()),
                 },
             ) {
-                self.handler.emit_err(err);
+                Ok(()) => {
+                    self.type_table.borrow_mut().insert(&input_var.identifier(), type_);
+                    if !self.is_transition_function {
+                        self.declare_variable(input_var.identifier());
+                    }
+                }
+                Err(err) => self.handler.emit_err(err),
             }
         });
 
@@ -170,15 +415,28 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
                 Output::Internal(output_type) => {
                     // Check that the type of output is valid.
                     self.assert_type_is_valid(output_type.span, &output_type.type_);
+                    self.assert_not_array(output_type.span, &output_type.type_);
+                    self.assert_not_string(output_type.span, &output_type.type_);
 
                     // Check that the mode of the output is valid.
                     if output_type.mode == Mode::Const {
                         self.emit_err(TypeCheckerError::cannot_have_constant_output_mode(output_type.span));
                     }
+
+                    // Only transitions can return records.
+                    if !self.is_transition_function {
+                        self.assert_not_record(output_type.span, &output_type.type_);
+                    }
                 }
             }
         });
 
+        // Warn if the function's body is empty.
+        // Note that functions with a return type are caught by the check for a missing return below.
+        if function.block.statements.is_empty() && function.output_type == Type::Unit {
+            self.emit_warning(TypeCheckerWarning::empty_function(function.identifier, function.span));
+        }
+
         self.visit_block(&function.block);
 
         // Check that the return type is valid.
@@ -197,6 +455,9 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
         // Exit the scope for the function's parameters and body.
         self.exit_scope(scope_index);
 
+        // Type parameters are not in scope outside of the function.
+        self.type_parameters.clear();
+
         // Traverse and check the finalize block if it exists.
         if let Some(finalize) = &function.finalize {
             self.is_finalize = true;
@@ -228,6 +489,8 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
                 // Check that the type of input parameter is valid.
                 self.assert_type_is_valid(input_var.span(), &input_var.type_());
                 self.assert_not_tuple(input_var.span(), &input_var.type_());
+                self.assert_not_array(input_var.span(), &input_var.type_());
+                self.assert_not_string(input_var.span(), &input_var.type_());
 
                 // Check that the input parameter is not constant or private.
                 if input_var.mode() == Mode::Const || input_var.mode() == Mode::Private {
@@ -235,15 +498,19 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
                 }
 
                 // Check for conflicting variable names.
-                if let Err(err) = self.symbol_table.borrow_mut().insert_variable(
+                let type_ = self.resolve_sizes(&input_var.type_());
+                match self.symbol_table.borrow_mut().insert_variable(
                     input_var.identifier().name,
                     VariableSymbol {
-                        type_: input_var.type_(),
+                        type_: type_.clone(),
                         span: input_var.identifier().span(),
                         declaration: VariableType::Input(input_var.mode()),
                     },
                 ) {
-                    self.handler.emit_err(err);
+                    Ok(()) => {
+                        self.type_table.borrow_mut().insert(&input_var.identifier(), type_);
+                    }
+                    Err(err) => self.handler.emit_err(err),
                 }
             });
 
@@ -251,6 +518,8 @@ impl<'a> ProgramVisitor<'a> for TypeChecker<'a> {
             finalize.output.iter().for_each(|output_type| {
                 // Check that the type of output is valid.
                 self.assert_type_is_valid(output_type.span(), &output_type.type_());
+                self.assert_not_array(output_type.span(), &output_type.type_());
+                self.assert_not_string(output_type.span(), &output_type.type_());
 
                 // Check that the mode of the output is valid.
                 if output_type.mode() == Mode::Const {
@@ -18,6 +18,9 @@ use crate::{TypeChecker, VariableSymbol, VariableType};
 
 use leo_ast::*;
 use leo_errors::TypeCheckerError;
+use leo_span::sym;
+
+use indexmap::IndexSet;
 
 impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
     fn visit_statement(&mut self, input: &'a Statement) {
@@ -28,19 +31,38 @@ impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
         }
 
         match input {
+            Statement::Assert(stmt) => self.visit_assert(stmt),
             Statement::Assign(stmt) => self.visit_assign(stmt),
             Statement::Block(stmt) => self.visit_block(stmt),
+            Statement::Cfg(stmt) => self.visit_cfg(stmt),
             Statement::Conditional(stmt) => self.visit_conditional(stmt),
-            Statement::Console(stmt) => self.visit_console(stmt),
             Statement::Decrement(stmt) => self.visit_decrement(stmt),
             Statement::Definition(stmt) => self.visit_definition(stmt),
+            Statement::Expression(stmt) => self.visit_expression_statement(stmt),
             Statement::Finalize(stmt) => self.visit_finalize(stmt),
             Statement::Increment(stmt) => self.visit_increment(stmt),
             Statement::Iteration(stmt) => self.visit_iteration(stmt),
+            Statement::Match(stmt) => self.visit_match(stmt),
             Statement::Return(stmt) => self.visit_return(stmt),
         }
     }
 
+    fn visit_assert(&mut self, input: &'a AssertStatement) {
+        match &input.variant {
+            AssertVariant::Assert(expr) => {
+                let type_ = self.visit_expression(expr, &Some(Type::Boolean));
+                self.assert_bool_type(&type_, expr.span());
+            }
+            AssertVariant::AssertEq(left, right) | AssertVariant::AssertNeq(left, right) => {
+                let t1 = self.visit_expression(left, &None);
+                let t2 = self.visit_expression(right, &None);
+
+                // Check that the types are equal.
+                self.check_eq_types(&t1, &t2, input.span());
+            }
+        }
+    }
+
     fn visit_assign(&mut self, input: &'a AssignStatement) {
         let var_name = match input.place {
             Expression::Identifier(id) => id,
@@ -66,7 +88,8 @@ impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
             None
         };
 
-        if var_type.is_some() {
+        if let Some(type_) = &var_type {
+            self.type_table.borrow_mut().insert(&var_name, type_.clone());
             self.visit_expression(&input.value, &var_type);
         }
     }
@@ -129,22 +152,6 @@ impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
         self.has_finalize = previous_has_finalize || (then_block_has_finalize && otherwise_block_has_finalize);
     }
 
-    fn visit_console(&mut self, input: &'a ConsoleStatement) {
-        match &input.function {
-            ConsoleFunction::Assert(expr) => {
-                let type_ = self.visit_expression(expr, &Some(Type::Boolean));
-                self.assert_bool_type(&type_, expr.span());
-            }
-            ConsoleFunction::AssertEq(left, right) | ConsoleFunction::AssertNeq(left, right) => {
-                let t1 = self.visit_expression(left, &None);
-                let t2 = self.visit_expression(right, &None);
-
-                // Check that the types are equal.
-                self.check_eq_types(&t1, &t2, input.span());
-            }
-        }
-    }
-
     fn visit_decrement(&mut self, input: &'a DecrementStatement) {
         if !self.is_finalize {
             self.emit_err(TypeCheckerError::increment_or_decrement_outside_finalize(input.span()));
@@ -186,20 +193,104 @@ impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
             VariableType::Mut
         };
 
-        // Check that the type of the definition is valid.
-        self.assert_type_is_valid(input.span, &input.type_);
+        // Check that the type of the definition is valid, if it is annotated.
+        let annotation = input.type_().map(|type_| self.resolve_sizes(&type_));
+        if let Some(type_) = &annotation {
+            self.assert_type_is_valid(input.span, type_);
+        }
 
-        self.visit_expression(&input.value, &Some(input.type_.clone()));
+        let value_type = self.visit_expression(&input.value, &annotation);
 
-        if let Err(err) = self.symbol_table.borrow_mut().insert_variable(
-            input.variable_name.name,
-            VariableSymbol {
-                type_: input.type_.clone(),
-                span: input.span(),
-                declaration,
+        // An unannotated definition takes the type of its value, as does the length of an annotated string.
+        // The inferred type is recorded in the definition, since later passes rely on the types of definitions.
+        let type_ = match (annotation, value_type) {
+            (Some(Type::String(None)), Some(type_ @ Type::String(Some(_)))) => {
+                *input.type_.borrow_mut() = Some(type_.clone());
+                type_
+            }
+            (Some(type_), _) => type_,
+            (None, Some(type_)) if !matches!(type_, Type::Unit | Type::Err) => {
+                *input.type_.borrow_mut() = Some(type_.clone());
+                type_
+            }
+            (None, value_type) => {
+                // Values whose types could not be determined have already been reported.
+                if matches!(value_type, Some(Type::Unit)) || (value_type.is_none() && !self.handler.had_errors()) {
+                    self.emit_err(TypeCheckerError::cannot_infer_definition_type(
+                        &input.place,
+                        input.value.span(),
+                    ));
+                }
+                Type::Err
+            }
+        };
+
+        // Strings are only allowed as the values of constants.
+        if declaration != VariableType::Const || !matches!(type_, Type::String(_)) {
+            self.assert_not_string(input.place.span(), &type_);
+        }
+
+        // Insert the variables into the symbol table.
+        let insert_variable =
+            |identifier: &Identifier, type_: &Type| match self.symbol_table.borrow_mut().insert_variable(
+                identifier.name,
+                VariableSymbol {
+                    type_: type_.clone(),
+                    span: input.span(),
+                    declaration: declaration.clone(),
+                },
+            ) {
+                Ok(()) => {
+                    self.type_table.borrow_mut().insert(identifier, type_.clone());
+                    self.declare_variable(*identifier)
+                }
+                Err(err) => self.handler.emit_err(err),
+            };
+        match &input.place {
+            Expression::Identifier(identifier) => {
+                insert_variable(identifier, &type_);
+
+                // Record the value of the constant if it is known at compile time, so that it can be used in loop bounds.
+                if declaration == VariableType::Const {
+                    if let Some(value) = self.evaluate_constant(&input.value) {
+                        self.symbol_table.borrow_mut().insert_constant(identifier.name, value);
+                    }
+                }
+            }
+            // For a destructuring definition, each variable is given the type of the corresponding tuple element.
+            Expression::Tuple(tuple) => match &type_ {
+                Type::Tuple(tuple_type) if tuple_type.len() == tuple.elements.len() => {
+                    for (element, type_) in tuple.elements.iter().zip(tuple_type.iter()) {
+                        match element {
+                            Expression::Identifier(identifier) => insert_variable(identifier, type_),
+                            _ => unreachable!("The parser guarantees that destructured variables are identifiers."),
+                        }
+                    }
+                }
+                Type::Tuple(tuple_type) => self.emit_err(TypeCheckerError::incorrect_tuple_length(
+                    tuple_type.len(),
+                    tuple.elements.len(),
+                    input.place.span(),
+                )),
+                Type::Err => {}
+                type_ => self.emit_err(TypeCheckerError::type_should_be(type_, "tuple", input.place.span())),
             },
-        ) {
-            self.handler.emit_err(err);
+            _ => unreachable!(
+                "The parser guarantees that the left-hand side of a definition is an identifier or a tuple."
+            ),
+        }
+    }
+
+    fn visit_expression_statement(&mut self, input: &'a ExpressionStatement) {
+        match &input.expression {
+            // `Mapping::set` may only be used as a statement.
+            Expression::Access(AccessExpression::AssociatedFunction(access)) if matches!(access.ty, Type::Identifier(identifier) if identifier.name == sym::Mapping) =>
+            {
+                self.check_mapping_operation(access, &None, true);
+            }
+            expression => {
+                self.visit_expression(expression, &None);
+            }
         }
     }
 
@@ -280,22 +371,30 @@ impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
     }
 
     fn visit_iteration(&mut self, input: &'a IterationStatement) {
-        let iter_type = &Some(input.type_.clone());
-        self.assert_int_type(iter_type, input.variable.span);
+        let type_ = self.resolve_sizes(&input.type_);
+        let iter_type = &Some(type_.clone());
+        // The variable of a loop over a range is an integer, and the variable of a loop over an array is an element of the array.
+        if input.stop.is_some() {
+            self.assert_int_type(iter_type, input.variable.span);
+        }
 
         // Create a new scope for the loop body.
         let scope_index = self.create_child_scope();
 
         // Add the loop variable to the scope of the loop body.
-        if let Err(err) = self.symbol_table.borrow_mut().insert_variable(
+        match self.symbol_table.borrow_mut().insert_variable(
             input.variable.name,
             VariableSymbol {
-                type_: input.type_.clone(),
+                type_: type_.clone(),
                 span: input.span(),
                 declaration: VariableType::Const,
             },
         ) {
-            self.handler.emit_err(err);
+            Ok(()) => {
+                self.type_table.borrow_mut().insert(&input.variable, type_.clone());
+                self.declare_variable(input.variable)
+            }
+            Err(err) => self.handler.emit_err(err),
         }
 
         let prior_has_return = core::mem::take(&mut self.has_return);
@@ -317,19 +416,116 @@ impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
         // Exit the scope.
         self.exit_scope(scope_index);
 
-        self.visit_expression(&input.start, iter_type);
-
-        // If `input.start` is a literal, instantiate it as a value.
-        if let Expression::Literal(literal) = &input.start {
-            input.start_value.replace(Some(Value::from(literal)));
+        match &input.stop {
+            Some(stop) => {
+                self.visit_expression(&input.start, iter_type);
+
+                // If `input.start` is a constant expression, instantiate it as a value.
+                input.start_value.replace(self.evaluate_constant(&input.start));
+
+                self.visit_expression(stop, iter_type);
+
+                // If `input.stop` is a constant expression, instantiate it as a value.
+                input.stop_value.replace(self.evaluate_constant(stop));
+
+                // If both bounds are known, check that the range counts upwards.
+                // Note that the bounds are only compared if they are integers of the same type, since other types have already been reported.
+                if let (Some(start_value), Some(stop_value)) =
+                    (input.start_value.borrow().clone(), input.stop_value.borrow().clone())
+                {
+                    let span = input.start.span() + stop.span();
+                    let is_comparable = matches!(
+                        (Type::from(&start_value), Type::from(&stop_value)),
+                        (Type::Integer(left), Type::Integer(right)) if left == right
+                    );
+                    if is_comparable && matches!(start_value.gt(stop_value, span), Ok(Value::Boolean(true, _))) {
+                        self.emit_err(TypeCheckerError::loop_range_reversed(&input.start, stop, span));
+                    }
+                }
+            }
+            None => self.check_iterated_array(&input.start, &type_),
         }
+    }
+
+    fn visit_match(&mut self, input: &'a MatchStatement) {
+        // Check that the matched value is an enum.
+        let enum_ = match self.visit_expression(&input.expression, &None) {
+            Some(Type::Identifier(identifier)) if self.symbol_table.borrow().lookup_enum(identifier.name).is_some() => {
+                self.symbol_table.borrow().lookup_enum(identifier.name).cloned()
+            }
+            Some(type_) => {
+                self.emit_err(TypeCheckerError::match_on_non_enum(type_, input.expression.span()));
+                None
+            }
+            None => None,
+        };
+
+        // The variants matched by the arms visited so far, and whether they match every value.
+        let mut covered = IndexSet::new();
+        let mut exhaustive = false;
+
+        let previous_has_return = core::mem::take(&mut self.has_return);
+        let previous_has_finalize = core::mem::take(&mut self.has_finalize);
+        let mut arms_have_return = true;
+        let mut arms_have_finalize = true;
+
+        for arm in input.arms.iter() {
+            // An arm is unreachable if the arms before it match every value, or if it matches a variant that they match.
+            let reachable = match (&arm.pattern, &enum_) {
+                (MatchPattern::Variant(name, _), Some(enum_)) if name.name != enum_.name() => {
+                    self.emit_err(TypeCheckerError::match_pattern_mismatch(
+                        enum_.identifier,
+                        name,
+                        arm.pattern.span(),
+                    ));
+                    true
+                }
+                (MatchPattern::Variant(name, variant), Some(enum_)) => match enum_.variant_index(variant.name) {
+                    Some(index) => {
+                        let reachable = covered.insert(index) && !exhaustive;
+                        exhaustive |= covered.len() == enum_.variants.len();
+                        reachable
+                    }
+                    None => {
+                        self.emit_err(TypeCheckerError::unknown_variant(name, variant, variant.span));
+                        true
+                    }
+                },
+                (MatchPattern::Wildcard(_), _) => !core::mem::replace(&mut exhaustive, true),
+                (_, None) => true,
+            };
+            if !reachable {
+                self.emit_err(TypeCheckerError::unreachable_match_arm(arm.pattern.span()));
+            }
 
-        self.visit_expression(&input.stop, iter_type);
+            self.has_return = false;
+            self.has_finalize = false;
 
-        // If `input.stop` is a literal, instantiate it as a value.
-        if let Expression::Literal(literal) = &input.stop {
-            input.stop_value.replace(Some(Value::from(literal)));
+            self.visit_block(&arm.block);
+
+            arms_have_return &= self.has_return;
+            arms_have_finalize &= self.has_finalize;
+        }
+
+        // Check that the arms match every variant of the enum.
+        if let Some(enum_) = &enum_ {
+            if !exhaustive {
+                let missing = enum_
+                    .variants
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| !covered.contains(index))
+                    .map(|(_, variant)| format!("`{}::{}`", enum_.identifier, variant))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                self.emit_err(TypeCheckerError::non_exhaustive_match(missing, input.span()));
+            }
         }
+
+        // The statement returns or finalizes if every arm does.
+        let has_arms = !input.arms.is_empty();
+        self.has_return = previous_has_return || (has_arms && arms_have_return);
+        self.has_finalize = previous_has_finalize || (has_arms && arms_have_finalize);
     }
 
     fn visit_return(&mut self, input: &'a ReturnStatement) {
@@ -347,6 +543,21 @@ impl<'a> StatementVisitor<'a> for TypeChecker<'a> {
                 false => f.output_type.clone(),
             });
 
+        // A return may appear in a conditional branch, in which case flattening folds it into the final return.
+        // If the function has a finalize block, then every path that returns must have already called `finalize`.
+        if !self.is_finalize
+            && !self.has_finalize
+            && self
+                .symbol_table
+                .borrow()
+                .lookup_fn_symbol(parent)
+                .map_or(false, |f| f.finalize.is_some())
+        {
+            self.emit_err(TypeCheckerError::missing_finalize(input.span()));
+            // Mark the path as finalized to avoid reporting the same error for the whole function.
+            self.has_finalize = true;
+        }
+
         self.has_return = true;
 
         self.visit_expression(&input.expression, return_type);
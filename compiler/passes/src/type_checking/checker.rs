@@ -14,15 +14,19 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::SymbolTable;
+use crate::{CallGraph, ConstEvaluator, FunctionSymbol, ScopeIndex, StructGraph, SymbolTable, TypeTable};
 
-use leo_ast::{Identifier, IntegerType, Node, Type};
+use leo_ast::{
+    AssociatedFunction, CallExpression, Expression, ExpressionVisitor, Identifier, IntegerType, Literal, Node, Size,
+    Struct, Type, Value, WideInteger,
+};
 use leo_core::*;
-use leo_errors::{emitter::Handler, TypeCheckerError};
-use leo_span::{Span, Symbol};
+use leo_errors::{emitter::Handler, Result, TypeCheckerError, TypeCheckerWarning};
+use leo_span::{sym, Span, Symbol};
 
+use indexmap::{IndexMap, IndexSet};
 use itertools::Itertools;
-use std::cell::RefCell;
+use std::{cell::RefCell, fmt::Display, iter, num::NonZeroUsize};
 
 pub struct TypeChecker<'a> {
     /// The symbol table for the program.
@@ -39,6 +43,30 @@ pub struct TypeChecker<'a> {
     pub(crate) is_transition_function: bool,
     /// Whether or not we are currently traversing a finalize block.
     pub(crate) is_finalize: bool,
+    /// The type of unsuffixed integer literals whose type cannot be inferred from their context.
+    pub(crate) default_integer_type: Option<IntegerType>,
+    /// The call graph of the program, with an edge from each function to every local function that it calls.
+    pub(crate) call_graph: CallGraph,
+    /// The span of the first call along each edge of the call graph.
+    pub(crate) call_sites: IndexMap<(Symbol, Symbol), Span>,
+    /// The strongly connected components of the call graph that contain a cycle.
+    /// The functions in these components are recursive, so they cannot be inlined.
+    pub(crate) recursive_components: Vec<IndexSet<Symbol>>,
+    /// The struct graph of the program, with an edge from each struct or record to every struct that it has a member of.
+    pub(crate) struct_graph: StructGraph,
+    /// The first member along each edge of the struct graph.
+    pub(crate) struct_members: IndexMap<(Symbol, Symbol), Identifier>,
+    /// The variables declared in the scopes that we are currently traversing that have not been read, by name.
+    /// A warning is emitted for each of them when its scope is exited.
+    pub(crate) unused_variables: RefCell<IndexMap<Symbol, Identifier>>,
+    /// The type parameters of the generic function that we are currently traversing, which stand for integer types.
+    pub(crate) type_parameters: IndexSet<Symbol>,
+    /// The types of the expressions and variables checked so far, keyed by the IDs of their nodes.
+    pub(crate) type_table: RefCell<TypeTable>,
+    /// The values of the program constants that are positive integers, which may be used as sizes, by name.
+    pub(crate) sizes: IndexMap<Symbol, NonZeroUsize>,
+    /// The const parameters of the generic struct that we are currently traversing, which may be used as sizes.
+    pub(crate) const_parameters: IndexSet<Symbol>,
 }
 
 const BOOLEAN_TYPE: Type = Type::Boolean;
@@ -85,8 +113,8 @@ const MAGNITUDE_TYPES: [Type; 3] = [
 ];
 
 impl<'a> TypeChecker<'a> {
-    /// Returns a new type checker given a symbol table and error handler.
-    pub fn new(symbol_table: SymbolTable, handler: &'a Handler) -> Self {
+    /// Returns a new type checker given a symbol table, error handler, and default integer type.
+    pub fn new(symbol_table: SymbolTable, handler: &'a Handler, default_integer_type: Option<IntegerType>) -> Self {
         Self {
             is_transition_function: false,
             symbol_table: RefCell::new(symbol_table),
@@ -95,19 +123,27 @@ impl<'a> TypeChecker<'a> {
             has_return: false,
             has_finalize: false,
             is_finalize: false,
+            default_integer_type,
+            call_graph: CallGraph::default(),
+            call_sites: IndexMap::new(),
+            recursive_components: Vec::new(),
+            struct_graph: StructGraph::default(),
+            struct_members: IndexMap::new(),
+            unused_variables: RefCell::new(IndexMap::new()),
+            type_parameters: IndexSet::new(),
+            type_table: RefCell::new(TypeTable::default()),
+            sizes: IndexMap::new(),
+            const_parameters: IndexSet::new(),
         }
     }
 
     /// Enters a child scope.
-    pub(crate) fn enter_scope(&mut self, index: usize) {
-        let previous_symbol_table = std::mem::take(&mut self.symbol_table);
-        self.symbol_table
-            .swap(previous_symbol_table.borrow().lookup_scope_by_index(index).unwrap());
-        self.symbol_table.borrow_mut().parent = Some(Box::new(previous_symbol_table.into_inner()));
+    pub(crate) fn enter_scope(&mut self, index: ScopeIndex) {
+        self.symbol_table.borrow_mut().enter_scope(index);
     }
 
     /// Creates a new child scope.
-    pub(crate) fn create_child_scope(&mut self) -> usize {
+    pub(crate) fn create_child_scope(&mut self) -> ScopeIndex {
         // Creates a new child scope.
         let scope_index = self.symbol_table.borrow_mut().insert_block();
         // Enter the new scope.
@@ -116,12 +152,29 @@ impl<'a> TypeChecker<'a> {
         scope_index
     }
 
-    /// Exits the current scope.
-    pub(crate) fn exit_scope(&mut self, index: usize) {
-        let previous_symbol_table = *self.symbol_table.borrow_mut().parent.take().unwrap();
-        self.symbol_table
-            .swap(previous_symbol_table.lookup_scope_by_index(index).unwrap());
-        self.symbol_table = RefCell::new(previous_symbol_table);
+    /// Exits the current scope, which is the scope at `index`.
+    pub(crate) fn exit_scope(&mut self, index: ScopeIndex) {
+        debug_assert_eq!(self.symbol_table.borrow().current, index);
+
+        // Warn about the variables declared in the scope that were never read.
+        let unused: Vec<Identifier> = self
+            .symbol_table
+            .borrow()
+            .current_scope()
+            .variables
+            .keys()
+            .filter_map(|name| self.unused_variables.borrow_mut().shift_remove(name))
+            .collect();
+        for identifier in unused {
+            self.emit_warning(TypeCheckerWarning::unused_variable(identifier, identifier.span));
+        }
+
+        self.symbol_table.borrow_mut().exit_scope();
+    }
+
+    /// Records that the variable `identifier` is declared in the current scope, so that a warning is emitted if it is never read.
+    pub(crate) fn declare_variable(&self, identifier: Identifier) {
+        self.unused_variables.borrow_mut().insert(identifier.name, identifier);
     }
 
     /// Emits a type checker error.
@@ -129,11 +182,134 @@ impl<'a> TypeChecker<'a> {
         self.handler.emit_err(err);
     }
 
+    /// Emits a type checker warning.
+    pub(crate) fn emit_warning(&self, warning: TypeCheckerWarning) {
+        self.handler.emit_warning(warning.into());
+    }
+
+    /// Emits an error for each strongly connected component of the call graph that contains a cycle, and records the component.
+    /// The error lists a cycle through the component, starting from its first function, along with the span of each call in the cycle.
+    pub(crate) fn check_for_recursion(&mut self) {
+        for component in self.call_graph.strongly_connected_components() {
+            let first = component[0];
+            if component.len() == 1 && !self.call_graph.contains_edge(first, first) {
+                continue;
+            }
+
+            // Every path from a successor of `first` back to `first` stays within the component.
+            let successor = *component
+                .iter()
+                .find(|node| self.call_graph.contains_edge(first, **node))
+                .expect("Every function in a cycle calls a function in the same cycle.");
+            let cycle = iter::once(first)
+                .chain(self.call_graph.path(successor, first).unwrap_or_default())
+                .collect::<Vec<_>>();
+            let calls = cycle
+                .iter()
+                .tuple_windows()
+                .map(|(caller, callee)| (*caller, *callee, self.call_sites[&(*caller, *callee)]))
+                .collect::<Vec<_>>();
+
+            self.emit_err(TypeCheckerError::cyclic_function_dependency(
+                cycle.iter().map(|name| format!("`{name}`")).join(" --> "),
+                calls
+                    .iter()
+                    .map(|(caller, callee, span)| format!("`{caller}` calls `{callee}` at {span}"))
+                    .join(", "),
+                calls[0].2,
+            ));
+            self.recursive_components.push(component);
+        }
+    }
+
+    /// Emits an error for each strongly connected component of the struct graph that contains a cycle.
+    /// The error lists a cycle through the component, starting from its first struct, along with the span of each member in the cycle.
+    pub(crate) fn check_for_struct_cycles(&self) {
+        for component in self.struct_graph.strongly_connected_components() {
+            let first = component[0];
+            if component.len() == 1 && !self.struct_graph.contains_edge(first, first) {
+                continue;
+            }
+
+            // Every path from a successor of `first` back to `first` stays within the component.
+            let successor = *component
+                .iter()
+                .find(|node| self.struct_graph.contains_edge(first, **node))
+                .expect("Every struct in a cycle has a member of a struct in the same cycle.");
+            let cycle = iter::once(first)
+                .chain(self.struct_graph.path(successor, first).unwrap_or_default())
+                .collect::<Vec<_>>();
+            let members = cycle
+                .iter()
+                .tuple_windows()
+                .map(|(struct_, member_struct)| (*struct_, self.struct_members[&(*struct_, *member_struct)]))
+                .collect::<Vec<_>>();
+
+            self.emit_err(TypeCheckerError::cyclic_struct_dependency(
+                cycle.iter().map(|name| format!("`{name}`")).join(" --> "),
+                members
+                    .iter()
+                    .map(|(struct_, member)| format!("`{struct_}.{member}` at {}", member.span))
+                    .join(", "),
+                members[0].1.span,
+            ));
+        }
+    }
+
+    /// Renders a type for diagnostics, summarizing the members of struct and record types.
+    pub(crate) fn display_type(&self, type_: &Type) -> String {
+        match self.symbol_table.try_borrow() {
+            Ok(symbol_table) => type_.display_friendly(&|name| symbol_table.lookup_struct(name)),
+            // The symbol table is only mutably borrowed while entering or exiting scopes.
+            Err(_) => type_.to_string(),
+        }
+    }
+
+    /// Returns the type with each size that names a program constant replaced by the value of the constant.
+    /// Declared types are resolved as they are checked, so that their sizes can be compared.
+    pub(crate) fn resolve_sizes(&self, type_: &Type) -> Type {
+        type_.substitute_sizes(&self.sizes)
+    }
+
+    /// Evaluates an expression whose value may be known at compile time, e.g. a loop bound.
+    /// Expressions are only evaluated if no errors have been emitted, since the evaluator assumes that they are well-typed.
+    pub(crate) fn evaluate_constant(&self, expression: &Expression) -> Option<Value> {
+        if self.handler.had_errors() {
+            return None;
+        }
+        match ConstEvaluator::new(&self.symbol_table.borrow()).evaluate(expression) {
+            Ok(value) => value,
+            Err(err) => {
+                self.handler.emit_err(err);
+                None
+            }
+        }
+    }
+
+    /// Emits an error if a checked integer operation over operands known at compile time overflows.
+    /// Overflows within the operands were reported when they were visited, so they are not reported again.
+    pub(crate) fn check_integer_overflow(
+        &self,
+        operation: impl Display,
+        span: Span,
+        evaluation: Result<Option<(IntegerType, Option<WideInteger>)>>,
+    ) {
+        if let Ok(Some((integer_type, result))) = evaluation {
+            if !matches!(result, Some(result) if result.fits(integer_type)) {
+                self.emit_err(ConstEvaluator::overflow_error(operation, span, integer_type, result));
+            }
+        }
+    }
+
     /// Emits an error to the handler if the given type is invalid.
     fn check_type(&self, is_valid: impl Fn(&Type) -> bool, error_string: String, type_: &Option<Type>, span: Span) {
         if let Some(type_) = type_ {
             if !is_valid(type_) {
-                self.emit_err(TypeCheckerError::expected_one_type_of(error_string, type_, span));
+                self.emit_err(TypeCheckerError::expected_one_type_of(
+                    error_string,
+                    self.display_type(type_),
+                    span,
+                ));
             }
         }
     }
@@ -141,13 +317,46 @@ impl<'a> TypeChecker<'a> {
     /// Emits an error if the two given types are not equal.
     pub(crate) fn check_eq_types(&self, t1: &Option<Type>, t2: &Option<Type>, span: Span) {
         match (t1, t2) {
-            (Some(t1), Some(t2)) if !Type::eq_flat(t1, t2) => {
-                self.emit_err(TypeCheckerError::type_should_be(t1, t2, span))
+            (Some(t1), Some(t2)) if !Type::eq_flat(t1, t2) => self.emit_err(TypeCheckerError::type_should_be(
+                self.display_type(t1),
+                self.display_type(t2),
+                span,
+            )),
+            (Some(type_), None) | (None, Some(type_)) => self.emit_err(TypeCheckerError::type_should_be(
+                "no type",
+                self.display_type(type_),
+                span,
+            )),
+            _ => {}
+        }
+    }
+
+    /// Type checks the operands of a binary expression that do not constrain each other's type.
+    /// If exactly one operand is an unsuffixed integer literal, the other operand is visited first so that its integer type can be inferred for the literal.
+    pub(crate) fn visit_operands(
+        &mut self,
+        left: &'a Expression,
+        right: &'a Expression,
+    ) -> (Option<Type>, Option<Type>) {
+        let is_unsuffixed =
+            |expression: &Expression| matches!(expression, Expression::Literal(Literal::Unsuffixed(..)));
+        let integer_type = |type_: &Option<Type>| match type_ {
+            Some(Type::Integer(integer_type)) => Some(Type::Integer(*integer_type)),
+            _ => None,
+        };
+
+        match (is_unsuffixed(left), is_unsuffixed(right)) {
+            (true, false) => {
+                let t2 = self.visit_expression(right, &None);
+                let t1 = self.visit_expression(left, &integer_type(&t2));
+                (t1, t2)
             }
-            (Some(type_), None) | (None, Some(type_)) => {
-                self.emit_err(TypeCheckerError::type_should_be("no type", type_, span))
+            (false, true) => {
+                let t1 = self.visit_expression(left, &None);
+                let t2 = self.visit_expression(right, &integer_type(&t1));
+                (t1, t2)
             }
-            _ => {}
+            _ => (self.visit_expression(left, &None), self.visit_expression(right, &None)),
         }
     }
 
@@ -156,7 +365,11 @@ impl<'a> TypeChecker<'a> {
     pub(crate) fn assert_and_return_type(&self, actual: Type, expected: &Option<Type>, span: Span) -> Type {
         if let Some(expected) = expected {
             if !actual.eq_flat(expected) {
-                self.emit_err(TypeCheckerError::type_should_be(actual.clone(), expected, span));
+                self.emit_err(TypeCheckerError::type_should_be(
+                    self.display_type(&actual),
+                    self.display_type(expected),
+                    span,
+                ));
             }
         }
 
@@ -167,7 +380,7 @@ impl<'a> TypeChecker<'a> {
     pub(crate) fn assert_type(&self, actual: &Option<Type>, expected: &Type, span: Span) {
         self.check_type(
             |actual: &Type| actual.eq_flat(expected),
-            expected.to_string(),
+            self.display_type(expected),
             actual,
             span,
         )
@@ -203,10 +416,16 @@ impl<'a> TypeChecker<'a> {
         )
     }
 
+    /// Returns `true` if the given type is an integer, or a type parameter that stands for one.
+    pub(crate) fn is_int_type(&self, type_: &Type) -> bool {
+        INT_TYPES.contains(type_)
+            || matches!(type_, Type::Identifier(identifier) if self.type_parameters.contains(&identifier.name))
+    }
+
     /// Emits an error to the handler if the given type is not an integer.
     pub(crate) fn assert_int_type(&self, type_: &Option<Type>, span: Span) {
         self.check_type(
-            |type_: &Type| INT_TYPES.contains(type_),
+            |type_: &Type| self.is_int_type(type_),
             types_to_string(&INT_TYPES),
             type_,
             span,
@@ -246,7 +465,7 @@ impl<'a> TypeChecker<'a> {
     /// Emits an error to the handler if the given type is not a boolean or an integer.
     pub(crate) fn assert_bool_int_type(&self, type_: &Option<Type>, span: Span) {
         self.check_type(
-            |type_: &Type| BOOLEAN_TYPE.eq(type_) | INT_TYPES.contains(type_),
+            |type_: &Type| BOOLEAN_TYPE.eq(type_) | self.is_int_type(type_),
             format!("{}, {}", BOOLEAN_TYPE, types_to_string(&INT_TYPES)),
             type_,
             span,
@@ -256,7 +475,7 @@ impl<'a> TypeChecker<'a> {
     /// Emits an error to the handler if the given type is not a field or integer.
     pub(crate) fn assert_field_int_type(&self, type_: &Option<Type>, span: Span) {
         self.check_type(
-            |type_: &Type| FIELD_TYPE.eq(type_) | INT_TYPES.contains(type_),
+            |type_: &Type| FIELD_TYPE.eq(type_) | self.is_int_type(type_),
             format!("{}, {}", FIELD_TYPE, types_to_string(&INT_TYPES)),
             type_,
             span,
@@ -276,7 +495,7 @@ impl<'a> TypeChecker<'a> {
     /// Emits an error to the handler if the given type is not a field, group, or integer.
     pub(crate) fn assert_field_group_int_type(&self, type_: &Option<Type>, span: Span) {
         self.check_type(
-            |type_: &Type| FIELD_TYPE.eq(type_) | GROUP_TYPE.eq(type_) | INT_TYPES.contains(type_),
+            |type_: &Type| FIELD_TYPE.eq(type_) | GROUP_TYPE.eq(type_) | self.is_int_type(type_),
             format!("{}, {}, {}", FIELD_TYPE, GROUP_TYPE, types_to_string(&INT_TYPES),),
             type_,
             span,
@@ -296,7 +515,7 @@ impl<'a> TypeChecker<'a> {
     /// Emits an error to the handler if the given type is not a field, scalar, or integer.
     pub(crate) fn assert_field_scalar_int_type(&self, type_: &Option<Type>, span: Span) {
         self.check_type(
-            |type_: &Type| FIELD_TYPE.eq(type_) | SCALAR_TYPE.eq(type_) | INT_TYPES.contains(type_),
+            |type_: &Type| FIELD_TYPE.eq(type_) | SCALAR_TYPE.eq(type_) | self.is_int_type(type_),
             format!("{}, {}, {}", FIELD_TYPE, SCALAR_TYPE, types_to_string(&INT_TYPES),),
             type_,
             span,
@@ -307,7 +526,7 @@ impl<'a> TypeChecker<'a> {
     pub(crate) fn assert_field_group_scalar_int_type(&self, type_: &Option<Type>, span: Span) {
         self.check_type(
             |type_: &Type| {
-                FIELD_TYPE.eq(type_) | GROUP_TYPE.eq(type_) | SCALAR_TYPE.eq(type_) | INT_TYPES.contains(type_)
+                FIELD_TYPE.eq(type_) | GROUP_TYPE.eq(type_) | SCALAR_TYPE.eq(type_) | self.is_int_type(type_)
             },
             format!(
                 "{}, {}, {}, {}",
@@ -341,15 +560,145 @@ impl<'a> TypeChecker<'a> {
         None
     }
 
+    /// Type checks a mapping operation, `Mapping::get`, `Mapping::get_or_use`, or `Mapping::set`, returning the type of its output.
+    /// `is_statement` indicates whether the operation is evaluated as an expression statement.
+    pub(crate) fn check_mapping_operation(
+        &mut self,
+        access: &'a AssociatedFunction,
+        expected: &Option<Type>,
+        is_statement: bool,
+    ) -> Option<Type> {
+        let operation = access.name.name;
+        let num_args = match operation {
+            sym::get => 2,
+            sym::get_or_use | sym::set => 3,
+            _ => {
+                self.emit_err(TypeCheckerError::invalid_core_function(
+                    sym::Mapping,
+                    operation,
+                    access.name.span(),
+                ));
+                return None;
+            }
+        };
+
+        // Mappings can only be accessed on-chain.
+        if !self.is_finalize {
+            self.emit_err(TypeCheckerError::mapping_operation_outside_finalize(
+                operation,
+                access.span(),
+            ));
+        }
+
+        if access.args.len() != num_args {
+            self.emit_err(TypeCheckerError::incorrect_num_args_to_call(
+                num_args,
+                access.args.len(),
+                access.span(),
+            ));
+            return None;
+        }
+
+        // Check that the first argument is a mapping.
+        let mapping_type = match (&access.args[0], self.visit_expression(&access.args[0], &None)) {
+            (Expression::Identifier(_), Some(Type::Mapping(mapping_type))) => mapping_type,
+            (mapping, Some(type_)) => {
+                self.emit_err(TypeCheckerError::expected_one_type_of("mapping", type_, mapping.span()));
+                return None;
+            }
+            (_, None) => return None,
+        };
+
+        // Check that the key matches the key type of the mapping.
+        self.visit_expression(&access.args[1], &Some(*mapping_type.key));
+
+        // Check that the default or new value matches the value type of the mapping.
+        if let Some(value) = access.args.get(2) {
+            self.visit_expression(value, &Some(*mapping_type.value.clone()));
+        }
+
+        match operation {
+            sym::set => {
+                if !is_statement {
+                    self.emit_err(TypeCheckerError::mapping_set_used_as_value(access.span()));
+                }
+                Some(Type::Unit)
+            }
+            _ => Some(self.assert_and_return_type(*mapping_type.value, expected, access.span())),
+        }
+    }
+
     /// Returns the `struct` type and emits an error if the `expected` type does not match.
-    pub(crate) fn check_expected_struct(&mut self, struct_: Identifier, expected: &Option<Type>, span: Span) -> Type {
-        if let Some(Type::Identifier(expected)) = expected {
-            if !struct_.matches(expected) {
-                self.emit_err(TypeCheckerError::type_should_be(struct_.name, expected.name, span));
+    pub(crate) fn check_expected_struct(&mut self, struct_: Type, expected: &Option<Type>, span: Span) -> Type {
+        if let Some(expected @ (Type::Identifier(_) | Type::StructInstance(_))) = expected {
+            if !struct_.eq_flat(expected) {
+                self.emit_err(TypeCheckerError::type_should_be(&struct_, expected, span));
             }
         }
 
-        Type::Identifier(struct_)
+        struct_
+    }
+
+    /// Returns the definition of a struct type.
+    /// The member types of an instance of a generic struct are instantiated with the const arguments of the instance.
+    pub(crate) fn lookup_struct_type(&self, type_: &Type) -> Option<Struct> {
+        let (identifier, const_arguments) = match type_ {
+            Type::Identifier(identifier) => (identifier, [].as_slice()),
+            Type::StructInstance(instance) => (&instance.identifier, instance.const_arguments.as_slice()),
+            _ => return None,
+        };
+        let struct_ = self.symbol_table.borrow().lookup_struct(identifier.name)?.clone();
+        Some(Struct {
+            members: struct_.instantiate_members(const_arguments),
+            ..struct_
+        })
+    }
+
+    /// Checks that `array`, whose elements a loop iterates over, is an array of elements of type `element_type`.
+    pub(crate) fn check_iterated_array(&mut self, array: &'a Expression, element_type: &Type) {
+        // The length of an array expression is known, so unsuffixed elements can be inferred to be of the element type.
+        let expected = match array {
+            Expression::Array(array) => NonZeroUsize::new(array.elements.len())
+                .map(|length| Type::Array(Box::new(element_type.clone()), Size::Literal(length))),
+            _ => None,
+        };
+
+        match self.visit_expression(array, &expected) {
+            // Note that the elements of an array expression have already been checked against the expected type.
+            Some(Type::Array(actual, _)) if expected.is_none() => {
+                self.assert_type(&Some(*actual), element_type, array.span())
+            }
+            Some(Type::Array(..)) => {}
+            Some(type_) => self.emit_err(TypeCheckerError::type_should_be(type_, "array", array.span())),
+            None => self.emit_err(TypeCheckerError::could_not_determine_type(array, array.span())),
+        }
+    }
+
+    /// Emits an error if a value of type `from` cannot be cast to type `to`.
+    /// Casts are only defined between the literal types that the `cast` instruction converts between.
+    pub(crate) fn assert_castable(&self, from: &Type, to: &Type, span: Span) {
+        let is_castable = match (from, to) {
+            (Type::Err, _) | (_, Type::Err) => true,
+            (Type::Address | Type::Boolean | Type::Field | Type::Group | Type::Integer(_) | Type::Scalar, _)
+                if from == to =>
+            {
+                true
+            }
+            (Type::Integer(_), Type::Integer(_) | Type::Field | Type::Scalar)
+            | (Type::Boolean, Type::Integer(_) | Type::Field)
+            | (Type::Field, Type::Integer(_) | Type::Group | Type::Scalar | Type::Address)
+            | (Type::Scalar, Type::Integer(_) | Type::Field)
+            | (Type::Group, Type::Field | Type::Address)
+            | (Type::Address, Type::Field | Type::Group) => true,
+            _ => false,
+        };
+        if !is_castable {
+            self.emit_err(TypeCheckerError::invalid_cast(
+                self.display_type(from),
+                self.display_type(to),
+                span,
+            ));
+        }
     }
 
     /// Emits an error if the type is a tuple.
@@ -359,6 +708,52 @@ impl<'a> TypeChecker<'a> {
         }
     }
 
+    /// Emits an error if the type is or contains an array.
+    pub(crate) fn assert_not_array(&self, span: Span, type_: &Type) {
+        match type_ {
+            Type::Array(..) => self.emit_err(TypeCheckerError::array_not_allowed(span)),
+            Type::Tuple(tuple_type) => {
+                for type_ in tuple_type.iter() {
+                    self.assert_not_array(span, type_)
+                }
+            }
+            _ => {} // Do nothing.
+        }
+    }
+
+    /// Emits an error if the type is a record, which only transitions can take or return.
+    pub(crate) fn assert_not_record(&self, span: Span, type_: &Type) {
+        if let Type::Identifier(identifier) = type_ {
+            if self
+                .symbol_table
+                .borrow()
+                .lookup_struct(identifier.name)
+                .map_or(false, |struct_| struct_.is_record)
+            {
+                self.emit_err(TypeCheckerError::record_outside_transition(identifier.name, span))
+            }
+        }
+    }
+
+    /// Emits an error if the type is or contains a string.
+    /// Strings are only allowed as the values of constants, so they cannot be stored, passed or returned.
+    pub(crate) fn assert_not_string(&self, span: Span, type_: &Type) {
+        match type_ {
+            Type::String(_) => self.emit_err(TypeCheckerError::string_not_constant(span)),
+            Type::Array(element_type, _) => self.assert_not_string(span, element_type),
+            Type::Tuple(tuple_type) => {
+                for type_ in tuple_type.iter() {
+                    self.assert_not_string(span, type_)
+                }
+            }
+            Type::Mapping(mapping_type) => {
+                self.assert_not_string(span, &mapping_type.key);
+                self.assert_not_string(span, &mapping_type.value);
+            }
+            _ => {} // Do nothing.
+        }
+    }
+
     /// Emits an error if the struct member is a record type.
     pub(crate) fn assert_member_is_not_record(&self, span: Span, parent: Symbol, type_: &Type) {
         match type_ {
@@ -387,9 +782,57 @@ impl<'a> TypeChecker<'a> {
     /// Emits an error if the type is not valid.
     pub(crate) fn assert_type_is_valid(&self, span: Span, type_: &Type) {
         match type_ {
-            // Check that the named composite type has been defined.
-            Type::Identifier(identifier) if self.symbol_table.borrow().lookup_struct(identifier.name).is_none() => {
-                self.emit_err(TypeCheckerError::undefined_type(identifier.name, span));
+            // Check that the named struct or enum type has been defined, unless it is a type parameter.
+            // A generic struct must be given const arguments.
+            Type::Identifier(identifier) if !self.type_parameters.contains(&identifier.name) => {
+                match self.symbol_table.borrow().lookup_struct(identifier.name) {
+                    Some(struct_) if struct_.is_generic() => {
+                        self.emit_err(TypeCheckerError::incorrect_num_const_arguments(
+                            identifier.name,
+                            struct_.const_parameters.len(),
+                            0,
+                            span,
+                        ));
+                    }
+                    Some(_) => {}
+                    None if self.symbol_table.borrow().lookup_enum(identifier.name).is_some() => {}
+                    None => self.emit_err(TypeCheckerError::undefined_type(identifier.name, span)),
+                }
+            }
+            // Check that the struct is generic, and that each const argument is a valid size of the type of its parameter.
+            Type::StructInstance(instance) => {
+                match self.symbol_table.borrow().lookup_struct(instance.identifier.name) {
+                    Some(struct_) if !struct_.is_generic() => {
+                        self.emit_err(TypeCheckerError::struct_not_generic(instance.identifier.name, span));
+                    }
+                    Some(struct_) if struct_.const_parameters.len() != instance.const_arguments.len() => {
+                        self.emit_err(TypeCheckerError::incorrect_num_const_arguments(
+                            instance.identifier.name,
+                            struct_.const_parameters.len(),
+                            instance.const_arguments.len(),
+                            span,
+                        ));
+                    }
+                    Some(struct_) => {
+                        for (parameter, argument) in
+                            struct_.const_parameters.iter().zip(instance.const_arguments.iter())
+                        {
+                            self.assert_size_is_valid(span, argument);
+                            let value = argument.substitute(&self.sizes).value();
+                            if !value.map_or(true, |value| {
+                                WideInteger::from_magnitude(value.get() as u128).fits(parameter.type_)
+                            }) {
+                                self.emit_err(TypeCheckerError::invalid_const_argument(
+                                    argument,
+                                    parameter.identifier,
+                                    parameter.type_,
+                                    span,
+                                ));
+                            }
+                        }
+                    }
+                    None => self.emit_err(TypeCheckerError::undefined_type(instance.identifier.name, span)),
+                }
             }
             // Check that the constituent types of the tuple are valid.
             Type::Tuple(tuple_type) => {
@@ -397,6 +840,12 @@ impl<'a> TypeChecker<'a> {
                     self.assert_type_is_valid(span, type_)
                 }
             }
+            // Check that the element type of the array is valid, and that its size is known.
+            Type::Array(element_type, length) => {
+                self.assert_size_is_valid(span, length);
+                self.assert_type_is_valid(span, element_type);
+                self.assert_not_tuple(span, element_type);
+            }
             // Check that the constituent types of mapping are valid.
             Type::Mapping(mapping_type) => {
                 self.assert_type_is_valid(span, &mapping_type.key);
@@ -406,6 +855,74 @@ impl<'a> TypeChecker<'a> {
         }
     }
 
+    /// Emits an error if the size names neither a program constant with a positive integer value,
+    /// nor a const parameter of the generic struct that we are currently traversing.
+    fn assert_size_is_valid(&self, span: Span, size: &Size) {
+        if let Size::Constant(name) = size {
+            if !self.sizes.contains_key(&name.name) && !self.const_parameters.contains(&name.name) {
+                self.emit_err(TypeCheckerError::invalid_size_constant(name, span));
+            }
+        }
+    }
+
+    /// Type checks a call to a generic function, returning the type of its output.
+    /// Each type parameter is instantiated with the type of the first argument whose parameter it annotates,
+    /// and the instantiation is recorded in the call so that the function can be monomorphized.
+    pub(crate) fn check_generic_call(
+        &mut self,
+        func: &FunctionSymbol,
+        input: &'a CallExpression,
+        expected: &Option<Type>,
+    ) -> Option<Type> {
+        // Check number of function arguments.
+        if func.input.len() != input.arguments.len() {
+            self.emit_err(TypeCheckerError::incorrect_num_args_to_call(
+                func.input.len(),
+                input.arguments.len(),
+                input.span(),
+            ));
+        }
+
+        // Check function argument types, inferring the type parameters.
+        let mut substitution = IndexMap::new();
+        for (parameter, argument) in func.input.iter().zip(input.arguments.iter()) {
+            match parameter.type_() {
+                Type::Identifier(type_parameter) if func.type_parameters.contains(&type_parameter.name) => {
+                    match substitution.get(&type_parameter.name).cloned() {
+                        Some(type_) => {
+                            self.visit_expression(argument, &Some(type_));
+                        }
+                        None => {
+                            if let Some(type_) = self.visit_expression(argument, &None) {
+                                if !self.is_int_type(&type_) {
+                                    self.emit_err(TypeCheckerError::invalid_type_argument(
+                                        type_parameter.name,
+                                        self.display_type(&type_),
+                                        argument.span(),
+                                    ));
+                                }
+                                substitution.insert(type_parameter.name, type_);
+                            }
+                        }
+                    }
+                }
+                type_ => {
+                    self.visit_expression(argument, &Some(type_));
+                }
+            }
+        }
+
+        // Note that an error has already been emitted if a type parameter could not be inferred.
+        let type_arguments = func
+            .type_parameters
+            .iter()
+            .map(|type_parameter| substitution.get(type_parameter).cloned())
+            .collect::<Option<Vec<_>>>()?;
+        *input.type_arguments.borrow_mut() = type_arguments;
+
+        Some(self.assert_and_return_type(func.output_type.substitute(&substitution), expected, func.span))
+    }
+
     /// Emits an error if the type is not a mapping.
     pub(crate) fn assert_mapping_type(&self, type_: &Option<Type>, span: Span) {
         self.check_type(
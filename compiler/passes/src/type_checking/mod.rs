@@ -27,20 +27,27 @@ pub use check_statements::*;
 pub mod checker;
 pub use checker::*;
 
-use crate::{Pass, SymbolTable};
+use crate::{CallGraph, Pass, PassSummary, StructGraph, SymbolTable, TypeTable};
 
-use leo_ast::{Ast, ProgramVisitor};
+use leo_ast::{Ast, IntegerType, ProgramVisitor};
 use leo_errors::{emitter::Handler, Result};
 
 impl<'a> Pass for TypeChecker<'a> {
-    type Input = (&'a Ast, &'a Handler, SymbolTable);
-    type Output = Result<SymbolTable>;
+    type Input = (&'a Ast, &'a Handler, SymbolTable, Option<IntegerType>);
+    type Output = Result<(SymbolTable, TypeTable, StructGraph, CallGraph, PassSummary)>;
 
-    fn do_pass((ast, handler, st): Self::Input) -> Self::Output {
-        let mut visitor = TypeChecker::new(st, handler);
+    fn do_pass((ast, handler, st, default_integer_type): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("type checker", Some(handler));
+        let mut visitor = TypeChecker::new(st, handler, default_integer_type);
         visitor.visit_program(ast.as_repr());
         handler.last_err()?;
 
-        Ok(visitor.symbol_table.take())
+        Ok((
+            visitor.symbol_table.take(),
+            visitor.type_table.take(),
+            visitor.struct_graph,
+            visitor.call_graph,
+            recorder.finish(Some(handler)),
+        ))
     }
 }
@@ -0,0 +1,48 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The validation pass checks structural invariants of the AST that later passes rely on, and may be run between any two passes.
+//! It always checks that no `Err` expressions remain. Given the invariants established by the passes run so far, it also checks each of them,
+//! e.g. that no `DefinitionStatement`s remain after static single assignment and that no `ConditionalStatement`s remain after flattening.
+//! Once the program has been type checked, it checks that every identifier that the program refers to resolves in the symbol table,
+//! looking up variables in the scope that encloses their use. Scopes are matched to blocks in the order that type checking created them.
+//! Identifiers generated by the compiler are internal symbols, which the symbol table does not describe, so they are not checked.
+//!
+//! Each violation is emitted as an error, so the pass is intended for debugging the compiler rather than for checking programs.
+
+pub mod validator;
+pub use validator::*;
+
+use crate::{Pass, PassSummary, SymbolTable};
+
+use leo_ast::{Ast, ProgramVisitor};
+use leo_errors::{emitter::Handler, Result};
+
+use indexmap::IndexSet;
+
+impl<'a> Pass for Validator<'a> {
+    type Input = (&'a Ast, &'a Handler, Option<&'a SymbolTable>, &'a IndexSet<Invariant>);
+    type Output = Result<PassSummary>;
+
+    fn do_pass((ast, handler, symbol_table, invariants): Self::Input) -> Self::Output {
+        let recorder = PassSummary::start("validation", Some(handler));
+        let mut visitor = Validator::new(handler, symbol_table, invariants);
+        visitor.visit_program(ast.as_repr());
+        handler.last_err()?;
+
+        Ok(recorder.finish(Some(handler)))
+    }
+}
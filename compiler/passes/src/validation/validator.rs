@@ -0,0 +1,509 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{ScopeIndex, SymbolTable};
+
+use leo_ast::{
+    AccessExpression, AssignStatement, Block, CfgStatement, ConditionalStatement, DefinitionStatement, ErrExpression,
+    Expression, ExpressionVisitor, Function, Identifier, IterationStatement, MatchStatement, Node, ProgramVisitor,
+    StatementVisitor, Struct, TernaryExpression,
+};
+use leo_errors::{emitter::Handler, AstError};
+use leo_span::{sym, Span};
+
+use indexmap::IndexSet;
+use std::fmt;
+
+/// A property of the AST, or of the state produced alongside it, that compiler passes require or establish.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Invariant {
+    /// The symbol table of the program has been created.
+    SymbolTable,
+    /// The program has been type checked, producing its struct and call graphs.
+    TypeChecked,
+    /// No `ErrExpression`s remain in the AST.
+    NoErrExpressions,
+    /// Every identifier that the program refers to resolves in the symbol table.
+    ResolvedIdentifiers,
+    /// No `CfgStatement`s remain in the AST.
+    NoCfgStatements,
+    /// No `MatchStatement`s remain in the AST.
+    NoMatchStatements,
+    /// No generic functions or structs remain, since each has been replaced by its instances.
+    NoGenerics,
+    /// No `IterationStatement`s remain in the AST.
+    NoLoops,
+    /// No variable is a tuple, since the only tuples that remain are the destructured outputs of calls and the values of return statements.
+    NoTuples,
+    /// The AST is in static single assignment form, so no `DefinitionStatement`s remain.
+    SingleAssignment,
+    /// No `ConditionalStatement`s remain in the AST.
+    NoConditionals,
+}
+
+impl fmt::Display for Invariant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::SymbolTable => write!(f, "the symbol table has been created"),
+            Self::TypeChecked => write!(f, "the program has been type checked"),
+            Self::NoErrExpressions => write!(f, "no `Err` expressions remain"),
+            Self::ResolvedIdentifiers => write!(f, "every identifier resolves in the symbol table"),
+            Self::NoCfgStatements => write!(f, "no `@cfg` statements remain"),
+            Self::NoMatchStatements => write!(f, "no `match` statements remain"),
+            Self::NoGenerics => write!(f, "no generic functions or structs remain"),
+            Self::NoLoops => write!(f, "no loops remain"),
+            Self::NoTuples => write!(f, "no tuples remain, other than destructured calls and returned values"),
+            Self::SingleAssignment => write!(f, "the program is in static single assignment form"),
+            Self::NoConditionals => write!(f, "no conditional statements remain"),
+        }
+    }
+}
+
+/// Checks the structural invariants of an AST, emitting an error for each violation.
+pub struct Validator<'a> {
+    /// An error handler used for any errors found during validation.
+    handler: &'a Handler,
+    /// The symbol table that identifiers are resolved in, once the program has been type checked.
+    symbol_table: Option<&'a SymbolTable>,
+    /// The invariants that are checked.
+    invariants: IndexSet<Invariant>,
+    /// The scope of the symbol table that encloses the node being visited, if it is known.
+    scope: Option<ScopeIndex>,
+    /// The number of sub-scopes of the current scope that have been visited.
+    scope_index: usize,
+}
+
+impl<'a> Validator<'a> {
+    /// Returns a new validator that checks `invariants`, along with the invariants that hold for every AST.
+    pub(crate) fn new(
+        handler: &'a Handler,
+        symbol_table: Option<&'a SymbolTable>,
+        invariants: &IndexSet<Invariant>,
+    ) -> Self {
+        let mut invariants = invariants.clone();
+        invariants.insert(Invariant::NoErrExpressions);
+        let symbol_table = symbol_table.filter(|_| invariants.contains(&Invariant::TypeChecked));
+        if symbol_table.is_some() {
+            invariants.insert(Invariant::ResolvedIdentifiers);
+        }
+        Self {
+            handler,
+            symbol_table,
+            invariants,
+            scope: Some(ScopeIndex::PROGRAM),
+            scope_index: 0,
+        }
+    }
+
+    /// Returns the next sub-scope of the current scope, which type checking created in traversal order.
+    /// Returns `None` if the scope is unknown, e.g. if a pass has added a block that type checking did not see.
+    fn next_scope(&mut self) -> Option<ScopeIndex> {
+        let symbol_table = self.symbol_table?;
+        let scope = self
+            .scope
+            .and_then(|scope| symbol_table.scope(scope).children.get(self.scope_index).copied());
+        self.scope_index += 1;
+        scope
+    }
+
+    /// Enters `scope`, returning the previous scope and the number of its sub-scopes that have been visited.
+    fn enter_scope(&mut self, scope: Option<ScopeIndex>) -> (Option<ScopeIndex>, usize) {
+        (
+            core::mem::replace(&mut self.scope, scope),
+            core::mem::replace(&mut self.scope_index, 0),
+        )
+    }
+
+    /// Exits the current scope, returning to the scope that `enter_scope` returned.
+    fn exit_scope(&mut self, (scope, scope_index): (Option<ScopeIndex>, usize)) {
+        self.scope = scope;
+        self.scope_index = scope_index;
+    }
+
+    /// Emits an error if `invariant` is checked, since the node at `span` violates it.
+    fn violates(&self, invariant: Invariant, span: Span) {
+        if self.invariants.contains(&invariant) {
+            self.handler.emit_err(AstError::invariant_violated(invariant, span));
+        }
+    }
+
+    /// Emits an error if tuples are checked and the tuple `place` is assigned a value other than the outputs of a call.
+    fn check_destructuring(&self, place: &Expression, value: &Expression) {
+        if matches!(place, Expression::Tuple(_)) && !matches!(value, Expression::Call(_)) {
+            self.violates(Invariant::NoTuples, place.span());
+        }
+    }
+
+    /// Returns whether `identifier` names a variable in scope, or a function, struct, or enum of the symbol table,
+    /// or is generated by the compiler, or is a keyword that is used as an identifier, such as `self`.
+    /// If the enclosing scope is unknown, a variable of any scope is accepted.
+    fn resolves(&self, symbol_table: &SymbolTable, identifier: &Identifier) -> bool {
+        let name = identifier.name;
+        let variable_in_scope = match self.scope {
+            Some(scope) => symbol_table.variable_in_scope(scope, name),
+            None => symbol_table.variable_in_any_scope(name),
+        };
+        name.is_internal()
+            || name == sym::SelfLower
+            || name == sym::block
+            || variable_in_scope
+            || symbol_table.lookup_fn_symbol(name).is_some()
+            || symbol_table.lookup_struct(name).is_some()
+            || symbol_table.lookup_enum(name).is_some()
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for Validator<'a> {
+    type AdditionalInput = ();
+    type Output = ();
+
+    fn visit_access(&mut self, input: &'a AccessExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        match input {
+            AccessExpression::Array(array) => {
+                self.visit_expression(&array.array, additional);
+                self.visit_expression(&array.index, additional);
+            }
+            AccessExpression::AssociatedFunction(function) => {
+                function
+                    .args
+                    .iter()
+                    .for_each(|arg| self.visit_expression(arg, additional));
+            }
+            AccessExpression::Member(member) => self.visit_expression(&member.inner, additional),
+            AccessExpression::Tuple(tuple) => {
+                self.violates(Invariant::NoTuples, tuple.span());
+                self.visit_expression(&tuple.tuple, additional);
+            }
+            AccessExpression::AssociatedConstant(_) => {}
+        }
+    }
+
+    fn visit_err(&mut self, input: &'a ErrExpression, _additional: &Self::AdditionalInput) -> Self::Output {
+        self.violates(Invariant::NoErrExpressions, input.span());
+    }
+
+    fn visit_identifier(&mut self, input: &'a Identifier, _additional: &Self::AdditionalInput) -> Self::Output {
+        if let Some(symbol_table) = self.symbol_table {
+            if !self.resolves(symbol_table, input) {
+                self.violates(Invariant::ResolvedIdentifiers, input.span());
+            }
+        }
+    }
+
+    fn visit_ternary(&mut self, input: &'a TernaryExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        if matches!(*input.if_true, Expression::Tuple(_)) {
+            self.violates(Invariant::NoTuples, input.span());
+        }
+        self.visit_expression(&input.condition, additional);
+        self.visit_expression(&input.if_true, additional);
+        self.visit_expression(&input.if_false, additional);
+    }
+}
+
+impl<'a> StatementVisitor<'a> for Validator<'a> {
+    fn visit_block(&mut self, input: &'a Block) {
+        let scope = self.next_scope();
+        let previous = self.enter_scope(scope);
+        input.statements.iter().for_each(|stmt| self.visit_statement(stmt));
+        self.exit_scope(previous);
+    }
+
+    fn visit_assign(&mut self, input: &'a AssignStatement) {
+        self.check_destructuring(&input.place, &input.value);
+        self.visit_expression(&input.value, &Default::default());
+    }
+
+    fn visit_cfg(&mut self, input: &'a CfgStatement) {
+        self.violates(Invariant::NoCfgStatements, input.span());
+        self.visit_statement(&input.statement);
+    }
+
+    fn visit_conditional(&mut self, input: &'a ConditionalStatement) {
+        self.violates(Invariant::NoConditionals, input.span());
+        self.visit_expression(&input.condition, &Default::default());
+        self.visit_block(&input.then);
+        if let Some(stmt) = input.otherwise.as_ref() {
+            self.visit_statement(stmt);
+        }
+    }
+
+    fn visit_definition(&mut self, input: &'a DefinitionStatement) {
+        self.violates(Invariant::SingleAssignment, input.span());
+        self.check_destructuring(&input.place, &input.value);
+        self.visit_expression(&input.value, &Default::default());
+    }
+
+    fn visit_iteration(&mut self, input: &'a IterationStatement) {
+        self.violates(Invariant::NoLoops, input.span());
+        self.visit_expression(&input.start, &Default::default());
+        if let Some(stop) = input.stop.as_ref() {
+            self.visit_expression(stop, &Default::default());
+        }
+        // The loop variable is declared in a scope of its own, which encloses the scope of the body.
+        let scope = self.next_scope();
+        let previous = self.enter_scope(scope);
+        self.visit_block(&input.block);
+        self.exit_scope(previous);
+    }
+
+    fn visit_match(&mut self, input: &'a MatchStatement) {
+        self.violates(Invariant::NoMatchStatements, input.span());
+        self.visit_expression(&input.expression, &Default::default());
+        input.arms.iter().for_each(|arm| self.visit_block(&arm.block));
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for Validator<'a> {
+    fn visit_struct(&mut self, input: &'a Struct) {
+        if !input.const_parameters.is_empty() {
+            self.violates(Invariant::NoGenerics, input.span);
+        }
+    }
+
+    fn visit_function(&mut self, input: &'a Function) {
+        if !input.type_parameters.is_empty() {
+            self.violates(Invariant::NoGenerics, input.span);
+        }
+        // The inputs of the function and of its finalize block are declared in sub-scopes of the scope of the function.
+        let scope = self
+            .symbol_table
+            .and_then(|symbol_table| symbol_table.lookup_fn_scope(input.name()));
+        let function = self.enter_scope(scope);
+
+        let scope = self.next_scope();
+        let previous = self.enter_scope(scope);
+        self.visit_block(&input.block);
+        self.exit_scope(previous);
+
+        if let Some(finalize) = &input.finalize {
+            let scope = self.next_scope();
+            let previous = self.enter_scope(scope);
+            self.visit_block(&finalize.block);
+            self.exit_scope(previous);
+        }
+
+        self.exit_scope(function);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::{CreateSymbolTable, Pass, TypeChecker};
+
+    use leo_ast::{Ast, NodeBuilder, Statement};
+    use leo_parser::Edition;
+    use leo_span::{span::BytePos, symbol::create_session_if_not_set_then};
+
+    /// A program that violates none of the structural invariants.
+    const VALID: &str = "program test.aleo {
+        transition main(a: u8) -> u8 {
+            return a + 1u8;
+        }
+    }";
+
+    fn parse(handler: &Handler, source: &str) -> Ast {
+        leo_parser::parse_ast(handler, &NodeBuilder::default(), source, BytePos(0), Edition::default()).unwrap()
+    }
+
+    /// Returns the number of errors emitted by validating `ast` with the given symbol table and invariants.
+    fn violations(ast: &Ast, symbol_table: Option<&SymbolTable>, invariants: &[Invariant]) -> usize {
+        let (handler, _) = Handler::new_with_buf();
+        let invariants = invariants.iter().copied().collect();
+        Validator::new(&handler, symbol_table, &invariants).visit_program(ast.as_repr());
+        handler.err_count()
+    }
+
+    /// Asserts that `source` satisfies every invariant other than `invariant`, which it violates.
+    fn assert_violates(source: &str, invariant: Invariant) {
+        create_session_if_not_set_then(|_| {
+            let (handler, _) = Handler::new_with_buf();
+            let ast = parse(&handler, source);
+            assert_eq!(violations(&ast, None, &[]), 0, "{source}");
+            assert!(violations(&ast, None, &[invariant]) > 0, "{source}");
+        });
+    }
+
+    #[test]
+    fn test_valid_program() {
+        create_session_if_not_set_then(|_| {
+            let (handler, _) = Handler::new_with_buf();
+            let ast = parse(&handler, VALID);
+            let invariants = [
+                Invariant::NoCfgStatements,
+                Invariant::NoMatchStatements,
+                Invariant::NoGenerics,
+                Invariant::NoLoops,
+                Invariant::NoTuples,
+                Invariant::SingleAssignment,
+                Invariant::NoConditionals,
+            ];
+            assert_eq!(violations(&ast, None, &invariants), 0);
+        });
+    }
+
+    #[test]
+    fn test_err_expression() {
+        create_session_if_not_set_then(|_| {
+            let (handler, _) = Handler::new_with_buf();
+            let mut ast = parse(&handler, VALID);
+            assert_eq!(violations(&ast, None, &[]), 0);
+
+            let mut program = ast.into_repr();
+            let function = program.program_scopes[0].functions.get_index_mut(0).unwrap().1;
+            match function.block.statements.last_mut() {
+                Some(Statement::Return(return_)) => {
+                    return_.expression = Expression::Err(ErrExpression {
+                        span: return_.span,
+                        id: return_.id,
+                    })
+                }
+                _ => unreachable!("The function ends with a return statement."),
+            }
+            ast = Ast::new(program);
+            assert_eq!(violations(&ast, None, &[]), 1);
+        });
+    }
+
+    #[test]
+    fn test_unresolved_identifier() {
+        create_session_if_not_set_then(|_| {
+            // `b` is declared in the scope of the conditional, so it is not in scope where it is returned.
+            let declared = "program test.aleo {
+                transition main(a: u8) -> u8 {
+                    if a > 0u8 {
+                        let b: u8 = a;
+                    }
+                    return a;
+                }
+            }";
+            let (handler, _) = Handler::new_with_buf();
+            let ast = parse(&handler, declared);
+            let symbol_table = CreateSymbolTable::do_pass((&ast, &handler)).unwrap().0;
+            let symbol_table = TypeChecker::do_pass((&ast, &handler, symbol_table, None)).unwrap().0;
+            assert_eq!(violations(&ast, Some(&symbol_table), &[Invariant::TypeChecked]), 0);
+
+            let used = parse(&handler, &declared.replace("return a;", "return b;"));
+            assert_eq!(violations(&used, Some(&symbol_table), &[Invariant::TypeChecked]), 1);
+            let unknown = parse(&handler, &declared.replace("return a;", "return c;"));
+            assert_eq!(violations(&unknown, Some(&symbol_table), &[Invariant::TypeChecked]), 1);
+        });
+    }
+
+    #[test]
+    fn test_cfg_statement() {
+        assert_violates(
+            "program test.aleo {
+                transition main(a: u8) -> u8 {
+                    @cfg(test)
+                    assert(a > 0u8);
+                    return a;
+                }
+            }",
+            Invariant::NoCfgStatements,
+        );
+    }
+
+    #[test]
+    fn test_match_statement() {
+        assert_violates(
+            "program test.aleo {
+                enum State {
+                    Idle,
+                    Done,
+                }
+                transition main(a: u8) -> u8 {
+                    let state: State = State::Idle;
+                    match state {
+                        State::Idle => {}
+                        _ => {}
+                    }
+                    return a;
+                }
+            }",
+            Invariant::NoMatchStatements,
+        );
+    }
+
+    #[test]
+    fn test_generic_function() {
+        assert_violates(
+            "program test.aleo {
+                function max<T: integer>(a: T, b: T) -> T {
+                    return a > b ? a : b;
+                }
+            }",
+            Invariant::NoGenerics,
+        );
+    }
+
+    #[test]
+    fn test_loop() {
+        assert_violates(
+            "program test.aleo {
+                transition main(a: u8) -> u8 {
+                    for i: u8 in 0u8..2u8 {
+                        assert(a > i);
+                    }
+                    return a;
+                }
+            }",
+            Invariant::NoLoops,
+        );
+    }
+
+    #[test]
+    fn test_tuple() {
+        assert_violates(
+            "program test.aleo {
+                transition main(a: u8, b: u8) -> u8 {
+                    let (c, d): (u8, u8) = (a + b, a * b);
+                    return c;
+                }
+            }",
+            Invariant::NoTuples,
+        );
+    }
+
+    #[test]
+    fn test_definition() {
+        assert_violates(
+            "program test.aleo {
+                transition main(a: u8) -> u8 {
+                    let b: u8 = a;
+                    return b;
+                }
+            }",
+            Invariant::SingleAssignment,
+        );
+    }
+
+    #[test]
+    fn test_conditional() {
+        assert_violates(
+            "program test.aleo {
+                transition main(a: u8) -> u8 {
+                    if a > 0u8 {
+                        return a;
+                    }
+                    return 0u8;
+                }
+            }",
+            Invariant::NoConditionals,
+        );
+    }
+}
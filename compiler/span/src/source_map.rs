@@ -82,6 +82,12 @@ impl SourceMap {
         Some(LineCol { source_file, line, col })
     }
 
+    /// Returns the 1-based line and 1-based (character) column of `pos`.
+    pub fn byte_pos_to_line_col(&self, pos: BytePos) -> Option<(usize, usize)> {
+        let LineCol { line, col, .. } = self.find_line_col(pos)?;
+        Some((line, col.to_usize() + 1))
+    }
+
     /// Retrives the location (source file, line, col) on the given span.
     pub fn span_to_location(&self, sp: Span) -> Option<SpanLocation> {
         let lo = self.find_line_col(sp.lo)?;
@@ -276,10 +282,38 @@ impl SourceFile {
         }
     }
 
+    /// Returns the 1-based line and 1-based (character) column of `pos`,
+    /// or `None` if `pos` is outside of this file.
+    pub fn byte_pos_to_line_col(&self, pos: BytePos) -> Option<(usize, usize)> {
+        if pos < self.start_pos || pos > self.end_pos {
+            return None;
+        }
+        let (line, col) = self.lookup_file_pos(pos);
+        Some((line, col.to_usize() + 1))
+    }
+
+    /// Returns the absolute `BytePos` of the 1-based `line` and 1-based (character) `col`,
+    /// or `None` if the position is not within this file.
+    /// A column one past the last character of a line refers to the end of that line.
+    pub fn line_col_to_byte_pos(&self, line: usize, col: usize) -> Option<BytePos> {
+        let line_start = *self.lines.get(line.checked_sub(1)?)?;
+        let line_end = self.lines.get(line).copied().unwrap_or(self.end_pos);
+        let offset = (line_start - self.start_pos).to_usize();
+        let line_src = &self.src[offset..(line_end - self.start_pos).to_usize()];
+        let byte = match line_src.char_indices().nth(col.checked_sub(1)?) {
+            Some((byte, _)) => byte,
+            // Allow pointing directly after the final character of the line.
+            None if line_src.chars().count() + 1 == col => line_src.len(),
+            None => return None,
+        };
+        Some(line_start + BytePos::from_usize(byte))
+    }
+
     /// Returns contents of a `span` assumed to be within the given file.
     fn contents_of_span(&self, span: Span) -> String {
-        let begin_pos = self.bytepos_to_file_charpos(span.lo).to_usize();
-        let end_pos = self.bytepos_to_file_charpos(span.hi).to_usize();
+        // Spans are byte ranges, so index the source by bytes rather than characters.
+        let begin_pos = (span.lo - self.start_pos).to_usize();
+        let end_pos = (span.hi - self.start_pos).to_usize();
         String::from_utf8_lossy(&self.src.as_bytes()[begin_pos..end_pos]).into_owned()
     }
 }
@@ -31,12 +31,19 @@ pub struct Span {
     /// The end (high) position of the span, exclusive.
     /// The length is simply `hi - lo`.
     pub hi: BytePos,
+    /// Whether the span belongs to code that the compiler generated from the code at the span, rather than code written there.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub synthetic: bool,
 }
 
 impl Span {
     /// Generate a new span from the `start`ing and `end`ing positions.
     pub fn new(start: BytePos, end: BytePos) -> Self {
-        Self { lo: start, hi: end }
+        Self {
+            lo: start,
+            hi: end,
+            synthetic: false,
+        }
     }
 
     /// Generates a dummy span with all defaults.
@@ -45,12 +52,75 @@ impl Span {
         Self {
             lo: BytePos(0),
             hi: BytePos(0),
+            synthetic: false,
         }
     }
 
     /// Is the span a dummy?
+    /// A synthetic span is a dummy if the code that it was generated from has no span.
     pub fn is_dummy(&self) -> bool {
-        self == &Self::dummy()
+        self.lo == BytePos(0) && self.hi == BytePos(0)
+    }
+
+    /// Returns the span marked as synthetic, for code that the compiler generates from the code at `self`.
+    pub fn synthesized(self) -> Self {
+        Self {
+            synthetic: true,
+            ..self
+        }
+    }
+
+    /// Returns the length of the span in bytes.
+    pub fn len(&self) -> usize {
+        (self.hi - self.lo).to_usize()
+    }
+
+    /// Does the span cover no bytes?
+    pub fn is_empty(&self) -> bool {
+        self.lo == self.hi
+    }
+
+    /// Does `self` fully enclose `other`?
+    pub fn contains(&self, other: Span) -> bool {
+        self.lo <= other.lo && other.hi <= self.hi
+    }
+
+    /// Returns the smallest span that includes both `self` and `other`.
+    /// Dummy spans are ignored, so merging with a dummy span returns the other span unchanged.
+    pub fn merge(self, other: Span) -> Span {
+        match (self.is_dummy(), other.is_dummy()) {
+            (true, _) => other,
+            (_, true) => self,
+            _ => self + other,
+        }
+    }
+
+    /// Merges all `spans` into the smallest span that includes each of them.
+    /// Returns a dummy span if `spans` is empty.
+    pub fn merge_all(spans: impl IntoIterator<Item = Span>) -> Span {
+        spans.into_iter().fold(Self::dummy(), Self::merge)
+    }
+
+    /// Returns the (possibly empty) span between the end of `self` and the start of `other`.
+    pub fn between(self, other: Span) -> Span {
+        Self::new(self.hi, other.lo.max(self.hi))
+    }
+
+    /// Returns the sub-span of `self` covering the byte offsets `start..end`, relative to `self.lo`.
+    /// Returns `None` if the range is inverted or not within `self`.
+    pub fn sub_span(&self, start: usize, end: usize) -> Option<Span> {
+        (start <= end && end <= self.len())
+            .then(|| Self::new(self.lo + BytePos::from_usize(start), self.lo + BytePos::from_usize(end)))
+    }
+
+    /// Returns an empty span at the start of `self`.
+    pub fn shrink_to_lo(&self) -> Span {
+        Self::new(self.lo, self.lo)
+    }
+
+    /// Returns an empty span at the end of `self`.
+    pub fn shrink_to_hi(&self) -> Span {
+        Self::new(self.hi, self.hi)
     }
 }
 
@@ -72,11 +142,14 @@ impl std::ops::Add for Span {
     type Output = Self;
 
     /// Add two spans together.
-    /// The resulting span is the smallest span that includes both.
+    /// The resulting span is the smallest span that includes both, and is only synthetic if both spans are.
     fn add(self, other: Self) -> Self {
         let lo = self.lo.min(other.lo);
         let hi = self.hi.max(other.hi);
-        Self::new(lo, hi)
+        Self {
+            synthetic: self.synthetic && other.synthetic,
+            ..Self::new(lo, hi)
+        }
     }
 }
 
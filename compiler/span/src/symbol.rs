@@ -143,13 +143,34 @@ symbols! {
     BHP512,
     BHP768,
     BHP1024,
+    ChaCha,
     commit,
+    get,
+    get_or_use,
     hash,
+    Mapping,
     Pedersen64,
     Pedersen128,
     Poseidon2,
     Poseidon4,
     Poseidon8,
+    rand_address,
+    rand_bool,
+    rand_field,
+    rand_group,
+    rand_i8,
+    rand_i16,
+    rand_i32,
+    rand_i64,
+    rand_i128,
+    rand_scalar,
+    rand_u8,
+    rand_u16,
+    rand_u32,
+    rand_u64,
+    rand_u128,
+    set,
+    verify,
 
     // types
     address,
@@ -165,8 +186,10 @@ symbols! {
     i32,
     i64,
     i128,
+    integer,
     record,
     scalar,
+    signature,
     string,
     u8,
     u16,
@@ -180,8 +203,10 @@ symbols! {
 
     // general keywords
     AlwaysConst,
+    As: "as",
     assert,
     Async: "async",
+    block,
     caller,
     circuit,
     Class: "class",
@@ -189,14 +214,19 @@ symbols! {
     CoreFunction,
     console,
     decrement,
+    default_integer_type,
+    edition,
     Else: "else",
+    Enum: "enum",
     finalize,
     For: "for",
     function,
+    height,
     If: "if",
     In: "in",
     import,
     increment,
+    inline,
     input,
     Let: "let",
     leo,
@@ -204,6 +234,7 @@ symbols! {
     assert_neq,
     main,
     mapping,
+    Match: "match",
     Mut: "mut",
     prelude,
     Public,
@@ -222,7 +253,15 @@ symbols! {
     public,
     private,
     owner,
+    signer,
     gates,
+    mint,
+    allow,
+    always,
+    never,
+    cfg,
+    feature,
+    network,
     _nonce,
     program,
 
@@ -232,10 +271,18 @@ symbols! {
     state_leaf,
 }
 
+/// The bit of a `Symbol`'s index that marks it as internal.
+const INTERNAL_BIT: u32 = 1 << 31;
+
 /// An interned string.
 ///
 /// Represented as an index internally, with all operations based on that.
 /// A `Symbol` reserves the value `0`, so that `Option<Symbol>` only takes up 4 bytes.
+///
+/// Symbols are in one of two namespaces.
+/// Identifiers in the source are interned with [`Symbol::intern`],
+/// while names generated by the compiler, e.g. `$var$0`, are interned with [`Symbol::intern_internal`].
+/// A symbol in one namespace is never equal to a symbol in the other, even if they have the same text.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Symbol(
     #[serde(deserialize_with = "Symbol::serde_to_symbol")]
@@ -258,9 +305,25 @@ impl Symbol {
         with_session_globals(|session_globals| session_globals.symbol_interner.intern(string))
     }
 
+    /// Maps a string to its interned representation in the namespace reserved for names generated by the compiler.
+    /// The parser never interns into this namespace, so the resulting symbol cannot collide with an identifier in the source.
+    pub fn intern_internal(string: &str) -> Self {
+        with_session_globals(|session_globals| {
+            Self::new(session_globals.internal_interner.intern(string).as_u32() | INTERNAL_BIT)
+        })
+    }
+
+    /// Returns whether the symbol is a name generated by the compiler.
+    pub const fn is_internal(self) -> bool {
+        self.as_u32() & INTERNAL_BIT != 0
+    }
+
     /// Convert to effectively a `&'static str` given the `SessionGlobals`.
     pub fn as_str<R>(self, s: &SessionGlobals, with: impl FnOnce(&str) -> R) -> R {
-        s.symbol_interner.get(self, with)
+        match self.is_internal() {
+            true => s.internal_interner.get(self, with),
+            false => s.symbol_interner.get(self, with),
+        }
     }
 
     /// Converts this symbol to the raw index.
@@ -268,8 +331,13 @@ impl Symbol {
         self.0.get() - 1
     }
 
+    /// Note that identifiers in the source cannot contain `$`, so a name containing it was generated by the compiler.
     fn serde_to_symbol<'de, D: Deserializer<'de>>(de: D) -> Result<NonZeroU32, D::Error> {
-        Ok(Symbol::intern(<&str>::deserialize(de)?).0)
+        let string = <&str>::deserialize(de)?;
+        match string.contains('$') {
+            true => Ok(Symbol::intern_internal(string).0),
+            false => Ok(Symbol::intern(string).0),
+        }
     }
 
     fn serde_from_symbol<S: Serializer>(index: &NonZeroU32, ser: S) -> Result<S::Ok, S::Error> {
@@ -293,6 +361,8 @@ impl fmt::Display for Symbol {
 pub struct SessionGlobals {
     /// The interner for `Symbol`s used in the compiler.
     symbol_interner: Interner,
+    /// The interner for `Symbol`s generated by the compiler.
+    internal_interner: Interner,
     /// The source map used in the compiler.
     pub source_map: SourceMap,
 }
@@ -301,6 +371,7 @@ impl Default for SessionGlobals {
     fn default() -> Self {
         Self {
             symbol_interner: Interner::prefilled(),
+            internal_interner: Interner::prefill(&[]),
             source_map: SourceMap::default(),
         }
     }
@@ -411,6 +482,6 @@ impl Interner {
     /// Returns the corresponding string for the given symbol.
     fn get<R>(&self, symbol: Symbol, with: impl FnOnce(&str) -> R) -> R {
         let set = &self.inner.borrow().set;
-        with(set.get_index(symbol.as_u32() as usize).unwrap())
+        with(set.get_index((symbol.as_u32() & !INTERNAL_BIT) as usize).unwrap())
     }
 }
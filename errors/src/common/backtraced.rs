@@ -44,6 +44,8 @@ pub struct Backtraced {
     pub type_: String,
     /// Is this Backtrace a warning or error?
     pub error: bool,
+    /// Is this Backtrace a warning that is denied, and so emitted as an error?
+    pub denied: bool,
     #[derivative(PartialEq = "ignore")]
     #[derivative(Hash = "ignore")]
     /// The backtrace representing where the error occured in Leo.
@@ -71,6 +73,7 @@ impl Backtraced {
             code_identifier,
             type_,
             error,
+            denied: false,
             backtrace,
         }
     }
@@ -115,6 +118,9 @@ impl fmt::Display for Backtraced {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let (kind, code) = if self.error {
             ("Error", self.error_code())
+        } else if self.denied {
+            // A denied warning keeps its warning code, so that its lint can be looked up.
+            ("Error", self.warning_code())
         } else {
             ("Warning", self.warning_code())
         };
@@ -122,7 +128,7 @@ impl fmt::Display for Backtraced {
 
         // To avoid the color enabling characters for comparison with test expectations.
         if is_not_test_framework() {
-            if self.error {
+            if self.error || self.denied {
                 write!(f, "{}", message.bold().red())?;
             } else {
                 write!(f, "{}", message.bold().yellow())?;
@@ -122,6 +122,9 @@ impl fmt::Display for Formatted {
 
         let (kind, code) = if self.backtrace.error {
             ("Error", self.error_code())
+        } else if self.backtrace.denied {
+            // A denied warning keeps its warning code, so that its lint can be looked up.
+            ("Error", self.warning_code())
         } else {
             ("Warning", self.warning_code())
         };
@@ -135,7 +138,7 @@ impl fmt::Display for Formatted {
             .to_owned()
             .is_empty()
         {
-            if self.backtrace.error {
+            if self.backtrace.error || self.backtrace.denied {
                 write!(f, "{}", message.bold().red())?;
             } else {
                 write!(f, "{}", message.bold().yellow())?;
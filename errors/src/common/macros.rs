@@ -86,6 +86,60 @@ macro_rules! create_messages {
         }
 
 
+        impl $type_ {
+            /// Returns the span of the source code that the message refers to, if it refers to any.
+            pub fn span(&self) -> Option<leo_span::Span> {
+                match self {
+                    Self::Formatted(formatted) => Some(formatted.span),
+                    Self::Backtraced(_) => None,
+                }
+            }
+
+            /// Returns the message, without its help or the source code that it refers to.
+            pub fn message(&self) -> String {
+                self.backtraced().message.clone()
+            }
+
+            /// Returns the help of the message, which suggests how to fix it, if it has any.
+            pub fn help(&self) -> Option<String> {
+                self.backtraced().help.clone()
+            }
+
+            /// Returns the name of the message, which is the name of the function that created it.
+            pub fn name(&self) -> &'static str {
+                Self::names()[(self.backtraced().code - Self::code_mask()) as usize]
+            }
+
+            /// Returns the names of all the messages of this type, in the order that they are defined.
+            pub fn names() -> &'static [&'static str] {
+                &[$(stringify!($names)),*]
+            }
+
+            /// Marks the message as denied, so that a warning is emitted as an error.
+            pub fn deny(mut self) -> Self {
+                let name = self.name();
+                let backtraced = match &mut self {
+                    Self::Formatted(formatted) => &mut formatted.backtrace,
+                    Self::Backtraced(backtraced) => backtraced,
+                };
+                backtraced.denied = true;
+                let note = format!("The lint `{name}` is denied.");
+                backtraced.help = Some(match backtraced.help.take() {
+                    Some(help) => format!("{help} {note}"),
+                    None => note,
+                });
+                self
+            }
+
+            /// Returns the message without the source code that it refers to.
+            pub fn backtraced(&self) -> &Backtraced {
+                match self {
+                    Self::Formatted(formatted) => &formatted.backtrace,
+                    Self::Backtraced(backtraced) => backtraced,
+                }
+            }
+        }
+
         // Steps over the list of functions with an initial code of 0.
         impl $type_ {
             create_messages!(@step 0i32, $(($(#[$docs])* $formatted_or_backtraced_list, $names($($arg_names: $arg_types,)*), $messages, $helps),)*);
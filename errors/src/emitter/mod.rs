@@ -19,8 +19,12 @@ use crate::LeoWarning;
 use super::LeoError;
 use core::default::Default;
 use core::fmt;
+use leo_span::{symbol::with_session_globals, Span};
+use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
+use std::str::FromStr;
 
 /// Types that are sinks for compiler errors.
 pub trait Emitter {
@@ -55,6 +59,133 @@ impl Emitter for StderrEmitter {
     }
 }
 
+/// The range of a source file that a diagnostic refers to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonSpan {
+    /// The name of the file.
+    pub file: String,
+    /// The offset in bytes of the start of the range from the start of the file.
+    pub lo: usize,
+    /// The offset in bytes of the end of the range from the start of the file.
+    pub hi: usize,
+    /// The 1-based line of the start of the range.
+    pub line_start: usize,
+    /// The 1-based column of the start of the range.
+    pub col_start: usize,
+    /// The 1-based line of the end of the range.
+    pub line_stop: usize,
+    /// The 1-based column of the end of the range.
+    pub col_stop: usize,
+}
+
+impl JsonSpan {
+    /// Returns the range of `span`, unless it does not refer to a source file.
+    pub(crate) fn new(span: Span) -> Option<Self> {
+        if span.is_dummy() {
+            return None;
+        }
+        let location = with_session_globals(|s| s.source_map.span_to_location(span))?;
+        Some(Self {
+            file: location.source_file.name.to_string(),
+            lo: (span.lo - location.source_file.start_pos).0 as usize,
+            hi: (span.hi - location.source_file.start_pos).0 as usize,
+            line_start: location.line_start,
+            col_start: location.col_start,
+            line_stop: location.line_stop,
+            col_stop: location.col_stop,
+        })
+    }
+}
+
+/// An error or warning, as written by the [`JsonEmitter`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonDiagnostic {
+    /// Either `error` or `warning`.
+    pub level: String,
+    /// The code of the diagnostic, e.g. `EPAR0370005`, if it has one.
+    pub code: Option<String>,
+    /// The message, without its help or the source code that it refers to.
+    pub message: String,
+    /// The help, which suggests how to fix the diagnostic, if it has any.
+    pub help: Option<String>,
+    /// The range of the source file that the diagnostic refers to, if it refers to any.
+    pub span: Option<JsonSpan>,
+}
+
+impl JsonDiagnostic {
+    /// Returns the diagnostic of `err`, unless it only refers to an error that was already emitted.
+    pub fn error(err: &LeoError) -> Option<Self> {
+        let code = match err {
+            LeoError::LastErrorCode(_) => return None,
+            LeoError::Anyhow(_) => None,
+            err => Some(err.error_code()),
+        };
+        Some(Self {
+            level: "error".to_string(),
+            code,
+            message: err.message(),
+            help: err.help(),
+            span: err.span().and_then(JsonSpan::new),
+        })
+    }
+
+    /// Returns the diagnostic of `warning`.
+    pub fn warning(warning: &LeoWarning) -> Self {
+        Self {
+            level: "warning".to_string(),
+            code: Some(warning.error_code()),
+            message: warning.message(),
+            help: warning.help(),
+            span: warning.span().and_then(JsonSpan::new),
+        }
+    }
+}
+
+/// An `Emitter` that writes each error and warning to the standard error as a line of JSON, for editors and CI systems.
+/// Each line is a [`JsonDiagnostic`].
+#[derive(Default)]
+pub struct JsonEmitter {
+    /// Exit code of the last emitted error.
+    last_error_code: Option<i32>,
+}
+
+impl JsonEmitter {
+    /// Writes `diagnostic` as a line of JSON.
+    fn emit(diagnostic: &JsonDiagnostic) {
+        eprintln!(
+            "{}",
+            serde_json::to_string(diagnostic).expect("Failed to serialize the diagnostic.")
+        );
+    }
+}
+
+impl Emitter for JsonEmitter {
+    fn emit_err(&mut self, err: LeoError) {
+        self.last_error_code = Some(err.exit_code());
+        if let Some(diagnostic) = JsonDiagnostic::error(&err) {
+            Self::emit(&diagnostic);
+        }
+    }
+
+    fn last_emitted_err_code(&self) -> Option<i32> {
+        self.last_error_code
+    }
+
+    fn emit_warning(&mut self, warning: LeoWarning) {
+        Self::emit(&JsonDiagnostic::warning(&warning));
+    }
+}
+
+/// The format in which a [`Handler`] writes errors and warnings to the standard error.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// Each diagnostic is rendered for people, along with the source code that it refers to.
+    #[default]
+    Human,
+    /// Each diagnostic is written as a line of JSON by the [`JsonEmitter`], for editors, CI systems, and other tools.
+    Json,
+}
+
 /// A buffer of `T`s.
 #[derive(Debug)]
 pub struct Buffer<T>(Vec<T>);
@@ -136,6 +267,73 @@ impl Emitter for BufferEmitter {
     }
 }
 
+/// The level of a lint, which determines what happens to the warnings that belong to it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize)]
+pub enum LintLevel {
+    /// The warnings are not emitted.
+    Allow,
+    /// The warnings are emitted as warnings.
+    #[default]
+    Warn,
+    /// The warnings are emitted as errors.
+    Deny,
+}
+
+impl fmt::Display for LintLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Allow => write!(f, "allow"),
+            Self::Warn => write!(f, "warn"),
+            Self::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+impl FromStr for LintLevel {
+    type Err = String;
+
+    /// Parses a level from its name, e.g. `deny`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(Self::Allow),
+            "warn" => Ok(Self::Warn),
+            "deny" => Ok(Self::Deny),
+            _ => Err(format!(
+                "`{}` is not a lint level, expected one of allow, warn, or deny",
+                s
+            )),
+        }
+    }
+}
+
+/// Decides the level of each warning.
+/// Lints allowed in a span of the source take precedence over the levels of lints, which take precedence over the default level.
+#[derive(Default)]
+struct LintPolicy {
+    /// The level of lints without a level of their own.
+    default_level: LintLevel,
+    /// The levels of lints, by name.
+    levels: HashMap<String, LintLevel>,
+    /// The names of lints that are allowed within a span, e.g. by an `@allow(..)` annotation.
+    allowed_spans: Vec<(String, Span)>,
+}
+
+impl LintPolicy {
+    /// Returns the level of `warning`.
+    fn level(&self, warning: &LeoWarning) -> LintLevel {
+        let name = warning.name();
+        let allowed = warning.span().map_or(false, |span| {
+            self.allowed_spans
+                .iter()
+                .any(|(lint, allowed)| lint == name && allowed.lo <= span.lo && span.hi <= allowed.hi)
+        });
+        match allowed {
+            true => LintLevel::Allow,
+            false => self.levels.get(name).copied().unwrap_or(self.default_level),
+        }
+    }
+}
+
 /// Contains the actual data for `Handler`.
 /// Modelled this way to afford an API using interior mutability.
 struct HandlerInner {
@@ -145,6 +343,10 @@ struct HandlerInner {
     warn_count: usize,
     /// The sink through which errors will be emitted.
     emitter: Box<dyn Emitter>,
+    /// Decides whether warnings are emitted, and whether as warnings or as errors.
+    lints: LintPolicy,
+    /// The warnings emitted thus far.
+    warnings: Vec<LeoWarning>,
 }
 
 impl HandlerInner {
@@ -159,10 +361,17 @@ impl HandlerInner {
         self.emitter.last_emitted_err_code()
     }
 
-    /// Emit the error `err`.
+    /// Emit the warning `warning`, according to the level of its lint.
     fn emit_warning(&mut self, warning: LeoWarning) {
-        self.warn_count = self.warn_count.saturating_add(1);
-        self.emitter.emit_warning(warning);
+        match self.lints.level(&warning) {
+            LintLevel::Allow => {}
+            LintLevel::Warn => {
+                self.warn_count = self.warn_count.saturating_add(1);
+                self.warnings.push(warning.clone());
+                self.emitter.emit_warning(warning);
+            }
+            LintLevel::Deny => self.emit_err(LeoError::DeniedWarning(warning.deny())),
+        }
     }
 }
 
@@ -186,10 +395,20 @@ impl Handler {
             err_count: 0,
             warn_count: 0,
             emitter,
+            lints: LintPolicy::default(),
+            warnings: Vec::new(),
         });
         Self { inner }
     }
 
+    /// Construct a `Handler` that writes errors and warnings to the standard error in the given `format`.
+    pub fn with_format(format: ErrorFormat) -> Self {
+        match format {
+            ErrorFormat::Human => Self::default(),
+            ErrorFormat::Json => Self::new(Box::new(JsonEmitter::default())),
+        }
+    }
+
     /// Construct a `Handler` that will append to `buf`.
     pub fn new_with_buf() -> (Self, BufferEmitter) {
         let buf = BufferEmitter::default();
@@ -214,6 +433,21 @@ impl Handler {
         self.inner.borrow_mut().emit_warning(warning);
     }
 
+    /// Sets the level of lints without a level of their own, e.g. to deny all warnings.
+    pub fn set_default_lint_level(&self, level: LintLevel) {
+        self.inner.borrow_mut().lints.default_level = level;
+    }
+
+    /// Sets the level of the lint named `lint`.
+    pub fn set_lint_level(&self, lint: impl Into<String>, level: LintLevel) {
+        self.inner.borrow_mut().lints.levels.insert(lint.into(), level);
+    }
+
+    /// Allows the lint named `lint` within `span`, regardless of its level.
+    pub fn allow_lint_in(&self, lint: impl Into<String>, span: Span) {
+        self.inner.borrow_mut().lints.allowed_spans.push((lint.into(), span));
+    }
+
     /// Emits the error `err`.
     /// This will immediately abort compilation.
     pub fn fatal_err(&self, err: LeoError) -> ! {
@@ -232,6 +466,11 @@ impl Handler {
         self.inner.borrow().warn_count
     }
 
+    /// The warnings emitted thus far, excluding those that were allowed or denied.
+    pub fn warnings(&self) -> Vec<LeoWarning> {
+        self.inner.borrow().warnings.clone()
+    }
+
     /// Did we have any errors thus far?
     pub fn had_errors(&self) -> bool {
         self.err_count() > 0
@@ -264,8 +503,8 @@ impl Handler {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ParserError;
-    use leo_span::{symbol::create_session_if_not_set_then, Span};
+    use crate::{ParserError, TypeCheckerWarning};
+    use leo_span::{source_map::FileName, span::BytePos, symbol::create_session_if_not_set_then, Span};
 
     #[test]
     fn fresh_no_errors() {
@@ -302,4 +541,82 @@ mod tests {
             Handler::with(|_| Ok(())).unwrap();
         })
     }
+
+    #[test]
+    fn json_diagnostic() {
+        create_session_if_not_set_then(|s| {
+            let source = s
+                .source_map
+                .new_source("program test.aleo {}", FileName::Custom("main.leo".to_string()));
+            let span = Span::new(source.start_pos + BytePos(8), source.start_pos + BytePos(12));
+            let diagnostic = JsonDiagnostic::error(&ParserError::unexpected_eof(span).into()).unwrap();
+            assert_eq!(diagnostic.level, "error");
+            assert_eq!(diagnostic.code.as_deref(), Some("EPAR0370003"));
+            assert_eq!(
+                diagnostic.span,
+                Some(JsonSpan {
+                    file: "main.leo".to_string(),
+                    lo: 8,
+                    hi: 12,
+                    line_start: 1,
+                    col_start: 9,
+                    line_stop: 1,
+                    col_stop: 13,
+                })
+            );
+
+            // The help is only written in its own field.
+            let diagnostic = JsonDiagnostic::error(&ParserError::leo_imports_only(span).into()).unwrap();
+            assert_eq!(diagnostic.message, "Invalid import call to non-leo file.");
+            assert_eq!(
+                diagnostic.help.as_deref(),
+                Some("Only imports of programs, e.g. `foo.aleo` or `foo.leo`, are currently supported.")
+            );
+
+            assert_eq!(JsonDiagnostic::error(&LeoError::LastErrorCode(1)), None);
+            let diagnostic = JsonDiagnostic::error(&ParserError::unexpected_eof(Span::default()).into()).unwrap();
+            assert_eq!(diagnostic.span, None);
+        })
+    }
+
+    #[test]
+    fn lint_levels() {
+        create_session_if_not_set_then(|_| {
+            let span = |lo, hi| Span::new(BytePos(lo), BytePos(hi));
+            let empty_function = |lo, hi| TypeCheckerWarning::empty_function("foo", span(lo, hi)).into();
+            let no_functions = || TypeCheckerWarning::program_has_no_functions("test", span(0, 10)).into();
+
+            let (handler, buf) = Handler::new_with_buf();
+            handler.emit_warning(empty_function(0, 10));
+            assert_eq!((handler.warning_count(), handler.err_count()), (1, 0));
+
+            // Warnings of allowed lints are not emitted.
+            handler.set_lint_level("empty_function", LintLevel::Allow);
+            handler.emit_warning(empty_function(0, 10));
+            assert_eq!((handler.warning_count(), handler.err_count()), (1, 0));
+
+            // Warnings of denied lints are emitted as errors, which name the lint.
+            handler.set_default_lint_level(LintLevel::Deny);
+            handler.emit_warning(no_functions());
+            assert_eq!((handler.warning_count(), handler.err_count()), (1, 1));
+            let err = buf.extract_errs().to_string();
+            assert!(err.starts_with("Error [WTYC0372000]"), "{}", err);
+            assert!(
+                err.contains("The lint `program_has_no_functions` is denied."),
+                "{}",
+                err
+            );
+
+            // Lints allowed within a span are allowed regardless of their level.
+            handler.set_lint_level("empty_function", LintLevel::Deny);
+            handler.allow_lint_in("empty_function", span(20, 40));
+            handler.emit_warning(empty_function(25, 30));
+            assert_eq!(handler.err_count(), 1);
+            handler.emit_warning(empty_function(15, 30));
+            assert_eq!(handler.err_count(), 2);
+
+            assert!(LeoWarning::names().any(|name| name == "empty_function"));
+            assert_eq!("deny".parse(), Ok(LintLevel::Deny));
+        })
+    }
 }
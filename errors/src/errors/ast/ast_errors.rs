@@ -121,4 +121,36 @@ create_messages!(
         msg: format!("variable `{var}` shadowed by"),
         help: None,
     }
+
+    /// For when a user shadows an enum.
+    @formatted
+    shadowed_enum {
+        args: (enum_: impl Display),
+        msg: format!("enum `{enum_}` shadowed by"),
+        help: None,
+    }
+
+    /// For when the symbol table fails to be represented as a JSON string.
+    @backtraced
+    failed_to_convert_symbol_table_to_json_string {
+        args: (error: impl ErrorArg),
+        msg: format!("failed to convert symbol table to a json string {}", error),
+        help: None,
+    }
+
+    /// For when a JSON string fails to be represented as a symbol table.
+    @backtraced
+    failed_to_read_json_string_to_symbol_table {
+        args: (error: impl ErrorArg),
+        msg: format!("failed to convert json string to a symbol table {}", error),
+        help: None,
+    }
+
+    /// For when the AST violates an invariant that the compiler relies on.
+    @formatted
+    invariant_violated {
+        args: (invariant: impl Display),
+        msg: format!("The AST violates the invariant that {invariant}."),
+        help: Some("This is a bug in the Leo compiler, which should be reported.".to_string()),
+    }
 );
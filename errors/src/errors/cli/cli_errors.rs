@@ -150,4 +150,18 @@ create_messages!(
         msg: format!("Failed to parse the `aleo deploy` command.\nSnarkVM Error: {}", error),
         help: None,
     }
+
+    @formatted
+    test_failed {
+        args: (test: impl Display),
+        msg: format!("The test `{test}` failed."),
+        help: None,
+    }
+
+    @backtraced
+    tests_failed {
+        args: (failed: usize, total: usize),
+        msg: format!("{failed} of {total} tests failed."),
+        help: None,
+    }
 );
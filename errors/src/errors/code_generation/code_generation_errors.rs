@@ -0,0 +1,33 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::create_messages;
+use std::fmt::{Debug, Display};
+
+create_messages!(
+    /// CodeGenError enum that represents all the errors for code generation in the `leo-passes` crate.
+    CodeGenError,
+    code_mask: 8000i32,
+    code_prefix: "CDG",
+
+    /// For when an expression cannot be compiled to Aleo instructions.
+    @formatted
+    unsupported_expression {
+        args: (kind: impl Display, expression: impl Display),
+        msg: format!("The {kind} `{expression}` cannot be compiled to Aleo instructions."),
+        help: Some(format!("The {kind} is not yet supported by the Aleo instruction set.")),
+    }
+);
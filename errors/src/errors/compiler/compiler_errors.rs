@@ -70,4 +70,124 @@ create_messages!(
         msg: format!("The program scope name `{program_scope_name}` must match `{file_name}`."),
         help: None,
     }
+
+    /// For when the compiler can't write the build fingerprint to the provided path.
+    @backtraced
+    failed_to_write_build_fingerprint {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("Cannot write the build fingerprint to '{:?}': {}", path, error),
+        help: None,
+    }
+
+    /// For when the compiler can't write the cached build to the provided path.
+    @backtraced
+    failed_to_write_build_cache {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("Cannot write the cached build to '{:?}': {}", path, error),
+        help: None,
+    }
+
+    /// For when the compiler can't write the profile to the provided path.
+    @backtraced
+    failed_to_write_profile {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("Cannot write the profile to '{:?}': {}", path, error),
+        help: None,
+    }
+
+    /// For when the compiler can't write the source map to the provided path.
+    @backtraced
+    failed_to_write_source_map {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("Cannot write the source map to '{:?}': {}", path, error),
+        help: None,
+    }
+
+    /// For when the compiler can't write the disassembly to the provided path.
+    @backtraced
+    failed_to_write_disassembly {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("Cannot write the disassembly to '{:?}': {}", path, error),
+        help: None,
+    }
+
+    /// For when the level of a lint that does not exist is set.
+    @backtraced
+    unknown_lint {
+        args: (lint: impl Display, lints: impl Display),
+        msg: format!("Unknown lint: `{lint}`."),
+        help: Some(format!("The lints are {lints}.")),
+    }
+
+    /// For when the compiler can't write the artifact manifest to the provided path.
+    @backtraced
+    failed_to_write_artifact_manifest {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("Cannot write the artifact manifest to '{:?}': {}", path, error),
+        help: None,
+    }
+
+    /// For when the compiler can't remove an artifact of a previous build.
+    @backtraced
+    failed_to_remove_artifact {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("Cannot remove the stale artifact '{:?}': {}", path, error),
+        help: None,
+    }
+
+    /// For when a program or a module imports the same module more than once.
+    @formatted
+    duplicate_module_import {
+        args: (module: impl Display),
+        msg: format!("The module `{module}` is imported more than once."),
+        help: None,
+    }
+
+    /// For when modules import each other in a cycle.
+    @formatted
+    cyclic_module_import {
+        args: (cycle: impl Display),
+        msg: format!("The modules {cycle} import each other in a cycle."),
+        help: Some("Move the definitions that the modules share into a module that imports neither of them.".to_string()),
+    }
+
+    /// For when a definition of an imported module has the same name as another definition of the program.
+    @formatted
+    duplicate_module_definition {
+        args: (definition: impl Display, existing: impl Display),
+        msg: format!("The definition `{definition}` conflicts with `{existing}`."),
+        help: Some("The definitions of imported modules share the program scope, so their names must be unique.".to_string()),
+    }
+
+    /// For when the compiler can't write the symbol table to the provided path.
+    @backtraced
+    failed_to_write_symbol_table {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("Cannot write the symbol table to '{:?}': {}", path, error),
+        help: None,
+    }
+
+    /// For when a compiler pass is run before the passes that establish what it requires, or after a pass that breaks it.
+    @backtraced
+    pass_requirement_not_met {
+        args: (pass: impl Display, requirement: impl Display),
+        msg: format!("The {pass} pass requires that {requirement}, but it does not hold once the earlier passes have run."),
+        help: Some("Register the passes with the pass manager in an order that satisfies their requirements.".to_string()),
+    }
+
+    /// For when the AST produced by a compiler pass does not uphold the invariants established by the passes run so far.
+    @backtraced
+    pass_invariant_violated {
+        args: (pass: impl Display),
+        msg: format!("The AST produced by the {pass} pass violates the invariants established so far."),
+        help: None,
+    }
+
+    /// For when the compiler can't write an output of a cached build to the provided path.
+    @backtraced
+    failed_to_write_cached_output {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("Cannot write the cached output to '{:?}': {}", path, error),
+        help: None,
+    }
 );
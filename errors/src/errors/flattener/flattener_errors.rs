@@ -53,4 +53,52 @@ create_messages!(
         ),
         help: None,
     }
+
+    /// For when a loop bound cannot be evaluated at compile time.
+    @formatted
+    loop_bound_not_constant {
+        args: (bound: impl Display),
+        msg: format!(
+            "The loop bound `{bound}` cannot be resolved to a compile-time integer.",
+        ),
+        help: Some("Loop bounds must be integer literals, constants, or expressions over them.".to_string()),
+    }
+
+    /// For when the length of an array that a loop iterates over cannot be determined at compile time.
+    @formatted
+    loop_array_length_unknown {
+        args: (array: impl Display),
+        msg: format!(
+            "The length of the array `{array}` that the loop iterates over cannot be determined.",
+        ),
+        help: Some("Loops may iterate over array expressions, array variables, and the elements of arrays of arrays.".to_string()),
+    }
+
+    /// For when the start of a loop range, which could only be evaluated while unrolling, is greater than its stop.
+    @formatted
+    loop_range_reversed {
+        args: (start: impl Display, stop: impl Display),
+        msg: format!(
+            "The loop range from `{start}` to `{stop}` is reversed.",
+        ),
+        help: Some("Loops count upwards, so the start of a range must not be greater than its stop.".to_string()),
+    }
+
+    /// For when an array index cannot be evaluated once loops are unrolled.
+    @formatted
+    array_index_not_constant {
+        args: (index: impl Display),
+        msg: format!(
+            "The array index `{index}` cannot be resolved to a compile-time integer.",
+        ),
+        help: Some("Array indices must be integer literals, constants, loop variables, or expressions over them.".to_string()),
+    }
+
+    /// For when an array index, which could only be evaluated while unrolling, is out of range.
+    @formatted
+    array_index_out_of_range {
+        args: (index: impl Display, length: impl Display),
+        msg: format!("Array index `{index}` out of range for an array with length `{length}`."),
+        help: None,
+    }
 );
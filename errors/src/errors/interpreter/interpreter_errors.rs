@@ -0,0 +1,73 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::create_messages;
+use std::fmt::{Debug, Display};
+
+create_messages!(
+    /// InterpreterError enum that represents all the errors for the `leo-interpreter` crate.
+    InterpreterError,
+    code_mask: 9000i32,
+    code_prefix: "INT",
+
+    /// For when an assertion fails during interpretation.
+    @formatted
+    assertion_failed {
+        args: (assertion: impl Display),
+        msg: format!("The assertion `{assertion}` failed."),
+        help: None,
+    }
+
+    /// For when an operation halts during interpretation, as it would when executed by snarkVM.
+    @formatted
+    halted {
+        args: (expression: impl Display, reason: impl Display),
+        msg: format!("The execution halted at `{expression}`: {reason}."),
+        help: None,
+    }
+
+    /// For when the interpreter reaches an expression that it cannot evaluate.
+    @formatted
+    unsupported_expression {
+        args: (kind: impl Display, expression: impl Display),
+        msg: format!("The interpreter does not support the {kind} `{expression}`."),
+        help: Some("Programs that use it can only be executed with snarkVM, e.g. by `leo run`.".to_string()),
+    }
+
+    /// For when a function to interpret does not exist.
+    @backtraced
+    unknown_function {
+        args: (function: impl Display),
+        msg: format!("The program does not define a function `{function}`."),
+        help: None,
+    }
+
+    /// For when a function is interpreted with the wrong number of inputs.
+    @backtraced
+    wrong_number_of_inputs {
+        args: (function: impl Display, expected: impl Display, found: impl Display),
+        msg: format!("The function `{function}` takes {expected} inputs, but {found} were given."),
+        help: None,
+    }
+
+    /// For when an input cannot be parsed as a value of the type of its parameter.
+    @backtraced
+    invalid_input {
+        args: (input: impl Display, type_: impl Display),
+        msg: format!("`{input}` is not a valid input of type `{type_}`."),
+        help: Some("The interpreter accepts literals of the primitive types, e.g. `1u8`, `2field`, or `true`.".to_string()),
+    }
+);
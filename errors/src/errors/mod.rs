@@ -15,7 +15,8 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 /// Contains the ASG error definitions.
-use crate::LeoMessageCode;
+use crate::{LeoMessageCode, LeoWarning};
+use leo_span::Span;
 
 /// Contains the AST error definitions.
 pub mod ast;
@@ -25,6 +26,10 @@ pub use self::ast::*;
 pub mod cli;
 pub use self::cli::*;
 
+/// Contains the Code Generation error definitions.
+pub mod code_generation;
+pub use self::code_generation::*;
+
 /// Contains the AST error definitions.
 pub mod compiler;
 pub use self::compiler::*;
@@ -37,6 +42,10 @@ pub use self::flattener::*;
 pub mod input;
 pub use self::input::*;
 
+/// Contains the Interpreter error definitions.
+pub mod interpreter;
+pub use self::interpreter::*;
+
 /// Contains the Package error definitions.
 pub mod package;
 pub use self::package::*;
@@ -77,6 +86,15 @@ pub enum LeoError {
     /// Represents a Flatten Error in a Leo Error.
     #[error(transparent)]
     FlattenError(#[from] FlattenError),
+    /// Represents a Code Generation Error in a Leo Error.
+    #[error(transparent)]
+    CodeGenError(#[from] CodeGenError),
+    /// Represents an Interpreter Error in a Leo Error.
+    #[error(transparent)]
+    InterpreterError(#[from] InterpreterError),
+    /// Represents a warning that is denied, and so is emitted as an error.
+    #[error(transparent)]
+    DeniedWarning(LeoWarning),
     /// Purely for just exiting with the correct status code and
     /// not re-displaying an error.
     #[error("")]
@@ -100,6 +118,9 @@ impl LeoError {
             PackageError(error) => error.error_code(),
             TypeCheckerError(error) => error.error_code(),
             FlattenError(error) => error.error_code(),
+            CodeGenError(error) => error.error_code(),
+            InterpreterError(error) => error.error_code(),
+            DeniedWarning(warning) => warning.error_code(),
             LastErrorCode(_) => unreachable!(),
             Anyhow(_) => unimplemented!(), // todo: implement error codes for snarkvm errors.
         }
@@ -118,10 +139,74 @@ impl LeoError {
             PackageError(error) => error.exit_code(),
             TypeCheckerError(error) => error.exit_code(),
             FlattenError(error) => error.exit_code(),
+            CodeGenError(error) => error.exit_code(),
+            InterpreterError(error) => error.exit_code(),
+            DeniedWarning(warning) => warning.exit_code(),
             LastErrorCode(code) => *code,
             Anyhow(_) => unimplemented!(), // todo: implement exit codes for snarkvm errors.
         }
     }
+
+    /// Returns the span of the source code that the error refers to, if it refers to any.
+    pub fn span(&self) -> Option<Span> {
+        use LeoError::*;
+
+        match self {
+            AstError(error) => error.span(),
+            CompilerError(error) => error.span(),
+            CliError(error) => error.span(),
+            InputError(error) => error.span(),
+            ParserError(error) => error.span(),
+            PackageError(error) => error.span(),
+            TypeCheckerError(error) => error.span(),
+            FlattenError(error) => error.span(),
+            CodeGenError(error) => error.span(),
+            InterpreterError(error) => error.span(),
+            DeniedWarning(warning) => warning.span(),
+            LastErrorCode(_) | Anyhow(_) => None,
+        }
+    }
+
+    /// Returns the message of the error, without its help or the source code that it refers to.
+    pub fn message(&self) -> String {
+        use LeoError::*;
+
+        match self {
+            AstError(error) => error.message(),
+            CompilerError(error) => error.message(),
+            CliError(error) => error.message(),
+            InputError(error) => error.message(),
+            ParserError(error) => error.message(),
+            PackageError(error) => error.message(),
+            TypeCheckerError(error) => error.message(),
+            FlattenError(error) => error.message(),
+            CodeGenError(error) => error.message(),
+            InterpreterError(error) => error.message(),
+            DeniedWarning(warning) => warning.message(),
+            LastErrorCode(_) => String::new(),
+            Anyhow(error) => error.to_string(),
+        }
+    }
+
+    /// Returns the help of the error, which suggests how to fix it, if it has any.
+    pub fn help(&self) -> Option<String> {
+        use LeoError::*;
+
+        match self {
+            AstError(error) => error.help(),
+            CompilerError(error) => error.help(),
+            CliError(error) => error.help(),
+            InputError(error) => error.help(),
+            ParserError(error) => error.help(),
+            PackageError(error) => error.help(),
+            TypeCheckerError(error) => error.help(),
+            FlattenError(error) => error.help(),
+            CodeGenError(error) => error.help(),
+            InterpreterError(error) => error.help(),
+            DeniedWarning(warning) => warning.help(),
+            LastErrorCode(_) | Anyhow(_) => None,
+        }
+    }
 }
 
 /// A global result type for all Leo crates, that defaults the errors to be a LeoError.
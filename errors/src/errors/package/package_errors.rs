@@ -305,4 +305,11 @@ create_messages!(
         msg: "The `src/` directory can contain only one file and must be named `main.leo`.".to_string(),
         help: None,
     }
+
+    @backtraced
+    failed_to_write_leo_file {
+        args: (path: impl Display, error: impl ErrorArg),
+        msg: format!("Failed to write Leo file `{}`: {}.", path, error),
+        help: None,
+    }
 );
@@ -227,7 +227,7 @@ create_messages!(
     leo_imports_only {
         args: (),
         msg: "Invalid import call to non-leo file.",
-        help: Some("Only imports of Leo `.leo` files are currently supported.".to_string()),
+        help: Some("Only imports of programs, e.g. `foo.aleo` or `foo.leo`, are currently supported.".to_string()),
     }
 
     @formatted
@@ -271,4 +271,109 @@ create_messages!(
         msg: "Invalid network identifier. The only supported identifier is `aleo`.",
         help: None,
     }
+
+    @formatted
+    invalid_array_length {
+        args: (length: impl Display),
+        msg: format!("Invalid array length `{length}`. Array lengths must be non-zero integers."),
+        help: None,
+    }
+
+    @formatted
+    empty_array {
+        args: (),
+        msg: "Arrays must contain at least one element.",
+        help: None,
+    }
+
+    @formatted
+    unknown_program_annotation {
+        args: (annotation: impl Display),
+        msg: format!("Unknown program annotation: `{annotation}`."),
+        help: Some("The program annotations are `@default_integer_type(<type>)` and `@edition(<edition>)`.".to_string()),
+    }
+
+    @formatted
+    invalid_default_integer_type {
+        args: (type_: impl Display),
+        msg: format!("Expected an integer type as the default integer type, found `{type_}`."),
+        help: None,
+    }
+
+    @formatted
+    invalid_array_length_constant {
+        args: (name: impl Display),
+        msg: format!("The array length `{name}` is not a program constant with an integer literal value."),
+        help: Some("Declare the length before its use, e.g. `const N: u32 = 4u32;`.".to_string()),
+    }
+
+    @formatted
+    feature_requires_edition {
+        args: (feature: impl Display, required: impl Display, edition: impl Display),
+        msg: format!("{feature} were introduced in edition {required} of Leo, but the program is written for edition {edition}."),
+        help: Some(format!("Declare `@edition({required})` before the program scope, or build with `--edition {required}`.")),
+    }
+
+    @formatted
+    unknown_edition {
+        args: (edition: impl Display, editions: impl Display),
+        msg: format!("Unknown edition of Leo: `{edition}`."),
+        help: Some(format!("The editions are {editions}.")),
+    }
+
+    @formatted
+    invalid_const_parameter_type {
+        args: (type_: impl Display),
+        msg: format!("Expected an unsigned integer type for a const parameter, found `{type_}`."),
+        help: None,
+    }
+
+    @formatted
+    unknown_generic_struct {
+        args: (name: impl Display),
+        msg: format!("`{name}` is not a generic struct, so it cannot be given const arguments."),
+        help: None,
+    }
+
+    @formatted
+    const_argument_count {
+        args: (name: impl Display, expected: impl Display, found: impl Display),
+        msg: format!("The struct `{name}` expects {expected} const arguments, but {found} were given."),
+        help: None,
+    }
+
+    @formatted
+    invalid_const_argument {
+        args: (value: impl Display, type_: impl Display),
+        msg: format!("The const argument `{value}` is not a valid `{type_}`."),
+        help: None,
+    }
+
+    @formatted
+    empty_enum {
+        args: (name: impl Display),
+        msg: format!("The enum `{name}` has no variants."),
+        help: Some("Declare at least one variant, e.g. `enum State { Idle }`.".to_string()),
+    }
+
+    @formatted
+    empty_cfg {
+        args: (),
+        msg: "A `@cfg` annotation must have at least one condition.",
+        help: Some("The conditions are `test`, `network = \"<name>\"`, and `feature = \"<name>\"`.".to_string()),
+    }
+
+    @formatted
+    invalid_cfg_condition {
+        args: (condition: impl Display),
+        msg: format!("`{condition}` is not a valid `@cfg` condition."),
+        help: Some("The conditions are `test`, `network = \"<name>\"`, and `feature = \"<name>\"`.".to_string()),
+    }
+
+    @formatted
+    invalid_statement_annotation {
+        args: (annotation: impl Display),
+        msg: format!("The annotation `{annotation}` cannot be applied to a statement."),
+        help: Some("Only `@cfg(..)` annotations can be applied to statements.".to_string()),
+    }
 );
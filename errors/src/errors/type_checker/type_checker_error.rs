@@ -385,7 +385,7 @@ create_messages!(
     @formatted
     invalid_self_access {
         args: (),
-        msg: format!("The allowed accesses to `self` are `self.caller`."),
+        msg: format!("The allowed accesses to `self` are `self.caller` and `self.signer`."),
         help: None,
     }
 
@@ -423,4 +423,270 @@ create_messages!(
         msg: format!("Cannot call a local transition function from a transition function."),
         help: None,
     }
+
+    @formatted
+    incorrect_array_length {
+        args: (expected: impl Display, actual: impl Display),
+        msg: format!("Expected an array of length `{expected}` found length `{actual}`"),
+        help: None,
+    }
+
+    @formatted
+    array_out_of_range {
+        args: (index: impl Display, length: impl Display),
+        msg: format!("Array index `{index}` out of range for an array with length `{length}`"),
+        help: None,
+    }
+
+    @formatted
+    array_index_must_be_constant {
+        args: (),
+        msg: format!("Array indices must be integer literals."),
+        help: None,
+    }
+
+    @formatted
+    array_not_allowed {
+        args: (),
+        msg: format!("Arrays are only allowed as local variables."),
+        help: Some("Arrays cannot be used as function inputs or outputs, record members, mapping types, or operands. Struct members may only be arrays of primitive types.".to_string()),
+    }
+
+    @formatted
+    cannot_infer_integer_type {
+        args: (value: impl Display),
+        msg: format!("Could not infer the type of the integer literal `{value}`."),
+        help: Some(format!("Add a type suffix, e.g. `{value}u32`, or set a default with `@default_integer_type(<type>)`.")),
+    }
+
+    @formatted
+    const_value_not_constant {
+        args: (name: impl Display),
+        msg: format!("The value of the program constant `{name}` cannot be evaluated at compile time."),
+        help: Some("Program constants must be booleans or integers computed from literals and other program constants.".to_string()),
+    }
+
+    @formatted
+    mapping_operation_outside_finalize {
+        args: (operation: impl Display),
+        msg: format!("`Mapping::{operation}` must be inside a finalize block."),
+        help: None,
+    }
+
+    @formatted
+    mapping_set_used_as_value {
+        args: (),
+        msg: format!("`Mapping::set` does not produce a value."),
+        help: Some("Call `Mapping::set` as a statement, e.g. `Mapping::set(balances, addr, amount);`.".to_string()),
+    }
+
+    @formatted
+    cyclic_function_dependency {
+        args: (path: impl Display, calls: impl Display),
+        msg: format!("Cyclic dependency between functions: {path}"),
+        help: Some(format!("Functions cannot call themselves, directly or through other functions. The cycle is formed by the following calls: {calls}.")),
+    }
+
+    @formatted
+    cyclic_struct_dependency {
+        args: (path: impl Display, members: impl Display),
+        msg: format!("Cyclic dependency between structs: {path}"),
+        help: Some(format!("Structs cannot contain themselves, directly or through other structs. The cycle is formed by the following members: {members}.")),
+    }
+
+    @formatted
+    unknown_lint {
+        args: (lint: impl Display, lints: impl Display),
+        msg: format!("Unknown lint: `{lint}`."),
+        help: Some(format!("The lints are {lints}.")),
+    }
+
+    @formatted
+    loop_range_reversed {
+        args: (start: impl Display, stop: impl Display),
+        msg: format!("The loop range from `{start}` to `{stop}` is reversed."),
+        help: Some("Loops count upwards, so the start of a range must not be greater than its stop.".to_string()),
+    }
+
+    @formatted
+    integer_overflow {
+        args: (expression: impl Display, type_: impl Display, result: impl Display),
+        msg: format!("The operation `{expression}` overflows the type `{type_}`."),
+        help: Some(format!("Its operands are known at compile time and its result is {result}, so it would always fail at runtime.")),
+    }
+
+    @formatted
+    cannot_infer_definition_type {
+        args: (place: impl Display),
+        msg: format!("Could not infer the type of `{place}`."),
+        help: Some("Add a type annotation to the definition.".to_string()),
+    }
+
+    @formatted
+    invalid_cast {
+        args: (from: impl Display, to: impl Display),
+        msg: format!("Cannot cast a value of type `{from}` to `{to}`."),
+        help: Some("Integers can be cast to integers, fields and scalars; booleans to integers and fields; fields to integers, groups, scalars and addresses; scalars to integers and fields; groups to fields and addresses; and addresses to fields and groups.".to_string()),
+    }
+
+    @formatted
+    string_not_constant {
+        args: (),
+        msg: format!("Strings are only allowed as the values of constants."),
+        help: Some("A string can be bound to a `const`, compared with `==` or `!=`, or hashed, but not stored in a variable, array or struct, passed to or returned from a function, or selected with a ternary.".to_string()),
+    }
+
+    @formatted
+    string_too_long_to_hash {
+        args: (length: impl Display, max: impl Display),
+        msg: format!("Cannot hash a string of {length} bytes."),
+        help: Some(format!("Only strings of at most {max} bytes, which fit in a single field, can be hashed.")),
+    }
+
+    @formatted
+    invalid_core_function_argument {
+        args: (position: impl Display, function: impl Display, type_: impl Display, allowed: impl Display),
+        msg: format!("The {position} argument of `{function}` cannot have type `{type_}`."),
+        help: Some(format!("It must be {allowed}.")),
+    }
+
+    @formatted
+    random_outside_finalize {
+        args: (function: impl Display),
+        msg: format!("`{function}` must be inside a finalize block."),
+        help: Some("Random values can only be generated on chain, where the caller cannot choose them. Move the call into a finalize block.".to_string()),
+    }
+
+    @formatted
+    invalid_block_access {
+        args: (),
+        msg: format!("The allowed access to `block` is `block.height`."),
+        help: None,
+    }
+
+    @formatted
+    block_height_outside_finalize {
+        args: (),
+        msg: format!("`block.height` must be inside a finalize block."),
+        help: Some("The height of the block is only known on chain, where the finalize block is executed.".to_string()),
+    }
+
+    @formatted
+    unknown_external_transition {
+        args: (program: impl Display, function: impl Display),
+        msg: format!("`{program}.aleo` does not declare a transition `{function}`."),
+        help: Some(format!("Import the program with `import {program}.aleo;`, and only call the transitions declared in its stub `imports/{program}.leo`.")),
+    }
+
+    @formatted
+    record_outside_transition {
+        args: (record: impl Display),
+        msg: format!("Only transitions can take or return the record `{record}`."),
+        help: Some("Records are consumed and produced by transitions, so declare the function with `transition`.".to_string()),
+    }
+
+    @formatted
+    unknown_type_bound {
+        args: (bound: impl Display),
+        msg: format!("Unknown type bound `{bound}`."),
+        help: Some("Type parameters can only be bounded by `integer`.".to_string()),
+    }
+
+    @formatted
+    uninferable_type_parameter {
+        args: (parameter: impl Display),
+        msg: format!("The type parameter `{parameter}` is not the type of any input."),
+        help: Some("Type parameters are inferred from the arguments of each call, so each one must be the type of an input.".to_string()),
+    }
+
+    @formatted
+    generic_transition {
+        args: (function: impl Display),
+        msg: format!("The transition `{function}` cannot have type parameters."),
+        help: Some("Transitions are the interface of a program, so their types must be known. Declare the function with `function` or `inline` instead.".to_string()),
+    }
+
+    @formatted
+    invalid_type_argument {
+        args: (parameter: impl Display, type_: impl Display),
+        msg: format!("The type parameter `{parameter}` cannot be instantiated with `{type_}`."),
+        help: Some("Type parameters bounded by `integer` can only be instantiated with integer types.".to_string()),
+    }
+
+    @formatted
+    unknown_variant {
+        args: (enum_: impl Display, variant: impl Display),
+        msg: format!("`{variant}` is not a variant of the enum `{enum_}`."),
+        help: None,
+    }
+
+    @formatted
+    match_on_non_enum {
+        args: (type_: impl Display),
+        msg: format!("A `match` statement can only match a value of an enum, but found a value of type `{type_}`."),
+        help: None,
+    }
+
+    @formatted
+    match_pattern_mismatch {
+        args: (expected: impl Display, found: impl Display),
+        msg: format!("Expected a variant of the enum `{expected}`, but found a variant of `{found}`."),
+        help: None,
+    }
+
+    @formatted
+    unreachable_match_arm {
+        args: (),
+        msg: format!("This arm is unreachable, since the arms before it match every value that it matches."),
+        help: Some("Remove the arm, or move it before the arms that cover it.".to_string()),
+    }
+
+    @formatted
+    non_exhaustive_match {
+        args: (missing: impl Display),
+        msg: format!("The `match` statement does not cover the variants {missing}."),
+        help: Some("Add an arm for each missing variant, or a wildcard arm `_ => { ... }`.".to_string()),
+    }
+
+    @formatted
+    duplicate_enum_variant {
+        args: (enum_: impl Display, variant: impl Display),
+        msg: format!("Enum {enum_} defined with more than one variant named `{variant}`."),
+        help: None,
+    }
+
+    @formatted
+    invalid_test_function {
+        args: (function: impl Display),
+        msg: format!("The test `{function}` must be a transition without inputs."),
+        help: Some("Tests are run as transitions, so declare the test as a `transition` and construct the values it checks in its body.".to_string()),
+    }
+
+    @formatted
+    invalid_size_constant {
+        args: (name: impl Display),
+        msg: format!("The size `{name}` is not a program constant with a positive integer value."),
+        help: Some("Declare the size as a program constant, e.g. `const N: u32 = 4u32;`.".to_string()),
+    }
+
+    @formatted
+    struct_not_generic {
+        args: (name: impl Display),
+        msg: format!("`{name}` is not a generic struct, so it cannot be given const arguments."),
+        help: None,
+    }
+
+    @formatted
+    incorrect_num_const_arguments {
+        args: (name: impl Display, expected: impl Display, received: impl Display),
+        msg: format!("The struct `{name}` expects {expected} const arguments, but {received} were given."),
+        help: None,
+    }
+
+    @formatted
+    invalid_const_argument {
+        args: (argument: impl Display, parameter: impl Display, type_: impl Display),
+        msg: format!("The const argument `{argument}` is not a valid `{type_}`, which is the type of the const parameter `{parameter}`."),
+        help: None,
+    }
 );
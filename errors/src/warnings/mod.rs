@@ -16,19 +16,30 @@
 
 /// The LeoError type that contains all sub error types.
 /// This allows a unified error type throughout the Leo crates.
-use crate::LeoMessageCode;
+use crate::{emitter::JsonSpan, Backtraced, Formatted, LeoMessageCode};
+use leo_span::Span;
+
+use backtrace::Backtrace;
+use serde::{Deserialize, Serialize};
 
 /// Contains the Parser warning definitions.
 pub mod parser;
 pub use self::parser::*;
 
+/// Contains the Type Checker warning definitions.
+pub mod type_checker;
+pub use self::type_checker::*;
+
 /// The LeoWarning type that contains all sub error types.
 /// This allows a unified error type throughout the Leo crates.
-#[derive(Debug, Error)]
+#[derive(Clone, Debug, Error)]
 pub enum LeoWarning {
     /// Represents an Parser Error in a Leo Error.
     #[error(transparent)]
     ParserWarning(#[from] ParserWarning),
+    /// Represents a Type Checker Warning in a Leo Warning.
+    #[error(transparent)]
+    TypeCheckerWarning(#[from] TypeCheckerWarning),
 }
 
 impl LeoWarning {
@@ -38,6 +49,146 @@ impl LeoWarning {
 
         match self {
             ParserWarning(warning) => warning.warning_code(),
+            TypeCheckerWarning(warning) => warning.warning_code(),
+        }
+    }
+
+    /// Implement exit code for each type of Warning.
+    pub fn exit_code(&self) -> i32 {
+        use LeoWarning::*;
+
+        match self {
+            ParserWarning(warning) => warning.exit_code(),
+            TypeCheckerWarning(warning) => warning.exit_code(),
+        }
+    }
+
+    /// Returns the name of the lint that the warning belongs to, e.g. `empty_function`.
+    pub fn name(&self) -> &'static str {
+        use LeoWarning::*;
+
+        match self {
+            ParserWarning(warning) => warning.name(),
+            TypeCheckerWarning(warning) => warning.name(),
+        }
+    }
+
+    /// Returns the names of all lints.
+    pub fn names() -> impl Iterator<Item = &'static str> {
+        ParserWarning::names()
+            .iter()
+            .chain(TypeCheckerWarning::names())
+            .copied()
+    }
+
+    /// Marks the warning as denied, so that it is emitted as an error.
+    pub fn deny(self) -> Self {
+        use LeoWarning::*;
+
+        match self {
+            ParserWarning(warning) => ParserWarning(warning.deny()),
+            TypeCheckerWarning(warning) => TypeCheckerWarning(warning.deny()),
+        }
+    }
+
+    /// Returns the span of the source code that the warning refers to, if it refers to any.
+    pub fn span(&self) -> Option<Span> {
+        use LeoWarning::*;
+
+        match self {
+            ParserWarning(warning) => warning.span(),
+            TypeCheckerWarning(warning) => warning.span(),
+        }
+    }
+
+    /// Returns the message of the warning, without its help or the source code that it refers to.
+    pub fn message(&self) -> String {
+        use LeoWarning::*;
+
+        match self {
+            ParserWarning(warning) => warning.message(),
+            TypeCheckerWarning(warning) => warning.message(),
+        }
+    }
+
+    /// Returns the help of the warning, which suggests how to fix it, if it has any.
+    pub fn help(&self) -> Option<String> {
+        use LeoWarning::*;
+
+        match self {
+            ParserWarning(warning) => warning.help(),
+            TypeCheckerWarning(warning) => warning.help(),
+        }
+    }
+
+    /// Returns the warning without the source code that it refers to.
+    fn backtraced(&self) -> &Backtraced {
+        use LeoWarning::*;
+
+        match self {
+            ParserWarning(warning) => warning.backtraced(),
+            TypeCheckerWarning(warning) => warning.backtraced(),
+        }
+    }
+}
+
+/// A warning that can be serialized, so that a later session can emit it again, e.g. when a cached build is reused.
+/// Its span is stored as a range of its source file, since the spans of a session are only valid within the session.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredWarning {
+    /// The message of the warning.
+    pub message: String,
+    /// The help of the warning, if it has any.
+    pub help: Option<String>,
+    /// The code of the warning, which identifies its lint.
+    pub code: i32,
+    /// The leading digits of the code of the warning.
+    pub code_identifier: i8,
+    /// The characters representing the type of the warning, e.g. `PAR` for parser warnings.
+    pub type_: String,
+    /// The range of the source file that the warning refers to, if it refers to any.
+    pub span: Option<JsonSpan>,
+}
+
+impl StoredWarning {
+    /// Returns the stored form of `warning`.
+    pub fn new(warning: &LeoWarning) -> Self {
+        let backtraced = warning.backtraced();
+        Self {
+            message: backtraced.message.clone(),
+            help: backtraced.help.clone(),
+            code: backtraced.code,
+            code_identifier: backtraced.code_identifier,
+            type_: backtraced.type_.clone(),
+            span: warning.span().and_then(JsonSpan::new),
+        }
+    }
+
+    /// Returns the warning, which refers to `span` of the current session if its range could be found in it.
+    pub fn into_warning(self, span: Option<Span>) -> LeoWarning {
+        let is_parser_warning = self.type_ == ParserWarning::message_type();
+        let backtraced = Backtraced::new_from_backtrace(
+            self.message,
+            self.help,
+            self.code,
+            self.code_identifier,
+            self.type_,
+            false,
+            Backtrace::new(),
+        );
+        match (is_parser_warning, span) {
+            (true, Some(span)) => ParserWarning::Formatted(Formatted {
+                span,
+                backtrace: backtraced,
+            })
+            .into(),
+            (true, None) => ParserWarning::Backtraced(backtraced).into(),
+            (false, Some(span)) => TypeCheckerWarning::Formatted(Formatted {
+                span,
+                backtrace: backtraced,
+            })
+            .into(),
+            (false, None) => TypeCheckerWarning::Backtraced(backtraced).into(),
         }
     }
 }
@@ -16,6 +16,8 @@
 
 use crate::create_messages;
 
+use std::fmt::Display;
+
 create_messages!(
     /// ParserWarning enum that represents all the warnings for the `leo-parser` crate.
     ParserWarning,
@@ -29,4 +31,20 @@ create_messages!(
          msg: "`constant` is preferred over `const` for function parameters to indicate a R1CS constant.",
          help: None,
      }
+
+    /// For when a legacy construct has no equivalent in current syntax.
+    @formatted
+    could_not_migrate {
+        args: (construct: impl Display),
+        msg: format!("`{construct}` could not be migrated to current syntax automatically."),
+        help: Some("Rewrite this by hand, or remove it.".to_string()),
+    }
+
+    /// For when a user asserts with the deprecated `console` syntax.
+    @formatted
+    console_assert_deprecated {
+        args: (function: impl Display),
+        msg: format!("`console.{function}` is deprecated."),
+        help: Some(format!("Use the `{function}` statement instead, e.g. `{function}(...);`.")),
+    }
 );
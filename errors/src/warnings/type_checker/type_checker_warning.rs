@@ -0,0 +1,89 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::create_messages;
+
+use std::fmt::Display;
+
+create_messages!(
+    /// TypeCheckerWarning enum that represents all the warnings for the type checker.
+    TypeCheckerWarning,
+    code_mask: 2000i32,
+    code_prefix: "TYC",
+
+    @formatted
+    program_has_no_functions {
+        args: (program: impl Display),
+        msg: format!("The program `{program}` does not contain any functions."),
+        help: Some("A program without a `function` or `transition` cannot be executed.".to_string()),
+    }
+
+    @formatted
+    empty_function {
+        args: (function: impl Display),
+        msg: format!("The function `{function}` has an empty body."),
+        help: Some("An empty function has no effect. Consider removing it.".to_string()),
+    }
+
+    @formatted
+    struct_members_can_be_reordered {
+        args: (struct_: impl Display, order: impl Display, current: impl Display, reordered: impl Display),
+        msg: format!("Reordering the members of `{struct_}` would reduce its serialized size from {current} to {reordered} bytes."),
+        help: Some(format!("Consider declaring the members in the order {order}, so that small members share field elements.")),
+    }
+
+    @formatted
+    struct_member_can_be_narrowed {
+        args: (struct_: impl Display, member: impl Display, type_: impl Display, current: impl Display, narrowed: impl Display),
+        msg: format!("Every value assigned to `{struct_}.{member}` fits in a `{type_}`, which would reduce the serialized size of `{struct_}` from {current} to {narrowed} bytes."),
+        help: Some(format!("Consider declaring `{member}` as a `{type_}`.")),
+    }
+
+    @formatted
+    record_gates_can_increase {
+        args: (function: impl Display, excess: impl Display),
+        msg: format!("The records output by `{function}` may hold more gates than its record inputs, by `{excess}`."),
+        help: Some(format!("If `{function}` is intended to mint gates, annotate it with `@mint`.")),
+    }
+
+    @formatted
+    assertion_can_fail {
+        args: (inputs: impl Display),
+        msg: format!("This assertion fails when the inputs are `{inputs}`."),
+        help: Some("Consider handling these inputs before the assertion is reached.".to_string()),
+    }
+
+    @formatted
+    public_value_depends_on_private_inputs {
+        args: (value: impl Display, inputs: impl Display),
+        msg: format!("The {value} of this transition is public, but depends on the private inputs {inputs}."),
+        help: Some("Public values may reveal the private inputs they are computed from. If this is intended, consider declaring the inputs public.".to_string()),
+    }
+
+    @formatted
+    unused_variable {
+        args: (variable: impl Display),
+        msg: format!("The variable `{variable}` is never used."),
+        help: Some(format!("Consider removing `{variable}`, or annotate the function with `@allow(unused_variable)`.")),
+    }
+
+    @formatted
+    unused_function {
+        args: (function: impl Display),
+        msg: format!("The function `{function}` is never called."),
+        help: Some(format!("Consider removing `{function}`, or annotate it with `@allow(unused_function)`.")),
+    }
+);
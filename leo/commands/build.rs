@@ -17,11 +17,12 @@
 use crate::commands::ALEO_CLI_COMMAND;
 use crate::{commands::Command, context::Context};
 
-use leo_ast::Struct;
-use leo_compiler::{Compiler, InputAst, OutputOptions};
+use leo_ast::{IntegerType, Program, Struct};
+use leo_compiler::{AstSnapshot, Compiler, InputAst, OutputOptions};
 use leo_errors::{CliError, CompilerError, PackageError, Result};
-use leo_package::source::SourceDirectory;
+use leo_package::source::{MainFile, SourceDirectory};
 use leo_package::{inputs::InputFile, outputs::OutputsDirectory};
+use leo_parser::Edition;
 use leo_span::symbol::with_session_globals;
 
 use aleo::commands::Build as AleoBuild;
@@ -32,7 +33,7 @@ use snarkvm::prelude::{ProgramID, Testnet3};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
-use leo_errors::emitter::Handler;
+use leo_errors::emitter::{ErrorFormat, Handler, LintLevel};
 use leo_package::build::BuildDirectory;
 use leo_package::imports::ImportsDirectory;
 use leo_span::Symbol;
@@ -44,6 +45,8 @@ use tracing::span::Span;
 pub struct BuildOptions {
     #[structopt(long, help = "Enables offline mode.")]
     pub offline: bool,
+    #[structopt(long, help = "Writes errors and warnings to stderr as lines of JSON.")]
+    pub json_errors: bool,
     #[structopt(long, help = "Enable spans in AST snapshots.")]
     pub enable_spans: bool,
     #[structopt(long, help = "Writes all AST snapshots for the different compiler phases.")]
@@ -58,27 +61,183 @@ pub struct BuildOptions {
     pub enable_ssa_ast_snapshot: bool,
     #[structopt(long, help = "Writes AST snapshot of the flattened AST.")]
     pub enable_flattened_ast_snapshot: bool,
+    #[structopt(
+        long,
+        value_delimiter = ',',
+        help = "Writes AST snapshots after the named passes, e.g. `ssa,flatten`. One of initial, lower, monomorphize, unroll, ssa, flatten, inline, simplify, range, reduce, balance, or outline."
+    )]
+    pub enable_ast_snapshot: Vec<AstSnapshot>,
+    #[structopt(
+        long,
+        help = "Writes variables renamed by the compiler with their source names in AST snapshots."
+    )]
+    pub enable_source_names: bool,
+    #[structopt(
+        long,
+        help = "Numbers the variables renamed by the compiler with a single counter, as in earlier versions, e.g. `x$37`."
+    )]
+    pub global_ssa_numbering: bool,
+    #[structopt(long, help = "Writes the symbol table after type checking.")]
+    pub enable_symbol_table_snapshot: bool,
+    #[structopt(
+        long,
+        help = "Writes a profile of the estimated cost of proving each line of the program."
+    )]
+    pub enable_profile: bool,
+    #[structopt(
+        long,
+        help = "Writes a map from each generated Aleo instruction to the Leo code it was generated from."
+    )]
+    pub enable_source_map: bool,
+    #[structopt(
+        long,
+        help = "Writes the Leo code of each function alongside its Aleo instructions, for side-by-side viewers."
+    )]
+    pub enable_disassembly: bool,
+    #[structopt(long, help = "Prints the time taken and the work performed by each compiler pass.")]
+    pub enable_pass_timings: bool,
+    #[structopt(
+        long,
+        help = "Prints the estimated number of constraints of each function and line of the program."
+    )]
+    pub cost: bool,
+    #[structopt(
+        long,
+        help = "Prints the number of instructions, registers, structs and records, and of each opcode, of the generated program."
+    )]
+    pub stats: bool,
+    #[structopt(
+        long,
+        help = "The type of unsuffixed integer literals whose type cannot be inferred, e.g. `u32`."
+    )]
+    pub default_integer_type: Option<IntegerType>,
+    #[structopt(
+        long,
+        help = "The edition of Leo that programs without an edition annotation are written for, e.g. `2022`."
+    )]
+    pub edition: Option<Edition>,
+    #[structopt(
+        long,
+        value_delimiter = ',',
+        help = "Enables the named features for `@cfg(feature = \"..\")` annotations, e.g. `logging`."
+    )]
+    pub features: Vec<String>,
+    #[structopt(long, help = "Compiles the functions and statements annotated with `@cfg(test)`.")]
+    pub cfg_test: bool,
+    #[structopt(
+        long,
+        help = "Reuses the outputs of the previous build if the sources and options are unchanged."
+    )]
+    pub incremental: bool,
+    #[structopt(
+        long,
+        help = "Outlines repeated instruction sequences into closures to reduce program size."
+    )]
+    pub enable_function_outlining: bool,
+    #[structopt(long, help = "Warns about struct layouts that could be serialized in fewer bytes.")]
+    pub enable_layout_suggestions: bool,
+    #[structopt(
+        long,
+        help = "Injects runtime assertions that check the compiler's invariants, for debugging."
+    )]
+    pub enable_sanitizer: bool,
+    #[structopt(
+        long,
+        help = "Inlines standard functions when doing so adds at most this many statements, e.g. `16`."
+    )]
+    pub inlining_budget: Option<usize>,
+    #[structopt(
+        long,
+        help = "Rebalances chains of at least this many selections into trees of smaller depth, e.g. `8`."
+    )]
+    pub ternary_balancing_threshold: Option<usize>,
+    #[structopt(long, help = "Searches for inputs on which the assertions of each transition fail.")]
+    pub enable_assertion_checking: bool,
+    #[structopt(
+        long,
+        help = "Warns about public outputs and finalize arguments that depend on private inputs."
+    )]
+    pub enable_privacy_warnings: bool,
+    #[structopt(
+        long,
+        help = "Validates the AST after every compiler pass, for debugging the compiler."
+    )]
+    pub debug_compiler: bool,
+    #[structopt(long, help = "Emits warnings as errors, unless their lint is allowed.")]
+    pub deny_warnings: bool,
+    #[structopt(long, value_delimiter = ',', help = "Allows the named lints, e.g. `empty_function`.")]
+    pub allow: Vec<String>,
+    #[structopt(long, value_delimiter = ',', help = "Denies the named lints, e.g. `empty_function`.")]
+    pub deny: Vec<String>,
+}
+
+impl BuildOptions {
+    /// Returns the format in which errors and warnings are written.
+    pub(crate) fn error_format(&self) -> ErrorFormat {
+        match self.json_errors {
+            true => ErrorFormat::Json,
+            false => ErrorFormat::Human,
+        }
+    }
+}
+
+impl From<BuildOptions> for leo_compiler::BuildOptions {
+    fn from(options: BuildOptions) -> Self {
+        Self {
+            assertion_checking: options.enable_assertion_checking,
+            default_integer_type: options.default_integer_type,
+            debug_compiler: options.debug_compiler,
+            deny_warnings: options.deny_warnings,
+            edition: options.edition,
+            features: options.features,
+            function_outlining: options.enable_function_outlining,
+            global_ssa_numbering: options.global_ssa_numbering,
+            incremental: options.incremental,
+            inlining_budget: options.inlining_budget,
+            layout_suggestions: options.enable_layout_suggestions,
+            lint_levels: options
+                .allow
+                .into_iter()
+                .map(|lint| (lint, LintLevel::Allow))
+                .chain(options.deny.into_iter().map(|lint| (lint, LintLevel::Deny)))
+                .collect(),
+            privacy_warnings: options.enable_privacy_warnings,
+            sanitize: options.enable_sanitizer,
+            ternary_balancing: options.ternary_balancing_threshold,
+            test: options.cfg_test,
+        }
+    }
 }
 
 impl From<BuildOptions> for OutputOptions {
     fn from(options: BuildOptions) -> Self {
-        let mut out_options = Self {
-            spans_enabled: options.enable_spans,
-            initial_input_ast: options.enable_initial_input_ast_snapshot,
-            initial_ast: options.enable_initial_ast_snapshot,
-            unrolled_ast: options.enable_unrolled_ast_snapshot,
-            ssa_ast: options.enable_ssa_ast_snapshot,
-            flattened_ast: options.enable_flattened_ast_snapshot,
-        };
+        let mut ast_snapshots = options.enable_ast_snapshot;
+        for (enabled, snapshot) in [
+            (options.enable_initial_ast_snapshot, AstSnapshot::Initial),
+            (options.enable_unrolled_ast_snapshot, AstSnapshot::Unroll),
+            (options.enable_ssa_ast_snapshot, AstSnapshot::Ssa),
+            (options.enable_flattened_ast_snapshot, AstSnapshot::Flatten),
+        ] {
+            if enabled {
+                ast_snapshots.push(snapshot);
+            }
+        }
         if options.enable_all_ast_snapshots {
-            out_options.initial_input_ast = true;
-            out_options.initial_ast = true;
-            out_options.unrolled_ast = true;
-            out_options.ssa_ast = true;
-            out_options.flattened_ast = true;
+            ast_snapshots = AstSnapshot::ALL.to_vec();
         }
 
-        out_options
+        Self {
+            spans_enabled: options.enable_spans,
+            initial_input_ast: options.enable_initial_input_ast_snapshot || options.enable_all_ast_snapshots,
+            ast_snapshots,
+            source_names: options.enable_source_names,
+            profile: options.enable_profile,
+            source_map: options.enable_source_map,
+            disassembly: options.enable_disassembly,
+            cost_report: options.cost,
+            pass_report: options.enable_pass_timings,
+            symbol_table: options.enable_symbol_table_snapshot,
+        }
     }
 }
 
@@ -116,7 +275,7 @@ impl Command for Build {
         let build_directory = BuildDirectory::open(&package_path)?;
 
         // Initialize error handler
-        let handler = Handler::default();
+        let handler = Handler::with_format(self.compiler_options.error_format());
 
         // Fetch paths to all .leo files in the source directory.
         let source_files = SourceDirectory::files(&package_path)?;
@@ -173,7 +332,7 @@ impl Command for Build {
                 .map_err(|e| CompilerError::file_read_error(&input_file_path, e))?;
 
             // TODO: This is a hack to notify the user that something is wrong with the input file. Redesign.
-            leo_parser::parse_input(&handler, &input_sf.src, input_sf.start_pos)
+            leo_parser::parse_input(&handler, &Default::default(), &input_sf.src, input_sf.start_pos)
                 .map_err(|_e| println!("Warning: Failed to parse input file"))
                 .ok()
         } else {
@@ -234,6 +393,9 @@ fn compile_leo_file(
         false => format!("main.{}", program_id.network()),
     });
 
+    let enable_pass_timings = options.enable_pass_timings;
+    let enable_statistics = options.stats;
+
     // Create a new instance of the Leo compiler.
     let mut compiler = Compiler::new(
         program_name,
@@ -241,11 +403,36 @@ fn compile_leo_file(
         handler,
         file_path.clone(),
         outputs.to_path_buf(),
+        Some(options.clone().into()),
         Some(options.into()),
     );
 
     // Compile the Leo program into Aleo instructions.
-    let (symbol_table, instructions) = compiler.compile_and_generate_instructions()?;
+    let (symbols, instructions) = compiler.compile_and_generate_instructions()?;
+
+    // Emit the work performed by each compiler pass.
+    for pass in compiler.report().passes() {
+        tracing::debug!(
+            pass = pass.name,
+            duration = ?pass.duration,
+            nodes_visited = pass.nodes_visited,
+            nodes_rewritten = pass.nodes_rewritten,
+            diagnostics_emitted = ?pass.diagnostics_emitted,
+            "Ran compiler pass"
+        );
+        for decision in &pass.decisions {
+            tracing::debug!(pass = pass.name, decision = decision.as_str(), "Compiler pass decision");
+        }
+    }
+    if enable_pass_timings {
+        tracing::info!("Compiler passes for '{}':\n{}", file_name, compiler.report());
+    }
+    if let Some(report) = compiler.cost_report() {
+        tracing::info!("Estimated constraints of '{}':\n{}", file_name, report);
+    }
+    if let Some(statistics) = compiler.statistics().filter(|_| enable_statistics) {
+        tracing::info!("Statistics of '{}':\n{}", file_name, statistics);
+    }
 
     // Write the instructions.
     std::fs::File::create(&aleo_file_path)
@@ -253,11 +440,38 @@ fn compile_leo_file(
         .write_all(instructions.as_bytes())
         .map_err(CliError::failed_to_load_instructions)?;
 
+    // Write the build fingerprint, so that deployed instructions can be traced back to the source.
+    compiler
+        .fingerprint()
+        .write_to_file(outputs, &format!("{}.fingerprint.json", compiler.program_name))?;
+
     // Prepare the path string.
     let _path_string = format!("(in \"{}\")", aleo_file_path.display());
 
     // Log the build as successful.
     tracing::info!("Compiled '{}' into Aleo instructions", file_name,);
 
-    Ok(symbol_table.structs)
+    Ok(symbols.into_structs())
+}
+
+/// Runs the front end of the compiler on the main Leo file of the package, returning the program that the interpreter evaluates.
+/// Note that this should be called before the package is built, since building changes the working directory.
+pub(crate) fn interpretable_program(context: &Context, options: BuildOptions) -> Result<Program> {
+    let package_path = context.dir()?;
+    let program_id = context.open_manifest()?.program_id().clone();
+    let outputs_directory = OutputsDirectory::create(&package_path)?;
+
+    let handler = Handler::with_format(options.error_format());
+    let mut compiler = Compiler::new(
+        program_id.name().to_string(),
+        program_id.network().to_string(),
+        &handler,
+        package_path.join(MainFile::filename()),
+        outputs_directory,
+        Some(options.clone().into()),
+        Some(options.into()),
+    );
+    compiler.interpreter_stages()?;
+
+    Ok(compiler.ast.into_repr())
 }
@@ -0,0 +1,72 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{commands::Command, context::Context};
+
+use leo_ast::Formatter;
+use leo_errors::{emitter::Handler, CompilerError, PackageError, Result};
+use leo_package::source::SourceDirectory;
+use leo_span::symbol::with_session_globals;
+
+use clap::StructOpt;
+use colored::Colorize;
+use tracing::span::Span;
+
+/// Format the Leo source files of the current package in place.
+#[derive(StructOpt, Debug)]
+pub struct Fmt {
+    #[structopt(long, default_value = "100", help = "The maximum width of a formatted line.")]
+    pub width: usize,
+    #[structopt(long, default_value = "4", help = "The number of spaces per indentation level.")]
+    pub indent: usize,
+}
+
+impl Command for Fmt {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Formatting")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        let package_path = context.dir()?;
+        let formatter = Formatter::new(self.width, self.indent);
+        let handler = Handler::default();
+
+        // Format all .leo files in the source directory.
+        for file_path in SourceDirectory::files(&package_path)? {
+            // Load the file into the source map.
+            let source_file = with_session_globals(|s| s.source_map.load_file(&file_path))
+                .map_err(|e| CompilerError::file_read_error(&file_path, e))?;
+
+            let formatted = leo_parser::format(&handler, &source_file.src, source_file.start_pos, &formatter)?;
+
+            // Only rewrite the files whose formatting changed.
+            if formatted != source_file.src {
+                std::fs::write(&file_path, formatted)
+                    .map_err(|e| PackageError::failed_to_write_leo_file(file_path.display(), e))?;
+                tracing::info!("formatted {}", file_path.display().to_string().dimmed());
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -24,6 +24,9 @@ pub use clean::Clean;
 pub mod deploy;
 pub use deploy::Deploy;
 
+pub mod fmt;
+pub use fmt::Fmt;
+
 pub mod new;
 pub use new::New;
 
@@ -33,6 +36,9 @@ pub use node::Node;
 pub mod run;
 pub use run::Run;
 
+pub mod test;
+pub use test::Test;
+
 use crate::context::*;
 use leo_errors::Result;
 
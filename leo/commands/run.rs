@@ -14,14 +14,17 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use super::build::BuildOptions;
+use super::build::{interpretable_program, BuildOptions};
 use crate::commands::ALEO_CLI_COMMAND;
 use crate::{
     commands::{Build, Command},
     context::Context,
 };
+use leo_ast::Program;
 use leo_errors::{CliError, PackageError, Result};
+use leo_interpreter::Interpreter;
 use leo_package::build::BuildDirectory;
+use leo_span::Symbol;
 
 use aleo::commands::Run as AleoRun;
 
@@ -40,12 +43,18 @@ pub struct Run {
     )]
     inputs: Vec<String>,
 
+    #[structopt(
+        long,
+        help = "Evaluates the program with the Leo interpreter, instead of executing it with snarkVM."
+    )]
+    dry_run: bool,
+
     #[structopt(flatten)]
     pub(crate) compiler_options: BuildOptions,
 }
 
 impl Command for Run {
-    type Input = <Build as Command>::Output;
+    type Input = (<Build as Command>::Output, Option<Program>);
     type Output = ();
 
     fn log_span(&self) -> Span {
@@ -53,13 +62,19 @@ impl Command for Run {
     }
 
     fn prelude(&self, context: Context) -> Result<Self::Input> {
-        (Build {
+        // The program is interpreted as it is after the front end of the compiler, so it is compiled separately.
+        let program = match self.dry_run {
+            true => Some(interpretable_program(&context, self.compiler_options.clone())?),
+            false => None,
+        };
+        let output = (Build {
             compiler_options: self.compiler_options.clone(),
         })
-        .execute(context)
+        .execute(context)?;
+        Ok((output, program))
     }
 
-    fn apply(self, context: Context, input: Self::Input) -> Result<Self::Output> {
+    fn apply(self, context: Context, (input, program): Self::Input) -> Result<Self::Output> {
         // If input values are provided, then run the program with those inputs.
         // Otherwise, use the input file.
         let mut inputs = match self.inputs.is_empty() {
@@ -70,6 +85,14 @@ impl Command for Run {
             false => self.inputs,
         };
 
+        // Evaluate the program with the Leo interpreter, if requested.
+        if let Some(program) = program {
+            let mut interpreter = Interpreter::new(&program)?;
+            let output = interpreter.call_with_strings(Symbol::intern(&self.name), &inputs)?;
+            tracing::info!("{} => {}", self.name, output);
+            return Ok(());
+        }
+
         // Compose the `aleo run` command.
         let mut arguments = vec![ALEO_CLI_COMMAND.to_string(), self.name];
         arguments.append(&mut inputs);
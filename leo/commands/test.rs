@@ -0,0 +1,95 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::build::{interpretable_program, BuildOptions};
+use crate::{
+    commands::{Build, Command},
+    context::Context,
+};
+use leo_ast::Program;
+use leo_compiler::UnitTest;
+use leo_errors::{emitter::Handler, CliError, Result};
+use leo_interpreter::Interpreter;
+
+use clap::StructOpt;
+use tracing::span::Span;
+
+/// Build the program for testing and run its `@test` functions
+#[derive(StructOpt, Debug)]
+pub struct Test {
+    #[structopt(name = "FILTER", help = "Only runs the tests whose names contain this string.")]
+    filter: Option<String>,
+
+    #[structopt(flatten)]
+    pub(crate) compiler_options: BuildOptions,
+}
+
+impl Command for Test {
+    type Input = Program;
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Leo")
+    }
+
+    fn prelude(&self, context: Context) -> Result<Self::Input> {
+        // Build the program with the functions and statements that are only compiled for testing.
+        let compiler_options = BuildOptions {
+            cfg_test: true,
+            ..self.compiler_options.clone()
+        };
+        // The tests are run by the Leo interpreter, which evaluates the program as it is after the front end of the compiler.
+        let program = interpretable_program(&context, compiler_options.clone())?;
+        (Build { compiler_options }).execute(context)?;
+        Ok(program)
+    }
+
+    fn apply(self, _context: Context, program: Self::Input) -> Result<Self::Output> {
+        let unit_tests: Vec<_> = UnitTest::collect(&program)
+            .into_iter()
+            .filter(|test| {
+                self.filter
+                    .as_ref()
+                    .map_or(true, |filter| test.name.to_string().contains(filter.as_str()))
+            })
+            .collect();
+
+        tracing::info!("Running {} tests", unit_tests.len());
+
+        // Run each test with a fresh interpreter, so that the mappings updated by one test are not seen by the next.
+        let handler = Handler::default();
+        let mut failed = 0;
+        for test in unit_tests.iter() {
+            match Interpreter::new(&program).and_then(|mut interpreter| interpreter.call(test.name, Vec::new())) {
+                Ok(_) => tracing::info!("test {} ... ok", test.name),
+                Err(error) => {
+                    tracing::info!("test {} ... FAILED", test.name);
+                    handler.emit_err(error);
+                    handler.emit_err(CliError::test_failed(test.name, test.span));
+                    failed += 1;
+                }
+            }
+        }
+
+        match failed {
+            0 => {
+                tracing::info!("All {} tests passed", unit_tests.len());
+                Ok(())
+            }
+            _ => Err(CliError::tests_failed(failed, unit_tests.len()).into()),
+        }
+    }
+}
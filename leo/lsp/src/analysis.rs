@@ -0,0 +1,520 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Checks Leo programs and answers queries about them, independently of the protocol that the server speaks.
+//!
+//! A program is checked in a session of its own, since the names and spans of its AST are only valid within
+//! the session that it was parsed in. Each query therefore checks the program again, which takes a few
+//! milliseconds for programs of a typical size.
+
+use leo_ast::{
+    AssignStatement, CallExpression, CallType, ConstDeclaration, DefinitionStatement, Expression, ExpressionVisitor,
+    Function, Identifier, Input, IterationStatement, Literal, Mapping, Mode, Node, Program, ProgramVisitor,
+    StatementVisitor, Struct, StructExpression, Type,
+};
+use leo_compiler::Compiler;
+use leo_errors::{
+    emitter::{Emitter, Handler},
+    LeoError, LeoWarning,
+};
+use leo_passes::{SymbolTable, VariableSymbol};
+use leo_span::{
+    source_map::{FileName, SourceFile},
+    span::BytePos,
+    symbol::{create_session_if_not_set_then, with_session_globals},
+    Span, Symbol,
+};
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range};
+use std::{
+    cell::RefCell,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+/// Returns the errors and warnings of the program in `source`, which is the content of the file at `path`.
+pub fn diagnostics(path: &Path, source: &str) -> Vec<Diagnostic> {
+    analyze(path, source, |analysis| analysis.diagnostics.clone())
+}
+
+/// Returns the range of the definition of the variable, function, or struct at `position`, if there is one.
+pub fn definition(path: &Path, source: &str, position: Position) -> Option<Range> {
+    analyze(path, source, |analysis| {
+        let span = match analysis.occurrence_at(position)? {
+            Occurrence::Variable(identifier, body) => analysis.lookup_variable(identifier, body)?.span,
+            Occurrence::Function(identifier) => analysis.symbol_table()?.lookup_fn_symbol(identifier.name)?.span,
+            Occurrence::Struct(identifier) => analysis.symbol_table()?.lookup_struct(identifier.name)?.span,
+            Occurrence::Literal(_) => return None,
+        };
+        analysis.range(span)
+    })
+}
+
+/// Returns the Leo code that describes the variable, function, struct, or integer literal at `position`,
+/// e.g. the type of a variable or the type that an unsuffixed literal is inferred to have.
+pub fn hover(path: &Path, source: &str, position: Position) -> Option<String> {
+    analyze(path, source, |analysis| match analysis.occurrence_at(position)? {
+        Occurrence::Variable(identifier, body) => {
+            let variable = analysis.lookup_variable(identifier, body)?;
+            Some(format!("{}: {}", identifier, variable.type_))
+        }
+        Occurrence::Function(identifier) => analysis.lookup_function(identifier.name).map(signature),
+        Occurrence::Struct(identifier) => analysis
+            .symbol_table()?
+            .lookup_struct(identifier.name)
+            .map(|struct_| struct_.to_string()),
+        Occurrence::Literal(literal) => literal.integer_type().map(|type_| type_.to_string()),
+    })
+}
+
+/// Checks the program in `source` and answers `query` about it, within the session that it was checked in.
+fn analyze<T>(path: &Path, source: &str, query: impl FnOnce(&Analysis) -> T) -> T {
+    create_session_if_not_set_then(|_| {
+        let emitter = DiagnosticEmitter::new(path.to_path_buf());
+        let handler = Handler::new(Box::new(emitter.clone()));
+        let mut compiler = Compiler::new(
+            program_name(source),
+            "aleo".to_string(),
+            &handler,
+            path.to_path_buf(),
+            path.parent().unwrap_or(path).to_path_buf(),
+            None,
+            None,
+        );
+
+        // The symbol table of a program with type errors lacks the variables of its functions,
+        // but it is kept for its functions and structs.
+        let mut symbol_table = None;
+        let result = compiler
+            .parse_program_from_string(source, FileName::Real(path.to_path_buf()))
+            .and_then(|()| {
+                symbol_table = Some(compiler.symbol_table_pass()?);
+                let (checked, ..) = compiler.type_checker_pass(symbol_table.clone().unwrap_or_default())?;
+                symbol_table = Some(checked);
+                Ok(())
+            });
+        if let Err(error) = result {
+            emitter.clone().emit_err(error);
+        }
+
+        let source_file = compiler
+            .ast
+            .ast
+            .program_scopes
+            .values()
+            .next()
+            .and_then(|scope| with_session_globals(|s| s.source_map.span_to_location(scope.span)))
+            .map(|location| location.source_file);
+        let diagnostics = emitter.diagnostics.borrow().clone();
+        query(&Analysis {
+            path,
+            source_file,
+            program: &compiler.ast.ast,
+            symbol_table,
+            diagnostics,
+        })
+    })
+}
+
+/// Returns the name declared by the program scope of `source`, e.g. `hello` for `program hello.aleo { .. }`.
+/// The program is checked under the name it declares, so that files outside of a package can be checked as well.
+fn program_name(source: &str) -> String {
+    source
+        .split_once("program ")
+        .and_then(|(_, rest)| rest.split_once('.'))
+        .map(|(name, _)| name.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Returns the position of the 1-based `line` and `col` of the source map.
+/// Columns are counted in characters, which are UTF-16 code units for the ASCII code of Leo programs.
+fn position(line: usize, col: usize) -> Position {
+    Position::new(line.saturating_sub(1) as u32, col.saturating_sub(1) as u32)
+}
+
+/// Returns the message of a diagnostic, followed by its help if it has any, since the protocol has no field for the help.
+fn with_help(message: String, help: Option<String>) -> String {
+    match help {
+        Some(help) => format!("{message}\n{help}"),
+        None => message,
+    }
+}
+
+/// Returns the signature of `function`, e.g. `transition mint(public receiver: address) -> token`.
+fn signature(function: &Function) -> String {
+    let keyword = match function.call_type {
+        CallType::Inline => "inline",
+        CallType::Standard => "function",
+        CallType::Transition => "transition",
+    };
+    let input = |input: &Input| match input.mode() {
+        Mode::None => format!("{}: {}", input.identifier(), input.type_()),
+        mode => format!("{} {}: {}", mode, input.identifier(), input.type_()),
+    };
+    let inputs = function.input.iter().map(input).collect::<Vec<_>>().join(", ");
+    match function.output_type {
+        Type::Unit => format!("{} {}({})", keyword, function.identifier, inputs),
+        ref output => format!("{} {}({}) -> {}", keyword, function.identifier, inputs, output),
+    }
+}
+
+/// An `Emitter` that collects the errors and warnings of a program as diagnostics of the protocol.
+#[derive(Clone)]
+struct DiagnosticEmitter {
+    /// The path of the file that is checked. Diagnostics of other files, e.g. imports, are placed at its start.
+    path: PathBuf,
+    diagnostics: Rc<RefCell<Vec<Diagnostic>>>,
+    last_error_code: Rc<RefCell<Option<i32>>>,
+}
+
+impl DiagnosticEmitter {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            diagnostics: Default::default(),
+            last_error_code: Default::default(),
+        }
+    }
+
+    fn push(&self, span: Option<Span>, severity: DiagnosticSeverity, code: Option<String>, message: String) {
+        let range = span
+            .and_then(|span| with_session_globals(|s| s.source_map.span_to_location(span)))
+            .filter(|location| matches!(&location.source_file.name, FileName::Real(path) if *path == self.path))
+            .map(|location| Range {
+                start: position(location.line_start, location.col_start),
+                end: position(location.line_stop, location.col_stop),
+            })
+            .unwrap_or_default();
+        self.diagnostics.borrow_mut().push(Diagnostic {
+            range,
+            severity: Some(severity),
+            code: code.map(NumberOrString::String),
+            source: Some("leo".to_string()),
+            message,
+            ..Default::default()
+        });
+    }
+}
+
+impl Emitter for DiagnosticEmitter {
+    fn emit_err(&mut self, err: LeoError) {
+        match err {
+            // The error was already emitted.
+            LeoError::LastErrorCode(_) => {}
+            LeoError::Anyhow(_) => self.push(None, DiagnosticSeverity::ERROR, None, err.message()),
+            err => {
+                *self.last_error_code.borrow_mut() = Some(err.exit_code());
+                let message = with_help(err.message(), err.help());
+                self.push(err.span(), DiagnosticSeverity::ERROR, Some(err.error_code()), message);
+            }
+        }
+    }
+
+    fn last_emitted_err_code(&self) -> Option<i32> {
+        *self.last_error_code.borrow()
+    }
+
+    fn emit_warning(&mut self, warning: LeoWarning) {
+        let message = with_help(warning.message(), warning.help());
+        self.push(
+            warning.span(),
+            DiagnosticSeverity::WARNING,
+            Some(warning.error_code()),
+            message,
+        );
+    }
+}
+
+/// A checked program.
+struct Analysis<'a> {
+    /// The path of the file that the program was read from.
+    path: &'a Path,
+    /// The source file of the program, if it was parsed.
+    source_file: Option<Rc<SourceFile>>,
+    program: &'a Program,
+    /// The symbol table of the program, if the symbol table pass succeeded.
+    symbol_table: Option<SymbolTable>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Analysis<'a> {
+    fn symbol_table(&self) -> Option<&SymbolTable> {
+        self.symbol_table.as_ref()
+    }
+
+    /// Returns the range of `span`, if it is within the file of the program.
+    fn range(&self, span: Span) -> Option<Range> {
+        let source_file = self.source_file.as_ref()?;
+        let (line_start, col_start) = source_file.byte_pos_to_line_col(span.lo)?;
+        let (line_stop, col_stop) = source_file.byte_pos_to_line_col(span.hi)?;
+        match &source_file.name {
+            FileName::Real(path) if path == self.path => Some(Range {
+                start: position(line_start, col_start),
+                end: position(line_stop, col_stop),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the variable, function, struct, or literal of the program at `position`, if there is one.
+    fn occurrence_at(&self, position: Position) -> Option<Occurrence> {
+        let position = self
+            .source_file
+            .as_ref()?
+            .line_col_to_byte_pos(position.line as usize + 1, position.character as usize + 1)?;
+        let mut locator = Locator {
+            position,
+            body: Span::dummy(),
+            found: None,
+        };
+        locator.visit_program(self.program);
+        locator.found
+    }
+
+    /// Returns the function named `name`.
+    fn lookup_function(&self, name: Symbol) -> Option<&'a Function> {
+        self.program
+            .program_scopes
+            .values()
+            .flat_map(|scope| scope.functions.values())
+            .find(|function| function.identifier.name == name)
+    }
+
+    /// Returns the variable that `identifier` refers to within the function or finalize block spanning `body`.
+    /// Since Leo does not allow shadowing, this is the last variable of that name defined before `identifier`,
+    /// or otherwise a constant or mapping of the program.
+    fn lookup_variable(&self, identifier: Identifier, body: Span) -> Option<VariableSymbol> {
+        let symbol_table = self.symbol_table()?;
+        symbol_table
+            .variables_in_sub_scopes()
+            .into_iter()
+            .filter(|(name, variable)| {
+                *name == identifier.name
+                    && body.lo <= variable.span.lo
+                    && variable.span.hi <= body.hi
+                    && variable.span.lo <= identifier.span.lo
+            })
+            .max_by_key(|(_, variable)| variable.span.lo)
+            .map(|(_, variable)| variable)
+            .or_else(|| symbol_table.lookup_variable(identifier.name).cloned())
+    }
+}
+
+/// A name or literal in a program.
+enum Occurrence {
+    /// A variable, along with the span of the function or finalize block that it occurs in.
+    Variable(Identifier, Span),
+    Function(Identifier),
+    Struct(Identifier),
+    Literal(Literal),
+}
+
+/// Finds the name or literal at a position in a program.
+struct Locator {
+    position: BytePos,
+    /// The span of the function or finalize block being visited, or a dummy span outside of them.
+    body: Span,
+    found: Option<Occurrence>,
+}
+
+impl Locator {
+    fn contains(&self, span: Span) -> bool {
+        span.lo <= self.position && self.position <= span.hi
+    }
+
+    fn visit_variable(&mut self, identifier: Identifier) {
+        if self.contains(identifier.span) {
+            self.found = Some(Occurrence::Variable(identifier, self.body));
+        }
+    }
+
+    fn visit_type(&mut self, type_: &Type) {
+        match type_ {
+            Type::Identifier(identifier) if self.contains(identifier.span) => {
+                self.found = Some(Occurrence::Struct(*identifier));
+            }
+            Type::StructInstance(instance) if self.contains(instance.identifier.span) => {
+                self.found = Some(Occurrence::Struct(instance.identifier));
+            }
+            Type::Array(element, _) => self.visit_type(element),
+            Type::Mapping(mapping) => {
+                self.visit_type(&mapping.key);
+                self.visit_type(&mapping.value);
+            }
+            Type::Tuple(tuple) => tuple.0.iter().for_each(|type_| self.visit_type(type_)),
+            _ => {}
+        }
+    }
+
+    fn visit_inputs(&mut self, inputs: &[Input]) {
+        for input in inputs {
+            self.visit_variable(input.identifier());
+            self.visit_type(&input.type_());
+        }
+    }
+}
+
+impl<'a> ExpressionVisitor<'a> for Locator {
+    type AdditionalInput = ();
+    type Output = ();
+
+    fn visit_call(&mut self, input: &'a CallExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        if let Expression::Identifier(identifier) = &*input.function {
+            if self.contains(identifier.span) {
+                self.found = Some(Occurrence::Function(*identifier));
+            }
+        }
+        input.arguments.iter().for_each(|argument| {
+            self.visit_expression(argument, additional);
+        });
+    }
+
+    fn visit_struct_init(&mut self, input: &'a StructExpression, additional: &Self::AdditionalInput) -> Self::Output {
+        if self.contains(input.name.span) {
+            self.found = Some(Occurrence::Struct(input.name));
+        }
+        for member in &input.members {
+            match &member.expression {
+                Some(expression) => self.visit_expression(expression, additional),
+                // A member without an expression is initialized with the variable of the same name.
+                None => self.visit_variable(member.identifier),
+            }
+        }
+    }
+
+    fn visit_identifier(&mut self, input: &'a Identifier, _additional: &Self::AdditionalInput) -> Self::Output {
+        self.visit_variable(*input);
+    }
+
+    fn visit_literal(&mut self, input: &'a Literal, _additional: &Self::AdditionalInput) -> Self::Output {
+        if self.contains(input.span()) {
+            self.found = Some(Occurrence::Literal(input.clone()));
+        }
+    }
+}
+
+impl<'a> StatementVisitor<'a> for Locator {
+    fn visit_assign(&mut self, input: &'a AssignStatement) {
+        self.visit_expression(&input.place, &());
+        self.visit_expression(&input.value, &());
+    }
+
+    fn visit_definition(&mut self, input: &'a DefinitionStatement) {
+        self.visit_expression(&input.place, &());
+        if let Some(type_) = input.type_() {
+            self.visit_type(&type_);
+        }
+        self.visit_expression(&input.value, &());
+    }
+
+    fn visit_iteration(&mut self, input: &'a IterationStatement) {
+        self.visit_variable(input.variable);
+        self.visit_type(&input.type_);
+        self.visit_expression(&input.start, &());
+        if let Some(stop) = input.stop.as_ref() {
+            self.visit_expression(stop, &());
+        }
+        self.visit_block(&input.block);
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for Locator {
+    fn visit_const(&mut self, input: &'a ConstDeclaration) {
+        self.visit_variable(input.identifier);
+        self.visit_type(&input.type_);
+        self.visit_expression(&input.value, &());
+    }
+
+    fn visit_struct(&mut self, input: &'a Struct) {
+        if self.contains(input.identifier.span) {
+            self.found = Some(Occurrence::Struct(input.identifier));
+        }
+        input.members.iter().for_each(|member| self.visit_type(&member.type_));
+    }
+
+    fn visit_mapping(&mut self, input: &'a Mapping) {
+        self.visit_variable(input.identifier);
+        self.visit_type(&input.key_type);
+        self.visit_type(&input.value_type);
+    }
+
+    fn visit_function(&mut self, input: &'a Function) {
+        if self.contains(input.identifier.span) {
+            self.found = Some(Occurrence::Function(input.identifier));
+        }
+
+        self.body = input.span;
+        self.visit_inputs(&input.input);
+        self.visit_type(&input.output_type);
+        self.visit_block(&input.block);
+
+        if let Some(finalize) = &input.finalize {
+            self.body = finalize.span;
+            self.visit_inputs(&finalize.input);
+            self.visit_type(&finalize.output_type);
+            self.visit_block(&finalize.block);
+        }
+        self.body = Span::dummy();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: &str = "program test.aleo {
+    struct Point {
+        x: u8,
+        y: u8,
+    }
+
+    transition main(a: u8) -> Point {
+        let b: u8 = a + 1;
+        return Point { x: b, y: a };
+    }
+}
+";
+
+    fn path() -> PathBuf {
+        PathBuf::from("/package/src/main.leo")
+    }
+
+    #[test]
+    fn test_definition_of_variable() {
+        let range = definition(&path(), PROGRAM, Position::new(8, 26)).unwrap();
+        assert_eq!(range.start, Position::new(7, 8));
+    }
+
+    #[test]
+    fn test_hover() {
+        let hover = |line, character| hover(&path(), PROGRAM, Position::new(line, character));
+        assert_eq!(hover(7, 20).as_deref(), Some("a: u8"));
+        assert_eq!(hover(7, 24).as_deref(), Some("u8"));
+        assert_eq!(hover(6, 15).as_deref(), Some("transition main(a: u8) -> Point"));
+        assert!(hover(6, 30).unwrap().starts_with("struct Point"));
+        assert_eq!(hover(0, 0), None);
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        assert!(diagnostics(&path(), PROGRAM).is_empty());
+
+        let diagnostics = diagnostics(&path(), &PROGRAM.replace("a + 1", "a + true"));
+        assert!(!diagnostics.is_empty());
+        assert!(diagnostics.iter().all(|diagnostic| diagnostic.range.start.line == 7));
+        assert!(matches!(&diagnostics[0].code, Some(NumberOrString::String(code)) if code.starts_with("ETYC")));
+    }
+}
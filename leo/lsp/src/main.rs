@@ -0,0 +1,27 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_lsp::ServerResult;
+
+use lsp_server::Connection;
+
+fn main() -> ServerResult<()> {
+    // The server speaks the protocol over the standard input and output, so it only logs to the standard error.
+    let (connection, io_threads) = Connection::stdio();
+    leo_lsp::run(connection)?;
+    io_threads.join()?;
+    Ok(())
+}
@@ -0,0 +1,203 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::analysis;
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument, Notification as _,
+        PublishDiagnostics,
+    },
+    request::{GotoDefinition, HoverRequest, Request as _},
+    Diagnostic, DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DidSaveTextDocumentParams, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents, HoverParams,
+    HoverProviderCapability, Location, MarkupContent, MarkupKind, OneOf, PublishDiagnosticsParams, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions, TextDocumentSyncSaveOptions, Url,
+};
+use serde::de::DeserializeOwned;
+use std::{
+    collections::HashMap,
+    error::Error,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+};
+
+/// The result of running the server, whose errors are those of the connection to the client.
+pub type ServerResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+/// Serves a client over `connection` until it shuts the server down.
+///
+/// Diagnostics are published when a document is opened or saved, rather than on every change,
+/// since each check parses and type checks the whole program.
+pub fn run(connection: Connection) -> ServerResult<()> {
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Options(TextDocumentSyncOptions {
+            open_close: Some(true),
+            change: Some(TextDocumentSyncKind::FULL),
+            save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+            ..Default::default()
+        })),
+        definition_provider: Some(OneOf::Left(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        ..Default::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+
+    let mut server = Server {
+        connection: &connection,
+        documents: HashMap::new(),
+    };
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+                server.handle_request(request)?;
+            }
+            Message::Notification(notification) => server.handle_notification(notification)?,
+            Message::Response(_) => {}
+        }
+    }
+    Ok(())
+}
+
+struct Server<'a> {
+    connection: &'a Connection,
+    /// The content of each open document, as last sent by the client.
+    documents: HashMap<Url, String>,
+}
+
+impl<'a> Server<'a> {
+    fn handle_request(&mut self, request: Request) -> ServerResult<()> {
+        let id = request.id.clone();
+        let response = match request.method.as_str() {
+            GotoDefinition::METHOD => self.params(request, |server, id, params: GotoDefinitionParams| {
+                let position = params.text_document_position_params;
+                let uri = position.text_document.uri;
+                let range = server.analyze(&uri, |path, source| {
+                    analysis::definition(path, source, position.position)
+                })?;
+                let location = range.map(|range| GotoDefinitionResponse::Scalar(Location::new(uri, range)));
+                Some(Response::new_ok(id, location))
+            }),
+            HoverRequest::METHOD => self.params(request, |server, id, params: HoverParams| {
+                let position = params.text_document_position_params;
+                let code = server.analyze(&position.text_document.uri, |path, source| {
+                    analysis::hover(path, source, position.position)
+                })?;
+                let hover = code.map(|code| Hover {
+                    contents: HoverContents::Markup(MarkupContent {
+                        kind: MarkupKind::Markdown,
+                        value: format!("```leo\n{}\n```", code),
+                    }),
+                    range: None,
+                });
+                Some(Response::new_ok(id, hover))
+            }),
+            method => Some(Response::new_err(
+                id.clone(),
+                ErrorCode::MethodNotFound as i32,
+                format!("Unsupported request `{}`.", method),
+            )),
+        };
+        let response = response.unwrap_or_else(|| {
+            Response::new_err(
+                id,
+                ErrorCode::InternalError as i32,
+                "The program could not be checked.".to_string(),
+            )
+        });
+        self.connection.sender.send(Message::Response(response))?;
+        Ok(())
+    }
+
+    /// Answers a request with `handle`, or with an error if its parameters are invalid.
+    fn params<P: DeserializeOwned>(
+        &self,
+        request: Request,
+        handle: impl FnOnce(&Self, RequestId, P) -> Option<Response>,
+    ) -> Option<Response> {
+        match serde_json::from_value(request.params) {
+            Ok(params) => handle(self, request.id, params),
+            Err(error) => Some(Response::new_err(
+                request.id,
+                ErrorCode::InvalidParams as i32,
+                error.to_string(),
+            )),
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> ServerResult<()> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+                self.documents.insert(uri.clone(), params.text_document.text);
+                self.publish_diagnostics(uri)
+            }
+            DidChangeTextDocument::METHOD => {
+                // The whole document is sent on each change.
+                let params: DidChangeTextDocumentParams = serde_json::from_value(notification.params)?;
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.documents.insert(params.text_document.uri, change.text);
+                }
+                Ok(())
+            }
+            DidSaveTextDocument::METHOD => {
+                let params: DidSaveTextDocumentParams = serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+                if let Some(text) = params.text {
+                    self.documents.insert(uri.clone(), text);
+                }
+                self.publish_diagnostics(uri)
+            }
+            DidCloseTextDocument::METHOD => {
+                // Clear the diagnostics of the document, since they are no longer updated.
+                let params: DidCloseTextDocumentParams = serde_json::from_value(notification.params)?;
+                self.documents.remove(&params.text_document.uri);
+                self.send_diagnostics(params.text_document.uri, Vec::new())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn publish_diagnostics(&self, uri: Url) -> ServerResult<()> {
+        let diagnostics = self.analyze(&uri, analysis::diagnostics).unwrap_or_default();
+        self.send_diagnostics(uri, diagnostics)
+    }
+
+    fn send_diagnostics(&self, uri: Url, diagnostics: Vec<Diagnostic>) -> ServerResult<()> {
+        let params = PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version: None,
+        };
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        self.connection.sender.send(Message::Notification(notification))?;
+        Ok(())
+    }
+
+    /// Analyzes the open document at `uri` with `analyze`.
+    /// Returns `None` if the document is not open, or if the compiler panics while checking it,
+    /// so that the server keeps running.
+    fn analyze<T>(&self, uri: &Url, analyze: impl FnOnce(&std::path::Path, &str) -> T) -> Option<T> {
+        let source = self.documents.get(uri)?;
+        let path = uri.to_file_path().unwrap_or_else(|()| PathBuf::from(uri.path()));
+        panic::catch_unwind(AssertUnwindSafe(|| analyze(&path, source))).ok()
+    }
+}
@@ -77,11 +77,21 @@ enum Commands {
         #[structopt(flatten)]
         command: Clean,
     },
+    #[structopt(about = "Format the Leo source files of the current package in place")]
+    Fmt {
+        #[structopt(flatten)]
+        command: Fmt,
+    },
     #[structopt(about = "Run a program with input variables")]
     Run {
         #[structopt(flatten)]
         command: Run,
     },
+    #[structopt(about = "Run the `@test` functions of a program")]
+    Test {
+        #[structopt(flatten)]
+        command: Test,
+    },
     #[structopt(subcommand)]
     Node(Node),
 
@@ -152,7 +162,9 @@ pub fn run_with_args(cli: CLI) -> Result<()> {
         Commands::New { command } => command.try_execute(context),
         Commands::Build { command } => command.try_execute(context),
         Commands::Clean { command } => command.try_execute(context),
+        Commands::Fmt { command } => command.try_execute(context),
         Commands::Run { command } => command.try_execute(context),
+        Commands::Test { command } => command.try_execute(context),
         Commands::Node(command) => command.try_execute(context),
         Commands::Deploy { command } => command.try_execute(context),
     }
@@ -16,15 +16,18 @@
 
 //! This file contains tools for benchmarking the Leo compiler and its stages.
 
-use leo_compiler::Compiler;
+use leo_compiler::{Compiler, CompilerPass, PassManager, PassState};
 use leo_errors::emitter::{Emitter, Handler};
 use leo_span::{source_map::FileName, symbol::SESSION_GLOBALS};
-use leo_test_framework::get_benches;
+use leo_test_framework::{get_benches, get_diagnostic_benches};
 
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, Criterion};
+use serde::Deserialize;
 use std::{
-    path::PathBuf,
-    time::{Duration, Instant},
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
 };
 
 /// An enum to represent the stage of the Compiler we are benchmarking.
@@ -43,6 +46,8 @@ enum BenchMode {
     Flatten,
     /// Benchmarks all the above stages.
     Full,
+    /// Benchmarks parsing, symbol table generation, and type checking of invalid programs, including rendering their errors.
+    Diagnostics,
 }
 
 /// A dummy buffer emitter since we only test on valid programs.
@@ -72,6 +77,15 @@ struct Sample {
 }
 
 /// A helper function to help create a Leo Compiler struct.
+/// Runs the frontend passes followed by `passes` on the parsed program of `compiler`.
+fn run_passes(compiler: &mut Compiler, passes: &[CompilerPass]) -> PassState {
+    passes
+        .iter()
+        .fold(PassManager::frontend(), |manager, pass| manager.register(*pass))
+        .run(compiler, PassState::default())
+        .expect("failed to run the passes preceding the benchmarked pass")
+}
+
 fn new_compiler(handler: &Handler) -> Compiler<'_> {
     Compiler::new(
         String::from("bench"),
@@ -80,6 +94,7 @@ fn new_compiler(handler: &Handler) -> Compiler<'_> {
         PathBuf::from(String::new()),
         PathBuf::from(String::new()),
         None,
+        None,
     )
 }
 
@@ -94,6 +109,14 @@ impl Sample {
             .collect()
     }
 
+    /// Loads the benchmark samples that are expected to fail.
+    fn load_diagnostic_samples() -> Vec<Self> {
+        get_diagnostic_benches()
+            .into_iter()
+            .map(|(name, input)| Self { name, input })
+            .collect()
+    }
+
     fn data(&self) -> (&str, FileName) {
         black_box((&self.input, FileName::Custom(String::new())))
     }
@@ -107,6 +130,7 @@ impl Sample {
             BenchMode::Ssa => self.bench_ssa(c),
             BenchMode::Flatten => self.bench_flattener(c),
             BenchMode::Full => self.bench_full(c),
+            BenchMode::Diagnostics => self.bench_diagnostics(c),
         }
     }
 
@@ -147,7 +171,7 @@ impl Sample {
     }
 
     fn bench_symbol_table(&self, c: &mut Criterion) {
-        self.bencher_after_parse(c, "symbol table pass", |compiler| {
+        self.bencher_after_parse(c, "symbol table pass", |mut compiler| {
             let start = Instant::now();
             let out = compiler.symbol_table_pass();
             let time = start.elapsed();
@@ -157,7 +181,7 @@ impl Sample {
     }
 
     fn bench_type_checker(&self, c: &mut Criterion) {
-        self.bencher_after_parse(c, "type checker pass", |compiler| {
+        self.bencher_after_parse(c, "type checker pass", |mut compiler| {
             let symbol_table = compiler.symbol_table_pass().expect("failed to generate symbol table");
             let start = Instant::now();
             let out = compiler.type_checker_pass(symbol_table);
@@ -169,12 +193,14 @@ impl Sample {
 
     fn bench_loop_unroller(&self, c: &mut Criterion) {
         self.bencher_after_parse(c, "loop unrolling pass", |mut compiler| {
-            let symbol_table = compiler.symbol_table_pass().expect("failed to generate symbol table");
-            let symbol_table = compiler
-                .type_checker_pass(symbol_table)
-                .expect("failed to run type check pass");
+            let state = run_passes(
+                &mut compiler,
+                &[CompilerPass::EnumLowering, CompilerPass::Monomorphization],
+            );
+            let symbol_table = state.symbol_table.expect("the frontend creates the symbol table");
+            let type_table = state.type_table.expect("the type checker creates the type table");
             let start = Instant::now();
-            let out = compiler.loop_unrolling_pass(symbol_table);
+            let out = compiler.loop_unrolling_pass(symbol_table, type_table);
             let time = start.elapsed();
             out.expect("failed to run loop unrolling pass");
             time
@@ -182,16 +208,19 @@ impl Sample {
     }
 
     fn bench_ssa(&self, c: &mut Criterion) {
-        self.bencher_after_parse(c, "full", |mut compiler| {
-            let symbol_table = compiler.symbol_table_pass().expect("failed to generate symbol table");
-            let symbol_table = compiler
-                .type_checker_pass(symbol_table)
-                .expect("failed to run type check pass");
-            compiler
-                .loop_unrolling_pass(symbol_table)
-                .expect("failed to run loop unrolling pass");
+        self.bencher_after_parse(c, "ssa pass", |mut compiler| {
+            let state = run_passes(
+                &mut compiler,
+                &[
+                    CompilerPass::EnumLowering,
+                    CompilerPass::Monomorphization,
+                    CompilerPass::LoopUnrolling,
+                    CompilerPass::TupleLowering,
+                ],
+            );
+            let mut type_table = state.type_table.expect("the type checker creates the type table");
             let start = Instant::now();
-            let out = compiler.static_single_assignment_pass();
+            let out = compiler.static_single_assignment_pass(&mut type_table);
             let time = start.elapsed();
             out.expect("failed to run ssa pass");
             time
@@ -200,18 +229,21 @@ impl Sample {
 
     fn bench_flattener(&self, c: &mut Criterion) {
         self.bencher_after_parse(c, "flattener pass", |mut compiler| {
-            let symbol_table = compiler.symbol_table_pass().expect("failed to generate symbol table");
-            let symbol_table = compiler
-                .type_checker_pass(symbol_table)
-                .expect("failed to run type check pass");
-            let symbol_table = compiler
-                .loop_unrolling_pass(symbol_table)
-                .expect("failed to run loop unrolling pass");
-            let assigner = compiler
-                .static_single_assignment_pass()
-                .expect("failed to run ssa pass");
+            let state = run_passes(
+                &mut compiler,
+                &[
+                    CompilerPass::EnumLowering,
+                    CompilerPass::Monomorphization,
+                    CompilerPass::LoopUnrolling,
+                    CompilerPass::TupleLowering,
+                    CompilerPass::StaticSingleAssignment,
+                ],
+            );
+            let symbol_table = state.symbol_table.expect("the frontend creates the symbol table");
+            let mut type_table = state.type_table.expect("the type checker creates the type table");
+            let assigner = state.assigner.expect("the ssa pass creates the assigner");
             let start = Instant::now();
-            let out = compiler.flattening_pass(&symbol_table, assigner);
+            let out = compiler.flattening_pass(&symbol_table, &mut type_table, assigner);
             let time = start.elapsed();
             out.expect("failed to run flattener pass");
             time
@@ -225,22 +257,46 @@ impl Sample {
             compiler
                 .parse_program_from_string(input, name)
                 .expect("Failed to parse program");
-            let symbol_table = compiler.symbol_table_pass().expect("failed to generate symbol table");
-            let symbol_table = compiler
-                .type_checker_pass(symbol_table)
-                .expect("failed to run type check pass");
-            let symbol_table = compiler
-                .loop_unrolling_pass(symbol_table)
-                .expect("failed to run loop unrolling pass");
-            let assigner = compiler
-                .static_single_assignment_pass()
-                .expect("failed to run ssa pass");
-            compiler
-                .flattening_pass(&symbol_table, assigner)
-                .expect("failed to run flattening pass");
+            compiler.compiler_stages().expect("failed to run the compiler stages");
             start.elapsed()
         })
     }
+
+    /// Benchmarks checking a program that contains errors, as an editor does while the program is being written.
+    /// Errors are collected by a buffered emitter and rendered, since reporting them dominates the time taken.
+    fn bench_diagnostics(&self, c: &mut Criterion) {
+        c.bench_function(&format!("diagnostics {}", self.name), |b| {
+            b.iter_custom(|iters| {
+                (0..iters)
+                    .map(|_| {
+                        SESSION_GLOBALS.set(&<_>::default(), || {
+                            let (handler, buf) = Handler::new_with_buf();
+                            let mut compiler = new_compiler(&handler);
+                            let (input, name) = self.data();
+                            let start = Instant::now();
+                            let out = compiler.parse_program_from_string(input, name).and_then(|()| {
+                                let symbol_table = compiler.symbol_table_pass()?;
+                                compiler.type_checker_pass(symbol_table)
+                            });
+                            let failed = out.is_err();
+                            let errors = buf.extract_errs().into_inner();
+                            let warnings = buf.extract_warnings().into_inner();
+                            let rendered: Vec<String> = errors
+                                .iter()
+                                .map(|err| err.to_string())
+                                .chain(out.err().map(|err| err.to_string()))
+                                .chain(warnings.iter().map(|warning| warning.to_string()))
+                                .collect();
+                            let time = start.elapsed();
+                            assert!(failed, "expected the sample to contain errors");
+                            black_box(rendered);
+                            time
+                        })
+                    })
+                    .sum()
+            });
+        });
+    }
 }
 
 macro_rules! bench {
@@ -259,6 +315,12 @@ bench!(bench_ssa, BenchMode::Ssa);
 bench!(bench_flatten, BenchMode::Flatten);
 bench!(bench_full, BenchMode::Full);
 
+fn bench_diagnostics(c: &mut Criterion) {
+    Sample::load_diagnostic_samples()
+        .into_iter()
+        .for_each(|s| s.bench(c, BenchMode::Diagnostics))
+}
+
 criterion_group!(
     name = benches;
     config = Criterion::default().sample_size(200).measurement_time(Duration::from_secs(10)).nresamples(200_000);
@@ -269,6 +331,166 @@ criterion_group!(
         bench_unroll,
         bench_ssa,
         bench_flatten,
-        bench_full
+        bench_full,
+        bench_diagnostics
 );
-criterion_main!(benches);
+
+/// The names each benchmark mode uses as the prefix of its benchmark ids.
+/// Note that longer names must precede names they are a prefix of.
+const PASS_NAMES: &[&str] = &[
+    "parse",
+    "symbol table pass",
+    "type checker pass",
+    "loop unrolling pass",
+    "ssa pass",
+    "flattener pass",
+    "full",
+    "diagnostics",
+];
+
+/// The default percentage a pass may slow down by before it is reported as a regression.
+const DEFAULT_REGRESSION_THRESHOLD: f64 = 5.0;
+
+/// The subset of criterion's `benchmark.json` needed to identify a benchmark.
+#[derive(Deserialize)]
+struct BenchmarkRecord {
+    full_id: String,
+}
+
+/// A single statistic from criterion's `estimates.json`.
+#[derive(Deserialize)]
+struct Estimate {
+    point_estimate: f64,
+}
+
+/// The subset of criterion's `estimates.json` used for baseline comparisons.
+#[derive(Deserialize)]
+struct Estimates {
+    mean: Estimate,
+}
+
+/// Returns the directory criterion writes its estimates to.
+/// Mirrors criterion's own lookup, falling back to the workspace target directory.
+fn criterion_directory() -> PathBuf {
+    if let Some(home) = std::env::var_os("CRITERION_HOME") {
+        PathBuf::from(home)
+    } else if let Some(target) = std::env::var_os("CARGO_TARGET_DIR") {
+        PathBuf::from(target).join("criterion")
+    } else {
+        [env!("CARGO_MANIFEST_DIR"), "../../target/criterion"].iter().collect()
+    }
+}
+
+/// Recursively collects the mean estimate, in nanoseconds, of every benchmark under `dir` that was run since `since`.
+/// Criterion keeps the results of benchmarks that were not run, e.g. because of a different `TEST_FILTER`, so they are skipped.
+fn collect_estimates(dir: &Path, since: SystemTime, estimates: &mut BTreeMap<String, f64>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        // Criterion stores the results of the latest run of a benchmark in a directory named `new`.
+        if path.ends_with("new") {
+            let modified = fs::metadata(path.join("estimates.json")).and_then(|metadata| metadata.modified());
+            if !matches!(modified, Ok(modified) if modified >= since) {
+                continue;
+            }
+            let read = |file: &str| fs::read_to_string(path.join(file)).ok();
+            if let (Some(benchmark), Some(estimate)) = (read("benchmark.json"), read("estimates.json")) {
+                let benchmark: BenchmarkRecord = serde_json::from_str(&benchmark).expect("invalid benchmark.json");
+                let estimate: Estimates = serde_json::from_str(&estimate).expect("invalid estimates.json");
+                estimates.insert(benchmark.full_id, estimate.mean.point_estimate);
+            }
+        } else {
+            collect_estimates(&path, since, estimates);
+        }
+    }
+}
+
+/// Sums the estimates of all benchmarks belonging to each pass.
+fn estimates_per_pass(estimates: &BTreeMap<String, f64>) -> BTreeMap<&'static str, f64> {
+    let mut passes = BTreeMap::new();
+    for (id, estimate) in estimates {
+        if let Some(pass) = PASS_NAMES.iter().find(|name| id.starts_with(&format!("{} ", name))) {
+            *passes.entry(*pass).or_insert(0.0) += estimate;
+        }
+    }
+    passes
+}
+
+/// Compares the estimates of the benchmarks run since `since` against a stored baseline.
+///
+/// The baseline is a JSON object mapping benchmark ids to mean estimates in nanoseconds, read from the file
+/// given by `LEO_BENCH_BASELINE`. Only benchmarks present in both runs are compared, and they are summed
+/// per pass. A pass regresses if it is slower than the baseline by more than `LEO_BENCH_THRESHOLD` percent.
+/// If `LEO_BENCH_SAVE_BASELINE` is set, the latest estimates are written to the given file instead.
+///
+/// Returns the number of regressed passes.
+fn compare_with_baseline(since: SystemTime) -> usize {
+    let mut current = BTreeMap::new();
+    collect_estimates(&criterion_directory(), since, &mut current);
+
+    if let Ok(path) = std::env::var("LEO_BENCH_SAVE_BASELINE") {
+        let json = serde_json::to_string_pretty(&current).expect("failed to serialize baseline");
+        fs::write(&path, json).expect("failed to write baseline file");
+        println!("saved {} benchmark estimates to {}", current.len(), path);
+        return 0;
+    }
+
+    let path = match std::env::var("LEO_BENCH_BASELINE") {
+        Ok(path) => path,
+        Err(_) => return 0,
+    };
+    let threshold = std::env::var("LEO_BENCH_THRESHOLD")
+        .map(|t| t.parse::<f64>().expect("LEO_BENCH_THRESHOLD must be a percentage"))
+        .unwrap_or(DEFAULT_REGRESSION_THRESHOLD);
+    let baseline: BTreeMap<String, f64> =
+        serde_json::from_str(&fs::read_to_string(&path).expect("failed to read baseline file"))
+            .expect("invalid baseline file");
+
+    // Only compare the benchmarks that were run in both the baseline and the latest run.
+    current.retain(|id, _| baseline.contains_key(id));
+    let baseline = baseline
+        .into_iter()
+        .filter(|(id, _)| current.contains_key(id))
+        .collect();
+
+    let current = estimates_per_pass(&current);
+    let baseline = estimates_per_pass(&baseline);
+
+    let mut regressions = 0;
+    for (pass, new) in current {
+        // A pass without a positive baseline estimate has no change to measure against.
+        let old = match baseline.get(pass) {
+            Some(old) if *old > 0.0 => *old,
+            _ => {
+                println!("{}: no baseline to compare with ({:.0}ns)", pass, new);
+                continue;
+            }
+        };
+        let change = (new - old) / old * 100.0;
+        if change > threshold {
+            regressions += 1;
+            println!("{}: regressed by {:.2}% ({:.0}ns -> {:.0}ns)", pass, change, old, new);
+        } else {
+            println!("{}: changed by {:.2}%", pass, change);
+        }
+    }
+    regressions
+}
+
+fn main() {
+    let started = SystemTime::now();
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+
+    let regressions = compare_with_baseline(started);
+    if regressions > 0 {
+        println!("{} pass(es) regressed beyond the threshold", regressions);
+        std::process::exit(1);
+    }
+}
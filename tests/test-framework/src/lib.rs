@@ -34,6 +34,8 @@ pub mod output;
 
 pub mod runner;
 
+pub mod snapshot;
+
 pub mod test;
 
 pub use runner::*;
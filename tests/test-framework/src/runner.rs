@@ -24,7 +24,7 @@ use std::{
     thread,
 };
 
-use crate::{error::*, fetch::find_tests, output::TestExpectation, test::*};
+use crate::{error::*, fetch::find_tests, output::TestExpectation, snapshot::SnapshotWriter, test::*};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum ParseType {
@@ -156,7 +156,7 @@ impl TestCases {
         output
     }
 
-    fn load_expectations(&self, path: &Path) -> (PathBuf, Option<TestExpectation>) {
+    fn load_expectations(&self, path: &Path, bless: bool) -> (PathBuf, Option<TestExpectation>) {
         let test_dir = [env!("CARGO_MANIFEST_DIR"), "../../tests/"].iter().collect::<PathBuf>();
         let relative_path = path.strip_prefix(&test_dir).expect("path error for test");
         let expectation_path = test_dir
@@ -166,7 +166,7 @@ impl TestCases {
             .with_extension("out");
 
         if expectation_path.exists() {
-            if !is_env_var_set("CLEAR_LEO_TEST_EXPECTATIONS") {
+            if bless {
                 (expectation_path, None)
             } else {
                 let raw = std::fs::read_to_string(&expectation_path).expect("failed to read expectations file");
@@ -181,21 +181,50 @@ impl TestCases {
     }
 }
 
+/// Options controlling how a test run treats existing expectations.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Ignores existing expectations and regenerates the expectations of every test that is run.
+    pub bless: bool,
+}
+
+impl RunOptions {
+    /// Reads the options from the environment.
+    /// Setting `CLEAR_LEO_TEST_EXPECTATIONS` enables `bless`.
+    pub fn from_env() -> Self {
+        Self {
+            bless: !is_env_var_set("CLEAR_LEO_TEST_EXPECTATIONS"),
+        }
+    }
+}
+
+/// Runs the tests in `expectation_category`, with options read from the environment.
 pub fn run_tests<T: Runner>(runner: &T, expectation_category: &str) {
+    run_tests_with_options(runner, expectation_category, &RunOptions::from_env())
+}
+
+/// Runs the tests in `expectation_category` and regenerates all of their expectations.
+pub fn bless_tests<T: Runner>(runner: &T, expectation_category: &str) {
+    run_tests_with_options(runner, expectation_category, &RunOptions { bless: true })
+}
+
+/// Runs the tests in `expectation_category`.
+/// Expectations are only written if every test passes, in which case they are written in parallel.
+pub fn run_tests_with_options<T: Runner>(runner: &T, expectation_category: &str, options: &RunOptions) {
     let (mut cases, configs) = TestCases::new(expectation_category, |_| true);
 
     let mut pass_categories = 0;
     let mut pass_tests = 0;
     let mut fail_tests = 0;
 
-    let mut outputs = vec![];
+    let mut outputs = SnapshotWriter::new();
     cases.process_tests(configs, |cases, (path, content, test_name, config)| {
         let namespace = match runner.resolve_namespace(&config.namespace) {
             Some(ns) => ns,
             None => return,
         };
 
-        let (expectation_path, expectations) = cases.load_expectations(path);
+        let (expectation_path, expectations) = cases.load_expectations(path, options.bless);
 
         let tests = match namespace.parse_type() {
             ParseType::Line => crate::fetch::split_tests_one_line(content)
@@ -245,14 +274,14 @@ pub fn run_tests<T: Runner>(runner: &T, expectation_category: &str) {
 
         if errors.is_empty() {
             if expectations.is_none() {
-                outputs.push((
+                outputs.push(
                     expectation_path,
                     TestExpectation {
                         namespace: config.namespace,
                         expectation: config.expectation,
                         outputs: new_outputs,
                     },
-                ));
+                );
             }
             pass_categories += 1;
         } else {
@@ -282,14 +311,7 @@ pub fn run_tests<T: Runner>(runner: &T, expectation_category: &str) {
             cases.fail_categories.len() + pass_categories
         );
     } else {
-        for (path, new_expectation) in outputs {
-            std::fs::create_dir_all(path.parent().unwrap()).expect("failed to make test expectation parent directory");
-            std::fs::write(
-                &path,
-                serde_yaml::to_string(&new_expectation).expect("failed to serialize expectation yaml"),
-            )
-            .expect("failed to write expectation file");
-        }
+        outputs.write_all().expect("failed to write expectation files");
         println!(
             "passed {}/{} tests in {}/{} categories",
             pass_tests,
@@ -317,3 +339,14 @@ pub fn get_benches() -> Vec<(String, String)> {
         (test_name.to_string(), content.to_string())
     })
 }
+
+/// returns (name, content) for all benchmark samples that are expected to fail, which measure the cost of reporting errors
+pub fn get_diagnostic_benches() -> Vec<(String, String)> {
+    let (mut cases, configs) = TestCases::new("compiler", |config| {
+        &config.namespace == "Bench" && config.expectation == TestExpectationMode::Fail
+    });
+
+    cases.process_tests(configs, |_, (_, content, test_name, _)| {
+        (test_name.to_string(), content.to_string())
+    })
+}
@@ -0,0 +1,107 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::output::TestExpectation;
+
+use std::{
+    fs, io,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    thread,
+};
+
+/// Collects test expectations and writes them to disk as a batch.
+///
+/// The batch is split across worker threads, and each expectation is written to a temporary file
+/// next to its destination before being renamed into place, so an interrupted run never leaves
+/// a partially written expectation file behind.
+#[derive(Default)]
+pub struct SnapshotWriter {
+    pending: Vec<(PathBuf, TestExpectation)>,
+}
+
+impl SnapshotWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues an expectation to be written to `path`.
+    pub fn push(&mut self, path: PathBuf, expectation: TestExpectation) {
+        self.pending.push((path, expectation));
+    }
+
+    /// Returns the number of queued expectations.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns `true` if no expectations are queued.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Writes all queued expectations, using up to one thread per available core.
+    /// Returns the first error encountered, after every thread has finished.
+    pub fn write_all(self) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let threads = thread::available_parallelism()
+            .map(NonZeroUsize::get)
+            .unwrap_or(1)
+            .min(self.pending.len());
+
+        thread::scope(|scope| {
+            // Each thread writes every `threads`-th expectation, starting from its own index.
+            let pending = &self.pending;
+            let handles = (0..threads)
+                .map(|start| {
+                    scope.spawn(move || {
+                        pending
+                            .iter()
+                            .skip(start)
+                            .step_by(threads)
+                            .try_for_each(|(path, expectation)| write_atomic(path, expectation))
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            // Join every thread before reporting an error, so no write is left in flight.
+            let results = handles
+                .into_iter()
+                .map(|handle| handle.join().expect("snapshot writer thread panicked"))
+                .collect::<Vec<_>>();
+            results.into_iter().collect()
+        })
+    }
+}
+
+/// Writes a single expectation to a temporary file and atomically renames it to `path`.
+fn write_atomic(path: &Path, expectation: &TestExpectation) -> io::Result<()> {
+    let parent = path.parent().expect("expectation path has no parent directory");
+    fs::create_dir_all(parent)?;
+
+    let serialized =
+        serde_yaml::to_string(expectation).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+    let file_name = path
+        .file_name()
+        .expect("expectation path has no file name")
+        .to_string_lossy();
+    let temp_path = parent.join(format!(".{}.tmp", file_name));
+    fs::write(&temp_path, serialized)?;
+    fs::rename(&temp_path, path)
+}